@@ -0,0 +1,318 @@
+//! Procedural macros for [simple-perm-manager](https://github.com/carlos-el/simple-perm-manager).
+//!
+//! This crate is re-exported by `simple-perm-manager`, use the macros from there.
+
+mod validation;
+use proc_macro::{Delimiter, Group, Spacing, TokenStream, TokenTree};
+use std::collections::{BTreeMap, BTreeSet};
+use validation::{
+    unquote, validate_action, validate_key, variant_name, ACTION_DIVIDER, MAX_DEPTH_ALLOWED,
+};
+
+/// Creates a `Permission` from actions written inline, checking action syntax at compile time.
+///
+/// See the documentation of `simple_perm_manager::perm` for usage.
+#[proc_macro]
+pub fn perm(input: TokenStream) -> TokenStream {
+    match expand_perm(input) {
+        Ok(output) => output,
+        Err(message) => format!("compile_error!({:?})", message)
+            .parse()
+            .expect("compile_error invocation should be valid tokens"),
+    }
+}
+
+/// Generates an enum with a variant per action of a universe, checking action syntax at compile time.
+///
+/// See the documentation of `simple_perm_manager::define_permissions` for usage.
+#[proc_macro]
+pub fn define_permissions(input: TokenStream) -> TokenStream {
+    match expand_define_permissions(input) {
+        Ok(output) => output,
+        Err(message) => format!("compile_error!({:?})", message)
+            .parse()
+            .expect("compile_error invocation should be valid tokens"),
+    }
+}
+
+fn expand_perm(input: TokenStream) -> Result<TokenStream, String> {
+    let tokens: Vec<TokenTree> = input.into_iter().collect();
+
+    // Optional manager expression before a top level ';'
+    let (manager, tokens) = match tokens.iter().position(|t| is_punct(t, ';')) {
+        Some(position) => (Some(&tokens[..position]), &tokens[position + 1..]),
+        None => (None, &tokens[..]),
+    };
+
+    let actions = parse_actions(tokens)?;
+
+    let actions_set = format!(
+        "::std::collections::HashSet::<::std::string::String>::from([{}])",
+        actions
+            .iter()
+            .map(|action| format!("::std::string::String::from({:?})", action))
+            .collect::<Vec<String>>()
+            .join(", ")
+    );
+
+    let mut output = TokenStream::new();
+    match manager {
+        Some([]) => {
+            return Err(String::from(
+                "expected a PermissionManager expression before ';'",
+            ))
+        }
+        Some(manager) => {
+            output.extend([TokenTree::Group(Group::new(
+                Delimiter::Parenthesis,
+                manager.iter().cloned().collect(),
+            ))]);
+            output.extend(parse_tokens(&format!(
+                ".perm_from_actions({})",
+                actions_set
+            )));
+        }
+        None => output.extend(parse_tokens(&format!(
+            "::simple_perm_manager::Permission::from_actions({})",
+            actions_set
+        ))),
+    }
+
+    Ok(output)
+}
+
+fn expand_define_permissions(input: TokenStream) -> Result<TokenStream, String> {
+    let tokens: Vec<TokenTree> = input.into_iter().collect();
+
+    // Attributes and visibility before 'enum' are copied to the generated enum
+    let enum_position = match tokens
+        .iter()
+        .position(|t| matches!(t, TokenTree::Ident(ident) if ident.to_string() == "enum"))
+    {
+        Some(position) => position,
+        None => return Err(String::from("expected 'enum' followed by the enum name")),
+    };
+    let name = match tokens.get(enum_position + 1) {
+        Some(TokenTree::Ident(name)) => name.clone(),
+        _ => return Err(String::from("expected the enum name after 'enum'")),
+    };
+    let body = match tokens.get(enum_position + 2) {
+        Some(TokenTree::Group(group))
+            if group.delimiter() == Delimiter::Brace && tokens.len() == enum_position + 3 =>
+        {
+            group.stream().into_iter().collect::<Vec<TokenTree>>()
+        }
+        _ => {
+            return Err(String::from(
+                "expected the universe actions between braces after the enum name",
+            ))
+        }
+    };
+
+    let actions = parse_actions(&body)?;
+    if actions.is_empty() {
+        return Err(String::from("the universe needs at least one action"));
+    }
+
+    let mut variants: BTreeMap<String, String> = BTreeMap::new();
+    for action in actions {
+        let variant = variant_name(&action)?;
+        if let Some(other) = variants.get(&variant) {
+            return Err(format!(
+                "actions '{}' and '{}' map to the same variant name '{}'",
+                other, action, variant
+            ));
+        }
+        variants.insert(variant, action);
+    }
+
+    let mut output =
+        parse_tokens("#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, PartialOrd, Ord)]");
+    output.extend(tokens[..enum_position].iter().cloned());
+    output.extend(parse_tokens("enum"));
+    output.extend([TokenTree::Ident(name.clone())]);
+    output.extend([TokenTree::Group(Group::new(
+        Delimiter::Brace,
+        parse_tokens(
+            &variants
+                .iter()
+                .map(|(variant, action)| format!("#[doc = {:?}] {},", action, variant))
+                .collect::<String>(),
+        ),
+    ))]);
+
+    // Sorted by action string, the canonical order of the PermissionManager
+    let mut sorted: Vec<(&String, &String)> = variants.iter().collect();
+    sorted.sort_by_key(|(_, action)| *action);
+    let all = sorted
+        .iter()
+        .map(|(variant, _)| format!("{}::{}", name, variant))
+        .collect::<Vec<String>>()
+        .join(", ");
+    let as_str_arms = variants
+        .iter()
+        .map(|(variant, action)| format!("{}::{} => {:?},", name, variant, action))
+        .collect::<String>();
+    let from_str_arms = variants
+        .iter()
+        .map(|(variant, action)| format!("{:?} => Some({}::{}),", action, name, variant))
+        .collect::<String>();
+
+    output.extend(parse_tokens(&format!(
+        r#"
+        impl {name} {{
+            /// Every action of the universe, in the canonical order used by the PermissionManager.
+            pub const ALL: &'static [{name}] = &[{all}];
+
+            /// Returns the action string of the variant.
+            pub fn as_str(&self) -> &'static str {{
+                match self {{ {as_str_arms} }}
+            }}
+
+            /// Returns the variant for an action string or `None` if it is not in the universe.
+            pub fn from_action(action: &str) -> Option<{name}> {{
+                match action {{ {from_str_arms} _ => None }}
+            }}
+
+            /// Creates a PermissionManager whose universe contains every action of the enum.
+            pub fn permission_manager() -> ::simple_perm_manager::PermissionManager {{
+                ::simple_perm_manager::PermissionManager::from_actions(
+                    Self::ALL.iter().map(|action| ::std::string::String::from(action.as_str())).collect()
+                )
+            }}
+        }}
+
+        impl ::simple_perm_manager::Action for {name} {{
+            fn as_str(&self) -> &str {{
+                {name}::as_str(self)
+            }}
+
+            fn from_str(action: &str) -> Option<{name}> {{
+                {name}::from_action(action)
+            }}
+        }}
+
+        impl ::std::convert::AsRef<str> for {name} {{
+            fn as_ref(&self) -> &str {{
+                self.as_str()
+            }}
+        }}
+
+        impl ::std::fmt::Display for {name} {{
+            fn fmt(&self, f: &mut ::std::fmt::Formatter<'_>) -> ::std::fmt::Result {{
+                f.write_str(self.as_str())
+            }}
+        }}
+        "#,
+        name = name,
+        all = all,
+        as_str_arms = as_str_arms,
+        from_str_arms = from_str_arms,
+    )));
+
+    Ok(output)
+}
+
+// Parses actions written as a list of strings or in object style, detected by
+// the first string being followed by ':'.
+fn parse_actions(tokens: &[TokenTree]) -> Result<BTreeSet<String>, String> {
+    let mut actions: BTreeSet<String> = BTreeSet::new();
+    if tokens.len() > 1 && is_punct(&tokens[1], ':') {
+        parse_object(tokens, "", 0, &mut actions)?;
+    } else {
+        parse_list(tokens, &mut actions)?;
+    }
+
+    Ok(actions)
+}
+
+// Parses a comma separated list of string literals with full actions.
+fn parse_list(tokens: &[TokenTree], actions: &mut BTreeSet<String>) -> Result<(), String> {
+    for (n, token) in tokens.iter().enumerate() {
+        if n % 2 == 1 {
+            if !is_punct(token, ',') {
+                return Err(format!("expected ',' between actions, found '{}'", token));
+            }
+            continue;
+        }
+
+        let action = string_literal(token)?;
+        validate_action(&action)?;
+        actions.insert(action);
+    }
+
+    Ok(())
+}
+
+// Parses the contents of an object style permission: `"key": value` pairs separated by commas
+// where values are `true`, `false` or a nested `{ ... }` object.
+fn parse_object(
+    tokens: &[TokenTree],
+    prefix: &str,
+    depth: usize,
+    actions: &mut BTreeSet<String>,
+) -> Result<(), String> {
+    if depth >= MAX_DEPTH_ALLOWED {
+        return Err(format!(
+            "too much nesting in permission, maximum depth is {}",
+            MAX_DEPTH_ALLOWED
+        ));
+    }
+
+    let mut tokens = tokens.iter().peekable();
+    while let Some(key_token) = tokens.next() {
+        let key = string_literal(key_token)?;
+        validate_key(&key)?;
+        let action = if prefix.is_empty() {
+            key
+        } else {
+            format!("{}{}{}", prefix, ACTION_DIVIDER, key)
+        };
+
+        match tokens.next() {
+            Some(token) if is_punct(token, ':') => (),
+            _ => return Err(format!("expected ':' after key '{}'", action)),
+        }
+
+        match tokens.next() {
+            Some(TokenTree::Ident(ident)) if ident.to_string() == "true" => {
+                actions.insert(action);
+            }
+            Some(TokenTree::Ident(ident)) if ident.to_string() == "false" => (),
+            Some(TokenTree::Group(group)) if group.delimiter() == Delimiter::Brace => {
+                let inner: Vec<TokenTree> = group.stream().into_iter().collect();
+                parse_object(&inner, &action, depth + 1, actions)?;
+            }
+            _ => {
+                return Err(format!(
+                    "expected true, false or an object as value for '{}'",
+                    action
+                ))
+            }
+        }
+
+        match tokens.next() {
+            None => (),
+            Some(token) if is_punct(token, ',') => (),
+            Some(token) => return Err(format!("expected ',' between keys, found '{}'", token)),
+        }
+    }
+
+    Ok(())
+}
+
+fn string_literal(token: &TokenTree) -> Result<String, String> {
+    match token {
+        TokenTree::Literal(literal) => unquote(&literal.to_string()),
+        other => Err(format!("expected a string literal, found '{}'", other)),
+    }
+}
+
+fn is_punct(token: &TokenTree, c: char) -> bool {
+    matches!(token, TokenTree::Punct(punct) if punct.as_char() == c && (c != ':' || punct.spacing() == Spacing::Alone))
+}
+
+fn parse_tokens(code: &str) -> TokenStream {
+    code.parse()
+        .expect("generated permission code should be valid tokens")
+}
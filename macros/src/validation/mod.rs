@@ -0,0 +1,114 @@
+//// TESTS ////
+#[cfg(test)]
+mod tests;
+
+// Character dividing action groups, must match the one used by simple-perm-manager.
+pub const ACTION_DIVIDER: char = ':';
+// Maximum nesting allowed for object style permissions, same as simple-perm-manager JSON.
+pub const MAX_DEPTH_ALLOWED: usize = 20;
+
+/// Checks that an action string has valid syntax: it is not empty, has no empty groups
+/// (leading, trailing or repeated dividers) and contains no whitespace.
+pub fn validate_action(action: &str) -> Result<(), String> {
+    if action.is_empty() {
+        return Err(String::from("action can not be empty"));
+    }
+    if action.chars().any(char::is_whitespace) {
+        return Err(format!("action '{}' contains whitespace", action));
+    }
+    if action.split(ACTION_DIVIDER).any(str::is_empty) {
+        return Err(format!(
+            "action '{}' has an empty group, check for leading, trailing or repeated '{}'",
+            action, ACTION_DIVIDER
+        ));
+    }
+
+    Ok(())
+}
+
+/// Checks that a key of an object style permission is a valid single group or action name.
+pub fn validate_key(key: &str) -> Result<(), String> {
+    if key.contains(ACTION_DIVIDER) {
+        return Err(format!(
+            "key '{}' contains '{}', nest objects to create groups instead",
+            key, ACTION_DIVIDER
+        ));
+    }
+
+    validate_action(key)
+}
+
+/// Returns the value of a string literal as written in source code (including quotes).
+/// Supports plain and raw strings. Escapes other than `\\` and `\"` are rejected as they
+/// are never needed in action names.
+pub fn unquote(literal: &str) -> Result<String, String> {
+    if let Some(raw) = literal.strip_prefix('r') {
+        let hashes = "#".repeat(raw.len() - raw.trim_start_matches('#').len());
+        let value = raw
+            .strip_prefix(&format!("{}\"", hashes))
+            .and_then(|rest| rest.strip_suffix(&format!("\"{}", hashes)));
+        return match value {
+            Some(value) => Ok(value.to_string()),
+            None => Err(format!("expected a string literal, found {}", literal)),
+        };
+    }
+
+    let inner = match literal
+        .strip_prefix('"')
+        .and_then(|rest| rest.strip_suffix('"'))
+    {
+        Some(inner) => inner,
+        None => return Err(format!("expected a string literal, found {}", literal)),
+    };
+
+    let mut value = String::with_capacity(inner.len());
+    let mut chars = inner.chars();
+    while let Some(c) = chars.next() {
+        if c == '\\' {
+            match chars.next() {
+                Some(escaped @ ('\\' | '"')) => value.push(escaped),
+                _ => {
+                    return Err(format!(
+                        "unsupported escape sequence in action literal {}",
+                        literal
+                    ))
+                }
+            }
+        } else {
+            value.push(c);
+        }
+    }
+
+    Ok(value)
+}
+
+/// Returns the enum variant name for an action: every group and the action name converted
+/// to upper camel case and joined (`building:meter_readings:view` is `BuildingMeterReadingsView`).
+/// Underscores and hyphens split words.
+pub fn variant_name(action: &str) -> Result<String, String> {
+    let name: String = action
+        .split([ACTION_DIVIDER, '_', '-'])
+        .filter(|word| !word.is_empty())
+        .map(|word| {
+            let mut chars = word.chars();
+            match chars.next() {
+                Some(first) => first.to_uppercase().chain(chars).collect(),
+                None => String::new(),
+            }
+        })
+        .collect();
+
+    let mut chars = name.chars();
+    let valid = match chars.next() {
+        Some(first) => first.is_alphabetic() && chars.all(char::is_alphanumeric),
+        None => false,
+    };
+    if !valid {
+        return Err(format!(
+            "action '{}' can not be converted to an enum variant name",
+            action
+        ));
+    }
+
+    Ok(name)
+}
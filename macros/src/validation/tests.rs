@@ -0,0 +1,53 @@
+use super::*;
+
+#[test]
+fn validate_action_test() {
+    assert_eq!(validate_action("view"), Ok(()));
+    assert_eq!(validate_action("building:meter:view"), Ok(()));
+
+    assert!(validate_action("").is_err());
+    assert!(validate_action("building view").is_err());
+    assert!(validate_action(":view").is_err());
+    assert!(validate_action("building:").is_err());
+    assert!(validate_action("building::view").is_err());
+}
+
+#[test]
+fn validate_key_test() {
+    assert_eq!(validate_key("building"), Ok(()));
+
+    assert!(validate_key("building:view").is_err());
+    assert!(validate_key("").is_err());
+    assert!(validate_key("a b").is_err());
+}
+
+#[test]
+fn unquote_test() {
+    assert_eq!(unquote(r#""view""#), Ok(String::from("view")));
+    assert_eq!(unquote(r#""a\"b\\c""#), Ok(String::from(r#"a"b\c"#)));
+    assert_eq!(unquote(r#"r"view""#), Ok(String::from("view")));
+    assert_eq!(unquote(r##"r#"vi"ew"#"##), Ok(String::from(r#"vi"ew"#)));
+
+    assert!(unquote("view").is_err());
+    assert!(unquote("3").is_err());
+    assert!(unquote(r#""a\nb""#).is_err());
+    assert!(unquote(r#"r#"view""#).is_err());
+}
+
+#[test]
+fn variant_name_test() {
+    assert_eq!(variant_name("view"), Ok(String::from("View")));
+    assert_eq!(
+        variant_name("building:meter_readings:view"),
+        Ok(String::from("BuildingMeterReadingsView"))
+    );
+    assert_eq!(
+        variant_name("user:two-factor"),
+        Ok(String::from("UserTwoFactor"))
+    );
+    assert_eq!(variant_name("v2:api"), Ok(String::from("V2Api")));
+
+    assert!(variant_name("2fa").is_err());
+    assert!(variant_name("building:view.all").is_err());
+    assert!(variant_name("_").is_err());
+}
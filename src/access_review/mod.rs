@@ -0,0 +1,111 @@
+use crate::condition::time_to_value;
+use serde_json::{Map, Value};
+use std::time::SystemTime;
+
+//// TESTS ////
+#[cfg(test)]
+mod tests;
+
+/// Access of a single subject in an [`AccessReview`](crate::AccessReview). Actions are sorted.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct SubjectReview {
+    /// Subject reviewed.
+    pub subject: String,
+    /// Actions of the effective [`Permission`](crate::Permission) of the subject.
+    pub actions: Vec<String>,
+    /// Names of the [`Role`](crate::Role)s assigned to the subject or to its groups, sorted, with the effective actions each one provides.
+    pub roles: Vec<(String, Vec<String>)>,
+    /// Actions of the temporary grants of the subject and of its groups that have not expired, with the time they expire,
+    /// sorted by expiration.
+    pub expirations: Vec<(Vec<String>, SystemTime)>,
+}
+
+impl SubjectReview {
+    fn to_value(&self) -> Value {
+        let mut roles = Map::new();
+        for (role, actions) in &self.roles {
+            roles.insert(role.clone(), Value::from(actions.clone()));
+        }
+        let expirations = self
+            .expirations
+            .iter()
+            .map(|(actions, valid_until)| {
+                let mut expiration = Map::new();
+                expiration.insert(String::from("actions"), Value::from(actions.clone()));
+                expiration.insert(
+                    String::from("valid_until"),
+                    time_to_value(Some(*valid_until)),
+                );
+                Value::Object(expiration)
+            })
+            .collect();
+
+        let mut map = Map::new();
+        map.insert(String::from("actions"), Value::from(self.actions.clone()));
+        map.insert(String::from("roles"), Value::Object(roles));
+        map.insert(String::from("expirations"), Value::Array(expirations));
+        Value::Object(map)
+    }
+}
+
+/// Report of the access held by every subject of an [`Authorizer`](crate::Authorizer), created with
+/// [`Authorizer::access_review`](crate::Authorizer::access_review()) for periodic access reviews.
+///
+/// # Examples:
+///
+/// ```
+/// use simple_perm_manager::{Authorizer, PermissionManager};
+///
+/// let mut auth = Authorizer::new(PermissionManager::from_json(r#"{"post": {"view": true, "edit": true}}"#));
+/// let editor = auth.manager().perm_from_json(r#"{"post": {"edit": true}}"#);
+/// auth.roles_mut().add_role("editor", editor);
+/// auth.roles_mut().assign("alice", "editor");
+/// let view = auth.manager().perm_from_json(r#"{"post": {"view": true}}"#);
+/// auth.grants_mut().grant(String::from("alice"), &view);
+///
+/// let review = auth.access_review();
+/// let alice = review.subject("alice").unwrap();
+/// assert_eq!(alice.actions, vec![String::from("post:edit"), String::from("post:view")]);
+/// assert_eq!(alice.roles, vec![(String::from("editor"), vec![String::from("post:edit")])]);
+/// assert_eq!(
+///     review.to_json(),
+///     r#"{"alice":{"actions":["post:edit","post:view"],"expirations":[],"roles":{"editor":["post:edit"]}}}"#
+/// );
+/// ```
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct AccessReview {
+    subjects: Vec<SubjectReview>,
+}
+
+impl AccessReview {
+    // Creates a review with the subject reviews provided, sorted by subject.
+    pub(crate) fn new(mut subjects: Vec<SubjectReview>) -> AccessReview {
+        subjects.sort_by(|a, b| a.subject.cmp(&b.subject));
+        AccessReview { subjects }
+    }
+
+    /// Returns the [`SubjectReview`](crate::SubjectReview)s of the [`AccessReview`](crate::AccessReview) sorted by subject.
+    pub fn subjects(&self) -> &[SubjectReview] {
+        &self.subjects
+    }
+
+    /// Returns the [`SubjectReview`](crate::SubjectReview) of a subject, if any.
+    pub fn subject(&self, subject: &str) -> Option<&SubjectReview> {
+        self.subjects
+            .binary_search_by(|review| review.subject.as_str().cmp(subject))
+            .ok()
+            .map(|position| &self.subjects[position])
+    }
+
+    /// Serializes the [`AccessReview`](crate::AccessReview) into a JSON string mapping each subject to an object with the format
+    /// `{"actions": [...], "expirations": [{"actions": [...], "valid_until": <ms>}], "roles": {"<role>": [...]}}`,
+    /// where times are in milliseconds since the UNIX epoch.
+    pub fn to_json(&self) -> String {
+        let mut map = Map::new();
+        for review in &self.subjects {
+            map.insert(review.subject.clone(), review.to_value());
+        }
+
+        Value::Object(map).to_string()
+    }
+}
@@ -0,0 +1,48 @@
+use super::*;
+use std::time::{Duration, UNIX_EPOCH};
+
+fn test_review() -> AccessReview {
+    AccessReview::new(vec![
+        SubjectReview {
+            subject: String::from("bob"),
+            actions: vec![],
+            roles: vec![],
+            expirations: vec![],
+        },
+        SubjectReview {
+            subject: String::from("alice"),
+            actions: vec![String::from("doc:edit"), String::from("doc:view")],
+            roles: vec![(String::from("editor"), vec![String::from("doc:edit")])],
+            expirations: vec![(
+                vec![String::from("doc:view")],
+                UNIX_EPOCH + Duration::from_millis(1_700_000_000_000),
+            )],
+        },
+    ])
+}
+
+#[test]
+fn subjects_test() {
+    let review = test_review();
+
+    assert_eq!(
+        review
+            .subjects()
+            .iter()
+            .map(|review| review.subject.as_str())
+            .collect::<Vec<&str>>(),
+        vec!["alice", "bob"]
+    );
+    assert_eq!(review.subject("bob").unwrap().actions.is_empty(), true);
+    assert_eq!(review.subject("carol"), None);
+    assert_eq!(AccessReview::default().subjects().is_empty(), true);
+}
+
+#[test]
+fn to_json_test() {
+    assert_eq!(
+        test_review().to_json(),
+        r#"{"alice":{"actions":["doc:edit","doc:view"],"expirations":[{"actions":["doc:view"],"valid_until":1700000000000}],"roles":{"editor":["doc:edit"]}},"bob":{"actions":[],"expirations":[],"roles":{}}}"#
+    );
+    assert_eq!(AccessReview::default().to_json(), "{}");
+}
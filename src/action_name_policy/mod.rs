@@ -0,0 +1,112 @@
+use crate::permission::{is_well_formed_action, ACTION_DIVIDER};
+use std::collections::HashSet;
+
+//// TESTS ////
+#[cfg(test)]
+mod tests;
+
+/// Rules that action names must follow, enforced by [`Permission::try_from_actions`](crate::Permission::try_from_actions()),
+/// [`Permission::try_from_json`](crate::Permission::try_from_json()) and the [`PermissionManager`](crate::PermissionManager) universe
+/// construction, so names that would later break serialization are rejected when they are created.
+///
+/// Actions are always rejected if they are empty or have empty groups (e.g. `"group::view"`). The rules of the policy apply to
+/// every group and name of an action, not to the separator dividing them.
+///
+/// # Examples:
+///
+/// ```
+/// use std::collections::HashSet;
+/// use simple_perm_manager::{ActionNamePolicy, PermissionManager, ValidationError};
+///
+/// let policy = ActionNamePolicy::identifiers().with_max_length(20).with_forbidden_chars(".");
+///
+/// assert!(policy.is_allowed("building:room_2:view"));
+/// assert!(!policy.is_allowed("building.view"));
+/// assert!(!policy.is_allowed("building:vïew"));
+///
+/// let manager = PermissionManager::try_from_actions_with_policy(
+///     HashSet::from([String::from("building:view"), String::from("building view")]),
+///     policy,
+/// );
+/// assert_eq!(manager.unwrap_err(), ValidationError::MalformedActions(vec![String::from("building view")]));
+/// ```
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct ActionNamePolicy {
+    max_length: Option<usize>,
+    charset: Option<HashSet<char>>,
+    forbidden_chars: HashSet<char>,
+}
+
+impl ActionNamePolicy {
+    /// Creates an [`ActionNamePolicy`](crate::ActionNamePolicy) that only rejects empty actions and empty groups.
+    pub fn new() -> ActionNamePolicy {
+        ActionNamePolicy::default()
+    }
+
+    /// Creates an [`ActionNamePolicy`](crate::ActionNamePolicy) allowing only ASCII letters, digits, `_` and `-` in action groups
+    /// and names.
+    pub fn identifiers() -> ActionNamePolicy {
+        ActionNamePolicy::new()
+            .with_charset("abcdefghijklmnopqrstuvwxyzABCDEFGHIJKLMNOPQRSTUVWXYZ0123456789_-")
+    }
+
+    /// Returns the [`ActionNamePolicy`](crate::ActionNamePolicy) with a maximum length in characters for whole actions,
+    /// separators included.
+    pub fn with_max_length(mut self, max_length: usize) -> ActionNamePolicy {
+        self.max_length = Some(max_length);
+        self
+    }
+
+    /// Returns the [`ActionNamePolicy`](crate::ActionNamePolicy) allowing only the characters of `charset` in action groups and names.
+    pub fn with_charset(mut self, charset: &str) -> ActionNamePolicy {
+        self.charset = Some(charset.chars().collect());
+        self
+    }
+
+    /// Returns the [`ActionNamePolicy`](crate::ActionNamePolicy) rejecting the characters of `chars` in action groups and names,
+    /// e.g. other separators (`.`, `/`) that would make actions ambiguous when migrating between formats.
+    pub fn with_forbidden_chars(mut self, chars: &str) -> ActionNamePolicy {
+        self.forbidden_chars.extend(chars.chars());
+        self
+    }
+
+    /// Returns `true` if the action follows the [`ActionNamePolicy`](crate::ActionNamePolicy) with the default
+    /// [`ACTION_DIVIDER`](crate::ACTION_DIVIDER) separator.
+    pub fn is_allowed(&self, action: &str) -> bool {
+        self.is_allowed_with_separator(action, ACTION_DIVIDER)
+    }
+
+    /// Returns `true` if the action, divided by `separator`, follows the [`ActionNamePolicy`](crate::ActionNamePolicy).
+    pub fn is_allowed_with_separator(&self, action: &str, separator: char) -> bool {
+        if self
+            .max_length
+            .is_some_and(|max_length| action.chars().count() > max_length)
+        {
+            return false;
+        }
+
+        is_well_formed_action(action, separator)
+            && action.chars().filter(|c| *c != separator).all(|c| {
+                !self.forbidden_chars.contains(&c)
+                    && self
+                        .charset
+                        .as_ref()
+                        .is_none_or(|charset| charset.contains(&c))
+            })
+    }
+
+    // Returns the actions that do not follow the policy, sorted.
+    pub(crate) fn rejected<'a, I>(&self, actions: I, separator: char) -> Vec<String>
+    where
+        I: IntoIterator<Item = &'a String>,
+    {
+        let mut rejected: Vec<String> = actions
+            .into_iter()
+            .filter(|action| !self.is_allowed_with_separator(action, separator))
+            .cloned()
+            .collect();
+        rejected.sort();
+
+        rejected
+    }
+}
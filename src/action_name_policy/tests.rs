@@ -0,0 +1,53 @@
+use super::*;
+
+#[test]
+fn default_policy_test() {
+    let policy = ActionNamePolicy::new();
+
+    assert_eq!(policy.is_allowed("view"), true);
+    assert_eq!(policy.is_allowed("building:room:view"), true);
+    assert_eq!(policy.is_allowed("building view.ñ"), true);
+    assert_eq!(policy.is_allowed(""), false);
+    assert_eq!(policy.is_allowed(":view"), false);
+    assert_eq!(policy.is_allowed("building:"), false);
+    assert_eq!(policy.is_allowed("building::view"), false);
+}
+
+#[test]
+fn rules_test() {
+    let policy = ActionNamePolicy::identifiers()
+        .with_max_length(12)
+        .with_forbidden_chars("-");
+
+    assert_eq!(policy.is_allowed("user:edit_2"), true);
+    assert_eq!(policy.is_allowed("user:edit-2"), false);
+    assert_eq!(policy.is_allowed("user:edit 2"), false);
+    assert_eq!(policy.is_allowed("user:setting"), true);
+    assert_eq!(policy.is_allowed("user:settings"), false);
+
+    let policy = ActionNamePolicy::new().with_charset("abc");
+    assert_eq!(policy.is_allowed("ab:c"), true);
+    assert_eq!(policy.is_allowed("ab:d"), false);
+    assert_eq!(policy.is_allowed_with_separator("ab.c", '.'), true);
+    assert_eq!(policy.is_allowed_with_separator("ab:c", '.'), false);
+}
+
+#[test]
+fn rejected_test() {
+    let actions = HashSet::from([
+        String::from("view"),
+        String::from("user.edit"),
+        String::from("a::b"),
+    ]);
+
+    assert_eq!(
+        ActionNamePolicy::new()
+            .with_forbidden_chars(".")
+            .rejected(&actions, ACTION_DIVIDER),
+        vec![String::from("a::b"), String::from("user.edit")]
+    );
+    assert_eq!(
+        ActionNamePolicy::new().rejected(&actions, '.'),
+        Vec::<String>::new()
+    );
+}
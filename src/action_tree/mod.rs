@@ -0,0 +1,144 @@
+use crate::permission::ACTION_DIVIDER;
+use std::collections::{BTreeMap, HashSet};
+
+//// TESTS ////
+#[cfg(test)]
+mod tests;
+
+/// Nested structure of a set of actions, where each group and action name is a node (the shape of the
+/// [`Permission`](crate::Permission) JSON format), for consumers that need to walk the hierarchy without
+/// splitting action strings.
+///
+/// A node is a leaf if it is an action itself, and a group if it has children. Nodes can be both, as in
+/// `"building"` and `"building:view"`.
+///
+/// # Examples:
+///
+/// ```
+/// use simple_perm_manager::{ActionTree, Permission};
+///
+/// let perm = Permission::from_json(r#"{"building": {"view": true, "room": {"edit": true}}, "home": true}"#);
+/// let tree = perm.to_tree();
+///
+/// let building = tree.node("building").unwrap();
+/// assert!(building.is_group() && !building.is_leaf());
+/// assert_eq!(building.children().map(|(name, _)| name).collect::<Vec<&str>>(), vec!["room", "view"]);
+/// assert!(tree.node("building:room:edit").unwrap().is_leaf());
+///
+/// assert_eq!(Permission::from_tree(&tree).get_actions(), perm.get_actions());
+/// ```
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct ActionTree {
+    root: ActionNode,
+    separator: char,
+}
+
+/// Node of an [`ActionTree`](crate::ActionTree): a group, an action or both.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct ActionNode {
+    children: BTreeMap<String, ActionNode>,
+    leaf: bool,
+}
+
+impl ActionNode {
+    /// Returns the children of the node with their names, sorted by name.
+    pub fn children(&self) -> impl Iterator<Item = (&str, &ActionNode)> + '_ {
+        self.children
+            .iter()
+            .map(|(name, node)| (name.as_str(), node))
+    }
+
+    /// Returns the child of the node with the name provided, if any.
+    pub fn child(&self, name: &str) -> Option<&ActionNode> {
+        self.children.get(name)
+    }
+
+    /// Returns `true` if the node is an action.
+    pub fn is_leaf(&self) -> bool {
+        self.leaf
+    }
+
+    /// Returns `true` if the node is a group of actions, i.e. it has children.
+    pub fn is_group(&self) -> bool {
+        !self.children.is_empty()
+    }
+
+    // Adds the actions of the node and its descendants, prefixed with the path of the node, to the set provided.
+    fn collect_actions(&self, path: &str, separator: char, actions: &mut HashSet<String>) {
+        if self.leaf {
+            actions.insert(path.to_string());
+        }
+        for (name, child) in &self.children {
+            let child_path = if path.is_empty() {
+                name.clone()
+            } else {
+                format!("{}{}{}", path, separator, name)
+            };
+            child.collect_actions(&child_path, separator, actions);
+        }
+    }
+}
+
+impl ActionTree {
+    /// Creates an empty [`ActionTree`](crate::ActionTree) whose actions are divided by the separator provided.
+    pub fn new(separator: char) -> ActionTree {
+        ActionTree {
+            root: ActionNode::default(),
+            separator,
+        }
+    }
+
+    /// Creates an [`ActionTree`](crate::ActionTree) with the actions provided, divided by the separator provided.
+    pub fn from_actions(actions: &HashSet<String>, separator: char) -> ActionTree {
+        let mut tree = ActionTree::new(separator);
+        for action in actions {
+            tree.insert(action);
+        }
+
+        tree
+    }
+
+    /// Adds an action to the [`ActionTree`](crate::ActionTree), creating the nodes of its groups as needed.
+    pub fn insert(&mut self, action: &str) {
+        let mut node = &mut self.root;
+        for name in action.split(self.separator) {
+            node = node.children.entry(name.to_string()).or_default();
+        }
+        node.leaf = true;
+    }
+
+    /// Returns the root node of the [`ActionTree`](crate::ActionTree), whose children are the top-level groups and actions.
+    pub fn root(&self) -> &ActionNode {
+        &self.root
+    }
+
+    /// Returns the node of an action or group, e.g. `"building:room"`, if it is in the [`ActionTree`](crate::ActionTree).
+    pub fn node(&self, path: &str) -> Option<&ActionNode> {
+        path.split(self.separator)
+            .try_fold(&self.root, |node, name| node.child(name))
+    }
+
+    /// Returns the character dividing the groups and name of the actions of the [`ActionTree`](crate::ActionTree).
+    pub fn separator(&self) -> char {
+        self.separator
+    }
+
+    /// Returns the actions of the [`ActionTree`](crate::ActionTree) joined with its separator.
+    pub fn actions(&self) -> HashSet<String> {
+        self.actions_with_separator(self.separator)
+    }
+
+    /// Returns the actions of the [`ActionTree`](crate::ActionTree) joined with the separator provided.
+    pub fn actions_with_separator(&self, separator: char) -> HashSet<String> {
+        let mut actions = HashSet::new();
+        self.root.collect_actions("", separator, &mut actions);
+
+        actions
+    }
+}
+
+impl Default for ActionTree {
+    fn default() -> ActionTree {
+        ActionTree::new(ACTION_DIVIDER)
+    }
+}
@@ -0,0 +1,46 @@
+use super::*;
+
+#[test]
+fn from_actions_test() {
+    let actions = HashSet::from([
+        String::from("building"),
+        String::from("building:view"),
+        String::from("building:room:edit"),
+        String::from("home"),
+    ]);
+    let tree = ActionTree::from_actions(&actions, ':');
+
+    let building = tree.node("building").unwrap();
+    assert_eq!(building.is_leaf(), true);
+    assert_eq!(building.is_group(), true);
+    assert_eq!(tree.node("building:room").unwrap().is_leaf(), false);
+    assert_eq!(tree.node("building:room:edit").unwrap().is_group(), false);
+    assert_eq!(tree.node("building:other").is_none(), true);
+    assert_eq!(
+        tree.root()
+            .children()
+            .map(|(name, _)| name)
+            .collect::<Vec<&str>>(),
+        vec!["building", "home"]
+    );
+    assert_eq!(tree.actions(), actions);
+}
+
+#[test]
+fn separator_test() {
+    let mut tree = ActionTree::new('.');
+    tree.insert("building.room.edit");
+    tree.insert("building.view");
+
+    assert_eq!(tree.separator(), '.');
+    assert_eq!(tree.node("building.room").unwrap().is_group(), true);
+    assert_eq!(
+        tree.actions_with_separator(':'),
+        HashSet::from([
+            String::from("building:room:edit"),
+            String::from("building:view")
+        ])
+    );
+    assert_eq!(ActionTree::default().separator(), ACTION_DIVIDER);
+    assert_eq!(ActionTree::default().actions().is_empty(), true);
+}
@@ -0,0 +1,182 @@
+use crate::condition::time_to_value;
+use crate::decision::Decision;
+use serde_json::{Map, Value};
+use std::fmt;
+use std::fs::{File, OpenOptions};
+use std::io::{self, Write};
+use std::panic::{RefUnwindSafe, UnwindSafe};
+use std::path::Path;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::Mutex;
+use std::time::SystemTime;
+
+//// TESTS ////
+#[cfg(test)]
+mod tests;
+
+/// Authorization decision recorded by an [`AuditSink`](crate::AuditSink).
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct DecisionEvent {
+    /// Subject whose access was checked.
+    pub subject: String,
+    /// Resource the access was checked on, if any.
+    pub resource: Option<String>,
+    /// Action checked.
+    pub action: String,
+    /// Result of the check.
+    pub decision: Decision,
+    /// Time of the check.
+    pub timestamp: SystemTime,
+}
+
+impl DecisionEvent {
+    /// Serializes the [`DecisionEvent`](crate::DecisionEvent) into a single line JSON string with the format
+    /// `{"action": "...", "decision": "allow", "resource": "..." | null, "subject": "...", "timestamp": <ms>}`,
+    /// where the timestamp is in milliseconds since the UNIX epoch.
+    pub fn to_json(&self) -> String {
+        let mut map = Map::new();
+        map.insert(String::from("subject"), Value::from(self.subject.as_str()));
+        map.insert(
+            String::from("resource"),
+            self.resource.as_deref().map_or(Value::Null, Value::from),
+        );
+        map.insert(String::from("action"), Value::from(self.action.as_str()));
+        map.insert(
+            String::from("decision"),
+            Value::from(self.decision.to_string()),
+        );
+        map.insert(
+            String::from("timestamp"),
+            time_to_value(Some(self.timestamp)),
+        );
+
+        Value::Object(map).to_string()
+    }
+}
+
+/// Destination of the [`DecisionEvent`](crate::DecisionEvent)s of every check made through an [`Authorizer`](crate::Authorizer),
+/// registered with [`Authorizer::add_audit_sink`](crate::Authorizer::add_audit_sink()).
+///
+/// Implemented for any `Fn(DecisionEvent)` closure. Sinks must be thread and unwind safe, so the
+/// [`Authorizer`](crate::Authorizer) can be shared across threads.
+///
+/// # Examples:
+///
+/// ```
+/// use std::sync::{Arc, Mutex};
+/// use simple_perm_manager::{Authorizer, DecisionEvent, PermissionManager};
+///
+/// let events = Arc::new(Mutex::new(Vec::new()));
+/// let sink_events = events.clone();
+///
+/// let mut auth = Authorizer::new(PermissionManager::from_json(r#"{"view": true}"#));
+/// auth.add_audit_sink(move |event: DecisionEvent| sink_events.lock().unwrap().push(event));
+/// auth.check("alice", "view");
+///
+/// assert_eq!(events.lock().unwrap()[0].subject, "alice");
+/// ```
+pub trait AuditSink: Send + Sync + UnwindSafe + RefUnwindSafe {
+    /// Records a [`DecisionEvent`](crate::DecisionEvent).
+    fn record(&self, event: DecisionEvent);
+}
+
+impl<F> AuditSink for F
+where
+    F: Fn(DecisionEvent) + Send + Sync + UnwindSafe + RefUnwindSafe,
+{
+    fn record(&self, event: DecisionEvent) {
+        self(event)
+    }
+}
+
+/// [`AuditSink`](crate::AuditSink) appending each [`DecisionEvent`](crate::DecisionEvent) to a file as a line of JSON,
+/// in the [`DecisionEvent::to_json`](crate::DecisionEvent::to_json()) format.
+///
+/// Write errors do not make checks fail. They are counted, see [`failures`](crate::JsonLinesSink::failures()), passed to the
+/// callback registered with [`on_error`](crate::JsonLinesSink::on_error()) and reported by
+/// [`flush`](crate::JsonLinesSink::flush()).
+pub struct JsonLinesSink {
+    file: Mutex<File>,
+    failures: AtomicUsize,
+    reported: AtomicUsize,
+    error_hook: Option<ErrorHook>,
+}
+
+type ErrorHook = Box<dyn Fn(&io::Error) + Send + Sync + UnwindSafe + RefUnwindSafe>;
+
+impl JsonLinesSink {
+    /// Creates a new [`JsonLinesSink`](crate::JsonLinesSink) appending to the file at the path provided, creating it if needed.
+    ///
+    /// # Errors:
+    ///
+    /// Returns an error if the file can not be opened for appending.
+    pub fn new<P: AsRef<Path>>(path: P) -> io::Result<JsonLinesSink> {
+        let file = OpenOptions::new().create(true).append(true).open(path)?;
+
+        Ok(JsonLinesSink {
+            file: Mutex::new(file),
+            failures: AtomicUsize::new(0),
+            reported: AtomicUsize::new(0),
+            error_hook: None,
+        })
+    }
+
+    /// Registers a callback called with the error of every [`DecisionEvent`](crate::DecisionEvent) that could not be written.
+    /// Replaces the previous callback, if any.
+    pub fn on_error<F>(&mut self, hook: F)
+    where
+        F: Fn(&io::Error) + Send + Sync + UnwindSafe + RefUnwindSafe + 'static,
+    {
+        self.error_hook = Some(Box::new(hook));
+    }
+
+    /// Returns the number of [`DecisionEvent`](crate::DecisionEvent)s that could not be written since the sink was created.
+    pub fn failures(&self) -> usize {
+        self.failures.load(Ordering::SeqCst)
+    }
+
+    /// Flushes the file.
+    ///
+    /// # Errors:
+    ///
+    /// Returns an error if the file can not be flushed, or if any [`DecisionEvent`](crate::DecisionEvent) could not be
+    /// written since the previous call to [`flush`](crate::JsonLinesSink::flush()).
+    pub fn flush(&self) -> io::Result<()> {
+        self.file
+            .lock()
+            .unwrap_or_else(|error| error.into_inner())
+            .flush()?;
+
+        let failures = self.failures();
+        let reported = self.reported.swap(failures, Ordering::SeqCst);
+        if failures > reported {
+            return Err(io::Error::other(format!(
+                "{} audit events could not be written",
+                failures - reported
+            )));
+        }
+
+        Ok(())
+    }
+}
+
+impl fmt::Debug for JsonLinesSink {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("JsonLinesSink")
+            .field("file", &self.file)
+            .field("failures", &self.failures)
+            .finish_non_exhaustive()
+    }
+}
+
+impl AuditSink for JsonLinesSink {
+    fn record(&self, event: DecisionEvent) {
+        let mut file = self.file.lock().unwrap_or_else(|error| error.into_inner());
+        if let Err(error) = writeln!(file, "{}", event.to_json()) {
+            self.failures.fetch_add(1, Ordering::SeqCst);
+            if let Some(hook) = &self.error_hook {
+                hook(&error);
+            }
+        }
+    }
+}
@@ -0,0 +1,87 @@
+use super::*;
+use std::time::{Duration, UNIX_EPOCH};
+
+fn test_event() -> DecisionEvent {
+    DecisionEvent {
+        subject: String::from("alice"),
+        resource: Some(String::from("readme")),
+        action: String::from("doc:view"),
+        decision: Decision::Allow,
+        timestamp: UNIX_EPOCH + Duration::from_millis(1_700_000_000_000),
+    }
+}
+
+#[test]
+fn to_json_test() {
+    assert_eq!(
+        test_event().to_json(),
+        r#"{"action":"doc:view","decision":"allow","resource":"readme","subject":"alice","timestamp":1700000000000}"#
+    );
+
+    let event = DecisionEvent {
+        resource: None,
+        decision: Decision::NotApplicable,
+        ..test_event()
+    };
+    assert_eq!(
+        event.to_json(),
+        r#"{"action":"doc:view","decision":"not_applicable","resource":null,"subject":"alice","timestamp":1700000000000}"#
+    );
+}
+
+#[test]
+fn json_lines_sink_test() {
+    let path = std::env::temp_dir().join(format!("audit-{}.jsonl", uuid::Uuid::new_v4()));
+    let sink = JsonLinesSink::new(&path).unwrap();
+    sink.record(test_event());
+    sink.record(DecisionEvent {
+        decision: Decision::Deny,
+        ..test_event()
+    });
+
+    // Reopening appends to the existing file
+    let sink = JsonLinesSink::new(&path).unwrap();
+    sink.record(test_event());
+
+    let lines: Vec<String> = std::fs::read_to_string(&path)
+        .unwrap()
+        .lines()
+        .map(String::from)
+        .collect();
+    std::fs::remove_file(&path).unwrap();
+
+    assert_eq!(lines.len(), 3);
+    assert_eq!(lines[0], test_event().to_json());
+    assert_eq!(lines[1].contains(r#""decision":"deny""#), true);
+    assert_eq!(sink.failures(), 0);
+    assert_eq!(sink.flush().is_ok(), true);
+
+    assert_eq!(
+        JsonLinesSink::new(std::env::temp_dir().join("missing-dir").join("audit.jsonl")).is_err(),
+        true
+    );
+}
+
+#[cfg(target_os = "linux")]
+#[test]
+fn json_lines_sink_errors_test() {
+    use std::sync::Arc;
+
+    let errors = Arc::new(AtomicUsize::new(0));
+    let hook_errors = errors.clone();
+
+    // Writes to /dev/full always fail
+    let mut sink = JsonLinesSink::new("/dev/full").unwrap();
+    sink.on_error(move |_| {
+        hook_errors.fetch_add(1, Ordering::SeqCst);
+    });
+    sink.record(test_event());
+    sink.record(test_event());
+
+    assert_eq!(sink.failures(), 2);
+    assert_eq!(errors.load(Ordering::SeqCst), 2);
+    assert_eq!(sink.flush().is_err(), true);
+    // Failures are reported once
+    assert_eq!(sink.flush().is_ok(), true);
+    assert_eq!(sink.failures(), 2);
+}
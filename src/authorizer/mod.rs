@@ -0,0 +1,686 @@
+mod opa;
+use crate::access_review::{AccessReview, SubjectReview};
+use crate::audit::{AuditSink, DecisionEvent};
+use crate::condition::Context;
+use crate::decision::Decision;
+use crate::grant_store::GrantStore;
+use crate::object_acl::ObjectAcl;
+use crate::permission::Permission;
+use crate::permission_manager::PermissionManager;
+use crate::role_manager::RoleManager;
+use crate::simulation::{OutcomeFlip, PolicyChange};
+use crate::telemetry;
+use crate::trace::{GrantSource, Trace, TraceStep};
+use std::collections::{HashMap, HashSet};
+use std::fmt;
+use std::sync::{Arc, RwLock, RwLockReadGuard, RwLockWriteGuard};
+use std::time::{Instant, SystemTime};
+
+//// TESTS ////
+#[cfg(test)]
+mod tests;
+
+/// Computes the effective [`Permission`](crate::Permission) of subjects by composing all the subsystems of a
+/// [`PermissionManager`](crate::PermissionManager): direct grants and group memberships of a [`GrantStore`](crate::GrantStore),
+/// role assignments of a [`RoleManager`](crate::RoleManager), action implications and deny rules.
+///
+/// Subjects are identified by string ids chosen by the application. Groups are subjects too, and the roles assigned to a
+/// group apply to all of its members. Resources are identified by string ids too, and the actions allowed on each one are
+/// kept in an [`ObjectAcl`](crate::ObjectAcl).  
+/// Effective [`Permission`](crate::Permission)s are cached by subject. The cache is cleared whenever grants, roles or deny rules
+/// may change, i.e. when calling [`grants_mut`](crate::Authorizer::grants_mut()), [`roles_mut`](crate::Authorizer::roles_mut()),
+/// [`deny`](crate::Authorizer::deny()) or [`undeny`](crate::Authorizer::undeny()), and cached entries are computed again once a
+/// temporary grant becomes active or expires or the time window of a conditional grant opens or closes. The universe can not change while the
+/// [`PermissionManager`](crate::PermissionManager) is shared with the [`Authorizer`](crate::Authorizer).  
+/// Every check and evaluation is recorded in the [`AuditSink`](crate::AuditSink)s registered with
+/// [`add_audit_sink`](crate::Authorizer::add_audit_sink()).
+///
+/// # Examples:
+///
+/// ```
+/// use simple_perm_manager::{Authorizer, PermissionManager};
+///
+/// let mut manager = PermissionManager::from_json(r#"{"post": {"view": true, "edit": true, "delete": true}}"#);
+/// manager.add_implication("post:edit", "post:view");
+/// let mut auth = Authorizer::new(manager);
+///
+/// let editor = auth.manager().perm_from_json(r#"{"post": {"edit": true, "delete": true}}"#);
+/// auth.roles_mut().add_role("editor", editor);
+/// auth.roles_mut().assign("backend", "editor");
+/// auth.grants_mut().add_member(String::from("alice"), String::from("backend"));
+/// auth.deny("alice", &auth.manager().perm_from_json(r#"{"post": {"delete": true}}"#));
+///
+/// let perm = auth.effective("alice");
+/// assert!(perm.contains_action("post:view"));
+/// assert!(perm.contains_action("post:edit"));
+/// assert!(!perm.contains_action("post:delete"));
+/// ```
+pub struct Authorizer {
+    manager: Arc<PermissionManager>,
+    grants: GrantStore<String>,
+    roles: RoleManager,
+    acl: ObjectAcl<String>,
+    // Subject ids mapped to the actions denied to them
+    denies: HashMap<String, Permission>,
+    // Subject ids mapped to their last computed effective permission and the time it stops being valid
+    cache: RwLock<HashMap<String, (Permission, Option<SystemTime>)>>,
+    audit_sinks: Vec<Box<dyn AuditSink>>,
+}
+
+impl Authorizer {
+    /// Creates a new empty [`Authorizer`](crate::Authorizer) for the [`PermissionManager`](crate::PermissionManager) provided.  
+    /// Accepts an owned [`PermissionManager`](crate::PermissionManager) or a shared one, such as the ones stored in a
+    /// [`ManagerRegistry`](crate::ManagerRegistry).
+    pub fn new<M>(manager: M) -> Authorizer
+    where
+        M: Into<Arc<PermissionManager>>,
+    {
+        let manager = manager.into();
+        Authorizer {
+            grants: GrantStore::new(manager.clone()),
+            roles: RoleManager::new(manager.clone()),
+            acl: ObjectAcl::new(manager.clone()),
+            manager,
+            denies: HashMap::new(),
+            cache: RwLock::new(HashMap::new()),
+            audit_sinks: Vec::new(),
+        }
+    }
+
+    /// Returns the [`PermissionManager`](crate::PermissionManager) of the [`Authorizer`](crate::Authorizer).
+    pub fn manager(&self) -> &PermissionManager {
+        &self.manager
+    }
+
+    /// Returns the [`GrantStore`](crate::GrantStore) holding direct grants and group memberships.
+    pub fn grants(&self) -> &GrantStore<String> {
+        &self.grants
+    }
+
+    /// Returns a mutable reference to the [`GrantStore`](crate::GrantStore) holding direct grants and group memberships.
+    /// Clears the cache of effective [`Permission`](crate::Permission)s.
+    pub fn grants_mut(&mut self) -> &mut GrantStore<String> {
+        self.invalidate_all();
+        &mut self.grants
+    }
+
+    /// Returns the [`RoleManager`](crate::RoleManager) holding roles and their assignments.
+    pub fn roles(&self) -> &RoleManager {
+        &self.roles
+    }
+
+    /// Returns a mutable reference to the [`RoleManager`](crate::RoleManager) holding roles and their assignments.
+    /// Clears the cache of effective [`Permission`](crate::Permission)s.
+    pub fn roles_mut(&mut self) -> &mut RoleManager {
+        self.invalidate_all();
+        &mut self.roles
+    }
+
+    /// Returns the [`ObjectAcl`](crate::ObjectAcl) holding the actions allowed on each resource.
+    pub fn acl(&self) -> &ObjectAcl<String> {
+        &self.acl
+    }
+
+    /// Returns a mutable reference to the [`ObjectAcl`](crate::ObjectAcl) holding the actions allowed on each resource.
+    pub fn acl_mut(&mut self) -> &mut ObjectAcl<String> {
+        &mut self.acl
+    }
+
+    /// Registers an [`AuditSink`](crate::AuditSink) recording the [`DecisionEvent`](crate::DecisionEvent) of every check and evaluation.
+    pub fn add_audit_sink<A>(&mut self, sink: A)
+    where
+        A: AuditSink + 'static,
+    {
+        self.audit_sinks.push(Box::new(sink));
+    }
+
+    /// Denies the actions of the [`Permission`](crate::Permission) provided to a subject, in addition to the ones already denied.  
+    /// Denied actions are removed from the effective [`Permission`](crate::Permission) of the subject and of all the members of
+    /// the subject when it is a group, no matter where they were granted.  
+    /// [`Permission`](crate::Permission)s created by the [`PermissionManager`](crate::PermissionManager) include implied actions; use the
+    /// denied [`Permission`](crate::Permission) of a [`Policy`](crate::Policy) created with
+    /// [`PermissionManager::policy_from_json`](crate::PermissionManager::policy_from_json()) to deny an action without the actions it implies.
+    ///
+    /// # Panics:
+    ///
+    /// Panics if the [`Permission`](crate::Permission) is not valid for the [`PermissionManager`](crate::PermissionManager).
+    pub fn deny(&mut self, subject: &str, perm: &Permission) {
+        if !self.manager.validate_perm(perm) {
+            panic!("Permission to deny is not valid for the Authorizer PermissionManager");
+        }
+        self.invalidate_all();
+
+        match self.denies.get_mut(subject) {
+            Some(denied) => denied.union_with(perm),
+            None => {
+                self.denies.insert(String::from(subject), perm.clone());
+            }
+        }
+    }
+
+    /// Removes the actions of the [`Permission`](crate::Permission) provided from the ones denied to a subject.
+    ///
+    /// # Panics:
+    ///
+    /// Panics if the [`Permission`](crate::Permission) is not managed by the [`PermissionManager`](crate::PermissionManager).
+    pub fn undeny(&mut self, subject: &str, perm: &Permission) {
+        if !self.manager.get_universe().has_same_manager(perm) {
+            panic!("Permission to undeny is not managed by the Authorizer PermissionManager");
+        }
+        self.invalidate_all();
+
+        if let Some(denied) = self.denies.get_mut(subject) {
+            denied.difference_with(perm);
+            if denied.is_empty() {
+                self.denies.remove(subject);
+            }
+        }
+    }
+
+    /// Returns the actions denied directly to a subject.  
+    /// Subjects without deny rules get an empty [`Permission`](crate::Permission) managed by the [`PermissionManager`](crate::PermissionManager).
+    pub fn denied(&self, subject: &str) -> Permission {
+        self.manager.union_all(self.denies.get(subject))
+    }
+
+    /// Returns the effective [`Permission`](crate::Permission) of a subject, computed as:
+    ///
+    /// 1. The direct grants of the subject and of all the groups it belongs to.
+    /// 2. The effective [`Permission`](crate::Permission)s of the roles assigned to the subject and to its groups.
+    /// 3. The actions implied by the ones above.
+    /// 4. Minus the actions denied to the subject and to its groups, and the actions implying them.
+    ///
+    /// # Panics:
+    ///
+    /// Panics if the group nesting of the subject is deeper than the maximum resolution depth of the [`GrantStore`](crate::GrantStore).
+    pub fn effective(&self, subject: &str) -> Permission {
+        let now = SystemTime::now();
+        if let Some((perm, valid_until)) = self.cache_read().get(subject) {
+            if valid_until.is_none_or(|until| now < until) {
+                return perm.clone();
+            }
+        }
+
+        let effective = self.compute_effective(subject, now, &Context::new());
+        self.cache_write().insert(
+            String::from(subject),
+            (effective.clone(), self.grants.next_transition(now)),
+        );
+
+        effective
+    }
+
+    /// Removes the cached effective [`Permission`](crate::Permission) of a subject, so it is computed again on the next call to
+    /// [`effective`](crate::Authorizer::effective()).
+    pub fn invalidate(&self, subject: &str) {
+        self.cache_write().remove(subject);
+    }
+
+    /// Removes all the cached effective [`Permission`](crate::Permission)s.
+    pub fn invalidate_all(&self) {
+        self.cache_write().clear();
+    }
+
+    /// Returns `true` if the action is in the effective [`Permission`](crate::Permission) of a subject.
+    ///
+    /// # Panics:
+    ///
+    /// Panics if the group nesting of the subject is deeper than the maximum resolution depth of the [`GrantStore`](crate::GrantStore).
+    #[cfg_attr(feature = "tracing", tracing::instrument(skip_all, fields(manager_id = %self.manager.id(), subject, action = action_str), ret))]
+    pub fn check(&self, subject: &str, action_str: &str) -> bool {
+        let started = Instant::now();
+        let allowed = self.effective(subject).contains_action(action_str);
+        self.record(subject, None, action_str, allowed.into(), started);
+        allowed
+    }
+
+    /// Returns the effective [`Permission`](crate::Permission) of a subject for the [`Context`](crate::Context) provided, including
+    /// the conditional grants whose [`Condition`](crate::Condition) holds. Computed as in [`effective`](crate::Authorizer::effective())
+    /// but never cached.
+    ///
+    /// # Panics:
+    ///
+    /// Panics if the group nesting of the subject is deeper than the maximum resolution depth of the [`GrantStore`](crate::GrantStore).
+    pub fn effective_with(&self, subject: &str, context: &Context) -> Permission {
+        self.compute_effective(subject, SystemTime::now(), context)
+    }
+
+    /// Returns `true` if the action is in the effective [`Permission`](crate::Permission) of a subject for the [`Context`](crate::Context) provided.
+    ///
+    /// # Panics:
+    ///
+    /// Panics if the group nesting of the subject is deeper than the maximum resolution depth of the [`GrantStore`](crate::GrantStore).
+    #[cfg_attr(feature = "tracing", tracing::instrument(skip_all, fields(manager_id = %self.manager.id(), subject, action = action_str), ret))]
+    pub fn check_with(&self, subject: &str, action_str: &str, context: &Context) -> bool {
+        let started = Instant::now();
+        let allowed = self
+            .effective_with(subject, context)
+            .contains_action(action_str);
+        self.record(subject, None, action_str, allowed.into(), started);
+        allowed
+    }
+
+    /// Decides whether a subject can perform an action on a resource, tying together the effective [`Permission`](crate::Permission)
+    /// of the subject and the [`ObjectAcl`](crate::ObjectAcl) of the resource:
+    ///
+    /// - [`Decision::Allow`](crate::Decision::Allow) if the action is in the effective [`Permission`](crate::Permission) of the subject
+    ///   and allowed on the resource, directly or inherited from its ancestors.
+    /// - [`Decision::Deny`](crate::Decision::Deny) otherwise, including resources without actions in the [`ObjectAcl`](crate::ObjectAcl).
+    /// - [`Decision::NotApplicable`](crate::Decision::NotApplicable) if the action is not in the universe of the
+    ///   [`PermissionManager`](crate::PermissionManager).
+    ///
+    /// Conditional grants are evaluated against an empty [`Context`](crate::Context), use
+    /// [`evaluate_with`](crate::Authorizer::evaluate_with()) to provide one.
+    ///
+    /// # Examples:
+    ///
+    /// ```
+    /// use simple_perm_manager::{Authorizer, Decision, PermissionManager};
+    ///
+    /// let mut auth = Authorizer::new(PermissionManager::from_json(r#"{"doc": {"view": true, "edit": true}}"#));
+    /// let edit = auth.manager().perm_from_json(r#"{"doc": {"view": true, "edit": true}}"#);
+    /// auth.grants_mut().grant(String::from("alice"), &edit);
+    /// let view = auth.manager().perm_from_json(r#"{"doc": {"view": true}}"#);
+    /// auth.acl_mut().grant_on(String::from("readme"), &view);
+    ///
+    /// assert_eq!(auth.evaluate("alice", "readme", "doc:view"), Decision::Allow);
+    /// assert_eq!(auth.evaluate("alice", "readme", "doc:edit"), Decision::Deny);
+    /// assert_eq!(auth.evaluate("alice", "readme", "doc:share"), Decision::NotApplicable);
+    /// ```
+    ///
+    /// # Panics:
+    ///
+    /// Panics if the group nesting of the subject is deeper than the maximum resolution depth of the [`GrantStore`](crate::GrantStore).
+    #[cfg_attr(feature = "tracing", tracing::instrument(skip_all, fields(manager_id = %self.manager.id(), subject, resource, action = action_str), ret))]
+    pub fn evaluate(&self, subject: &str, resource: &str, action_str: &str) -> Decision {
+        let started = Instant::now();
+        let decision = self.decide_on(&self.effective(subject), resource, action_str);
+        self.record(subject, Some(resource), action_str, decision, started);
+        decision
+    }
+
+    /// Same as [`evaluate`](crate::Authorizer::evaluate()) but evaluating conditional grants against the [`Context`](crate::Context) provided.
+    ///
+    /// # Panics:
+    ///
+    /// Panics if the group nesting of the subject is deeper than the maximum resolution depth of the [`GrantStore`](crate::GrantStore).
+    #[cfg_attr(feature = "tracing", tracing::instrument(skip_all, fields(manager_id = %self.manager.id(), subject, resource, action = action_str), ret))]
+    pub fn evaluate_with(
+        &self,
+        subject: &str,
+        resource: &str,
+        action_str: &str,
+        context: &Context,
+    ) -> Decision {
+        let started = Instant::now();
+        let decision = self.decide_on(&self.effective_with(subject, context), resource, action_str);
+        self.record(subject, Some(resource), action_str, decision, started);
+        decision
+    }
+
+    // Records the decision of a check started at `started` in the metrics and the audit sinks.
+    fn record(
+        &self,
+        subject: &str,
+        resource: Option<&str>,
+        action_str: &str,
+        decision: Decision,
+        started: Instant,
+    ) {
+        telemetry::record_decision(decision, started);
+        if self.audit_sinks.is_empty() {
+            return;
+        }
+
+        let event = DecisionEvent {
+            subject: String::from(subject),
+            resource: resource.map(String::from),
+            action: String::from(action_str),
+            decision,
+            timestamp: SystemTime::now(),
+        };
+        for sink in &self.audit_sinks {
+            sink.record(event.clone());
+        }
+    }
+
+    /// Same as [`evaluate_with`](crate::Authorizer::evaluate_with()) but also returns the obligations of the action when the
+    /// [`Decision`](crate::Decision) is [`Decision::Allow`](crate::Decision::Allow), see
+    /// [`PermissionManager::add_obligation`](crate::PermissionManager::add_obligation()).
+    ///
+    /// # Panics:
+    ///
+    /// Panics if the group nesting of the subject is deeper than the maximum resolution depth of the [`GrantStore`](crate::GrantStore).
+    pub fn evaluate_with_obligations(
+        &self,
+        subject: &str,
+        resource: &str,
+        action_str: &str,
+        context: &Context,
+    ) -> (Decision, Vec<String>) {
+        match self.evaluate_with(subject, resource, action_str, context) {
+            Decision::Allow => (Decision::Allow, self.manager.obligations_for(action_str)),
+            decision => (decision, Vec::new()),
+        }
+    }
+
+    /// Same as [`evaluate_with`](crate::Authorizer::evaluate_with()) but also returns a [`Trace`](crate::Trace) explaining the
+    /// [`Decision`](crate::Decision): which grants and roles supplied the action, which conditions failed, which deny rules
+    /// blocked it or whether the resource does not allow it.
+    ///
+    /// # Panics:
+    ///
+    /// Panics if the group nesting of the subject is deeper than the maximum resolution depth of the [`GrantStore`](crate::GrantStore).
+    pub fn decide_explained(
+        &self,
+        subject: &str,
+        resource: &str,
+        action_str: &str,
+        context: &Context,
+    ) -> (Decision, Trace) {
+        let mut trace = Trace::new();
+        let decision = self.evaluate_with(subject, resource, action_str, context);
+        if decision == Decision::NotApplicable {
+            trace.push(TraceStep::UnknownAction);
+            return (decision, trace);
+        }
+
+        let subject = String::from(subject);
+        let mut subjects: Vec<&String> = self.grants.groups(&subject).into_iter().collect();
+        subjects.sort();
+        subjects.insert(0, &subject);
+        let now = SystemTime::now();
+
+        for s in &subjects {
+            for step in self.grants.trace_grants(s, s, action_str, now, context) {
+                trace.push(step);
+            }
+            let mut roles: Vec<String> = self.roles.subject_roles(s).into_iter().collect();
+            roles.sort();
+            for role in roles {
+                if self
+                    .roles
+                    .effective_role_perm(&role)
+                    .contains_action(action_str)
+                {
+                    trace.push(TraceStep::Granted {
+                        subject: String::from(s.as_str()),
+                        source: GrantSource::Role(role),
+                    });
+                }
+            }
+        }
+        let granted = trace
+            .steps()
+            .iter()
+            .any(|step| matches!(step, TraceStep::Granted { .. }));
+        if !granted {
+            trace.push(TraceStep::MissingAction);
+        }
+
+        // Deny rules also block the actions implying a denied action
+        let implied = self.manager.expand(
+            &self
+                .manager
+                .perm_from_actions(HashSet::from([String::from(action_str)])),
+        );
+        for s in &subjects {
+            if self
+                .denies
+                .get(*s)
+                .is_some_and(|denied| implied.actions().any(|action| denied.has_action(action)))
+            {
+                trace.push(TraceStep::DeniedBy {
+                    subject: String::from(s.as_str()),
+                });
+            }
+        }
+
+        if !self
+            .acl
+            .effective_on(&String::from(resource))
+            .contains_action(action_str)
+        {
+            trace.push(TraceStep::NotAllowedOnResource {
+                resource: String::from(resource),
+            });
+        }
+
+        (decision, trace)
+    }
+
+    /// Applies the [`PolicyChange`](crate::PolicyChange)s provided in order to a copy of the [`Authorizer`](crate::Authorizer) and
+    /// returns the checks of the queries, given as `(subject, action)` pairs, whose outcome would change.  
+    /// The [`Authorizer`](crate::Authorizer) is not modified: grant hooks are not called and simulated checks are not audited.
+    ///
+    /// # Examples:
+    ///
+    /// ```
+    /// use simple_perm_manager::{Authorizer, OutcomeFlip, PermissionManager, PolicyChange};
+    ///
+    /// let mut auth = Authorizer::new(PermissionManager::from_json(r#"{"post": {"view": true, "delete": true}}"#));
+    /// let admin = auth.manager().perm_from_json(r#"{"post": {"view": true, "delete": true}}"#);
+    /// auth.roles_mut().add_role("admin", admin);
+    ///
+    /// let flips = auth.simulate(
+    ///     &[PolicyChange::Assign { subject: String::from("alice"), role: String::from("admin") }],
+    ///     &[("alice", "post:view"), ("alice", "post:delete"), ("bob", "post:view")],
+    /// );
+    ///
+    /// assert_eq!(flips.len(), 2);
+    /// assert_eq!(flips[1].to_string(), "alice post:delete: denied -> allowed");
+    /// assert!(!auth.check("alice", "post:delete"));
+    /// ```
+    ///
+    /// # Panics:
+    ///
+    /// - Panics in the same cases that the methods applying each [`PolicyChange`](crate::PolicyChange) do.
+    /// - Panics if the group nesting of any queried subject is deeper than the maximum resolution depth of the [`GrantStore`](crate::GrantStore).
+    pub fn simulate(&self, changes: &[PolicyChange], queries: &[(&str, &str)]) -> Vec<OutcomeFlip> {
+        let mut simulated = Authorizer {
+            manager: self.manager.clone(),
+            grants: self.grants.detached(),
+            roles: self.roles.clone(),
+            acl: self.acl.clone(),
+            denies: self.denies.clone(),
+            cache: RwLock::new(HashMap::new()),
+            audit_sinks: Vec::new(),
+        };
+        for change in changes {
+            match change {
+                PolicyChange::Grant { subject, perm } => {
+                    simulated.grants.grant(subject.clone(), perm)
+                }
+                PolicyChange::Revoke { subject, perm } => simulated.grants.revoke(subject, perm),
+                PolicyChange::AddMember { member, group } => {
+                    simulated.grants.add_member(member.clone(), group.clone());
+                }
+                PolicyChange::RemoveMember { member, group } => {
+                    simulated.grants.remove_member(member, group);
+                }
+                PolicyChange::AddRole { name, perm } => {
+                    simulated.roles.add_role(name, perm.clone())
+                }
+                PolicyChange::RemoveRole { name } => {
+                    simulated.roles.remove_role(name);
+                }
+                PolicyChange::Assign { subject, role } => {
+                    simulated.roles.assign(subject, role);
+                }
+                PolicyChange::Unassign { subject, role } => {
+                    simulated.roles.unassign(subject, role);
+                }
+                PolicyChange::Deny { subject, perm } => simulated.deny(subject, perm),
+                PolicyChange::Undeny { subject, perm } => simulated.undeny(subject, perm),
+            }
+        }
+
+        queries
+            .iter()
+            .filter_map(|(subject, action_str)| {
+                let before = self.effective(subject).contains_action(action_str);
+                let after = simulated.effective(subject).contains_action(action_str);
+                (before != after).then(|| OutcomeFlip {
+                    subject: String::from(*subject),
+                    action: String::from(*action_str),
+                    before,
+                    after,
+                })
+            })
+            .collect()
+    }
+
+    /// Returns an [`AccessReview`](crate::AccessReview) of every subject with grants, group memberships, roles or deny rules:
+    /// its effective actions, the actions each of its [`Role`](crate::Role)s provides and its temporary grants that have not expired.
+    ///
+    /// # Panics:
+    ///
+    /// Panics if the group nesting of any subject is deeper than the maximum resolution depth of the [`GrantStore`](crate::GrantStore).
+    pub fn access_review(&self) -> AccessReview {
+        let now = SystemTime::now();
+        let subjects: HashSet<&String> = self
+            .grants
+            .known_subjects()
+            .into_iter()
+            .chain(self.roles.assigned_subjects())
+            .chain(self.denies.keys())
+            .collect();
+
+        AccessReview::new(
+            subjects
+                .into_iter()
+                .map(|subject| {
+                    let effective = self.effective(subject);
+                    let subjects: Vec<&String> = self
+                        .grants
+                        .groups(subject)
+                        .into_iter()
+                        .chain([subject])
+                        .collect();
+
+                    let mut roles: Vec<(String, Vec<String>)> = subjects
+                        .iter()
+                        .flat_map(|s| self.roles.subject_roles(s))
+                        .collect::<HashSet<String>>()
+                        .into_iter()
+                        .map(|role| {
+                            let mut provided = self.roles.effective_role_perm(&role);
+                            provided.intersect_with(&effective);
+                            (role, sorted(provided.actions()))
+                        })
+                        .collect();
+                    roles.sort();
+
+                    let mut expirations: Vec<(Vec<String>, SystemTime)> = subjects
+                        .iter()
+                        .flat_map(|s| self.grants.timed_grants_of(s))
+                        .filter(|perm| !perm.is_expired(now))
+                        .filter_map(|perm| {
+                            perm.valid_until()
+                                .map(|until| (sorted(perm.perm().actions()), until))
+                        })
+                        .collect();
+                    expirations.sort_by(|a, b| a.1.cmp(&b.1).then_with(|| a.0.cmp(&b.0)));
+
+                    SubjectReview {
+                        subject: subject.clone(),
+                        actions: sorted(effective.actions()),
+                        roles,
+                        expirations,
+                    }
+                })
+                .collect(),
+        )
+    }
+
+    fn decide_on(&self, perm: &Permission, resource: &str, action_str: &str) -> Decision {
+        if !self.manager.get_universe().contains_action(action_str) {
+            return Decision::NotApplicable;
+        }
+
+        if perm.contains_action(action_str)
+            && self
+                .acl
+                .effective_on(&String::from(resource))
+                .contains_action(action_str)
+        {
+            Decision::Allow
+        } else {
+            Decision::Deny
+        }
+    }
+
+    fn compute_effective(&self, subject: &str, at: SystemTime, context: &Context) -> Permission {
+        let subject = String::from(subject);
+        let subjects: Vec<&String> = self
+            .grants
+            .groups(&subject)
+            .into_iter()
+            .chain([&subject])
+            .collect();
+
+        let mut allowed = self.grants.effective_perm_in(&subject, at, context);
+        for s in &subjects {
+            allowed.union_with(&self.roles.subject_perm(s));
+        }
+        let allowed = self.manager.expand(&allowed);
+        let denied = self
+            .manager
+            .union_all(subjects.iter().filter_map(|s| self.denies.get(*s)));
+
+        // Actions implying a denied action are denied too, otherwise the denied action would still be implied.
+        let actions: HashSet<String> = allowed
+            .actions()
+            .filter(|action| {
+                !self
+                    .manager
+                    .perm_from_actions(HashSet::from([String::from(*action)]))
+                    .actions()
+                    .any(|implied| denied.has_action(implied))
+            })
+            .map(String::from)
+            .collect();
+
+        self.manager.perm_from_actions(actions)
+    }
+
+    // The cache only holds computed values, so a poisoned lock can be recovered.
+    fn cache_read(&self) -> RwLockReadGuard<'_, HashMap<String, (Permission, Option<SystemTime>)>> {
+        self.cache.read().unwrap_or_else(|error| error.into_inner())
+    }
+
+    fn cache_write(
+        &self,
+    ) -> RwLockWriteGuard<'_, HashMap<String, (Permission, Option<SystemTime>)>> {
+        self.cache
+            .write()
+            .unwrap_or_else(|error| error.into_inner())
+    }
+}
+
+// Returns the actions provided sorted.
+fn sorted<I, S>(actions: I) -> Vec<String>
+where
+    I: IntoIterator<Item = S>,
+    S: AsRef<str>,
+{
+    let mut actions: Vec<String> = actions
+        .into_iter()
+        .map(|action| String::from(action.as_ref()))
+        .collect();
+    actions.sort();
+    actions
+}
+
+impl fmt::Debug for Authorizer {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Authorizer")
+            .field("manager", &self.manager)
+            .field("grants", &self.grants)
+            .field("roles", &self.roles)
+            .field("acl", &self.acl)
+            .field("denies", &self.denies)
+            .field("audit_sinks", &self.audit_sinks.len())
+            .finish_non_exhaustive()
+    }
+}
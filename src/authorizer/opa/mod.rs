@@ -0,0 +1,120 @@
+use super::{sorted, Authorizer};
+use serde_json::{json, Map, Value};
+use std::collections::HashSet;
+
+//// TESTS ////
+#[cfg(test)]
+mod tests;
+
+// Reference Rego policy enforcing the exported data document
+const OPA_POLICY: &str = include_str!("policy.rego");
+// Key of the data document under which the model is exported, i.e. `data.simple_perm_manager` in OPA
+const OPA_DATA_KEY: &str = "simple_perm_manager";
+
+// Returns a JSON object mapping each key with values to its sorted values
+fn sorted_map<I>(entries: I) -> Value
+where
+    I: IntoIterator<Item = (String, HashSet<String>)>,
+{
+    Value::Object(
+        entries
+            .into_iter()
+            .filter(|(_, values)| !values.is_empty())
+            .map(|(key, values)| (key, json!(sorted(&values))))
+            .collect::<Map<String, Value>>(),
+    )
+}
+
+impl Authorizer {
+    /// Returns the grants, group memberships, roles, deny rules, resource ACLs and universe of the [`Authorizer`](crate::Authorizer)
+    /// as an [OPA](https://www.openpolicyagent.org) data JSON document, so gateways running OPA can enforce the same model with the
+    /// reference policy from [`opa_policy`](crate::Authorizer::opa_policy()).
+    ///
+    /// The model is under the `simple_perm_manager` key, so it is loaded at `data.simple_perm_manager`, with:
+    /// - `universe`: the universe actions.
+    /// - `implications`: the actions implied by each action, transitively.
+    /// - `grants`: the permanent direct grants of each subject. Temporary and conditional grants are not exported.
+    /// - `groups`: the groups each subject is a direct member of.
+    /// - `roles`: the effective actions of each role, including inherited ones.
+    /// - `assignments`: the roles assigned to each subject.
+    /// - `denies`: the actions denied to each subject.
+    /// - `acl`: the actions allowed on each resource, including the ones inherited from its ancestors.
+    ///
+    /// Actions and lists are sorted, so the same model always produces the same document.
+    ///
+    /// # Examples:
+    ///
+    /// ```
+    /// use serde_json::Value;
+    /// use simple_perm_manager::{Authorizer, PermissionManager};
+    ///
+    /// let mut auth = Authorizer::new(PermissionManager::from_json(r#"{"post": {"view": true, "edit": true}}"#));
+    /// let view = auth.manager().perm_from_json(r#"{"post": {"view": true}}"#);
+    /// auth.grants_mut().grant(String::from("alice"), &view);
+    ///
+    /// let data: Value = serde_json::from_str(&auth.to_opa_data()).unwrap();
+    /// assert_eq!(data["simple_perm_manager"]["grants"]["alice"], serde_json::json!(["post:view"]));
+    /// assert_eq!(data["simple_perm_manager"]["universe"], serde_json::json!(["post:edit", "post:view"]));
+    /// ```
+    pub fn to_opa_data(&self) -> String {
+        let manager = &self.manager;
+        let universe = manager.to_actions();
+
+        let implications = universe.iter().map(|action| {
+            let mut implied = manager
+                .perm_from_actions(HashSet::from([action.clone()]))
+                .get_actions()
+                .into_owned();
+            implied.remove(action);
+            (action.clone(), implied)
+        });
+        let grants = self
+            .grants
+            .known_subjects()
+            .into_iter()
+            .filter_map(|subject| {
+                self.grants
+                    .grant_of(subject)
+                    .map(|perm| (subject.clone(), perm.get_actions().into_owned()))
+            });
+        let roles = self.roles.roles().map(|role| {
+            let perm = self.roles.effective_role_perm(role.name());
+            (String::from(role.name()), perm.get_actions().into_owned())
+        });
+        let assignments = self
+            .roles
+            .assigned_subjects()
+            .map(|subject| (subject.clone(), self.roles.subject_roles(subject)));
+        let denies = self
+            .denies
+            .iter()
+            .map(|(subject, perm)| (subject.clone(), perm.get_actions().into_owned()));
+        let acl = self.acl.resources().map(|resource| {
+            let perm = self.acl.effective_on(resource);
+            (resource.clone(), perm.get_actions().into_owned())
+        });
+
+        let model = json!({
+            "universe": sorted(&universe),
+            "implications": sorted_map(implications),
+            "grants": sorted_map(grants),
+            "groups": sorted_map(self.grants.direct_memberships().clone()),
+            "roles": sorted_map(roles),
+            "assignments": sorted_map(assignments),
+            "denies": sorted_map(denies),
+            "acl": sorted_map(acl),
+        });
+
+        json!({ OPA_DATA_KEY: model }).to_string()
+    }
+
+    /// Returns a reference [Rego](https://www.openpolicyagent.org/docs/latest/policy-language/) policy for the document of
+    /// [`to_opa_data`](crate::Authorizer::to_opa_data()), in the `simple_perm_manager.authz` package.
+    ///
+    /// Its `allow` rule mirrors [`check`](crate::Authorizer::check()) for an input with a `subject` and an `action`, and
+    /// [`evaluate`](crate::Authorizer::evaluate()) when the input also has a `resource`. Its `effective` set holds the effective
+    /// actions of the subject.
+    pub fn opa_policy() -> &'static str {
+        OPA_POLICY
+    }
+}
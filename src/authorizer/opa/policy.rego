@@ -0,0 +1,60 @@
+# Reference policy enforcing the model exported by Authorizer::to_opa_data.
+# Input: {"subject": "alice", "action": "post:edit"} and optionally "resource": "readme".
+package simple_perm_manager.authz
+
+import rego.v1
+
+model := data.simple_perm_manager
+
+# The subject and every group it belongs to, directly or through nested groups
+subjects := graph.reachable(model.groups, {input.subject})
+
+granted contains action if {
+	some subject in subjects
+	some action in model.grants[subject]
+}
+
+granted contains action if {
+	some subject in subjects
+	some role in model.assignments[subject]
+	some action in model.roles[role]
+}
+
+# Granted actions and the actions they imply
+allowed contains action if {
+	some action in granted
+}
+
+allowed contains implied if {
+	some action in granted
+	some implied in model.implications[action]
+}
+
+denied contains action if {
+	some subject in subjects
+	some action in model.denies[subject]
+}
+
+# Actions implying a denied action are denied too
+implies_denied(action) if {
+	some implied in model.implications[action]
+	implied in denied
+}
+
+effective contains action if {
+	some action in allowed
+	not action in denied
+	not implies_denied(action)
+}
+
+default allow := false
+
+allow if {
+	not input.resource
+	input.action in effective
+}
+
+allow if {
+	input.action in effective
+	input.action in model.acl[input.resource]
+}
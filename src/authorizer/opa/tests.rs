@@ -0,0 +1,116 @@
+use super::*;
+use crate::PermissionManager;
+
+#[test]
+fn sorted_map_test() {
+    assert_eq!(
+        sorted_map([
+            (
+                String::from("alice"),
+                HashSet::from([String::from("b"), String::from("a")])
+            ),
+            (String::from("bob"), HashSet::new()),
+        ]),
+        json!({"alice": ["a", "b"]})
+    );
+}
+
+#[test]
+fn to_opa_data_test() {
+    let mut manager = PermissionManager::from_json(
+        r#"{"post": {"view": true, "edit": true, "delete": true}, "admin": true}"#,
+    );
+    manager.add_implication("post:delete", "post:edit");
+    manager.add_implication("post:edit", "post:view");
+    let mut auth = Authorizer::new(manager);
+
+    let view = auth.manager().perm_from_json(r#"{"post": {"view": true}}"#);
+    auth.grants_mut().grant(String::from("alice"), &view);
+    auth.grants_mut()
+        .add_member(String::from("alice"), String::from("backend"));
+    let editor = auth.manager().perm_from_json(r#"{"post": {"edit": true}}"#);
+    auth.roles_mut().add_role("editor", editor);
+    let admin = auth.manager().perm_from_json(r#"{"admin": true}"#);
+    auth.roles_mut().add_role("admin", admin);
+    auth.roles_mut().inherit("admin", "editor");
+    auth.roles_mut().assign("backend", "editor");
+    let delete = auth
+        .manager()
+        .perm_from_json(r#"{"post": {"delete": true}}"#);
+    auth.deny("alice", &delete);
+    let acl_view = auth.manager().perm_from_json(r#"{"post": {"view": true}}"#);
+    auth.acl_mut().grant_on(String::from("docs"), &acl_view);
+    auth.acl_mut()
+        .set_parent(String::from("readme"), String::from("docs"));
+    let acl_edit = auth.manager().perm_from_json(r#"{"post": {"edit": true}}"#);
+    auth.acl_mut().grant_on(String::from("readme"), &acl_edit);
+
+    let data: Value = serde_json::from_str(&auth.to_opa_data()).unwrap();
+    assert_eq!(
+        data,
+        json!({
+            "simple_perm_manager": {
+                "universe": ["admin", "post:delete", "post:edit", "post:view"],
+                "implications": {
+                    "post:delete": ["post:edit", "post:view"],
+                    "post:edit": ["post:view"]
+                },
+                "grants": {"alice": ["post:view"]},
+                "groups": {"alice": ["backend"]},
+                "roles": {
+                    "admin": ["admin", "post:edit", "post:view"],
+                    "editor": ["post:edit", "post:view"]
+                },
+                "assignments": {"backend": ["editor"]},
+                "denies": {"alice": ["post:delete", "post:edit", "post:view"]},
+                "acl": {
+                    "docs": ["post:view"],
+                    "readme": ["post:edit", "post:view"]
+                }
+            }
+        })
+    );
+}
+
+#[test]
+fn to_opa_data_empty_test() {
+    let auth = Authorizer::new(PermissionManager::from_json(r#"{"view": true}"#));
+
+    assert_eq!(
+        serde_json::from_str::<Value>(&auth.to_opa_data()).unwrap(),
+        json!({
+            "simple_perm_manager": {
+                "universe": ["view"],
+                "implications": {},
+                "grants": {},
+                "groups": {},
+                "roles": {},
+                "assignments": {},
+                "denies": {},
+                "acl": {}
+            }
+        })
+    );
+}
+
+#[test]
+fn opa_policy_test() {
+    let policy = Authorizer::opa_policy();
+
+    assert_eq!(policy.contains("package simple_perm_manager.authz"), true);
+    assert_eq!(
+        policy.contains(&format!("model := data.{}", OPA_DATA_KEY)),
+        true
+    );
+    for key in [
+        "groups",
+        "grants",
+        "assignments",
+        "roles",
+        "implications",
+        "denies",
+        "acl",
+    ] {
+        assert_eq!(policy.contains(&format!("model.{}", key)), true);
+    }
+}
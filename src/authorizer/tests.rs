@@ -0,0 +1,603 @@
+use super::*;
+use crate::ExpiringPermission;
+
+fn test_authorizer() -> Authorizer {
+    let mut manager = PermissionManager::from_json(
+        r#"{"post": {"view": true, "edit": true, "delete": true}, "comment": {"view": true, "edit": true}}"#,
+    );
+    manager.add_implication("post:edit", "post:view");
+    Authorizer::new(manager)
+}
+
+#[test]
+fn new_test() {
+    let manager = Arc::new(PermissionManager::from_json(r#"{"view": true}"#));
+    let auth = Authorizer::new(manager.clone());
+
+    assert_eq!(auth.manager().id(), manager.id());
+    assert_eq!(auth.grants().manager().id(), manager.id());
+    assert_eq!(auth.roles().manager().id(), manager.id());
+}
+
+#[test]
+fn deny_test() {
+    let mut auth = test_authorizer();
+    auth.deny(
+        "alice",
+        &auth
+            .manager()
+            .perm_from_json(r#"{"comment": {"edit": true}}"#),
+    );
+    auth.deny(
+        "alice",
+        &auth
+            .manager()
+            .perm_from_json(r#"{"post": {"delete": true}}"#),
+    );
+
+    assert_eq!(
+        *auth.denied("alice").get_actions(),
+        HashSet::from([String::from("comment:edit"), String::from("post:delete")])
+    );
+    assert_eq!(auth.denied("bob").get_actions().is_empty(), true);
+
+    match std::panic::catch_unwind(move || {
+        auth.deny(
+            "alice",
+            &Permission::from_json(r#"{"post": {"view": true}}"#),
+        );
+    }) {
+        Ok(_) => panic!("denying a permission of another manager should have panicked"),
+        Err(_) => (),
+    }
+}
+
+#[test]
+fn undeny_test() {
+    let mut auth = test_authorizer();
+    let delete = auth
+        .manager()
+        .perm_from_json(r#"{"post": {"delete": true}}"#);
+    auth.deny("alice", &delete);
+    auth.undeny("alice", &delete);
+
+    assert_eq!(auth.denied("alice").get_actions().is_empty(), true);
+
+    match std::panic::catch_unwind(move || {
+        auth.undeny("alice", &Permission::from_json("{}"));
+    }) {
+        Ok(_) => panic!("undenying a permission of another manager should have panicked"),
+        Err(_) => (),
+    }
+}
+
+#[test]
+fn effective_test() {
+    let mut auth = test_authorizer();
+    let commenter = auth
+        .manager()
+        .perm_from_json(r#"{"comment": {"view": true, "edit": true}}"#);
+    auth.roles_mut().add_role("commenter", commenter);
+    auth.roles_mut().assign("engineering", "commenter");
+
+    let edit = auth.manager().perm_from_json(r#"{"post": {"edit": true}}"#);
+    auth.grants_mut().grant(String::from("backend"), &edit);
+    let delete = auth
+        .manager()
+        .perm_from_json(r#"{"post": {"delete": true}}"#);
+    auth.grants_mut().grant(String::from("alice"), &delete);
+
+    auth.grants_mut()
+        .add_member(String::from("alice"), String::from("backend"));
+    auth.grants_mut()
+        .add_member(String::from("backend"), String::from("engineering"));
+    auth.deny(
+        "engineering",
+        &auth
+            .manager()
+            .perm_from_json(r#"{"comment": {"edit": true}}"#),
+    );
+
+    assert_eq!(
+        *auth.effective("alice").get_actions(),
+        HashSet::from([
+            String::from("post:view"),
+            String::from("post:edit"),
+            String::from("post:delete"),
+            String::from("comment:view"),
+        ])
+    );
+    assert_eq!(
+        *auth.effective("engineering").get_actions(),
+        HashSet::from([String::from("comment:view")])
+    );
+    assert_eq!(auth.effective("nobody").get_actions().is_empty(), true);
+    assert_eq!(auth.manager().validate_perm(&auth.effective("alice")), true);
+}
+
+#[test]
+fn check_test() {
+    let mut auth = test_authorizer();
+    let edit = auth.manager().perm_from_json(r#"{"post": {"edit": true}}"#);
+    auth.grants_mut().grant(String::from("alice"), &edit);
+
+    assert_eq!(auth.check("alice", "post:edit"), true);
+    assert_eq!(auth.check("alice", "post:view"), true);
+    assert_eq!(auth.check("bob", "post:edit"), false);
+
+    // Denying an implied action also denies the actions implying it
+    auth.deny(
+        "alice",
+        &auth.manager().perm_from_json(r#"{"post": {"view": true}}"#),
+    );
+    assert_eq!(auth.check("alice", "post:edit"), false);
+    assert_eq!(auth.check("alice", "post:view"), false);
+}
+
+#[test]
+fn cache_test() {
+    let mut auth = test_authorizer();
+    let edit = auth.manager().perm_from_json(r#"{"post": {"edit": true}}"#);
+    auth.grants_mut().grant(String::from("alice"), &edit);
+    assert_eq!(auth.check("alice", "post:edit"), true);
+    assert_eq!(auth.cache_read().contains_key("alice"), true);
+
+    // Mutations clear the cache
+    auth.deny("alice", &edit);
+    assert_eq!(auth.cache_read().is_empty(), true);
+    assert_eq!(auth.check("alice", "post:edit"), false);
+
+    auth.undeny("alice", &edit);
+    assert_eq!(auth.check("alice", "post:edit"), true);
+
+    let delete = auth
+        .manager()
+        .perm_from_json(r#"{"post": {"delete": true}}"#);
+    auth.roles_mut().add_role("deleter", delete);
+    auth.roles_mut().assign("alice", "deleter");
+    assert_eq!(auth.check("alice", "post:delete"), true);
+
+    auth.grants_mut().revoke_all(&String::from("alice"));
+    assert_eq!(auth.check("alice", "post:edit"), false);
+}
+
+#[test]
+fn invalidate_test() {
+    let auth = test_authorizer();
+    auth.effective("alice");
+    auth.effective("bob");
+
+    auth.invalidate("alice");
+    assert_eq!(auth.cache_read().contains_key("alice"), false);
+    assert_eq!(auth.cache_read().contains_key("bob"), true);
+
+    auth.invalidate_all();
+    assert_eq!(auth.cache_read().is_empty(), true);
+}
+
+#[test]
+fn expiring_cache_test() {
+    let mut auth = test_authorizer();
+    let now = SystemTime::now();
+    let edit = auth
+        .manager()
+        .perm_from_json(r#"{"comment": {"edit": true}}"#);
+    auth.grants_mut().grant_expiring(
+        String::from("alice"),
+        ExpiringPermission::until(edit, now + std::time::Duration::from_millis(50)),
+    );
+
+    assert_eq!(auth.check("alice", "comment:edit"), true);
+    std::thread::sleep(std::time::Duration::from_millis(60));
+    assert_eq!(auth.check("alice", "comment:edit"), false);
+}
+
+#[test]
+fn time_window_cache_test() {
+    let mut auth = test_authorizer();
+    let now = SystemTime::now();
+    let edit = auth
+        .manager()
+        .perm_from_json(r#"{"comment": {"edit": true}}"#);
+    auth.grants_mut().grant_when(
+        String::from("alice"),
+        &edit,
+        crate::ConditionSpec::TimeWindow {
+            from: None,
+            until: Some(now + std::time::Duration::from_millis(50)),
+        },
+    );
+
+    assert_eq!(auth.check("alice", "comment:edit"), true);
+    std::thread::sleep(std::time::Duration::from_millis(60));
+    assert_eq!(auth.check("alice", "comment:edit"), false);
+}
+
+#[test]
+fn check_with_test() {
+    let mut auth = test_authorizer();
+    let delete = auth
+        .manager()
+        .perm_from_json(r#"{"post": {"delete": true}}"#);
+    auth.grants_mut()
+        .grant_if(String::from("alice"), &delete, |context: &Context| {
+            context.get("state") == Some(&"draft".into())
+        });
+    let draft = Context::new().with("state", "draft");
+
+    assert_eq!(auth.check_with("alice", "post:delete", &draft), true);
+    assert_eq!(auth.check("alice", "post:delete"), false);
+
+    auth.deny("alice", &delete);
+    assert_eq!(auth.check_with("alice", "post:delete", &draft), false);
+}
+
+#[test]
+fn evaluate_test() {
+    let mut auth = test_authorizer();
+    let editor = auth
+        .manager()
+        .perm_from_json(r#"{"post": {"edit": true}, "comment": {"edit": true}}"#);
+    auth.roles_mut().add_role("editor", editor);
+    auth.roles_mut().assign("alice", "editor");
+    let delete = auth
+        .manager()
+        .perm_from_json(r#"{"post": {"delete": true}}"#);
+    auth.grants_mut()
+        .grant_if(String::from("alice"), &delete, |context: &Context| {
+            context.get("owner") == Some(&true.into())
+        });
+
+    let all = auth.manager().get_universe();
+    auth.acl_mut().grant_on(String::from("blog"), &all);
+    auth.acl_mut()
+        .set_parent(String::from("post-1"), String::from("blog"));
+    let view = auth.manager().perm_from_json(r#"{"post": {"view": true}}"#);
+    auth.acl_mut().grant_on(String::from("archive"), &view);
+
+    assert_eq!(
+        auth.evaluate("alice", "post-1", "post:edit"),
+        Decision::Allow
+    );
+    assert_eq!(
+        auth.evaluate("alice", "post-1", "post:view"),
+        Decision::Allow
+    );
+    assert_eq!(
+        auth.evaluate("alice", "archive", "post:edit"),
+        Decision::Deny
+    );
+    assert_eq!(
+        auth.evaluate("alice", "archive", "post:view"),
+        Decision::Allow
+    );
+    assert_eq!(
+        auth.evaluate("alice", "unknown", "post:view"),
+        Decision::Deny
+    );
+    assert_eq!(auth.evaluate("bob", "post-1", "post:view"), Decision::Deny);
+    assert_eq!(
+        auth.evaluate("alice", "post-1", "post:share"),
+        Decision::NotApplicable
+    );
+
+    let owner = Context::new().with("owner", true);
+    assert_eq!(
+        auth.evaluate("alice", "post-1", "post:delete"),
+        Decision::Deny
+    );
+    assert_eq!(
+        auth.evaluate_with("alice", "post-1", "post:delete", &owner),
+        Decision::Allow
+    );
+    assert_eq!(
+        auth.evaluate_with("alice", "archive", "post:delete", &owner),
+        Decision::Deny
+    );
+}
+
+#[test]
+fn decide_explained_test() {
+    let mut auth = test_authorizer();
+    let alice = String::from("alice");
+    let backend = String::from("backend");
+    let edit = auth.manager().perm_from_json(r#"{"post": {"edit": true}}"#);
+    auth.roles_mut().add_role("editor", edit.clone());
+    auth.roles_mut().assign("backend", "editor");
+    auth.grants_mut().add_member(alice.clone(), backend.clone());
+    auth.grants_mut().grant(alice.clone(), &edit);
+    auth.grants_mut()
+        .grant_if(alice.clone(), &edit, |_: &Context| false);
+    auth.grants_mut().grant_expiring(
+        alice.clone(),
+        ExpiringPermission::until(
+            edit.clone(),
+            std::time::UNIX_EPOCH + std::time::Duration::from_secs(1),
+        ),
+    );
+    let all = auth.manager().get_universe();
+    auth.acl_mut().grant_on(String::from("blog"), &all);
+
+    let (decision, trace) = auth.decide_explained("alice", "blog", "post:view", &Context::new());
+    assert_eq!(decision, Decision::Allow);
+    assert_eq!(
+        trace.steps(),
+        &[
+            TraceStep::Granted {
+                subject: alice.clone(),
+                source: GrantSource::Direct
+            },
+            TraceStep::InactiveGrant {
+                subject: alice.clone()
+            },
+            TraceStep::ConditionFailed {
+                subject: alice.clone()
+            },
+            TraceStep::Granted {
+                subject: backend.clone(),
+                source: GrantSource::Role(String::from("editor"))
+            },
+        ]
+    );
+
+    auth.deny(
+        "backend",
+        &auth.manager().perm_from_json(r#"{"post": {"view": true}}"#),
+    );
+    let (decision, trace) = auth.decide_explained("alice", "archive", "post:edit", &Context::new());
+    assert_eq!(decision, Decision::Deny);
+    assert_eq!(
+        trace.steps()[trace.steps().len() - 2..],
+        [
+            TraceStep::DeniedBy {
+                subject: backend.clone()
+            },
+            TraceStep::NotAllowedOnResource {
+                resource: String::from("archive")
+            },
+        ]
+    );
+
+    let (decision, trace) = auth.decide_explained("bob", "blog", "post:edit", &Context::new());
+    assert_eq!(decision, Decision::Deny);
+    assert_eq!(trace.steps(), &[TraceStep::MissingAction]);
+
+    let (decision, trace) = auth.decide_explained("bob", "blog", "post:share", &Context::new());
+    assert_eq!(decision, Decision::NotApplicable);
+    assert_eq!(trace.steps(), &[TraceStep::UnknownAction]);
+}
+
+#[test]
+fn evaluate_with_obligations_test() {
+    let mut manager = PermissionManager::from_json(r#"{"post": {"view": true, "delete": true}}"#);
+    manager.add_obligation("post:delete", "require_mfa");
+    let mut auth = Authorizer::new(manager);
+    let all = auth.manager().get_universe();
+    auth.grants_mut().grant(String::from("alice"), &all);
+    auth.acl_mut().grant_on(String::from("blog"), &all);
+
+    assert_eq!(
+        auth.evaluate_with_obligations("alice", "blog", "post:delete", &Context::new()),
+        (Decision::Allow, vec![String::from("require_mfa")])
+    );
+    assert_eq!(
+        auth.evaluate_with_obligations("alice", "blog", "post:view", &Context::new()),
+        (Decision::Allow, vec![])
+    );
+    assert_eq!(
+        auth.evaluate_with_obligations("bob", "blog", "post:delete", &Context::new()),
+        (Decision::Deny, vec![])
+    );
+}
+
+#[test]
+fn simulate_test() {
+    let mut auth = test_authorizer();
+    let editor = auth.manager().perm_from_json(r#"{"post": {"edit": true}}"#);
+    auth.roles_mut().add_role("editor", editor);
+    auth.roles_mut().assign("backend", "editor");
+    let comment = auth
+        .manager()
+        .perm_from_json(r#"{"comment": {"view": true}}"#);
+    auth.grants_mut().grant(String::from("bob"), &comment);
+    let events = Arc::new(std::sync::Mutex::new(Vec::new()));
+    let sink_events = events.clone();
+    auth.add_audit_sink(move |event: DecisionEvent| sink_events.lock().unwrap().push(event));
+
+    let queries = [
+        ("alice", "post:edit"),
+        ("alice", "post:view"),
+        ("bob", "comment:view"),
+        ("bob", "post:edit"),
+    ];
+    let flips = auth.simulate(
+        &[
+            PolicyChange::AddMember {
+                member: String::from("alice"),
+                group: String::from("backend"),
+            },
+            PolicyChange::Revoke {
+                subject: String::from("bob"),
+                perm: comment,
+            },
+            PolicyChange::Deny {
+                subject: String::from("alice"),
+                perm: auth.manager().perm_from_json(r#"{"post": {"view": true}}"#),
+            },
+        ],
+        &queries,
+    );
+
+    // Denying post:view also denies post:edit, which implies it
+    assert_eq!(
+        flips,
+        vec![OutcomeFlip {
+            subject: String::from("bob"),
+            action: String::from("comment:view"),
+            before: true,
+            after: false,
+        }]
+    );
+    let flips = auth.simulate(
+        &[PolicyChange::AddMember {
+            member: String::from("alice"),
+            group: String::from("backend"),
+        }],
+        &queries,
+    );
+    assert_eq!(
+        flips
+            .iter()
+            .map(OutcomeFlip::to_string)
+            .collect::<Vec<String>>(),
+        vec![
+            String::from("alice post:edit: denied -> allowed"),
+            String::from("alice post:view: denied -> allowed"),
+        ]
+    );
+
+    // The authorizer is not modified nor audited
+    assert_eq!(auth.check("alice", "post:edit"), false);
+    assert_eq!(auth.check("bob", "comment:view"), true);
+    assert_eq!(events.lock().unwrap().len(), 2);
+    assert_eq!(auth.simulate(&[], &queries).is_empty(), true);
+}
+
+#[test]
+fn access_review_test() {
+    let mut auth = test_authorizer();
+    let now = SystemTime::now();
+    let editor = auth.manager().perm_from_json(r#"{"post": {"edit": true}}"#);
+    auth.roles_mut().add_role("editor", editor);
+    auth.roles_mut().assign("backend", "editor");
+    auth.grants_mut()
+        .add_member(String::from("alice"), String::from("backend"));
+    let comment = auth
+        .manager()
+        .perm_from_json(r#"{"comment": {"view": true}}"#);
+    let until = now + std::time::Duration::from_secs(3600);
+    auth.grants_mut().grant_expiring(
+        String::from("alice"),
+        ExpiringPermission::until(comment.clone(), until),
+    );
+    auth.grants_mut().grant_expiring(
+        String::from("alice"),
+        ExpiringPermission::until(comment, now - std::time::Duration::from_secs(1)),
+    );
+    auth.deny(
+        "carol",
+        &auth
+            .manager()
+            .perm_from_json(r#"{"post": {"delete": true}}"#),
+    );
+
+    let review = auth.access_review();
+    assert_eq!(
+        review
+            .subjects()
+            .iter()
+            .map(|review| review.subject.as_str())
+            .collect::<Vec<&str>>(),
+        vec!["alice", "backend", "carol"]
+    );
+
+    // Roles of groups are reported, expired grants are not
+    let alice = review.subject("alice").unwrap();
+    assert_eq!(
+        alice.actions,
+        vec![
+            String::from("comment:view"),
+            String::from("post:edit"),
+            String::from("post:view")
+        ]
+    );
+    assert_eq!(
+        alice.roles,
+        vec![(
+            String::from("editor"),
+            vec![String::from("post:edit"), String::from("post:view")]
+        )]
+    );
+    assert_eq!(
+        alice.expirations,
+        vec![(vec![String::from("comment:view")], until)]
+    );
+    assert_eq!(review.subject("carol").unwrap().actions.is_empty(), true);
+
+    // Denied actions are not provided by roles
+    auth.deny(
+        "alice",
+        &auth.manager().perm_from_json(r#"{"post": {"view": true}}"#),
+    );
+    assert_eq!(
+        auth.access_review().subject("alice").unwrap().roles,
+        vec![(String::from("editor"), vec![])]
+    );
+}
+
+#[test]
+fn audit_sink_test() {
+    let mut auth = test_authorizer();
+    let events = Arc::new(std::sync::Mutex::new(Vec::new()));
+    let sink_events = events.clone();
+    auth.add_audit_sink(move |event: DecisionEvent| sink_events.lock().unwrap().push(event));
+    let view = auth.manager().perm_from_json(r#"{"post": {"view": true}}"#);
+    auth.grants_mut().grant(String::from("alice"), &view);
+    auth.acl_mut().grant_on(String::from("blog"), &view);
+
+    auth.check("alice", "post:view");
+    auth.check_with("bob", "post:view", &Context::new());
+    auth.evaluate("alice", "blog", "post:view");
+    auth.evaluate_with_obligations("alice", "blog", "post:share", &Context::new());
+    auth.decide_explained("alice", "blog", "post:edit", &Context::new());
+
+    let events: Vec<(String, Option<String>, String, Decision)> = events
+        .lock()
+        .unwrap()
+        .iter()
+        .map(|event| {
+            (
+                event.subject.clone(),
+                event.resource.clone(),
+                event.action.clone(),
+                event.decision,
+            )
+        })
+        .collect();
+    let blog = Some(String::from("blog"));
+    assert_eq!(
+        events,
+        vec![
+            (
+                String::from("alice"),
+                None,
+                String::from("post:view"),
+                Decision::Allow
+            ),
+            (
+                String::from("bob"),
+                None,
+                String::from("post:view"),
+                Decision::Deny
+            ),
+            (
+                String::from("alice"),
+                blog.clone(),
+                String::from("post:view"),
+                Decision::Allow
+            ),
+            (
+                String::from("alice"),
+                blog.clone(),
+                String::from("post:share"),
+                Decision::NotApplicable
+            ),
+            (
+                String::from("alice"),
+                blog.clone(),
+                String::from("post:edit"),
+                Decision::Deny
+            ),
+        ]
+    );
+}
@@ -0,0 +1,220 @@
+use crate::permission::Permission;
+use crate::permission_manager::PermissionManager;
+use crate::telemetry;
+use crate::validation_error::ValidationError;
+use std::collections::HashSet;
+
+//// TESTS ////
+#[cfg(test)]
+mod tests;
+
+// Fact holding an action granted by the token, e.g. `right("post:edit");`
+const RIGHT_FACT: &str = "right";
+// Fact holding an action the authorizer requires, e.g. `action("post:edit");`
+const ACTION_FACT: &str = "action";
+
+// Returns a Datalog string literal, escaping quotes and backslashes
+fn literal(value: &str) -> String {
+    format!("\"{}\"", value.replace('\\', "\\\\").replace('"', "\\\""))
+}
+
+// Returns the actions sorted as Datalog string literals
+fn sorted_literals(perm: &Permission) -> Vec<String> {
+    let mut actions: Vec<&str> = perm.actions().collect();
+    actions.sort();
+
+    actions.into_iter().map(literal).collect()
+}
+
+// Parses a Datalog string literal, returning its value and the rest of the input
+fn parse_literal(input: &str) -> Result<(String, &str), String> {
+    let Some(input) = input.strip_prefix('"') else {
+        return Err(String::from("expected a string"));
+    };
+
+    let mut value = String::new();
+    let mut chars = input.char_indices();
+    while let Some((position, c)) = chars.next() {
+        match c {
+            '"' => return Ok((value, &input[position + 1..])),
+            '\\' => match chars.next() {
+                Some((_, escaped @ ('"' | '\\'))) => value.push(escaped),
+                _ => return Err(String::from("invalid escape in string")),
+            },
+            c => value.push(c),
+        }
+    }
+
+    Err(String::from("unterminated string"))
+}
+
+// Returns the values of the `right` facts of the Datalog code of a block, ignoring every other statement
+fn parse_rights(datalog: &str) -> Result<HashSet<String>, String> {
+    let mut rights: HashSet<String> = HashSet::new();
+    let mut rest = datalog;
+
+    loop {
+        rest = rest.trim_start();
+        if rest.is_empty() {
+            return Ok(rights);
+        }
+        // Comments run until the end of the line
+        if let Some(comment) = rest.strip_prefix("//") {
+            rest = comment.split_once('\n').map_or("", |(_, next)| next);
+            continue;
+        }
+
+        let arguments = rest
+            .strip_prefix(RIGHT_FACT)
+            .map(str::trim_start)
+            .and_then(|rest| rest.strip_prefix('('));
+        if let Some(arguments) = arguments {
+            let (right, after) = parse_literal(arguments.trim_start())
+                .map_err(|error| format!("{} in right fact", error))?;
+            rest = after
+                .trim_start()
+                .strip_prefix(')')
+                .map(str::trim_start)
+                .and_then(|rest| rest.strip_prefix(';'))
+                .ok_or_else(|| String::from("expected a single string in right fact"))?;
+            rights.insert(right);
+        } else {
+            rest = skip_statement(rest)?;
+        }
+    }
+}
+
+// Skips a Datalog statement up to its terminating ';', ignoring the ones inside strings
+fn skip_statement(input: &str) -> Result<&str, String> {
+    let mut rest = input;
+    loop {
+        match rest.find(['"', ';']) {
+            Some(position) if rest[position..].starts_with(';') => {
+                return Ok(&rest[position + 1..]);
+            }
+            Some(position) => rest = parse_literal(&rest[position..])?.1,
+            None => return Err(String::from("statement without terminating ';'")),
+        }
+    }
+}
+
+impl Permission {
+    /// Returns the actions of the [`Permission`](crate::Permission) as [Biscuit](https://www.biscuitsec.org) Datalog facts, one
+    /// `right("<action>");` fact per action, sorted. Available with the `biscuit` feature.
+    ///
+    /// Add them to the authority block of a token built with a Biscuit library, e.g. `biscuit-auth`, to issue a token carrying the
+    /// [`Permission`](crate::Permission). Holders can attenuate the token offline by appending a block with the
+    /// [`to_biscuit_attenuation`](Self::to_biscuit_attenuation()) of a subset.
+    ///
+    /// # Examples:
+    ///
+    /// ```
+    /// use simple_perm_manager::PermissionManager;
+    ///
+    /// let manager = PermissionManager::from_json(r#"{"post": {"view": true, "edit": true}}"#);
+    /// let perm = manager.perm_from_json(r#"{"post": true}"#);
+    ///
+    /// assert_eq!(perm.to_biscuit_facts(), "right(\"post:edit\");\nright(\"post:view\");\n");
+    /// ```
+    pub fn to_biscuit_facts(&self) -> String {
+        sorted_literals(self)
+            .into_iter()
+            .map(|action| format!("{}({});\n", RIGHT_FACT, action))
+            .collect()
+    }
+
+    /// Returns a [Biscuit](https://www.biscuitsec.org) Datalog check restricting a token to the actions of the
+    /// [`Permission`](crate::Permission): every `action` fact of the authorizer must be one of them. Available with the `biscuit`
+    /// feature.
+    ///
+    /// Append it as a new block to attenuate a token offline, without the root key.
+    ///
+    /// # Examples:
+    ///
+    /// ```
+    /// use simple_perm_manager::Permission;
+    ///
+    /// let perm = Permission::from_json(r#"{"post": {"view": true}}"#);
+    ///
+    /// assert_eq!(perm.to_biscuit_attenuation(), "check all action($action), [\"post:view\"].contains($action);\n");
+    /// ```
+    pub fn to_biscuit_attenuation(&self) -> String {
+        format!(
+            "check all {}($action), [{}].contains($action);\n",
+            ACTION_FACT,
+            sorted_literals(self).join(", ")
+        )
+    }
+
+    /// Returns the [Biscuit](https://www.biscuitsec.org) Datalog code authorizing a token only if it grants every action of the
+    /// [`Permission`](crate::Permission), taken as the required one. Available with the `biscuit` feature.
+    ///
+    /// The code has an `action("<action>");` fact and a `check if right("<action>");` check per action, so the checks of
+    /// attenuation blocks apply to the required actions, and an `allow if true;` policy. Add it to the authorizer of the Biscuit
+    /// library after verifying the token signature.
+    ///
+    /// # Examples:
+    ///
+    /// ```
+    /// use simple_perm_manager::Permission;
+    ///
+    /// let required = Permission::from_json(r#"{"post": {"edit": true}}"#);
+    ///
+    /// assert_eq!(
+    ///     required.to_biscuit_authorizer(),
+    ///     "action(\"post:edit\");\ncheck if right(\"post:edit\");\nallow if true;\n"
+    /// );
+    /// ```
+    pub fn to_biscuit_authorizer(&self) -> String {
+        let actions = sorted_literals(self);
+        let facts = actions
+            .iter()
+            .map(|action| format!("{}({});\n", ACTION_FACT, action));
+        let checks = actions
+            .iter()
+            .map(|action| format!("check if {}({});\n", RIGHT_FACT, action));
+
+        facts
+            .chain(checks)
+            .chain([String::from("allow if true;\n")])
+            .collect()
+    }
+}
+
+impl PermissionManager {
+    /// Returns a managed [`Permission`](crate::Permission) with the actions of the `right` facts of [Biscuit](https://www.biscuitsec.org)
+    /// Datalog code, validated against the universe, e.g. the output of [`Permission::to_biscuit_facts`](crate::Permission::to_biscuit_facts()).
+    /// Other statements are ignored. Available with the `biscuit` feature.
+    ///
+    /// It only parses Datalog: it does not read, verify or authorize Biscuit tokens. Do not pass the blocks of an untrusted token,
+    /// as their signatures and checks are not taken into account.
+    ///
+    /// # Examples:
+    ///
+    /// ```
+    /// use simple_perm_manager::PermissionManager;
+    ///
+    /// let manager = PermissionManager::from_json(r#"{"post": {"view": true, "edit": true}}"#);
+    /// let perm = manager.perm_from_json(r#"{"post": {"view": true}}"#);
+    ///
+    /// let datalog = format!("user(\"alice\");\n{}", perm.to_biscuit_facts());
+    /// let from_facts = manager.perm_from_biscuit_facts(&datalog).unwrap();
+    ///
+    /// assert!(manager.validate_perm(&from_facts));
+    /// assert_eq!(from_facts.get_actions(), perm.get_actions());
+    /// ```
+    ///
+    /// # Errors:
+    ///
+    /// - Returns [`ValidationError::InvalidDatalog`](crate::ValidationError::InvalidDatalog) if the code is not valid Datalog or one
+    ///   of its `right` facts does not have a single string.
+    /// - Returns [`ValidationError::UnknownActions`](crate::ValidationError::UnknownActions) if any right is not in the universe.
+    pub fn perm_from_biscuit_facts(&self, datalog: &str) -> Result<Permission, ValidationError> {
+        let rights = parse_rights(datalog).map_err(|error| {
+            telemetry::record_parse_error();
+            ValidationError::InvalidDatalog(error)
+        })?;
+
+        self.try_perm_from_actions(rights)
+    }
+}
@@ -0,0 +1,87 @@
+use super::*;
+
+fn manager() -> PermissionManager {
+    PermissionManager::from_json(r#"{"post": {"view": true, "edit": true}, "quote\"d": true}"#)
+}
+
+#[test]
+fn literal_test() {
+    assert_eq!(literal("post:view"), r#""post:view""#);
+    assert_eq!(literal(r#"a"b\c"#), r#""a\"b\\c""#);
+
+    assert_eq!(
+        parse_literal(r#""a\"b\\c" rest"#),
+        Ok((String::from(r#"a"b\c"#), " rest"))
+    );
+    assert_eq!(parse_literal("post").is_err(), true);
+    assert_eq!(parse_literal(r#""post"#).is_err(), true);
+    assert_eq!(parse_literal(r#""post\n""#).is_err(), true);
+}
+
+#[test]
+fn to_biscuit_test() {
+    let manager = manager();
+    let perm = manager.perm_from_json(r#"{"post": {"view": true}, "quote\"d": true}"#);
+
+    assert_eq!(
+        perm.to_biscuit_facts(),
+        "right(\"post:view\");\nright(\"quote\\\"d\");\n"
+    );
+    assert_eq!(
+        perm.to_biscuit_attenuation(),
+        "check all action($action), [\"post:view\", \"quote\\\"d\"].contains($action);\n"
+    );
+    assert_eq!(
+        perm.to_biscuit_authorizer(),
+        "action(\"post:view\");\naction(\"quote\\\"d\");\ncheck if right(\"post:view\");\ncheck if right(\"quote\\\"d\");\nallow if true;\n"
+    );
+
+    let empty = manager.perm_from_json("{}");
+    assert_eq!(empty.to_biscuit_facts(), "");
+    assert_eq!(empty.to_biscuit_authorizer(), "allow if true;\n");
+}
+
+#[test]
+fn perm_from_biscuit_facts_test() {
+    let manager = manager();
+    let perm = manager.perm_from_json(r#"{"post": true, "quote\"d": true}"#);
+
+    let from_facts = manager
+        .perm_from_biscuit_facts(&perm.to_biscuit_facts())
+        .unwrap();
+    assert_eq!(manager.validate_perm(&from_facts), true);
+    assert_eq!(*from_facts.get_actions(), *perm.get_actions());
+
+    // Other statements, comments and semicolons in strings are skipped
+    let datalog = r#"
+        // issued for alice; expires soon
+        user("alice;bob");
+        rights("post:edit");
+        check if time($time), $time < 2030-01-01T00:00:00Z;
+        right ( "post:view" ) ;
+    "#;
+    assert_eq!(
+        *manager
+            .perm_from_biscuit_facts(datalog)
+            .unwrap()
+            .get_actions(),
+        HashSet::from([String::from("post:view")])
+    );
+
+    match manager.perm_from_biscuit_facts(r#"right("post:share");"#) {
+        Err(ValidationError::UnknownActions(_)) => (),
+        _ => panic!("rights outside the universe should have failed"),
+    }
+    for datalog in [
+        r#"right("post:view", "other");"#,
+        r#"right(post);"#,
+        r#"right("post:view")"#,
+        r#"user("alice")"#,
+        r#"user("alice);"#,
+    ] {
+        match manager.perm_from_biscuit_facts(datalog) {
+            Err(ValidationError::InvalidDatalog(_)) => (),
+            _ => panic!("datalog {} should have failed", datalog),
+        }
+    }
+}
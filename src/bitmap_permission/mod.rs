@@ -0,0 +1,277 @@
+use crate::permission_manager::action_index::ActionIndex;
+use roaring::RoaringBitmap;
+use std::fmt;
+use std::sync::Arc;
+use uuid::Uuid;
+
+//// TESTS ////
+#[cfg(test)]
+mod tests;
+
+/// Managed [`Permission`](crate::Permission) representation backed by a compressed [roaring](https://docs.rs/roaring) bitmap.  
+/// Intended for very large universes where [`Permission`](crate::Permission) operations over sets of strings dominate CPU.
+///
+/// A [`PermissionManager`](crate::PermissionManager) maps every universe action to a dense integer id
+/// (its position in the canonical order used by [`encode_perm`](crate::PermissionManager::encode_perm())),
+/// so [`union`](Self::union()), [`difference`](Self::difference()) and [`contains`](Self::contains())
+/// become bitmap operations instead of string hashing and cloning.
+///
+/// [`BitmapPermission`](crate::BitmapPermission)s are created from managed [`Permission`](crate::Permission)s with
+/// [`PermissionManager::perm_to_bitmap`](crate::PermissionManager::perm_to_bitmap()) and converted back with
+/// [`PermissionManager::perm_from_bitmap`](crate::PermissionManager::perm_from_bitmap()).
+/// As managed permissions, they can only operate with [`BitmapPermission`](crate::BitmapPermission)s of the same
+/// [`PermissionManager`](crate::PermissionManager).
+///
+/// Action names are not stored in the [`BitmapPermission`](crate::BitmapPermission): all the
+/// [`BitmapPermission`](crate::BitmapPermission)s of a [`PermissionManager`](crate::PermissionManager) reference
+/// its interned action table, which keeps memory use low when thousands of permissions share the same universe.
+#[derive(Clone)]
+pub struct BitmapPermission {
+    actions: RoaringBitmap,
+    manager_id: Uuid,
+    index: Arc<ActionIndex>,
+}
+
+impl BitmapPermission {
+    pub(crate) fn from_bitmap_and_index(
+        actions: RoaringBitmap,
+        manager_id: Uuid,
+        index: Arc<ActionIndex>,
+    ) -> BitmapPermission {
+        BitmapPermission {
+            actions,
+            manager_id,
+            index,
+        }
+    }
+
+    pub(crate) fn get_manager_id(&self) -> Uuid {
+        self.manager_id
+    }
+
+    /// Returns the ids of the actions in the [`BitmapPermission`](crate::BitmapPermission) in ascending order.
+    ///
+    /// # Examples:
+    ///
+    /// ```
+    /// use simple_perm_manager::PermissionManager;
+    ///
+    /// let manager = PermissionManager::from_json(r#"{"create": true, "edit": true, "view": true}"#);
+    /// let perm = manager.perm_to_bitmap(&manager.perm_from_json(r#"{"create": true, "view": true}"#));
+    ///
+    /// assert_eq!(perm.action_ids().collect::<Vec<u32>>(), vec![0, 2]);
+    /// ```
+    pub fn action_ids(&self) -> impl Iterator<Item = u32> + '_ {
+        self.actions.iter()
+    }
+
+    /// Returns the names of the actions in the [`BitmapPermission`](crate::BitmapPermission) in canonical order.  
+    /// Names are borrowed from the [`PermissionManager`](crate::PermissionManager) interned action table.
+    ///
+    /// # Examples:
+    ///
+    /// ```
+    /// use simple_perm_manager::PermissionManager;
+    ///
+    /// let manager = PermissionManager::from_json(r#"{"create": true, "edit": true, "view": true}"#);
+    /// let perm = manager.perm_to_bitmap(&manager.perm_from_json(r#"{"view": true, "create": true}"#));
+    ///
+    /// assert_eq!(perm.actions().collect::<Vec<&str>>(), vec!["create", "view"]);
+    /// ```
+    pub fn actions(&self) -> impl Iterator<Item = &str> + '_ {
+        self.actions
+            .iter()
+            .filter_map(|id| self.index.action(id as usize))
+    }
+
+    /// Returns the number of actions in the [`BitmapPermission`](crate::BitmapPermission).
+    pub fn len(&self) -> usize {
+        self.actions.len() as usize
+    }
+
+    /// Returns `true` if the [`BitmapPermission`](crate::BitmapPermission) has no actions.
+    pub fn is_empty(&self) -> bool {
+        self.actions.is_empty()
+    }
+
+    /// Returns `true` if the [`BitmapPermission`](crate::BitmapPermission) calling has the same
+    /// [`PermissionManager`](crate::PermissionManager) as the [`BitmapPermission`](crate::BitmapPermission) used as argument.
+    pub fn has_same_manager(&self, other: &BitmapPermission) -> bool {
+        self.manager_id.eq(&other.manager_id)
+    }
+
+    /// Returns a [`BitmapPermission`](crate::BitmapPermission) containing all the actions in the calling
+    /// [`BitmapPermission`](crate::BitmapPermission) and in the [`BitmapPermission`](crate::BitmapPermission) used as argument.
+    ///
+    /// # Examples:
+    ///
+    /// ```
+    /// use simple_perm_manager::PermissionManager;
+    ///
+    /// let manager = PermissionManager::from_json(r#"{"create": true, "edit": true, "view": true}"#);
+    /// let perm_foo = manager.perm_to_bitmap(&manager.perm_from_json(r#"{"create": true}"#));
+    /// let perm_bar = manager.perm_to_bitmap(&manager.perm_from_json(r#"{"view": true}"#));
+    ///
+    /// let perm_foo_bar = manager.perm_from_bitmap(&perm_foo.union(&perm_bar));
+    /// assert_eq!(*perm_foo_bar.get_actions(), *manager.perm_from_json(r#"{"create": true, "view": true}"#).get_actions());
+    /// ```
+    ///
+    /// # Panics:
+    ///
+    /// Panics if the [`BitmapPermission`](crate::BitmapPermission)s do not have the same [`PermissionManager`](crate::PermissionManager).
+    pub fn union(&self, other: &BitmapPermission) -> BitmapPermission {
+        if !self.has_same_manager(other) {
+            panic!("Permissions in union operation do not have same manager");
+        }
+
+        BitmapPermission::from_bitmap_and_index(
+            &self.actions | &other.actions,
+            self.manager_id,
+            self.index.clone(),
+        )
+    }
+
+    /// Returns a [`BitmapPermission`](crate::BitmapPermission) containing the actions that are in the calling
+    /// [`BitmapPermission`](crate::BitmapPermission) but not in the [`BitmapPermission`](crate::BitmapPermission) used as argument.
+    ///
+    /// # Examples:
+    ///
+    /// ```
+    /// use simple_perm_manager::PermissionManager;
+    ///
+    /// let manager = PermissionManager::from_json(r#"{"create": true, "edit": true, "view": true}"#);
+    /// let perm_foo_bar = manager.perm_to_bitmap(&manager.perm_from_json(r#"{"create": true, "view": true}"#));
+    /// let perm_bar = manager.perm_to_bitmap(&manager.perm_from_json(r#"{"view": true}"#));
+    ///
+    /// let perm_foo = manager.perm_from_bitmap(&perm_foo_bar.difference(&perm_bar));
+    /// assert!(perm_foo.contains_action("create"));
+    /// assert!(!perm_foo.contains_action("view"));
+    /// ```
+    ///
+    /// # Panics:
+    ///
+    /// Panics if the [`BitmapPermission`](crate::BitmapPermission)s do not have the same [`PermissionManager`](crate::PermissionManager).
+    pub fn difference(&self, other: &BitmapPermission) -> BitmapPermission {
+        if !self.has_same_manager(other) {
+            panic!("Permissions in difference operation do not have same manager");
+        }
+
+        BitmapPermission::from_bitmap_and_index(
+            &self.actions - &other.actions,
+            self.manager_id,
+            self.index.clone(),
+        )
+    }
+
+    /// Returns a [`BitmapPermission`](crate::BitmapPermission) containing the actions that are both in the calling
+    /// [`BitmapPermission`](crate::BitmapPermission) and in the [`BitmapPermission`](crate::BitmapPermission) used as argument.
+    ///
+    /// # Examples:
+    ///
+    /// ```
+    /// use simple_perm_manager::PermissionManager;
+    ///
+    /// let manager = PermissionManager::from_json(r#"{"create": true, "edit": true, "view": true}"#);
+    /// let perm_foo = manager.perm_to_bitmap(&manager.perm_from_json(r#"{"create": true, "view": true}"#));
+    /// let perm_bar = manager.perm_to_bitmap(&manager.perm_from_json(r#"{"edit": true, "view": true}"#));
+    ///
+    /// let common = manager.perm_from_bitmap(&perm_foo.intersection(&perm_bar));
+    /// assert_eq!(*common.get_actions(), *manager.perm_from_json(r#"{"view": true}"#).get_actions());
+    /// ```
+    ///
+    /// # Panics:
+    ///
+    /// Panics if the [`BitmapPermission`](crate::BitmapPermission)s do not have the same [`PermissionManager`](crate::PermissionManager).
+    pub fn intersection(&self, other: &BitmapPermission) -> BitmapPermission {
+        if !self.has_same_manager(other) {
+            panic!("Permissions in intersection operation do not have same manager");
+        }
+
+        BitmapPermission::from_bitmap_and_index(
+            &self.actions & &other.actions,
+            self.manager_id,
+            self.index.clone(),
+        )
+    }
+
+    /// Returns `true` if the [`BitmapPermission`](crate::BitmapPermission) calling contains at least
+    /// all the actions in the [`BitmapPermission`](crate::BitmapPermission) used as argument.
+    ///
+    /// # Examples:
+    ///
+    /// ```
+    /// use simple_perm_manager::PermissionManager;
+    ///
+    /// let manager = PermissionManager::from_json(r#"{"create": true, "edit": true, "view": true}"#);
+    /// let perm_foo_bar = manager.perm_to_bitmap(&manager.perm_from_json(r#"{"create": true, "view": true}"#));
+    /// let perm_bar = manager.perm_to_bitmap(&manager.perm_from_json(r#"{"view": true}"#));
+    ///
+    /// assert!(perm_foo_bar.contains(&perm_bar));
+    /// assert!(!perm_bar.contains(&perm_foo_bar));
+    /// ```
+    ///
+    /// # Panics:
+    ///
+    /// Panics if the [`BitmapPermission`](crate::BitmapPermission)s do not have the same [`PermissionManager`](crate::PermissionManager).
+    pub fn contains(&self, other: &BitmapPermission) -> bool {
+        if !self.has_same_manager(other) {
+            panic!("Permissions in contains operation do not have same manager");
+        }
+
+        self.actions.is_superset(&other.actions)
+    }
+
+    /// Returns `true` if the [`BitmapPermission`](crate::BitmapPermission) contains the action with the id provided.  
+    /// Action ids are obtained with [`PermissionManager::action_id`](crate::PermissionManager::action_id()).
+    ///
+    /// # Examples:
+    ///
+    /// ```
+    /// use simple_perm_manager::PermissionManager;
+    ///
+    /// let manager = PermissionManager::from_json(r#"{"create": true, "view": true}"#);
+    /// let perm = manager.perm_to_bitmap(&manager.perm_from_json(r#"{"view": true}"#));
+    /// let view_id = manager.action_id("view").unwrap();
+    ///
+    /// assert!(perm.contains_action_id(view_id));
+    /// ```
+    pub fn contains_action_id(&self, action_id: u32) -> bool {
+        self.actions.contains(action_id)
+    }
+
+    /// Returns `true` if the [`BitmapPermission`](crate::BitmapPermission) contains the action used as argument.  
+    /// Aliases declared in the [`PermissionManager`](crate::PermissionManager) are resolved.
+    ///
+    /// # Examples:
+    ///
+    /// ```
+    /// use simple_perm_manager::PermissionManager;
+    ///
+    /// let manager = PermissionManager::from_json(r#"{"create": true, "view": true}"#);
+    /// let perm = manager.perm_to_bitmap(&manager.perm_from_json(r#"{"view": true}"#));
+    ///
+    /// assert!(perm.contains_action("view"));
+    /// assert!(!perm.contains_action("create"));
+    /// assert!(!perm.contains_action("other"));
+    /// ```
+    pub fn contains_action(&self, action_str: &str) -> bool {
+        match self
+            .index
+            .resolve(action_str)
+            .and_then(|action| self.index.position(action))
+        {
+            Some(position) => self.actions.contains(position as u32),
+            None => false,
+        }
+    }
+}
+
+// Debug output lists action names instead of dumping the whole interned action table
+impl fmt::Debug for BitmapPermission {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("BitmapPermission")
+            .field("actions", &self.actions().collect::<Vec<&str>>())
+            .field("manager_id", &self.manager_id)
+            .finish()
+    }
+}
@@ -0,0 +1,162 @@
+use super::*;
+use std::collections::HashSet;
+
+// Index with the actions 'action_0' to 'action_9', ids follow the number in the name
+fn test_index() -> Arc<ActionIndex> {
+    Arc::new(ActionIndex::from_actions(
+        &(0..10)
+            .map(|n| format!("action_{}", n))
+            .collect::<HashSet<String>>(),
+    ))
+}
+
+#[test]
+fn from_bitmap_and_index_test() {
+    let id = Uuid::new_v4();
+    let actions: RoaringBitmap = [1, 5].into_iter().collect();
+    let p = BitmapPermission::from_bitmap_and_index(actions.clone(), id, test_index());
+
+    assert_eq!(p.actions, actions);
+    assert_eq!(p.get_manager_id(), id);
+    assert_eq!(p.len(), 2);
+    assert_eq!(p.is_empty(), false);
+    assert_eq!(p.action_ids().collect::<Vec<u32>>(), vec![1, 5]);
+}
+
+#[test]
+fn actions_test() {
+    let p = BitmapPermission::from_bitmap_and_index(
+        [1, 5].into_iter().collect(),
+        Uuid::new_v4(),
+        test_index(),
+    );
+
+    assert_eq!(
+        p.actions().collect::<Vec<&str>>(),
+        vec!["action_1", "action_5"]
+    );
+    assert_eq!(
+        format!("{:?}", p),
+        format!(
+            "BitmapPermission {{ actions: [\"action_1\", \"action_5\"], manager_id: {:?} }}",
+            p.get_manager_id()
+        )
+    );
+}
+
+#[test]
+fn contains_action_test() {
+    let p = BitmapPermission::from_bitmap_and_index(
+        [1, 5].into_iter().collect(),
+        Uuid::new_v4(),
+        test_index(),
+    );
+
+    assert_eq!(p.contains_action("action_1"), true);
+    assert_eq!(p.contains_action("action_2"), false);
+    assert_eq!(p.contains_action("other"), false);
+}
+
+#[test]
+fn has_same_manager_test() {
+    let id = Uuid::new_v4();
+    let p1 = BitmapPermission::from_bitmap_and_index(RoaringBitmap::new(), id, test_index());
+    let p2 = BitmapPermission::from_bitmap_and_index(RoaringBitmap::new(), id, test_index());
+    let p3 =
+        BitmapPermission::from_bitmap_and_index(RoaringBitmap::new(), Uuid::new_v4(), test_index());
+
+    assert_eq!(p1.has_same_manager(&p2), true);
+    assert_eq!(p1.has_same_manager(&p3), false);
+}
+
+#[test]
+fn union_test() {
+    let id = Uuid::new_v4();
+    let p1 =
+        BitmapPermission::from_bitmap_and_index([1, 2].into_iter().collect(), id, test_index());
+    let p2 =
+        BitmapPermission::from_bitmap_and_index([2, 3].into_iter().collect(), id, test_index());
+    let diff_id =
+        BitmapPermission::from_bitmap_and_index(RoaringBitmap::new(), Uuid::new_v4(), test_index());
+
+    assert_eq!(
+        p1.union(&p2).action_ids().collect::<Vec<u32>>(),
+        vec![1, 2, 3]
+    );
+
+    match std::panic::catch_unwind(|| {
+        p1.union(&diff_id);
+    }) {
+        Ok(_) => panic!("operation with perm with different manager_id should have panicked"),
+        Err(_) => (),
+    }
+}
+
+#[test]
+fn difference_test() {
+    let id = Uuid::new_v4();
+    let p1 =
+        BitmapPermission::from_bitmap_and_index([1, 2].into_iter().collect(), id, test_index());
+    let p2 =
+        BitmapPermission::from_bitmap_and_index([2, 3].into_iter().collect(), id, test_index());
+    let diff_id =
+        BitmapPermission::from_bitmap_and_index(RoaringBitmap::new(), Uuid::new_v4(), test_index());
+
+    assert_eq!(
+        p1.difference(&p2).action_ids().collect::<Vec<u32>>(),
+        vec![1]
+    );
+
+    match std::panic::catch_unwind(|| {
+        p1.difference(&diff_id);
+    }) {
+        Ok(_) => panic!("operation with perm with different manager_id should have panicked"),
+        Err(_) => (),
+    }
+}
+
+#[test]
+fn intersection_test() {
+    let id = Uuid::new_v4();
+    let p1 =
+        BitmapPermission::from_bitmap_and_index([1, 2].into_iter().collect(), id, test_index());
+    let p2 =
+        BitmapPermission::from_bitmap_and_index([2, 3].into_iter().collect(), id, test_index());
+    let diff_id =
+        BitmapPermission::from_bitmap_and_index(RoaringBitmap::new(), Uuid::new_v4(), test_index());
+
+    assert_eq!(
+        p1.intersection(&p2).action_ids().collect::<Vec<u32>>(),
+        vec![2]
+    );
+
+    match std::panic::catch_unwind(|| {
+        p1.intersection(&diff_id);
+    }) {
+        Ok(_) => panic!("operation with perm with different manager_id should have panicked"),
+        Err(_) => (),
+    }
+}
+
+#[test]
+fn contains_test() {
+    let id = Uuid::new_v4();
+    let p1 =
+        BitmapPermission::from_bitmap_and_index([1, 2, 3].into_iter().collect(), id, test_index());
+    let p2 =
+        BitmapPermission::from_bitmap_and_index([2, 3].into_iter().collect(), id, test_index());
+    let diff_id =
+        BitmapPermission::from_bitmap_and_index(RoaringBitmap::new(), Uuid::new_v4(), test_index());
+
+    assert_eq!(p1.contains(&p2), true);
+    assert_eq!(p2.contains(&p1), false);
+    assert_eq!(p1.contains_action_id(1), true);
+    assert_eq!(p2.contains_action_id(1), false);
+
+    match std::panic::catch_unwind(|| {
+        p1.contains(&diff_id);
+    }) {
+        Ok(_) => panic!("operation with perm with different manager_id should have panicked"),
+        Err(_) => (),
+    }
+}
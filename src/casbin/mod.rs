@@ -0,0 +1,234 @@
+use crate::permission::{Permission, ACTION_DIVIDER};
+use crate::permission_manager::PermissionManager;
+use crate::telemetry;
+use crate::validation_error::ValidationError;
+use std::collections::{BTreeMap, HashMap, HashSet};
+
+//// TESTS ////
+#[cfg(test)]
+mod tests;
+
+// Casbin policy type of the permission rules, e.g. `p, alice, data1, read`
+const POLICY_TYPE: &str = "p";
+// Effect of the rules that grant access, the only one a permission can hold
+const ALLOW_EFFECT: &str = "allow";
+
+// Returns a CSV field, quoting it if it has commas, quotes or surrounding whitespace
+fn quote(field: &str) -> String {
+    if field.contains([',', '"']) || field.trim() != field {
+        format!("\"{}\"", field.replace('"', "\"\""))
+    } else {
+        String::from(field)
+    }
+}
+
+// Returns the trimmed fields of a CSV line, unquoting quoted fields
+fn parse_fields(line: &str) -> Result<Vec<String>, String> {
+    let mut fields: Vec<String> = Vec::new();
+    let mut chars = line.chars().peekable();
+
+    loop {
+        while chars.next_if(|c| *c == ' ' || *c == '\t').is_some() {}
+
+        let mut field = String::new();
+        if chars.next_if_eq(&'"').is_some() {
+            loop {
+                match chars.next() {
+                    Some('"') if chars.next_if_eq(&'"').is_some() => field.push('"'),
+                    Some('"') => break,
+                    Some(c) => field.push(c),
+                    None => return Err(String::from("unterminated quoted field")),
+                }
+            }
+            while chars.next_if(|c| *c == ' ' || *c == '\t').is_some() {}
+            if chars.peek().is_some_and(|c| *c != ',') {
+                return Err(String::from("unexpected characters after quoted field"));
+            }
+        } else {
+            while let Some(c) = chars.next_if(|c| *c != ',') {
+                field.push(c);
+            }
+            field = String::from(field.trim_end());
+        }
+        fields.push(field);
+
+        if chars.next().is_none() {
+            return Ok(fields);
+        }
+    }
+}
+
+// Returns the actions of every subject in the `p` rules of a Casbin policy, with the object and action of each rule
+// joined by the separator. Blank lines, comments and other policy types are skipped
+fn parse_policy(
+    csv: &str,
+    separator: char,
+) -> Result<BTreeMap<String, HashSet<String>>, ValidationError> {
+    let mut subjects: BTreeMap<String, HashSet<String>> = BTreeMap::new();
+
+    for (number, line) in csv.lines().enumerate() {
+        let line = line.trim();
+        if line.is_empty() || line.starts_with('#') {
+            continue;
+        }
+
+        let invalid = |message: &str| {
+            telemetry::record_parse_error();
+            ValidationError::InvalidJson(format!(
+                "wrong format in casbin policy - line {}: {}",
+                number + 1,
+                message
+            ))
+        };
+        let fields = parse_fields(line).map_err(|message| invalid(&message))?;
+        if fields[0] != POLICY_TYPE {
+            continue;
+        }
+        let (subject, object, action) = match fields.as_slice() {
+            [_, subject, object, action] => (subject, object, action),
+            [_, subject, object, action, effect] if effect == ALLOW_EFFECT => {
+                (subject, object, action)
+            }
+            [_, _, _, _, effect] => {
+                return Err(invalid(&format!("'{}' effect is not supported", effect)))
+            }
+            _ => return Err(invalid("expected 'p, subject, object, action'")),
+        };
+        if subject.is_empty() || object.is_empty() || action.is_empty() {
+            return Err(invalid("empty subject, object or action"));
+        }
+
+        subjects
+            .entry(subject.clone())
+            .or_default()
+            .insert(format!("{}{}{}", object, separator, action));
+    }
+
+    Ok(subjects)
+}
+
+impl Permission {
+    /// Returns the [`Permission`](crate::Permission) as [Casbin](https://casbin.org) CSV policy lines granting its actions to a
+    /// subject, one `p, subject, object, action` line per action sorted by action.  
+    /// The object is the action groups and the action its name, so `"data:reports:read"` becomes `p, alice, data:reports, read`.
+    /// Fields with commas, quotes or surrounding whitespace are quoted.
+    ///
+    /// # Examples:
+    ///
+    /// ```
+    /// use simple_perm_manager::perm;
+    ///
+    /// let perm = perm!["data1:read", "data2:write"];
+    ///
+    /// assert_eq!(
+    ///     perm.to_casbin_policy("alice").unwrap(),
+    ///     "p, alice, data1, read\np, alice, data2, write\n"
+    /// );
+    /// ```
+    ///
+    /// # Errors:
+    ///
+    /// Returns [`ValidationError::UntranslatableActions`](crate::ValidationError::UntranslatableActions) with the sorted actions
+    /// that have no group, as Casbin rules need an object.
+    pub fn to_casbin_policy(&self, subject: &str) -> Result<String, ValidationError> {
+        let separator = self.separator();
+        let mut actions: Vec<&str> = self.actions().collect();
+        actions.sort();
+
+        let mut untranslatable: Vec<String> = Vec::new();
+        let mut lines = String::new();
+        for action in actions {
+            match action.rsplit_once(separator) {
+                Some((object, name)) => lines.push_str(&format!(
+                    "{}, {}, {}, {}\n",
+                    POLICY_TYPE,
+                    quote(subject),
+                    quote(object),
+                    quote(name)
+                )),
+                None => untranslatable.push(action.to_string()),
+            }
+        }
+        if !untranslatable.is_empty() {
+            return Err(ValidationError::UntranslatableActions(untranslatable));
+        }
+
+        Ok(lines)
+    }
+}
+
+impl PermissionManager {
+    /// Returns a new [`PermissionManager`](crate::PermissionManager) whose universe is every `object:action` of the `p` rules of a
+    /// [Casbin](https://casbin.org) CSV policy, to bootstrap a universe from an existing Casbin deployment.  
+    /// Blank lines, `#` comments and other policy types (such as `g` role assignments) are skipped.
+    ///
+    /// # Examples:
+    ///
+    /// ```
+    /// use simple_perm_manager::PermissionManager;
+    ///
+    /// let policy = "
+    ///     p, alice, data1, read
+    ///     p, bob, data2, write
+    ///     g, carol, admin
+    /// ";
+    ///
+    /// let manager = PermissionManager::from_casbin_policy(policy).unwrap();
+    /// assert_eq!(manager.to_actions().len(), 2);
+    /// assert!(manager.to_actions().contains("data2:write"));
+    /// ```
+    ///
+    /// # Errors:
+    ///
+    /// - Returns [`ValidationError::InvalidJson`](crate::ValidationError::InvalidJson) if a `p` rule does not have a subject,
+    ///   object and action, or has an effect other than `allow`.
+    /// - Returns [`ValidationError::MalformedActions`](crate::ValidationError::MalformedActions) if an object and action do not
+    ///   form a valid action, e.g. the object ends with `:`.
+    pub fn from_casbin_policy(csv: &str) -> Result<PermissionManager, ValidationError> {
+        let actions: HashSet<String> = parse_policy(csv, ACTION_DIVIDER)?
+            .into_values()
+            .flatten()
+            .collect();
+
+        PermissionManager::try_from_actions(actions)
+    }
+
+    /// Returns a managed [`Permission`](crate::Permission) for every subject of the `p` rules of a [Casbin](https://casbin.org)
+    /// CSV policy, holding the `object:action` of its rules.  
+    /// Blank lines, `#` comments and other policy types (such as `g` role assignments) are skipped.
+    ///
+    /// # Examples:
+    ///
+    /// ```
+    /// use simple_perm_manager::PermissionManager;
+    ///
+    /// let policy = "
+    ///     p, alice, data1, read
+    ///     p, alice, data1, write
+    ///     p, bob, data1, read
+    /// ";
+    ///
+    /// let manager = PermissionManager::from_casbin_policy(policy).unwrap();
+    /// let perms = manager.perms_from_casbin_policy(policy).unwrap();
+    ///
+    /// assert!(perms["alice"].contains_action("data1:write"));
+    /// assert!(!perms["bob"].contains_action("data1:write"));
+    /// assert_eq!(perms["bob"].to_casbin_policy("bob").unwrap(), "p, bob, data1, read\n");
+    /// ```
+    ///
+    /// # Errors:
+    ///
+    /// - Returns [`ValidationError::InvalidJson`](crate::ValidationError::InvalidJson) if a `p` rule does not have a subject,
+    ///   object and action, or has an effect other than `allow`.
+    /// - Returns [`ValidationError::UnknownActions`](crate::ValidationError::UnknownActions) if a rule has an action that is not
+    ///   in the universe.
+    pub fn perms_from_casbin_policy(
+        &self,
+        csv: &str,
+    ) -> Result<HashMap<String, Permission>, ValidationError> {
+        parse_policy(csv, self.separator())?
+            .into_iter()
+            .map(|(subject, actions)| Ok((subject, self.try_perm_from_actions(actions)?)))
+            .collect()
+    }
+}
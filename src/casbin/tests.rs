@@ -0,0 +1,157 @@
+use super::*;
+
+const POLICY: &str = "
+# Data access
+p, alice, data1, read
+p, alice, data1, write, allow
+p, bob, reports:monthly, read
+p, \"carol, jr\", data1, \"read\"
+
+g, dave, admin
+p2, bob, data2, read
+";
+
+#[test]
+fn quote_test() {
+    assert_eq!(quote("alice"), "alice");
+    assert_eq!(quote("carol, jr"), r#""carol, jr""#);
+    assert_eq!(quote(r#"say "hi""#), r#""say ""hi""""#);
+    assert_eq!(quote(" padded"), r#"" padded""#);
+}
+
+#[test]
+fn parse_fields_test() {
+    assert_eq!(
+        parse_fields("p, alice,data1 ,  read"),
+        Ok(vec![
+            String::from("p"),
+            String::from("alice"),
+            String::from("data1"),
+            String::from("read")
+        ])
+    );
+    assert_eq!(
+        parse_fields(r#"p, "carol, jr", "say ""hi""" , x"#),
+        Ok(vec![
+            String::from("p"),
+            String::from("carol, jr"),
+            String::from(r#"say "hi""#),
+            String::from("x")
+        ])
+    );
+    assert_eq!(
+        parse_fields("p,,"),
+        Ok(vec![String::from("p"), String::new(), String::new()])
+    );
+    assert_eq!(parse_fields(r#"p, "alice"#).is_err(), true);
+    assert_eq!(parse_fields(r#"p, "alice"x, data1"#).is_err(), true);
+}
+
+#[test]
+fn parse_policy_test() {
+    let subjects = parse_policy(POLICY, ':').unwrap();
+
+    assert_eq!(subjects.len(), 3);
+    assert_eq!(
+        subjects["alice"],
+        HashSet::from([String::from("data1:read"), String::from("data1:write")])
+    );
+    assert_eq!(
+        subjects["bob"],
+        HashSet::from([String::from("reports:monthly:read")])
+    );
+    assert_eq!(
+        subjects["carol, jr"],
+        HashSet::from([String::from("data1:read")])
+    );
+
+    for (policy, line) in [
+        ("p, alice, data1", 1),
+        ("\np, alice, data1, read, deny", 2),
+        ("p, alice, data1, read, allow, extra", 1),
+        ("p, alice, , read", 1),
+        ("p, \"alice, data1, read", 1),
+    ] {
+        match parse_policy(policy, ':') {
+            Err(ValidationError::InvalidJson(message)) => assert_eq!(
+                message.starts_with(&format!("wrong format in casbin policy - line {}:", line)),
+                true
+            ),
+            _ => panic!("policy '{}' should have failed", policy),
+        }
+    }
+}
+
+#[test]
+fn to_casbin_policy_test() {
+    let perm = Permission::from_actions(HashSet::from([
+        String::from("data1:write"),
+        String::from("data1:read"),
+        String::from("reports:monthly:read"),
+    ]));
+
+    assert_eq!(
+        perm.to_casbin_policy("carol, jr").unwrap(),
+        "p, \"carol, jr\", data1, read\np, \"carol, jr\", data1, write\np, \"carol, jr\", reports:monthly, read\n"
+    );
+    assert_eq!(
+        Permission::from_actions(HashSet::new()).to_casbin_policy("alice"),
+        Ok(String::new())
+    );
+    assert_eq!(
+        Permission::from_actions(HashSet::from([
+            String::from("view"),
+            String::from("edit"),
+            String::from("data1:read"),
+        ]))
+        .to_casbin_policy("alice")
+        .unwrap_err(),
+        ValidationError::UntranslatableActions(vec![String::from("edit"), String::from("view")])
+    );
+}
+
+#[test]
+fn from_casbin_policy_test() {
+    let manager = PermissionManager::from_casbin_policy(POLICY).unwrap();
+    assert_eq!(
+        manager.to_actions(),
+        HashSet::from([
+            String::from("data1:read"),
+            String::from("data1:write"),
+            String::from("reports:monthly:read"),
+        ])
+    );
+
+    assert_eq!(
+        PermissionManager::from_casbin_policy("p, alice, data1:, read").unwrap_err(),
+        ValidationError::MalformedActions(vec![String::from("data1::read")])
+    );
+}
+
+#[test]
+fn perms_from_casbin_policy_test() {
+    let manager = PermissionManager::from_casbin_policy(POLICY).unwrap();
+    let perms = manager.perms_from_casbin_policy(POLICY).unwrap();
+
+    assert_eq!(perms.len(), 3);
+    assert_eq!(manager.validate_perm(&perms["alice"]), true);
+    assert_eq!(
+        *perms["alice"].get_actions(),
+        HashSet::from([String::from("data1:read"), String::from("data1:write")])
+    );
+
+    // Exported rules load back into the same permissions
+    let exported = perms["bob"].to_casbin_policy("bob").unwrap()
+        + &perms["carol, jr"].to_casbin_policy("carol, jr").unwrap();
+    let reloaded = manager.perms_from_casbin_policy(&exported).unwrap();
+    assert_eq!(reloaded.len(), 2);
+    assert_eq!(
+        *reloaded["carol, jr"].get_actions(),
+        *perms["carol, jr"].get_actions()
+    );
+
+    match manager.perms_from_casbin_policy("p, alice, data2, read") {
+        Err(ValidationError::UnknownActions(_)) => (),
+        _ => panic!("rules with actions not in the universe should have failed"),
+    }
+}
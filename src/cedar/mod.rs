@@ -0,0 +1,216 @@
+use crate::permission::Permission;
+use crate::permission_manager::PermissionManager;
+use crate::telemetry;
+use crate::validation_error::ValidationError;
+use serde_json::{json, Value};
+use std::collections::{BTreeMap, BTreeSet, HashSet};
+
+//// TESTS ////
+#[cfg(test)]
+mod tests;
+
+// Cedar entity type of actions and action groups
+const ACTION_TYPE: &str = "Action";
+
+// Returns a Cedar string literal, escaping quotes and backslashes
+fn literal(value: &str) -> String {
+    format!("\"{}\"", value.replace('\\', "\\\\").replace('"', "\\\""))
+}
+
+// Returns a Cedar entity reference, e.g. `Action::"post:view"`
+fn entity(entity_type: &str, id: &str) -> String {
+    format!("{}::{}", entity_type, literal(id))
+}
+
+// Returns the JSON uid of a Cedar action entity
+fn action_uid(id: &str) -> Value {
+    json!({"type": ACTION_TYPE, "id": id})
+}
+
+// Parses a Cedar string literal, returning its value and the rest of the input
+fn parse_literal(input: &str) -> Result<(String, &str), String> {
+    let Some(input) = input.strip_prefix('"') else {
+        return Err(String::from("expected a string"));
+    };
+
+    let mut value = String::new();
+    let mut chars = input.char_indices();
+    while let Some((position, c)) = chars.next() {
+        match c {
+            '"' => return Ok((value, &input[position + 1..])),
+            '\\' => match chars.next() {
+                Some((_, escaped @ ('"' | '\\' | '\''))) => value.push(escaped),
+                Some((_, 'n')) => value.push('\n'),
+                Some((_, 'r')) => value.push('\r'),
+                Some((_, 't')) => value.push('\t'),
+                Some((_, '0')) => value.push('\0'),
+                _ => return Err(String::from("invalid escape in string")),
+            },
+            c => value.push(c),
+        }
+    }
+
+    Err(String::from("unterminated string"))
+}
+
+// Returns the ids of the `Action::"..."` entity references of Cedar policies, including namespaced ones,
+// ignoring comments and the contents of other strings
+fn action_references(policies: &str) -> Result<BTreeSet<String>, String> {
+    let mut references: BTreeSet<String> = BTreeSet::new();
+    let mut rest = policies;
+
+    while let Some(position) = rest.find(['/', '"', 'A']) {
+        let (before, candidate) = rest.split_at(position);
+        if let Some(comment) = candidate.strip_prefix("//") {
+            rest = comment.split_once('\n').map_or("", |(_, next)| next);
+        } else if candidate.starts_with('"') {
+            rest = parse_literal(candidate)?.1;
+        } else {
+            // `Action` must be a whole identifier, not the end of a longer one
+            let reference = candidate
+                .strip_prefix(ACTION_TYPE)
+                .filter(|_| {
+                    !before
+                        .chars()
+                        .next_back()
+                        .is_some_and(|c| c.is_alphanumeric() || c == '_')
+                })
+                .map(str::trim_start)
+                .and_then(|after| after.strip_prefix("::"))
+                .map(str::trim_start)
+                .filter(|after| after.starts_with('"'));
+            match reference {
+                Some(reference) => {
+                    let (id, after) = parse_literal(reference)?;
+                    references.insert(id);
+                    rest = after;
+                }
+                None => rest = &candidate[1..],
+            }
+        }
+    }
+
+    Ok(references)
+}
+
+impl Permission {
+    /// Returns a [Cedar](https://www.cedarpolicy.com) policy permitting a principal the actions of the [`Permission`](crate::Permission)
+    /// on any resource, or an empty string if it has no actions. Actions are sorted and referenced as `Action::"<action>"` entities,
+    /// see [`PermissionManager::to_cedar_entities`](crate::PermissionManager::to_cedar_entities()).
+    ///
+    /// # Examples:
+    ///
+    /// ```
+    /// use simple_perm_manager::Permission;
+    ///
+    /// let perm = Permission::from_json(r#"{"post": {"view": true, "edit": true}}"#);
+    ///
+    /// assert_eq!(perm.to_cedar_policy("User", "alice"), r#"permit (
+    ///     principal == User::"alice",
+    ///     action in [Action::"post:edit", Action::"post:view"],
+    ///     resource
+    /// );
+    /// "#);
+    /// ```
+    pub fn to_cedar_policy(&self, principal_type: &str, principal_id: &str) -> String {
+        if self.is_empty() {
+            return String::new();
+        }
+
+        let mut actions: Vec<&str> = self.actions().collect();
+        actions.sort();
+        let actions: Vec<String> = actions
+            .into_iter()
+            .map(|action| entity(ACTION_TYPE, action))
+            .collect();
+
+        format!(
+            "permit (\n    principal == {},\n    action in [{}],\n    resource\n);\n",
+            entity(principal_type, principal_id),
+            actions.join(", ")
+        )
+    }
+}
+
+impl PermissionManager {
+    /// Returns the universe as a [Cedar](https://www.cedarpolicy.com) entities JSON array: an `Action` entity per action and per
+    /// group, where each one is a member of its parent group. Policies can then permit whole groups with
+    /// `action in Action::"<group>"`.
+    ///
+    /// # Examples:
+    ///
+    /// ```
+    /// use simple_perm_manager::PermissionManager;
+    ///
+    /// let manager = PermissionManager::from_json(r#"{"post": {"view": true}}"#);
+    ///
+    /// assert_eq!(manager.to_cedar_entities(), r#"[{"attrs":{},"parents":[],"uid":{"id":"post","type":"Action"}},{"attrs":{},"parents":[{"id":"post","type":"Action"}],"uid":{"id":"post:view","type":"Action"}}]"#);
+    /// ```
+    pub fn to_cedar_entities(&self) -> String {
+        let separator = self.separator();
+
+        // Actions and groups mapped to their parent group
+        let mut parents: BTreeMap<String, Option<String>> = BTreeMap::new();
+        for action in self.to_actions() {
+            let mut child = action;
+            while let Some((group, _)) = child.rsplit_once(separator) {
+                let group = String::from(group);
+                parents.insert(child, Some(group.clone()));
+                child = group;
+            }
+            parents.insert(child, None);
+        }
+
+        let entities: Vec<Value> = parents
+            .into_iter()
+            .map(|(id, parent)| {
+                json!({
+                    "uid": action_uid(&id),
+                    "attrs": {},
+                    "parents": parent.iter().map(|parent| action_uid(parent)).collect::<Vec<Value>>(),
+                })
+            })
+            .collect();
+
+        Value::Array(entities).to_string()
+    }
+
+    /// Checks that every `Action::"..."` referenced by a [Cedar](https://www.cedarpolicy.com) policy set is an action or a group of
+    /// the universe, so policies do not silently drift from it. Comments and other strings are ignored.
+    ///
+    /// # Examples:
+    ///
+    /// ```
+    /// use simple_perm_manager::{PermissionManager, ValidationError};
+    ///
+    /// let manager = PermissionManager::from_json(r#"{"post": {"view": true, "edit": true}}"#);
+    ///
+    /// assert!(manager.verify_cedar_policies(r#"permit (principal, action in Action::"post", resource);"#).is_ok());
+    /// assert_eq!(
+    ///     manager.verify_cedar_policies(r#"permit (principal, action == Action::"post:veiw", resource);"#).unwrap_err(),
+    ///     ValidationError::UnknownActions(vec![(String::from("post:veiw"), vec![String::from("post:view")])])
+    /// );
+    /// ```
+    ///
+    /// # Errors:
+    ///
+    /// - Returns [`ValidationError::UnknownActions`](crate::ValidationError::UnknownActions) with the referenced actions that are
+    ///   not in the universe, with did-you-mean suggestions.
+    /// - Returns [`ValidationError::InvalidJson`](crate::ValidationError::InvalidJson) if the policies have an unterminated string
+    ///   or an invalid escape.
+    pub fn verify_cedar_policies(&self, policies: &str) -> Result<(), ValidationError> {
+        let references = action_references(policies).map_err(|message| {
+            telemetry::record_parse_error();
+            ValidationError::InvalidJson(format!("wrong format in cedar policies - {}", message))
+        })?;
+
+        // Groups are valid references too, the rest must be actions (or aliases) of the universe
+        let actions: HashSet<String> = references
+            .into_iter()
+            .filter(|id| self.universe_subtree(id).is_empty())
+            .collect();
+        self.try_perm_from_actions(actions)?;
+
+        Ok(())
+    }
+}
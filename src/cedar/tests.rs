@@ -0,0 +1,128 @@
+use super::*;
+
+fn manager() -> PermissionManager {
+    PermissionManager::from_json(
+        r#"{"post": {"view": true, "edit": true, "comment": {"create": true}}, "export": true}"#,
+    )
+}
+
+#[test]
+fn literal_test() {
+    assert_eq!(literal("post:view"), r#""post:view""#);
+    assert_eq!(literal(r#"a"b\c"#), r#""a\"b\\c""#);
+    assert_eq!(entity("User", "alice"), r#"User::"alice""#);
+
+    assert_eq!(
+        parse_literal(r#""a\"b\\c\n\'" rest"#),
+        Ok((String::from("a\"b\\c\n'"), " rest"))
+    );
+    assert_eq!(parse_literal("post").is_err(), true);
+    assert_eq!(parse_literal(r#""post"#).is_err(), true);
+    assert_eq!(parse_literal(r#""post\q""#).is_err(), true);
+}
+
+#[test]
+fn action_references_test() {
+    let policies = r#"
+        // permit (principal, action == Action::"commented", resource);
+        permit (
+            principal == User::"Action::\"in a string\"",
+            action in [Action::"post:view", App::Action :: "post:edit"],
+            resource
+        ) when { resource.tag == "Action" };
+        forbid (principal, action == MyAction::"not an action", resource);
+    "#;
+
+    assert_eq!(
+        action_references(policies),
+        Ok(BTreeSet::from([
+            String::from("post:edit"),
+            String::from("post:view")
+        ]))
+    );
+    assert_eq!(action_references("").unwrap().is_empty(), true);
+    assert_eq!(
+        action_references(r#"action == Action::"post"#).is_err(),
+        true
+    );
+}
+
+#[test]
+fn to_cedar_policy_test() {
+    let perm =
+        manager().perm_from_json(r#"{"post": {"comment": {"create": true}}, "export": true}"#);
+
+    assert_eq!(
+        perm.to_cedar_policy("Team", "a\"b"),
+        "permit (\n    principal == Team::\"a\\\"b\",\n    action in [Action::\"export\", Action::\"post:comment:create\"],\n    resource\n);\n"
+    );
+    assert_eq!(
+        Permission::from_actions(HashSet::new()).to_cedar_policy("User", "alice"),
+        ""
+    );
+}
+
+#[test]
+fn to_cedar_entities_test() {
+    let entities: Value = serde_json::from_str(&manager().to_cedar_entities()).unwrap();
+    let parents: BTreeMap<String, Vec<Value>> = entities
+        .as_array()
+        .unwrap()
+        .iter()
+        .map(|entity| {
+            assert_eq!(entity["uid"]["type"], ACTION_TYPE);
+            assert_eq!(entity["attrs"], json!({}));
+            (
+                String::from(entity["uid"]["id"].as_str().unwrap()),
+                entity["parents"].as_array().unwrap().clone(),
+            )
+        })
+        .collect();
+
+    assert_eq!(
+        parents,
+        BTreeMap::from([
+            (String::from("export"), vec![]),
+            (String::from("post"), vec![]),
+            (String::from("post:comment"), vec![action_uid("post")]),
+            (
+                String::from("post:comment:create"),
+                vec![action_uid("post:comment")]
+            ),
+            (String::from("post:edit"), vec![action_uid("post")]),
+            (String::from("post:view"), vec![action_uid("post")]),
+        ])
+    );
+}
+
+#[test]
+fn verify_cedar_policies_test() {
+    let manager = manager();
+
+    // Policies generated from the manager permissions are consistent with it
+    let perm = manager.perm_from_json(r#"{"post": true, "export": true}"#);
+    assert_eq!(
+        manager.verify_cedar_policies(&perm.to_cedar_policy("User", "alice")),
+        Ok(())
+    );
+    assert_eq!(
+        manager.verify_cedar_policies(
+            r#"permit (principal, action in [Action::"post", Action::"post:comment"], resource);"#
+        ),
+        Ok(())
+    );
+
+    match manager.verify_cedar_policies(
+        r#"permit (principal, action in [Action::"post:share", Action::"imports", Action::"post:view"], resource);"#,
+    ) {
+        Err(ValidationError::UnknownActions(unknown)) => assert_eq!(
+            unknown.iter().map(|(action, _)| action.as_str()).collect::<Vec<&str>>(),
+            vec!["imports", "post:share"]
+        ),
+        _ => panic!("policies with actions not in the universe should have failed"),
+    }
+    match manager.verify_cedar_policies(r#"permit (principal, action == Action::"post"#) {
+        Err(ValidationError::InvalidJson(_)) => (),
+        _ => panic!("policies with unterminated strings should have failed"),
+    }
+}
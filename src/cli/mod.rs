@@ -0,0 +1,229 @@
+use serde_json::Value;
+use simple_perm_manager::{ActionNamePolicy, Permission, PermissionManager, ACTION_DIVIDER};
+use std::collections::BTreeMap;
+
+//// TESTS ////
+#[cfg(test)]
+mod tests;
+
+pub const USAGE: &str = "usage:
+    spm validate <universe.json> <perm.json>
+    spm convert --to <json|yaml> <perm.json>
+    spm inspect <perm.json>
+    spm check <perm.json> <action>
+    spm diff <old.json> <new.json>
+    spm universe-diff <old-universe.json> <new-universe.json>
+    spm merge <perm.json> <perm.json>...
+
+Use '-' as a file path to read from the standard input.";
+
+/// Result of a command that ran: text to print and whether the files checked passed.
+#[derive(Debug, PartialEq, Eq)]
+pub struct Report {
+    pub output: String,
+    pub success: bool,
+}
+
+impl Report {
+    fn success(output: String) -> Report {
+        Report {
+            output,
+            success: true,
+        }
+    }
+
+    fn failure(output: String) -> Report {
+        Report {
+            output,
+            success: false,
+        }
+    }
+}
+
+/// Runs the command in `args` (program name excluded), reading files with `read`.  
+/// Returns an error message for wrong usage and unreadable or unparseable files.
+pub fn run<R>(args: &[String], read: R) -> Result<Report, String>
+where
+    R: Fn(&str) -> Result<String, String>,
+{
+    let args: Vec<&str> = args.iter().map(String::as_str).collect();
+    match args.as_slice() {
+        ["validate", universe, perm] => validate(&read(universe)?, &read(perm)?),
+        ["convert", "--to", format, perm] => convert(&read_perm(&read, perm)?, format),
+        ["inspect", perm] => Ok(inspect(&read_perm(&read, perm)?)),
+        ["check", perm, action] => Ok(check(&read_perm(&read, perm)?, action)),
+        ["diff", old, new] => Ok(diff(&read_perm(&read, old)?, &read_perm(&read, new)?)),
+        ["universe-diff", old, new] => {
+            let diff = read_universe(&read, old)?.diff_universe(&read_universe(&read, new)?);
+            Ok(match diff.is_empty() {
+                true => Report::success(diff.to_string()),
+                false => Report::failure(diff.to_string()),
+            })
+        }
+        ["merge", perms @ ..] if perms.len() >= 2 => {
+            let perms = perms
+                .iter()
+                .map(|path| read_perm(&read, path))
+                .collect::<Result<Vec<Permission>, String>>()?;
+            Ok(Report::success(Permission::union_all(&perms).to_json()))
+        }
+        _ => Err(String::from(USAGE)),
+    }
+}
+
+// Reads an unmanaged permission from the file at `path`.
+fn read_perm<R>(read: &R, path: &str) -> Result<Permission, String>
+where
+    R: Fn(&str) -> Result<String, String>,
+{
+    Permission::try_from_json(&read(path)?, &ActionNamePolicy::new())
+        .map_err(|error| format!("{}: {}", path, error))
+}
+
+// Reads a universe from the file at `path`.
+fn read_universe<R>(read: &R, path: &str) -> Result<PermissionManager, String>
+where
+    R: Fn(&str) -> Result<String, String>,
+{
+    PermissionManager::try_from_json(&read(path)?)
+        .map_err(|error| format!("{}: invalid universe: {}", path, error))
+}
+
+fn validate(universe_json: &str, perm_json: &str) -> Result<Report, String> {
+    let manager = PermissionManager::try_from_json(universe_json)
+        .map_err(|error| format!("invalid universe: {}", error))?;
+
+    Ok(match manager.try_perm_from_json(perm_json) {
+        Ok(perm) => Report::success(format!("valid: {} actions", perm.actions().count())),
+        Err(error) => Report::failure(format!("invalid: {}", error)),
+    })
+}
+
+fn convert(perm: &Permission, format: &str) -> Result<Report, String> {
+    match format {
+        "json" => Ok(Report::success(perm.to_json())),
+        "yaml" => {
+            let value: Value = serde_json::from_str(&perm.to_json())
+                .expect("permission JSON should always be valid");
+            let mut yaml = String::new();
+            write_yaml(&value, 0, &mut yaml);
+            Ok(Report::success(yaml.trim_end().to_string()))
+        }
+        _ => Err(format!(
+            "unknown format '{}', expected 'json' or 'yaml'",
+            format
+        )),
+    }
+}
+
+fn inspect(perm: &Permission) -> Report {
+    let mut actions: Vec<&str> = perm.actions().collect();
+    actions.sort();
+
+    let mut lines = vec![format!("actions ({}):", actions.len())];
+    lines.extend(actions.iter().map(|action| format!("  {}", action)));
+    lines.push(String::from("groups:"));
+    for (level, groups) in perm.groups().into_iter().enumerate() {
+        let mut groups: Vec<String> = groups.into_iter().collect();
+        groups.sort();
+        lines.push(format!("  level {}: {}", level + 1, groups.join(", ")));
+    }
+
+    Report::success(lines.join("\n"))
+}
+
+fn check(perm: &Permission, action: &str) -> Report {
+    if perm.contains_action(action) {
+        Report::success(String::from("allowed"))
+    } else {
+        Report::failure(String::from("denied"))
+    }
+}
+
+// Lists the actions added and removed from `old` to `new` under their top level group, sorted. Fails if there are differences.
+fn diff(old: &Permission, new: &Permission) -> Report {
+    let mut subtrees: BTreeMap<&str, Vec<(&str, char)>> = BTreeMap::new();
+    let changes = new
+        .actions()
+        .filter(|action| !old.contains_action(action))
+        .map(|action| (action, '+'))
+        .chain(
+            old.actions()
+                .filter(|action| !new.contains_action(action))
+                .map(|action| (action, '-')),
+        );
+    for (action, sign) in changes {
+        let subtree = match action.split_once(ACTION_DIVIDER) {
+            Some((group, _)) => group,
+            None => "",
+        };
+        subtrees.entry(subtree).or_default().push((action, sign));
+    }
+
+    if subtrees.is_empty() {
+        return Report::success(String::from("no differences"));
+    }
+    let mut lines = Vec::new();
+    for (subtree, mut changes) in subtrees {
+        changes.sort();
+        lines.push(match subtree {
+            "" => String::from("(top level):"),
+            _ => format!("{}:", subtree),
+        });
+        lines.extend(
+            changes
+                .iter()
+                .map(|(action, sign)| format!("  {} {}", sign, action)),
+        );
+    }
+
+    Report::failure(lines.join("\n"))
+}
+
+// Writes the value as YAML block mappings indented by `indent` levels. Permission JSON only has objects and `true` leaves.
+fn write_yaml(value: &Value, indent: usize, yaml: &mut String) {
+    match value {
+        Value::Object(map) if map.is_empty() => yaml.push_str("{}\n"),
+        Value::Object(map) => {
+            for (key, value) in map {
+                yaml.push_str(&"  ".repeat(indent));
+                yaml.push_str(&yaml_key(key));
+                yaml.push(':');
+                match value {
+                    Value::Object(map) if !map.is_empty() => {
+                        yaml.push('\n');
+                        write_yaml(value, indent + 1, yaml);
+                    }
+                    _ => {
+                        yaml.push(' ');
+                        write_yaml(value, indent + 1, yaml);
+                    }
+                }
+            }
+        }
+        _ => {
+            yaml.push_str(&value.to_string());
+            yaml.push('\n');
+        }
+    }
+}
+
+// Returns the key unquoted if YAML would read it back as the same string, or as a JSON string, which is valid YAML, otherwise.
+fn yaml_key(key: &str) -> String {
+    let plain = !key.is_empty()
+        && !key.starts_with('-')
+        && key
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || c == '_' || c == '-')
+        && key.parse::<f64>().is_err()
+        && !matches!(
+            key.to_ascii_lowercase().as_str(),
+            "true" | "false" | "yes" | "no" | "on" | "off" | "y" | "n" | "null"
+        );
+
+    if plain {
+        key.to_string()
+    } else {
+        Value::from(key).to_string()
+    }
+}
@@ -0,0 +1,238 @@
+use super::*;
+use std::collections::HashMap;
+
+const UNIVERSE: &str = r#"{"building": {"view": true, "edit": true, "room": {"view": true}}, "user": {"delete": true}}"#;
+
+fn run_with(args: &[&str], files: &[(&str, &str)]) -> Result<Report, String> {
+    let files: HashMap<&str, &str> = files.iter().cloned().collect();
+    let args: Vec<String> = args.iter().map(|arg| arg.to_string()).collect();
+
+    run(&args, |path| {
+        files
+            .get(path)
+            .map(|content| content.to_string())
+            .ok_or(format!("{}: not found", path))
+    })
+}
+
+#[test]
+fn validate_test() {
+    let report = run_with(
+        &["validate", "universe.json", "perm.json"],
+        &[
+            ("universe.json", UNIVERSE),
+            (
+                "perm.json",
+                r#"{"building": {"view": true, "room": {"view": true}}}"#,
+            ),
+        ],
+    );
+    assert_eq!(
+        report,
+        Ok(Report::success(String::from("valid: 2 actions")))
+    );
+
+    let report = run_with(
+        &["validate", "universe.json", "perm.json"],
+        &[
+            ("universe.json", UNIVERSE),
+            ("perm.json", r#"{"building": {"veiw": true}}"#),
+        ],
+    );
+    assert_eq!(
+        report,
+        Ok(Report::failure(String::from(
+            "invalid: actions not allowed in permission manager: building:veiw (did you mean 'building:view'?)"
+        )))
+    );
+
+    let report = run_with(
+        &["validate", "universe.json", "perm.json"],
+        &[("universe.json", "{"), ("perm.json", "{}")],
+    );
+    assert_eq!(report.unwrap_err().starts_with("invalid universe: "), true);
+    let report = run_with(&["validate", "universe.json", "perm.json"], &[]);
+    assert_eq!(report, Err(String::from("universe.json: not found")));
+}
+
+#[test]
+fn convert_test() {
+    let files = [(
+        "perm.json",
+        r#"{"building": {"view": true, "room": {"edit": true}}, "true": {"2": true, "a b": true}}"#,
+    )];
+
+    assert_eq!(
+        run_with(&["convert", "--to", "yaml", "perm.json"], &files),
+        Ok(Report::success(String::from(
+            "building:\n  room:\n    edit: true\n  view: true\n\"true\":\n  \"2\": true\n  \"a b\": true"
+        )))
+    );
+    assert_eq!(
+        run_with(&["convert", "--to", "json", "perm.json"], &files),
+        Ok(Report::success(String::from(
+            r#"{"building":{"room":{"edit":true},"view":true},"true":{"2":true,"a b":true}}"#
+        )))
+    );
+    assert_eq!(
+        run_with(
+            &["convert", "--to", "yaml", "perm.json"],
+            &[("perm.json", "{}")]
+        ),
+        Ok(Report::success(String::from("{}")))
+    );
+    assert_eq!(
+        run_with(&["convert", "--to", "toml", "perm.json"], &files),
+        Err(String::from(
+            "unknown format 'toml', expected 'json' or 'yaml'"
+        ))
+    );
+    assert_eq!(
+        run_with(
+            &["convert", "--to", "yaml", "perm.json"],
+            &[("perm.json", "[]")]
+        )
+        .unwrap_err()
+        .starts_with("perm.json: "),
+        true
+    );
+}
+
+#[test]
+fn inspect_test() {
+    let report = run_with(&["inspect", "perm.json"], &[("perm.json", UNIVERSE)]);
+
+    assert_eq!(
+        report,
+        Ok(Report::success(String::from(
+            "actions (4):
+  building:edit
+  building:room:view
+  building:view
+  user:delete
+groups:
+  level 1: building, user
+  level 2: building:room"
+        )))
+    );
+}
+
+#[test]
+fn check_test() {
+    let files = [("perm.json", r#"{"building": {"view": true}}"#)];
+
+    assert_eq!(
+        run_with(&["check", "perm.json", "building:view"], &files),
+        Ok(Report::success(String::from("allowed")))
+    );
+    assert_eq!(
+        run_with(&["check", "perm.json", "building:edit"], &files),
+        Ok(Report::failure(String::from("denied")))
+    );
+}
+
+#[test]
+fn usage_test() {
+    assert_eq!(run_with(&[], &[]), Err(String::from(USAGE)));
+    assert_eq!(
+        run_with(&["check", "perm.json"], &[]),
+        Err(String::from(USAGE))
+    );
+    assert_eq!(
+        run_with(&["convert", "yaml", "perm.json"], &[]),
+        Err(String::from(USAGE))
+    );
+}
+
+#[test]
+fn diff_test() {
+    let files = [
+        (
+            "old.json",
+            r#"{"building": {"view": true, "edit": true}, "login": true}"#,
+        ),
+        (
+            "new.json",
+            r#"{"building": {"view": true, "room": {"edit": true}}, "user": {"view": true}, "admin": true}"#,
+        ),
+    ];
+
+    assert_eq!(
+        run_with(&["diff", "old.json", "new.json"], &files),
+        Ok(Report::failure(String::from(
+            "(top level):
+  + admin
+  - login
+building:
+  - building:edit
+  + building:room:edit
+user:
+  + user:view"
+        )))
+    );
+    assert_eq!(
+        run_with(&["diff", "old.json", "old.json"], &files),
+        Ok(Report::success(String::from("no differences")))
+    );
+}
+
+#[test]
+fn universe_diff_test() {
+    let files = [
+        (
+            "v1.json",
+            r#"{"post": {"view": true, "audit": true}, "publish": true}"#,
+        ),
+        (
+            "v2.json",
+            r#"{"post": {"view": true, "edit": true, "publish": true}}"#,
+        ),
+        ("broken.json", r#"{"post": 1}"#),
+    ];
+
+    assert_eq!(
+        run_with(&["universe-diff", "v1.json", "v2.json"], &files),
+        Ok(Report::failure(String::from(
+            "added:
+  + post:edit
+removed:
+  - post:audit
+moved:
+  ~ publish -> post:publish"
+        )))
+    );
+    assert_eq!(
+        run_with(&["universe-diff", "v1.json", "v1.json"], &files),
+        Ok(Report::success(String::from("no differences")))
+    );
+    assert_eq!(
+        run_with(&["universe-diff", "v1.json", "broken.json"], &files)
+            .unwrap_err()
+            .starts_with("broken.json: invalid universe: "),
+        true
+    );
+}
+
+#[test]
+fn merge_test() {
+    let files = [
+        ("a.json", r#"{"building": {"view": true}}"#),
+        ("b.json", r#"{"building": {"edit": true}}"#),
+        ("c.json", r#"{"user": {"view": true}}"#),
+    ];
+
+    assert_eq!(
+        run_with(&["merge", "a.json", "b.json", "c.json"], &files),
+        Ok(Report::success(String::from(
+            r#"{"building":{"edit":true,"view":true},"user":{"view":true}}"#
+        )))
+    );
+    assert_eq!(
+        run_with(&["merge", "a.json"], &files),
+        Err(String::from(USAGE))
+    );
+    assert_eq!(
+        run_with(&["merge", "a.json", "d.json"], &files),
+        Err(String::from("d.json: not found"))
+    );
+}
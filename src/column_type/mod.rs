@@ -0,0 +1,123 @@
+use crate::action_name_policy::ActionNamePolicy;
+use crate::permission::Permission;
+use crate::validation_error::ValidationError;
+
+//// TESTS ////
+#[cfg(test)]
+mod tests;
+
+// Version byte leading the JSONB binary format of Postgres
+#[cfg(any(feature = "postgres", feature = "diesel"))]
+const JSONB_VERSION: u8 = 1;
+
+// Returns the JSONB binary format of a permission: the version byte followed by the actions JSON string
+#[cfg(any(feature = "postgres", feature = "diesel"))]
+fn to_jsonb(perm: &Permission) -> Vec<u8> {
+    let mut bytes = vec![JSONB_VERSION];
+    bytes.extend_from_slice(perm.to_json().as_bytes());
+    bytes
+}
+
+// Parses an unmanaged permission from the JSONB binary format of Postgres
+#[cfg(any(feature = "postgres", feature = "diesel"))]
+fn from_jsonb(bytes: &[u8]) -> Result<Permission, ValidationError> {
+    match bytes.split_first() {
+        Some((&JSONB_VERSION, json)) => from_json_bytes(json),
+        _ => Err(ValidationError::InvalidJson(String::from(
+            "wrong format in permission jsonb value",
+        ))),
+    }
+}
+
+// Parses an unmanaged permission from the bytes of an actions JSON string
+fn from_json_bytes(bytes: &[u8]) -> Result<Permission, ValidationError> {
+    let json = std::str::from_utf8(bytes).map_err(|_| {
+        ValidationError::InvalidJson(String::from("wrong format in permission json string"))
+    })?;
+
+    Permission::try_from_json(json, &ActionNamePolicy::new())
+}
+
+// `Permission` as a sqlx Postgres column or parameter, stored as JSONB (or read from JSON) in the `to_json` format
+#[cfg(feature = "postgres")]
+mod sqlx_postgres {
+    use super::*;
+    use sqlx::encode::IsNull;
+    use sqlx::error::BoxDynError;
+    use sqlx::postgres::{PgArgumentBuffer, PgTypeInfo, PgValueFormat, PgValueRef};
+    use sqlx::{Decode, Encode, Postgres, Type, TypeInfo, ValueRef};
+
+    impl Type<Postgres> for Permission {
+        fn type_info() -> PgTypeInfo {
+            PgTypeInfo::with_name("jsonb")
+        }
+
+        fn compatible(ty: &PgTypeInfo) -> bool {
+            ty.name().eq_ignore_ascii_case("jsonb") || ty.name().eq_ignore_ascii_case("json")
+        }
+    }
+
+    impl Encode<'_, Postgres> for Permission {
+        fn encode_by_ref(&self, buf: &mut PgArgumentBuffer) -> Result<IsNull, BoxDynError> {
+            buf.extend_from_slice(&to_jsonb(self));
+
+            Ok(IsNull::No)
+        }
+    }
+
+    impl<'r> Decode<'r, Postgres> for Permission {
+        fn decode(value: PgValueRef<'r>) -> Result<Permission, BoxDynError> {
+            let jsonb = value.type_info().name().eq_ignore_ascii_case("jsonb");
+            let perm = match value.format() {
+                PgValueFormat::Binary if jsonb => from_jsonb(value.as_bytes()?)?,
+                _ => from_json_bytes(value.as_bytes()?)?,
+            };
+
+            Ok(perm)
+        }
+    }
+}
+
+// `Permission` as a rusqlite column or parameter, stored as TEXT in the `to_json` format
+#[cfg(feature = "sqlite")]
+mod rusqlite_text {
+    use super::*;
+    use rusqlite::types::{FromSql, FromSqlError, FromSqlResult, ToSql, ToSqlOutput, ValueRef};
+
+    impl ToSql for Permission {
+        fn to_sql(&self) -> rusqlite::Result<ToSqlOutput<'_>> {
+            Ok(ToSqlOutput::from(self.to_json()))
+        }
+    }
+
+    impl FromSql for Permission {
+        fn column_result(value: ValueRef<'_>) -> FromSqlResult<Permission> {
+            from_json_bytes(value.as_bytes()?).map_err(|error| FromSqlError::Other(Box::new(error)))
+        }
+    }
+}
+
+// `Permission` as a Diesel `Jsonb` column or expression for the Postgres backend, in the `to_json` format
+#[cfg(feature = "diesel")]
+mod diesel_jsonb {
+    use super::*;
+    use diesel::deserialize::{self, FromSql};
+    use diesel::pg::{Pg, PgValue};
+    use diesel::serialize::{self, IsNull, Output, ToSql};
+    use diesel::sql_types::Jsonb;
+    use std::io::Write;
+
+    impl ToSql<Jsonb, Pg> for Permission {
+        fn to_sql<'b>(&'b self, out: &mut Output<'b, '_, Pg>) -> serialize::Result {
+            out.write_all(&to_jsonb(self))?;
+
+            Ok(IsNull::No)
+        }
+    }
+
+    impl FromSql<Jsonb, Pg> for Permission {
+        fn from_sql(value: PgValue<'_>) -> deserialize::Result<Permission> {
+            Ok(from_jsonb(value.as_bytes())?)
+        }
+    }
+}
@@ -0,0 +1,70 @@
+use super::*;
+
+#[cfg(any(feature = "postgres", feature = "diesel"))]
+#[test]
+fn jsonb_test() {
+    use std::collections::HashSet;
+
+    let perm = Permission::from_json(r#"{"post": {"view": true, "edit": true}}"#);
+    let bytes = to_jsonb(&perm);
+
+    assert_eq!(bytes[0], JSONB_VERSION);
+    assert_eq!(
+        *from_jsonb(&bytes).unwrap().get_actions(),
+        HashSet::from([String::from("post:view"), String::from("post:edit")])
+    );
+    assert_eq!(from_jsonb(&bytes[1..]).is_err(), true);
+    assert_eq!(from_jsonb(&[]).is_err(), true);
+    assert_eq!(from_jsonb(&[JSONB_VERSION, 0xff]).is_err(), true);
+}
+
+#[cfg(feature = "postgres")]
+#[test]
+fn sqlx_type_test() {
+    use sqlx::postgres::PgTypeInfo;
+    use sqlx::{Postgres, Type};
+
+    assert_eq!(
+        <Permission as Type<Postgres>>::compatible(&PgTypeInfo::with_name("JSONB")),
+        true
+    );
+    assert_eq!(
+        <Permission as Type<Postgres>>::compatible(&PgTypeInfo::with_name("json")),
+        true
+    );
+    assert_eq!(
+        <Permission as Type<Postgres>>::compatible(&PgTypeInfo::with_name("bytea")),
+        false
+    );
+}
+
+#[cfg(feature = "sqlite")]
+#[test]
+fn rusqlite_test() {
+    let connection = rusqlite::Connection::open_in_memory().unwrap();
+    connection
+        .execute("CREATE TABLE users (name TEXT, perm TEXT)", [])
+        .unwrap();
+    let perm = Permission::from_json(r#"{"post": {"view": true}}"#);
+    connection
+        .execute(
+            "INSERT INTO users (name, perm) VALUES (?1, ?2), ('bob', '{\"post\": 1}')",
+            rusqlite::params!["alice", perm],
+        )
+        .unwrap();
+
+    let loaded: Permission = connection
+        .query_row("SELECT perm FROM users WHERE name = 'alice'", [], |row| {
+            row.get(0)
+        })
+        .unwrap();
+    assert_eq!(*loaded.get_actions(), *perm.get_actions());
+    assert_eq!(
+        connection
+            .query_row("SELECT perm FROM users WHERE name = 'bob'", [], |row| {
+                row.get::<_, Permission>(0)
+            })
+            .is_err(),
+        true
+    );
+}
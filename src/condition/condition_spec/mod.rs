@@ -0,0 +1,218 @@
+use super::expression::{compare_values, Expression};
+use super::{Condition, Context};
+use crate::validation_error::ValidationError;
+use serde_json::{Map, Value};
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+//// TESTS ////
+#[cfg(test)]
+mod tests;
+
+/// Serializable [`Condition`](crate::Condition) definition, so conditional grants can be stored in JSON alongside permissions.
+///
+/// Serialized as a JSON object with a `"type"` key:
+/// - `{"type": "time_window", "from": <ms>|null, "until": <ms>|null}` with milliseconds since the UNIX epoch.
+/// - `{"type": "attribute", "attribute": "region", "op": "==", "value": "EU"}` with any [`Expression`](crate::Expression) comparison operator.
+/// - `{"type": "expression", "expression": "amount <= 1000"}`.
+///
+/// # Examples:
+///
+/// ```
+/// use simple_perm_manager::{Condition, ConditionSpec, Context};
+///
+/// let spec = ConditionSpec::from_json(r#"{"type": "attribute", "attribute": "region", "op": "==", "value": "EU"}"#);
+///
+/// assert!(spec.evaluate(&Context::new().with("region", "EU")));
+/// assert_eq!(ConditionSpec::from_json(&spec.to_json()), spec);
+/// ```
+#[derive(Clone, Debug, PartialEq)]
+pub enum ConditionSpec {
+    /// Holds between two times, checked against the current time or the time of the check. Both ends are optional and the
+    /// period includes `from` and excludes `until`.
+    TimeWindow {
+        from: Option<SystemTime>,
+        until: Option<SystemTime>,
+    },
+    /// Holds when a [`Context`](crate::Context) attribute compares with a value. Missing attributes are `null`.
+    Attribute {
+        attribute: String,
+        op: String,
+        value: Value,
+    },
+    /// Holds when the [`Expression`](crate::Expression) holds.
+    Expression(Expression),
+}
+
+impl ConditionSpec {
+    /// Creates a [`ConditionSpec::Attribute`](crate::ConditionSpec::Attribute) comparing a [`Context`](crate::Context) attribute with a value.
+    ///
+    /// # Panics:
+    ///
+    /// Panics if the operator is not one of `==`, `!=`, `<`, `<=`, `>`, `>=`.
+    pub fn attribute<V: Into<Value>>(attribute: &str, op: &str, value: V) -> ConditionSpec {
+        if compare_values(&Value::Null, op, &Value::Null).is_none() {
+            panic!("invalid comparison operator in condition: {}", op);
+        }
+
+        ConditionSpec::Attribute {
+            attribute: String::from(attribute),
+            op: String::from(op),
+            value: value.into(),
+        }
+    }
+
+    /// Serializes the [`ConditionSpec`](crate::ConditionSpec) into a JSON string.
+    pub fn to_json(&self) -> String {
+        Value::Object(self.to_map()).to_string()
+    }
+
+    /// Creates a [`ConditionSpec`](crate::ConditionSpec) from a JSON string created with
+    /// [`ConditionSpec::to_json`](crate::ConditionSpec::to_json()).
+    ///
+    /// # Panics:
+    ///
+    /// Panics if the JSON string is not a valid condition definition.
+    pub fn from_json(json: &str) -> ConditionSpec {
+        match ConditionSpec::try_from_json(json) {
+            Ok(spec) => spec,
+            Err(error) => panic!("{}", error),
+        }
+    }
+
+    /// Creates a [`ConditionSpec`](crate::ConditionSpec) from a JSON string, returning an error instead of panicking.
+    ///
+    /// # Errors:
+    ///
+    /// - Returns [`ValidationError::InvalidJson`](crate::ValidationError::InvalidJson) if the JSON string is not a valid condition definition.
+    /// - Returns [`ValidationError::InvalidExpression`](crate::ValidationError::InvalidExpression) if the expression of the condition is not valid.
+    pub fn try_from_json(json: &str) -> Result<ConditionSpec, ValidationError> {
+        match serde_json::from_str(json) {
+            Ok(Value::Object(map)) => ConditionSpec::try_from_map(&map),
+            _ => Err(ValidationError::InvalidJson(String::from(
+                "wrong format in condition json string",
+            ))),
+        }
+    }
+
+    #[doc(hidden)]
+    /// Serializes the [`ConditionSpec`](crate::ConditionSpec) into a serde_json::Map.
+    pub fn to_map(&self) -> Map<String, Value> {
+        let mut map = Map::new();
+        match self {
+            ConditionSpec::TimeWindow { from, until } => {
+                map.insert(String::from("type"), Value::from("time_window"));
+                map.insert(String::from("from"), time_to_value(*from));
+                map.insert(String::from("until"), time_to_value(*until));
+            }
+            ConditionSpec::Attribute {
+                attribute,
+                op,
+                value,
+            } => {
+                map.insert(String::from("type"), Value::from("attribute"));
+                map.insert(String::from("attribute"), Value::from(attribute.as_str()));
+                map.insert(String::from("op"), Value::from(op.as_str()));
+                map.insert(String::from("value"), value.clone());
+            }
+            ConditionSpec::Expression(expression) => {
+                map.insert(String::from("type"), Value::from("expression"));
+                map.insert(String::from("expression"), Value::from(expression.as_str()));
+            }
+        }
+
+        map
+    }
+
+    #[doc(hidden)]
+    /// Deserializes a [`ConditionSpec`](crate::ConditionSpec) from a serde_json::Map created with `to_map`.
+    pub fn try_from_map(map: &Map<String, Value>) -> Result<ConditionSpec, ValidationError> {
+        let invalid = |message: &str| {
+            ValidationError::InvalidJson(format!(
+                "wrong format in condition json string - {}",
+                message
+            ))
+        };
+
+        match map.get("type").and_then(Value::as_str) {
+            Some("time_window") => Ok(ConditionSpec::TimeWindow {
+                from: value_to_time(map.get("from")).ok_or_else(|| invalid("invalid from"))?,
+                until: value_to_time(map.get("until")).ok_or_else(|| invalid("invalid until"))?,
+            }),
+            Some("attribute") => {
+                let attribute = map
+                    .get("attribute")
+                    .and_then(Value::as_str)
+                    .ok_or_else(|| invalid("missing attribute"))?;
+                let op = map
+                    .get("op")
+                    .and_then(Value::as_str)
+                    .filter(|op| compare_values(&Value::Null, op, &Value::Null).is_some())
+                    .ok_or_else(|| invalid("invalid op"))?;
+                let value = map.get("value").ok_or_else(|| invalid("missing value"))?;
+
+                Ok(ConditionSpec::attribute(attribute, op, value.clone()))
+            }
+            Some("expression") => {
+                let expression = map
+                    .get("expression")
+                    .and_then(Value::as_str)
+                    .ok_or_else(|| invalid("missing expression"))?;
+
+                Ok(ConditionSpec::Expression(Expression::try_parse(
+                    expression,
+                )?))
+            }
+            _ => Err(invalid("unknown type")),
+        }
+    }
+}
+
+impl Condition for ConditionSpec {
+    fn evaluate(&self, context: &Context) -> bool {
+        self.evaluate_at(context, SystemTime::now())
+    }
+
+    fn evaluate_at(&self, context: &Context, at: SystemTime) -> bool {
+        match self {
+            ConditionSpec::TimeWindow { from, until } => {
+                from.is_none_or(|from| from <= at) && until.is_none_or(|until| at < until)
+            }
+            ConditionSpec::Attribute {
+                attribute,
+                op,
+                value,
+            } => {
+                let attribute = context.get(attribute).unwrap_or(&Value::Null);
+                compare_values(attribute, op, value).unwrap_or(false)
+            }
+            ConditionSpec::Expression(expression) => expression.evaluate(context),
+        }
+    }
+}
+
+#[doc(hidden)]
+/// Serializes an optional time as milliseconds since the UNIX epoch, or `null`.
+pub fn time_to_value(time: Option<SystemTime>) -> Value {
+    match time {
+        Some(time) => {
+            let millis = time
+                .duration_since(UNIX_EPOCH)
+                .unwrap_or(Duration::ZERO)
+                .as_millis();
+            Value::from(millis as u64)
+        }
+        None => Value::Null,
+    }
+}
+
+#[doc(hidden)]
+/// Deserializes an optional time serialized with `time_to_value`. Returns `None` if the value is not valid.
+/// Missing values are deserialized as no time.
+pub fn value_to_time(value: Option<&Value>) -> Option<Option<SystemTime>> {
+    match value {
+        None | Some(Value::Null) => Some(None),
+        Some(value) => value
+            .as_u64()
+            .map(|millis| Some(UNIX_EPOCH + Duration::from_millis(millis))),
+    }
+}
@@ -0,0 +1,95 @@
+use super::*;
+
+#[test]
+fn attribute_test() {
+    let spec = ConditionSpec::attribute("amount", "<=", 1000);
+
+    assert_eq!(spec.evaluate(&Context::new().with("amount", 250)), true);
+    assert_eq!(spec.evaluate(&Context::new().with("amount", 5000)), false);
+    assert_eq!(spec.evaluate(&Context::new()), false);
+
+    match std::panic::catch_unwind(|| {
+        ConditionSpec::attribute("amount", "=~", 1000);
+    }) {
+        Ok(_) => panic!("an invalid operator should have panicked"),
+        Err(_) => (),
+    }
+}
+
+#[test]
+fn time_window_test() {
+    let now = SystemTime::now();
+    let hour = Duration::from_secs(3600);
+
+    let active = ConditionSpec::TimeWindow {
+        from: Some(now - hour),
+        until: Some(now + hour),
+    };
+    let expired = ConditionSpec::TimeWindow {
+        from: None,
+        until: Some(now - hour),
+    };
+    let future = ConditionSpec::TimeWindow {
+        from: Some(now + hour),
+        until: None,
+    };
+
+    assert_eq!(active.evaluate(&Context::new()), true);
+    assert_eq!(expired.evaluate(&Context::new()), false);
+    assert_eq!(future.evaluate(&Context::new()), false);
+
+    assert_eq!(active.evaluate_at(&Context::new(), now + 2 * hour), false);
+    assert_eq!(expired.evaluate_at(&Context::new(), now - 2 * hour), true);
+    assert_eq!(future.evaluate_at(&Context::new(), now + hour), true);
+}
+
+#[test]
+fn json_test() {
+    let specs = [
+        ConditionSpec::TimeWindow {
+            from: Some(UNIX_EPOCH + Duration::from_millis(1_700_000_000_000)),
+            until: None,
+        },
+        ConditionSpec::attribute("region", "==", "EU"),
+        ConditionSpec::Expression(Expression::parse("amount <= 1000 && internal")),
+    ];
+
+    for spec in specs {
+        assert_eq!(ConditionSpec::from_json(&spec.to_json()), spec);
+    }
+
+    assert_eq!(
+        ConditionSpec::attribute("region", "!=", "EU").to_json(),
+        r#"{"attribute":"region","op":"!=","type":"attribute","value":"EU"}"#
+    );
+}
+
+#[test]
+fn try_from_json_test() {
+    for json in [
+        "[]",
+        r#"{"type": "unknown"}"#,
+        r#"{"type": "time_window", "from": "yesterday"}"#,
+        r#"{"type": "attribute", "attribute": "region", "op": "=~", "value": 1}"#,
+        r#"{"type": "attribute", "attribute": "region", "op": "=="}"#,
+        r#"{"type": "expression"}"#,
+    ] {
+        assert_eq!(
+            matches!(
+                ConditionSpec::try_from_json(json),
+                Err(ValidationError::InvalidJson(_))
+            ),
+            true,
+            "{}",
+            json
+        );
+    }
+
+    assert_eq!(
+        matches!(
+            ConditionSpec::try_from_json(r#"{"type": "expression", "expression": "a <"}"#),
+            Err(ValidationError::InvalidExpression(_))
+        ),
+        true
+    );
+}
@@ -0,0 +1,382 @@
+use super::{Condition, Context};
+use crate::validation_error::ValidationError;
+use serde_json::Value;
+use std::cmp::Ordering;
+use std::fmt;
+
+//// TESTS ////
+#[cfg(test)]
+mod tests;
+
+// Constant for defining maximun nesting allowed in an expression when parsing.
+// As the parser is recursive, this prevent overflows.
+const MAX_EXPRESSION_DEPTH: u8 = 32;
+
+/// [`Condition`](crate::Condition) written in a small expression language, so conditions can be stored as strings in
+/// configuration instead of compiled closures.
+///
+/// - Values: numbers (`1000`, `2.5`), strings (`'EU'` or `"EU"`), `true`, `false`, `null` and [`Context`](crate::Context)
+///   attributes by name (`amount`). Missing attributes are `null`.
+/// - Comparisons: `==`, `!=`, `<`, `<=`, `>`, `>=`. Numbers and strings can be ordered, other values only compared for equality.
+/// - Logic: `&&`, `||`, `!` and parentheses. A value on its own holds only if it is `true`.
+///
+/// # Examples:
+///
+/// ```
+/// use simple_perm_manager::{Condition, Context, Expression};
+///
+/// let expression = Expression::parse("amount <= 1000 && region == 'EU'");
+///
+/// assert!(expression.evaluate(&Context::new().with("amount", 250).with("region", "EU")));
+/// assert!(!expression.evaluate(&Context::new().with("amount", 5000).with("region", "EU")));
+/// assert!(!expression.evaluate(&Context::new()));
+/// ```
+#[derive(Clone, Debug, PartialEq)]
+pub struct Expression {
+    source: String,
+    root: Node,
+}
+
+#[derive(Clone, Debug, PartialEq)]
+enum Node {
+    Literal(Value),
+    Attribute(String),
+    Not(Box<Node>),
+    // Chains of `&&` and `||` are kept flat, so long chains are evaluated and dropped without recursion
+    And(Vec<Node>),
+    Or(Vec<Node>),
+    Compare(Box<Node>, CompareOp, Box<Node>),
+}
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+enum CompareOp {
+    Eq,
+    Ne,
+    Lt,
+    Le,
+    Gt,
+    Ge,
+}
+
+#[derive(Clone, Debug, PartialEq)]
+enum Token {
+    Number(f64),
+    Str(String),
+    Ident(String),
+    Op(CompareOp),
+    And,
+    Or,
+    Not,
+    LParen,
+    RParen,
+}
+
+impl Expression {
+    /// Parses an [`Expression`](crate::Expression) from its source string.
+    ///
+    /// # Panics:
+    ///
+    /// Panics if the source string is not a valid expression.
+    pub fn parse(source: &str) -> Expression {
+        match Expression::try_parse(source) {
+            Ok(expression) => expression,
+            Err(error) => panic!("{}", error),
+        }
+    }
+
+    /// Parses an [`Expression`](crate::Expression) from its source string, returning an error instead of panicking.
+    ///
+    /// # Examples:
+    ///
+    /// ```
+    /// use simple_perm_manager::{Expression, ValidationError};
+    ///
+    /// assert!(Expression::try_parse("amount <= 1000").is_ok());
+    /// assert!(matches!(Expression::try_parse("amount <="), Err(ValidationError::InvalidExpression(_))));
+    /// ```
+    ///
+    /// # Errors:
+    ///
+    /// Returns [`ValidationError::InvalidExpression`](crate::ValidationError::InvalidExpression) if the source string is not a valid expression.
+    pub fn try_parse(source: &str) -> Result<Expression, ValidationError> {
+        let tokens = tokenize(source).map_err(ValidationError::InvalidExpression)?;
+        let mut parser = Parser {
+            tokens,
+            position: 0,
+        };
+        let root = parser
+            .parse_or(0)
+            .map_err(ValidationError::InvalidExpression)?;
+        if parser.position < parser.tokens.len() {
+            return Err(ValidationError::InvalidExpression(format!(
+                "unexpected token in expression: {:?}",
+                parser.tokens[parser.position]
+            )));
+        }
+
+        Ok(Expression {
+            source: String::from(source),
+            root,
+        })
+    }
+
+    /// Returns the source string of the [`Expression`](crate::Expression).
+    pub fn as_str(&self) -> &str {
+        &self.source
+    }
+}
+
+impl Condition for Expression {
+    fn evaluate(&self, context: &Context) -> bool {
+        self.root.value(context) == Value::Bool(true)
+    }
+}
+
+impl fmt::Display for Expression {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.source)
+    }
+}
+
+impl Node {
+    fn value(&self, context: &Context) -> Value {
+        match self {
+            Node::Literal(value) => value.clone(),
+            Node::Attribute(name) => context.get(name).cloned().unwrap_or(Value::Null),
+            Node::Not(node) => Value::Bool(node.value(context) != Value::Bool(true)),
+            Node::And(nodes) => Value::Bool(
+                nodes
+                    .iter()
+                    .all(|node| node.value(context) == Value::Bool(true)),
+            ),
+            Node::Or(nodes) => Value::Bool(
+                nodes
+                    .iter()
+                    .any(|node| node.value(context) == Value::Bool(true)),
+            ),
+            Node::Compare(left, op, right) => {
+                Value::Bool(compare(&left.value(context), *op, &right.value(context)))
+            }
+        }
+    }
+}
+
+#[doc(hidden)]
+/// Compares two values with a comparison operator of the expression language (`==`, `!=`, `<`, `<=`, `>`, `>=`).
+/// Returns `None` if the operator is not valid.
+pub fn compare_values(left: &Value, op: &str, right: &Value) -> Option<bool> {
+    let op = match op {
+        "==" => CompareOp::Eq,
+        "!=" => CompareOp::Ne,
+        "<" => CompareOp::Lt,
+        "<=" => CompareOp::Le,
+        ">" => CompareOp::Gt,
+        ">=" => CompareOp::Ge,
+        _ => return None,
+    };
+
+    Some(compare(left, op, right))
+}
+
+fn compare(left: &Value, op: CompareOp, right: &Value) -> bool {
+    let ordering = match (left, right) {
+        (Value::Number(l), Value::Number(r)) => l.as_f64().partial_cmp(&r.as_f64()),
+        (Value::String(l), Value::String(r)) => Some(l.cmp(r)),
+        _ => {
+            return match op {
+                CompareOp::Eq => left == right,
+                CompareOp::Ne => left != right,
+                _ => false,
+            }
+        }
+    };
+
+    match (ordering, op) {
+        (None, CompareOp::Ne) => true,
+        (None, _) => false,
+        (Some(ordering), CompareOp::Eq) => ordering == Ordering::Equal,
+        (Some(ordering), CompareOp::Ne) => ordering != Ordering::Equal,
+        (Some(ordering), CompareOp::Lt) => ordering == Ordering::Less,
+        (Some(ordering), CompareOp::Le) => ordering != Ordering::Greater,
+        (Some(ordering), CompareOp::Gt) => ordering == Ordering::Greater,
+        (Some(ordering), CompareOp::Ge) => ordering != Ordering::Less,
+    }
+}
+
+fn tokenize(source: &str) -> Result<Vec<Token>, String> {
+    let chars: Vec<char> = source.chars().collect();
+    let mut tokens = Vec::new();
+    let mut i = 0;
+
+    while i < chars.len() {
+        let c = chars[i];
+        let next = chars.get(i + 1).copied();
+        match c {
+            _ if c.is_whitespace() => i += 1,
+            '(' => {
+                tokens.push(Token::LParen);
+                i += 1;
+            }
+            ')' => {
+                tokens.push(Token::RParen);
+                i += 1;
+            }
+            '&' if next == Some('&') => {
+                tokens.push(Token::And);
+                i += 2;
+            }
+            '|' if next == Some('|') => {
+                tokens.push(Token::Or);
+                i += 2;
+            }
+            '=' if next == Some('=') => {
+                tokens.push(Token::Op(CompareOp::Eq));
+                i += 2;
+            }
+            '!' if next == Some('=') => {
+                tokens.push(Token::Op(CompareOp::Ne));
+                i += 2;
+            }
+            '!' => {
+                tokens.push(Token::Not);
+                i += 1;
+            }
+            '<' | '>' => {
+                let op = match (c, next == Some('=')) {
+                    ('<', false) => CompareOp::Lt,
+                    ('<', true) => CompareOp::Le,
+                    ('>', false) => CompareOp::Gt,
+                    _ => CompareOp::Ge,
+                };
+                tokens.push(Token::Op(op));
+                i += if next == Some('=') { 2 } else { 1 };
+            }
+            '\'' | '"' => {
+                let end = chars[i + 1..]
+                    .iter()
+                    .position(|x| *x == c)
+                    .ok_or_else(|| String::from("unterminated string in expression"))?;
+                tokens.push(Token::Str(chars[i + 1..i + 1 + end].iter().collect()));
+                i += end + 2;
+            }
+            _ if c.is_ascii_digit() || (c == '-' && next.is_some_and(|n| n.is_ascii_digit())) => {
+                let start = i;
+                i += 1;
+                while i < chars.len() && (chars[i].is_ascii_digit() || chars[i] == '.') {
+                    i += 1;
+                }
+                let number: String = chars[start..i].iter().collect();
+                let number = number
+                    .parse()
+                    .map_err(|_| format!("invalid number in expression: {}", number))?;
+                tokens.push(Token::Number(number));
+            }
+            _ if c.is_alphabetic() || c == '_' => {
+                let start = i;
+                while i < chars.len()
+                    && (chars[i].is_alphanumeric() || chars[i] == '_' || chars[i] == '.')
+                {
+                    i += 1;
+                }
+                tokens.push(Token::Ident(chars[start..i].iter().collect()));
+            }
+            _ => return Err(format!("unexpected character in expression: {}", c)),
+        }
+    }
+
+    Ok(tokens)
+}
+
+struct Parser {
+    tokens: Vec<Token>,
+    position: usize,
+}
+
+impl Parser {
+    fn next(&mut self) -> Option<Token> {
+        let token = self.tokens.get(self.position).cloned();
+        self.position += 1;
+        token
+    }
+
+    fn peek(&self) -> Option<&Token> {
+        self.tokens.get(self.position)
+    }
+
+    fn parse_or(&mut self, depth: u8) -> Result<Node, String> {
+        if depth >= MAX_EXPRESSION_DEPTH {
+            return Err(String::from("too much nesting in expression"));
+        }
+
+        let mut nodes = vec![self.parse_and(depth)?];
+        while self.peek() == Some(&Token::Or) {
+            self.position += 1;
+            nodes.push(self.parse_and(depth)?);
+        }
+
+        Ok(match nodes.len() {
+            1 => nodes.remove(0),
+            _ => Node::Or(nodes),
+        })
+    }
+
+    fn parse_and(&mut self, depth: u8) -> Result<Node, String> {
+        let mut nodes = vec![self.parse_not(depth)?];
+        while self.peek() == Some(&Token::And) {
+            self.position += 1;
+            nodes.push(self.parse_not(depth)?);
+        }
+
+        Ok(match nodes.len() {
+            1 => nodes.remove(0),
+            _ => Node::And(nodes),
+        })
+    }
+
+    fn parse_not(&mut self, depth: u8) -> Result<Node, String> {
+        if self.peek() == Some(&Token::Not) {
+            self.position += 1;
+            if depth + 1 >= MAX_EXPRESSION_DEPTH {
+                return Err(String::from("too much nesting in expression"));
+            }
+            return Ok(Node::Not(Box::new(self.parse_not(depth + 1)?)));
+        }
+
+        self.parse_compare(depth)
+    }
+
+    fn parse_compare(&mut self, depth: u8) -> Result<Node, String> {
+        let left = self.parse_primary(depth)?;
+        match self.peek() {
+            Some(Token::Op(op)) => {
+                let op = *op;
+                self.position += 1;
+                let right = self.parse_primary(depth)?;
+                Ok(Node::Compare(Box::new(left), op, Box::new(right)))
+            }
+            _ => Ok(left),
+        }
+    }
+
+    fn parse_primary(&mut self, depth: u8) -> Result<Node, String> {
+        match self.next() {
+            Some(Token::Number(number)) => Ok(Node::Literal(Value::from(number))),
+            Some(Token::Str(string)) => Ok(Node::Literal(Value::String(string))),
+            Some(Token::Ident(ident)) => Ok(match ident.as_str() {
+                "true" => Node::Literal(Value::Bool(true)),
+                "false" => Node::Literal(Value::Bool(false)),
+                "null" => Node::Literal(Value::Null),
+                _ => Node::Attribute(ident),
+            }),
+            Some(Token::LParen) => {
+                let node = self.parse_or(depth + 1)?;
+                match self.next() {
+                    Some(Token::RParen) => Ok(node),
+                    _ => Err(String::from("missing closing parenthesis in expression")),
+                }
+            }
+            Some(token) => Err(format!("unexpected token in expression: {:?}", token)),
+            None => Err(String::from("unexpected end of expression")),
+        }
+    }
+}
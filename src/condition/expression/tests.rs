@@ -0,0 +1,103 @@
+use super::*;
+
+fn eval(source: &str, context: &Context) -> bool {
+    Expression::parse(source).evaluate(context)
+}
+
+#[test]
+fn parse_test() {
+    let expression = Expression::parse("amount <= 1000 && region == 'EU'");
+    assert_eq!(expression.as_str(), "amount <= 1000 && region == 'EU'");
+    assert_eq!(expression.to_string(), "amount <= 1000 && region == 'EU'");
+
+    for source in [
+        "",
+        "amount <=",
+        "(amount > 1",
+        "amount > 1)",
+        "region == 'EU",
+        "amount # 1",
+        "a == b == c",
+        "&& a",
+    ] {
+        match std::panic::catch_unwind(|| {
+            Expression::parse(source);
+        }) {
+            Ok(_) => panic!("parsing {:?} should have panicked", source),
+            Err(_) => (),
+        }
+    }
+}
+
+#[test]
+fn try_parse_test() {
+    assert_eq!(Expression::try_parse("!internal").is_ok(), true);
+    assert_eq!(
+        Expression::try_parse("amount <").unwrap_err(),
+        ValidationError::InvalidExpression(String::from("unexpected end of expression"))
+    );
+
+    let nested = format!("{}true{}", "(".repeat(40), ")".repeat(40));
+    assert_eq!(Expression::try_parse(&nested).is_err(), true);
+    let negated = format!("{}true", "!".repeat(40));
+    assert_eq!(Expression::try_parse(&negated).is_err(), true);
+}
+
+#[test]
+fn compare_test() {
+    let context = Context::new()
+        .with("amount", 250)
+        .with("ratio", 0.5)
+        .with("region", "EU")
+        .with("internal", true);
+
+    assert_eq!(eval("amount == 250", &context), true);
+    assert_eq!(eval("amount != 250", &context), false);
+    assert_eq!(eval("amount < 250.5", &context), true);
+    assert_eq!(eval("amount >= 251", &context), false);
+    assert_eq!(eval("ratio > -1", &context), true);
+    assert_eq!(eval(r#"region == "EU""#, &context), true);
+    assert_eq!(eval("region < 'US'", &context), true);
+    assert_eq!(eval("internal == true", &context), true);
+    assert_eq!(eval("missing == null", &context), true);
+
+    // Values of different types are never equal nor ordered
+    assert_eq!(eval("region == 1", &context), false);
+    assert_eq!(eval("region != 1", &context), true);
+    assert_eq!(eval("internal > false", &context), false);
+}
+
+#[test]
+fn logic_test() {
+    let context = Context::new()
+        .with("amount", 250)
+        .with("region", "EU")
+        .with("internal", true);
+
+    assert_eq!(eval("internal", &context), true);
+    assert_eq!(eval("region", &context), false);
+    assert_eq!(eval("!internal", &context), false);
+    assert_eq!(eval("!missing", &context), true);
+    assert_eq!(eval("amount > 1000 || region == 'EU'", &context), true);
+    assert_eq!(
+        eval("amount > 1000 || region == 'EU' && !internal", &context),
+        false
+    );
+    assert_eq!(
+        eval("(amount > 1000 || region == 'EU') && internal", &context),
+        true
+    );
+    assert_eq!(eval("!(amount > 1000)", &context), true);
+}
+
+#[test]
+fn long_chain_test() {
+    let context = Context::new().with("a", true);
+    let and_chain = vec!["a"; 20_000].join(" && ");
+    let or_chain = format!("{} || a", vec!["missing"; 20_000].join(" || "));
+
+    assert_eq!(eval(&and_chain, &context), true);
+    assert_eq!(eval(&format!("{} && missing", and_chain), &context), false);
+    assert_eq!(eval(&or_chain, &context), true);
+    assert_eq!(eval(&or_chain, &Context::new()), false);
+}
@@ -0,0 +1,92 @@
+mod condition_spec;
+pub use condition_spec::ConditionSpec;
+pub(crate) use condition_spec::{time_to_value, value_to_time};
+mod expression;
+pub use expression::Expression;
+use serde_json::Value;
+use std::collections::HashMap;
+use std::panic::{RefUnwindSafe, UnwindSafe};
+use std::time::SystemTime;
+
+//// TESTS ////
+#[cfg(test)]
+mod tests;
+
+/// Condition that must hold for a conditional grant to be effective, evaluated against the [`Context`](crate::Context)
+/// of each check (e.g. the request comes from an internal IP or the resource state is "draft").
+///
+/// Implemented for any `Fn(&Context) -> bool` closure. Conditions must be thread and unwind safe, so the stores holding them
+/// can be shared across threads.
+///
+/// # Examples:
+///
+/// ```
+/// use simple_perm_manager::{Condition, Context};
+///
+/// let is_draft = |context: &Context| context.get("state").is_some_and(|state| state == "draft");
+///
+/// assert!(is_draft.evaluate(&Context::new().with("state", "draft")));
+/// assert!(!is_draft.evaluate(&Context::new()));
+/// ```
+pub trait Condition: Send + Sync + UnwindSafe + RefUnwindSafe {
+    /// Returns `true` if the condition holds for the [`Context`](crate::Context) provided.
+    fn evaluate(&self, context: &Context) -> bool;
+
+    /// Returns `true` if the condition holds for the [`Context`](crate::Context) provided at the time provided, for checks of a
+    /// time other than the current one.  
+    /// Defaults to [`evaluate`](Self::evaluate()), ignoring the time.
+    fn evaluate_at(&self, context: &Context, at: SystemTime) -> bool {
+        let _ = at;
+        self.evaluate(context)
+    }
+}
+
+impl<F> Condition for F
+where
+    F: Fn(&Context) -> bool + Send + Sync + UnwindSafe + RefUnwindSafe,
+{
+    fn evaluate(&self, context: &Context) -> bool {
+        self(context)
+    }
+}
+
+/// Map of attributes describing the circumstances of a check, such as request or resource attributes,
+/// passed to the [`Condition`](crate::Condition)s of conditional grants.
+///
+/// # Examples:
+///
+/// ```
+/// use simple_perm_manager::Context;
+///
+/// let context = Context::new().with("ip", "10.0.0.1").with("amount", 250);
+///
+/// assert_eq!(context.get("amount").and_then(|amount| amount.as_i64()), Some(250));
+/// assert!(context.get("region").is_none());
+/// ```
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct Context {
+    attributes: HashMap<String, Value>,
+}
+
+impl Context {
+    /// Creates a new [`Context`](crate::Context) without attributes.
+    pub fn new() -> Context {
+        Context::default()
+    }
+
+    /// Returns the [`Context`](crate::Context) with the attribute provided added.
+    pub fn with<V: Into<Value>>(mut self, key: &str, value: V) -> Context {
+        self.insert(key, value);
+        self
+    }
+
+    /// Adds an attribute to the [`Context`](crate::Context), replacing the previous value of the attribute if any.
+    pub fn insert<V: Into<Value>>(&mut self, key: &str, value: V) {
+        self.attributes.insert(String::from(key), value.into());
+    }
+
+    /// Returns the value of an attribute, if any.
+    pub fn get(&self, key: &str) -> Option<&Value> {
+        self.attributes.get(key)
+    }
+}
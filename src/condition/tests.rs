@@ -0,0 +1,33 @@
+use super::*;
+
+#[test]
+fn condition_test() {
+    let internal = |context: &Context| {
+        context
+            .get("ip")
+            .and_then(Value::as_str)
+            .is_some_and(|ip| ip.starts_with("10."))
+    };
+    let condition: Box<dyn Condition> = Box::new(internal);
+
+    assert_eq!(
+        condition.evaluate(&Context::new().with("ip", "10.1.2.3")),
+        true
+    );
+    assert_eq!(
+        condition.evaluate(&Context::new().with("ip", "8.8.8.8")),
+        false
+    );
+    assert_eq!(condition.evaluate(&Context::new()), false);
+}
+
+#[test]
+fn context_test() {
+    let mut context = Context::new().with("state", "draft").with("amount", 10);
+    context.insert("amount", 20);
+
+    assert_eq!(context.get("state"), Some(&Value::from("draft")));
+    assert_eq!(context.get("amount"), Some(&Value::from(20)));
+    assert_eq!(context.get("other"), None);
+    assert_eq!(Context::new(), Context::default());
+}
@@ -0,0 +1,56 @@
+use std::fmt;
+
+//// TESTS ////
+#[cfg(test)]
+mod tests;
+
+/// Result of an authorization decision, such as [`PermissionManager::decide`](crate::PermissionManager::decide()).  
+/// Unlike a bare `bool`, it tells an explicit denial apart from a decision the evaluated rules do not cover.
+///
+/// # Examples:
+///
+/// ```
+/// use simple_perm_manager::Decision;
+///
+/// assert!(Decision::Allow.is_allowed());
+/// assert!(!Decision::NotApplicable.is_allowed());
+/// assert_eq!(Decision::Deny.to_string(), "deny");
+/// ```
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum Decision {
+    /// The action is allowed.
+    Allow,
+    /// The action is denied.
+    Deny,
+    /// The evaluated rules do not apply to the action, so they neither allow nor deny it.
+    NotApplicable,
+}
+
+impl Decision {
+    /// Returns `true` if the [`Decision`](crate::Decision) is [`Allow`](crate::Decision::Allow).  
+    /// [`NotApplicable`](crate::Decision::NotApplicable) decisions are not allowed.
+    pub fn is_allowed(&self) -> bool {
+        *self == Decision::Allow
+    }
+}
+
+impl From<bool> for Decision {
+    /// Converts `true` into [`Allow`](crate::Decision::Allow) and `false` into [`Deny`](crate::Decision::Deny).
+    fn from(allowed: bool) -> Decision {
+        if allowed {
+            Decision::Allow
+        } else {
+            Decision::Deny
+        }
+    }
+}
+
+impl fmt::Display for Decision {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Decision::Allow => write!(f, "allow"),
+            Decision::Deny => write!(f, "deny"),
+            Decision::NotApplicable => write!(f, "not_applicable"),
+        }
+    }
+}
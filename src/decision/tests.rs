@@ -0,0 +1,21 @@
+use super::*;
+
+#[test]
+fn is_allowed_test() {
+    assert_eq!(Decision::Allow.is_allowed(), true);
+    assert_eq!(Decision::Deny.is_allowed(), false);
+    assert_eq!(Decision::NotApplicable.is_allowed(), false);
+}
+
+#[test]
+fn display_test() {
+    assert_eq!(Decision::Allow.to_string(), "allow");
+    assert_eq!(Decision::Deny.to_string(), "deny");
+    assert_eq!(Decision::NotApplicable.to_string(), "not_applicable");
+}
+
+#[test]
+fn from_bool_test() {
+    assert_eq!(Decision::from(true), Decision::Allow);
+    assert_eq!(Decision::from(false), Decision::Deny);
+}
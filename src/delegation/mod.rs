@@ -0,0 +1,83 @@
+use crate::permission::Permission;
+use crate::validation_error::ValidationError;
+
+//// TESTS ////
+#[cfg(test)]
+mod tests;
+
+/// Record of a [`Permission`](crate::Permission) handed from subject to subject, keeping the chain of subjects it went through.
+///
+/// Each delegation can only hand a subset of the [`Permission`](crate::Permission) held by the delegating subject,
+/// see [`Permission::delegate`](crate::Permission::delegate()).
+///
+/// # Examples:
+///
+/// ```
+/// use simple_perm_manager::{Delegation, PermissionManager};
+///
+/// let manager = PermissionManager::from_json(r#"{"view": true, "edit": true}"#);
+/// let root = Delegation::new("alice", manager.perm_from_json(r#"{"view": true, "edit": true}"#));
+///
+/// let bob = root.delegate("bob", &manager.perm_from_json(r#"{"view": true}"#)).unwrap();
+/// assert_eq!(bob.holder(), "bob");
+/// assert_eq!(bob.chain(), &[String::from("alice"), String::from("bob")]);
+///
+/// assert!(bob.delegate("carol", &manager.perm_from_json(r#"{"edit": true}"#)).is_err());
+/// ```
+#[derive(Clone, Debug)]
+pub struct Delegation {
+    perm: Permission,
+    // Subjects the permission went through, from the original holder to the current one
+    chain: Vec<String>,
+}
+
+impl Delegation {
+    /// Creates a new [`Delegation`](crate::Delegation) for the original holder of a [`Permission`](crate::Permission).
+    pub fn new(subject: &str, perm: Permission) -> Delegation {
+        Delegation {
+            perm,
+            chain: vec![String::from(subject)],
+        }
+    }
+
+    /// Delegates a subset of the held [`Permission`](crate::Permission) to another subject, extending the chain.
+    ///
+    /// # Errors:
+    ///
+    /// Returns [`ValidationError::NotDelegable`](crate::ValidationError::NotDelegable) if the subset has actions not held by the current holder.  
+    /// Returns [`ValidationError::ManagerMismatch`](crate::ValidationError::ManagerMismatch) if the subset does not have the same
+    /// [`PermissionManager`](crate::PermissionManager) as the held [`Permission`](crate::Permission).
+    pub fn delegate(
+        &self,
+        subject: &str,
+        subset: &Permission,
+    ) -> Result<Delegation, ValidationError> {
+        let perm = self.perm.delegate(subset)?;
+        let mut chain = self.chain.clone();
+        chain.push(String::from(subject));
+
+        Ok(Delegation { perm, chain })
+    }
+
+    /// Returns the delegated [`Permission`](crate::Permission).
+    pub fn perm(&self) -> &Permission {
+        &self.perm
+    }
+
+    /// Returns the subjects the [`Permission`](crate::Permission) went through, from the original holder to the current one.
+    pub fn chain(&self) -> &[String] {
+        &self.chain
+    }
+
+    /// Returns the subject currently holding the [`Permission`](crate::Permission).
+    pub fn holder(&self) -> &str {
+        self.chain
+            .last()
+            .expect("unexpected error, delegation chain should not be empty")
+    }
+
+    /// Returns the original holder of the [`Permission`](crate::Permission).
+    pub fn origin(&self) -> &str {
+        &self.chain[0]
+    }
+}
@@ -0,0 +1,59 @@
+use super::*;
+use crate::PermissionManager;
+use std::collections::HashSet;
+
+#[test]
+fn new_test() {
+    let manager = PermissionManager::from_json(r#"{"view": true}"#);
+    let root = Delegation::new("alice", manager.perm_from_json(r#"{"view": true}"#));
+
+    assert_eq!(root.origin(), "alice");
+    assert_eq!(root.holder(), "alice");
+    assert_eq!(root.chain(), &[String::from("alice")]);
+}
+
+#[test]
+fn delegate_test() {
+    let manager = PermissionManager::from_json(r#"{"view": true, "edit": true, "delete": true}"#);
+    let root = Delegation::new(
+        "alice",
+        manager.perm_from_json(r#"{"view": true, "edit": true}"#),
+    );
+
+    let bob = root
+        .delegate(
+            "bob",
+            &manager.perm_from_json(r#"{"view": true, "edit": true}"#),
+        )
+        .unwrap();
+    let carol = bob
+        .delegate("carol", &manager.perm_from_json(r#"{"view": true}"#))
+        .unwrap();
+
+    assert_eq!(
+        *carol.perm().get_actions(),
+        HashSet::from([String::from("view")])
+    );
+    assert_eq!(carol.origin(), "alice");
+    assert_eq!(carol.holder(), "carol");
+    assert_eq!(
+        carol.chain(),
+        &[
+            String::from("alice"),
+            String::from("bob"),
+            String::from("carol")
+        ]
+    );
+
+    assert_eq!(
+        carol
+            .delegate("dave", &manager.perm_from_json(r#"{"edit": true}"#))
+            .unwrap_err(),
+        ValidationError::NotDelegable(vec![String::from("edit")])
+    );
+    assert_eq!(
+        root.delegate("dave", &manager.perm_from_json(r#"{"delete": true}"#))
+            .is_err(),
+        true
+    );
+}
@@ -0,0 +1,538 @@
+use crate::authorizer::Authorizer;
+use crate::condition::{time_to_value, value_to_time};
+use crate::permission::Permission;
+use crate::permission_manager::PermissionManager;
+use crate::telemetry;
+use crate::validation_error::ValidationError;
+use serde_json::{Map, Value};
+use std::collections::{HashMap, HashSet};
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+//// TESTS ////
+#[cfg(test)]
+mod tests;
+
+/// Change to the permission state of an [`Authorizer`](crate::Authorizer), recorded in an [`EventLog`](crate::EventLog).  
+/// Events hold plain actions instead of [`Permission`](crate::Permission)s, so they stay valid across universe changes.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum PermissionEvent {
+    /// Actions granted to a subject, see [`GrantStore::grant`](crate::GrantStore::grant()).
+    Granted {
+        subject: String,
+        actions: HashSet<String>,
+    },
+    /// Actions revoked from a subject, see [`GrantStore::revoke`](crate::GrantStore::revoke()).
+    Revoked {
+        subject: String,
+        actions: HashSet<String>,
+    },
+    /// Subject added to a group, see [`GrantStore::add_member`](crate::GrantStore::add_member()).
+    MemberAdded { member: String, group: String },
+    /// Subject removed from a group, see [`GrantStore::remove_member`](crate::GrantStore::remove_member()).
+    MemberRemoved { member: String, group: String },
+    /// [`Role`](crate::Role) added, see [`RoleManager::add_role`](crate::RoleManager::add_role()).
+    RoleAdded {
+        role: String,
+        actions: HashSet<String>,
+    },
+    /// [`Role`](crate::Role) removed with its assignments, see [`RoleManager::remove_role`](crate::RoleManager::remove_role()).
+    RoleRemoved { role: String },
+    /// [`Role`](crate::Role) assigned to a subject, see [`RoleManager::assign`](crate::RoleManager::assign()).
+    RoleAssigned { subject: String, role: String },
+    /// [`Role`](crate::Role) removed from a subject, see [`RoleManager::unassign`](crate::RoleManager::unassign()).
+    RoleUnassigned { subject: String, role: String },
+    /// Actions denied to a subject, see [`Authorizer::deny`](crate::Authorizer::deny()).
+    Denied {
+        subject: String,
+        actions: HashSet<String>,
+    },
+    /// Deny rules removed from a subject, see [`Authorizer::undeny`](crate::Authorizer::undeny()).
+    Undenied {
+        subject: String,
+        actions: HashSet<String>,
+    },
+    /// Universe replaced by the actions provided. Actions no longer in the universe are dropped from grants, roles and deny rules.
+    UniverseChanged { actions: HashSet<String> },
+}
+
+impl PermissionEvent {
+    // Name of the event in its JSON serialization
+    fn kind(&self) -> &'static str {
+        match self {
+            PermissionEvent::Granted { .. } => "granted",
+            PermissionEvent::Revoked { .. } => "revoked",
+            PermissionEvent::MemberAdded { .. } => "member_added",
+            PermissionEvent::MemberRemoved { .. } => "member_removed",
+            PermissionEvent::RoleAdded { .. } => "role_added",
+            PermissionEvent::RoleRemoved { .. } => "role_removed",
+            PermissionEvent::RoleAssigned { .. } => "role_assigned",
+            PermissionEvent::RoleUnassigned { .. } => "role_unassigned",
+            PermissionEvent::Denied { .. } => "denied",
+            PermissionEvent::Undenied { .. } => "undenied",
+            PermissionEvent::UniverseChanged { .. } => "universe_changed",
+        }
+    }
+}
+
+/// [`PermissionEvent`](crate::PermissionEvent) appended to an [`EventLog`](crate::EventLog).
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct LoggedEvent {
+    /// Position of the event in the log, starting at 1.
+    pub sequence: u64,
+    /// Time the event was appended.
+    pub recorded_at: SystemTime,
+    /// Event appended.
+    pub event: PermissionEvent,
+}
+
+impl LoggedEvent {
+    /// Serializes the [`LoggedEvent`](crate::LoggedEvent) into a single line JSON string with the format
+    /// `{"event": "granted", "recorded_at": <ms>, "sequence": 1, ...}`, followed by the fields of the event
+    /// (`subject`, `member`, `group`, `role`, and `actions` sorted), where the time is in milliseconds since the UNIX epoch.
+    pub fn to_json(&self) -> String {
+        let sorted = |actions: &HashSet<String>| {
+            let mut actions: Vec<String> = actions.iter().cloned().collect();
+            actions.sort();
+            Value::from(actions)
+        };
+
+        let mut map = Map::new();
+        map.insert(String::from("sequence"), Value::from(self.sequence));
+        map.insert(
+            String::from("recorded_at"),
+            time_to_value(Some(self.recorded_at)),
+        );
+        map.insert(String::from("event"), Value::from(self.event.kind()));
+        let mut insert = |key: &str, value: Value| map.insert(String::from(key), value);
+        match &self.event {
+            PermissionEvent::Granted { subject, actions }
+            | PermissionEvent::Revoked { subject, actions }
+            | PermissionEvent::Denied { subject, actions }
+            | PermissionEvent::Undenied { subject, actions } => {
+                insert("subject", Value::from(subject.as_str()));
+                insert("actions", sorted(actions));
+            }
+            PermissionEvent::MemberAdded { member, group }
+            | PermissionEvent::MemberRemoved { member, group } => {
+                insert("member", Value::from(member.as_str()));
+                insert("group", Value::from(group.as_str()));
+            }
+            PermissionEvent::RoleAdded { role, actions } => {
+                insert("role", Value::from(role.as_str()));
+                insert("actions", sorted(actions));
+            }
+            PermissionEvent::RoleRemoved { role } => {
+                insert("role", Value::from(role.as_str()));
+            }
+            PermissionEvent::RoleAssigned { subject, role }
+            | PermissionEvent::RoleUnassigned { subject, role } => {
+                insert("subject", Value::from(subject.as_str()));
+                insert("role", Value::from(role.as_str()));
+            }
+            PermissionEvent::UniverseChanged { actions } => {
+                insert("actions", sorted(actions));
+            }
+        };
+
+        Value::Object(map).to_string()
+    }
+
+    // Deserializes an event serialized with `to_json`, returns None if the line does not have the event format
+    fn from_json(line: &str) -> Option<LoggedEvent> {
+        let map = match serde_json::from_str(line) {
+            Ok(Value::Object(map)) => map,
+            _ => return None,
+        };
+        let string = |key: &str| map.get(key)?.as_str().map(String::from);
+        let actions = || -> Option<HashSet<String>> {
+            map.get("actions")?
+                .as_array()?
+                .iter()
+                .map(|action| action.as_str().map(String::from))
+                .collect()
+        };
+
+        let event = match map.get("event")?.as_str()? {
+            "granted" => PermissionEvent::Granted {
+                subject: string("subject")?,
+                actions: actions()?,
+            },
+            "revoked" => PermissionEvent::Revoked {
+                subject: string("subject")?,
+                actions: actions()?,
+            },
+            "member_added" => PermissionEvent::MemberAdded {
+                member: string("member")?,
+                group: string("group")?,
+            },
+            "member_removed" => PermissionEvent::MemberRemoved {
+                member: string("member")?,
+                group: string("group")?,
+            },
+            "role_added" => PermissionEvent::RoleAdded {
+                role: string("role")?,
+                actions: actions()?,
+            },
+            "role_removed" => PermissionEvent::RoleRemoved {
+                role: string("role")?,
+            },
+            "role_assigned" => PermissionEvent::RoleAssigned {
+                subject: string("subject")?,
+                role: string("role")?,
+            },
+            "role_unassigned" => PermissionEvent::RoleUnassigned {
+                subject: string("subject")?,
+                role: string("role")?,
+            },
+            "denied" => PermissionEvent::Denied {
+                subject: string("subject")?,
+                actions: actions()?,
+            },
+            "undenied" => PermissionEvent::Undenied {
+                subject: string("subject")?,
+                actions: actions()?,
+            },
+            "universe_changed" => PermissionEvent::UniverseChanged {
+                actions: actions()?,
+            },
+            _ => return None,
+        };
+
+        Some(LoggedEvent {
+            sequence: map.get("sequence")?.as_u64()?,
+            recorded_at: value_to_time(Some(map.get("recorded_at")?))??,
+            event,
+        })
+    }
+}
+
+/// Append-only log of [`PermissionEvent`](crate::PermissionEvent)s that can be replayed into an [`Authorizer`](crate::Authorizer),
+/// so permission state can be reconstructed and audited at any point of its history instead of from snapshots.
+///
+/// Replay starts from an empty universe, so logs usually start with a [`PermissionEvent::UniverseChanged`](crate::PermissionEvent::UniverseChanged).
+///
+/// # Examples:
+///
+/// ```
+/// use std::collections::HashSet;
+/// use simple_perm_manager::{EventLog, PermissionEvent};
+///
+/// let actions = |actions: &[&str]| actions.iter().map(|action| action.to_string()).collect::<HashSet<String>>();
+///
+/// let mut log = EventLog::new();
+/// log.append(PermissionEvent::UniverseChanged { actions: actions(&["post:view", "post:edit"]) });
+/// log.append(PermissionEvent::Granted { subject: String::from("alice"), actions: actions(&["post:view", "post:edit"]) });
+/// let revoked = log.append(PermissionEvent::Revoked { subject: String::from("alice"), actions: actions(&["post:edit"]) });
+///
+/// let auth = log.replay().unwrap();
+/// assert!(auth.check("alice", "post:view"));
+/// assert!(!auth.check("alice", "post:edit"));
+///
+/// // State before the revocation
+/// let auth = log.replay_until(revoked - 1).unwrap();
+/// assert!(auth.check("alice", "post:edit"));
+///
+/// // Logs can be stored and loaded as JSON lines
+/// let loaded = EventLog::from_json_lines(&log.to_json_lines()).unwrap();
+/// assert_eq!(loaded.events(), log.events());
+/// ```
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct EventLog {
+    events: Vec<LoggedEvent>,
+}
+
+impl EventLog {
+    /// Creates a new empty [`EventLog`](crate::EventLog).
+    pub fn new() -> EventLog {
+        EventLog::default()
+    }
+
+    /// Appends a [`PermissionEvent`](crate::PermissionEvent) recorded at the current time, truncated to milliseconds so it
+    /// survives serialization. Returns the sequence number of the event.
+    pub fn append(&mut self, event: PermissionEvent) -> u64 {
+        let sequence = self.events.len() as u64 + 1;
+        let since_epoch = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .unwrap_or(Duration::ZERO);
+        self.events.push(LoggedEvent {
+            sequence,
+            recorded_at: UNIX_EPOCH + Duration::from_millis(since_epoch.as_millis() as u64),
+            event,
+        });
+
+        sequence
+    }
+
+    /// Returns the [`LoggedEvent`](crate::LoggedEvent)s of the [`EventLog`](crate::EventLog) in the order they were appended.
+    pub fn events(&self) -> &[LoggedEvent] {
+        &self.events
+    }
+
+    /// Returns the [`LoggedEvent`](crate::LoggedEvent)s appended after the sequence number provided.
+    pub fn events_since(&self, sequence: u64) -> &[LoggedEvent] {
+        let start = (sequence as usize).min(self.events.len());
+        &self.events[start..]
+    }
+
+    /// Returns the number of events of the [`EventLog`](crate::EventLog).
+    pub fn len(&self) -> usize {
+        self.events.len()
+    }
+
+    /// Returns `true` if the [`EventLog`](crate::EventLog) has no events.
+    pub fn is_empty(&self) -> bool {
+        self.events.is_empty()
+    }
+
+    /// Replays every event of the [`EventLog`](crate::EventLog) into a new [`Authorizer`](crate::Authorizer), whose
+    /// [`PermissionManager`](crate::PermissionManager) has the last universe of the log and a new [`ManagerId`](crate::ManagerId).
+    ///
+    /// # Errors:
+    ///
+    /// - Returns [`ValidationError::MalformedActions`](crate::ValidationError::MalformedActions) if a universe has malformed actions.
+    /// - Returns [`ValidationError::UnknownActions`](crate::ValidationError::UnknownActions) if an event has actions that were not
+    ///   in the universe at the time.
+    /// - Returns [`ValidationError::InvalidEvent`](crate::ValidationError::InvalidEvent) if an event can not be applied, e.g. it
+    ///   adds a [`Role`](crate::Role) that already exists or creates a group membership cycle.
+    pub fn replay(&self) -> Result<Authorizer, ValidationError> {
+        self.replay_until(self.events.len() as u64)
+    }
+
+    /// Same as [`replay`](Self::replay()) but only replays the events up to the sequence number provided, included.
+    ///
+    /// # Errors:
+    ///
+    /// Returns the same errors that [`replay`](Self::replay()) does.
+    pub fn replay_until(&self, sequence: u64) -> Result<Authorizer, ValidationError> {
+        let mut state = ReplayState::default();
+        for logged in self.events.iter().take_while(|e| e.sequence <= sequence) {
+            state.apply(logged)?;
+        }
+
+        Ok(state.into_authorizer())
+    }
+
+    /// Serializes the [`EventLog`](crate::EventLog) into JSON lines, one [`LoggedEvent::to_json`](crate::LoggedEvent::to_json()) per event.
+    pub fn to_json_lines(&self) -> String {
+        self.events
+            .iter()
+            .map(|logged| logged.to_json() + "\n")
+            .collect()
+    }
+
+    /// Deserializes an [`EventLog`](crate::EventLog) serialized with [`to_json_lines`](Self::to_json_lines()). Empty lines are ignored.
+    ///
+    /// # Errors:
+    ///
+    /// Returns [`ValidationError::InvalidJson`](crate::ValidationError::InvalidJson) if a line is not a valid event or the sequence
+    /// numbers are not consecutive from 1.
+    pub fn from_json_lines(json_lines: &str) -> Result<EventLog, ValidationError> {
+        let mut log = EventLog::new();
+        for (line_number, line) in json_lines
+            .lines()
+            .enumerate()
+            .filter(|(_, line)| !line.trim().is_empty())
+        {
+            match LoggedEvent::from_json(line) {
+                Some(logged) if logged.sequence == log.events.len() as u64 + 1 => {
+                    log.events.push(logged)
+                }
+                _ => {
+                    telemetry::record_parse_error();
+                    return Err(ValidationError::InvalidJson(format!(
+                        "wrong format in event log json lines - invalid event at line {}",
+                        line_number + 1
+                    )));
+                }
+            }
+        }
+
+        Ok(log)
+    }
+}
+
+// Permission state built while replaying events, validated against the universe of each moment
+#[derive(Default)]
+struct ReplayState {
+    manager: Option<PermissionManager>,
+    grants: HashMap<String, HashSet<String>>,
+    memberships: HashMap<String, HashSet<String>>,
+    roles: HashMap<String, HashSet<String>>,
+    assignments: HashMap<String, HashSet<String>>,
+    denies: HashMap<String, HashSet<String>>,
+}
+
+impl ReplayState {
+    fn apply(&mut self, logged: &LoggedEvent) -> Result<(), ValidationError> {
+        let invalid = |reason: String| {
+            telemetry::record_validation_failure();
+            ValidationError::InvalidEvent(logged.sequence, reason)
+        };
+
+        match &logged.event {
+            PermissionEvent::Granted { subject, actions } => {
+                let actions = self.validated(actions)?;
+                self.grants
+                    .entry(subject.clone())
+                    .or_default()
+                    .extend(actions);
+            }
+            PermissionEvent::Revoked { subject, actions } => {
+                let actions = self.validated(actions)?;
+                remove_values(&mut self.grants, subject, &actions);
+            }
+            PermissionEvent::MemberAdded { member, group } => {
+                if member == group || self.belongs_to(group, member) {
+                    return Err(invalid(String::from(
+                        "group membership would create a cycle",
+                    )));
+                }
+                self.memberships
+                    .entry(member.clone())
+                    .or_default()
+                    .insert(group.clone());
+            }
+            PermissionEvent::MemberRemoved { member, group } => {
+                remove_values(
+                    &mut self.memberships,
+                    member,
+                    &HashSet::from([group.clone()]),
+                );
+            }
+            PermissionEvent::RoleAdded { role, actions } => {
+                if self.roles.contains_key(role) {
+                    return Err(invalid(format!(
+                        "a role already exists with name '{}'",
+                        role
+                    )));
+                }
+                let actions = self.validated(actions)?;
+                self.roles.insert(role.clone(), actions);
+            }
+            PermissionEvent::RoleRemoved { role } => {
+                self.roles.remove(role);
+                for roles in self.assignments.values_mut() {
+                    roles.remove(role);
+                }
+                self.assignments.retain(|_, roles| !roles.is_empty());
+            }
+            PermissionEvent::RoleAssigned { subject, role } => {
+                if !self.roles.contains_key(role) {
+                    return Err(invalid(format!("no role exists with name '{}'", role)));
+                }
+                self.assignments
+                    .entry(subject.clone())
+                    .or_default()
+                    .insert(role.clone());
+            }
+            PermissionEvent::RoleUnassigned { subject, role } => {
+                remove_values(
+                    &mut self.assignments,
+                    subject,
+                    &HashSet::from([role.clone()]),
+                );
+            }
+            PermissionEvent::Denied { subject, actions } => {
+                let actions = self.validated(actions)?;
+                self.denies
+                    .entry(subject.clone())
+                    .or_default()
+                    .extend(actions);
+            }
+            PermissionEvent::Undenied { subject, actions } => {
+                let actions = self.validated(actions)?;
+                remove_values(&mut self.denies, subject, &actions);
+            }
+            PermissionEvent::UniverseChanged { actions } => {
+                let manager = PermissionManager::try_from_actions(actions.clone())?;
+                let universe = manager.to_actions();
+                for actions in self.grants.values_mut().chain(self.roles.values_mut()) {
+                    actions.retain(|action| universe.contains(action));
+                }
+                for actions in self.denies.values_mut() {
+                    actions.retain(|action| universe.contains(action));
+                }
+                self.grants.retain(|_, actions| !actions.is_empty());
+                self.denies.retain(|_, actions| !actions.is_empty());
+                self.manager = Some(manager);
+            }
+        }
+
+        Ok(())
+    }
+
+    // Returns the actions normalized by the current manager, or an error if any is not in the current universe
+    fn validated(&self, actions: &HashSet<String>) -> Result<HashSet<String>, ValidationError> {
+        let perm = match &self.manager {
+            Some(manager) => manager.try_perm_from_actions(actions.clone())?,
+            None => PermissionManager::from_actions(HashSet::new())
+                .try_perm_from_actions(actions.clone())?,
+        };
+
+        Ok(perm.get_actions().into_owned())
+    }
+
+    // Returns true if the subject belongs to the group directly or through nested groups
+    fn belongs_to(&self, subject: &str, group: &str) -> bool {
+        let mut visited: HashSet<&str> = HashSet::new();
+        let mut pending = vec![subject];
+        while let Some(current) = pending.pop() {
+            for parent in self.memberships.get(current).into_iter().flatten() {
+                if parent == group {
+                    return true;
+                }
+                if visited.insert(parent) {
+                    pending.push(parent);
+                }
+            }
+        }
+
+        false
+    }
+
+    fn into_authorizer(self) -> Authorizer {
+        let manager = self
+            .manager
+            .unwrap_or_else(|| PermissionManager::from_actions(HashSet::new()));
+        let mut auth = Authorizer::new(manager);
+        let perm = |auth: &Authorizer, actions: HashSet<String>| {
+            auth.manager()
+                .clean_perm(&Permission::from_actions(actions))
+        };
+
+        for (subject, actions) in self.grants {
+            let perm = perm(&auth, actions);
+            auth.grants_mut().grant(subject, &perm);
+        }
+        for (member, groups) in self.memberships {
+            for group in groups {
+                auth.grants_mut().add_member(member.clone(), group);
+            }
+        }
+        for (role, actions) in self.roles {
+            let perm = perm(&auth, actions);
+            auth.roles_mut().add_role(&role, perm);
+        }
+        for (subject, roles) in self.assignments {
+            for role in roles {
+                auth.roles_mut().assign(&subject, &role);
+            }
+        }
+        for (subject, actions) in self.denies {
+            let perm = perm(&auth, actions);
+            auth.deny(&subject, &perm);
+        }
+
+        auth
+    }
+}
+
+// Removes the values from the set of the key, removing the key if its set is left empty
+fn remove_values(map: &mut HashMap<String, HashSet<String>>, key: &str, values: &HashSet<String>) {
+    if let Some(set) = map.get_mut(key) {
+        set.retain(|value| !values.contains(value));
+        if set.is_empty() {
+            map.remove(key);
+        }
+    }
+}
@@ -0,0 +1,187 @@
+use super::*;
+
+fn actions(actions: &[&str]) -> HashSet<String> {
+    actions.iter().map(|action| action.to_string()).collect()
+}
+
+fn universe_log() -> EventLog {
+    let mut log = EventLog::new();
+    log.append(PermissionEvent::UniverseChanged {
+        actions: actions(&["post:view", "post:edit", "post:delete"]),
+    });
+    log
+}
+
+#[test]
+fn append_test() {
+    let mut log = universe_log();
+    let sequence = log.append(PermissionEvent::RoleRemoved {
+        role: String::from("admin"),
+    });
+
+    assert_eq!(sequence, 2);
+    assert_eq!(log.len(), 2);
+    assert_eq!(log.is_empty(), false);
+    assert_eq!(log.events()[1].sequence, 2);
+    assert_eq!(log.events_since(1).len(), 1);
+    assert_eq!(log.events_since(5).len(), 0);
+    assert_eq!(EventLog::new().is_empty(), true);
+}
+
+#[test]
+fn replay_test() {
+    let mut log = universe_log();
+    log.append(PermissionEvent::Granted {
+        subject: String::from("staff"),
+        actions: actions(&["post:view"]),
+    });
+    log.append(PermissionEvent::MemberAdded {
+        member: String::from("alice"),
+        group: String::from("staff"),
+    });
+    log.append(PermissionEvent::RoleAdded {
+        role: String::from("editor"),
+        actions: actions(&["post:edit", "post:delete"]),
+    });
+    log.append(PermissionEvent::RoleAssigned {
+        subject: String::from("alice"),
+        role: String::from("editor"),
+    });
+    let denied = log.append(PermissionEvent::Denied {
+        subject: String::from("alice"),
+        actions: actions(&["post:delete"]),
+    });
+
+    let auth = log.replay().unwrap();
+    assert_eq!(auth.check("alice", "post:view"), true);
+    assert_eq!(auth.check("alice", "post:edit"), true);
+    assert_eq!(auth.check("alice", "post:delete"), false);
+    assert_eq!(auth.check("bob", "post:view"), false);
+
+    let auth = log.replay_until(denied - 1).unwrap();
+    assert_eq!(auth.check("alice", "post:delete"), true);
+
+    log.append(PermissionEvent::Undenied {
+        subject: String::from("alice"),
+        actions: actions(&["post:delete"]),
+    });
+    log.append(PermissionEvent::RoleRemoved {
+        role: String::from("editor"),
+    });
+    log.append(PermissionEvent::MemberRemoved {
+        member: String::from("alice"),
+        group: String::from("staff"),
+    });
+    log.append(PermissionEvent::Revoked {
+        subject: String::from("staff"),
+        actions: actions(&["post:view"]),
+    });
+
+    let auth = log.replay().unwrap();
+    assert_eq!(auth.effective("alice").get_actions().is_empty(), true);
+    assert_eq!(auth.roles().roles().count(), 0);
+    assert_eq!(auth.grants().subjects().count(), 0);
+}
+
+#[test]
+fn universe_change_test() {
+    let mut log = universe_log();
+    log.append(PermissionEvent::Granted {
+        subject: String::from("alice"),
+        actions: actions(&["post:view", "post:delete"]),
+    });
+    log.append(PermissionEvent::UniverseChanged {
+        actions: actions(&["post:view", "post:archive"]),
+    });
+
+    let auth = log.replay().unwrap();
+    assert_eq!(
+        auth.manager().to_actions(),
+        actions(&["post:view", "post:archive"])
+    );
+    assert_eq!(
+        *auth.effective("alice").get_actions(),
+        actions(&["post:view"])
+    );
+
+    log.append(PermissionEvent::Granted {
+        subject: String::from("alice"),
+        actions: actions(&["post:delete"]),
+    });
+    assert_eq!(
+        log.replay().unwrap_err(),
+        ValidationError::UnknownActions(vec![(String::from("post:delete"), vec![])])
+    );
+}
+
+#[test]
+fn invalid_event_test() {
+    let mut log = universe_log();
+    log.append(PermissionEvent::RoleAssigned {
+        subject: String::from("alice"),
+        role: String::from("admin"),
+    });
+    assert_eq!(
+        log.replay().unwrap_err(),
+        ValidationError::InvalidEvent(2, String::from("no role exists with name 'admin'"))
+    );
+
+    let mut log = universe_log();
+    log.append(PermissionEvent::MemberAdded {
+        member: String::from("alice"),
+        group: String::from("staff"),
+    });
+    log.append(PermissionEvent::MemberAdded {
+        member: String::from("staff"),
+        group: String::from("alice"),
+    });
+    assert_eq!(
+        log.replay().unwrap_err(),
+        ValidationError::InvalidEvent(3, String::from("group membership would create a cycle"))
+    );
+
+    let mut log = EventLog::new();
+    log.append(PermissionEvent::Granted {
+        subject: String::from("alice"),
+        actions: actions(&["view"]),
+    });
+    assert_eq!(
+        log.replay().unwrap_err(),
+        ValidationError::UnknownActions(vec![(String::from("view"), vec![])])
+    );
+}
+
+#[test]
+fn json_lines_test() {
+    let mut log = universe_log();
+    log.append(PermissionEvent::Granted {
+        subject: String::from("alice"),
+        actions: actions(&["post:view", "post:edit"]),
+    });
+    log.append(PermissionEvent::RoleUnassigned {
+        subject: String::from("alice"),
+        role: String::from("editor"),
+    });
+
+    let json_lines = log.to_json_lines();
+    let lines: Vec<&str> = json_lines.lines().collect();
+    assert_eq!(lines.len(), 3);
+    assert_eq!(
+        lines[1].replace(
+            &time_to_value(Some(log.events()[1].recorded_at)).to_string(),
+            "0"
+        ),
+        r#"{"actions":["post:edit","post:view"],"event":"granted","recorded_at":0,"sequence":2,"subject":"alice"}"#
+    );
+    assert_eq!(EventLog::from_json_lines(&json_lines), Ok(log.clone()));
+    assert_eq!(EventLog::from_json_lines("\n").unwrap().is_empty(), true);
+
+    let error = ValidationError::InvalidJson(String::from(
+        "wrong format in event log json lines - invalid event at line 1",
+    ));
+    assert_eq!(EventLog::from_json_lines(lines[1]), Err(error));
+    assert_eq!(
+        EventLog::from_json_lines(r#"{"event":"unknown","recorded_at":0,"sequence":1}"#).is_err(),
+        true
+    );
+}
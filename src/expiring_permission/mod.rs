@@ -0,0 +1,89 @@
+use crate::permission::Permission;
+use std::time::SystemTime;
+
+//// TESTS ////
+#[cfg(test)]
+mod tests;
+
+/// [`Permission`](crate::Permission) that is only active during a period of time, for temporary access.  
+/// Both ends of the period are optional: a missing `valid_from` means it is active since ever and a missing
+/// `valid_until` means it never expires. The period includes `valid_from` and excludes `valid_until`.
+///
+/// # Examples:
+///
+/// ```
+/// use std::time::{Duration, SystemTime};
+/// use simple_perm_manager::{ExpiringPermission, Permission};
+///
+/// let now = SystemTime::now();
+/// let perm = ExpiringPermission::until(Permission::from_json(r#"{"view": true}"#), now + Duration::from_secs(3600));
+///
+/// assert!(perm.is_active(now));
+/// assert!(!perm.is_active(now + Duration::from_secs(7200)));
+/// ```
+#[derive(Clone, Debug)]
+pub struct ExpiringPermission {
+    perm: Permission,
+    valid_from: Option<SystemTime>,
+    valid_until: Option<SystemTime>,
+}
+
+impl ExpiringPermission {
+    /// Creates a new [`ExpiringPermission`](crate::ExpiringPermission) active from `valid_from` until `valid_until`.
+    ///
+    /// # Panics:
+    ///
+    /// Panics if `valid_from` is not earlier than `valid_until`.
+    pub fn new(
+        perm: Permission,
+        valid_from: Option<SystemTime>,
+        valid_until: Option<SystemTime>,
+    ) -> ExpiringPermission {
+        if let (Some(from), Some(until)) = (valid_from, valid_until) {
+            if from >= until {
+                panic!("ExpiringPermission valid_from must be earlier than valid_until");
+            }
+        }
+
+        ExpiringPermission {
+            perm,
+            valid_from,
+            valid_until,
+        }
+    }
+
+    /// Creates a new [`ExpiringPermission`](crate::ExpiringPermission) active until `valid_until`.
+    pub fn until(perm: Permission, valid_until: SystemTime) -> ExpiringPermission {
+        ExpiringPermission::new(perm, None, Some(valid_until))
+    }
+
+    /// Returns the [`Permission`](crate::Permission), whether it is active or not.
+    pub fn perm(&self) -> &Permission {
+        &self.perm
+    }
+
+    /// Returns a mutable reference to the [`Permission`](crate::Permission).
+    pub(crate) fn perm_mut(&mut self) -> &mut Permission {
+        &mut self.perm
+    }
+
+    /// Returns the time the [`Permission`](crate::Permission) becomes active, if any.
+    pub fn valid_from(&self) -> Option<SystemTime> {
+        self.valid_from
+    }
+
+    /// Returns the time the [`Permission`](crate::Permission) expires, if any.
+    pub fn valid_until(&self) -> Option<SystemTime> {
+        self.valid_until
+    }
+
+    /// Returns `true` if the [`Permission`](crate::Permission) is active at the time provided.
+    pub fn is_active(&self, at: SystemTime) -> bool {
+        self.valid_from.is_none_or(|from| from <= at) && !self.is_expired(at)
+    }
+
+    /// Returns `true` if the [`Permission`](crate::Permission) has expired at the time provided, so it will never be active again.
+    pub fn is_expired(&self, at: SystemTime) -> bool {
+        self.valid_until.is_some_and(|until| until <= at)
+    }
+}
@@ -0,0 +1,57 @@
+use super::*;
+use std::time::Duration;
+
+#[test]
+fn new_test() {
+    let now = SystemTime::now();
+    let perm = ExpiringPermission::new(
+        Permission::from_json(r#"{"view": true}"#),
+        Some(now),
+        Some(now + Duration::from_secs(60)),
+    );
+
+    assert_eq!(perm.valid_from(), Some(now));
+    assert_eq!(perm.valid_until(), Some(now + Duration::from_secs(60)));
+    assert_eq!(perm.perm().contains_action("view"), true);
+
+    match std::panic::catch_unwind(|| {
+        ExpiringPermission::new(Permission::from_json("{}"), Some(now), Some(now));
+    }) {
+        Ok(_) => panic!("an empty validity period should have panicked"),
+        Err(_) => (),
+    }
+}
+
+#[test]
+fn until_test() {
+    let now = SystemTime::now();
+    let perm = ExpiringPermission::until(Permission::from_json("{}"), now);
+
+    assert_eq!(perm.valid_from(), None);
+    assert_eq!(perm.valid_until(), Some(now));
+}
+
+#[test]
+fn is_active_test() {
+    let now = SystemTime::now();
+    let hour = Duration::from_secs(3600);
+    let perm = ExpiringPermission::new(Permission::from_json("{}"), Some(now), Some(now + hour));
+
+    assert_eq!(perm.is_active(now - hour), false);
+    assert_eq!(perm.is_active(now), true);
+    assert_eq!(perm.is_active(now + hour / 2), true);
+    assert_eq!(perm.is_active(now + hour), false);
+
+    let forever = ExpiringPermission::new(Permission::from_json("{}"), None, None);
+    assert_eq!(forever.is_active(now), true);
+}
+
+#[test]
+fn is_expired_test() {
+    let now = SystemTime::now();
+    let hour = Duration::from_secs(3600);
+    let perm = ExpiringPermission::new(Permission::from_json("{}"), Some(now), Some(now + hour));
+
+    assert_eq!(perm.is_expired(now - hour), false);
+    assert_eq!(perm.is_expired(now + hour), true);
+}
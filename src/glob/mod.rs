@@ -0,0 +1,80 @@
+//// TESTS ////
+#[cfg(test)]
+mod tests;
+
+// Returns `true` if the action matches the glob pattern. Both are split in groups by the separator:
+// a `**` group matches any number of groups (including none) and, inside a group, `*` matches any
+// sequence of characters and `?` any single character.
+pub(crate) fn glob_matches(pattern: &str, action: &str, separator: char) -> bool {
+    let pattern: Vec<&str> = pattern.split(separator).collect();
+    let action: Vec<&str> = action.split(separator).collect();
+
+    groups_match(&pattern, &action)
+}
+
+// Returns `true` if the whole text matches the wildcard pattern, where `*` matches any sequence of characters
+// (separators included) and `?` any single character
+pub(crate) fn wildcard_matches(pattern: &str, text: &str) -> bool {
+    let pattern: Vec<char> = pattern.chars().collect();
+    let text: Vec<char> = text.chars().collect();
+
+    group_matches(&pattern, &text)
+}
+
+fn groups_match(pattern: &[&str], action: &[&str]) -> bool {
+    let pattern: Vec<Vec<char>> = pattern
+        .iter()
+        .map(|group| group.chars().collect())
+        .collect();
+    let action: Vec<Vec<char>> = action.iter().map(|name| name.chars().collect()).collect();
+
+    sequence_matches(
+        &pattern,
+        &action,
+        |group| group[..] == ['*', '*'],
+        |group, name| group_matches(group, name),
+    )
+}
+
+fn group_matches(pattern: &[char], name: &[char]) -> bool {
+    sequence_matches(
+        pattern,
+        name,
+        |c| *c == '*',
+        |c, name_c| *c == '?' || c == name_c,
+    )
+}
+
+// Returns `true` if the items match the pattern, where the pattern elements for which `is_any` holds match any sequence of items
+// (including none) and the others match a single item for which `matches` holds.
+// Only the position of the last `is_any` element is kept for backtracking, so it takes at most pattern length times items
+// steps instead of an exponential number of them.
+fn sequence_matches<P, T>(
+    pattern: &[P],
+    items: &[T],
+    is_any: impl Fn(&P) -> bool,
+    matches: impl Fn(&P, &T) -> bool,
+) -> bool {
+    let (mut p, mut i) = (0, 0);
+    // Pattern position after the last `is_any` element and the item position it has matched up to
+    let mut backtrack: Option<(usize, usize)> = None;
+
+    while i < items.len() {
+        if p < pattern.len() && is_any(&pattern[p]) {
+            p += 1;
+            backtrack = Some((p, i));
+        } else if p < pattern.len() && matches(&pattern[p], &items[i]) {
+            p += 1;
+            i += 1;
+        } else if let Some((after_any, matched)) = backtrack {
+            // The last `is_any` element takes one more item and the rest of the pattern is matched again after it
+            p = after_any;
+            i = matched + 1;
+            backtrack = Some((after_any, i));
+        } else {
+            return false;
+        }
+    }
+
+    pattern[p..].iter().all(is_any)
+}
@@ -0,0 +1,92 @@
+use super::*;
+
+#[test]
+fn glob_matches_test() {
+    assert_eq!(glob_matches("building:view", "building:view", ':'), true);
+    assert_eq!(glob_matches("building:view", "building:edit", ':'), false);
+    assert_eq!(glob_matches("building:*", "building:view", ':'), true);
+    assert_eq!(glob_matches("building:*", "building:room:view", ':'), false);
+    assert_eq!(
+        glob_matches("building:*:view", "building:room:view", ':'),
+        true
+    );
+    assert_eq!(glob_matches("building:*:view", "building:view", ':'), false);
+    assert_eq!(glob_matches("*:view", "user:view", ':'), true);
+    assert_eq!(glob_matches("building:v*", "building:view", ':'), true);
+    assert_eq!(glob_matches("building:*w", "building:view", ':'), true);
+    assert_eq!(glob_matches("building:vi?w", "building:view", ':'), true);
+    assert_eq!(glob_matches("building:vi?w", "building:viw", ':'), false);
+}
+
+#[test]
+fn recursive_glob_test() {
+    assert_eq!(glob_matches("building:**", "building:view", ':'), true);
+    assert_eq!(
+        glob_matches("building:**", "building:room:door:open", ':'),
+        true
+    );
+    assert_eq!(glob_matches("building:**", "user:view", ':'), false);
+    assert_eq!(glob_matches("**:view", "view", ':'), true);
+    assert_eq!(glob_matches("**:view", "building:room:view", ':'), true);
+    assert_eq!(glob_matches("building:**:view", "building:view", ':'), true);
+    assert_eq!(
+        glob_matches("building:**:view", "building:room:edit", ':'),
+        false
+    );
+    assert_eq!(glob_matches("**", "anything:at:all", ':'), true);
+}
+
+#[test]
+fn recursive_glob_backtracking_test() {
+    // Patterns with many `*` and `**` groups are matched without exponential backtracking
+    let action = vec!["a"; 40].join(":");
+    let pattern = format!("{}:x", vec!["*:**"; 20].join(":"));
+
+    assert_eq!(glob_matches(&pattern, &action, ':'), false);
+    assert_eq!(glob_matches(&pattern, &format!("{}:x", action), ':'), true);
+    assert_eq!(
+        glob_matches(&format!("{}:x", vec!["**:a*"; 30].join(":")), &action, ':'),
+        false
+    );
+}
+
+#[test]
+fn separator_test() {
+    assert_eq!(
+        glob_matches("building.*.view", "building.room.view", '.'),
+        true
+    );
+    assert_eq!(
+        glob_matches("building.*.view", "building:room:view", '.'),
+        false
+    );
+}
+
+#[test]
+fn wildcard_matches_test() {
+    assert_eq!(wildcard_matches("s3:GetObject", "s3:GetObject"), true);
+    assert_eq!(wildcard_matches("s3:Get*", "s3:GetObject"), true);
+    assert_eq!(wildcard_matches("s3:Get*", "s3:PutObject"), false);
+    assert_eq!(wildcard_matches("*", "s3:bucket:GetObject"), true);
+    assert_eq!(wildcard_matches("s3:*Object", "s3:bucket:GetObject"), true);
+    assert_eq!(wildcard_matches("s3:?etObject", "s3:GetObject"), true);
+    assert_eq!(wildcard_matches("s3:?etObject", "s3:etObject"), false);
+    assert_eq!(wildcard_matches("*a*b", "xaybzb"), true);
+    assert_eq!(wildcard_matches("*a*b", "xaybzc"), false);
+    assert_eq!(wildcard_matches("a**", "a"), true);
+    assert_eq!(wildcard_matches("", ""), true);
+    assert_eq!(wildcard_matches("?", ""), false);
+}
+
+#[test]
+fn wildcard_backtracking_test() {
+    // Patterns with many wildcards are matched without exponential backtracking
+    let action = "a".repeat(43);
+
+    assert_eq!(wildcard_matches("*a*a*a*a*a*a*a*a*a*b", &action), false);
+    assert_eq!(wildcard_matches("*a*a*a*a*a*a*a*a*a*a", &action), true);
+    assert_eq!(
+        wildcard_matches(&format!("{}b", "*a".repeat(40)), &action),
+        false
+    );
+}
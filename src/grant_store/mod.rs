@@ -0,0 +1,1048 @@
+use crate::condition::{time_to_value, value_to_time, Condition, ConditionSpec, Context};
+use crate::expiring_permission::ExpiringPermission;
+use crate::manager_id::ManagerId;
+use crate::permission::Permission;
+use crate::permission_manager::PermissionManager;
+use crate::trace::{GrantSource, TraceStep};
+use serde_json::{Map, Value};
+use std::collections::{HashMap, HashSet};
+use std::fmt;
+use std::fmt::Display;
+use std::hash::Hash;
+use std::panic::{RefUnwindSafe, UnwindSafe};
+use std::str::FromStr;
+use std::sync::Arc;
+use std::time::SystemTime;
+
+//// TESTS ////
+#[cfg(test)]
+mod tests;
+
+// Default maximum number of nested group levels resolved for a subject.
+const DEFAULT_MAX_GROUP_DEPTH: usize = 16;
+
+/// Store of the [`Permission`](crate::Permission)s granted directly to subjects, validated against a
+/// [`PermissionManager`](crate::PermissionManager).
+///
+/// Subjects can be any hashable id, such as user ids, API key strings or a custom enum.  
+/// Subjects can also be members of groups, which are subjects themselves (a user belongs to teams and teams
+/// belong to departments). The effective permission of a subject aggregates the grants of all the groups it belongs to,
+/// directly or through nested groups.  
+/// Grants can be temporary using [`grant_expiring`](crate::GrantStore::grant_expiring()). Evaluation methods ignore grants
+/// that are not active at the current time, and their `_at` variants at the time provided.  
+/// Grants can also be conditional using [`grant_if`](crate::GrantStore::grant_if()). Their [`Condition`](crate::Condition)s are
+/// evaluated against the [`Context`](crate::Context) provided to the `_with` evaluation methods, and against an empty
+/// [`Context`](crate::Context) by the other ones.  
+/// Callbacks registered with [`on_grant`](crate::GrantStore::on_grant()) and [`on_revoke`](crate::GrantStore::on_revoke())
+/// are called after every grant and revocation, e.g. to sync caches or publish events.
+///
+/// # Examples:
+///
+/// ```
+/// use simple_perm_manager::{GrantStore, PermissionManager};
+///
+/// let mut grants: GrantStore<u64> = GrantStore::new(PermissionManager::from_json(r#"{"view": true, "edit": true}"#));
+///
+/// let view = grants.manager().perm_from_json(r#"{"view": true}"#);
+/// grants.grant(42, &view);
+///
+/// assert!(grants.check(&42, "view"));
+/// assert!(!grants.check(&42, "edit"));
+/// assert!(!grants.check(&7, "view"));
+/// ```
+pub struct GrantStore<S: Hash + Eq> {
+    manager: Arc<PermissionManager>,
+    grants: HashMap<S, Permission>,
+    timed_grants: HashMap<S, Vec<ExpiringPermission>>,
+    conditional_grants: HashMap<S, Vec<ConditionalGrant>>,
+    memberships: HashMap<S, HashSet<S>>,
+    max_depth: usize,
+    grant_hooks: Vec<GrantHook<S>>,
+    revoke_hooks: Vec<GrantHook<S>>,
+}
+
+// Callback receiving the subject and the permission granted to or revoked from it.
+type GrantHook<S> = Box<dyn Fn(&S, &Permission) + Send + Sync + UnwindSafe + RefUnwindSafe>;
+
+impl<S: Hash + Eq> GrantStore<S> {
+    /// Creates a new empty [`GrantStore`](crate::GrantStore) for the [`PermissionManager`](crate::PermissionManager) provided.  
+    /// Accepts an owned [`PermissionManager`](crate::PermissionManager) or a shared one, such as the ones stored in a
+    /// [`ManagerRegistry`](crate::ManagerRegistry).
+    pub fn new<M>(manager: M) -> GrantStore<S>
+    where
+        M: Into<Arc<PermissionManager>>,
+    {
+        GrantStore {
+            manager: manager.into(),
+            grants: HashMap::new(),
+            timed_grants: HashMap::new(),
+            conditional_grants: HashMap::new(),
+            memberships: HashMap::new(),
+            max_depth: DEFAULT_MAX_GROUP_DEPTH,
+            grant_hooks: Vec::new(),
+            revoke_hooks: Vec::new(),
+        }
+    }
+
+    /// Returns the [`PermissionManager`](crate::PermissionManager) grants are validated against.
+    pub fn manager(&self) -> &PermissionManager {
+        &self.manager
+    }
+
+    /// Grants the actions of the [`Permission`](crate::Permission) provided to a subject, in addition to the ones already granted.
+    ///
+    /// # Panics:
+    ///
+    /// Panics if the [`Permission`](crate::Permission) is not valid for the [`PermissionManager`](crate::PermissionManager).
+    pub fn grant(&mut self, subject: S, perm: &Permission) {
+        if !self.manager.validate_perm(perm) {
+            panic!("Permission to grant is not valid for the GrantStore PermissionManager");
+        }
+
+        for hook in &self.grant_hooks {
+            hook(&subject, perm);
+        }
+        match self.grants.get_mut(&subject) {
+            Some(granted) => granted.union_with(perm),
+            None => {
+                self.grants.insert(subject, perm.clone());
+            }
+        }
+    }
+
+    /// Grants the actions of an [`ExpiringPermission`](crate::ExpiringPermission) to a subject only while it is active.
+    ///
+    /// # Examples:
+    ///
+    /// ```
+    /// use std::time::{Duration, SystemTime};
+    /// use simple_perm_manager::{ExpiringPermission, GrantStore, PermissionManager};
+    ///
+    /// let mut grants = GrantStore::new(PermissionManager::from_json(r#"{"view": true, "edit": true}"#));
+    /// let now = SystemTime::now();
+    /// let edit = grants.manager().perm_from_json(r#"{"edit": true}"#);
+    /// grants.grant_expiring("alice", ExpiringPermission::until(edit, now + Duration::from_secs(3600)));
+    ///
+    /// assert!(grants.check(&"alice", "edit"));
+    /// assert!(!grants.check_at(&"alice", "edit", now + Duration::from_secs(7200)));
+    /// ```
+    ///
+    /// # Panics:
+    ///
+    /// Panics if the [`Permission`](crate::Permission) is not valid for the [`PermissionManager`](crate::PermissionManager).
+    pub fn grant_expiring(&mut self, subject: S, perm: ExpiringPermission) {
+        if !self.manager.validate_perm(perm.perm()) {
+            panic!("Permission to grant is not valid for the GrantStore PermissionManager");
+        }
+
+        for hook in &self.grant_hooks {
+            hook(&subject, perm.perm());
+        }
+        self.timed_grants.entry(subject).or_default().push(perm);
+    }
+
+    /// Grants the actions of the [`Permission`](crate::Permission) provided to a subject only when the [`Condition`](crate::Condition)
+    /// holds for the [`Context`](crate::Context) of the check.
+    ///
+    /// # Examples:
+    ///
+    /// ```
+    /// use simple_perm_manager::{Context, GrantStore, PermissionManager};
+    ///
+    /// let mut grants = GrantStore::new(PermissionManager::from_json(r#"{"view": true, "edit": true}"#));
+    /// let edit = grants.manager().perm_from_json(r#"{"edit": true}"#);
+    /// grants.grant_if("alice", &edit, |context: &Context| context.get("state").is_some_and(|state| state == "draft"));
+    ///
+    /// assert!(grants.check_with(&"alice", "edit", &Context::new().with("state", "draft")));
+    /// assert!(!grants.check_with(&"alice", "edit", &Context::new().with("state", "published")));
+    /// assert!(!grants.check(&"alice", "edit"));
+    /// ```
+    ///
+    /// # Panics:
+    ///
+    /// Panics if the [`Permission`](crate::Permission) is not valid for the [`PermissionManager`](crate::PermissionManager).
+    pub fn grant_if<C>(&mut self, subject: S, perm: &Permission, condition: C)
+    where
+        C: Condition + 'static,
+    {
+        if !self.manager.validate_perm(perm) {
+            panic!("Permission to grant is not valid for the GrantStore PermissionManager");
+        }
+
+        for hook in &self.grant_hooks {
+            hook(&subject, perm);
+        }
+        self.conditional_grants
+            .entry(subject)
+            .or_default()
+            .push(ConditionalGrant {
+                perm: perm.clone(),
+                condition: Arc::new(condition),
+                spec: None,
+            });
+    }
+
+    /// Same as [`grant_if`](crate::GrantStore::grant_if()) but with a serializable [`ConditionSpec`](crate::ConditionSpec),
+    /// so the grant is kept by [`to_json`](crate::GrantStore::to_json()).
+    ///
+    /// # Panics:
+    ///
+    /// Panics if the [`Permission`](crate::Permission) is not valid for the [`PermissionManager`](crate::PermissionManager).
+    pub fn grant_when(&mut self, subject: S, perm: &Permission, spec: ConditionSpec) {
+        if !self.manager.validate_perm(perm) {
+            panic!("Permission to grant is not valid for the GrantStore PermissionManager");
+        }
+
+        for hook in &self.grant_hooks {
+            hook(&subject, perm);
+        }
+        self.conditional_grants
+            .entry(subject)
+            .or_default()
+            .push(ConditionalGrant {
+                perm: perm.clone(),
+                condition: Arc::new(spec.clone()),
+                spec: Some(spec),
+            });
+    }
+
+    /// Removes the temporary grants that have expired at the time provided. Returns the number of grants removed.
+    pub fn purge_expired(&mut self, at: SystemTime) -> usize {
+        let mut purged = 0;
+        self.timed_grants.retain(|_, perms| {
+            let len = perms.len();
+            perms.retain(|perm| !perm.is_expired(at));
+            purged += len - perms.len();
+            !perms.is_empty()
+        });
+
+        purged
+    }
+
+    /// Returns the earliest time after the one provided at which a temporary grant becomes active or expires, or the time window
+    /// of a conditional grant opens or closes, if any.  
+    /// Evaluations are guaranteed to give the same result until then, except for conditional grants whose closures depend on the
+    /// current time.
+    pub fn next_transition(&self, after: SystemTime) -> Option<SystemTime> {
+        let timed = self
+            .timed_grants
+            .values()
+            .flatten()
+            .flat_map(|perm| [perm.valid_from(), perm.valid_until()]);
+        let windows = self
+            .conditional_grants
+            .values()
+            .flatten()
+            .flat_map(|grant| match grant.spec {
+                Some(ConditionSpec::TimeWindow { from, until }) => [from, until],
+                _ => [None, None],
+            });
+
+        timed
+            .chain(windows)
+            .flatten()
+            .filter(|time| *time > after)
+            .min()
+    }
+
+    /// Revokes the actions of the [`Permission`](crate::Permission) provided from a subject, including its temporary and
+    /// conditional grants.
+    ///
+    /// # Examples:
+    ///
+    /// ```
+    /// use simple_perm_manager::{GrantStore, PermissionManager};
+    ///
+    /// let mut grants = GrantStore::new(PermissionManager::from_json(r#"{"view": true, "edit": true}"#));
+    /// grants.grant("alice", &grants.manager().perm_from_json(r#"{"view": true, "edit": true}"#));
+    ///
+    /// grants.revoke(&"alice", &grants.manager().perm_from_json(r#"{"edit": true}"#));
+    ///
+    /// assert!(grants.check(&"alice", "view"));
+    /// assert!(!grants.check(&"alice", "edit"));
+    /// ```
+    ///
+    /// # Panics:
+    ///
+    /// Panics if the [`Permission`](crate::Permission) is not managed by the [`PermissionManager`](crate::PermissionManager).
+    pub fn revoke(&mut self, subject: &S, perm: &Permission) {
+        if !self.manager.get_universe().has_same_manager(perm) {
+            panic!("Permission to revoke is not managed by the GrantStore PermissionManager");
+        }
+
+        if let Some(granted) = self.grants.get_mut(subject) {
+            granted.difference_with(perm);
+            if granted.is_empty() {
+                self.grants.remove(subject);
+            }
+        }
+        if let Some(timed) = self.timed_grants.get_mut(subject) {
+            for granted in timed.iter_mut() {
+                granted.perm_mut().difference_with(perm);
+            }
+            timed.retain(|granted| !granted.perm().is_empty());
+            if timed.is_empty() {
+                self.timed_grants.remove(subject);
+            }
+        }
+        if let Some(conditional) = self.conditional_grants.get_mut(subject) {
+            for granted in conditional.iter_mut() {
+                granted.perm.difference_with(perm);
+            }
+            conditional.retain(|granted| !granted.perm.is_empty());
+            if conditional.is_empty() {
+                self.conditional_grants.remove(subject);
+            }
+        }
+        for hook in &self.revoke_hooks {
+            hook(subject, perm);
+        }
+    }
+
+    /// Revokes all the actions granted to a subject, including its temporary and conditional grants.  
+    /// Returns the [`Permission`](crate::Permission) that was unconditionally and permanently granted to the subject, if any.
+    pub fn revoke_all(&mut self, subject: &S) -> Option<Permission> {
+        let timed = self.timed_grants.remove(subject).unwrap_or_default();
+        let conditional = self.conditional_grants.remove(subject).unwrap_or_default();
+        let granted = self.grants.remove(subject);
+
+        if !self.revoke_hooks.is_empty() {
+            let revoked = self.manager.union_all(
+                granted
+                    .iter()
+                    .chain(timed.iter().map(|perm| perm.perm()))
+                    .chain(conditional.iter().map(|grant| &grant.perm)),
+            );
+            if !revoked.is_empty() {
+                for hook in &self.revoke_hooks {
+                    hook(subject, &revoked);
+                }
+            }
+        }
+
+        granted
+    }
+
+    /// Registers a callback called with the subject and the [`Permission`](crate::Permission) granted every time actions are
+    /// granted, including temporary and conditional grants.
+    ///
+    /// # Examples:
+    ///
+    /// ```
+    /// use std::sync::{Arc, Mutex};
+    /// use simple_perm_manager::{GrantStore, PermissionManager};
+    ///
+    /// let events = Arc::new(Mutex::new(Vec::new()));
+    /// let hook_events = events.clone();
+    ///
+    /// let mut grants = GrantStore::new(PermissionManager::from_json(r#"{"view": true}"#));
+    /// grants.on_grant(move |subject: &&str, perm| hook_events.lock().unwrap().push((subject.to_string(), perm.to_json())));
+    /// grants.grant("alice", &grants.manager().perm_from_json(r#"{"view": true}"#));
+    ///
+    /// assert_eq!(*events.lock().unwrap(), vec![(String::from("alice"), String::from(r#"{"view":true}"#))]);
+    /// ```
+    pub fn on_grant<F>(&mut self, hook: F)
+    where
+        F: Fn(&S, &Permission) + Send + Sync + UnwindSafe + RefUnwindSafe + 'static,
+    {
+        self.grant_hooks.push(Box::new(hook));
+    }
+
+    /// Registers a callback called with the subject and the [`Permission`](crate::Permission) revoked every time actions are
+    /// revoked with [`revoke`](crate::GrantStore::revoke()) or [`revoke_all`](crate::GrantStore::revoke_all()).  
+    /// [`revoke_all`](crate::GrantStore::revoke_all()) only calls it for subjects that had grants, with all the actions they had.
+    pub fn on_revoke<F>(&mut self, hook: F)
+    where
+        F: Fn(&S, &Permission) + Send + Sync + UnwindSafe + RefUnwindSafe + 'static,
+    {
+        self.revoke_hooks.push(Box::new(hook));
+    }
+
+    /// Returns the [`Permission`](crate::Permission) granted to a subject, directly or through the groups it belongs to.  
+    /// Subjects without grants get an empty [`Permission`](crate::Permission) managed by the [`PermissionManager`](crate::PermissionManager).
+    ///
+    /// # Examples:
+    ///
+    /// ```
+    /// use simple_perm_manager::{GrantStore, PermissionManager};
+    ///
+    /// let mut grants = GrantStore::new(PermissionManager::from_json(r#"{"view": true, "edit": true}"#));
+    /// grants.grant("engineering", &grants.manager().perm_from_json(r#"{"view": true}"#));
+    /// grants.grant("backend", &grants.manager().perm_from_json(r#"{"edit": true}"#));
+    /// grants.add_member("backend", "engineering");
+    /// grants.add_member("alice", "backend");
+    ///
+    /// assert_eq!(grants.effective_perm(&"alice").get_actions().len(), 2);
+    /// ```
+    ///
+    /// # Panics:
+    ///
+    /// Panics if the group nesting of the subject is deeper than the maximum resolution depth.
+    pub fn effective_perm(&self, subject: &S) -> Permission {
+        self.effective_perm_at(subject, SystemTime::now())
+    }
+
+    /// Returns the [`Permission`](crate::Permission) granted to a subject at the time provided, directly or through the groups
+    /// it belongs to.
+    ///
+    /// # Panics:
+    ///
+    /// Panics if the group nesting of the subject is deeper than the maximum resolution depth.
+    pub fn effective_perm_at(&self, subject: &S, at: SystemTime) -> Permission {
+        self.effective_perm_in(subject, at, &Context::new())
+    }
+
+    /// Returns the [`Permission`](crate::Permission) granted to a subject for the [`Context`](crate::Context) provided, directly or
+    /// through the groups it belongs to.
+    ///
+    /// # Panics:
+    ///
+    /// Panics if the group nesting of the subject is deeper than the maximum resolution depth.
+    pub fn effective_perm_with(&self, subject: &S, context: &Context) -> Permission {
+        self.effective_perm_in(subject, SystemTime::now(), context)
+    }
+
+    /// Returns `true` if the action is granted to a subject, directly or through the groups it belongs to.
+    ///
+    /// # Panics:
+    ///
+    /// Panics if the group nesting of the subject is deeper than the maximum resolution depth.
+    pub fn check(&self, subject: &S, action_str: &str) -> bool {
+        self.check_at(subject, action_str, SystemTime::now())
+    }
+
+    /// Returns `true` if the action is granted to a subject at the time provided, directly or through the groups it belongs to.
+    ///
+    /// # Panics:
+    ///
+    /// Panics if the group nesting of the subject is deeper than the maximum resolution depth.
+    pub fn check_at(&self, subject: &S, action_str: &str, at: SystemTime) -> bool {
+        self.check_in(subject, action_str, at, &Context::new())
+    }
+
+    /// Returns `true` if the action is granted to a subject for the [`Context`](crate::Context) provided, directly or through the
+    /// groups it belongs to.
+    ///
+    /// # Panics:
+    ///
+    /// Panics if the group nesting of the subject is deeper than the maximum resolution depth.
+    pub fn check_with(&self, subject: &S, action_str: &str, context: &Context) -> bool {
+        self.check_in(subject, action_str, SystemTime::now(), context)
+    }
+
+    /// Makes a subject member of a group. Returns `false` if the subject was already a direct member of the group.
+    ///
+    /// # Panics:
+    ///
+    /// Panics if the membership would create a cycle, i.e. the group is the subject itself or already belongs to it.
+    pub fn add_member(&mut self, member: S, group: S) -> bool {
+        if member == group || self.membership_closure(&group).0.contains(&member) {
+            panic!("group membership would create a cycle");
+        }
+
+        self.memberships.entry(member).or_default().insert(group)
+    }
+
+    /// Removes a subject from a group. Returns `false` if the subject was not a direct member of the group.
+    pub fn remove_member(&mut self, member: &S, group: &S) -> bool {
+        let Some(groups) = self.memberships.get_mut(member) else {
+            return false;
+        };
+        let removed = groups.remove(group);
+        if groups.is_empty() {
+            self.memberships.remove(member);
+        }
+
+        removed
+    }
+
+    /// Returns the groups a subject belongs to, directly or through nested groups, in no particular order.
+    ///
+    /// # Panics:
+    ///
+    /// Panics if the group nesting of the subject is deeper than the maximum resolution depth.
+    pub fn groups(&self, subject: &S) -> HashSet<&S> {
+        self.resolve_groups(subject)
+    }
+
+    /// Sets the maximum number of nested group levels resolved for a subject. Defaults to 16.
+    pub fn set_max_depth(&mut self, max_depth: usize) {
+        self.max_depth = max_depth;
+    }
+
+    /// Returns the maximum number of nested group levels resolved for a subject.
+    pub fn max_depth(&self) -> usize {
+        self.max_depth
+    }
+
+    /// Returns the subjects with permanent grants in no particular order.
+    pub fn subjects(&self) -> impl Iterator<Item = &S> + '_ {
+        self.grants.keys()
+    }
+
+    // Returns a copy of the store without the grant and revoke hooks, for evaluating hypothetical changes.
+    pub(crate) fn detached(&self) -> GrantStore<S>
+    where
+        S: Clone,
+    {
+        GrantStore {
+            manager: self.manager.clone(),
+            grants: self.grants.clone(),
+            timed_grants: self.timed_grants.clone(),
+            conditional_grants: self.conditional_grants.clone(),
+            memberships: self.memberships.clone(),
+            max_depth: self.max_depth,
+            grant_hooks: Vec::new(),
+            revoke_hooks: Vec::new(),
+        }
+    }
+
+    // Returns every subject with grants or group memberships, including the groups.
+    pub(crate) fn known_subjects(&self) -> HashSet<&S> {
+        self.grants
+            .keys()
+            .chain(self.timed_grants.keys())
+            .chain(self.conditional_grants.keys())
+            .chain(self.memberships.keys())
+            .chain(self.memberships.values().flatten())
+            .collect()
+    }
+
+    // Returns the permission granted directly to a single subject, without its temporary and conditional grants.
+    pub(crate) fn grant_of(&self, subject: &S) -> Option<&Permission> {
+        self.grants.get(subject)
+    }
+
+    // Returns the groups each subject is a direct member of.
+    pub(crate) fn direct_memberships(&self) -> &HashMap<S, HashSet<S>> {
+        &self.memberships
+    }
+
+    // Returns the temporary grants of a single subject, without its groups.
+    pub(crate) fn timed_grants_of(&self, subject: &S) -> &[ExpiringPermission] {
+        self.timed_grants.get(subject).map_or(&[], Vec::as_slice)
+    }
+
+    // Returns the trace steps of the grants of a single subject, without its groups, that have the action.
+    pub(crate) fn trace_grants(
+        &self,
+        subject: &S,
+        name: &str,
+        action_str: &str,
+        at: SystemTime,
+        context: &Context,
+    ) -> Vec<TraceStep> {
+        let granted = |source: GrantSource| TraceStep::Granted {
+            subject: String::from(name),
+            source,
+        };
+        let mut steps = Vec::new();
+
+        if self
+            .grants
+            .get(subject)
+            .is_some_and(|perm| perm.contains_action(action_str))
+        {
+            steps.push(granted(GrantSource::Direct));
+        }
+        for perm in self.timed_grants.get(subject).into_iter().flatten() {
+            if perm.perm().contains_action(action_str) {
+                steps.push(match perm.is_active(at) {
+                    true => granted(GrantSource::Temporary),
+                    false => TraceStep::InactiveGrant {
+                        subject: String::from(name),
+                    },
+                });
+            }
+        }
+        for grant in self.conditional_grants.get(subject).into_iter().flatten() {
+            if grant.perm.contains_action(action_str) {
+                steps.push(match grant.condition.evaluate_at(context, at) {
+                    true => granted(GrantSource::Conditional),
+                    false => TraceStep::ConditionFailed {
+                        subject: String::from(name),
+                    },
+                });
+            }
+        }
+
+        steps
+    }
+
+    // Returns the permanent and temporary grants of a subject active at the time provided.
+    fn active_grants<'a>(
+        &'a self,
+        subject: &S,
+        at: SystemTime,
+        context: &'a Context,
+    ) -> impl Iterator<Item = &'a Permission> + 'a {
+        self.grants
+            .get(subject)
+            .into_iter()
+            .chain(
+                self.timed_grants
+                    .get(subject)
+                    .into_iter()
+                    .flatten()
+                    .filter(move |perm| perm.is_active(at))
+                    .map(ExpiringPermission::perm),
+            )
+            .chain(
+                self.conditional_grants
+                    .get(subject)
+                    .into_iter()
+                    .flatten()
+                    .filter(move |granted| granted.condition.evaluate_at(context, at))
+                    .map(|granted| &granted.perm),
+            )
+    }
+
+    pub(crate) fn effective_perm_in(
+        &self,
+        subject: &S,
+        at: SystemTime,
+        context: &Context,
+    ) -> Permission {
+        let groups = self.resolve_groups(subject);
+        self.manager.union_all(
+            groups
+                .into_iter()
+                .chain([subject])
+                .flat_map(|s| self.active_grants(s, at, context)),
+        )
+    }
+
+    fn check_in(&self, subject: &S, action_str: &str, at: SystemTime, context: &Context) -> bool {
+        self.resolve_groups(subject)
+            .into_iter()
+            .chain([subject])
+            .flat_map(|s| self.active_grants(s, at, context))
+            .any(|granted| granted.contains_action(action_str))
+    }
+
+    // Returns the groups of a subject, panicking if the nesting is deeper than the maximum resolution depth.
+    fn resolve_groups(&self, subject: &S) -> HashSet<&S> {
+        let (groups, depth) = self.membership_closure(subject);
+        if depth > self.max_depth {
+            panic!(
+                "group nesting exceeds the maximum resolution depth of {}",
+                self.max_depth
+            );
+        }
+
+        groups
+    }
+
+    // Returns all the groups a subject belongs to along with the deepest nesting level found.
+    fn membership_closure(&self, subject: &S) -> (HashSet<&S>, usize) {
+        let mut closure: HashSet<&S> = HashSet::new();
+        let mut level: Vec<&S> = self
+            .memberships
+            .get(subject)
+            .into_iter()
+            .flatten()
+            .collect();
+        let mut depth = 0;
+
+        while !level.is_empty() {
+            depth += 1;
+            closure.extend(level.iter().copied());
+            level = level
+                .into_iter()
+                .filter_map(|s| self.memberships.get(s))
+                .flatten()
+                .filter(|group| !closure.contains(*group) && *group != subject)
+                .collect();
+        }
+
+        (closure, depth)
+    }
+}
+
+impl<S: Hash + Eq + Clone> GrantStore<S> {
+    /// Returns an immutable [`GrantSnapshot`](crate::GrantSnapshot) of every grant and group membership of the
+    /// [`GrantStore`](crate::GrantStore), taken at the current time, to [`restore`](Self::restore()) it later.
+    ///
+    /// # Examples:
+    ///
+    /// ```
+    /// use simple_perm_manager::{GrantStore, PermissionManager};
+    ///
+    /// let mut grants: GrantStore<u64> = GrantStore::new(PermissionManager::from_json(r#"{"view": true, "edit": true}"#));
+    /// let edit = grants.manager().perm_from_json(r#"{"edit": true}"#);
+    /// grants.grant(42, &edit);
+    ///
+    /// let snapshot = grants.snapshot();
+    /// grants.revoke_all(&42);
+    /// assert!(!grants.check(&42, "edit"));
+    ///
+    /// grants.restore(&snapshot);
+    /// assert!(grants.check(&42, "edit"));
+    /// ```
+    pub fn snapshot(&self) -> GrantSnapshot<S> {
+        GrantSnapshot {
+            manager_id: self.manager.id(),
+            grants: self.grants.clone(),
+            timed_grants: self.timed_grants.clone(),
+            conditional_grants: self.conditional_grants.clone(),
+            memberships: self.memberships.clone(),
+            taken_at: SystemTime::now(),
+        }
+    }
+
+    /// Replaces every grant and group membership of the [`GrantStore`](crate::GrantStore) with the ones of a
+    /// [`GrantSnapshot`](crate::GrantSnapshot), e.g. to roll back a bad bulk change.  
+    /// Revoke callbacks are called for each subject with the actions it loses, then grant callbacks with the actions it gains,
+    /// counting its temporary and conditional grants. Group membership changes do not call callbacks.
+    ///
+    /// # Panics:
+    ///
+    /// Panics if the [`GrantSnapshot`](crate::GrantSnapshot) was taken from a [`GrantStore`](crate::GrantStore) of another
+    /// [`PermissionManager`](crate::PermissionManager).
+    pub fn restore(&mut self, snapshot: &GrantSnapshot<S>) {
+        if snapshot.manager_id != self.manager.id() {
+            panic!("GrantSnapshot was not taken for the GrantStore PermissionManager");
+        }
+
+        let hooked = !self.grant_hooks.is_empty() || !self.revoke_hooks.is_empty();
+        let before: Vec<(S, Permission)> = if hooked {
+            self.grants
+                .keys()
+                .chain(self.timed_grants.keys())
+                .chain(self.conditional_grants.keys())
+                .chain(snapshot.grants.keys())
+                .chain(snapshot.timed_grants.keys())
+                .chain(snapshot.conditional_grants.keys())
+                .collect::<HashSet<&S>>()
+                .into_iter()
+                .map(|subject| (subject.clone(), self.direct_perm(subject)))
+                .collect()
+        } else {
+            Vec::new()
+        };
+
+        self.grants = snapshot.grants.clone();
+        self.timed_grants = snapshot.timed_grants.clone();
+        self.conditional_grants = snapshot.conditional_grants.clone();
+        self.memberships = snapshot.memberships.clone();
+
+        for (subject, before) in before {
+            let after = self.direct_perm(&subject);
+            let mut revoked = before.clone();
+            revoked.difference_with(&after);
+            let mut granted = after;
+            granted.difference_with(&before);
+
+            if !revoked.is_empty() {
+                for hook in &self.revoke_hooks {
+                    hook(&subject, &revoked);
+                }
+            }
+            if !granted.is_empty() {
+                for hook in &self.grant_hooks {
+                    hook(&subject, &granted);
+                }
+            }
+        }
+    }
+
+    // Returns the union of the permanent, temporary and conditional grants of a subject, without its groups.
+    fn direct_perm(&self, subject: &S) -> Permission {
+        self.manager.union_all(
+            self.grants
+                .get(subject)
+                .into_iter()
+                .chain(
+                    self.timed_grants
+                        .get(subject)
+                        .into_iter()
+                        .flatten()
+                        .map(|perm| perm.perm()),
+                )
+                .chain(
+                    self.conditional_grants
+                        .get(subject)
+                        .into_iter()
+                        .flatten()
+                        .map(|grant| &grant.perm),
+                ),
+        )
+    }
+}
+
+/// Immutable copy of the grants and group memberships of a [`GrantStore`](crate::GrantStore), created with
+/// [`GrantStore::snapshot`](crate::GrantStore::snapshot()) and applied with [`GrantStore::restore`](crate::GrantStore::restore()).
+#[derive(Clone, Debug)]
+pub struct GrantSnapshot<S: Hash + Eq> {
+    manager_id: ManagerId,
+    grants: HashMap<S, Permission>,
+    timed_grants: HashMap<S, Vec<ExpiringPermission>>,
+    conditional_grants: HashMap<S, Vec<ConditionalGrant>>,
+    memberships: HashMap<S, HashSet<S>>,
+    taken_at: SystemTime,
+}
+
+impl<S: Hash + Eq> GrantSnapshot<S> {
+    /// Returns the time the [`GrantSnapshot`](crate::GrantSnapshot) was taken.
+    pub fn taken_at(&self) -> SystemTime {
+        self.taken_at
+    }
+
+    /// Returns the [`ManagerId`](crate::ManagerId) of the [`PermissionManager`](crate::PermissionManager) of the
+    /// [`GrantStore`](crate::GrantStore) the [`GrantSnapshot`](crate::GrantSnapshot) was taken from.
+    pub fn manager_id(&self) -> ManagerId {
+        self.manager_id
+    }
+}
+
+impl<S: Hash + Eq + Display> GrantStore<S> {
+    /// Serializes the grants and group memberships into a JSON string with the format:
+    ///
+    /// ```json
+    /// {
+    ///     "max_depth": 16,
+    ///     "grants": {"alice": {"view": true}},
+    ///     "timed_grants": {"alice": [{"actions": {"edit": true}, "valid_from": null, "valid_until": 1700000000000}]},
+    ///     "conditional_grants": {"alice": [{"actions": {"edit": true}, "condition": {"type": "expression", "expression": "internal"}}]},
+    ///     "memberships": {"alice": ["backend"]}
+    /// }
+    /// ```
+    ///
+    /// Times are milliseconds since the UNIX epoch and conditions use the [`ConditionSpec::to_json`](crate::ConditionSpec::to_json()) format.
+    ///
+    /// # Panics:
+    ///
+    /// Panics if a conditional grant was created with [`grant_if`](crate::GrantStore::grant_if()), as its condition is not serializable.
+    pub fn to_json(&self) -> String {
+        let perm_value = |perm: &Permission| -> Value {
+            serde_json::from_str(&perm.to_json())
+                .expect("unexpected error serializing a Permission in a GrantStore")
+        };
+
+        let grants: Map<String, Value> = self
+            .grants
+            .iter()
+            .map(|(subject, perm)| (subject.to_string(), perm_value(perm)))
+            .collect();
+        let timed_grants: Map<String, Value> = self
+            .timed_grants
+            .iter()
+            .map(|(subject, perms)| {
+                let perms = perms
+                    .iter()
+                    .map(|perm| {
+                        let mut map = Map::new();
+                        map.insert(String::from("actions"), perm_value(perm.perm()));
+                        map.insert(String::from("valid_from"), time_to_value(perm.valid_from()));
+                        map.insert(
+                            String::from("valid_until"),
+                            time_to_value(perm.valid_until()),
+                        );
+                        Value::Object(map)
+                    })
+                    .collect();
+                (subject.to_string(), Value::Array(perms))
+            })
+            .collect();
+        let conditional_grants: Map<String, Value> = self
+            .conditional_grants
+            .iter()
+            .map(|(subject, granted)| {
+                let granted = granted
+                    .iter()
+                    .map(|granted| {
+                        let spec = granted.spec.as_ref().unwrap_or_else(|| {
+                            panic!("GrantStore has a conditional grant with a non serializable condition")
+                        });
+                        let mut map = Map::new();
+                        map.insert(String::from("actions"), perm_value(&granted.perm));
+                        map.insert(String::from("condition"), Value::Object(spec.to_map()));
+                        Value::Object(map)
+                    })
+                    .collect();
+                (subject.to_string(), Value::Array(granted))
+            })
+            .collect();
+        let memberships: Map<String, Value> = self
+            .memberships
+            .iter()
+            .map(|(member, groups)| {
+                let mut groups: Vec<String> =
+                    groups.iter().map(|group| group.to_string()).collect();
+                groups.sort();
+                (member.to_string(), Value::from(groups))
+            })
+            .collect();
+
+        let mut map = Map::new();
+        map.insert(String::from("max_depth"), Value::from(self.max_depth));
+        map.insert(String::from("grants"), Value::Object(grants));
+        map.insert(String::from("timed_grants"), Value::Object(timed_grants));
+        map.insert(
+            String::from("conditional_grants"),
+            Value::Object(conditional_grants),
+        );
+        map.insert(String::from("memberships"), Value::Object(memberships));
+
+        Value::Object(map).to_string()
+    }
+}
+
+impl<S: Hash + Eq + FromStr> GrantStore<S> {
+    /// Creates a [`GrantStore`](crate::GrantStore) for the [`PermissionManager`](crate::PermissionManager) provided from a JSON string
+    /// created with [`GrantStore::to_json`](crate::GrantStore::to_json()). Missing keys are considered empty.
+    ///
+    /// # Examples:
+    ///
+    /// ```
+    /// use std::sync::Arc;
+    /// use simple_perm_manager::{ConditionSpec, Context, GrantStore, PermissionManager};
+    ///
+    /// let manager = Arc::new(PermissionManager::from_json(r#"{"view": true, "edit": true}"#));
+    /// let mut grants: GrantStore<String> = GrantStore::new(manager.clone());
+    /// let edit = manager.perm_from_json(r#"{"edit": true}"#);
+    /// grants.grant_when(String::from("alice"), &edit, ConditionSpec::attribute("region", "==", "EU"));
+    ///
+    /// let restored: GrantStore<String> = GrantStore::from_json(manager, &grants.to_json());
+    /// assert!(restored.check_with(&String::from("alice"), "edit", &Context::new().with("region", "EU")));
+    /// ```
+    ///
+    /// # Panics:
+    ///
+    /// - Panics if the JSON string does not have the [`GrantStore::to_json`](crate::GrantStore::to_json()) format.
+    /// - Panics if a subject can not be parsed.
+    /// - Panics if any action is not in the universe of the [`PermissionManager`](crate::PermissionManager).
+    /// - Panics if the group memberships have cycles.
+    pub fn from_json<M>(manager: M, json: &str) -> GrantStore<S>
+    where
+        M: Into<Arc<PermissionManager>>,
+    {
+        let map = match serde_json::from_str(json) {
+            Ok(Value::Object(map)) => map,
+            _ => panic!("wrong format in GrantStore json string"),
+        };
+        let section = |key: &str| -> Map<String, Value> {
+            match map.get(key) {
+                Some(Value::Object(section)) => section.clone(),
+                None => Map::new(),
+                _ => panic!("wrong format in GrantStore json string - invalid {}", key),
+            }
+        };
+        let subject = |subject: &str| -> S {
+            match S::from_str(subject) {
+                Ok(subject) => subject,
+                Err(_) => panic!("wrong subject in GrantStore json string: {}", subject),
+            }
+        };
+        let entries = |value: &Value| -> Vec<Map<String, Value>> {
+            match value {
+                Value::Array(entries) => entries
+                    .iter()
+                    .map(|entry| match entry {
+                        Value::Object(entry) => entry.clone(),
+                        _ => panic!("wrong format in GrantStore json string - invalid grant"),
+                    })
+                    .collect(),
+                _ => panic!("wrong format in GrantStore json string - invalid grant list"),
+            }
+        };
+
+        let mut store = GrantStore::new(manager);
+        match map.get("max_depth") {
+            Some(Value::Number(depth)) if depth.is_u64() => {
+                store.set_max_depth(depth.as_u64().unwrap_or_default() as usize)
+            }
+            None => (),
+            _ => panic!("wrong format in GrantStore json string - invalid max_depth"),
+        }
+
+        for (name, actions) in section("grants") {
+            let perm = store.manager.perm_from_json(&actions.to_string());
+            store.grant(subject(&name), &perm);
+        }
+        for (name, timed) in section("timed_grants") {
+            for entry in entries(&timed) {
+                let perm = store.entry_perm(&entry);
+                let valid_from = value_to_time(entry.get("valid_from"))
+                    .expect("wrong format in GrantStore json string - invalid valid_from");
+                let valid_until = value_to_time(entry.get("valid_until"))
+                    .expect("wrong format in GrantStore json string - invalid valid_until");
+                store.grant_expiring(
+                    subject(&name),
+                    ExpiringPermission::new(perm, valid_from, valid_until),
+                );
+            }
+        }
+        for (name, conditional) in section("conditional_grants") {
+            for entry in entries(&conditional) {
+                let perm = store.entry_perm(&entry);
+                let spec = match entry.get("condition") {
+                    Some(Value::Object(condition)) => {
+                        match ConditionSpec::try_from_map(condition) {
+                            Ok(spec) => spec,
+                            Err(error) => panic!("{}", error),
+                        }
+                    }
+                    _ => panic!("wrong format in GrantStore json string - missing condition"),
+                };
+                store.grant_when(subject(&name), &perm, spec);
+            }
+        }
+        for (member, groups) in section("memberships") {
+            let groups = match groups {
+                Value::Array(groups) => groups,
+                _ => panic!("wrong format in GrantStore json string - invalid memberships"),
+            };
+            for group in groups {
+                match group {
+                    Value::String(group) => {
+                        store.add_member(subject(&member), subject(&group));
+                    }
+                    _ => panic!("wrong format in GrantStore json string - invalid group"),
+                }
+            }
+        }
+
+        store
+    }
+
+    // Returns the managed permission of the actions object of a serialized grant entry.
+    fn entry_perm(&self, entry: &Map<String, Value>) -> Permission {
+        match entry.get("actions") {
+            Some(actions @ Value::Object(_)) => self.manager.perm_from_json(&actions.to_string()),
+            _ => panic!("wrong format in GrantStore json string - missing actions"),
+        }
+    }
+}
+
+impl<S: Hash + Eq + fmt::Debug> fmt::Debug for GrantStore<S> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("GrantStore")
+            .field("manager", &self.manager)
+            .field("grants", &self.grants)
+            .field("timed_grants", &self.timed_grants)
+            .field("conditional_grants", &self.conditional_grants)
+            .field("memberships", &self.memberships)
+            .field("max_depth", &self.max_depth)
+            .field("grant_hooks", &self.grant_hooks.len())
+            .field("revoke_hooks", &self.revoke_hooks.len())
+            .finish()
+    }
+}
+
+// Permission granted only while its condition holds, with the definition of the condition when serializable.
+#[derive(Clone)]
+struct ConditionalGrant {
+    perm: Permission,
+    condition: Arc<dyn Condition>,
+    spec: Option<ConditionSpec>,
+}
+
+impl fmt::Debug for ConditionalGrant {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("ConditionalGrant")
+            .field("perm", &self.perm)
+            .field("spec", &self.spec)
+            .finish_non_exhaustive()
+    }
+}
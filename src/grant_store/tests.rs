@@ -0,0 +1,662 @@
+use super::*;
+use std::collections::HashSet;
+use std::time::Duration;
+
+fn test_grant_store() -> GrantStore<String> {
+    GrantStore::new(PermissionManager::from_json(
+        r#"{"post": {"view": true, "edit": true, "delete": true}}"#,
+    ))
+}
+
+#[test]
+fn new_test() {
+    let manager = Arc::new(PermissionManager::from_json(r#"{"view": true}"#));
+    let grants: GrantStore<u32> = GrantStore::new(manager.clone());
+
+    assert_eq!(grants.manager().id(), manager.id());
+    assert_eq!(grants.subjects().count(), 0);
+}
+
+#[test]
+fn grant_test() {
+    let mut grants = test_grant_store();
+    let view = grants
+        .manager()
+        .perm_from_json(r#"{"post": {"view": true}}"#);
+    let edit = grants
+        .manager()
+        .perm_from_json(r#"{"post": {"edit": true}}"#);
+
+    grants.grant(String::from("alice"), &view);
+    grants.grant(String::from("alice"), &edit);
+
+    assert_eq!(
+        *grants.effective_perm(&String::from("alice")).get_actions(),
+        HashSet::from([String::from("post:view"), String::from("post:edit")])
+    );
+
+    match std::panic::catch_unwind(move || {
+        grants.grant(
+            String::from("bob"),
+            &Permission::from_json(r#"{"post": {"view": true}}"#),
+        );
+    }) {
+        Ok(_) => panic!("granting a permission of another manager should have panicked"),
+        Err(_) => (),
+    }
+}
+
+#[test]
+fn revoke_test() {
+    let mut grants = test_grant_store();
+    let alice = String::from("alice");
+    grants.grant(
+        alice.clone(),
+        &grants
+            .manager()
+            .perm_from_json(r#"{"post": {"view": true, "edit": true}}"#),
+    );
+
+    grants.revoke(
+        &alice,
+        &grants
+            .manager()
+            .perm_from_json(r#"{"post": {"edit": true}}"#),
+    );
+    assert_eq!(grants.check(&alice, "post:view"), true);
+    assert_eq!(grants.check(&alice, "post:edit"), false);
+
+    // Subjects without actions are removed
+    grants.revoke(
+        &alice,
+        &grants
+            .manager()
+            .perm_from_json(r#"{"post": {"view": true}}"#),
+    );
+    assert_eq!(grants.subjects().count(), 0);
+
+    match std::panic::catch_unwind(move || {
+        grants.revoke(&alice, &Permission::from_json("{}"));
+    }) {
+        Ok(_) => panic!("revoking a permission of another manager should have panicked"),
+        Err(_) => (),
+    }
+}
+
+#[test]
+fn revoke_all_test() {
+    let mut grants = test_grant_store();
+    let alice = String::from("alice");
+    grants.grant(
+        alice.clone(),
+        &grants
+            .manager()
+            .perm_from_json(r#"{"post": {"view": true}}"#),
+    );
+
+    assert_eq!(
+        *grants.revoke_all(&alice).unwrap().get_actions(),
+        HashSet::from([String::from("post:view")])
+    );
+    assert_eq!(grants.revoke_all(&alice).is_none(), true);
+}
+
+#[test]
+fn effective_perm_test() {
+    let grants = test_grant_store();
+    let perm = grants.effective_perm(&String::from("nobody"));
+
+    assert_eq!(perm.get_actions().is_empty(), true);
+    assert_eq!(grants.manager().validate_perm(&perm), true);
+}
+
+#[test]
+fn check_test() {
+    let mut grants = test_grant_store();
+    grants.grant(
+        String::from("alice"),
+        &grants
+            .manager()
+            .perm_from_json(r#"{"post": {"view": true}}"#),
+    );
+
+    assert_eq!(grants.check(&String::from("alice"), "post:view"), true);
+    assert_eq!(grants.check(&String::from("alice"), "post:edit"), false);
+    assert_eq!(grants.check(&String::from("bob"), "post:view"), false);
+}
+
+#[test]
+fn hooks_test() {
+    let events = Arc::new(std::sync::Mutex::new(Vec::new()));
+    let mut grants = test_grant_store();
+    let grant_events = events.clone();
+    grants.on_grant(move |subject: &String, perm| {
+        grant_events
+            .lock()
+            .unwrap()
+            .push(format!("grant {} {}", subject, perm.to_json()));
+    });
+    let revoke_events = events.clone();
+    grants.on_revoke(move |subject: &String, perm| {
+        revoke_events
+            .lock()
+            .unwrap()
+            .push(format!("revoke {} {}", subject, perm.to_json()));
+    });
+
+    let view = grants
+        .manager()
+        .perm_from_json(r#"{"post": {"view": true}}"#);
+    let edit = grants
+        .manager()
+        .perm_from_json(r#"{"post": {"edit": true}}"#);
+    grants.grant(String::from("alice"), &view);
+    grants.grant_expiring(
+        String::from("alice"),
+        ExpiringPermission::until(edit.clone(), SystemTime::now() + Duration::from_secs(60)),
+    );
+    grants.revoke(&String::from("alice"), &view);
+    grants.revoke_all(&String::from("alice"));
+    // Subjects without grants are not reported by revoke_all
+    grants.revoke_all(&String::from("bob"));
+
+    assert_eq!(
+        *events.lock().unwrap(),
+        vec![
+            String::from(r#"grant alice {"post":{"view":true}}"#),
+            String::from(r#"grant alice {"post":{"edit":true}}"#),
+            String::from(r#"revoke alice {"post":{"view":true}}"#),
+            String::from(r#"revoke alice {"post":{"edit":true}}"#),
+        ]
+    );
+}
+
+#[test]
+fn add_member_test() {
+    let mut grants: GrantStore<&str> = GrantStore::new(PermissionManager::from_json("{}"));
+
+    assert_eq!(grants.add_member("alice", "backend"), true);
+    assert_eq!(grants.add_member("alice", "backend"), false);
+    assert_eq!(grants.add_member("backend", "engineering"), true);
+
+    match std::panic::catch_unwind(move || {
+        grants.add_member("engineering", "alice");
+    }) {
+        Ok(_) => panic!("creating a membership cycle should have panicked"),
+        Err(_) => (),
+    }
+
+    let mut grants: GrantStore<&str> = GrantStore::new(PermissionManager::from_json("{}"));
+    match std::panic::catch_unwind(move || {
+        grants.add_member("alice", "alice");
+    }) {
+        Ok(_) => panic!("making a subject member of itself should have panicked"),
+        Err(_) => (),
+    }
+}
+
+#[test]
+fn remove_member_test() {
+    let mut grants: GrantStore<&str> = GrantStore::new(PermissionManager::from_json("{}"));
+    grants.add_member("alice", "backend");
+
+    assert_eq!(grants.remove_member(&"alice", &"frontend"), false);
+    assert_eq!(grants.remove_member(&"alice", &"backend"), true);
+    assert_eq!(grants.remove_member(&"alice", &"backend"), false);
+    assert_eq!(grants.groups(&"alice").is_empty(), true);
+}
+
+#[test]
+fn groups_test() {
+    let mut grants: GrantStore<&str> = GrantStore::new(PermissionManager::from_json("{}"));
+    grants.add_member("alice", "backend");
+    grants.add_member("alice", "oncall");
+    grants.add_member("backend", "engineering");
+    grants.add_member("oncall", "engineering");
+
+    assert_eq!(
+        grants.groups(&"alice"),
+        HashSet::from([&"backend", &"oncall", &"engineering"])
+    );
+    assert_eq!(grants.groups(&"engineering").is_empty(), true);
+}
+
+#[test]
+fn group_effective_perm_test() {
+    let mut grants = test_grant_store();
+    let alice = String::from("alice");
+    let backend = String::from("backend");
+    let engineering = String::from("engineering");
+    grants.grant(
+        engineering.clone(),
+        &grants
+            .manager()
+            .perm_from_json(r#"{"post": {"view": true}}"#),
+    );
+    grants.grant(
+        backend.clone(),
+        &grants
+            .manager()
+            .perm_from_json(r#"{"post": {"edit": true}}"#),
+    );
+    grants.add_member(alice.clone(), backend.clone());
+    grants.add_member(backend.clone(), engineering.clone());
+
+    assert_eq!(
+        *grants.effective_perm(&alice).get_actions(),
+        HashSet::from([String::from("post:view"), String::from("post:edit")])
+    );
+    assert_eq!(grants.check(&alice, "post:view"), true);
+    assert_eq!(grants.check(&alice, "post:delete"), false);
+    assert_eq!(grants.check(&engineering, "post:edit"), false);
+}
+
+#[test]
+fn max_depth_test() {
+    let mut grants: GrantStore<u32> = GrantStore::new(PermissionManager::from_json("{}"));
+    for subject in 0..4 {
+        grants.add_member(subject, subject + 1);
+    }
+
+    assert_eq!(grants.max_depth(), 16);
+    assert_eq!(grants.groups(&0).len(), 4);
+
+    grants.set_max_depth(4);
+    assert_eq!(grants.groups(&0).len(), 4);
+
+    grants.set_max_depth(3);
+    assert_eq!(grants.groups(&1).len(), 3);
+    match std::panic::catch_unwind(move || {
+        grants.effective_perm(&0);
+    }) {
+        Ok(_) => panic!("resolving groups deeper than the maximum depth should have panicked"),
+        Err(_) => (),
+    }
+}
+
+#[test]
+fn grant_expiring_test() {
+    let mut grants = test_grant_store();
+    let alice = String::from("alice");
+    let now = SystemTime::now();
+    let hour = Duration::from_secs(3600);
+    grants.grant(
+        alice.clone(),
+        &grants
+            .manager()
+            .perm_from_json(r#"{"post": {"view": true}}"#),
+    );
+    grants.grant_expiring(
+        alice.clone(),
+        ExpiringPermission::until(
+            grants
+                .manager()
+                .perm_from_json(r#"{"post": {"edit": true}}"#),
+            now + hour,
+        ),
+    );
+    grants.grant_expiring(
+        alice.clone(),
+        ExpiringPermission::new(
+            grants
+                .manager()
+                .perm_from_json(r#"{"post": {"delete": true}}"#),
+            Some(now + hour),
+            None,
+        ),
+    );
+
+    assert_eq!(
+        *grants.effective_perm_at(&alice, now).get_actions(),
+        HashSet::from([String::from("post:view"), String::from("post:edit")])
+    );
+    assert_eq!(
+        *grants.effective_perm_at(&alice, now + hour).get_actions(),
+        HashSet::from([String::from("post:view"), String::from("post:delete")])
+    );
+    assert_eq!(grants.check(&alice, "post:edit"), true);
+    assert_eq!(grants.check_at(&alice, "post:edit", now + hour), false);
+    assert_eq!(grants.next_transition(now), Some(now + hour));
+    assert_eq!(grants.next_transition(now + hour), None);
+
+    match std::panic::catch_unwind(move || {
+        grants.grant_expiring(
+            alice,
+            ExpiringPermission::until(Permission::from_json("{}"), now),
+        );
+    }) {
+        Ok(_) => panic!("granting a permission of another manager should have panicked"),
+        Err(_) => (),
+    }
+}
+
+#[test]
+fn purge_expired_test() {
+    let mut grants = test_grant_store();
+    let now = SystemTime::now();
+    let view = grants
+        .manager()
+        .perm_from_json(r#"{"post": {"view": true}}"#);
+    grants.grant_expiring(
+        String::from("alice"),
+        ExpiringPermission::until(view.clone(), now),
+    );
+    grants.grant_expiring(
+        String::from("alice"),
+        ExpiringPermission::until(view.clone(), now + Duration::from_secs(60)),
+    );
+
+    assert_eq!(grants.purge_expired(now), 1);
+    assert_eq!(grants.purge_expired(now), 0);
+    assert_eq!(grants.purge_expired(now + Duration::from_secs(60)), 1);
+}
+
+#[test]
+fn revoke_expiring_test() {
+    let mut grants = test_grant_store();
+    let alice = String::from("alice");
+    let now = SystemTime::now();
+    let hour = Duration::from_secs(3600);
+    grants.grant_expiring(
+        alice.clone(),
+        ExpiringPermission::until(
+            grants
+                .manager()
+                .perm_from_json(r#"{"post": {"view": true, "edit": true}}"#),
+            now + hour,
+        ),
+    );
+
+    grants.revoke(
+        &alice,
+        &grants
+            .manager()
+            .perm_from_json(r#"{"post": {"edit": true}}"#),
+    );
+    assert_eq!(grants.check_at(&alice, "post:view", now), true);
+    assert_eq!(grants.check_at(&alice, "post:edit", now), false);
+
+    assert_eq!(grants.revoke_all(&alice).is_none(), true);
+    assert_eq!(grants.check_at(&alice, "post:view", now), false);
+}
+
+#[test]
+fn grant_if_test() {
+    let mut grants = test_grant_store();
+    let alice = String::from("alice");
+    let engineering = String::from("engineering");
+    let internal = |context: &Context| context.get("internal") == Some(&true.into());
+    grants.grant_if(
+        engineering.clone(),
+        &grants
+            .manager()
+            .perm_from_json(r#"{"post": {"delete": true}}"#),
+        internal,
+    );
+    grants.add_member(alice.clone(), engineering.clone());
+
+    let context = Context::new().with("internal", true);
+    assert_eq!(grants.check_with(&alice, "post:delete", &context), true);
+    assert_eq!(
+        grants.check_with(
+            &alice,
+            "post:delete",
+            &Context::new().with("internal", false)
+        ),
+        false
+    );
+    assert_eq!(grants.check(&alice, "post:delete"), false);
+    assert_eq!(
+        *grants.effective_perm_with(&alice, &context).get_actions(),
+        HashSet::from([String::from("post:delete")])
+    );
+    assert_eq!(grants.effective_perm(&alice).get_actions().is_empty(), true);
+
+    grants.revoke(
+        &engineering,
+        &grants
+            .manager()
+            .perm_from_json(r#"{"post": {"delete": true}}"#),
+    );
+    assert_eq!(grants.check_with(&alice, "post:delete", &context), false);
+
+    match std::panic::catch_unwind(move || {
+        grants.grant_if(alice, &Permission::from_json("{}"), internal);
+    }) {
+        Ok(_) => panic!("granting a permission of another manager should have panicked"),
+        Err(_) => (),
+    }
+}
+
+#[test]
+fn grant_when_test() {
+    let mut grants = test_grant_store();
+    let alice = String::from("alice");
+    grants.grant_when(
+        alice.clone(),
+        &grants
+            .manager()
+            .perm_from_json(r#"{"post": {"edit": true}}"#),
+        ConditionSpec::Expression(crate::Expression::parse("amount <= 1000")),
+    );
+
+    assert_eq!(
+        grants.check_with(&alice, "post:edit", &Context::new().with("amount", 10)),
+        true
+    );
+    assert_eq!(grants.check(&alice, "post:edit"), false);
+}
+
+#[test]
+fn grant_when_time_window_test() {
+    let mut grants = test_grant_store();
+    let alice = String::from("alice");
+    let now = SystemTime::now();
+    let hour = Duration::from_secs(3600);
+    grants.grant_when(
+        alice.clone(),
+        &grants
+            .manager()
+            .perm_from_json(r#"{"post": {"edit": true}}"#),
+        ConditionSpec::TimeWindow {
+            from: Some(now + hour),
+            until: Some(now + 2 * hour),
+        },
+    );
+
+    // Time windows are checked against the time of the check
+    assert_eq!(grants.check(&alice, "post:edit"), false);
+    assert_eq!(grants.check_at(&alice, "post:edit", now + hour), true);
+    assert_eq!(
+        grants
+            .effective_perm_at(&alice, now + 2 * hour)
+            .contains_action("post:edit"),
+        false
+    );
+
+    assert_eq!(grants.next_transition(now), Some(now + hour));
+    assert_eq!(grants.next_transition(now + hour), Some(now + 2 * hour));
+    assert_eq!(grants.next_transition(now + 2 * hour), None);
+}
+
+#[test]
+fn json_test() {
+    let manager = Arc::new(PermissionManager::from_json(
+        r#"{"post": {"view": true, "edit": true, "delete": true}}"#,
+    ));
+    let mut grants: GrantStore<String> = GrantStore::new(manager.clone());
+    let alice = String::from("alice");
+    let backend = String::from("backend");
+    let valid_until = std::time::UNIX_EPOCH + Duration::from_millis(4_000_000_000_000);
+    grants.set_max_depth(4);
+    grants.grant(
+        backend.clone(),
+        &manager.perm_from_json(r#"{"post": {"view": true}}"#),
+    );
+    grants.grant_expiring(
+        alice.clone(),
+        ExpiringPermission::until(
+            manager.perm_from_json(r#"{"post": {"edit": true}}"#),
+            valid_until,
+        ),
+    );
+    grants.grant_when(
+        alice.clone(),
+        &manager.perm_from_json(r#"{"post": {"delete": true}}"#),
+        ConditionSpec::attribute("internal", "==", true),
+    );
+    grants.add_member(alice.clone(), backend.clone());
+
+    let json = grants.to_json();
+    let restored: GrantStore<String> = GrantStore::from_json(manager.clone(), &json);
+
+    assert_eq!(restored.to_json(), json);
+    assert_eq!(restored.max_depth(), 4);
+    assert_eq!(restored.groups(&alice), HashSet::from([&backend]));
+    assert_eq!(
+        restored.next_transition(std::time::UNIX_EPOCH),
+        Some(valid_until)
+    );
+    assert_eq!(
+        *restored
+            .effective_perm_with(&alice, &Context::new().with("internal", true))
+            .get_actions(),
+        HashSet::from([
+            String::from("post:view"),
+            String::from("post:edit"),
+            String::from("post:delete"),
+        ])
+    );
+
+    let empty: GrantStore<String> = GrantStore::from_json(manager.clone(), "{}");
+    assert_eq!(empty.subjects().count(), 0);
+
+    grants.grant_if(alice, &manager.perm_from_json("{}"), |_: &Context| true);
+    match std::panic::catch_unwind(move || {
+        grants.to_json();
+    }) {
+        Ok(_) => panic!("serializing a closure condition should have panicked"),
+        Err(_) => (),
+    }
+
+    for json in [
+        "[]",
+        r#"{"grants": []}"#,
+        r#"{"grants": {"alice": {"post": {"share": true}}}}"#,
+        r#"{"timed_grants": {"alice": [{"valid_until": 1}]}}"#,
+        r#"{"conditional_grants": {"alice": [{"actions": {}}]}}"#,
+        r#"{"memberships": {"alice": ["alice"]}}"#,
+    ] {
+        let manager = manager.clone();
+        match std::panic::catch_unwind(move || {
+            GrantStore::<String>::from_json(manager, json);
+        }) {
+            Ok(_) => panic!("parsing {} should have panicked", json),
+            Err(_) => (),
+        }
+    }
+}
+
+#[test]
+fn snapshot_test() {
+    let mut grants = test_grant_store();
+    let view = grants
+        .manager()
+        .perm_from_json(r#"{"post": {"view": true}}"#);
+    let edit = grants
+        .manager()
+        .perm_from_json(r#"{"post": {"edit": true}}"#);
+    grants.grant(String::from("alice"), &view);
+    grants.grant_expiring(
+        String::from("bob"),
+        ExpiringPermission::new(
+            edit.clone(),
+            None,
+            Some(SystemTime::now() + Duration::from_secs(3600)),
+        ),
+    );
+    grants.grant_if(String::from("carol"), &edit, |_: &Context| true);
+    grants.add_member(String::from("dave"), String::from("alice"));
+
+    let before = SystemTime::now();
+    let snapshot = grants.snapshot();
+    assert_eq!(snapshot.taken_at() >= before, true);
+    assert_eq!(snapshot.manager_id(), grants.manager().id());
+
+    grants.revoke_all(&String::from("alice"));
+    grants.revoke_all(&String::from("bob"));
+    grants.revoke_all(&String::from("carol"));
+    grants.remove_member(&String::from("dave"), &String::from("alice"));
+    grants.grant(String::from("eve"), &edit);
+
+    grants.restore(&snapshot);
+    assert_eq!(grants.check(&String::from("alice"), "post:view"), true);
+    assert_eq!(grants.check(&String::from("bob"), "post:edit"), true);
+    assert_eq!(grants.check(&String::from("carol"), "post:edit"), true);
+    assert_eq!(grants.check(&String::from("dave"), "post:view"), true);
+    assert_eq!(grants.check(&String::from("eve"), "post:edit"), false);
+
+    let mut other = test_grant_store();
+    let result =
+        std::panic::catch_unwind(std::panic::AssertUnwindSafe(|| other.restore(&snapshot)));
+    assert_eq!(result.is_err(), true);
+}
+
+#[test]
+fn restore_hooks_test() {
+    let mut grants = test_grant_store();
+    let view = grants
+        .manager()
+        .perm_from_json(r#"{"post": {"view": true}}"#);
+    let edit = grants
+        .manager()
+        .perm_from_json(r#"{"post": {"edit": true}}"#);
+    grants.grant(String::from("alice"), &view);
+    grants.grant_if(String::from("bob"), &edit, |_: &Context| true);
+    let snapshot = grants.snapshot();
+
+    grants.grant(String::from("alice"), &edit);
+    grants.revoke_all(&String::from("bob"));
+    grants.grant(String::from("carol"), &view);
+
+    let events = std::sync::Arc::new(std::sync::Mutex::new(Vec::new()));
+    let grant_events = events.clone();
+    let revoke_events = events.clone();
+    grants.on_grant(move |subject: &String, perm| {
+        grant_events
+            .lock()
+            .unwrap()
+            .push((format!("grant {}", subject), perm.to_json()))
+    });
+    grants.on_revoke(move |subject: &String, perm| {
+        revoke_events
+            .lock()
+            .unwrap()
+            .push((format!("revoke {}", subject), perm.to_json()))
+    });
+
+    grants.restore(&snapshot);
+    let mut recorded = events.lock().unwrap().clone();
+    recorded.sort();
+    assert_eq!(
+        recorded,
+        vec![
+            (
+                String::from("grant bob"),
+                String::from(r#"{"post":{"edit":true}}"#)
+            ),
+            (
+                String::from("revoke alice"),
+                String::from(r#"{"post":{"edit":true}}"#)
+            ),
+            (
+                String::from("revoke carol"),
+                String::from(r#"{"post":{"view":true}}"#)
+            ),
+        ]
+    );
+
+    // Restoring the same grants calls no callbacks
+    grants.restore(&snapshot);
+    assert_eq!(events.lock().unwrap().len(), 3);
+}
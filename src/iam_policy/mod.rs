@@ -0,0 +1,228 @@
+use crate::glob::wildcard_matches;
+use crate::permission::Permission;
+use crate::permission_manager::PermissionManager;
+use crate::telemetry;
+use crate::validation_error::ValidationError;
+use serde_json::Value;
+use std::collections::HashSet;
+
+//// TESTS ////
+#[cfg(test)]
+mod tests;
+
+/// Actions allowed and denied by an [AWS IAM](https://docs.aws.amazon.com/IAM/latest/UserGuide/reference_policies_elements.html)
+/// style policy document, to bootstrap universes and permissions for teams mirroring their cloud permission model.
+///
+/// The import is best-effort: only the `Effect` and `Action` of each statement are read. Permissions can not be scoped to
+/// resources or conditions, so `Allow` statements with a `Condition`, a `NotResource` or a `Resource` other than `"*"` are
+/// skipped instead of being widened to unconditional grants, and listed by [`skipped`](Self::skipped()). `Deny` statements are
+/// always kept, denying their actions unconditionally. Actions may have `*` and `?` wildcards, which match across `:` as in IAM.
+///
+/// # Examples:
+///
+/// ```
+/// use simple_perm_manager::{IamPolicy, PermissionManager};
+///
+/// let policy = IamPolicy::from_json(r#"{
+///     "Version": "2012-10-17",
+///     "Statement": [
+///         {"Effect": "Allow", "Action": ["s3:Get*", "s3:ListBucket"], "Resource": "*"},
+///         {"Effect": "Deny", "Action": "s3:GetObjectAcl", "Resource": "*"}
+///     ]
+/// }"#).unwrap();
+///
+/// let manager = PermissionManager::from_json(r#"{
+///     "s3": {"GetObject": true, "GetObjectAcl": true, "ListBucket": true, "PutObject": true}
+/// }"#);
+/// let (allowed, denied) = manager.perms_from_iam_policy(&policy);
+/// let perm = allowed.difference(&denied);
+///
+/// assert!(perm.contains_action("s3:GetObject"));
+/// assert!(perm.contains_action("s3:ListBucket"));
+/// assert!(!perm.contains_action("s3:GetObjectAcl"));
+/// assert!(!perm.contains_action("s3:PutObject"));
+/// ```
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct IamPolicy {
+    // Action patterns of the statements with an `Allow` effect
+    allowed: HashSet<String>,
+    // Action patterns of the statements with a `Deny` effect
+    denied: HashSet<String>,
+    // Positions of the `Allow` statements scoped to resources or conditions
+    skipped: Vec<usize>,
+}
+
+// Returns `true` if the statement only applies to some resources or under some conditions
+fn is_scoped(statement: &Value) -> bool {
+    let all_resources = match statement.get("Resource") {
+        None => true,
+        Some(Value::String(resource)) => resource == "*",
+        Some(Value::Array(resources)) => resources.iter().any(|resource| resource == "*"),
+        Some(_) => false,
+    };
+
+    !all_resources || statement.get("NotResource").is_some() || statement.get("Condition").is_some()
+}
+
+impl IamPolicy {
+    /// Returns the [`IamPolicy`](crate::IamPolicy) of an IAM policy JSON document, whose `Statement` is a statement or an array of them.
+    ///
+    /// # Errors:
+    ///
+    /// Returns [`ValidationError::InvalidJson`](crate::ValidationError::InvalidJson) if `policy_json` is not valid JSON, or a
+    /// statement does not have an `Allow` or `Deny` effect and a string or array of strings as `Action`. Statements with
+    /// `NotAction` are rejected too, as the actions they grant or deny depend on services outside the policy.
+    pub fn from_json(policy_json: &str) -> Result<IamPolicy, ValidationError> {
+        let invalid = |message: &str| {
+            telemetry::record_parse_error();
+            ValidationError::InvalidJson(format!("wrong format in iam policy - {}", message))
+        };
+        let policy: Value = serde_json::from_str(policy_json).map_err(|error| {
+            telemetry::record_parse_error();
+            ValidationError::InvalidJson(error.to_string())
+        })?;
+
+        let statements = match policy.get("Statement") {
+            Some(Value::Array(statements)) => statements.iter().collect(),
+            Some(statement @ Value::Object(_)) => vec![statement],
+            _ => return Err(invalid("expected a statement or an array of statements")),
+        };
+
+        let mut iam_policy = IamPolicy::default();
+        for (number, statement) in statements.into_iter().enumerate() {
+            let invalid = |message: &str| invalid(&format!("statement {}: {}", number, message));
+            if statement.get("NotAction").is_some() {
+                return Err(invalid("NotAction is not supported"));
+            }
+            let actions: Vec<&str> = match statement.get("Action") {
+                Some(Value::String(action)) => vec![action],
+                Some(Value::Array(actions)) => actions
+                    .iter()
+                    .map(|action| {
+                        action
+                            .as_str()
+                            .ok_or_else(|| invalid("actions must be strings"))
+                    })
+                    .collect::<Result<Vec<&str>, ValidationError>>()?,
+                _ => {
+                    return Err(invalid(
+                        "expected a string or an array of strings as Action",
+                    ))
+                }
+            };
+            let target = match statement.get("Effect").and_then(Value::as_str) {
+                Some("Allow") if is_scoped(statement) => {
+                    iam_policy.skipped.push(number);
+                    continue;
+                }
+                Some("Allow") => &mut iam_policy.allowed,
+                Some("Deny") => &mut iam_policy.denied,
+                _ => return Err(invalid("expected 'Allow' or 'Deny' as Effect")),
+            };
+            target.extend(actions.into_iter().map(String::from));
+        }
+
+        Ok(iam_policy)
+    }
+
+    /// Returns the action patterns of the statements with an `Allow` effect.
+    pub fn allowed(&self) -> &HashSet<String> {
+        &self.allowed
+    }
+
+    /// Returns the action patterns of the statements with a `Deny` effect.
+    pub fn denied(&self) -> &HashSet<String> {
+        &self.denied
+    }
+
+    /// Returns the positions in the `Statement` array of the `Allow` statements that were skipped because they are scoped to
+    /// resources or conditions, in order.
+    ///
+    /// # Examples:
+    ///
+    /// ```
+    /// use simple_perm_manager::IamPolicy;
+    ///
+    /// let policy = IamPolicy::from_json(r#"{"Statement": [
+    ///     {"Effect": "Allow", "Action": "s3:GetObject", "Resource": "arn:aws:s3:::reports/*"},
+    ///     {"Effect": "Allow", "Action": "s3:ListBucket", "Resource": "*"}
+    /// ]}"#).unwrap();
+    ///
+    /// assert_eq!(policy.skipped(), [0]);
+    /// assert!(policy.allowed().contains("s3:ListBucket"));
+    /// assert!(!policy.allowed().contains("s3:GetObject"));
+    /// ```
+    pub fn skipped(&self) -> &[usize] {
+        &self.skipped
+    }
+
+    /// Returns the actions without wildcards of every statement, allowed or denied, e.g. to bootstrap a universe with
+    /// [`PermissionManager::try_from_actions`](crate::PermissionManager::try_from_actions()).
+    ///
+    /// # Examples:
+    ///
+    /// ```
+    /// use simple_perm_manager::{IamPolicy, PermissionManager};
+    ///
+    /// let policy = IamPolicy::from_json(r#"{"Statement": [
+    ///     {"Effect": "Allow", "Action": ["s3:GetObject", "s3:List*"]},
+    ///     {"Effect": "Deny", "Action": "s3:DeleteBucket"}
+    /// ]}"#).unwrap();
+    ///
+    /// let manager = PermissionManager::try_from_actions(policy.actions()).unwrap();
+    /// assert_eq!(manager.to_actions().len(), 2);
+    /// ```
+    pub fn actions(&self) -> HashSet<String> {
+        self.allowed
+            .iter()
+            .chain(self.denied.iter())
+            .filter(|action| !action.contains(['*', '?']))
+            .cloned()
+            .collect()
+    }
+}
+
+impl PermissionManager {
+    /// Returns the managed [`Permission`](crate::Permission)s with the universe actions allowed and denied by an
+    /// [`IamPolicy`](crate::IamPolicy), as `(allowed, denied)`. Denials take precedence in IAM, so the actions granted by the
+    /// policy are `allowed.difference(&denied)`.
+    ///
+    /// Action patterns are matched case-insensitively against the universe, as IAM does, and patterns matching no universe
+    /// action are ignored.
+    ///
+    /// # Examples:
+    ///
+    /// ```
+    /// use simple_perm_manager::{IamPolicy, PermissionManager};
+    ///
+    /// let policy = IamPolicy::from_json(r#"{"Statement": {"Effect": "Allow", "Action": "ec2:describe*"}}"#).unwrap();
+    /// let manager = PermissionManager::from_json(r#"{"ec2": {"DescribeInstances": true, "StartInstances": true}}"#);
+    ///
+    /// let (allowed, denied) = manager.perms_from_iam_policy(&policy);
+    /// assert!(allowed.contains_action("ec2:DescribeInstances"));
+    /// assert!(!allowed.contains_action("ec2:StartInstances"));
+    /// assert!(denied.get_actions().is_empty());
+    /// ```
+    pub fn perms_from_iam_policy(&self, policy: &IamPolicy) -> (Permission, Permission) {
+        let matching = |patterns: &HashSet<String>| -> HashSet<String> {
+            let patterns: Vec<String> = patterns
+                .iter()
+                .map(|pattern| pattern.to_lowercase())
+                .collect();
+            self.to_actions()
+                .into_iter()
+                .filter(|action| {
+                    let action = action.to_lowercase();
+                    patterns
+                        .iter()
+                        .any(|pattern| wildcard_matches(pattern, &action))
+                })
+                .collect()
+        };
+
+        (
+            self.perm_from_actions(matching(&policy.allowed)),
+            self.perm_from_actions(matching(&policy.denied)),
+        )
+    }
+}
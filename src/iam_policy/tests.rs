@@ -0,0 +1,171 @@
+use super::*;
+
+const POLICY: &str = r#"{
+    "Version": "2012-10-17",
+    "Statement": [
+        {"Sid": "Read", "Effect": "Allow", "Action": ["s3:Get*", "s3:ListBucket"], "Resource": "*"},
+        {"Effect": "Allow", "Action": "ec2:DescribeInstances"},
+        {"Effect": "Deny", "Action": ["s3:GetObjectAcl"], "Condition": {"Bool": {"aws:SecureTransport": "false"}}}
+    ]
+}"#;
+
+fn manager() -> PermissionManager {
+    PermissionManager::from_json(
+        r#"{
+            "s3": {"GetObject": true, "GetObjectAcl": true, "ListBucket": true, "PutObject": true},
+            "ec2": {"DescribeInstances": true, "StartInstances": true}
+        }"#,
+    )
+}
+
+#[test]
+fn from_json_test() {
+    let policy = IamPolicy::from_json(POLICY).unwrap();
+
+    assert_eq!(
+        *policy.allowed(),
+        HashSet::from([
+            String::from("s3:Get*"),
+            String::from("s3:ListBucket"),
+            String::from("ec2:DescribeInstances"),
+        ])
+    );
+    assert_eq!(
+        *policy.denied(),
+        HashSet::from([String::from("s3:GetObjectAcl")])
+    );
+
+    // A single statement object is also accepted
+    let policy =
+        IamPolicy::from_json(r#"{"Statement": {"Effect": "Deny", "Action": "*"}}"#).unwrap();
+    assert_eq!(policy.allowed().is_empty(), true);
+    assert_eq!(*policy.denied(), HashSet::from([String::from("*")]));
+}
+
+#[test]
+fn from_json_errors_test() {
+    for policy_json in [
+        "{",
+        r#"{"Version": "2012-10-17"}"#,
+        r#"{"Statement": "s3:GetObject"}"#,
+        r#"{"Statement": [{"Effect": "Allow"}]}"#,
+        r#"{"Statement": [{"Effect": "Allow", "Action": 3}]}"#,
+        r#"{"Statement": [{"Effect": "Allow", "Action": ["s3:GetObject", 3]}]}"#,
+        r#"{"Statement": [{"Effect": "allow", "Action": "s3:GetObject"}]}"#,
+        r#"{"Statement": [{"Action": "s3:GetObject"}]}"#,
+        r#"{"Statement": [{"Effect": "Deny", "NotAction": "s3:GetObject"}]}"#,
+    ] {
+        match IamPolicy::from_json(policy_json) {
+            Err(ValidationError::InvalidJson(_)) => (),
+            _ => panic!("policy {} should have failed", policy_json),
+        }
+    }
+
+    match IamPolicy::from_json(r#"{"Statement": [{"Effect": "Allow", "Action": "a"}, {"Effect": "Allow"}]}"#) {
+        Err(ValidationError::InvalidJson(message)) => assert_eq!(
+            message,
+            "wrong format in iam policy - statement 1: expected a string or an array of strings as Action"
+        ),
+        _ => panic!("statement without actions should have failed"),
+    }
+}
+
+#[test]
+fn scoped_statements_test() {
+    let policy = IamPolicy::from_json(
+        r#"{"Statement": [
+            {"Effect": "Allow", "Action": "s3:GetObject", "Resource": "arn:aws:s3:::reports/*"},
+            {"Effect": "Allow", "Action": "s3:ListBucket", "Resource": ["arn:aws:s3:::reports", "*"]},
+            {"Effect": "Allow", "Action": "s3:PutObject", "Resource": "*", "Condition": {"Bool": {"aws:MultiFactorAuthPresent": "true"}}},
+            {"Effect": "Allow", "Action": "ec2:StartInstances", "NotResource": "arn:aws:ec2:*:*:instance/prod-*"},
+            {"Effect": "Deny", "Action": "s3:GetObjectAcl", "Resource": "arn:aws:s3:::reports/*"}
+        ]}"#,
+    )
+    .unwrap();
+
+    // Scoped allows are skipped instead of granted everywhere, scoped denies are kept
+    assert_eq!(policy.skipped(), [0, 2, 3]);
+    assert_eq!(
+        *policy.allowed(),
+        HashSet::from([String::from("s3:ListBucket")])
+    );
+    assert_eq!(
+        *policy.denied(),
+        HashSet::from([String::from("s3:GetObjectAcl")])
+    );
+    assert_eq!(
+        IamPolicy::from_json(POLICY).unwrap().skipped().is_empty(),
+        true
+    );
+}
+
+#[test]
+fn actions_test() {
+    let policy = IamPolicy::from_json(POLICY).unwrap();
+
+    assert_eq!(
+        policy.actions(),
+        HashSet::from([
+            String::from("s3:ListBucket"),
+            String::from("ec2:DescribeInstances"),
+            String::from("s3:GetObjectAcl"),
+        ])
+    );
+    assert_eq!(IamPolicy::default().actions().is_empty(), true);
+}
+
+#[test]
+fn perms_from_iam_policy_test() {
+    let manager = manager();
+    let (allowed, denied) = manager.perms_from_iam_policy(&IamPolicy::from_json(POLICY).unwrap());
+
+    assert_eq!(manager.validate_perm(&allowed), true);
+    assert_eq!(manager.validate_perm(&denied), true);
+    assert_eq!(
+        *allowed.get_actions(),
+        HashSet::from([
+            String::from("s3:GetObject"),
+            String::from("s3:GetObjectAcl"),
+            String::from("s3:ListBucket"),
+            String::from("ec2:DescribeInstances"),
+        ])
+    );
+    assert_eq!(
+        *denied.get_actions(),
+        HashSet::from([String::from("s3:GetObjectAcl")])
+    );
+    assert_eq!(
+        *allowed.difference(&denied).get_actions(),
+        HashSet::from([
+            String::from("s3:GetObject"),
+            String::from("s3:ListBucket"),
+            String::from("ec2:DescribeInstances"),
+        ])
+    );
+}
+
+#[test]
+fn perms_from_iam_policy_wildcards_test() {
+    let manager = manager();
+
+    // Wildcards match across groups and patterns are case-insensitive
+    let policy = IamPolicy::from_json(
+        r#"{"Statement": [
+            {"Effect": "Allow", "Action": ["*Instances", "S3:?utobject", "iam:PassRole"]},
+            {"Effect": "Deny", "Action": "*"}
+        ]}"#,
+    )
+    .unwrap();
+    let (allowed, denied) = manager.perms_from_iam_policy(&policy);
+
+    assert_eq!(
+        *allowed.get_actions(),
+        HashSet::from([
+            String::from("ec2:DescribeInstances"),
+            String::from("ec2:StartInstances"),
+            String::from("s3:PutObject"),
+        ])
+    );
+    assert_eq!(*denied.get_actions(), manager.to_actions());
+    assert_eq!(allowed.difference(&denied).get_actions().is_empty(), true);
+}
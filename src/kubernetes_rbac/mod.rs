@@ -0,0 +1,356 @@
+use crate::glob::glob_matches;
+use crate::permission::{Permission, ACTION_DIVIDER};
+use crate::permission_manager::PermissionManager;
+use crate::telemetry;
+use crate::validation_error::ValidationError;
+use serde::Deserialize;
+use serde_json::{json, Map, Value};
+use std::collections::{BTreeMap, BTreeSet, HashMap, HashSet};
+
+//// TESTS ////
+#[cfg(test)]
+mod tests;
+
+// API version of the RBAC objects written
+const RBAC_API_VERSION: &str = "rbac.authorization.k8s.io/v1";
+// Action group standing for the core API group, whose name is the empty string
+const CORE_GROUP: &str = "core";
+// Wildcard for every API group, resource or verb
+const WILDCARD: &str = "*";
+
+// Returns the invalid JSON error for a Kubernetes RBAC document
+fn invalid(message: &str) -> ValidationError {
+    telemetry::record_parse_error();
+    ValidationError::InvalidJson(format!("wrong format in kubernetes rbac - {}", message))
+}
+
+// Returns the strings of a rule field, which must be an array of strings
+fn strings<'a>(rule: &'a Value, field: &str) -> Result<Vec<&'a str>, ValidationError> {
+    let Some(Value::Array(values)) = rule.get(field) else {
+        return Err(invalid(&format!(
+            "expected an array of strings as {}",
+            field
+        )));
+    };
+
+    values
+        .iter()
+        .map(|value| {
+            value
+                .as_str()
+                .ok_or_else(|| invalid(&format!("expected an array of strings as {}", field)))
+        })
+        .collect()
+}
+
+// Action patterns of the rules of a Role or ClusterRole
+#[derive(Debug, Default, PartialEq)]
+struct Role {
+    // Patterns of the rules that apply to every object of their resources
+    actions: HashSet<String>,
+    // Patterns of the rules restricted to some objects with `resourceNames`
+    scoped: HashSet<String>,
+}
+
+// Returns the key of a role, `ClusterRole/<name>` or `Role/<namespace>/<name>`, so roles with the same name in different
+// namespaces or of different kinds are kept apart
+fn role_key(kind: &str, namespace: Option<&str>, name: &str) -> String {
+    match kind {
+        "ClusterRole" => format!("{}/{}", kind, name),
+        _ => format!("{}/{}/{}", kind, namespace.unwrap_or_default(), name),
+    }
+}
+
+// Adds the action patterns of the rules of every Role and ClusterRole in a document, by role key.
+// Other kinds are skipped and lists are read item by item
+fn collect_roles(
+    document: &Value,
+    separator: char,
+    roles: &mut BTreeMap<String, Role>,
+) -> Result<(), ValidationError> {
+    let kind = document
+        .get("kind")
+        .and_then(Value::as_str)
+        .unwrap_or_default();
+    if kind.ends_with("List") {
+        for item in document
+            .get("items")
+            .and_then(Value::as_array)
+            .into_iter()
+            .flatten()
+        {
+            collect_roles(item, separator, roles)?;
+        }
+        return Ok(());
+    }
+    if kind != "Role" && kind != "ClusterRole" {
+        return Ok(());
+    }
+
+    let Some(name) = document.pointer("/metadata/name").and_then(Value::as_str) else {
+        return Err(invalid(&format!("{} without metadata name", kind)));
+    };
+    let namespace = document
+        .pointer("/metadata/namespace")
+        .and_then(Value::as_str);
+    let role = roles.entry(role_key(kind, namespace, name)).or_default();
+    for rule in document
+        .get("rules")
+        .and_then(Value::as_array)
+        .into_iter()
+        .flatten()
+    {
+        // Non resource URLs, such as '/healthz', do not map to actions
+        if rule.get("resources").is_none() && rule.get("nonResourceURLs").is_some() {
+            continue;
+        }
+
+        let resources = strings(rule, "resources")?;
+        let verbs = strings(rule, "verbs")?;
+        let actions = match rule.get("resourceNames") {
+            Some(Value::Array(names)) if !names.is_empty() => &mut role.scoped,
+            _ => &mut role.actions,
+        };
+        for group in strings(rule, "apiGroups")? {
+            let group = if group.is_empty() { CORE_GROUP } else { group };
+            for resource in &resources {
+                for verb in &verbs {
+                    actions.insert(format!(
+                        "{}{}{}{}{}",
+                        group, separator, resource, separator, verb
+                    ));
+                }
+            }
+        }
+    }
+
+    Ok(())
+}
+
+// Returns the action patterns of every Role and ClusterRole of Kubernetes RBAC YAML, by role key
+fn parse_roles(yaml: &str, separator: char) -> Result<BTreeMap<String, Role>, ValidationError> {
+    let mut roles: BTreeMap<String, Role> = BTreeMap::new();
+
+    for document in serde_yaml::Deserializer::from_str(yaml) {
+        let document = Value::deserialize(document).map_err(|error| {
+            telemetry::record_parse_error();
+            ValidationError::InvalidJson(error.to_string())
+        })?;
+        collect_roles(&document, separator, &mut roles)?;
+    }
+
+    Ok(roles)
+}
+
+// Returns `true` if an action pattern has a wildcard API group, resource or verb
+fn is_wildcard(action: &str, separator: char) -> bool {
+    action.split(separator).any(|part| part == WILDCARD)
+}
+
+impl Permission {
+    /// Returns the [`Permission`](crate::Permission) as a Kubernetes RBAC YAML `Role` in `namespace`, or a `ClusterRole` if
+    /// `namespace` is `None`.  
+    /// Each action must have the `apiGroup:resource:verb` form, where the `core` group stands for the core API group (`""`).
+    /// Resources of the same API group with the same verbs share a rule.
+    ///
+    /// # Examples:
+    ///
+    /// ```
+    /// use simple_perm_manager::perm;
+    ///
+    /// let perm = perm!["core:pods:get", "core:pods:list", "apps:deployments:get"];
+    ///
+    /// assert_eq!(perm.to_kubernetes_role("viewer", Some("default")).unwrap(), "\
+    /// apiVersion: rbac.authorization.k8s.io/v1
+    /// kind: Role
+    /// metadata:
+    ///   name: viewer
+    ///   namespace: default
+    /// rules:
+    /// - apiGroups:
+    ///   - ''
+    ///   resources:
+    ///   - pods
+    ///   verbs:
+    ///   - get
+    ///   - list
+    /// - apiGroups:
+    ///   - apps
+    ///   resources:
+    ///   - deployments
+    ///   verbs:
+    ///   - get
+    /// ");
+    /// ```
+    ///
+    /// # Errors:
+    ///
+    /// Returns [`ValidationError::UntranslatableActions`](crate::ValidationError::UntranslatableActions) with the sorted actions
+    /// that do not have the `apiGroup:resource:verb` form.
+    pub fn to_kubernetes_role(
+        &self,
+        name: &str,
+        namespace: Option<&str>,
+    ) -> Result<String, ValidationError> {
+        let mut verbs: BTreeMap<(&str, &str), BTreeSet<&str>> = BTreeMap::new();
+        let mut untranslatable: Vec<String> = Vec::new();
+        for action in self.actions() {
+            match action.split(self.separator()).collect::<Vec<&str>>()[..] {
+                [group, resource, verb] => {
+                    let group = if group == CORE_GROUP { "" } else { group };
+                    verbs.entry((group, resource)).or_default().insert(verb);
+                }
+                _ => untranslatable.push(action.to_string()),
+            }
+        }
+        if !untranslatable.is_empty() {
+            untranslatable.sort();
+            return Err(ValidationError::UntranslatableActions(untranslatable));
+        }
+
+        let mut resources: BTreeMap<(&str, Vec<&str>), Vec<&str>> = BTreeMap::new();
+        for ((group, resource), verbs) in verbs {
+            resources
+                .entry((group, verbs.into_iter().collect()))
+                .or_default()
+                .push(resource);
+        }
+        let rules: Vec<Value> = resources
+            .into_iter()
+            .map(|((group, verbs), resources)| {
+                json!({"apiGroups": [group], "resources": resources, "verbs": verbs})
+            })
+            .collect();
+
+        let mut metadata = Map::new();
+        metadata.insert(String::from("name"), json!(name));
+        if let Some(namespace) = namespace {
+            metadata.insert(String::from("namespace"), json!(namespace));
+        }
+        let role = json!({
+            "apiVersion": RBAC_API_VERSION,
+            "kind": if namespace.is_some() { "Role" } else { "ClusterRole" },
+            "metadata": metadata,
+            "rules": rules,
+        });
+
+        Ok(serde_yaml::to_string(&role)
+            .expect("unexpected error, a JSON value should always serialize to YAML"))
+    }
+}
+
+impl PermissionManager {
+    /// Returns a new [`PermissionManager`](crate::PermissionManager) whose universe is every `apiGroup:resource:verb` of the
+    /// `Role` and `ClusterRole` objects in Kubernetes RBAC YAML, which may have several documents and `List` objects.  
+    /// The core API group (`""`) becomes the `core` group. Other kinds, rules with `*` wildcards and non resource URL rules
+    /// are skipped. Rules restricted with `resourceNames` add their actions to the universe too.
+    ///
+    /// # Examples:
+    ///
+    /// ```
+    /// use simple_perm_manager::PermissionManager;
+    ///
+    /// let manager = PermissionManager::from_kubernetes_roles(r#"
+    /// apiVersion: rbac.authorization.k8s.io/v1
+    /// kind: ClusterRole
+    /// metadata:
+    ///   name: pod-reader
+    /// rules:
+    /// - apiGroups: [""]
+    ///   resources: ["pods", "pods/log"]
+    ///   verbs: ["get", "list"]
+    /// "#).unwrap();
+    ///
+    /// assert_eq!(manager.to_actions().len(), 4);
+    /// assert!(manager.to_actions().contains("core:pods/log:get"));
+    /// ```
+    ///
+    /// # Errors:
+    ///
+    /// - Returns [`ValidationError::InvalidJson`](crate::ValidationError::InvalidJson) if `yaml` is not valid YAML, a role has no
+    ///   name or a rule does not have arrays of strings as `apiGroups`, `resources` and `verbs`.
+    /// - Returns [`ValidationError::MalformedActions`](crate::ValidationError::MalformedActions) if a resource or verb is empty.
+    pub fn from_kubernetes_roles(yaml: &str) -> Result<PermissionManager, ValidationError> {
+        let actions: HashSet<String> = parse_roles(yaml, ACTION_DIVIDER)?
+            .into_values()
+            .flat_map(|role| role.actions.into_iter().chain(role.scoped))
+            .filter(|action| !is_wildcard(action, ACTION_DIVIDER))
+            .collect();
+
+        PermissionManager::try_from_actions(actions)
+    }
+
+    /// Returns a managed [`Permission`](crate::Permission) for every `Role` and `ClusterRole` in Kubernetes RBAC YAML,
+    /// holding the `apiGroup:resource:verb` of its rules. Rules with `*` wildcards grant every universe action they match.  
+    /// Permissions are keyed by `ClusterRole/<name>` or `Role/<namespace>/<name>` (`Role//<name>` if the role has no namespace),
+    /// so roles with the same name in different namespaces are kept apart.
+    ///
+    /// # Examples:
+    ///
+    /// ```
+    /// use simple_perm_manager::PermissionManager;
+    ///
+    /// let manager = PermissionManager::from_json(r#"{
+    ///     "core": {"pods": {"get": true, "list": true, "delete": true}},
+    ///     "apps": {"deployments": {"get": true}}
+    /// }"#);
+    ///
+    /// let perms = manager.perms_from_kubernetes_roles(r#"
+    /// kind: Role
+    /// metadata: {name: pod-admin, namespace: default}
+    /// rules:
+    /// - {apiGroups: [""], resources: ["pods"], verbs: ["*"]}
+    /// "#).unwrap();
+    ///
+    /// assert_eq!(perms["Role/default/pod-admin"].get_actions().len(), 3);
+    /// ```
+    ///
+    /// # Errors:
+    ///
+    /// - Returns [`ValidationError::InvalidJson`](crate::ValidationError::InvalidJson) if `yaml` is not valid YAML, a role has no
+    ///   name or a rule does not have arrays of strings as `apiGroups`, `resources` and `verbs`.
+    /// - Returns [`ValidationError::UnknownActions`](crate::ValidationError::UnknownActions) if a rule without wildcards has an
+    ///   action that is not in the universe.
+    /// - Returns [`ValidationError::UntranslatableActions`](crate::ValidationError::UntranslatableActions) with the sorted actions
+    ///   of the rules restricted with `resourceNames`, as a [`Permission`](crate::Permission) can not hold them without granting
+    ///   every object of the resource.
+    pub fn perms_from_kubernetes_roles(
+        &self,
+        yaml: &str,
+    ) -> Result<HashMap<String, Permission>, ValidationError> {
+        let separator = self.separator();
+        let universe = self.to_actions();
+        let roles = parse_roles(yaml, separator)?;
+
+        let scoped: BTreeSet<String> = roles
+            .values()
+            .flat_map(|role| role.scoped.iter().cloned())
+            .collect();
+        if !scoped.is_empty() {
+            return Err(ValidationError::UntranslatableActions(
+                scoped.into_iter().collect(),
+            ));
+        }
+
+        roles
+            .into_iter()
+            .map(|(key, role)| {
+                let mut actions: HashSet<String> = HashSet::new();
+                for pattern in role.actions {
+                    if is_wildcard(&pattern, separator) {
+                        actions.extend(
+                            universe
+                                .iter()
+                                .filter(|action| glob_matches(&pattern, action, separator))
+                                .cloned(),
+                        );
+                    } else {
+                        actions.insert(pattern);
+                    }
+                }
+
+                Ok((key, self.try_perm_from_actions(actions)?))
+            })
+            .collect()
+    }
+}
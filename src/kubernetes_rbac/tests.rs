@@ -0,0 +1,277 @@
+use super::*;
+
+const ROLES: &str = r#"
+apiVersion: rbac.authorization.k8s.io/v1
+kind: Role
+metadata:
+  name: pod-reader
+  namespace: default
+rules:
+- apiGroups: [""]
+  resources: ["pods", "pods/log"]
+  verbs: ["get", "list"]
+---
+apiVersion: rbac.authorization.k8s.io/v1
+kind: ClusterRole
+metadata:
+  name: deployer
+rules:
+- apiGroups: ["apps"]
+  resources: ["deployments"]
+  verbs: ["get", "update"]
+- nonResourceURLs: ["/healthz"]
+  verbs: ["get"]
+---
+apiVersion: rbac.authorization.k8s.io/v1
+kind: RoleBinding
+metadata:
+  name: read-pods
+subjects: []
+---
+apiVersion: v1
+kind: List
+items:
+- apiVersion: rbac.authorization.k8s.io/v1
+  kind: ClusterRole
+  metadata:
+    name: admin
+  rules:
+  - apiGroups: ["*"]
+    resources: ["*"]
+    verbs: ["*"]
+"#;
+
+#[test]
+fn parse_roles_test() {
+    let roles = parse_roles(ROLES, ':').unwrap();
+
+    assert_eq!(roles.len(), 3);
+    assert_eq!(
+        roles["Role/default/pod-reader"].actions,
+        HashSet::from([
+            String::from("core:pods:get"),
+            String::from("core:pods:list"),
+            String::from("core:pods/log:get"),
+            String::from("core:pods/log:list"),
+        ])
+    );
+    assert_eq!(
+        roles["ClusterRole/deployer"].actions,
+        HashSet::from([
+            String::from("apps:deployments:get"),
+            String::from("apps:deployments:update"),
+        ])
+    );
+    assert_eq!(
+        roles["ClusterRole/admin"].actions,
+        HashSet::from([String::from("*:*:*")])
+    );
+    assert_eq!(parse_roles("", ':').unwrap().is_empty(), true);
+}
+
+#[test]
+fn parse_roles_errors_test() {
+    for yaml in [
+        "kind: Role\nmetadata: {name: [",
+        "kind: Role\nmetadata: {}\nrules: []",
+        "kind: Role\nmetadata: {name: a}\nrules:\n- {resources: [pods], verbs: [get]}",
+        "kind: Role\nmetadata: {name: a}\nrules:\n- {apiGroups: [''], resources: pods, verbs: [get]}",
+        "kind: Role\nmetadata: {name: a}\nrules:\n- {apiGroups: [''], resources: [pods], verbs: [1]}",
+    ] {
+        match parse_roles(yaml, ':') {
+            Err(ValidationError::InvalidJson(_)) => (),
+            _ => panic!("yaml {} should have failed", yaml),
+        }
+    }
+}
+
+#[test]
+fn from_kubernetes_roles_test() {
+    let manager = PermissionManager::from_kubernetes_roles(ROLES).unwrap();
+
+    // Wildcard rules are not part of the universe
+    assert_eq!(
+        manager.to_actions(),
+        HashSet::from([
+            String::from("core:pods:get"),
+            String::from("core:pods:list"),
+            String::from("core:pods/log:get"),
+            String::from("core:pods/log:list"),
+            String::from("apps:deployments:get"),
+            String::from("apps:deployments:update"),
+        ])
+    );
+
+    match PermissionManager::from_kubernetes_roles(
+        "kind: Role\nmetadata: {name: a}\nrules:\n- {apiGroups: [''], resources: [''], verbs: [get]}",
+    ) {
+        Err(ValidationError::MalformedActions(_)) => (),
+        _ => panic!("empty resources should have failed"),
+    }
+}
+
+#[test]
+fn perms_from_kubernetes_roles_test() {
+    let manager = PermissionManager::from_kubernetes_roles(ROLES).unwrap();
+    let perms = manager.perms_from_kubernetes_roles(ROLES).unwrap();
+
+    assert_eq!(perms.len(), 3);
+    assert_eq!(manager.validate_perm(&perms["ClusterRole/deployer"]), true);
+    assert_eq!(perms["Role/default/pod-reader"].get_actions().len(), 4);
+    assert_eq!(
+        *perms["ClusterRole/admin"].get_actions(),
+        manager.to_actions()
+    );
+
+    // Wildcards in a single field only match that field
+    let perms = manager
+        .perms_from_kubernetes_roles(
+            "kind: ClusterRole\nmetadata: {name: getter}\nrules:\n- {apiGroups: ['*'], resources: ['*'], verbs: [get]}",
+        )
+        .unwrap();
+    assert_eq!(
+        *perms["ClusterRole/getter"].get_actions(),
+        HashSet::from([
+            String::from("core:pods:get"),
+            String::from("core:pods/log:get"),
+            String::from("apps:deployments:get"),
+        ])
+    );
+
+    match manager.perms_from_kubernetes_roles(
+        "kind: Role\nmetadata: {name: a}\nrules:\n- {apiGroups: [''], resources: [secrets], verbs: [get]}",
+    ) {
+        Err(ValidationError::UnknownActions(_)) => (),
+        _ => panic!("rules with actions not in the universe should have failed"),
+    }
+}
+
+#[test]
+fn role_keys_test() {
+    let yaml = r#"
+kind: Role
+metadata: {name: editor, namespace: dev}
+rules:
+- {apiGroups: [""], resources: ["pods"], verbs: ["get", "delete"]}
+---
+kind: Role
+metadata: {name: editor, namespace: prod}
+rules:
+- {apiGroups: [""], resources: ["pods"], verbs: ["get"]}
+---
+kind: ClusterRole
+metadata: {name: editor}
+rules:
+- {apiGroups: [""], resources: ["pods"], verbs: ["list"]}
+---
+kind: Role
+metadata: {name: editor}
+rules: []
+"#;
+    let manager = PermissionManager::from_kubernetes_roles(yaml).unwrap();
+    let perms = manager.perms_from_kubernetes_roles(yaml).unwrap();
+
+    // Roles with the same name in different namespaces or of different kinds are not merged
+    assert_eq!(perms.len(), 4);
+    assert_eq!(perms["Role/dev/editor"].get_actions().len(), 2);
+    assert_eq!(
+        *perms["Role/prod/editor"].get_actions(),
+        HashSet::from([String::from("core:pods:get")])
+    );
+    assert_eq!(
+        *perms["ClusterRole/editor"].get_actions(),
+        HashSet::from([String::from("core:pods:list")])
+    );
+    assert_eq!(perms["Role//editor"].is_empty(), true);
+}
+
+#[test]
+fn resource_names_test() {
+    let yaml = r#"
+kind: Role
+metadata: {name: web-editor, namespace: default}
+rules:
+- {apiGroups: [""], resources: ["pods"], verbs: ["get"]}
+- {apiGroups: [""], resources: ["pods"], verbs: ["update", "delete"], resourceNames: ["web"]}
+"#;
+    let roles = parse_roles(yaml, ':').unwrap();
+    assert_eq!(
+        roles["Role/default/web-editor"].scoped,
+        HashSet::from([
+            String::from("core:pods:delete"),
+            String::from("core:pods:update"),
+        ])
+    );
+
+    // Actions of rules with resource names are in the universe, but are not granted to every pod
+    let manager = PermissionManager::from_kubernetes_roles(yaml).unwrap();
+    assert_eq!(manager.to_actions().len(), 3);
+    assert_eq!(
+        manager.perms_from_kubernetes_roles(yaml).unwrap_err(),
+        ValidationError::UntranslatableActions(vec![
+            String::from("core:pods:delete"),
+            String::from("core:pods:update"),
+        ])
+    );
+}
+
+#[test]
+fn to_kubernetes_role_test() {
+    let perm = Permission::from_actions(HashSet::from([
+        String::from("core:pods:get"),
+        String::from("core:services:get"),
+        String::from("core:pods:list"),
+        String::from("apps:deployments:get"),
+    ]));
+
+    assert_eq!(
+        perm.to_kubernetes_role("reader", None).unwrap(),
+        "\
+apiVersion: rbac.authorization.k8s.io/v1
+kind: ClusterRole
+metadata:
+  name: reader
+rules:
+- apiGroups:
+  - ''
+  resources:
+  - services
+  verbs:
+  - get
+- apiGroups:
+  - ''
+  resources:
+  - pods
+  verbs:
+  - get
+  - list
+- apiGroups:
+  - apps
+  resources:
+  - deployments
+  verbs:
+  - get
+"
+    );
+
+    // Exported roles load back into the same permission
+    let manager = PermissionManager::from_actions(perm.get_actions().into_owned());
+    let perms = manager
+        .perms_from_kubernetes_roles(&perm.to_kubernetes_role("reader", Some("default")).unwrap())
+        .unwrap();
+    assert_eq!(
+        *perms["Role/default/reader"].get_actions(),
+        *perm.get_actions()
+    );
+
+    assert_eq!(
+        Permission::from_actions(HashSet::from([
+            String::from("view"),
+            String::from("core:pods:get"),
+            String::from("a:b:c:d"),
+        ]))
+        .to_kubernetes_role("reader", None)
+        .unwrap_err(),
+        ValidationError::UntranslatableActions(vec![String::from("a:b:c:d"), String::from("view")])
+    );
+}
@@ -5,7 +5,205 @@
 //!
 //! Also, permissions can be tied to a PermissionManager with a reference permission configuration for validation, instantiation and operations between linked permissions. This potentially grants more control and security to your permission management strategy.
 
+// Lets macros expanding to '::simple_perm_manager' paths work inside this crate too
+extern crate self as simple_perm_manager;
+
+mod access_review;
+pub use access_review::{AccessReview, SubjectReview};
+mod action_name_policy;
+pub use action_name_policy::ActionNamePolicy;
+mod action_tree;
+pub use action_tree::{ActionNode, ActionTree};
+mod audit;
+pub use audit::{AuditSink, DecisionEvent, JsonLinesSink};
+mod authorizer;
+pub use authorizer::Authorizer;
+#[cfg(feature = "biscuit")]
+mod biscuit;
+#[cfg(feature = "roaring")]
+mod bitmap_permission;
+mod casbin;
+mod cedar;
+#[cfg(any(feature = "postgres", feature = "sqlite", feature = "diesel"))]
+mod column_type;
+#[cfg(feature = "roaring")]
+pub use bitmap_permission::BitmapPermission;
+mod condition;
+pub use condition::{Condition, ConditionSpec, Context, Expression};
+mod decision;
+pub use decision::Decision;
+mod delegation;
+pub use delegation::Delegation;
+mod event_log;
+pub use event_log::{EventLog, LoggedEvent, PermissionEvent};
+mod expiring_permission;
+pub use expiring_permission::ExpiringPermission;
+mod glob;
+mod grant_store;
+pub use grant_store::{GrantSnapshot, GrantStore};
+mod iam_policy;
+pub use iam_policy::IamPolicy;
+#[cfg(feature = "kubernetes")]
+mod kubernetes_rbac;
+mod load_error;
+pub use load_error::LoadError;
+mod manager_id;
+pub use manager_id::ManagerId;
+mod manager_registry;
+pub use manager_registry::ManagerRegistry;
+mod migration;
+pub use migration::Migration;
+mod object_acl;
+pub use object_acl::ObjectAcl;
 mod permission;
-pub use permission::Permission;
+pub use permission::{Permission, ACTION_DIVIDER};
+mod permission_builder;
+pub use permission_builder::PermissionBuilder;
+mod permission_chain;
+pub use permission_chain::PermissionChain;
+mod permission_delta;
+pub use permission_delta::PermissionDelta;
 mod permission_manager;
 pub use permission_manager::PermissionManager;
+mod permission_store;
+#[cfg(feature = "async")]
+pub use permission_store::async_store::AsyncPermissionStore;
+#[cfg(all(feature = "postgres", feature = "async"))]
+pub use permission_store::AsyncPostgresStore;
+#[cfg(feature = "postgres")]
+pub use permission_store::PostgresStore;
+#[cfg(feature = "sqlite")]
+pub use permission_store::SqliteStore;
+pub use permission_store::{InMemoryStore, PermissionStore};
+#[cfg(feature = "sled")]
+pub use permission_store::{SledStore, SledStoreError};
+mod permission_translator;
+pub use permission_translator::PermissionTranslator;
+mod policy;
+pub use policy::{CombiningAlgorithm, Policy};
+mod posix_mode;
+#[cfg(feature = "redis")]
+mod redis_cache;
+#[cfg(feature = "redis")]
+pub use redis_cache::RedisPermissionCache;
+mod relation_tuple;
+pub use relation_tuple::RelationTuple;
+mod role_manager;
+pub use role_manager::{Role, RoleManager, RoleSnapshot};
+mod simulation;
+pub use simulation::{OutcomeFlip, PolicyChange};
+mod spicedb;
+mod telemetry;
+mod trace;
+pub use trace::{GrantSource, Trace, TraceStep};
+mod typed_permission;
+pub use typed_permission::Action;
+mod universe_diff;
+pub use universe_diff::UniverseDiff;
+mod universe_file;
+mod validation_error;
+pub use validation_error::ValidationError;
+mod validation_report;
+pub use validation_report::ValidationReport;
+#[cfg(feature = "watch")]
+mod watched_manager;
+#[cfg(feature = "watch")]
+pub use watched_manager::WatchedManager;
+
+/// Creates a [`Permission`](crate::Permission) from actions written inline, checking action syntax at compile time.
+///
+/// Actions can be written as a list of full action strings or using the nested object format of
+/// [`Permission::from_json`](crate::Permission::from_json()) (`true`/`false` values and nested objects for groups).
+/// Preceding the actions with a [`PermissionManager`](crate::PermissionManager) expression and `;` creates a
+/// managed [`Permission`](crate::Permission) through [`PermissionManager::perm_from_actions`](crate::PermissionManager::perm_from_actions()).
+///
+/// Empty actions, empty groups (leading, trailing or repeated `:`), whitespace, non boolean values
+/// and nesting deeper than 20 levels are rejected with a compile error.
+///
+/// # Examples:
+///
+/// ```
+/// use std::collections::HashSet;
+/// use simple_perm_manager::{perm, PermissionManager};
+///
+/// // List of actions
+/// let perm = perm!["building:view", "user:edit"];
+/// assert_eq!(*perm.get_actions(), HashSet::from([
+///     String::from("building:view"),
+///     String::from("user:edit"),
+/// ]));
+///
+/// // Nested object format
+/// let same_perm = perm!{
+///     "building": { "view": true, "edit": false },
+///     "user": { "edit": true }
+/// };
+/// assert_eq!(same_perm.get_actions(), perm.get_actions());
+///
+/// // Managed permission
+/// let manager = PermissionManager::from_json(r#"{"building": {"view": true}, "user": {"edit": true}}"#);
+/// let managed = perm!(manager; "building:view");
+/// assert!(manager.validate_perm(&managed));
+/// ```
+///
+/// Invalid actions do not compile:
+/// ```compile_fail
+/// use simple_perm_manager::perm;
+///
+/// let perm = perm!["building::view"];
+/// ```
+///
+/// # Panics:
+///
+/// Managed permissions panic in the same cases that [`PermissionManager::perm_from_actions`](crate::PermissionManager::perm_from_actions()) does.
+pub use simple_perm_manager_macros::perm;
+
+/// Generates an enum with one variant per action of a universe, so actions are checked by the compiler.
+///
+/// The universe is written inside the enum braces either as a list of action strings or in the nested object
+/// format of [`Permission::from_json`](crate::Permission::from_json()). Each action becomes a variant named
+/// after its groups and name in upper camel case (`"building:meter_readings:view"` is `BuildingMeterReadingsView`).
+/// Attributes and visibility written before `enum` are applied to the generated enum.
+///
+/// The generated enum derives `Clone`, `Copy`, `Debug`, `PartialEq`, `Eq`, `Hash`, `PartialOrd` and `Ord`,
+/// implements [`Action`](crate::Action), `AsRef<str>` and `Display` and has:
+/// - `ALL`: every variant in canonical order.
+/// - `as_str(&self)`: the action string of the variant.
+/// - `from_action(&str)`: the variant for an action string, if any.
+/// - `permission_manager()`: a new [`PermissionManager`](crate::PermissionManager) whose universe is every action of the enum.
+///
+/// Variants can be used to create permissions with [`PermissionManager::perm_from`](crate::PermissionManager::perm_from()).
+/// Invalid actions, actions mapping to the same variant name and empty universes are rejected with a compile error.
+///
+/// # Examples:
+///
+/// ```
+/// use simple_perm_manager::define_permissions;
+///
+/// define_permissions! {
+///     pub enum Action {
+///         "building": { "view": true, "edit": true },
+///         "user": { "create": true }
+///     }
+/// }
+///
+/// let manager = Action::permission_manager();
+/// let perm = manager.perm_from([Action::BuildingView, Action::UserCreate]);
+///
+/// assert!(perm.contains_action(Action::BuildingView.as_str()));
+/// assert_eq!(Action::from_action("user:create"), Some(Action::UserCreate));
+/// assert_eq!(Action::ALL.len(), 3);
+/// ```
+///
+/// Typos become build errors:
+/// ```compile_fail
+/// use simple_perm_manager::define_permissions;
+///
+/// define_permissions! {
+///     enum Action { "building": { "view": true } }
+/// }
+///
+/// let manager = Action::permission_manager();
+/// let perm = manager.perm_from([Action::BulidingView]);
+/// ```
+pub use simple_perm_manager_macros::define_permissions;
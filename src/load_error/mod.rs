@@ -0,0 +1,55 @@
+use crate::validation_error::ValidationError;
+use std::error::Error;
+use std::fmt;
+
+//// TESTS ////
+#[cfg(test)]
+mod tests;
+
+/// Error returned when a [`PermissionManager`](crate::PermissionManager) universe can not be loaded from outside the program,
+/// such as by [`PermissionManager::from_json_file`](crate::PermissionManager::from_json_file()) or
+/// [`PermissionManager::from_env`](crate::PermissionManager::from_env()).
+///
+/// # Examples:
+///
+/// ```
+/// use simple_perm_manager::{LoadError, PermissionManager};
+///
+/// match PermissionManager::from_env("UNSET_PERM_UNIVERSE") {
+///     Err(error @ LoadError::Env(_)) => assert_eq!(error.to_string(), "UNSET_PERM_UNIVERSE: environment variable not found"),
+///     _ => panic!("the variable is not set"),
+/// }
+/// ```
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum LoadError {
+    /// The universe file could not be read, with the path and the reason.
+    Io(String),
+    /// The universe environment variable could not be read, with the variable name and the reason.
+    Env(String),
+    /// The universe read is not valid.
+    Invalid(ValidationError),
+}
+
+impl fmt::Display for LoadError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            LoadError::Io(message) | LoadError::Env(message) => write!(f, "{}", message),
+            LoadError::Invalid(error) => write!(f, "invalid universe: {}", error),
+        }
+    }
+}
+
+impl Error for LoadError {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        match self {
+            LoadError::Io(_) | LoadError::Env(_) => None,
+            LoadError::Invalid(error) => Some(error),
+        }
+    }
+}
+
+impl From<ValidationError> for LoadError {
+    fn from(error: ValidationError) -> LoadError {
+        LoadError::Invalid(error)
+    }
+}
@@ -0,0 +1,37 @@
+use super::*;
+
+#[test]
+fn display_test() {
+    assert_eq!(
+        LoadError::Io(String::from("universe.json: No such file or directory")).to_string(),
+        "universe.json: No such file or directory"
+    );
+    assert_eq!(
+        LoadError::Env(String::from(
+            "PERM_UNIVERSE: environment variable not found"
+        ))
+        .to_string(),
+        "PERM_UNIVERSE: environment variable not found"
+    );
+    assert_eq!(
+        LoadError::from(ValidationError::MalformedActions(vec![String::from(
+            "a::b"
+        )]))
+        .to_string(),
+        "invalid universe: malformed actions: a::b"
+    );
+}
+
+#[test]
+fn source_test() {
+    assert_eq!(
+        LoadError::Io(String::from("error")).source().is_none(),
+        true
+    );
+    assert_eq!(
+        LoadError::Invalid(ValidationError::InvalidJson(String::from("error")))
+            .source()
+            .is_some(),
+        true
+    );
+}
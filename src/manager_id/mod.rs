@@ -0,0 +1,68 @@
+use std::fmt;
+use uuid::Uuid;
+
+//// TESTS ////
+#[cfg(test)]
+mod tests;
+
+/// Identity of a [`PermissionManager`](crate::PermissionManager).
+///
+/// Managed [`Permission`](crate::Permission)s can only operate with [`Permission`](crate::Permission)s that have the same [`ManagerId`](crate::ManagerId).  
+/// [`PermissionManager`](crate::PermissionManager)s created with [`from_actions`](crate::PermissionManager::from_actions()) or
+/// [`from_json`](crate::PermissionManager::from_json()) get a random [`ManagerId`](crate::ManagerId), while
+/// [`with_id`](crate::PermissionManager::with_id()) and [`from_json_with_id`](crate::PermissionManager::from_json_with_id())
+/// use the one supplied, so the identity can be stable across restarts and services.
+///
+/// # Examples:
+///
+/// ```
+/// use simple_perm_manager::ManagerId;
+///
+/// let id = ManagerId::parse("b4a3a1e4-3c1a-4f5e-9a57-2f1f0d1c6a10").unwrap();
+///
+/// assert_eq!(id.to_string(), "b4a3a1e4-3c1a-4f5e-9a57-2f1f0d1c6a10");
+/// assert!(ManagerId::parse("not an id").is_none());
+/// ```
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct ManagerId(Uuid);
+
+impl ManagerId {
+    /// Creates a [`ManagerId`](crate::ManagerId) from the UUID provided.
+    pub fn new(id: Uuid) -> ManagerId {
+        ManagerId(id)
+    }
+
+    /// Creates a new random [`ManagerId`](crate::ManagerId).
+    pub fn random() -> ManagerId {
+        ManagerId(Uuid::new_v4())
+    }
+
+    /// Parses a [`ManagerId`](crate::ManagerId) from its UUID string representation.  
+    /// Returns `None` if the string is not a valid UUID.
+    pub fn parse(id: &str) -> Option<ManagerId> {
+        Uuid::parse_str(id).ok().map(ManagerId)
+    }
+
+    /// Returns the UUID of the [`ManagerId`](crate::ManagerId).
+    pub fn as_uuid(&self) -> Uuid {
+        self.0
+    }
+}
+
+impl From<Uuid> for ManagerId {
+    fn from(id: Uuid) -> ManagerId {
+        ManagerId(id)
+    }
+}
+
+impl From<ManagerId> for Uuid {
+    fn from(id: ManagerId) -> Uuid {
+        id.0
+    }
+}
+
+impl fmt::Display for ManagerId {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.0)
+    }
+}
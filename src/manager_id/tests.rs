@@ -0,0 +1,25 @@
+use super::*;
+
+#[test]
+fn new_test() {
+    let uuid = Uuid::new_v4();
+    let id = ManagerId::new(uuid);
+
+    assert_eq!(id.as_uuid(), uuid);
+    assert_eq!(ManagerId::from(uuid), id);
+    assert_eq!(Uuid::from(id), uuid);
+}
+
+#[test]
+fn random_test() {
+    assert_ne!(ManagerId::random(), ManagerId::random());
+}
+
+#[test]
+fn parse_test() {
+    let id = ManagerId::random();
+
+    assert_eq!(ManagerId::parse(&id.to_string()), Some(id));
+    assert_eq!(ManagerId::parse(""), None);
+    assert_eq!(ManagerId::parse("b4a3a1e4-3c1a-4f5e"), None);
+}
@@ -0,0 +1,171 @@
+use crate::manager_id::ManagerId;
+use crate::permission::{deserialize_managed_actions, Permission, ACTION_DIVIDER};
+use crate::permission_manager::PermissionManager;
+use std::collections::HashMap;
+use std::sync::{Arc, OnceLock, RwLock};
+
+//// TESTS ////
+#[cfg(test)]
+mod tests;
+
+// Managers are stored by id and names are an alias to the id
+#[derive(Debug, Default)]
+struct Registry {
+    managers: HashMap<ManagerId, Arc<PermissionManager>>,
+    names: HashMap<String, ManagerId>,
+}
+
+/// Registry of [`PermissionManager`](crate::PermissionManager)s that can be retrieved by name or [`ManagerId`](crate::ManagerId).
+///
+/// A [`ManagerRegistry`](crate::ManagerRegistry) allows retrieving the [`PermissionManager`](crate::PermissionManager) of a deserialized
+/// [`Permission`](crate::Permission) anywhere in the program without threading references everywhere.  
+/// The registry is thread safe and can be shared, a program wide instance is available through [`global`](Self::global()).
+///
+/// # Examples:
+///
+/// ```
+/// use simple_perm_manager::{ManagerRegistry, PermissionManager};
+///
+/// let registry = ManagerRegistry::new();
+/// let manager = registry.register("buildings", PermissionManager::from_json(r#"{"create": true, "view": true}"#));
+///
+/// let perm = manager.perm_from_json(r#"{"view": true}"#);
+///
+/// // Retrieve the manager of a permission somewhere else
+/// let perm_manager = registry.get_by_id(perm.manager_id().unwrap()).unwrap();
+/// assert!(perm_manager.validate_perm(&perm));
+/// assert_eq!(registry.get("buildings").unwrap().id(), manager.id());
+/// ```
+#[derive(Debug, Default)]
+pub struct ManagerRegistry {
+    registry: RwLock<Registry>,
+}
+
+impl ManagerRegistry {
+    /// Creates a new empty [`ManagerRegistry`](crate::ManagerRegistry).
+    pub fn new() -> ManagerRegistry {
+        ManagerRegistry::default()
+    }
+
+    /// Returns the program wide [`ManagerRegistry`](crate::ManagerRegistry).
+    ///
+    /// # Examples:
+    ///
+    /// ```
+    /// use simple_perm_manager::{ManagerRegistry, PermissionManager};
+    ///
+    /// ManagerRegistry::global().register("global_doc_example", PermissionManager::from_json(r#"{"view": true}"#));
+    ///
+    /// assert!(ManagerRegistry::global().get("global_doc_example").is_some());
+    /// ```
+    pub fn global() -> &'static ManagerRegistry {
+        static GLOBAL: OnceLock<ManagerRegistry> = OnceLock::new();
+        GLOBAL.get_or_init(ManagerRegistry::new)
+    }
+
+    /// Registers the [`PermissionManager`](crate::PermissionManager) under the name provided and its [`ManagerId`](crate::ManagerId).  
+    /// Returns the registered [`PermissionManager`](crate::PermissionManager).
+    ///
+    /// # Panics:
+    ///
+    /// - Panics if a [`PermissionManager`](crate::PermissionManager) is already registered under the name provided.
+    /// - Panics if a [`PermissionManager`](crate::PermissionManager) with the same [`ManagerId`](crate::ManagerId) is already registered.
+    pub fn register(&self, name: &str, manager: PermissionManager) -> Arc<PermissionManager> {
+        let mut registry = self.registry.write().unwrap();
+        let id = manager.id();
+
+        // The lock guard is dropped before panicking so the registry is not poisoned
+        if registry.names.contains_key(name) {
+            drop(registry);
+            panic!("A manager is already registered with name '{}'", name);
+        }
+        if registry.managers.contains_key(&id) {
+            drop(registry);
+            panic!("A manager is already registered with id '{}'", id);
+        }
+
+        let manager = Arc::new(manager);
+        registry.names.insert(name.to_string(), id);
+        registry.managers.insert(id, manager.clone());
+
+        manager
+    }
+
+    /// Removes the [`PermissionManager`](crate::PermissionManager) registered under the name provided.  
+    /// Returns the removed [`PermissionManager`](crate::PermissionManager) or `None` if no manager was registered under the name.
+    ///
+    /// # Examples:
+    ///
+    /// ```
+    /// use simple_perm_manager::{ManagerRegistry, PermissionManager};
+    ///
+    /// let registry = ManagerRegistry::new();
+    /// registry.register("buildings", PermissionManager::from_json(r#"{"view": true}"#));
+    ///
+    /// assert!(registry.unregister("buildings").is_some());
+    /// assert!(registry.get("buildings").is_none());
+    /// ```
+    pub fn unregister(&self, name: &str) -> Option<Arc<PermissionManager>> {
+        let mut registry = self.registry.write().unwrap();
+        let id = registry.names.remove(name)?;
+
+        registry.managers.remove(&id)
+    }
+
+    /// Returns the [`PermissionManager`](crate::PermissionManager) registered under the name provided.
+    pub fn get(&self, name: &str) -> Option<Arc<PermissionManager>> {
+        let registry = self.registry.read().unwrap();
+        let id = registry.names.get(name)?;
+
+        registry.managers.get(id).cloned()
+    }
+
+    /// Returns the [`PermissionManager`](crate::PermissionManager) registered with the [`ManagerId`](crate::ManagerId) provided.
+    pub fn get_by_id(&self, id: ManagerId) -> Option<Arc<PermissionManager>> {
+        self.registry.read().unwrap().managers.get(&id).cloned()
+    }
+
+    /// Returns a [`Permission`](crate::Permission) from a JSON string created with [`Permission::to_managed_json`](crate::Permission::to_managed_json()),
+    /// linked to the registered [`PermissionManager`](crate::PermissionManager) with the manager id embedded in the JSON.
+    ///
+    /// # Examples:
+    ///
+    /// ```
+    /// use simple_perm_manager::{ManagerRegistry, PermissionManager};
+    ///
+    /// let registry = ManagerRegistry::new();
+    /// let manager = registry.register("buildings", PermissionManager::from_json(r#"{"create": true, "view": true}"#));
+    /// let json = manager.perm_from_json(r#"{"view": true}"#).to_managed_json();
+    ///
+    /// let perm = registry.adopt_json(&json);
+    /// assert!(manager.validate_perm(&perm));
+    /// ```
+    ///
+    /// # Panics:
+    ///
+    /// - Panics if `json` is not a valid managed JSON string or does not contain a manager id.
+    /// - Panics if no [`PermissionManager`](crate::PermissionManager) is registered with the manager id in `json`.
+    /// - Panics in the same cases that [`PermissionManager::adopt_json`](crate::PermissionManager::adopt_json()) does.
+    pub fn adopt_json(&self, json: &str) -> Permission {
+        let manager_id = match deserialize_managed_actions(json, ACTION_DIVIDER).0 {
+            Some(id) => ManagerId::new(id),
+            None => panic!("Permission to adopt is not managed"),
+        };
+
+        match self.get_by_id(manager_id) {
+            Some(manager) => manager.adopt_json(json),
+            None => panic!("No manager is registered with id '{}'", manager_id),
+        }
+    }
+
+    /// Returns the names of the registered [`PermissionManager`](crate::PermissionManager)s.
+    pub fn names(&self) -> Vec<String> {
+        self.registry
+            .read()
+            .unwrap()
+            .names
+            .keys()
+            .cloned()
+            .collect()
+    }
+}
@@ -0,0 +1,123 @@
+use super::*;
+
+#[test]
+fn register_test() {
+    let registry = ManagerRegistry::new();
+    let pm = registry.register(
+        "buildings",
+        PermissionManager::from_json(r#"{"view": true}"#),
+    );
+
+    assert_eq!(registry.get("buildings").unwrap().id(), pm.id());
+    assert_eq!(registry.names(), vec![String::from("buildings")]);
+
+    // Duplicated name
+    match std::panic::catch_unwind(|| {
+        registry.register(
+            "buildings",
+            PermissionManager::from_json(r#"{"view": true}"#),
+        );
+    }) {
+        Ok(_) => panic!("registering a duplicated name should have panicked"),
+        Err(_) => (),
+    }
+
+    // Duplicated id
+    let id = pm.id();
+    match std::panic::catch_unwind(|| {
+        registry.register(
+            "other",
+            PermissionManager::from_json_with_id(id, r#"{"view": true}"#),
+        );
+    }) {
+        Ok(_) => panic!("registering a duplicated id should have panicked"),
+        Err(_) => (),
+    }
+
+    // Failed registrations do not modify the registry
+    assert_eq!(registry.names(), vec![String::from("buildings")]);
+}
+
+#[test]
+fn unregister_test() {
+    let registry = ManagerRegistry::new();
+    let pm = registry.register(
+        "buildings",
+        PermissionManager::from_json(r#"{"view": true}"#),
+    );
+
+    assert_eq!(registry.unregister("buildings").unwrap().id(), pm.id());
+    assert_eq!(registry.get("buildings").is_none(), true);
+    assert_eq!(registry.get_by_id(pm.id()).is_none(), true);
+    assert_eq!(registry.unregister("buildings").is_none(), true);
+}
+
+#[test]
+fn get_test() {
+    let registry = ManagerRegistry::new();
+    let pm = registry.register(
+        "buildings",
+        PermissionManager::from_json(r#"{"view": true}"#),
+    );
+    let perm = pm.perm_from_json(r#"{"view": true}"#);
+
+    assert_eq!(registry.get("other").is_none(), true);
+    assert_eq!(
+        registry
+            .get_by_id(perm.manager_id().unwrap())
+            .unwrap()
+            .validate_perm(&perm),
+        true
+    );
+    assert_eq!(registry.get_by_id(ManagerId::random()).is_none(), true);
+}
+
+#[test]
+fn global_test() {
+    let pm = ManagerRegistry::global().register(
+        "global_test",
+        PermissionManager::from_json(r#"{"view": true}"#),
+    );
+
+    assert_eq!(
+        ManagerRegistry::global().get("global_test").unwrap().id(),
+        pm.id()
+    );
+    assert_eq!(
+        std::ptr::eq(ManagerRegistry::global(), ManagerRegistry::global()),
+        true
+    );
+}
+
+#[test]
+fn adopt_json_test() {
+    let registry = ManagerRegistry::new();
+    let pm = registry.register(
+        "buildings",
+        PermissionManager::from_json(r#"{"view": true}"#),
+    );
+    let perm = registry.adopt_json(&pm.perm_from_json(r#"{"view": true}"#).to_managed_json());
+
+    assert_eq!(pm.validate_perm(&perm), true);
+
+    // Unmanaged permission
+    match std::panic::catch_unwind(|| {
+        registry.adopt_json(&Permission::from_json(r#"{"view": true}"#).to_managed_json());
+    }) {
+        Ok(_) => panic!("adopting an unmanaged permission should have panicked"),
+        Err(_) => (),
+    }
+
+    // Manager not registered
+    let other_pm = PermissionManager::from_json(r#"{"view": true}"#);
+    match std::panic::catch_unwind(|| {
+        registry.adopt_json(
+            &other_pm
+                .perm_from_json(r#"{"view": true}"#)
+                .to_managed_json(),
+        );
+    }) {
+        Ok(_) => panic!("adopting a permission of a not registered manager should have panicked"),
+        Err(_) => (),
+    }
+}
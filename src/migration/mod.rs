@@ -0,0 +1,125 @@
+use std::collections::HashSet;
+
+//// TESTS ////
+#[cfg(test)]
+mod tests;
+
+/// Step upgrading the actions of [`Permission`](crate::Permission)s serialized under an older universe version, registered with
+/// [`PermissionManager::add_migration`](crate::PermissionManager::add_migration()).
+///
+/// Action names may also be groups, in which case the step applies to every action beneath the group.
+///
+/// # Examples:
+///
+/// ```
+/// use simple_perm_manager::{Migration, Permission, PermissionManager};
+///
+/// let mut manager = PermissionManager::from_json(r#"{"site": {"view": true, "edit": true, "publish": true}}"#);
+/// manager.add_migration(1, vec![
+///     Migration::rename("building", "site"),
+///     Migration::split("site:write", ["site:edit", "site:publish"]),
+///     Migration::remove("site:audit"),
+/// ]);
+///
+/// let stored = r#"{"actions": {"building": {"view": true, "write": true, "audit": true}}, "version": 0}"#;
+/// let perm = manager.perm_from_versioned_json(stored).unwrap();
+/// assert_eq!(perm.get_actions(), manager.get_universe().get_actions());
+/// ```
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum Migration {
+    /// Renames an action or group.
+    Rename { from: String, to: String },
+    /// Replaces an action or group with the actions provided.
+    Split { from: String, into: Vec<String> },
+    /// Removes an action or group.
+    Remove { action: String },
+}
+
+impl Migration {
+    /// Creates a [`Migration::Rename`](crate::Migration::Rename) step.
+    pub fn rename(from: &str, to: &str) -> Migration {
+        Migration::Rename {
+            from: from.to_string(),
+            to: to.to_string(),
+        }
+    }
+
+    /// Creates a [`Migration::Split`](crate::Migration::Split) step.
+    pub fn split<I, A>(from: &str, into: I) -> Migration
+    where
+        I: IntoIterator<Item = A>,
+        A: AsRef<str>,
+    {
+        Migration::Split {
+            from: from.to_string(),
+            into: into
+                .into_iter()
+                .map(|action| action.as_ref().to_string())
+                .collect(),
+        }
+    }
+
+    /// Creates a [`Migration::Remove`](crate::Migration::Remove) step.
+    pub fn remove(action: &str) -> Migration {
+        Migration::Remove {
+            action: action.to_string(),
+        }
+    }
+
+    // Returns the step with every action name mapped by the function provided
+    pub(crate) fn map_actions<F>(&self, map: F) -> Migration
+    where
+        F: Fn(&str) -> String,
+    {
+        match self {
+            Migration::Rename { from, to } => Migration::Rename {
+                from: map(from),
+                to: map(to),
+            },
+            Migration::Split { from, into } => Migration::Split {
+                from: map(from),
+                into: into.iter().map(|action| map(action)).collect(),
+            },
+            Migration::Remove { action } => Migration::Remove {
+                action: map(action),
+            },
+        }
+    }
+
+    // Applies the step to the actions, whose groups are divided by the separator provided
+    pub(crate) fn apply(&self, actions: HashSet<String>, separator: char) -> HashSet<String> {
+        // Part of the action after the action or group provided: Some("") for the action itself, None if not beneath it
+        let rest = |action: &str, name: &str| -> Option<String> {
+            let rest = action.strip_prefix(name)?;
+            if rest.is_empty() || rest.starts_with(separator) {
+                Some(rest.to_string())
+            } else {
+                None
+            }
+        };
+
+        match self {
+            Migration::Rename { from, to } => actions
+                .into_iter()
+                .map(|action| match rest(&action, from) {
+                    Some(rest) => format!("{}{}", to, rest),
+                    None => action,
+                })
+                .collect(),
+            Migration::Split { from, into } => actions
+                .into_iter()
+                .flat_map(|action| match rest(&action, from) {
+                    Some(rest) => into
+                        .iter()
+                        .map(|split| format!("{}{}", split, rest))
+                        .collect(),
+                    None => vec![action],
+                })
+                .collect(),
+            Migration::Remove { action: removed } => actions
+                .into_iter()
+                .filter(|action| rest(action, removed).is_none())
+                .collect(),
+        }
+    }
+}
@@ -0,0 +1,62 @@
+use super::*;
+
+fn actions(actions: &[&str]) -> HashSet<String> {
+    actions.iter().map(|action| action.to_string()).collect()
+}
+
+#[test]
+fn rename_test() {
+    let migration = Migration::rename("building", "site");
+
+    assert_eq!(
+        migration.apply(
+            actions(&["building", "building:view", "buildings:view"]),
+            ':'
+        ),
+        actions(&["site", "site:view", "buildings:view"])
+    );
+    assert_eq!(
+        migration.apply(actions(&["building.view", "building:view"]), '.'),
+        actions(&["site.view", "building:view"])
+    );
+}
+
+#[test]
+fn split_test() {
+    let migration = Migration::split("post:write", ["post:edit", "post:publish"]);
+
+    assert_eq!(
+        migration.apply(actions(&["post:write", "post:view"]), ':'),
+        actions(&["post:edit", "post:publish", "post:view"])
+    );
+    assert_eq!(
+        Migration::split("doc", ["post", "page"]).apply(actions(&["doc:view"]), ':'),
+        actions(&["post:view", "page:view"])
+    );
+}
+
+#[test]
+fn remove_test() {
+    assert_eq!(
+        Migration::remove("audit").apply(actions(&["audit:view", "audit", "auditor:view"]), ':'),
+        actions(&["auditor:view"])
+    );
+}
+
+#[test]
+fn map_actions_test() {
+    let mount = |action: &str| format!("org:{}", action);
+
+    assert_eq!(
+        Migration::split("write", ["edit"]).map_actions(mount),
+        Migration::split("org:write", ["org:edit"])
+    );
+    assert_eq!(
+        Migration::rename("a", "b").map_actions(mount),
+        Migration::rename("org:a", "org:b")
+    );
+    assert_eq!(
+        Migration::remove("a").map_actions(mount),
+        Migration::remove("org:a")
+    );
+}
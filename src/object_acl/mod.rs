@@ -0,0 +1,318 @@
+use crate::permission::Permission;
+use crate::permission_manager::PermissionManager;
+use serde_json::{json, Map, Value};
+use std::collections::{HashMap, HashSet};
+use std::fmt::Display;
+use std::hash::Hash;
+use std::str::FromStr;
+use std::sync::Arc;
+
+//// TESTS ////
+#[cfg(test)]
+mod tests;
+
+/// Access control list mapping resource ids to the [`Permission`](crate::Permission) allowed on each resource (per object permissions),
+/// validated against a [`PermissionManager`](crate::PermissionManager).
+///
+/// An action is allowed on a resource when the ACL of the resource and the [`Permission`](crate::Permission) of the subject both contain it.  
+/// Resources can be arranged in a tree (files in folders, projects in organizations...) where the actions allowed on a parent
+/// resource are inherited by all its descendants, unless a descendant stops inheriting with
+/// [`set_inherits`](crate::ObjectAcl::set_inherits()).
+///
+/// # Examples:
+///
+/// ```
+/// use simple_perm_manager::{ObjectAcl, PermissionManager};
+///
+/// let mut acl: ObjectAcl<u64> = ObjectAcl::new(PermissionManager::from_json(r#"{"view": true, "edit": true}"#));
+/// acl.grant_on(1, &acl.manager().perm_from_json(r#"{"view": true, "edit": true}"#));
+/// acl.grant_on(2, &acl.manager().perm_from_json(r#"{"view": true}"#));
+///
+/// let editor = acl.manager().perm_from_json(r#"{"view": true, "edit": true}"#);
+/// assert!(acl.check_on(&1, &editor, "edit"));
+/// assert!(!acl.check_on(&2, &editor, "edit"));
+/// assert!(!acl.check_on(&3, &editor, "view"));
+/// ```
+#[derive(Clone, Debug)]
+pub struct ObjectAcl<R: Hash + Eq> {
+    manager: Arc<PermissionManager>,
+    acl: HashMap<R, Permission>,
+    // Resources mapped to their parent resource
+    parents: HashMap<R, R>,
+    // Resources that do not inherit the actions allowed on their ancestors
+    no_inherit: HashSet<R>,
+}
+
+impl<R: Hash + Eq> ObjectAcl<R> {
+    /// Creates a new empty [`ObjectAcl`](crate::ObjectAcl) for the [`PermissionManager`](crate::PermissionManager) provided.  
+    /// Accepts an owned [`PermissionManager`](crate::PermissionManager) or a shared one, such as the ones stored in a
+    /// [`ManagerRegistry`](crate::ManagerRegistry).
+    pub fn new<M>(manager: M) -> ObjectAcl<R>
+    where
+        M: Into<Arc<PermissionManager>>,
+    {
+        ObjectAcl {
+            manager: manager.into(),
+            acl: HashMap::new(),
+            parents: HashMap::new(),
+            no_inherit: HashSet::new(),
+        }
+    }
+
+    /// Returns the [`PermissionManager`](crate::PermissionManager) the ACL is validated against.
+    pub fn manager(&self) -> &PermissionManager {
+        &self.manager
+    }
+
+    /// Allows the actions of the [`Permission`](crate::Permission) provided on a resource, in addition to the ones already allowed.
+    ///
+    /// # Panics:
+    ///
+    /// Panics if the [`Permission`](crate::Permission) is not valid for the [`PermissionManager`](crate::PermissionManager).
+    pub fn grant_on(&mut self, resource: R, perm: &Permission) {
+        if !self.manager.validate_perm(perm) {
+            panic!("Permission to grant is not valid for the ObjectAcl PermissionManager");
+        }
+
+        match self.acl.get_mut(&resource) {
+            Some(allowed) => allowed.union_with(perm),
+            None => {
+                self.acl.insert(resource, perm.clone());
+            }
+        }
+    }
+
+    /// Stops allowing the actions of the [`Permission`](crate::Permission) provided on a resource.
+    ///
+    /// # Panics:
+    ///
+    /// Panics if the [`Permission`](crate::Permission) is not managed by the [`PermissionManager`](crate::PermissionManager).
+    pub fn revoke_on(&mut self, resource: &R, perm: &Permission) {
+        if !self.manager.get_universe().has_same_manager(perm) {
+            panic!("Permission to revoke is not managed by the ObjectAcl PermissionManager");
+        }
+
+        if let Some(allowed) = self.acl.get_mut(resource) {
+            allowed.difference_with(perm);
+            if allowed.is_empty() {
+                self.acl.remove(resource);
+            }
+        }
+    }
+
+    /// Removes a resource from the ACL. Returns the [`Permission`](crate::Permission) that was allowed on it, if any.
+    pub fn remove(&mut self, resource: &R) -> Option<Permission> {
+        self.acl.remove(resource)
+    }
+
+    /// Returns the [`Permission`](crate::Permission) allowed on a resource.  
+    /// Resources not in the ACL get an empty [`Permission`](crate::Permission) managed by the [`PermissionManager`](crate::PermissionManager).
+    pub fn perm_on(&self, resource: &R) -> Permission {
+        self.manager.union_all(self.acl.get(resource))
+    }
+
+    /// Returns the [`Permission`](crate::Permission) allowed on a resource, including the actions inherited from its ancestors.
+    ///
+    /// # Examples:
+    ///
+    /// ```
+    /// use simple_perm_manager::{ObjectAcl, PermissionManager};
+    ///
+    /// let mut acl = ObjectAcl::new(PermissionManager::from_json(r#"{"view": true, "edit": true}"#));
+    /// acl.set_parent("report.pdf", "docs");
+    /// acl.grant_on("docs", &acl.manager().perm_from_json(r#"{"view": true}"#));
+    /// acl.grant_on("report.pdf", &acl.manager().perm_from_json(r#"{"edit": true}"#));
+    ///
+    /// assert_eq!(acl.effective_on(&"report.pdf").get_actions().len(), 2);
+    ///
+    /// acl.set_inherits("report.pdf", false);
+    /// assert_eq!(acl.effective_on(&"report.pdf").get_actions().len(), 1);
+    /// ```
+    pub fn effective_on(&self, resource: &R) -> Permission {
+        let mut ancestry = vec![resource];
+        let mut current = resource;
+        while !self.no_inherit.contains(current) {
+            match self.parents.get(current) {
+                Some(parent) => {
+                    ancestry.push(parent);
+                    current = parent;
+                }
+                None => break,
+            }
+        }
+
+        self.manager
+            .union_all(ancestry.into_iter().filter_map(|r| self.acl.get(r)))
+    }
+
+    /// Sets the parent of a resource. Returns the previous parent of the resource, if any.
+    ///
+    /// # Panics:
+    ///
+    /// Panics if the parent is the resource itself or one of its descendants.
+    pub fn set_parent(&mut self, resource: R, parent: R) -> Option<R> {
+        let mut ancestor = Some(&parent);
+        while let Some(current) = ancestor {
+            if *current == resource {
+                panic!("resource parent would create a cycle in the ObjectAcl hierarchy");
+            }
+            ancestor = self.parents.get(current);
+        }
+
+        self.parents.insert(resource, parent)
+    }
+
+    /// Removes the parent of a resource. Returns the removed parent, if any.
+    pub fn remove_parent(&mut self, resource: &R) -> Option<R> {
+        self.parents.remove(resource)
+    }
+
+    /// Returns the parent of a resource, if any.
+    pub fn parent(&self, resource: &R) -> Option<&R> {
+        self.parents.get(resource)
+    }
+
+    /// Sets whether a resource inherits the actions allowed on its ancestors, which it does by default.  
+    /// Resources that do not inherit only allow their own actions, overriding their ancestors, and their descendants
+    /// inherit from them but not from their ancestors.
+    pub fn set_inherits(&mut self, resource: R, inherits: bool) {
+        if inherits {
+            self.no_inherit.remove(&resource);
+        } else {
+            self.no_inherit.insert(resource);
+        }
+    }
+
+    /// Returns `true` if the action is allowed on the resource, directly or inherited from its ancestors, and contained in the
+    /// [`Permission`](crate::Permission) of the subject.
+    ///
+    /// # Panics:
+    ///
+    /// Panics if the [`Permission`](crate::Permission) of the subject is managed by a different [`PermissionManager`](crate::PermissionManager).
+    pub fn check_on(&self, resource: &R, subject_perm: &Permission, action_str: &str) -> bool {
+        if subject_perm.is_managed() && !self.manager.get_universe().has_same_manager(subject_perm)
+        {
+            panic!("subject Permission is managed by a different manager than the ObjectAcl one");
+        }
+
+        subject_perm.contains_action(action_str)
+            && self.effective_on(resource).contains_action(action_str)
+    }
+
+    /// Returns the resources in the ACL in no particular order.
+    pub fn resources(&self) -> impl Iterator<Item = &R> + '_ {
+        self.acl.keys()
+    }
+}
+
+impl<R: Hash + Eq + Display> ObjectAcl<R> {
+    /// Serializes the ACL into a JSON object with the actions allowed on each resource id as `acl`, in the
+    /// [`Permission::to_json`](crate::Permission::to_json()) format, the parent of each resource as `parents` and the sorted
+    /// resources that do not inherit from their ancestors as `no_inherit`.
+    ///
+    /// # Examples:
+    ///
+    /// ```
+    /// use simple_perm_manager::{ObjectAcl, PermissionManager};
+    ///
+    /// let mut acl = ObjectAcl::new(PermissionManager::from_json(r#"{"view": true, "edit": true}"#));
+    /// acl.grant_on(7, &acl.manager().perm_from_json(r#"{"view": true}"#));
+    /// acl.set_parent(8, 7);
+    /// acl.set_inherits(9, false);
+    ///
+    /// assert_eq!(acl.to_json(), r#"{"acl":{"7":{"view":true}},"no_inherit":["9"],"parents":{"8":"7"}}"#);
+    /// ```
+    pub fn to_json(&self) -> String {
+        let acl: Map<String, Value> = self
+            .acl
+            .iter()
+            .map(|(resource, perm)| {
+                let actions: Value = serde_json::from_str(&perm.to_json())
+                    .expect("unexpected error serializing a Permission in an ObjectAcl");
+                (resource.to_string(), actions)
+            })
+            .collect();
+        let parents: Map<String, Value> = self
+            .parents
+            .iter()
+            .map(|(resource, parent)| (resource.to_string(), Value::String(parent.to_string())))
+            .collect();
+        let mut no_inherit: Vec<String> = self.no_inherit.iter().map(R::to_string).collect();
+        no_inherit.sort();
+
+        json!({"acl": acl, "parents": parents, "no_inherit": no_inherit}).to_string()
+    }
+}
+
+// Returns the resource id of its string in an ObjectAcl JSON
+fn parse_resource<R: FromStr>(resource: &str) -> R {
+    match R::from_str(resource) {
+        Ok(resource) => resource,
+        Err(_) => panic!("wrong resource id in ObjectAcl json string: {}", resource),
+    }
+}
+
+impl<R: Hash + Eq + FromStr> ObjectAcl<R> {
+    /// Creates an [`ObjectAcl`](crate::ObjectAcl) for the [`PermissionManager`](crate::PermissionManager) provided from a JSON string
+    /// created with [`ObjectAcl::to_json`](crate::ObjectAcl::to_json()).
+    ///
+    /// # Examples:
+    ///
+    /// ```
+    /// use simple_perm_manager::{ObjectAcl, PermissionManager};
+    ///
+    /// let manager = PermissionManager::from_json(r#"{"view": true, "edit": true}"#);
+    /// let acl: ObjectAcl<u64> = ObjectAcl::from_json(
+    ///     manager,
+    ///     r#"{"acl": {"7": {"view": true}}, "parents": {"8": "7"}, "no_inherit": []}"#,
+    /// );
+    ///
+    /// assert!(acl.perm_on(&7).contains_action("view"));
+    /// assert!(acl.effective_on(&8).contains_action("view"));
+    /// ```
+    ///
+    /// # Panics:
+    ///
+    /// - Panics if the JSON string is not an object with a valid action object for each resource as `acl`, an object of resource
+    ///   ids as `parents` and an array of resource ids as `no_inherit`.
+    /// - Panics if a resource id can not be parsed.
+    /// - Panics if any action is not in the universe of the [`PermissionManager`](crate::PermissionManager).
+    /// - Panics if the parents create a cycle in the hierarchy.
+    pub fn from_json<M>(manager: M, acl_json: &str) -> ObjectAcl<R>
+    where
+        M: Into<Arc<PermissionManager>>,
+    {
+        let map = match serde_json::from_str(acl_json) {
+            Ok(Value::Object(map)) => map,
+            _ => panic!("wrong format in ObjectAcl json string"),
+        };
+        let (
+            Some(Value::Object(resources)),
+            Some(Value::Object(parents)),
+            Some(Value::Array(no_inherit)),
+        ) = (map.get("acl"), map.get("parents"), map.get("no_inherit"))
+        else {
+            panic!("wrong format in ObjectAcl json string");
+        };
+
+        let mut acl = ObjectAcl::new(manager);
+        for (resource, actions) in resources {
+            let perm = acl.manager.perm_from_json(&actions.to_string());
+            acl.grant_on(parse_resource(resource), &perm);
+        }
+        for (resource, parent) in parents {
+            let Some(parent) = parent.as_str() else {
+                panic!("wrong format in ObjectAcl json string");
+            };
+            acl.set_parent(parse_resource(resource), parse_resource(parent));
+        }
+        for resource in no_inherit {
+            let Some(resource) = resource.as_str() else {
+                panic!("wrong format in ObjectAcl json string");
+            };
+            acl.set_inherits(parse_resource(resource), false);
+        }
+
+        acl
+    }
+}
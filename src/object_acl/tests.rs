@@ -0,0 +1,300 @@
+use super::*;
+use std::collections::HashSet;
+
+fn test_acl() -> ObjectAcl<String> {
+    ObjectAcl::new(PermissionManager::from_json(
+        r#"{"doc": {"view": true, "edit": true, "delete": true}}"#,
+    ))
+}
+
+#[test]
+fn grant_on_test() {
+    let mut acl = test_acl();
+    let readme = String::from("readme");
+    acl.grant_on(
+        readme.clone(),
+        &acl.manager().perm_from_json(r#"{"doc": {"view": true}}"#),
+    );
+    acl.grant_on(
+        readme.clone(),
+        &acl.manager().perm_from_json(r#"{"doc": {"edit": true}}"#),
+    );
+
+    assert_eq!(
+        *acl.perm_on(&readme).get_actions(),
+        HashSet::from([String::from("doc:view"), String::from("doc:edit")])
+    );
+    assert_eq!(
+        acl.perm_on(&String::from("other")).get_actions().is_empty(),
+        true
+    );
+
+    match std::panic::catch_unwind(move || {
+        acl.grant_on(readme, &Permission::from_json(r#"{"doc": {"view": true}}"#));
+    }) {
+        Ok(_) => panic!("granting a permission of another manager should have panicked"),
+        Err(_) => (),
+    }
+}
+
+#[test]
+fn revoke_on_test() {
+    let mut acl = test_acl();
+    let readme = String::from("readme");
+    acl.grant_on(
+        readme.clone(),
+        &acl.manager()
+            .perm_from_json(r#"{"doc": {"view": true, "edit": true}}"#),
+    );
+
+    acl.revoke_on(
+        &readme,
+        &acl.manager().perm_from_json(r#"{"doc": {"edit": true}}"#),
+    );
+    assert_eq!(
+        *acl.perm_on(&readme).get_actions(),
+        HashSet::from([String::from("doc:view")])
+    );
+
+    acl.revoke_on(
+        &readme,
+        &acl.manager().perm_from_json(r#"{"doc": {"view": true}}"#),
+    );
+    assert_eq!(acl.resources().count(), 0);
+
+    match std::panic::catch_unwind(move || {
+        acl.revoke_on(&readme, &Permission::from_json("{}"));
+    }) {
+        Ok(_) => panic!("revoking a permission of another manager should have panicked"),
+        Err(_) => (),
+    }
+}
+
+#[test]
+fn remove_test() {
+    let mut acl = test_acl();
+    let readme = String::from("readme");
+    acl.grant_on(
+        readme.clone(),
+        &acl.manager().perm_from_json(r#"{"doc": {"view": true}}"#),
+    );
+
+    assert_eq!(acl.remove(&readme).is_some(), true);
+    assert_eq!(acl.remove(&readme).is_none(), true);
+}
+
+#[test]
+fn check_on_test() {
+    let mut acl = test_acl();
+    let readme = String::from("readme");
+    acl.grant_on(
+        readme.clone(),
+        &acl.manager()
+            .perm_from_json(r#"{"doc": {"view": true, "edit": true}}"#),
+    );
+    let viewer = acl.manager().perm_from_json(r#"{"doc": {"view": true}}"#);
+    let owner = acl.manager().perm_from_json(r#"{"doc": true}"#);
+
+    assert_eq!(acl.check_on(&readme, &viewer, "doc:view"), true);
+    assert_eq!(acl.check_on(&readme, &viewer, "doc:edit"), false);
+    assert_eq!(acl.check_on(&readme, &owner, "doc:edit"), true);
+    assert_eq!(acl.check_on(&readme, &owner, "doc:delete"), false);
+    assert_eq!(
+        acl.check_on(&String::from("other"), &owner, "doc:view"),
+        false
+    );
+
+    // Unmanaged permissions are accepted
+    let unmanaged = Permission::from_json(r#"{"doc": {"view": true}}"#);
+    assert_eq!(acl.check_on(&readme, &unmanaged, "doc:view"), true);
+
+    let other_manager = PermissionManager::from_json(r#"{"doc": {"view": true}}"#);
+    match std::panic::catch_unwind(|| {
+        acl.check_on(
+            &readme,
+            &other_manager.perm_from_json(r#"{"doc": {"view": true}}"#),
+            "doc:view",
+        );
+    }) {
+        Ok(_) => panic!("checking with a permission of another manager should have panicked"),
+        Err(_) => (),
+    }
+}
+
+#[test]
+fn json_test() {
+    let mut acl = test_acl();
+    acl.grant_on(
+        String::from("readme"),
+        &acl.manager().perm_from_json(r#"{"doc": {"view": true}}"#),
+    );
+    acl.grant_on(
+        String::from("license"),
+        &acl.manager()
+            .perm_from_json(r#"{"doc": {"view": true, "edit": true}}"#),
+    );
+
+    let restored: ObjectAcl<String> = ObjectAcl::from_json(
+        PermissionManager::from_json(r#"{"doc": {"view": true, "edit": true, "delete": true}}"#),
+        &acl.to_json(),
+    );
+    assert_eq!(restored.resources().count(), 2);
+    assert_eq!(
+        *restored.perm_on(&String::from("license")).get_actions(),
+        HashSet::from([String::from("doc:view"), String::from("doc:edit")])
+    );
+
+    match std::panic::catch_unwind(|| {
+        ObjectAcl::<u32>::from_json(
+            PermissionManager::from_json(r#"{"doc": {"view": true}}"#),
+            r#"{"acl": {"readme": {"doc": {"view": true}}}, "parents": {}, "no_inherit": []}"#,
+        );
+    }) {
+        Ok(_) => panic!("parsing an invalid resource id should have panicked"),
+        Err(_) => (),
+    }
+
+    match std::panic::catch_unwind(|| {
+        ObjectAcl::<u32>::from_json(
+            PermissionManager::from_json(r#"{"doc": {"view": true}}"#),
+            "[]",
+        );
+    }) {
+        Ok(_) => panic!("parsing a json that is not an object should have panicked"),
+        Err(_) => (),
+    }
+
+    match std::panic::catch_unwind(|| {
+        ObjectAcl::<u32>::from_json(
+            PermissionManager::from_json(r#"{"doc": {"view": true}}"#),
+            r#"{"acl": {}, "parents": {"1": "2", "2": "1"}, "no_inherit": []}"#,
+        );
+    }) {
+        Ok(_) => panic!("parsing parents with a cycle should have panicked"),
+        Err(_) => (),
+    }
+}
+
+fn test_hierarchy() -> ObjectAcl<&'static str> {
+    let mut acl = ObjectAcl::new(PermissionManager::from_json(
+        r#"{"doc": {"view": true, "edit": true, "delete": true}}"#,
+    ));
+    acl.set_parent("docs", "org");
+    acl.set_parent("private", "docs");
+    acl.set_parent("report.pdf", "private");
+    acl.grant_on(
+        "org",
+        &acl.manager().perm_from_json(r#"{"doc": {"view": true}}"#),
+    );
+    acl.grant_on(
+        "docs",
+        &acl.manager().perm_from_json(r#"{"doc": {"edit": true}}"#),
+    );
+    acl.grant_on(
+        "report.pdf",
+        &acl.manager().perm_from_json(r#"{"doc": {"delete": true}}"#),
+    );
+    acl
+}
+
+#[test]
+fn set_parent_test() {
+    let mut acl = test_hierarchy();
+
+    assert_eq!(acl.parent(&"report.pdf"), Some(&"private"));
+    assert_eq!(acl.set_parent("report.pdf", "docs"), Some("private"));
+    assert_eq!(acl.remove_parent(&"report.pdf"), Some("docs"));
+    assert_eq!(acl.parent(&"report.pdf"), None);
+
+    match std::panic::catch_unwind(move || {
+        acl.set_parent("org", "private");
+    }) {
+        Ok(_) => panic!("creating a cycle in the hierarchy should have panicked"),
+        Err(_) => (),
+    }
+
+    let mut acl = test_hierarchy();
+    match std::panic::catch_unwind(move || {
+        acl.set_parent("org", "org");
+    }) {
+        Ok(_) => panic!("making a resource its own parent should have panicked"),
+        Err(_) => (),
+    }
+}
+
+#[test]
+fn effective_on_test() {
+    let acl = test_hierarchy();
+
+    assert_eq!(
+        *acl.effective_on(&"report.pdf").get_actions(),
+        HashSet::from([
+            String::from("doc:view"),
+            String::from("doc:edit"),
+            String::from("doc:delete"),
+        ])
+    );
+    assert_eq!(
+        *acl.effective_on(&"private").get_actions(),
+        HashSet::from([String::from("doc:view"), String::from("doc:edit")])
+    );
+    assert_eq!(acl.effective_on(&"other").get_actions().is_empty(), true);
+
+    let owner = acl.manager().perm_from_json(r#"{"doc": true}"#);
+    assert_eq!(acl.check_on(&"report.pdf", &owner, "doc:view"), true);
+    assert_eq!(acl.check_on(&"org", &owner, "doc:edit"), false);
+}
+
+#[test]
+fn set_inherits_test() {
+    let mut acl = test_hierarchy();
+    acl.set_inherits("private", false);
+
+    assert_eq!(acl.effective_on(&"private").get_actions().is_empty(), true);
+    assert_eq!(
+        *acl.effective_on(&"report.pdf").get_actions(),
+        HashSet::from([String::from("doc:delete")])
+    );
+
+    acl.set_inherits("private", true);
+    assert_eq!(acl.effective_on(&"report.pdf").get_actions().len(), 3);
+}
+
+#[test]
+fn json_hierarchy_test() {
+    let mut acl = test_acl();
+    acl.set_parent(String::from("docs"), String::from("org"));
+    acl.set_parent(String::from("private"), String::from("docs"));
+    acl.set_parent(String::from("report.pdf"), String::from("private"));
+    acl.set_inherits(String::from("private"), false);
+    acl.grant_on(
+        String::from("org"),
+        &acl.manager().perm_from_json(r#"{"doc": {"view": true}}"#),
+    );
+    acl.grant_on(
+        String::from("private"),
+        &acl.manager().perm_from_json(r#"{"doc": {"edit": true}}"#),
+    );
+
+    let restored: ObjectAcl<String> = ObjectAcl::from_json(
+        PermissionManager::from_json(r#"{"doc": {"view": true, "edit": true, "delete": true}}"#),
+        &acl.to_json(),
+    );
+    assert_eq!(restored.to_json(), acl.to_json());
+    assert_eq!(
+        restored.parent(&String::from("report.pdf")),
+        Some(&String::from("private"))
+    );
+
+    // The restored ACL inherits along the same hierarchy, stopping at resources that do not inherit
+    assert_eq!(
+        *restored.effective_on(&String::from("docs")).get_actions(),
+        HashSet::from([String::from("doc:view")])
+    );
+    assert_eq!(
+        *restored
+            .effective_on(&String::from("report.pdf"))
+            .get_actions(),
+        HashSet::from([String::from("doc:edit")])
+    );
+}
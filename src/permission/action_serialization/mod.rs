@@ -1,5 +1,6 @@
 use serde_json::{Map, Value};
 use std::collections::HashSet;
+use uuid::Uuid;
 
 //// TESTS ////
 #[cfg(test)]
@@ -8,9 +9,10 @@ mod tests;
 // Constant for defining maximun nesting allowed in a json_object when deserializing
 // As the deserializing function is recursive, this prevent overflows.
 const MAX_JSON_DEPTH_ALLOWED: u8 = 20;
-// Contant for defining the character that acts as a divider for action subgroups in
-// serialization and deserialization.
-const ACTION_DIVIDER: char = ':';
+/// Default character dividing the groups and name of an action (e.g. `"building:room:view"`), used by unmanaged
+/// [`Permission`](crate::Permission)s and by [`PermissionManager`](crate::PermissionManager)s unless
+/// [`set_separator`](crate::PermissionManager::set_separator()) is called.
+pub const ACTION_DIVIDER: char = ':';
 
 #[doc(hidden)]
 /// Deserializes a JSON object into a HashSet of string actions.
@@ -19,28 +21,65 @@ const ACTION_DIVIDER: char = ':';
 /// # Arguments
 ///
 /// * `current_depth` - Current recursivity depth of the function. Should be set to cero (0) everytime the function is called explicitly.
-/// Can be set to a greter value if you want to reduce max recursivity allowed by the `MAX_JSON_DEPTH_ALLOWED` constant.
+///   Can be set to a greter value if you want to reduce max recursivity allowed by the `MAX_JSON_DEPTH_ALLOWED` constant.
 /// * `prefix` - Should be set to cero (0) everytime the function is called explicitly.
-/// Represents the action key acumulated value from previous function calls.
+///   Represents the action key acumulated value from previous function calls.
 /// * `json_obj` - serde_json::Map containing the JSON object with the actions that need to be deserialized into action strings.
+/// * `separator` - Character joining the groups and name of the actions.
 ///
 /// # Panics
 ///
-/// - Panics if `current_depth` is greater or equal to `MAX_JSON_DEPTH_ALLOWED` value.
-/// This usually means that max recursivity has been reached.
-/// - Panics if `json_obj` is not correctly formed and for any key contains a
-/// value different from a serde_json::Map or a serde_json::Bool.
+/// Panics with the error message returned by `try_deserialize_actions` if the JSON object can not be deserialized.
 pub fn deserialize_actions(
     current_depth: u8,
     prefix: &str,
     json_obj: &Map<String, Value>,
+    separator: char,
 ) -> HashSet<String> {
-    // If we have already reached max recursivity nesting allowed then panic.
+    match try_deserialize_actions(current_depth, prefix, json_obj, separator) {
+        Ok(actions) => actions,
+        Err(error) => panic!("{}", error),
+    }
+}
+
+#[doc(hidden)]
+/// Deserializes a JSON object into a HashSet of string actions, returning an error message instead of panicking.
+/// Arguments are the same as in `deserialize_actions`.
+///
+/// # Errors
+///
+/// - Returns an error if `current_depth` is greater or equal to `MAX_JSON_DEPTH_ALLOWED` value.
+///   This usually means that max recursivity has been reached.
+/// - Returns an error if `json_obj` is not correctly formed and for any key contains a
+///   value different from a serde_json::Map or a serde_json::Bool.
+pub fn try_deserialize_actions(
+    current_depth: u8,
+    prefix: &str,
+    json_obj: &Map<String, Value>,
+    separator: char,
+) -> Result<HashSet<String>, String> {
+    try_deserialize_policy_actions(current_depth, prefix, json_obj, separator)
+        .map(|(allowed, _)| allowed)
+}
+
+#[doc(hidden)]
+/// Deserializes a JSON object into two HashSets of string actions: the allowed actions (`true` values)
+/// and the denied actions (`false` values). Arguments and errors are the same as in `try_deserialize_actions`.
+pub fn try_deserialize_policy_actions(
+    current_depth: u8,
+    prefix: &str,
+    json_obj: &Map<String, Value>,
+    separator: char,
+) -> Result<(HashSet<String>, HashSet<String>), String> {
+    // If we have already reached max recursivity nesting allowed then fail.
     if current_depth >= MAX_JSON_DEPTH_ALLOWED {
-        panic!("wrong format in permission json string - too much nesting")
+        return Err(String::from(
+            "wrong format in permission json string - too much nesting",
+        ));
     }
 
-    let mut actions: HashSet<String> = HashSet::new();
+    let mut allowed: HashSet<String> = HashSet::new();
+    let mut denied: HashSet<String> = HashSet::new();
 
     // Iterate json map object
     for (key, value) in json_obj.into_iter() {
@@ -48,45 +87,153 @@ pub fn deserialize_actions(
         let action_value = if prefix.is_empty() {
             key.to_string()
         } else {
-            format!("{}{}{}", prefix, ACTION_DIVIDER, key)
+            format!("{}{}{}", prefix, separator, key)
         };
 
         match value {
             // If the value for a key is a json map again then call this function recursively passing current depth
             // and the current key value as prefix for subsequent actions.
             Value::Object(map) => {
-                actions = actions
-                    .union(&deserialize_actions(current_depth + 1, &action_value, map))
-                    .cloned()
-                    .collect();
+                let (sub_allowed, sub_denied) = try_deserialize_policy_actions(
+                    current_depth + 1,
+                    &action_value,
+                    map,
+                    separator,
+                )?;
+                allowed.extend(sub_allowed);
+                denied.extend(sub_denied);
             }
             // If the value is a boolean then we have reached the end of the action definition and
-            // can include the action in the actions HashSet.
+            // can include the action in the allowed or denied HashSet.
             Value::Bool(val) => {
                 if *val {
-                    actions.insert(action_value);
+                    allowed.insert(action_value);
+                } else {
+                    denied.insert(action_value);
                 }
             }
-            _ => panic!(
+            _ => {
+                return Err(format!(
                 "wrong format in permission json string - found no object or boolean value, {}-{}",
                 key, value
-            ),
+            ))
+            }
         };
     }
 
-    actions
+    Ok((allowed, denied))
 }
 
 #[doc(hidden)]
-/// Serializes a HashSet of string actions into a serde_json::Map which represents valid JSON.
-pub fn serialize_actions(actions: &HashSet<String>) -> Map<String, Value> {
+/// Parses a JSON string into a HashSet of string actions, returning an error message instead of panicking.
+///
+/// # Errors
+///
+/// - Returns an error if `actions_json` is not a valid JSON object.
+/// - Returns an error in the same cases that `try_deserialize_actions` does.
+pub fn try_parse_actions(actions_json: &str, separator: char) -> Result<HashSet<String>, String> {
+    match serde_json::from_str(actions_json) {
+        Ok(Value::Object(map)) => try_deserialize_actions(0, "", &map, separator),
+        Ok(_) => Err(String::from("wrong format in permission json string")),
+        Err(error) => Err(format!("wrong format in permission json string: {}", error)),
+    }
+}
+
+#[doc(hidden)]
+/// Parses a JSON string into the allowed (`true` values) and denied (`false` values) HashSets of string actions.
+///
+/// # Panics
+///
+/// - Panics if `actions_json` is not a valid JSON object.
+/// - Panics in the same cases that `deserialize_actions` does.
+pub fn parse_policy_actions(
+    actions_json: &str,
+    separator: char,
+) -> (HashSet<String>, HashSet<String>) {
+    let result = match serde_json::from_str(actions_json) {
+        Ok(Value::Object(map)) => try_deserialize_policy_actions(0, "", &map, separator),
+        Ok(_) => Err(String::from("wrong format in permission json string")),
+        Err(error) => Err(format!("wrong format in permission json string: {}", error)),
+    };
+
+    match result {
+        Ok(actions) => actions,
+        Err(error) => panic!("{}", error),
+    }
+}
+
+#[doc(hidden)]
+/// Serializes allowed and denied sets of string actions into a serde_json::Map, using `true` values for
+/// allowed actions and `false` values for denied ones. Actions both allowed and denied are serialized as denied.
+///
+/// # Panics
+///
+/// Panics if an action is also a group of another action (e.g. `"building"` and `"building:view"`), as
+/// a JSON key can not hold both a boolean and an object.
+pub fn serialize_policy_actions<A, D, S>(
+    allowed: A,
+    denied: D,
+    separator: char,
+) -> Map<String, Value>
+where
+    A: IntoIterator<Item = S>,
+    D: IntoIterator<Item = S>,
+    S: AsRef<str>,
+{
+    let mut map: Map<String, Value> = Map::new();
+    let values = allowed
+        .into_iter()
+        .map(|action| (action, true))
+        .chain(denied.into_iter().map(|action| (action, false)));
+
+    for (action, value) in values {
+        let mut map_pointer = &mut map;
+        let mut objects = action.as_ref().split(separator).peekable();
+
+        while let Some(obj) = objects.next() {
+            if objects.peek().is_none() {
+                if let Some(Value::Object(_)) =
+                    map_pointer.insert(String::from(obj), Value::Bool(value))
+                {
+                    panic!("action is also a group of another action in policy serialization");
+                }
+            } else {
+                let entry = map_pointer
+                    .entry(String::from(obj))
+                    .or_insert_with(|| Value::Object(Map::new()));
+                map_pointer = match entry {
+                    Value::Object(x) => x,
+                    _ => panic!("action is also a group of another action in policy serialization"),
+                };
+            }
+        }
+    }
+
+    map
+}
+
+#[doc(hidden)]
+/// Returns `true` if the action is well formed: it is not empty and has no empty groups between dividers.
+/// Malformed actions can not be serialized and deserialized back into the same action.
+pub fn is_well_formed_action(action: &str, separator: char) -> bool {
+    action.split(separator).all(|group| !group.is_empty())
+}
+
+#[doc(hidden)]
+/// Serializes a set of string actions into a serde_json::Map which represents valid JSON.
+pub fn serialize_actions<I, S>(actions: I, separator: char) -> Map<String, Value>
+where
+    I: IntoIterator<Item = S>,
+    S: AsRef<str>,
+{
     let mut map: Map<String, Value> = Map::new();
     // Fo each action
-    for action in actions.iter() {
+    for action in actions {
+        let action = action.as_ref();
         // Declare a mutable pointer to the map start
         let mut map_pointer = &mut map;
         // Get objects in an action string
-        let objects: Vec<&str> = action.split(ACTION_DIVIDER).collect();
+        let objects: Vec<&str> = action.split(separator).collect();
 
         // For each object
         for obj in &objects {
@@ -120,3 +267,58 @@ pub fn serialize_actions(actions: &HashSet<String>) -> Map<String, Value> {
 
     map
 }
+
+#[doc(hidden)]
+/// Serializes a set of string actions and the id of their manager into a serde_json::Map with the format
+/// `{"manager_id": "<uuid>" | null, "actions": {...}}`.
+pub fn serialize_managed_actions<I, S>(
+    manager_id: Option<Uuid>,
+    actions: I,
+    separator: char,
+) -> Map<String, Value>
+where
+    I: IntoIterator<Item = S>,
+    S: AsRef<str>,
+{
+    let mut map: Map<String, Value> = Map::new();
+    map.insert(
+        String::from("manager_id"),
+        match manager_id {
+            Some(id) => Value::String(id.to_string()),
+            None => Value::Null,
+        },
+    );
+    map.insert(
+        String::from("actions"),
+        Value::Object(serialize_actions(actions, separator)),
+    );
+
+    map
+}
+
+#[doc(hidden)]
+/// Deserializes a JSON string created with `serialize_managed_actions` into the manager id and the HashSet of string actions.
+///
+/// # Panics
+///
+/// - Panics if `json` is not valid JSON string or does not have the managed format.
+/// - Panics in the same cases that `deserialize_actions` does for the `actions` object.
+pub fn deserialize_managed_actions(json: &str, separator: char) -> (Option<Uuid>, HashSet<String>) {
+    let value: Value =
+        serde_json::from_str(json).expect("wrong format in managed permission json string");
+
+    let manager_id = match value.get("manager_id") {
+        Some(Value::String(id)) => Some(
+            Uuid::parse_str(id)
+                .expect("wrong format in managed permission json string - invalid manager id"),
+        ),
+        Some(Value::Null) => None,
+        _ => panic!("wrong format in managed permission json string - missing manager id"),
+    };
+    let actions = match value.get("actions") {
+        Some(Value::Object(map)) => deserialize_actions(0, "", map, separator),
+        _ => panic!("wrong format in managed permission json string - missing actions"),
+    };
+
+    (manager_id, actions)
+}
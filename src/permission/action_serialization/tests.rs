@@ -7,7 +7,8 @@ fn deserialize_actions_test() {
     // Test json returns correct actions
     let data_simple = json!({"building": { "view": true, "meter": {"create":true}}, "user": {"edit": true}, "simple_action": true});
     if let Value::Object(map) = data_simple {
-        let actions = std::panic::catch_unwind(|| deserialize_actions(0, "", &map)).unwrap();
+        let actions =
+            std::panic::catch_unwind(|| deserialize_actions(0, "", &map, ACTION_DIVIDER)).unwrap();
         assert_eq!(
             actions,
             HashSet::from([
@@ -24,7 +25,8 @@ fn deserialize_actions_test() {
     // Test json is very simple, just object with values
     let data_simple = json!({"view": true, "create": true});
     if let Value::Object(map) = data_simple {
-        let actions = std::panic::catch_unwind(|| deserialize_actions(0, "", &map)).unwrap();
+        let actions =
+            std::panic::catch_unwind(|| deserialize_actions(0, "", &map, ACTION_DIVIDER)).unwrap();
         assert_eq!(
             actions,
             HashSet::from([String::from("view"), String::from("create")])
@@ -37,7 +39,8 @@ fn deserialize_actions_test() {
     match data_too_much_nesting {
         Value::Object(map) => {
             // If catch_unwind does not return error then panic as error is expected
-            if let Ok(_) = std::panic::catch_unwind(|| deserialize_actions(0, "", &map)) {
+            if std::panic::catch_unwind(|| deserialize_actions(0, "", &map, ACTION_DIVIDER)).is_ok()
+            {
                 panic!("operation should have panicked for MAX_JSON_DEPTH_ALLOWED exceeded");
             }
         }
@@ -49,12 +52,14 @@ fn deserialize_actions_test() {
     match data_short {
         Value::Object(map) => {
             // If catch_unwind does not return error then panic as error is expected
-            if let Ok(_) = std::panic::catch_unwind(|| deserialize_actions(17, "", &map)) {
+            if std::panic::catch_unwind(|| deserialize_actions(17, "", &map, ACTION_DIVIDER))
+                .is_ok()
+            {
                 panic!("operation should have panicked for MAX_JSON_DEPTH_ALLOWED exceeded");
             }
 
             // Should not panic
-            deserialize_actions(16, "", &map);
+            deserialize_actions(16, "", &map, ACTION_DIVIDER);
         }
         _ => panic!("error in test data should be a Value::Object",),
     };
@@ -69,7 +74,8 @@ fn deserialize_actions_test() {
 
     match data_not_valid1 {
         Value::Object(map) => {
-            if let Ok(_) = std::panic::catch_unwind(|| deserialize_actions(0, "", &map)) {
+            if std::panic::catch_unwind(|| deserialize_actions(0, "", &map, ACTION_DIVIDER)).is_ok()
+            {
                 panic!("operation should have panicked for wrong json");
             }
         }
@@ -77,7 +83,8 @@ fn deserialize_actions_test() {
     };
     match data_not_valid2 {
         Value::Object(map) => {
-            if let Ok(_) = std::panic::catch_unwind(|| deserialize_actions(0, "", &map)) {
+            if std::panic::catch_unwind(|| deserialize_actions(0, "", &map, ACTION_DIVIDER)).is_ok()
+            {
                 panic!("operation should have panicked for wrong json");
             }
         }
@@ -114,13 +121,19 @@ fn serialize_actions_test() {
         },
     });
 
-    assert_eq!(Value::Object(serialize_actions(&actions)), result_map);
+    assert_eq!(
+        Value::Object(serialize_actions(&actions, ACTION_DIVIDER)),
+        result_map
+    );
 
     // Test empty actions to map
     let actions: HashSet<String> = HashSet::from([]);
     let result_map = json!({});
 
-    assert_eq!(Value::Object(serialize_actions(&actions)), result_map);
+    assert_eq!(
+        Value::Object(serialize_actions(&actions, ACTION_DIVIDER)),
+        result_map
+    );
 
     // Test simple actions to map
     let actions: HashSet<String> = HashSet::from([
@@ -134,5 +147,211 @@ fn serialize_actions_test() {
         "user_view": true,
     });
 
-    assert_eq!(Value::Object(serialize_actions(&actions)), result_map);
+    assert_eq!(
+        Value::Object(serialize_actions(&actions, ACTION_DIVIDER)),
+        result_map
+    );
+}
+
+#[test]
+fn serialize_managed_actions_test() {
+    let id = Uuid::new_v4();
+    let actions = HashSet::from([String::from("view"), String::from("group:edit")]);
+
+    assert_eq!(
+        Value::Object(serialize_managed_actions(
+            Some(id),
+            &actions,
+            ACTION_DIVIDER
+        ))
+        .to_string(),
+        format!(
+            r#"{{"actions":{{"group":{{"edit":true}},"view":true}},"manager_id":"{}"}}"#,
+            id
+        )
+    );
+    assert_eq!(
+        Value::Object(serialize_managed_actions(
+            None,
+            HashSet::<String>::new(),
+            ACTION_DIVIDER
+        ))
+        .to_string(),
+        r#"{"actions":{},"manager_id":null}"#
+    );
+}
+
+#[test]
+fn deserialize_managed_actions_test() {
+    let id = Uuid::new_v4();
+    let actions = HashSet::from([String::from("view"), String::from("group:edit")]);
+    let json = Value::Object(serialize_managed_actions(
+        Some(id),
+        &actions,
+        ACTION_DIVIDER,
+    ))
+    .to_string();
+
+    assert_eq!(
+        deserialize_managed_actions(&json, ACTION_DIVIDER),
+        (Some(id), actions)
+    );
+    assert_eq!(
+        deserialize_managed_actions(
+            r#"{"manager_id": null, "actions": {"view": true}}"#,
+            ACTION_DIVIDER
+        ),
+        (None, HashSet::from([String::from("view")]))
+    );
+
+    for json in [
+        r#"{"view": true}"#,
+        r#"{"manager_id": null}"#,
+        r#"{"manager_id": "no uuid", "actions": {}}"#,
+        r#"{"manager_id": null, "actions": {"view": 1}}"#,
+        "[]",
+    ] {
+        match std::panic::catch_unwind(|| {
+            deserialize_managed_actions(json, ACTION_DIVIDER);
+        }) {
+            Ok(_) => panic!("invalid managed json should have panicked"),
+            Err(_) => (),
+        }
+    }
+}
+
+#[test]
+fn try_deserialize_actions_test() {
+    let map: Map<String, Value> =
+        serde_json::from_str(r#"{"view": true, "group": {"edit": true, "delete": false}}"#)
+            .unwrap();
+    assert_eq!(
+        try_deserialize_actions(0, "", &map, ACTION_DIVIDER),
+        Ok(HashSet::from([
+            String::from("view"),
+            String::from("group:edit")
+        ]))
+    );
+
+    let map: Map<String, Value> = serde_json::from_str(r#"{"view": 1}"#).unwrap();
+    assert_eq!(
+        try_deserialize_actions(0, "", &map, ACTION_DIVIDER).is_err(),
+        true
+    );
+
+    let map: Map<String, Value> = serde_json::from_str(r#"{"a": {"b": {"c": true}}}"#).unwrap();
+    assert_eq!(
+        try_deserialize_actions(18, "", &map, ACTION_DIVIDER).is_err(),
+        true
+    );
+}
+
+#[test]
+fn try_parse_actions_test() {
+    assert_eq!(
+        try_parse_actions(r#"{"view": true}"#, ACTION_DIVIDER),
+        Ok(HashSet::from([String::from("view")]))
+    );
+    assert_eq!(try_parse_actions("not json", ACTION_DIVIDER).is_err(), true);
+    assert_eq!(try_parse_actions("[true]", ACTION_DIVIDER).is_err(), true);
+    assert_eq!(
+        try_parse_actions(r#"{"view": "yes"}"#, ACTION_DIVIDER).is_err(),
+        true
+    );
+}
+
+#[test]
+fn is_well_formed_action_test() {
+    assert_eq!(is_well_formed_action("view", ACTION_DIVIDER), true);
+    assert_eq!(
+        is_well_formed_action("group:sub:view", ACTION_DIVIDER),
+        true
+    );
+    assert_eq!(is_well_formed_action("", ACTION_DIVIDER), false);
+    assert_eq!(is_well_formed_action(":view", ACTION_DIVIDER), false);
+    assert_eq!(is_well_formed_action("group:", ACTION_DIVIDER), false);
+    assert_eq!(is_well_formed_action("group::view", ACTION_DIVIDER), false);
+}
+
+#[test]
+fn try_deserialize_policy_actions_test() {
+    let map: Map<String, Value> = serde_json::from_str(
+        r#"{"view": true, "group": {"edit": true, "delete": false}, "admin": false}"#,
+    )
+    .unwrap();
+
+    assert_eq!(
+        try_deserialize_policy_actions(0, "", &map, ACTION_DIVIDER),
+        Ok((
+            HashSet::from([String::from("view"), String::from("group:edit")]),
+            HashSet::from([String::from("group:delete"), String::from("admin")])
+        ))
+    );
+
+    let map: Map<String, Value> = serde_json::from_str(r#"{"view": null}"#).unwrap();
+    assert_eq!(
+        try_deserialize_policy_actions(0, "", &map, ACTION_DIVIDER).is_err(),
+        true
+    );
+}
+
+#[test]
+fn parse_policy_actions_test() {
+    assert_eq!(
+        parse_policy_actions(r#"{"view": true, "edit": false}"#, ACTION_DIVIDER),
+        (
+            HashSet::from([String::from("view")]),
+            HashSet::from([String::from("edit")])
+        )
+    );
+
+    match std::panic::catch_unwind(|| {
+        parse_policy_actions("[]", ACTION_DIVIDER);
+    }) {
+        Ok(_) => panic!("invalid policy json should have panicked"),
+        Err(_) => (),
+    }
+}
+
+#[test]
+fn serialize_policy_actions_test() {
+    let allowed = HashSet::from([String::from("view"), String::from("group:edit")]);
+    let denied = HashSet::from([String::from("group:delete"), String::from("view")]);
+
+    assert_eq!(
+        Value::Object(serialize_policy_actions(&allowed, &denied, ACTION_DIVIDER)).to_string(),
+        r#"{"group":{"delete":false,"edit":true},"view":false}"#
+    );
+    // Repeated group names are supported
+    assert_eq!(
+        Value::Object(serialize_policy_actions(
+            &HashSet::from([String::from("a:a")]),
+            &HashSet::new(),
+            ACTION_DIVIDER
+        ))
+        .to_string(),
+        r#"{"a":{"a":true}}"#
+    );
+
+    // Action that is also a group, in both insertion orders
+    match std::panic::catch_unwind(|| {
+        serialize_policy_actions(
+            &HashSet::from([String::from("group")]),
+            &HashSet::from([String::from("group:view")]),
+            ACTION_DIVIDER,
+        );
+    }) {
+        Ok(_) => panic!("action that is also a group should have panicked"),
+        Err(_) => (),
+    }
+    match std::panic::catch_unwind(|| {
+        serialize_policy_actions(
+            &HashSet::from([String::from("group:view")]),
+            &HashSet::from([String::from("group")]),
+            ACTION_DIVIDER,
+        );
+    }) {
+        Ok(_) => panic!("action that is also a group should have panicked"),
+        Err(_) => (),
+    }
 }
@@ -0,0 +1,160 @@
+use crate::permission_manager::action_index::ActionIndex;
+use std::borrow::Cow;
+use std::collections::HashSet;
+use std::fmt;
+use std::sync::Arc;
+
+//// TESTS ////
+#[cfg(test)]
+mod tests;
+
+#[doc(hidden)]
+/// Actions of a [`Permission`](crate::Permission).
+///
+/// Unmanaged permissions own their action names. Managed permissions linked to the universe of their manager hold the
+/// names interned in its [`ActionIndex`](ActionIndex), so permissions sharing a vocabulary share a single allocation per action.
+/// `String` copies of interned actions are only made when requested through [`to_strings`](Self::to_strings), and are not kept.
+#[derive(Clone)]
+pub enum ActionSet {
+    Owned(HashSet<String>),
+    Interned(HashSet<Arc<str>>),
+}
+
+impl ActionSet {
+    /// Returns the actions interned in the index provided. Actions that are not in the index get their own allocation.
+    pub fn interned<I, S>(actions: I, index: &ActionIndex) -> ActionSet
+    where
+        I: IntoIterator<Item = S>,
+        S: AsRef<str>,
+    {
+        ActionSet::Interned(
+            actions
+                .into_iter()
+                .map(|action| intern(action.as_ref(), index))
+                .collect(),
+        )
+    }
+
+    /// Returns the same actions interned in the index provided.
+    pub fn into_interned(self, index: &ActionIndex) -> ActionSet {
+        match self {
+            ActionSet::Owned(actions) => ActionSet::interned(actions, index),
+            ActionSet::Interned(actions) => {
+                ActionSet::interned(actions.iter().map(|action| action.as_ref()), index)
+            }
+        }
+    }
+
+    /// Returns the actions as a set of `String`s, borrowing owned actions and copying interned ones on every call.
+    pub fn to_strings(&self) -> Cow<'_, HashSet<String>> {
+        match self {
+            ActionSet::Owned(actions) => Cow::Borrowed(actions),
+            ActionSet::Interned(actions) => Cow::Owned(
+                actions
+                    .iter()
+                    .map(|action| String::from(action.as_ref()))
+                    .collect(),
+            ),
+        }
+    }
+
+    /// Returns the number of actions.
+    pub fn len(&self) -> usize {
+        match self {
+            ActionSet::Owned(actions) => actions.len(),
+            ActionSet::Interned(actions) => actions.len(),
+        }
+    }
+
+    /// Returns an iterator over the actions, without copying them.
+    pub fn iter(&self) -> impl Iterator<Item = &str> + '_ {
+        let (owned, interned) = match self {
+            ActionSet::Owned(actions) => (Some(actions), None),
+            ActionSet::Interned(actions) => (None, Some(actions)),
+        };
+
+        owned
+            .into_iter()
+            .flatten()
+            .map(String::as_str)
+            .chain(interned.into_iter().flatten().map(|action| action.as_ref()))
+    }
+
+    /// Returns `true` if the set has the action.
+    pub fn contains(&self, action: &str) -> bool {
+        match self {
+            ActionSet::Owned(actions) => actions.contains(action),
+            ActionSet::Interned(actions) => actions.contains(action),
+        }
+    }
+
+    /// Returns `true` if the set has every action of the other one.
+    pub fn is_superset(&self, other: &ActionSet) -> bool {
+        other.iter().all(|action| self.contains(action))
+    }
+
+    /// Adds an action, interning it in the index provided if the set is interned. Returns `false` if it was already present.
+    pub fn insert(&mut self, action: &str, index: Option<&ActionIndex>) -> bool {
+        if self.contains(action) {
+            return false;
+        }
+
+        match self {
+            ActionSet::Owned(actions) => actions.insert(String::from(action)),
+            ActionSet::Interned(actions) => actions.insert(match index {
+                Some(index) => intern(action, index),
+                None => Arc::from(action),
+            }),
+        }
+    }
+
+    /// Adds every action of the other set, sharing its interned names.
+    pub fn extend(&mut self, other: &ActionSet, index: Option<&ActionIndex>) {
+        match (&mut *self, other) {
+            (ActionSet::Interned(actions), ActionSet::Interned(other)) => {
+                actions.extend(other.iter().cloned());
+            }
+            _ => {
+                for action in other.iter() {
+                    self.insert(action, index);
+                }
+            }
+        }
+    }
+
+    /// Removes an action. Returns `false` if it was not present.
+    pub fn remove(&mut self, action: &str) -> bool {
+        match self {
+            ActionSet::Owned(actions) => actions.remove(action),
+            ActionSet::Interned(actions) => actions.remove(action),
+        }
+    }
+
+    /// Keeps only the actions for which the predicate returns `true`.
+    pub fn retain<F>(&mut self, mut keep: F)
+    where
+        F: FnMut(&str) -> bool,
+    {
+        match self {
+            ActionSet::Owned(actions) => actions.retain(|action| keep(action)),
+            ActionSet::Interned(actions) => actions.retain(|action| keep(action)),
+        }
+    }
+
+    /// Removes every action.
+    pub fn clear(&mut self) {
+        self.retain(|_| false);
+    }
+}
+
+// Returns the interned name of an action in the index, or a new allocation if it is not in the index
+fn intern(action: &str, index: &ActionIndex) -> Arc<str> {
+    index.intern(action).unwrap_or_else(|| Arc::from(action))
+}
+
+// Formatted as a set of actions whatever the representation
+impl fmt::Debug for ActionSet {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_set().entries(self.iter()).finish()
+    }
+}
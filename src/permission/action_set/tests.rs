@@ -0,0 +1,65 @@
+use super::*;
+
+fn index() -> ActionIndex {
+    ActionIndex::from_actions(&HashSet::from([String::from("view"), String::from("edit")]))
+}
+
+#[test]
+fn interned_test() {
+    let index = index();
+    let actions = ActionSet::interned(["view", "other"], &index);
+
+    match &actions {
+        ActionSet::Interned(actions) => {
+            // Universe actions share the allocation of the index, others get their own
+            let view = actions.get("view").unwrap();
+            assert_eq!(Arc::ptr_eq(view, &index.intern("view").unwrap()), true);
+            assert_eq!(actions.contains("other"), true);
+        }
+        ActionSet::Owned(_) => panic!("actions should be interned"),
+    }
+    assert_eq!(actions.iter().count(), 2);
+}
+
+#[test]
+fn to_strings_test() {
+    let index = index();
+    let mut actions = ActionSet::interned(["view"], &index);
+
+    assert_eq!(*actions.to_strings(), HashSet::from([String::from("view")]));
+    assert_eq!(actions.len(), 1);
+    assert_eq!(actions.insert("edit", Some(&index)), true);
+    assert_eq!(actions.insert("edit", Some(&index)), false);
+    assert_eq!(
+        *actions.to_strings(),
+        HashSet::from([String::from("view"), String::from("edit")])
+    );
+    assert_eq!(actions.remove("view"), true);
+    assert_eq!(*actions.to_strings(), HashSet::from([String::from("edit")]));
+    actions.clear();
+    assert_eq!(actions.to_strings().is_empty(), true);
+}
+
+#[test]
+fn extend_test() {
+    let index = index();
+    let mut interned = ActionSet::interned(["view"], &index);
+    let mut owned = ActionSet::Owned(HashSet::from([String::from("view")]));
+
+    interned.extend(&ActionSet::interned(["edit"], &index), Some(&index));
+    owned.extend(&ActionSet::interned(["edit"], &index), None);
+
+    assert_eq!(interned.to_strings(), owned.to_strings());
+    assert_eq!(interned.is_superset(&owned), true);
+    assert_eq!(
+        owned.is_superset(&ActionSet::interned(["other"], &index)),
+        false
+    );
+}
+
+#[test]
+fn debug_test() {
+    let actions = ActionSet::interned(["view"], &index());
+
+    assert_eq!(format!("{:?}", actions), r#"{"view"}"#);
+}
@@ -1,12 +1,38 @@
 mod action_serialization;
+mod action_set;
+use crate::action_name_policy::ActionNamePolicy;
+use crate::action_tree::ActionTree;
+use crate::glob::glob_matches;
+use crate::manager_id::ManagerId;
+use crate::permission_builder::PermissionBuilder;
+use crate::permission_delta::PermissionDelta;
+use crate::permission_manager::action_index::ActionIndex;
+use crate::telemetry;
+use crate::validation_error::ValidationError;
+pub use action_serialization::ACTION_DIVIDER;
+pub(crate) use action_serialization::{
+    deserialize_actions, deserialize_managed_actions, is_well_formed_action, parse_policy_actions,
+    serialize_actions, serialize_policy_actions, try_deserialize_actions, try_parse_actions,
+};
+use action_set::ActionSet;
+use base64::engine::general_purpose::URL_SAFE_NO_PAD;
+use base64::Engine;
 use serde_json::Value;
-use std::collections::HashSet;
+use std::borrow::Cow;
+use std::collections::{BTreeSet, HashSet};
+use std::fmt;
+use std::marker::PhantomData;
+use std::sync::Arc;
 use uuid::Uuid;
 
 //// TESTS ////
 #[cfg(test)]
 mod tests;
 
+// Keys of the universe hash and the base64 bitset in the JWT claims of managed permissions
+pub(crate) const CLAIM_UNIVERSE_KEY: &str = "u";
+pub(crate) const CLAIM_BITS_KEY: &str = "b";
+
 /// Struct for basic [`Permission`](crate::Permission) instantiation, operation and serialization.  
 /// [`Permission`](crate::Permission)s are modeled as a simple sets of actions that a
 /// [`Permission`](crate::Permission) allows for certain subjects or objects (at your choice).  
@@ -15,6 +41,7 @@ mod tests;
 ///         - Tied to a [`PermissionManager`](crate::PermissionManager).
 ///         - Can only operate with managed permissions of the same [`PermissionManager`](crate::PermissionManager).
 ///         - Created using a [`PermissionManager`](crate::PermissionManager) instance.
+///         - Share the action names interned by the [`PermissionManager`](crate::PermissionManager) instead of owning copies.
 ///     - Unmanaged permissions:
 ///         - Not tied to a [`PermissionManager`](crate::PermissionManager).
 ///         - Can only operate with any unmanaged permission.
@@ -22,248 +49,170 @@ mod tests;
 /// Please note that, although possible, it is not advisable to use unmanaged permissions
 /// unless your permissions are not really restricted by any common structure or management.
 /// Instead your should probably use managed permissions within a [`PermissionManager`](crate::PermissionManager).
-#[derive(Clone, Debug)]
-pub struct Permission {
-    actions: HashSet<String>,
+///
+/// With the `postgres`, `sqlite` and `diesel` features a [`Permission`](crate::Permission) can be a column type of sqlx, rusqlite
+/// and Diesel models, stored in the [`to_json`](Self::to_json()) format as `JSONB` in Postgres and `TEXT` in SQLite. Loaded
+/// [`Permission`](crate::Permission)s are unmanaged, use [`PermissionManager::try_perm_from_actions`](crate::PermissionManager::try_perm_from_actions())
+/// to validate them.
+///
+/// The action type parameter defaults to `String`, the string-based API. Typed [`Permission`](crate::Permission)s over a
+/// user defined [`Action`](crate::Action) type share the operations that do not depend on it, see [`Action`](crate::Action).
+#[cfg_attr(
+    feature = "diesel",
+    derive(diesel::expression::AsExpression, diesel::deserialize::FromSqlRow),
+    diesel(sql_type = diesel::sql_types::Jsonb)
+)]
+pub struct Permission<A = String> {
+    actions: ActionSet,
     manager_id: Option<Uuid>,
+    // Universe of the manager that created the permission, used to validate in-place changes.
+    universe: Option<Arc<ActionIndex>>,
+    // Group nodes granted as a whole by the manager, which also cover the actions added beneath them after the permission
+    // was issued.
+    groups: BTreeSet<String>,
+    // Type of the actions, only used by the typed API
+    action_type: PhantomData<fn() -> A>,
 }
 
-impl Permission {
-    /// Creates a new unmanaged [`Permission`](crate::Permission) containing all the actions specified in the actions set provided.
-    ///
-    /// # Arguments
-    ///
-    /// * `actions` - HashSet of String elements, each one being a valid action for the [`Permission`](crate::Permission) created.
-    ///
-    /// # Examples:
-    ///
-    /// ```
-    /// use std::collections::HashSet;
-    /// use simple_perm_manager::Permission;
-    ///
-    /// let actions = HashSet::from([
-    ///    String::from("create"),
-    ///    String::from("view"),
-    /// ]);
-    ///
-    /// let perm = Permission::from_actions(actions.clone());
-    /// // Should print a HashSet containing the elements 'create', 'view'.
-    /// println!("Permission actions: {:#?}", perm.get_actions());
-    ///
-    /// assert_eq!(actions, *perm.get_actions());
-    /// ```
-    pub fn from_actions(actions: HashSet<String>) -> Permission {
+// Implemented by hand so cloning does not require `A: Clone`
+impl<A> Clone for Permission<A> {
+    fn clone(&self) -> Self {
         Permission {
-            actions,
-            manager_id: None,
+            actions: self.actions.clone(),
+            manager_id: self.manager_id,
+            universe: self.universe.clone(),
+            groups: self.groups.clone(),
+            action_type: PhantomData,
         }
     }
+}
 
-    #[doc(hidden)]
-    /// Creates a new [`Permission`](crate::Permission) containing all the actions specified in the actions set provided.  
-    /// If you are using [`Permission`](crate::Permission) crate without a [`PermissionManager`](crate::PermissionManager)
-    /// you probably want to use the [`from_actions`](crate::Permission::from_actions) constructor instead.
-    ///
-    /// # Arguments
-    ///
-    /// * `actions` - HashSet of String elements, each one being a valid action for the [`Permission`](crate::Permission) created.
-    /// * `manager_id` - Intended for use only by [`PermissionManager`](crate::PermissionManager) implementation.
-    /// Set it to `None` if using [`Permission`](crate::Permission) without a [`PermissionManager`](crate::PermissionManager).
-    ///
-    /// # Examples:
-    ///
-    /// ```
-    /// use std::collections::HashSet;
-    /// use simple_perm_manager::Permission;
-    ///
-    /// let actions = HashSet::from([
-    ///    String::from("create"),
-    ///    String::from("view"),
-    /// ]);
-    ///
-    /// let perm = Permission::from_actions_and_uuid(actions.clone(), None);
-    /// // Should print a HashSet containing the elements 'create', 'view'.
-    /// println!("Permission actions: {:#?}", perm.get_actions());
-    ///
-    /// assert_eq!(actions, *perm.get_actions());
-    /// ```
-    pub fn from_actions_and_uuid(actions: HashSet<String>, manager_id: Option<Uuid>) -> Permission {
-        Permission {
-            actions,
-            manager_id,
+// Returns `true` if the action is strictly beneath the group, e.g. `"building:room:edit"` beneath `"building"`
+fn is_beneath(action: &str, group: &str, separator: char) -> bool {
+    action
+        .strip_prefix(group)
+        .is_some_and(|rest| rest.starts_with(separator))
+}
+
+impl<A> Permission<A> {
+    // Returns `true` if the permission contains the action string, see `contains_action`
+    pub(crate) fn holds(&self, action_str: &str) -> bool {
+        if self.actions.contains(action_str) {
+            return true;
+        }
+
+        // Managed permissions resolve aliases and implications declared in their PermissionManager universe
+        match &self.universe {
+            Some(universe) => {
+                let folded = universe.fold(action_str);
+                let action = universe.alias(&folded).unwrap_or(&folded);
+                self.actions.contains(action)
+                    || universe.is_implied(self.actions(), action)
+                    || (universe.position(action).is_none()
+                        && self
+                            .groups
+                            .iter()
+                            .any(|group| is_beneath(action, group, universe.separator())))
+            }
+            None => false,
         }
     }
 
-    /// Creates a new [`Permission`](crate::Permission) containing all the actions specified in the actions JSON string provided.
-    ///
-    /// # Arguments
-    ///
-    /// * `actions_json` - HashSet of String elements, each one being a valid action for the [`Permission`](crate::Permission) created.
-    /// * `manager_id` - Intended for use only by [`PermissionManager`](crate::PermissionManager) implementation.
-    /// Set it to `None` if using [`Permission`](crate::Permission) without a [`PermissionManager`](crate::PermissionManager).
-    ///
-    /// # JSON actions format:
-    ///
-    /// Actions for a [`Permission`](crate::Permission) can be modeled using JSON following some rules:  
-    /// - Actions can be nested using JSON objects in order to create subgroups of actions.  
-    /// - Last actions in a subgroup must always be boolean values.
-    /// Set it to `true` to include this particular action in the [`Permission`](crate::Permission) or to `false` in order to exclude it.
-    /// This is useful in case you are using a particular configuration and want to exclude actions but keeping the full picture of actions.
-    /// - Values for an object can only be:
-    ///     - Other object for creating a group.
-    ///     - A boolean for the final action.
-    /// - Maximum object nesting for JSON objects is set to 20.
-    /// - Groups preceding the final word of the full action created are divided by colons (:).
-    ///
-    /// JSON simple actions.  
-    /// Maps to actions: 'create', 'view', 'edit' and 'delete'.
-    /// ```json
-    /// {
-    ///     "create": true,
-    ///     "view": true,
-    ///     "edit": true,
-    ///     "delete": true,
-    /// }
-    /// ```
-    ///
-    /// JSON with nested groups and actions excluded.  
-    /// Maps to actions: 'user:create', 'user:edit', 'blog:view', 'blog:edit' and 'blog:comments:delete'.  
-    /// This JSON has a nesting depth of 2 (because of blog and comments groups).
-    /// ```json
-    /// {
-    ///     "user": {
-    ///         "create": true,
-    ///         "edit": true,
-    ///         "delete": false
-    ///     },
-    ///     "blog": {
-    ///         "view": true,
-    ///         "edit": true,
-    ///         "comments":{
-    ///             "delete": true
-    ///         }
-    ///     }
-    /// }
-    /// ```
-    ///
-    /// # Examples:
-    ///
-    /// Simple actions in JSON.
-    /// ```
-    /// use simple_perm_manager::Permission;
-    ///
-    /// let simple_actions_json = String::from(r#"{
-    ///     "create": true,
-    ///     "view": true
-    /// }"#);
-    ///
-    /// let perm = Permission::from_json(&simple_actions_json);
-    /// // Should print a HashSet containing the elements 'create', 'view'.
-    /// println!("Permission actions: {:#?}", perm.get_actions());
-    /// ```
-    ///
-    /// More complex actions JSON.
-    /// ```
-    /// use simple_perm_manager::Permission;
-    ///
-    /// let actions_json = String::from(r#"{
-    ///     "user": {
-    ///         "create": true,
-    ///         "edit": true
-    ///     },
-    ///     "blog": {
-    ///         "view": true
-    ///     }
-    /// }"#);
-    ///
-    /// let perm = Permission::from_json(&actions_json);
-    /// // Should print a HashSet containing the elements 'user:create', 'user:edit' and 'blog:view'.
-    /// println!("Permission actions: {:#?}", perm.get_actions());
-    /// ```
-    ///
-    /// # Panics:
-    ///
-    /// - Panics if `actions_json` argument is not valid JSON string.
-    /// - Panics if `actions_json` argument is not valid format for Permission actions.
-    /// - Panics if `actions_json` argument is s JSON with objects nested to a depth of more than 20.
-    pub fn from_json(actions_json: &str) -> Permission {
-        Permission::from_json_and_uuid(actions_json, None)
+    // Returns `true` if the action is one of the actions of the permission, without resolving aliases, implications or groups
+    pub(crate) fn has_action(&self, action: &str) -> bool {
+        self.actions.contains(action)
     }
 
-    #[doc(hidden)]
-    /// Creates a new [`Permission`](crate::Permission) containing all the actions specified in the actions JSON string provided.  
-    /// If you are using [`Permission`](crate::Permission) crate without a [`PermissionManager`](crate::PermissionManager)
-    /// you probably want to use the [`from_json`](crate::Permission::from_json) constructor instead.  
-    /// JSON actions format and examples in [`from_json`](crate::Permission::from_json) applies to  this method as well.
-    ///
-    /// # Arguments
-    ///
-    /// * `actions_json` - HashSet of String elements, each one being a valid action for the [`Permission`](crate::Permission) created.
-    /// * `manager_id` - Intended for use only by [`PermissionManager`](crate::PermissionManager) implementation.
-    /// Set it to `None` if using [`Permission`](crate::Permission) without a [`PermissionManager`](crate::PermissionManager).
-    ///
-    /// # Panics:
-    ///
-    /// - Panics if `actions_json` argument is not valid JSON string.
-    /// - Panics if `actions_json` argument is not valid format for Permission actions.
-    /// - Panics if `actions_json` argument is s JSON with objects nested to a depth of more than 20.
-    pub fn from_json_and_uuid(actions_json: &str, manager_id: Option<Uuid>) -> Permission {
-        let actions_generated: HashSet<String>;
-        let actions_value: Value =
-            serde_json::from_str(actions_json).expect("wrong format in permission json string");
+    // Returns the number of actions of the permission
+    pub(crate) fn len(&self) -> usize {
+        self.actions.len()
+    }
 
-        match actions_value {
-            Value::Object(map) => {
-                actions_generated = action_serialization::deserialize_actions(0, "", &map);
-            }
-            // This will never be reached as Values returned from a serde 'from_str' will always be Object
-            _ => panic!("wrong format in permission json string"),
+    // Returns `true` if both permissions have the same actions, whatever their managers
+    pub(crate) fn has_same_actions<B>(&self, other: &Permission<B>) -> bool {
+        self.actions.len() == other.actions.len() && self.actions.is_superset(&other.actions)
+    }
+
+    // Changes the action type of the permission, keeping its actions, manager, universe and groups
+    pub(crate) fn retyped<B>(self) -> Permission<B> {
+        Permission {
+            actions: self.actions,
+            manager_id: self.manager_id,
+            universe: self.universe,
+            groups: self.groups,
+            action_type: PhantomData,
         }
+    }
 
+    // Returns `true` if the group or one of its ancestors was granted as a whole to the permission.
+    fn has_group(&self, group: &str) -> bool {
+        let separator = self.separator();
+        self.groups
+            .iter()
+            .any(|granted| granted == group || is_beneath(group, granted, separator))
+    }
+
+    // Returns the separator of the linked universe, or the default one for permissions without a linked universe.
+    pub(crate) fn separator(&self) -> char {
+        self.universe
+            .as_ref()
+            .map_or(ACTION_DIVIDER, |universe| universe.separator())
+    }
+
+    // Returns a permission with the same manager (and universe link) as self and the actions provided.
+    fn with_actions<I, S>(&self, actions: I) -> Permission<A>
+    where
+        I: IntoIterator<Item = S>,
+        S: AsRef<str>,
+    {
         Permission {
-            actions: actions_generated,
-            manager_id,
+            actions: match &self.universe {
+                Some(universe) => ActionSet::interned(actions, universe),
+                None => ActionSet::Owned(
+                    actions
+                        .into_iter()
+                        .map(|action| String::from(action.as_ref()))
+                        .collect(),
+                ),
+            },
+            manager_id: self.manager_id,
+            universe: self.universe.clone(),
+            groups: BTreeSet::new(),
+            action_type: PhantomData,
         }
     }
 
-    /// Returns [`Permission`](crate::Permission) actions in a JSON formatted string.
+    /// Returns an iterator over the [`Permission`](crate::Permission) actions in no particular order.  
+    /// Managed [`Permission`](crate::Permission)s share the action names interned by their [`PermissionManager`](crate::PermissionManager),
+    /// and [`get_actions`](Self::get_actions()) copies them into `String`s on every call, so prefer this method for reading
+    /// the actions of managed [`Permission`](crate::Permission)s.
     ///
     /// # Examples:
     ///
     /// ```
-    /// use std::collections::HashSet;
-    /// use simple_perm_manager::Permission;
+    /// use simple_perm_manager::PermissionManager;
     ///
-    /// let perm = Permission::from_actions_and_uuid(HashSet::from([
-    ///    String::from("create"),
-    ///    String::from("view"),
-    /// ]), None);
+    /// let manager = PermissionManager::from_json(r#"{"create": true, "view": true}"#);
+    /// let perm = manager.perm_from_json(r#"{"view": true}"#);
     ///
-    /// // Should print a JSON string like "{"create": true, "view": true}".
-    /// println!("Permission actions as JSON: {:#?}", perm.to_json());
+    /// assert_eq!(perm.actions().collect::<Vec<&str>>(), vec!["view"]);
     /// ```
-    pub fn to_json(&self) -> String {
-        Value::Object(action_serialization::serialize_actions(self.get_actions())).to_string()
+    pub fn actions(&self) -> impl Iterator<Item = &str> + '_ {
+        self.actions.iter()
     }
 
-    /// Returns the [`Permission`](crate::Permission) actions.
+    /// Returns `true` if the [`Permission`](crate::Permission) has no actions.
     ///
     /// # Examples:
     ///
     /// ```
-    /// use std::collections::HashSet;
     /// use simple_perm_manager::Permission;
     ///
-    /// let perm = Permission::from_actions_and_uuid(HashSet::from([
-    ///    String::from("create"),
-    ///    String::from("view"),
-    /// ]), None);
-    ///
-    /// // Should print an HashSet containing the elements 'create', and 'view'.
-    /// println!("Permission actions as JSON: {:#?}", perm.get_actions());
+    /// assert!(Permission::from_json("{}").is_empty());
+    /// assert!(!Permission::from_json(r#"{"view": true}"#).is_empty());
     /// ```
-    pub fn get_actions(&self) -> &HashSet<String> {
-        &self.actions
+    pub fn is_empty(&self) -> bool {
+        self.actions().next().is_none()
     }
 
     /// Returns `true` if the [`Permission`](crate::Permission) is managed.  
@@ -297,6 +246,23 @@ impl Permission {
         self.manager_id.is_some()
     }
 
+    /// Returns the [`ManagerId`](crate::ManagerId) of the [`PermissionManager`](crate::PermissionManager) managing the
+    /// [`Permission`](crate::Permission) or `None` if the [`Permission`](crate::Permission) is unmanaged.
+    ///
+    /// # Examples:
+    ///
+    /// ```
+    /// use simple_perm_manager::{Permission, PermissionManager};
+    ///
+    /// let manager = PermissionManager::from_json(r#"{"create": true}"#);
+    ///
+    /// assert_eq!(manager.perm_from_json(r#"{"create": true}"#).manager_id(), Some(manager.id()));
+    /// assert_eq!(Permission::from_json(r#"{"create": true}"#).manager_id(), None);
+    /// ```
+    pub fn manager_id(&self) -> Option<ManagerId> {
+        self.manager_id.map(ManagerId::new)
+    }
+
     /// Returns `true` if the [`Permission`](crate::Permission) calling the method has the same
     /// [`PermissionManager`](crate::PermissionManager) as the [`Permission`](crate::Permission) used as argument.
     ///
@@ -323,7 +289,7 @@ impl Permission {
     /// assert!(!managed_perm_foo.has_same_manager(&managed_perm_bar));
     /// assert!(!managed_perm_foo.has_same_manager(&unmanaged_perm));
     /// ```
-    pub fn has_same_manager(&self, other: &Permission) -> bool {
+    pub fn has_same_manager(&self, other: &Permission<A>) -> bool {
         self.manager_id.eq(&other.manager_id)
     }
 
@@ -377,18 +343,15 @@ impl Permission {
     /// // This lines of code panics
     /// let panics = managed_perm.union(&unmanaged_perm);
     /// ```
-    pub fn union(&self, other: &Permission) -> Permission {
+    pub fn union(&self, other: &Permission<A>) -> Permission<A> {
         if !self.has_same_manager(other) {
             panic!("Permissions in union operation do not have same manager");
         }
 
-        let actions_union: HashSet<String> = self
-            .get_actions()
-            .union(other.get_actions())
-            .cloned()
-            .collect();
+        let mut union = self.clone();
+        union.union_with(other);
 
-        Permission::from_actions_and_uuid(actions_union, self.manager_id)
+        union
     }
 
     /// Returns a [`Permission`](crate::Permission) containing the actions that are in the calling
@@ -439,18 +402,48 @@ impl Permission {
     /// // This lines of code panics
     /// let panics = managed_perm.difference(&unmanaged_perm);
     /// ```
-    pub fn difference(&self, other: &Permission) -> Permission {
+    pub fn difference(&self, other: &Permission<A>) -> Permission<A> {
         if !self.has_same_manager(other) {
             panic!("Permissions in difference operation do not have same manager");
         }
 
-        let actions_diff: HashSet<String> = self
-            .get_actions()
-            .difference(other.get_actions())
-            .cloned()
-            .collect();
+        self.with_actions(
+            self.actions()
+                .filter(|action| !other.actions.contains(action)),
+        )
+    }
+
+    /// Adds to the calling [`Permission`](crate::Permission) all the actions in the [`Permission`](crate::Permission)
+    /// used as argument, modifying it in place instead of creating a new [`Permission`](crate::Permission)
+    /// like [`union`](crate::Permission::union()) does.
+    ///
+    /// # Examples:
+    ///
+    /// ```
+    /// use std::collections::HashSet;
+    /// use simple_perm_manager::PermissionManager;
+    ///
+    /// let manager = PermissionManager::from_json(r#"{"create": true, "view": true, "edit": true}"#);
+    ///
+    /// let mut perm = manager.perm_from_json(r#"{"create": true}"#);
+    /// perm.union_with(&manager.perm_from_json(r#"{"view": true}"#));
+    /// perm.union_with(&manager.perm_from_json(r#"{"edit": true}"#));
+    ///
+    /// assert_eq!(perm.get_actions(), manager.get_universe().get_actions());
+    /// ```
+    ///
+    /// # Panics:
+    ///
+    /// Panics if the calling [`Permission`](crate::Permission) and the [`Permission`](crate::Permission)
+    /// used as argument do not have the same [`PermissionManager`](crate::PermissionManager).
+    pub fn union_with(&mut self, other: &Permission<A>) {
+        if !self.has_same_manager(other) {
+            panic!("Permissions in union operation do not have same manager");
+        }
 
-        Permission::from_actions_and_uuid(actions_diff, self.manager_id)
+        self.actions
+            .extend(&other.actions, self.universe.as_deref());
+        self.groups.extend(other.groups.iter().cloned());
     }
 
     /// Returns `true` if the [`Permission`](crate::Permission) calling contains at least
@@ -498,35 +491,1033 @@ impl Permission {
     /// // This lines of code panics
     /// let panics = managed_perm.contains(&unmanaged_perm);
     /// ```
-    pub fn contains(&self, other: &Permission) -> bool {
+    pub fn contains(&self, other: &Permission<A>) -> bool {
         if !self.has_same_manager(other) {
             panic!("Permissions in contains operation do not have same manager");
         }
 
-        return self.get_actions().is_superset(other.get_actions());
+        self.actions.is_superset(&other.actions)
     }
+}
 
-    /// Returns `true` if the [`Permission`](crate::Permission) calling contains the action used as argument.
+impl Permission {
+    /// Returns a [`PermissionBuilder`](crate::PermissionBuilder) for creating an unmanaged [`Permission`](crate::Permission)
+    /// adding actions one by one or by groups.  
+    /// Use [`PermissionManager::perm_builder`](crate::PermissionManager::perm_builder()) for a managed one.
     ///
     /// # Examples:
     ///
     /// ```
+    /// use std::collections::HashSet;
     /// use simple_perm_manager::Permission;
-    /// use simple_perm_manager::PermissionManager;
-    ///
-    /// let manager = PermissionManager::from_json(&String::from(r#"{
-    ///     "create": true,
-    ///     "view": true,
-    ///     "edit": true
-    /// }"#));
     ///
-    /// // Create Permission
-    /// let perm = manager.perm_from_json(&String::from(r#"{"create": true, "view": true}"#));
+    /// let perm = Permission::builder()
+    ///     .action("building:view")
+    ///     .group("user", ["create", "edit"])
+    ///     .build();
     ///
-    /// assert!(perm.contains_action("create"));
-    /// assert!(!perm.contains_action("other"));
+    /// assert_eq!(*perm.get_actions(), HashSet::from([
+    ///     String::from("building:view"),
+    ///     String::from("user:create"),
+    ///     String::from("user:edit"),
+    /// ]));
     /// ```
-    pub fn contains_action(&self, action_str: &str) -> bool {
-        self.get_actions().contains(action_str)
+    pub fn builder() -> PermissionBuilder<'static> {
+        PermissionBuilder::new()
+    }
+
+    /// Creates a new unmanaged [`Permission`](crate::Permission) containing all the actions specified in the actions set provided.
+    ///
+    /// # Arguments
+    ///
+    /// * `actions` - HashSet of String elements, each one being a valid action for the [`Permission`](crate::Permission) created.
+    ///
+    /// # Examples:
+    ///
+    /// ```
+    /// use std::collections::HashSet;
+    /// use simple_perm_manager::Permission;
+    ///
+    /// let actions = HashSet::from([
+    ///    String::from("create"),
+    ///    String::from("view"),
+    /// ]);
+    ///
+    /// let perm = Permission::from_actions(actions.clone());
+    /// // Should print a HashSet containing the elements 'create', 'view'.
+    /// println!("Permission actions: {:#?}", perm.get_actions());
+    ///
+    /// assert_eq!(actions, *perm.get_actions());
+    /// ```
+    pub fn from_actions(actions: HashSet<String>) -> Permission {
+        Permission {
+            actions: ActionSet::Owned(actions),
+            manager_id: None,
+            universe: None,
+            groups: BTreeSet::new(),
+            action_type: PhantomData,
+        }
+    }
+
+    /// Same as [`from_actions`](Self::from_actions()) but returns a [`ValidationError::MalformedActions`](crate::ValidationError::MalformedActions)
+    /// listing the actions that do not follow the [`ActionNamePolicy`](crate::ActionNamePolicy) provided.
+    ///
+    /// # Examples:
+    ///
+    /// ```
+    /// use std::collections::HashSet;
+    /// use simple_perm_manager::{ActionNamePolicy, Permission, ValidationError};
+    ///
+    /// let policy = ActionNamePolicy::identifiers();
+    /// assert!(Permission::try_from_actions(HashSet::from([String::from("user:view")]), &policy).is_ok());
+    /// assert_eq!(
+    ///     Permission::try_from_actions(HashSet::from([String::from("user:view all")]), &policy).unwrap_err(),
+    ///     ValidationError::MalformedActions(vec![String::from("user:view all")])
+    /// );
+    /// ```
+    pub fn try_from_actions(
+        actions: HashSet<String>,
+        name_policy: &ActionNamePolicy,
+    ) -> Result<Permission, ValidationError> {
+        let rejected = name_policy.rejected(&actions, ACTION_DIVIDER);
+        if !rejected.is_empty() {
+            return Err(ValidationError::MalformedActions(rejected));
+        }
+
+        Ok(Permission::from_actions(actions))
+    }
+
+    #[doc(hidden)]
+    /// Creates a new [`Permission`](crate::Permission) containing all the actions specified in the actions set provided.  
+    /// If you are using [`Permission`](crate::Permission) crate without a [`PermissionManager`](crate::PermissionManager)
+    /// you probably want to use the [`from_actions`](crate::Permission::from_actions) constructor instead.
+    ///
+    /// # Arguments
+    ///
+    /// * `actions` - HashSet of String elements, each one being a valid action for the [`Permission`](crate::Permission) created.
+    /// * `manager_id` - Intended for use only by [`PermissionManager`](crate::PermissionManager) implementation.
+    ///   Set it to `None` if using [`Permission`](crate::Permission) without a [`PermissionManager`](crate::PermissionManager).
+    ///
+    /// # Examples:
+    ///
+    /// ```
+    /// use std::collections::HashSet;
+    /// use simple_perm_manager::Permission;
+    ///
+    /// let actions = HashSet::from([
+    ///    String::from("create"),
+    ///    String::from("view"),
+    /// ]);
+    ///
+    /// let perm = Permission::from_actions_and_uuid(actions.clone(), None);
+    /// // Should print a HashSet containing the elements 'create', 'view'.
+    /// println!("Permission actions: {:#?}", perm.get_actions());
+    ///
+    /// assert_eq!(actions, *perm.get_actions());
+    /// ```
+    pub fn from_actions_and_uuid(actions: HashSet<String>, manager_id: Option<Uuid>) -> Permission {
+        Permission {
+            actions: ActionSet::Owned(actions),
+            manager_id,
+            universe: None,
+            groups: BTreeSet::new(),
+            action_type: PhantomData,
+        }
+    }
+
+    /// Creates a new [`Permission`](crate::Permission) containing all the actions specified in the actions JSON string provided.
+    ///
+    /// # Arguments
+    ///
+    /// * `actions_json` - HashSet of String elements, each one being a valid action for the [`Permission`](crate::Permission) created.
+    /// * `manager_id` - Intended for use only by [`PermissionManager`](crate::PermissionManager) implementation.
+    ///   Set it to `None` if using [`Permission`](crate::Permission) without a [`PermissionManager`](crate::PermissionManager).
+    ///
+    /// # JSON actions format:
+    ///
+    /// Actions for a [`Permission`](crate::Permission) can be modeled using JSON following some rules:  
+    /// - Actions can be nested using JSON objects in order to create subgroups of actions.  
+    /// - Last actions in a subgroup must always be boolean values.
+    ///   Set it to `true` to include this particular action in the [`Permission`](crate::Permission) or to `false` in order to exclude it.
+    ///   This is useful in case you are using a particular configuration and want to exclude actions but keeping the full picture of actions.
+    /// - Values for an object can only be:
+    ///     - Other object for creating a group.
+    ///     - A boolean for the final action.
+    /// - Maximum object nesting for JSON objects is set to 20.
+    /// - Groups preceding the final word of the full action created are divided by colons (:).
+    ///
+    /// JSON simple actions.  
+    /// Maps to actions: 'create', 'view', 'edit' and 'delete'.
+    /// ```json
+    /// {
+    ///     "create": true,
+    ///     "view": true,
+    ///     "edit": true,
+    ///     "delete": true,
+    /// }
+    /// ```
+    ///
+    /// JSON with nested groups and actions excluded.  
+    /// Maps to actions: 'user:create', 'user:edit', 'blog:view', 'blog:edit' and 'blog:comments:delete'.  
+    /// This JSON has a nesting depth of 2 (because of blog and comments groups).
+    /// ```json
+    /// {
+    ///     "user": {
+    ///         "create": true,
+    ///         "edit": true,
+    ///         "delete": false
+    ///     },
+    ///     "blog": {
+    ///         "view": true,
+    ///         "edit": true,
+    ///         "comments":{
+    ///             "delete": true
+    ///         }
+    ///     }
+    /// }
+    /// ```
+    ///
+    /// # Examples:
+    ///
+    /// Simple actions in JSON.
+    /// ```
+    /// use simple_perm_manager::Permission;
+    ///
+    /// let simple_actions_json = String::from(r#"{
+    ///     "create": true,
+    ///     "view": true
+    /// }"#);
+    ///
+    /// let perm = Permission::from_json(&simple_actions_json);
+    /// // Should print a HashSet containing the elements 'create', 'view'.
+    /// println!("Permission actions: {:#?}", perm.get_actions());
+    /// ```
+    ///
+    /// More complex actions JSON.
+    /// ```
+    /// use simple_perm_manager::Permission;
+    ///
+    /// let actions_json = String::from(r#"{
+    ///     "user": {
+    ///         "create": true,
+    ///         "edit": true
+    ///     },
+    ///     "blog": {
+    ///         "view": true
+    ///     }
+    /// }"#);
+    ///
+    /// let perm = Permission::from_json(&actions_json);
+    /// // Should print a HashSet containing the elements 'user:create', 'user:edit' and 'blog:view'.
+    /// println!("Permission actions: {:#?}", perm.get_actions());
+    /// ```
+    ///
+    /// # Panics:
+    ///
+    /// - Panics if `actions_json` argument is not valid JSON string.
+    /// - Panics if `actions_json` argument is not valid format for Permission actions.
+    /// - Panics if `actions_json` argument is s JSON with objects nested to a depth of more than 20.
+    pub fn from_json(actions_json: &str) -> Permission {
+        Permission::from_json_and_uuid(actions_json, None)
+    }
+
+    /// Same as [`from_json`](Self::from_json()) but returns a [`ValidationError`](crate::ValidationError) instead of panicking, and a
+    /// [`ValidationError::MalformedActions`](crate::ValidationError::MalformedActions) listing the actions that do not follow the
+    /// [`ActionNamePolicy`](crate::ActionNamePolicy) provided.
+    ///
+    /// # Examples:
+    ///
+    /// ```
+    /// use simple_perm_manager::{ActionNamePolicy, Permission};
+    ///
+    /// let policy = ActionNamePolicy::new().with_max_length(10);
+    /// assert!(Permission::try_from_json(r#"{"user": {"view": true}}"#, &policy).is_ok());
+    /// assert!(Permission::try_from_json(r#"{"user": {"view_everything": true}}"#, &policy).is_err());
+    /// assert!(Permission::try_from_json(r#"{"user": "yes"}"#, &policy).is_err());
+    /// ```
+    pub fn try_from_json(
+        actions_json: &str,
+        name_policy: &ActionNamePolicy,
+    ) -> Result<Permission, ValidationError> {
+        let actions = try_parse_actions(actions_json, ACTION_DIVIDER).map_err(|error| {
+            telemetry::record_parse_error();
+            ValidationError::InvalidJson(error)
+        })?;
+
+        Permission::try_from_actions(actions, name_policy)
+    }
+
+    #[doc(hidden)]
+    /// Creates a new [`Permission`](crate::Permission) containing all the actions specified in the actions JSON string provided.  
+    /// If you are using [`Permission`](crate::Permission) crate without a [`PermissionManager`](crate::PermissionManager)
+    /// you probably want to use the [`from_json`](crate::Permission::from_json) constructor instead.  
+    /// JSON actions format and examples in [`from_json`](crate::Permission::from_json) applies to  this method as well.
+    ///
+    /// # Arguments
+    ///
+    /// * `actions_json` - HashSet of String elements, each one being a valid action for the [`Permission`](crate::Permission) created.
+    /// * `manager_id` - Intended for use only by [`PermissionManager`](crate::PermissionManager) implementation.
+    ///   Set it to `None` if using [`Permission`](crate::Permission) without a [`PermissionManager`](crate::PermissionManager).
+    ///
+    /// # Panics:
+    ///
+    /// - Panics if `actions_json` argument is not valid JSON string.
+    /// - Panics if `actions_json` argument is not valid format for Permission actions.
+    /// - Panics if `actions_json` argument is s JSON with objects nested to a depth of more than 20.
+    pub fn from_json_and_uuid(actions_json: &str, manager_id: Option<Uuid>) -> Permission {
+        let actions_value: Value =
+            serde_json::from_str(actions_json).expect("wrong format in permission json string");
+
+        let actions_generated: HashSet<String> = match actions_value {
+            Value::Object(map) => {
+                action_serialization::deserialize_actions(0, "", &map, ACTION_DIVIDER)
+            }
+            // This will never be reached as Values returned from a serde 'from_str' will always be Object
+            _ => panic!("wrong format in permission json string"),
+        };
+
+        Permission {
+            actions: ActionSet::Owned(actions_generated),
+            manager_id,
+            universe: None,
+            groups: BTreeSet::new(),
+            action_type: PhantomData,
+        }
+    }
+
+    // Links the permission to the universe of its manager so in-place changes can be validated, interning its actions in the
+    // universe index.
+    pub(crate) fn with_universe(mut self, universe: Arc<ActionIndex>) -> Permission {
+        self.actions = self.actions.into_interned(&universe);
+        self.universe = Some(universe);
+        self
+    }
+
+    // Returns the permission with the group nodes granted as a whole, see `groups`.
+    pub(crate) fn with_groups(mut self, groups: BTreeSet<String>) -> Permission {
+        self.groups = groups;
+        self
+    }
+
+    // Returns the group nodes granted as a whole to the permission.
+    pub(crate) fn granted_groups(&self) -> &BTreeSet<String> {
+        &self.groups
+    }
+
+    /// Returns [`Permission`](crate::Permission) actions in a JSON formatted string.
+    ///
+    /// # Examples:
+    ///
+    /// ```
+    /// use std::collections::HashSet;
+    /// use simple_perm_manager::Permission;
+    ///
+    /// let perm = Permission::from_actions_and_uuid(HashSet::from([
+    ///    String::from("create"),
+    ///    String::from("view"),
+    /// ]), None);
+    ///
+    /// // Should print a JSON string like "{"create": true, "view": true}".
+    /// println!("Permission actions as JSON: {:#?}", perm.to_json());
+    /// ```
+    pub fn to_json(&self) -> String {
+        Value::Object(action_serialization::serialize_actions(
+            self.actions(),
+            self.separator(),
+        ))
+        .to_string()
+    }
+
+    /// Returns [`Permission`](crate::Permission) actions and the [`ManagerId`](crate::ManagerId) of its manager in a JSON formatted string.  
+    /// The JSON has the format `{"actions": {...}, "manager_id": "<uuid>"}`, where `actions` uses the same format as [`to_json`](Self::to_json())
+    /// and `manager_id` is `null` for unmanaged [`Permission`](crate::Permission)s.
+    ///
+    /// A [`Permission`](crate::Permission) serialized this way can be re-linked to its manager with
+    /// [`PermissionManager::adopt_json`](crate::PermissionManager::adopt_json()) or [`ManagerRegistry::adopt_json`](crate::ManagerRegistry::adopt_json()).
+    ///
+    /// # Examples:
+    ///
+    /// ```
+    /// use simple_perm_manager::PermissionManager;
+    ///
+    /// let manager = PermissionManager::from_json(r#"{"create": true, "view": true}"#);
+    /// let perm = manager.perm_from_json(r#"{"view": true}"#);
+    ///
+    /// let json = perm.to_managed_json();
+    /// assert_eq!(json, format!(r#"{{"actions":{{"view":true}},"manager_id":"{}"}}"#, manager.id()));
+    ///
+    /// let adopted = manager.adopt_json(&json);
+    /// assert!(adopted.has_same_manager(&perm));
+    /// ```
+    pub fn to_managed_json(&self) -> String {
+        Value::Object(action_serialization::serialize_managed_actions(
+            self.manager_id,
+            self.actions(),
+            self.separator(),
+        ))
+        .to_string()
+    }
+
+    /// Returns the [`Permission`](crate::Permission) as a compact JWT claim value, so tokens can carry the permissions of their holder:
+    ///
+    /// - Managed [`Permission`](crate::Permission)s: an object `{"u": "<universe hash>", "b": "<bitset>"}` with the
+    ///   [`universe_hash`](crate::PermissionManager::universe_hash()) of the manager and the bitset of
+    ///   [`PermissionManager::perm_to_token`](crate::PermissionManager::perm_to_token()). Decode it with
+    ///   [`PermissionManager::perm_from_jwt_claim`](crate::PermissionManager::perm_from_jwt_claim()).
+    /// - Unmanaged [`Permission`](crate::Permission)s: a scope string with the actions sorted and separated by spaces, e.g.
+    ///   `"post:edit post:view"`.
+    ///
+    /// The claim is not signed, sign the token carrying it with a JWT library.
+    ///
+    /// # Examples:
+    ///
+    /// ```
+    /// use simple_perm_manager::{Permission, PermissionManager};
+    ///
+    /// let perm = Permission::from_json(r#"{"post": {"view": true, "edit": true}}"#);
+    /// assert_eq!(perm.to_jwt_claim(), "post:edit post:view");
+    ///
+    /// let manager = PermissionManager::from_json(r#"{"post": {"view": true, "edit": true}}"#);
+    /// let claim = manager.perm_from_json(r#"{"post": {"view": true}}"#).to_jwt_claim();
+    /// assert_eq!(claim["u"], manager.universe_hash());
+    /// assert_eq!(claim["b"], "Ag");
+    /// ```
+    ///
+    /// # Panics:
+    ///
+    /// Panics if the [`Permission`](crate::Permission) is unmanaged and any action contains whitespace.
+    pub fn to_jwt_claim(&self) -> Value {
+        if let Some(universe) = &self.universe {
+            let mut claim = serde_json::Map::new();
+            claim.insert(
+                String::from(CLAIM_UNIVERSE_KEY),
+                Value::String(universe.fingerprint()),
+            );
+            claim.insert(
+                String::from(CLAIM_BITS_KEY),
+                Value::String(URL_SAFE_NO_PAD.encode(universe.encode(self.actions()))),
+            );
+            return Value::Object(claim);
+        }
+
+        let mut actions: Vec<&str> = self.actions().collect();
+        if actions
+            .iter()
+            .any(|action| action.contains(char::is_whitespace))
+        {
+            panic!("Permission actions with whitespace can not be in a scope claim");
+        }
+        actions.sort();
+
+        Value::String(actions.join(" "))
+    }
+
+    /// Returns an unmanaged [`Permission`](crate::Permission) from a scope claim created with [`to_jwt_claim`](Self::to_jwt_claim()):
+    /// a string of actions separated by whitespace.
+    ///
+    /// # Examples:
+    ///
+    /// ```
+    /// use serde_json::json;
+    /// use simple_perm_manager::Permission;
+    ///
+    /// let perm = Permission::from_jwt_claim(&json!("post:view post:edit")).unwrap();
+    /// assert!(perm.contains_action("post:edit"));
+    /// ```
+    ///
+    /// # Errors:
+    ///
+    /// - Returns [`ValidationError::InvalidJson`](crate::ValidationError::InvalidJson) if the claim is not a string, e.g. the bitset
+    ///   claim of a managed [`Permission`](crate::Permission), which needs
+    ///   [`PermissionManager::perm_from_jwt_claim`](crate::PermissionManager::perm_from_jwt_claim()).
+    /// - Returns [`ValidationError::MalformedActions`](crate::ValidationError::MalformedActions) if any action has empty groups.
+    pub fn from_jwt_claim(claim: &Value) -> Result<Permission, ValidationError> {
+        match claim {
+            Value::String(scope) => Permission::try_from_actions(
+                scope.split_whitespace().map(String::from).collect(),
+                &ActionNamePolicy::new(),
+            ),
+            Value::Object(_) => {
+                telemetry::record_parse_error();
+                Err(ValidationError::InvalidJson(String::from(
+                    "wrong format in permission claim - bitset claims must be decoded with a PermissionManager",
+                )))
+            }
+            _ => {
+                telemetry::record_parse_error();
+                Err(ValidationError::InvalidJson(String::from(
+                    "wrong format in permission claim",
+                )))
+            }
+        }
+    }
+
+    /// Returns a [`Permission`](crate::Permission) with the same manager and the actions of this one with every `from` separator
+    /// replaced by `to`, e.g. for migrating stored dot-separated actions to the default [`ACTION_DIVIDER`](crate::ACTION_DIVIDER).  
+    /// The actions are not validated: use [`PermissionManager::validate_perm`](crate::PermissionManager::validate_perm()) on
+    /// managed [`Permission`](crate::Permission)s or [`PermissionManager::accept_separator`](crate::PermissionManager::accept_separator())
+    /// to validate while loading.
+    ///
+    /// # Examples:
+    ///
+    /// ```
+    /// use std::collections::HashSet;
+    /// use simple_perm_manager::Permission;
+    ///
+    /// let legacy = Permission::from_actions(HashSet::from([String::from("building.view"), String::from("user:edit")]));
+    /// let perm = legacy.normalize_separator('.', ':');
+    ///
+    /// assert!(perm.contains_action("building:view"));
+    /// assert!(perm.contains_action("user:edit"));
+    /// assert_eq!(perm.to_json(), r#"{"building":{"view":true},"user":{"edit":true}}"#);
+    /// ```
+    pub fn normalize_separator(&self, from: char, to: char) -> Permission {
+        self.with_actions(
+            self.actions()
+                .map(|action| action.replace(from, &to.to_string())),
+        )
+    }
+
+    /// Returns the actions of the [`Permission`](crate::Permission) matching a glob pattern, sorted.  
+    /// The pattern is divided in groups by the separator of the [`PermissionManager`](crate::PermissionManager) of the
+    /// [`Permission`](crate::Permission), or [`ACTION_DIVIDER`](crate::ACTION_DIVIDER) for unmanaged ones, and has the format
+    /// explained in [`PermissionManager::actions_matching`](crate::PermissionManager::actions_matching()).
+    ///
+    /// # Examples:
+    ///
+    /// ```
+    /// use simple_perm_manager::Permission;
+    ///
+    /// let perm = Permission::from_json(r#"{"building": {"view": true, "room": {"view": true, "edit": true}}}"#);
+    ///
+    /// assert_eq!(perm.actions_matching("**:view"), vec!["building:room:view", "building:view"]);
+    /// assert_eq!(perm.actions_matching("building:r*:e??t"), vec!["building:room:edit"]);
+    /// ```
+    pub fn actions_matching(&self, glob: &str) -> Vec<String> {
+        let separator = self.separator();
+        let mut actions: Vec<String> = self
+            .actions()
+            .filter(|action| glob_matches(glob, action, separator))
+            .map(String::from)
+            .collect();
+        actions.sort();
+
+        actions
+    }
+
+    /// Returns a [`Permission`](crate::Permission) with the same manager and only the actions of this one beneath a group,
+    /// e.g. for handing a module only its own slice of a [`Permission`](crate::Permission).  
+    /// Use [`subtree_rerooted`](Self::subtree_rerooted()) to remove the group prefix from the actions.
+    ///
+    /// # Examples:
+    ///
+    /// ```
+    /// use std::collections::HashSet;
+    /// use simple_perm_manager::Permission;
+    ///
+    /// let perm = Permission::from_json(r#"{"building": {"view": true, "room": {"edit": true}}, "user": {"view": true}}"#);
+    ///
+    /// assert_eq!(
+    ///     *perm.subtree("building").get_actions(),
+    ///     HashSet::from([String::from("building:view"), String::from("building:room:edit")])
+    /// );
+    /// assert_eq!(*perm.subtree("building:room").get_actions(), HashSet::from([String::from("building:room:edit")]));
+    /// ```
+    pub fn subtree(&self, group: &str) -> Permission {
+        let prefix = format!("{}{}", group, self.separator());
+        self.with_actions(self.actions().filter(|action| action.starts_with(&prefix)))
+    }
+
+    /// Returns an unmanaged [`Permission`](crate::Permission) with the actions of this one beneath a group, with the group
+    /// prefix removed, as if the group was the root of the actions.
+    ///
+    /// # Examples:
+    ///
+    /// ```
+    /// use std::collections::HashSet;
+    /// use simple_perm_manager::Permission;
+    ///
+    /// let perm = Permission::from_json(r#"{"building": {"view": true, "room": {"edit": true}}, "user": {"view": true}}"#);
+    ///
+    /// assert_eq!(
+    ///     *perm.subtree_rerooted("building").get_actions(),
+    ///     HashSet::from([String::from("view"), String::from("room:edit")])
+    /// );
+    /// ```
+    pub fn subtree_rerooted(&self, group: &str) -> Permission {
+        let prefix = format!("{}{}", group, self.separator());
+        Permission::from_actions(
+            self.actions()
+                .filter_map(|action| action.strip_prefix(&prefix))
+                .map(String::from)
+                .collect(),
+        )
+    }
+
+    /// Returns an unmanaged [`Permission`](crate::Permission) with the actions of this one mounted beneath a prefix group, e.g. to
+    /// apply generic actions to a resource namespace. The prefix may have several groups.
+    ///
+    /// # Examples:
+    ///
+    /// ```
+    /// use std::collections::HashSet;
+    /// use simple_perm_manager::Permission;
+    ///
+    /// let perm = Permission::from_json(r#"{"view": true, "edit": true}"#);
+    ///
+    /// assert_eq!(
+    ///     *perm.with_prefix("project_x").get_actions(),
+    ///     HashSet::from([String::from("project_x:view"), String::from("project_x:edit")])
+    /// );
+    /// ```
+    pub fn with_prefix(&self, prefix: &str) -> Permission {
+        let separator = self.separator();
+        Permission::from_actions(
+            self.actions()
+                .map(|action| format!("{}{}{}", prefix, separator, action))
+                .collect(),
+        )
+    }
+
+    /// Returns an unmanaged [`Permission`](crate::Permission) with the prefix group removed from the actions of this one, undoing
+    /// [`with_prefix`](Self::with_prefix()), or `None` if any action is not beneath the prefix.  
+    /// Use [`subtree_rerooted`](Self::subtree_rerooted()) to drop the actions that are not beneath it instead.
+    ///
+    /// # Examples:
+    ///
+    /// ```
+    /// use std::collections::HashSet;
+    /// use simple_perm_manager::Permission;
+    ///
+    /// let perm = Permission::from_json(r#"{"project_x": {"view": true, "edit": true}}"#);
+    ///
+    /// assert_eq!(
+    ///     *perm.strip_prefix("project_x").unwrap().get_actions(),
+    ///     HashSet::from([String::from("view"), String::from("edit")])
+    /// );
+    /// assert!(perm.strip_prefix("project_y").is_none());
+    /// ```
+    pub fn strip_prefix(&self, prefix: &str) -> Option<Permission> {
+        let prefix = format!("{}{}", prefix, self.separator());
+        self.actions()
+            .map(|action| action.strip_prefix(&prefix).map(String::from))
+            .collect::<Option<HashSet<String>>>()
+            .map(Permission::from_actions)
+    }
+
+    /// Returns the actions of the [`Permission`](crate::Permission) as an [`ActionTree`](crate::ActionTree), divided by the separator of
+    /// its [`PermissionManager`](crate::PermissionManager) or [`ACTION_DIVIDER`](crate::ACTION_DIVIDER) for unmanaged ones.
+    pub fn to_tree(&self) -> ActionTree {
+        let mut tree = ActionTree::new(self.separator());
+        for action in self.actions() {
+            tree.insert(action);
+        }
+
+        tree
+    }
+
+    /// Creates a new unmanaged [`Permission`](crate::Permission) with the actions of an [`ActionTree`](crate::ActionTree), joined with
+    /// [`ACTION_DIVIDER`](crate::ACTION_DIVIDER). Use [`PermissionManager::perm_from_tree`](crate::PermissionManager::perm_from_tree())
+    /// for managed [`Permission`](crate::Permission)s.
+    pub fn from_tree(tree: &ActionTree) -> Permission {
+        Permission::from_actions(tree.actions_with_separator(ACTION_DIVIDER))
+    }
+
+    /// Returns the groups of the [`Permission`](crate::Permission) actions by level: the first set has the top-level groups,
+    /// the second one the groups nested in them, and so on. Groups are returned as full prefixes (e.g. `"building:room"`),
+    /// so UIs can render collapsible sections without splitting actions.
+    ///
+    /// # Examples:
+    ///
+    /// ```
+    /// use std::collections::HashSet;
+    /// use simple_perm_manager::Permission;
+    ///
+    /// let perm = Permission::from_json(r#"{"building": {"view": true, "room": {"edit": true}}, "user": {"view": true}, "home": true}"#);
+    ///
+    /// assert_eq!(
+    ///     perm.groups(),
+    ///     vec![
+    ///         HashSet::from([String::from("building"), String::from("user")]),
+    ///         HashSet::from([String::from("building:room")]),
+    ///     ]
+    /// );
+    /// ```
+    pub fn groups(&self) -> Vec<HashSet<String>> {
+        let separator = self.separator();
+        let mut levels: Vec<HashSet<String>> = Vec::new();
+        for action in self.actions() {
+            for (level, (position, _)) in action.match_indices(separator).enumerate() {
+                if levels.len() == level {
+                    levels.push(HashSet::new());
+                }
+                levels[level].insert(action[..position].to_string());
+            }
+        }
+
+        levels
+    }
+
+    /// Returns the [`Permission`](crate::Permission) actions.  
+    /// Unmanaged [`Permission`](crate::Permission)s lend their actions, while managed ones copy the names interned by their
+    /// [`PermissionManager`](crate::PermissionManager) on every call, see [`actions`](Self::actions()).
+    ///
+    /// # Examples:
+    ///
+    /// ```
+    /// use std::collections::HashSet;
+    /// use simple_perm_manager::Permission;
+    ///
+    /// let perm = Permission::from_actions_and_uuid(HashSet::from([
+    ///    String::from("create"),
+    ///    String::from("view"),
+    /// ]), None);
+    ///
+    /// // Should print an HashSet containing the elements 'create', and 'view'.
+    /// println!("Permission actions as JSON: {:#?}", perm.get_actions());
+    /// ```
+    pub fn get_actions(&self) -> Cow<'_, HashSet<String>> {
+        self.actions.to_strings()
+    }
+
+    /// Returns a [`Permission`](crate::Permission) with the same [`PermissionManager`](crate::PermissionManager) whose actions are the
+    /// actions of this one with the changes of a [`PermissionDelta`](crate::PermissionDelta): removed actions are removed and added
+    /// actions are added. Added actions are not validated, use [`PermissionManager::validate_perm`](crate::PermissionManager::validate_perm())
+    /// on deltas from untrusted sources.
+    ///
+    /// # Examples:
+    ///
+    /// ```
+    /// use std::collections::HashSet;
+    /// use simple_perm_manager::{Permission, PermissionDelta};
+    ///
+    /// let perm = Permission::from_json(r#"{"view": true, "edit": true}"#);
+    /// let delta = PermissionDelta::new(HashSet::from([String::from("delete")]), HashSet::from([String::from("edit")]));
+    ///
+    /// assert_eq!(perm.apply_delta(&delta).to_json(), r#"{"delete":true,"view":true}"#);
+    /// ```
+    pub fn apply_delta(&self, delta: &PermissionDelta) -> Permission {
+        self.with_actions(
+            self.actions()
+                .filter(|action| !delta.removed().contains(*action))
+                .chain(delta.added().iter().map(String::as_str)),
+        )
+    }
+
+    /// Returns a [`Permission`](crate::Permission) containing all the actions in every [`Permission`](crate::Permission)
+    /// provided, folding them in a single pass over one accumulated set of actions.
+    ///
+    /// The resulting [`Permission`](crate::Permission) has the manager of the permissions provided.
+    /// An empty iterator results in an empty unmanaged [`Permission`](crate::Permission); use
+    /// [`PermissionManager::union_all`](crate::PermissionManager::union_all()) to always get a managed one.
+    ///
+    /// # Examples:
+    ///
+    /// ```
+    /// use simple_perm_manager::PermissionManager;
+    /// use simple_perm_manager::Permission;
+    ///
+    /// let manager = PermissionManager::from_json(r#"{"create": true, "view": true, "edit": true}"#);
+    ///
+    /// let grants = vec![
+    ///     manager.perm_from_json(r#"{"create": true}"#),
+    ///     manager.perm_from_json(r#"{"view": true}"#),
+    ///     manager.perm_from_json(r#"{"view": true, "edit": true}"#),
+    /// ];
+    ///
+    /// let perm = Permission::union_all(&grants);
+    /// assert_eq!(perm.get_actions(), manager.get_universe().get_actions());
+    /// ```
+    ///
+    /// # Panics:
+    ///
+    /// Panics if the [`Permission`](crate::Permission)s provided do not all have the same [`PermissionManager`](crate::PermissionManager).
+    pub fn union_all<'a, I>(perms: I) -> Permission
+    where
+        I: IntoIterator<Item = &'a Permission>,
+    {
+        let mut perms = perms.into_iter();
+
+        let mut union = match perms.next() {
+            Some(first) => first.clone(),
+            None => return Permission::from_actions(HashSet::new()),
+        };
+        for perm in perms {
+            union.union_with(perm);
+        }
+
+        union
+    }
+
+    /// Removes from the calling [`Permission`](crate::Permission) all the actions in the [`Permission`](crate::Permission)
+    /// used as argument, modifying it in place instead of creating a new [`Permission`](crate::Permission)
+    /// like [`difference`](crate::Permission::difference()) does.
+    ///
+    /// # Examples:
+    ///
+    /// ```
+    /// use simple_perm_manager::PermissionManager;
+    ///
+    /// let manager = PermissionManager::from_json(r#"{"create": true, "view": true, "edit": true}"#);
+    ///
+    /// let mut perm = manager.get_universe();
+    /// perm.difference_with(&manager.perm_from_json(r#"{"create": true, "edit": true}"#));
+    ///
+    /// assert_eq!(perm.get_actions(), manager.perm_from_json(r#"{"view": true}"#).get_actions());
+    /// ```
+    ///
+    /// # Panics:
+    ///
+    /// Panics if the calling [`Permission`](crate::Permission) and the [`Permission`](crate::Permission)
+    /// used as argument do not have the same [`PermissionManager`](crate::PermissionManager).
+    pub fn difference_with(&mut self, other: &Permission) {
+        if !self.has_same_manager(other) {
+            panic!("Permissions in difference operation do not have same manager");
+        }
+
+        self.actions.retain(|action| !other.contains_action(action));
+        // Groups partially removed are no longer granted as a whole
+        let separator = self.separator();
+        self.groups.retain(|group| {
+            !other.has_group(group)
+                && !other
+                    .groups
+                    .iter()
+                    .any(|removed| is_beneath(removed, group, separator))
+                && !other
+                    .actions()
+                    .any(|action| is_beneath(action, group, separator))
+        });
+    }
+
+    /// Keeps in the calling [`Permission`](crate::Permission) only the actions that are also in the
+    /// [`Permission`](crate::Permission) used as argument, modifying it in place.
+    ///
+    /// # Examples:
+    ///
+    /// ```
+    /// use simple_perm_manager::PermissionManager;
+    ///
+    /// let manager = PermissionManager::from_json(r#"{"create": true, "view": true, "edit": true}"#);
+    ///
+    /// let mut perm = manager.perm_from_json(r#"{"create": true, "view": true}"#);
+    /// perm.intersect_with(&manager.perm_from_json(r#"{"view": true, "edit": true}"#));
+    ///
+    /// assert_eq!(perm.get_actions(), manager.perm_from_json(r#"{"view": true}"#).get_actions());
+    /// ```
+    ///
+    /// # Panics:
+    ///
+    /// Panics if the calling [`Permission`](crate::Permission) and the [`Permission`](crate::Permission)
+    /// used as argument do not have the same [`PermissionManager`](crate::PermissionManager).
+    pub fn intersect_with(&mut self, other: &Permission) {
+        if !self.has_same_manager(other) {
+            panic!("Permissions in intersection operation do not have same manager");
+        }
+
+        self.actions.retain(|action| other.contains_action(action));
+        let groups: BTreeSet<String> = self
+            .groups
+            .iter()
+            .filter(|group| other.has_group(group))
+            .chain(other.groups.iter().filter(|group| self.has_group(group)))
+            .cloned()
+            .collect();
+        self.groups = groups;
+    }
+
+    /// Returns a new [`Permission`](crate::Permission) with the actions of the subset provided, only if all of them are held by the
+    /// calling [`Permission`](crate::Permission) (directly or implied). Allows a subject to safely hand a subset of its own access
+    /// to another subject.
+    ///
+    /// # Examples:
+    ///
+    /// ```
+    /// use simple_perm_manager::{PermissionManager, ValidationError};
+    ///
+    /// let manager = PermissionManager::from_json(r#"{"create": true, "view": true, "edit": true}"#);
+    /// let perm = manager.perm_from_json(r#"{"create": true, "view": true}"#);
+    ///
+    /// let delegated = perm.delegate(&manager.perm_from_json(r#"{"view": true}"#)).unwrap();
+    /// assert!(delegated.contains_action("view"));
+    ///
+    /// assert_eq!(
+    ///     perm.delegate(&manager.perm_from_json(r#"{"view": true, "edit": true}"#)).unwrap_err(),
+    ///     ValidationError::NotDelegable(vec![String::from("edit")])
+    /// );
+    /// ```
+    ///
+    /// # Errors:
+    ///
+    /// Returns [`ValidationError::NotDelegable`](crate::ValidationError::NotDelegable) with the sorted actions of the subset not
+    /// held by the calling [`Permission`](crate::Permission).  
+    /// Returns [`ValidationError::ManagerMismatch`](crate::ValidationError::ManagerMismatch) if both [`Permission`](crate::Permission)s
+    /// do not have the same [`PermissionManager`](crate::PermissionManager).
+    pub fn delegate(&self, subset: &Permission) -> Result<Permission, ValidationError> {
+        if !self.has_same_manager(subset) {
+            return Err(ValidationError::ManagerMismatch);
+        }
+
+        let mut not_held: Vec<String> = subset
+            .actions()
+            .filter(|action| !self.contains_action(action))
+            .map(String::from)
+            .collect();
+        if !not_held.is_empty() {
+            not_held.sort();
+            return Err(ValidationError::NotDelegable(not_held));
+        }
+
+        Ok(subset.clone())
+    }
+
+    /// Returns `true` if the [`Permission`](crate::Permission) calling contains the action used as argument.  
+    /// Managed [`Permission`](crate::Permission)s granted a group node (e.g. `"building"`) also contain the actions added beneath
+    /// the group after they were issued, see [`PermissionManager::expand`](crate::PermissionManager::expand()).
+    ///
+    /// # Examples:
+    ///
+    /// ```
+    /// use simple_perm_manager::Permission;
+    /// use simple_perm_manager::PermissionManager;
+    ///
+    /// let manager = PermissionManager::from_json(&String::from(r#"{
+    ///     "create": true,
+    ///     "view": true,
+    ///     "edit": true
+    /// }"#));
+    ///
+    /// // Create Permission
+    /// let perm = manager.perm_from_json(&String::from(r#"{"create": true, "view": true}"#));
+    ///
+    /// assert!(perm.contains_action("create"));
+    /// assert!(!perm.contains_action("other"));
+    /// ```
+    pub fn contains_action(&self, action_str: &str) -> bool {
+        self.holds(action_str)
+    }
+
+    /// Adds an action to the [`Permission`](crate::Permission) in place.  
+    /// Returns `true` if the action was not already in the [`Permission`](crate::Permission).
+    ///
+    /// Managed [`Permission`](crate::Permission)s only accept actions present in the universe of their
+    /// [`PermissionManager`](crate::PermissionManager).
+    ///
+    /// # Examples:
+    ///
+    /// ```
+    /// use simple_perm_manager::PermissionManager;
+    ///
+    /// let manager = PermissionManager::from_json(r#"{"create": true, "view": true}"#);
+    /// let mut perm = manager.perm_from_json(r#"{"view": true}"#);
+    ///
+    /// assert!(perm.add_action("create"));
+    /// assert!(!perm.add_action("create"));
+    /// assert!(perm.contains_action("create"));
+    /// ```
+    ///
+    /// # Panics:
+    ///
+    /// - Panics if the [`Permission`](crate::Permission) is managed and the action is not in its
+    ///   [`PermissionManager`](crate::PermissionManager) universe.
+    /// - Panics if the [`Permission`](crate::Permission) is managed but was not created by a
+    ///   [`PermissionManager`](crate::PermissionManager), so its universe can not be resolved.
+    /// ```rust,should_panic
+    /// use simple_perm_manager::PermissionManager;
+    ///
+    /// let manager = PermissionManager::from_json(r#"{"create": true, "view": true}"#);
+    /// let mut perm = manager.perm_from_json(r#"{"view": true}"#);
+    ///
+    /// // This line of code panics
+    /// perm.add_action("other_action");
+    /// ```
+    pub fn add_action(&mut self, action_str: &str) -> bool {
+        if self.is_managed() {
+            // Aliases are stored as the action they refer to
+            let Some(universe) = &self.universe else {
+                panic!("Managed Permission has no PermissionManager universe to validate actions against")
+            };
+            let action = match universe.resolve(&universe.fold(action_str)) {
+                Some(action) => action.to_string(),
+                None => panic!("Action to add not allowed in PermissionManager universe"),
+            };
+
+            return self.actions.insert(&action, Some(universe));
+        }
+
+        self.actions.insert(action_str, None)
+    }
+
+    /// Removes an action from the [`Permission`](crate::Permission) in place.  
+    /// Returns `true` if the action was in the [`Permission`](crate::Permission). Managed [`Permission`](crate::Permission)s
+    /// resolve aliases as in [`add_action`](Self::add_action()) and also remove the actions implying the removed one, so it is
+    /// no longer contained by [`contains_action`](Self::contains_action()) or [`contains`](Self::contains()).
+    ///
+    /// # Examples:
+    ///
+    /// ```
+    /// use simple_perm_manager::PermissionManager;
+    ///
+    /// let manager = PermissionManager::from_json(r#"{"create": true, "view": true}"#);
+    /// let mut perm = manager.perm_from_json(r#"{"create": true, "view": true}"#);
+    ///
+    /// assert!(perm.remove_action("create"));
+    /// assert!(!perm.remove_action("create"));
+    /// assert!(!perm.contains_action("create"));
+    /// ```
+    pub fn remove_action(&mut self, action_str: &str) -> bool {
+        let separator = self.separator();
+        match &self.universe {
+            Some(universe) => {
+                // Aliases are stored as the action they refer to
+                let folded = universe.fold(action_str);
+                let action = universe.alias(&folded).unwrap_or(&folded);
+                let implying: Vec<String> = self
+                    .actions
+                    .iter()
+                    .filter(|granted| universe.implied(granted).any(|implied| implied == action))
+                    .map(String::from)
+                    .collect();
+
+                let mut removed = false;
+                for action in implying.iter().map(String::as_str).chain([action]) {
+                    self.groups
+                        .retain(|group| !is_beneath(action, group, separator));
+                    removed |= self.actions.remove(action);
+                }
+                removed
+            }
+            None => self.actions.remove(action_str),
+        }
+    }
+
+    /// Removes every action from the [`Permission`](crate::Permission) in place.  
+    /// A managed [`Permission`](crate::Permission) keeps its [`PermissionManager`](crate::PermissionManager).
+    ///
+    /// # Examples:
+    ///
+    /// ```
+    /// use simple_perm_manager::PermissionManager;
+    ///
+    /// let manager = PermissionManager::from_json(r#"{"create": true, "view": true}"#);
+    /// let mut perm = manager.perm_from_json(r#"{"create": true, "view": true}"#);
+    ///
+    /// perm.clear();
+    /// assert!(perm.get_actions().is_empty());
+    /// assert!(manager.validate_perm(&perm));
+    /// ```
+    pub fn clear(&mut self) {
+        self.actions.clear();
+        self.groups.clear();
+    }
+}
+
+// Debug output shows the actions and manager, the linked universe is left out
+impl<A> fmt::Debug for Permission<A> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Permission")
+            .field("actions", &self.actions)
+            .field("manager_id", &self.manager_id)
+            .finish()
     }
 }
@@ -1,11 +1,12 @@
 use super::*;
+use crate::PermissionManager;
 
 #[test]
 fn from_actions_test() {
     let actions = HashSet::from([String::from("view"), String::from("create")]);
     let p = Permission::from_actions(actions.clone());
 
-    assert_eq!(actions, p.actions);
+    assert_eq!(actions, *p.actions.to_strings());
     assert_eq!(None, p.manager_id);
 }
 
@@ -16,7 +17,7 @@ fn from_actions_and_uuid_test() {
     let p = Permission::from_actions_and_uuid(actions.clone(), Some(id));
     let p2 = Permission::from_actions_and_uuid(actions.clone(), None);
 
-    assert_eq!(actions, p.actions);
+    assert_eq!(actions, *p.actions.to_strings());
     assert_eq!(Some(id), p.manager_id);
     assert_eq!(None, p2.manager_id);
 }
@@ -104,6 +105,73 @@ fn to_json_test() {
     assert_eq!(v1, v2)
 }
 
+#[test]
+fn to_managed_json_test() {
+    let id = Uuid::new_v4();
+    let actions = HashSet::from([String::from("view")]);
+
+    assert_eq!(
+        Permission::from_actions_and_uuid(actions.clone(), Some(id)).to_managed_json(),
+        format!(r#"{{"actions":{{"view":true}},"manager_id":"{}"}}"#, id)
+    );
+    assert_eq!(
+        Permission::from_actions(actions).to_managed_json(),
+        r#"{"actions":{"view":true},"manager_id":null}"#
+    );
+}
+
+#[test]
+fn to_jwt_claim_test() {
+    let perm = Permission::from_json(r#"{"post": {"view": true, "edit": true}, "share": true}"#);
+    assert_eq!(
+        perm.to_jwt_claim(),
+        Value::String(String::from("post:edit post:view share"))
+    );
+    assert_eq!(
+        Permission::from_json("{}").to_jwt_claim(),
+        Value::String(String::new())
+    );
+
+    let manager = PermissionManager::from_json(r#"{"post": {"view": true, "edit": true}}"#);
+    let claim = manager.perm_from_json(r#"{"post": true}"#).to_jwt_claim();
+    assert_eq!(
+        claim,
+        serde_json::json!({"u": manager.universe_hash(), "b": "Aw"})
+    );
+
+    // Ensure method panics for actions that can not be in a scope
+    match std::panic::catch_unwind(|| {
+        Permission::from_actions(HashSet::from([String::from("view all")])).to_jwt_claim();
+    }) {
+        Ok(_) => panic!("scope claim of an action with whitespace should have panicked"),
+        Err(_) => (),
+    }
+}
+
+#[test]
+fn from_jwt_claim_test() {
+    let perm =
+        Permission::from_jwt_claim(&Value::String(String::from(" post:edit  share "))).unwrap();
+    assert_eq!(perm.is_managed(), false);
+    assert_eq!(
+        *perm.get_actions(),
+        HashSet::from([String::from("post:edit"), String::from("share")])
+    );
+
+    assert_eq!(
+        Permission::from_jwt_claim(&serde_json::json!({"u": "hash", "b": "Aw"})).is_err(),
+        true
+    );
+    assert_eq!(
+        Permission::from_jwt_claim(&serde_json::json!(["post:edit"])).is_err(),
+        true
+    );
+    assert_eq!(
+        Permission::from_jwt_claim(&Value::String(String::from("post::edit"))).unwrap_err(),
+        ValidationError::MalformedActions(vec![String::from("post::edit")])
+    );
+}
+
 #[test]
 fn get_actions_test() {
     let actions = HashSet::from([String::from("view"), String::from("create")]);
@@ -126,6 +194,18 @@ fn is_managed_test() {
     assert_eq!(not_managed_2.is_managed(), false);
 }
 
+#[test]
+fn manager_id_test() {
+    let actions = HashSet::from([String::from("view")]);
+    let id = Uuid::new_v4();
+
+    assert_eq!(
+        Permission::from_actions_and_uuid(actions.clone(), Some(id)).manager_id(),
+        Some(ManagerId::new(id))
+    );
+    assert_eq!(Permission::from_actions(actions).manager_id(), None);
+}
+
 #[test]
 fn has_same_manager_test() {
     let actions = HashSet::from([String::from("view"), String::from("create")]);
@@ -433,5 +513,500 @@ fn contains_action_test() {
     assert_eq!(p1.contains_action(&String::from("view")), true);
     assert_eq!(p1.contains_action(&String::from("delete")), false);
     assert_eq!(p1.contains_action(&String::from("other")), false);
-    assert_eq!(p1.contains_action(&String::from("")), false);
+    assert_eq!(p1.contains_action(""), false);
+}
+
+#[test]
+fn add_action_test() {
+    let pm = PermissionManager::from_actions(HashSet::from([
+        String::from("view"),
+        String::from("create"),
+    ]));
+    let mut managed = pm.perm_from_actions(HashSet::from([String::from("view")]));
+    let mut unmanaged = Permission::from_actions(HashSet::from([]));
+
+    assert_eq!(managed.add_action("create"), true);
+    assert_eq!(managed.add_action("create"), false);
+    assert_eq!(
+        *managed.get_actions(),
+        HashSet::from([String::from("view"), String::from("create")])
+    );
+    assert_eq!(pm.validate_perm(&managed), true);
+
+    // Unmanaged permissions accept any action
+    assert_eq!(unmanaged.add_action("other"), true);
+    assert_eq!(
+        *unmanaged.get_actions(),
+        HashSet::from([String::from("other")])
+    );
+
+    // Results of operations keep the universe of the manager
+    let mut union = managed.union(&pm.perm_from_actions(HashSet::from([])));
+    assert_eq!(union.add_action("view"), false);
+
+    // Ensure method panics for actions outside the universe
+    match std::panic::catch_unwind(|| {
+        let mut managed = pm.perm_from_actions(HashSet::from([]));
+        managed.add_action("other");
+    }) {
+        Ok(_) => panic!("adding an action not in the manager universe should have panicked"),
+        Err(_) => (),
+    }
+
+    // Ensure method panics for managed permissions without a universe to validate against
+    match std::panic::catch_unwind(|| {
+        let mut no_universe =
+            Permission::from_actions_and_uuid(HashSet::from([]), Some(Uuid::new_v4()));
+        no_universe.add_action("view");
+    }) {
+        Ok(_) => {
+            panic!("adding an action to a managed permission without universe should have panicked")
+        }
+        Err(_) => (),
+    }
+}
+
+#[test]
+fn remove_action_test() {
+    let mut p = Permission::from_actions(HashSet::from([
+        String::from("view"),
+        String::from("create"),
+    ]));
+
+    assert_eq!(p.remove_action("view"), true);
+    assert_eq!(p.remove_action("view"), false);
+    assert_eq!(p.remove_action("other"), false);
+    assert_eq!(*p.get_actions(), HashSet::from([String::from("create")]));
+}
+
+#[test]
+fn clear_test() {
+    let id = Uuid::new_v4();
+    let mut p = Permission::from_actions_and_uuid(
+        HashSet::from([String::from("view"), String::from("create")]),
+        Some(id),
+    );
+
+    p.clear();
+    assert_eq!(*p.get_actions(), HashSet::from([]));
+    assert_eq!(p.manager_id, Some(id));
+}
+
+#[test]
+fn debug_test() {
+    let id = Uuid::new_v4();
+    let p = Permission::from_actions_and_uuid(HashSet::from([String::from("view")]), Some(id));
+
+    assert_eq!(
+        format!("{:?}", p),
+        format!(
+            "Permission {{ actions: {{\"view\"}}, manager_id: Some({:?}) }}",
+            id
+        )
+    );
+}
+
+#[test]
+fn union_with_test() {
+    let id = Uuid::new_v4();
+    let mut p1 = Permission::from_actions_and_uuid(
+        HashSet::from([String::from("view"), String::from("create")]),
+        Some(id),
+    );
+    let p2 = Permission::from_actions_and_uuid(
+        HashSet::from([String::from("create"), String::from("delete")]),
+        Some(id),
+    );
+
+    p1.union_with(&p2);
+    assert_eq!(
+        *p1.get_actions(),
+        HashSet::from([
+            String::from("view"),
+            String::from("create"),
+            String::from("delete")
+        ])
+    );
+    assert_eq!(p1.manager_id, Some(id));
+
+    let diff_id = Permission::from_actions_and_uuid(HashSet::from([]), Some(Uuid::new_v4()));
+    let none_id = Permission::from_actions_and_uuid(HashSet::from([]), None);
+
+    match std::panic::catch_unwind(|| {
+        p1.clone().union_with(&diff_id);
+    }) {
+        Ok(_) => panic!("operation with perm with different manager_id should have panicked"),
+        Err(_) => (),
+    }
+    match std::panic::catch_unwind(|| {
+        p1.clone().union_with(&none_id);
+    }) {
+        Ok(_) => panic!("operation with perm with different manager_id should have panicked"),
+        Err(_) => (),
+    }
+}
+
+#[test]
+fn difference_with_test() {
+    let id = Uuid::new_v4();
+    let mut p1 = Permission::from_actions_and_uuid(
+        HashSet::from([String::from("view"), String::from("create")]),
+        Some(id),
+    );
+    let p2 = Permission::from_actions_and_uuid(
+        HashSet::from([String::from("create"), String::from("delete")]),
+        Some(id),
+    );
+
+    p1.difference_with(&p2);
+    assert_eq!(*p1.get_actions(), HashSet::from([String::from("view")]));
+
+    let diff_id = Permission::from_actions_and_uuid(HashSet::from([]), Some(Uuid::new_v4()));
+
+    match std::panic::catch_unwind(|| {
+        p1.clone().difference_with(&diff_id);
+    }) {
+        Ok(_) => panic!("operation with perm with different manager_id should have panicked"),
+        Err(_) => (),
+    }
+}
+
+#[test]
+fn intersect_with_test() {
+    let id = Uuid::new_v4();
+    let mut p1 = Permission::from_actions_and_uuid(
+        HashSet::from([String::from("view"), String::from("create")]),
+        Some(id),
+    );
+    let p2 = Permission::from_actions_and_uuid(
+        HashSet::from([String::from("create"), String::from("delete")]),
+        Some(id),
+    );
+    let empty = Permission::from_actions_and_uuid(HashSet::from([]), Some(id));
+
+    p1.intersect_with(&p2);
+    assert_eq!(*p1.get_actions(), HashSet::from([String::from("create")]));
+
+    p1.intersect_with(&empty);
+    assert_eq!(*p1.get_actions(), HashSet::from([]));
+
+    let diff_id = Permission::from_actions_and_uuid(HashSet::from([]), Some(Uuid::new_v4()));
+
+    match std::panic::catch_unwind(|| {
+        p1.clone().intersect_with(&diff_id);
+    }) {
+        Ok(_) => panic!("operation with perm with different manager_id should have panicked"),
+        Err(_) => (),
+    }
+}
+
+#[test]
+fn union_all_test() {
+    let id = Uuid::new_v4();
+    let perms = vec![
+        Permission::from_actions_and_uuid(HashSet::from([String::from("view")]), Some(id)),
+        Permission::from_actions_and_uuid(HashSet::from([String::from("create")]), Some(id)),
+        Permission::from_actions_and_uuid(
+            HashSet::from([String::from("view"), String::from("delete")]),
+            Some(id),
+        ),
+    ];
+
+    let union = Permission::union_all(&perms);
+    assert_eq!(
+        *union.get_actions(),
+        HashSet::from([
+            String::from("view"),
+            String::from("create"),
+            String::from("delete")
+        ])
+    );
+    assert_eq!(union.manager_id, Some(id));
+
+    // Empty iterator results in an empty unmanaged permission
+    let empty = Permission::union_all(&vec![]);
+    assert_eq!(*empty.get_actions(), HashSet::from([]));
+    assert_eq!(empty.is_managed(), false);
+
+    // Ensure method panics if any permission has a different manager
+    let mut mixed = perms.clone();
+    mixed.push(Permission::from_actions(HashSet::from([])));
+
+    match std::panic::catch_unwind(|| {
+        Permission::union_all(&mixed);
+    }) {
+        Ok(_) => panic!("operation with perm with different manager_id should have panicked"),
+        Err(_) => (),
+    }
+}
+
+#[test]
+fn perm_macro_test() {
+    let expected = HashSet::from([
+        String::from("building:view"),
+        String::from("building:meter:view"),
+        String::from("user:edit"),
+    ]);
+
+    let list = crate::perm!["building:view", "building:meter:view", "user:edit",];
+    let object = crate::perm! {
+        "building": { "view": true, "edit": false, "meter": { "view": true } },
+        "user": { "edit": true },
+    };
+    let raw = crate::perm![r"building:view", r#"building:meter:view"#, "user:edit"];
+
+    assert_eq!(*list.get_actions(), expected);
+    assert_eq!(*object.get_actions(), expected);
+    assert_eq!(*raw.get_actions(), expected);
+    assert_eq!(list.is_managed(), false);
+    assert_eq!(*crate::perm![].get_actions(), HashSet::from([]));
+
+    // Managed permissions
+    let pm = PermissionManager::from_actions(expected.clone());
+    let managed = crate::perm!(pm; "user:edit");
+    assert_eq!(pm.validate_perm(&managed), true);
+
+    match std::panic::catch_unwind(|| {
+        crate::perm!(pm; "other");
+    }) {
+        Ok(_) => panic!("actions not in the manager universe should have panicked"),
+        Err(_) => (),
+    }
+}
+
+#[test]
+fn delegate_test() {
+    let manager = PermissionManager::from_json(r#"{"create": true, "view": true, "edit": true}"#);
+    let perm = manager.perm_from_json(r#"{"create": true, "view": true}"#);
+
+    let delegated = perm
+        .delegate(&manager.perm_from_json(r#"{"view": true}"#))
+        .unwrap();
+    assert_eq!(
+        *delegated.get_actions(),
+        HashSet::from([String::from("view")])
+    );
+    assert_eq!(manager.validate_perm(&delegated), true);
+    assert_eq!(
+        perm.delegate(&manager.perm_from_json(r#"{"edit": true, "create": true}"#))
+            .unwrap_err(),
+        ValidationError::NotDelegable(vec![String::from("edit")])
+    );
+
+    // Implied actions can be delegated
+    let mut manager = PermissionManager::from_json(r#"{"view": true, "edit": true}"#);
+    manager.add_implication("edit", "view");
+    let mut perm = manager.perm_from_json("{}");
+    perm.add_action("edit");
+    assert_eq!(
+        perm.delegate(&manager.perm_from_json(r#"{"view": true}"#))
+            .is_ok(),
+        true
+    );
+
+    let unmanaged = Permission::from_json(r#"{"view": true}"#);
+    assert_eq!(
+        perm.delegate(&unmanaged).unwrap_err(),
+        ValidationError::ManagerMismatch
+    );
+    let other_manager = PermissionManager::from_json(r#"{"view": true}"#);
+    assert_eq!(
+        perm.delegate(&other_manager.perm_from_json(r#"{"view": true}"#))
+            .unwrap_err(),
+        ValidationError::ManagerMismatch
+    );
+}
+
+#[test]
+fn normalize_separator_test() {
+    let legacy = Permission::from_actions(HashSet::from([
+        String::from("building.room.view"),
+        String::from("user:edit"),
+        String::from("home"),
+    ]));
+    let perm = legacy.normalize_separator('.', ':');
+
+    assert_eq!(
+        *perm.get_actions(),
+        HashSet::from([
+            String::from("building:room:view"),
+            String::from("user:edit"),
+            String::from("home"),
+        ])
+    );
+    assert_eq!(perm.is_managed(), false);
+
+    let manager = PermissionManager::from_json(r#"{"building": {"view": true}}"#);
+    let managed = manager
+        .perm_from_json(r#"{"building": {"view": true}}"#)
+        .normalize_separator(':', '.');
+    assert_eq!(managed.has_same_manager(&manager.get_universe()), true);
+    assert_eq!(managed.contains_action("building.view"), true);
+}
+
+#[test]
+fn try_from_with_policy_test() {
+    let policy = ActionNamePolicy::identifiers().with_forbidden_chars("-");
+
+    let perm =
+        Permission::try_from_json(r#"{"user": {"view": true, "edit_2": true}}"#, &policy).unwrap();
+    assert_eq!(
+        *perm.get_actions(),
+        HashSet::from([String::from("user:view"), String::from("user:edit_2")])
+    );
+    assert_eq!(
+        Permission::try_from_json(r#"{"user": {"view-all": true, "a.b": true}}"#, &policy)
+            .unwrap_err(),
+        ValidationError::MalformedActions(vec![
+            String::from("user:a.b"),
+            String::from("user:view-all")
+        ])
+    );
+    assert!(matches!(
+        Permission::try_from_json("[]", &policy),
+        Err(ValidationError::InvalidJson(_))
+    ));
+    assert_eq!(
+        Permission::try_from_actions(
+            HashSet::from([String::from("user::view")]),
+            &ActionNamePolicy::new()
+        )
+        .is_err(),
+        true
+    );
+}
+
+#[test]
+fn subtree_test() {
+    let manager = PermissionManager::from_json(
+        r#"{"building": {"view": true, "room": {"edit": true}}, "buildings": {"view": true}}"#,
+    );
+    let perm = manager.get_universe();
+
+    let subtree = perm.subtree("building");
+    assert_eq!(
+        *subtree.get_actions(),
+        HashSet::from([
+            String::from("building:view"),
+            String::from("building:room:edit")
+        ])
+    );
+    assert_eq!(manager.validate_perm(&subtree), true);
+    assert_eq!(perm.subtree("other").get_actions().is_empty(), true);
+    assert_eq!(perm.subtree("building:view").get_actions().is_empty(), true);
+
+    let rerooted = perm.subtree_rerooted("building");
+    assert_eq!(rerooted.is_managed(), false);
+    assert_eq!(
+        *rerooted.get_actions(),
+        HashSet::from([String::from("view"), String::from("room:edit")])
+    );
+}
+
+#[test]
+fn groups_test() {
+    let perm = Permission::from_json(
+        r#"{"a": {"b": {"c": {"d": true}}, "e": true}, "f": {"g": true}, "h": true}"#,
+    );
+
+    assert_eq!(
+        perm.groups(),
+        vec![
+            HashSet::from([String::from("a"), String::from("f")]),
+            HashSet::from([String::from("a:b")]),
+            HashSet::from([String::from("a:b:c")]),
+        ]
+    );
+    assert_eq!(
+        Permission::from_json(r#"{"view": true}"#)
+            .groups()
+            .is_empty(),
+        true
+    );
+
+    let mut manager = PermissionManager::from_json(r#"{"a": {"b": {"c": true}}}"#);
+    manager.set_separator('.');
+    assert_eq!(
+        manager.groups(),
+        vec![
+            HashSet::from([String::from("a")]),
+            HashSet::from([String::from("a.b")]),
+        ]
+    );
+}
+
+#[test]
+fn prefix_test() {
+    let manager = PermissionManager::from_json(r#"{"view": true, "room": {"edit": true}}"#);
+    let perm = manager.get_universe();
+
+    let mounted = perm.with_prefix("org:project");
+    assert_eq!(mounted.is_managed(), false);
+    assert_eq!(
+        *mounted.get_actions(),
+        HashSet::from([
+            String::from("org:project:view"),
+            String::from("org:project:room:edit")
+        ])
+    );
+    assert_eq!(
+        mounted.strip_prefix("org:project").unwrap().get_actions(),
+        perm.get_actions()
+    );
+    assert_eq!(
+        *mounted.strip_prefix("org").unwrap().get_actions(),
+        HashSet::from([
+            String::from("project:view"),
+            String::from("project:room:edit")
+        ])
+    );
+    assert_eq!(mounted.strip_prefix("org:proj").is_none(), true);
+    assert_eq!(
+        mounted
+            .union(&Permission::from_json(r#"{"view": true}"#))
+            .strip_prefix("org:project")
+            .is_none(),
+        true
+    );
+    assert_eq!(
+        Permission::from_actions(HashSet::new())
+            .strip_prefix("org")
+            .unwrap()
+            .get_actions()
+            .is_empty(),
+        true
+    );
+}
+
+#[test]
+fn interned_actions_test() {
+    let manager = PermissionManager::from_json(r#"{"view": true, "edit": true}"#);
+    let mut first = manager.perm_from_json(r#"{"view": true}"#);
+    let second = manager.perm_from_json(r#"{"view": true, "edit": true}"#);
+    first.union_with(&second);
+
+    // Managed permissions hold the names interned by their manager
+    let interned = |perm: &Permission, action: &str| match &perm.actions {
+        ActionSet::Interned(actions) => actions.get(action).unwrap().clone(),
+        ActionSet::Owned(_) => panic!("managed permission actions should be interned"),
+    };
+    let view = manager.intern("view").unwrap();
+    assert_eq!(Arc::ptr_eq(&interned(&first, "view"), &view), true);
+    assert_eq!(Arc::ptr_eq(&interned(&second, "view"), &view), true);
+    assert_eq!(
+        Arc::ptr_eq(&interned(&first, "edit"), &manager.intern("edit").unwrap()),
+        true
+    );
+    assert_eq!(
+        Arc::ptr_eq(
+            &interned(&first.difference(&manager.perm_from_json("{}")), "view"),
+            &view
+        ),
+        true
+    );
+
+    match Permission::from_json(r#"{"view": true}"#).actions {
+        ActionSet::Owned(_) => (),
+        ActionSet::Interned(_) => panic!("unmanaged permission actions should be owned"),
+    }
 }
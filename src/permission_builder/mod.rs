@@ -0,0 +1,145 @@
+use crate::permission::{Permission, ACTION_DIVIDER};
+use crate::permission_manager::PermissionManager;
+use std::collections::HashSet;
+
+//// TESTS ////
+#[cfg(test)]
+mod tests;
+
+/// Fluent construction of [`Permission`](crate::Permission)s without assembling the actions set beforehand.
+///
+/// Created with [`Permission::builder`](crate::Permission::builder()) for unmanaged permissions or with
+/// [`PermissionManager::perm_builder`](crate::PermissionManager::perm_builder()) for permissions managed
+/// (and validated at [`build`](Self::build())) by a [`PermissionManager`](crate::PermissionManager).
+///
+/// # Examples:
+///
+/// ```
+/// use simple_perm_manager::PermissionManager;
+///
+/// let manager = PermissionManager::from_json(r#"{
+///     "building": { "view": true, "edit": true },
+///     "user": { "create": true, "edit": true, "delete": true }
+/// }"#);
+///
+/// let perm = manager
+///     .perm_builder()
+///     .action("building:view")
+///     .group("user", ["create", "edit"])
+///     .build();
+///
+/// assert!(manager.validate_perm(&perm));
+/// assert!(perm.contains_action("user:edit"));
+/// ```
+#[derive(Debug, Default)]
+pub struct PermissionBuilder<'a> {
+    actions: HashSet<String>,
+    manager: Option<&'a PermissionManager>,
+}
+
+impl<'a> PermissionBuilder<'a> {
+    /// Creates a builder for an unmanaged [`Permission`](crate::Permission) with no actions.
+    pub fn new() -> PermissionBuilder<'a> {
+        PermissionBuilder::default()
+    }
+
+    /// Creates a builder for a [`Permission`](crate::Permission) managed by the [`PermissionManager`](crate::PermissionManager) provided.
+    pub fn for_manager(manager: &'a PermissionManager) -> PermissionBuilder<'a> {
+        PermissionBuilder {
+            actions: HashSet::new(),
+            manager: Some(manager),
+        }
+    }
+
+    /// Adds a single action.
+    ///
+    /// # Examples:
+    ///
+    /// ```
+    /// use simple_perm_manager::Permission;
+    ///
+    /// let perm = Permission::builder().action("view").action("create").build();
+    /// assert!(perm.contains_action("view") && perm.contains_action("create"));
+    /// ```
+    pub fn action(mut self, action: &str) -> PermissionBuilder<'a> {
+        self.actions.insert(action.to_string());
+        self
+    }
+
+    /// Adds every action provided.
+    ///
+    /// # Examples:
+    ///
+    /// ```
+    /// use simple_perm_manager::Permission;
+    ///
+    /// let perm = Permission::builder().actions(["view", "create"]).build();
+    /// assert_eq!(perm.get_actions().len(), 2);
+    /// ```
+    pub fn actions<I, S>(mut self, actions: I) -> PermissionBuilder<'a>
+    where
+        I: IntoIterator<Item = S>,
+        S: AsRef<str>,
+    {
+        self.actions.extend(
+            actions
+                .into_iter()
+                .map(|action| action.as_ref().to_string()),
+        );
+        self
+    }
+
+    /// Adds every action provided inside a group, prefixing them with the group name
+    /// and the action divider (`group:action`), which is the separator of the [`PermissionManager`](crate::PermissionManager)
+    /// for bound builders.  
+    /// Nested groups can be expressed including the divider in the group name (`building:meter`).
+    ///
+    /// # Examples:
+    ///
+    /// ```
+    /// use simple_perm_manager::Permission;
+    ///
+    /// let perm = Permission::builder()
+    ///     .group("building:meter", ["view", "edit"])
+    ///     .build();
+    ///
+    /// assert!(perm.contains_action("building:meter:view"));
+    /// assert!(perm.contains_action("building:meter:edit"));
+    /// ```
+    pub fn group<I, S>(mut self, group: &str, actions: I) -> PermissionBuilder<'a>
+    where
+        I: IntoIterator<Item = S>,
+        S: AsRef<str>,
+    {
+        let separator = self
+            .manager
+            .map_or(ACTION_DIVIDER, |manager| manager.separator());
+        self.actions.extend(
+            actions
+                .into_iter()
+                .map(|action| format!("{}{}{}", group, separator, action.as_ref())),
+        );
+        self
+    }
+
+    /// Creates the [`Permission`](crate::Permission) with the actions added to the builder.
+    ///
+    /// # Panics:
+    ///
+    /// Panics if the builder is bound to a [`PermissionManager`](crate::PermissionManager) and any of the
+    /// actions added is not in its universe.
+    /// ```rust,should_panic
+    /// use simple_perm_manager::PermissionManager;
+    ///
+    /// let manager = PermissionManager::from_json(r#"{"view": true}"#);
+    ///
+    /// // This line of code panics
+    /// let panics = manager.perm_builder().action("other_action").build();
+    /// ```
+    pub fn build(self) -> Permission {
+        match self.manager {
+            Some(manager) => manager.perm_from_actions(self.actions),
+            None => Permission::from_actions(self.actions),
+        }
+    }
+}
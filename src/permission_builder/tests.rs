@@ -0,0 +1,85 @@
+use super::*;
+
+#[test]
+fn new_test() {
+    let perm = PermissionBuilder::new().build();
+
+    assert_eq!(*perm.get_actions(), HashSet::from([]));
+    assert_eq!(perm.is_managed(), false);
+}
+
+#[test]
+fn action_test() {
+    let perm = PermissionBuilder::new()
+        .action("view")
+        .action("create")
+        .action("view")
+        .build();
+
+    assert_eq!(
+        *perm.get_actions(),
+        HashSet::from([String::from("view"), String::from("create")])
+    );
+}
+
+#[test]
+fn actions_test() {
+    let perm = PermissionBuilder::new()
+        .actions(vec![String::from("view"), String::from("create")])
+        .actions(["delete"])
+        .build();
+
+    assert_eq!(
+        *perm.get_actions(),
+        HashSet::from([
+            String::from("view"),
+            String::from("create"),
+            String::from("delete")
+        ])
+    );
+}
+
+#[test]
+fn group_test() {
+    let perm = PermissionBuilder::new()
+        .group("user", ["create", "edit"])
+        .group("building:meter", ["view"])
+        .group("empty", Vec::<String>::new())
+        .build();
+
+    assert_eq!(
+        *perm.get_actions(),
+        HashSet::from([
+            String::from("user:create"),
+            String::from("user:edit"),
+            String::from("building:meter:view")
+        ])
+    );
+}
+
+#[test]
+fn for_manager_test() {
+    let pm =
+        PermissionManager::from_json(r#"{"user": {"create": true, "edit": true}, "view": true}"#);
+
+    let perm = PermissionBuilder::for_manager(&pm)
+        .action("view")
+        .group("user", ["edit"])
+        .build();
+
+    assert_eq!(pm.validate_perm(&perm), true);
+    assert_eq!(
+        *perm.get_actions(),
+        HashSet::from([String::from("view"), String::from("user:edit")])
+    );
+
+    // Ensure build panics if actions are not in the manager universe
+    match std::panic::catch_unwind(|| {
+        PermissionBuilder::for_manager(&pm)
+            .group("user", ["delete"])
+            .build();
+    }) {
+        Ok(_) => panic!("building with actions not in the manager universe should have panicked"),
+        Err(_) => (),
+    }
+}
@@ -0,0 +1,253 @@
+use crate::permission::Permission;
+use crate::permission_manager::signing;
+use crate::permission_manager::PermissionManager;
+use crate::telemetry;
+use crate::validation_error::ValidationError;
+use base64::engine::general_purpose::URL_SAFE_NO_PAD;
+use base64::Engine;
+use serde_json::{json, Value};
+
+//// TESTS ////
+#[cfg(test)]
+mod tests;
+
+// Returns the HMAC-SHA256 tag chained through the links: the root is signed with the issuer key and every other link with the
+// tag of the previous one. Returns the key itself if there are no links
+fn chain_tag(key: &[u8], links: &[Permission]) -> Vec<u8> {
+    links.iter().fold(key.to_vec(), |key, link| {
+        signing::sign(&key, link.to_json().as_bytes())
+    })
+}
+
+// Returns `true` if every link has the same manager as the previous one and is contained in it
+fn is_attenuated(links: &[Permission]) -> bool {
+    links.windows(2).all(|pair| {
+        pair[0].has_same_manager(&pair[1])
+            && pair[1].actions().all(|action| pair[0].has_action(action))
+    })
+}
+
+// Returns the invalid JSON error for a permission chain
+fn invalid(message: &str) -> ValidationError {
+    telemetry::record_parse_error();
+    ValidationError::InvalidJson(format!("wrong format in permission chain - {}", message))
+}
+
+/// Chain of [`Permission`](crate::Permission)s where each link is the previous one attenuated by a caveat, so it can only shrink.
+///
+/// The first link is the [`Permission`](crate::Permission) given by the issuer and the last one the [`Permission`](crate::Permission)
+/// actually granted. The chain carries an HMAC-SHA256 tag: the issuer signs the root with its key and every caveat signs the new
+/// link with the previous tag, so any holder can add a caveat and hand the chain to another service, but no one can remove or
+/// widen links without the issuer key. Receivers holding the issuer key check the tags and the lineage with
+/// [`verify`](Self::verify()) or [`PermissionManager::chain_from_json`](crate::PermissionManager::chain_from_json()).
+///
+/// # Examples:
+///
+/// ```
+/// use simple_perm_manager::PermissionManager;
+///
+/// let manager = PermissionManager::from_json(r#"{"view": true, "edit": true, "delete": true}"#);
+/// let issued = manager.perm_from_json(r#"{"view": true, "edit": true}"#);
+///
+/// let chain = issued
+///     .to_chain(b"issuer key")
+///     .attenuate(&manager.perm_from_json(r#"{"view": true, "delete": true}"#))
+///     .attenuate(&manager.perm_from_json(r#"{"view": true, "edit": true}"#));
+///
+/// assert_eq!(chain.len(), 3);
+/// assert!(chain.verify(b"issuer key"));
+/// assert!(!chain.verify(b"other key"));
+/// assert!(chain.permission().contains_action("view"));
+/// assert!(!chain.permission().contains_action("delete"));
+/// ```
+#[derive(Clone, Debug)]
+pub struct PermissionChain {
+    // Links of the chain, from the issued permission to the granted one. Never empty
+    links: Vec<Permission>,
+    // HMAC-SHA256 tag of the last link, chained from the issuer key through every link
+    tag: Vec<u8>,
+}
+
+impl PermissionChain {
+    /// Creates a new [`PermissionChain`](crate::PermissionChain) with the issued [`Permission`](crate::Permission) as its only link,
+    /// signed with the issuer `key`.
+    pub fn new(root: Permission, key: &[u8]) -> PermissionChain {
+        let links = vec![root];
+        let tag = chain_tag(key, &links);
+
+        PermissionChain { links, tag }
+    }
+
+    /// Returns a new [`PermissionChain`](crate::PermissionChain) with an extra link holding the actions of the last link that are
+    /// also in the caveat, signed with the tag of the chain. It does not need the issuer key.
+    ///
+    /// # Panics:
+    ///
+    /// Panics if the caveat does not have the same [`PermissionManager`](crate::PermissionManager) as the chain.
+    pub fn attenuate(&self, caveat: &Permission) -> PermissionChain {
+        let mut link = self.permission().clone();
+        link.intersect_with(caveat);
+
+        let tag = chain_tag(&self.tag, std::slice::from_ref(&link));
+        let mut links = self.links.clone();
+        links.push(link);
+
+        PermissionChain { links, tag }
+    }
+
+    /// Returns the [`Permission`](crate::Permission) granted by the chain, its last link.
+    pub fn permission(&self) -> &Permission {
+        self.links
+            .last()
+            .expect("unexpected error, permission chain should not be empty")
+    }
+
+    /// Returns the [`Permission`](crate::Permission) the chain was issued with, its first link.
+    pub fn root(&self) -> &Permission {
+        &self.links[0]
+    }
+
+    /// Returns the links of the chain, from the issued [`Permission`](crate::Permission) to the granted one.
+    pub fn links(&self) -> &[Permission] {
+        &self.links
+    }
+
+    /// Returns the number of links in the chain, including the issued [`Permission`](crate::Permission).
+    pub fn len(&self) -> usize {
+        self.links.len()
+    }
+
+    /// Returns `true` if the chain has no links. A [`PermissionChain`](crate::PermissionChain) always has its root, so this is always `false`.
+    pub fn is_empty(&self) -> bool {
+        self.links.is_empty()
+    }
+
+    /// Returns `true` if the tag of the chain matches its links for the issuer `key`, and every link has the same
+    /// [`PermissionManager`](crate::PermissionManager) and is contained in the previous one.
+    pub fn verify(&self, key: &[u8]) -> bool {
+        self.has_valid_tag(key) && is_attenuated(&self.links)
+    }
+
+    // Returns `true` if the tag of the chain is the one chained from the issuer key through its links
+    fn has_valid_tag(&self, key: &[u8]) -> bool {
+        let (last, previous) = self
+            .links
+            .split_last()
+            .expect("unexpected error, permission chain should not be empty");
+
+        signing::verify(
+            &chain_tag(key, previous),
+            last.to_json().as_bytes(),
+            &self.tag,
+        )
+    }
+
+    /// Returns the chain as a JSON object with the actions of every link as `links`, from the issued [`Permission`](crate::Permission)
+    /// to the granted one, in the format of [`Permission::to_json`](crate::Permission::to_json()), and the URL-safe base64 tag
+    /// of the chain as `tag`.
+    ///
+    /// # Examples:
+    ///
+    /// ```
+    /// use simple_perm_manager::PermissionManager;
+    ///
+    /// let manager = PermissionManager::from_json(r#"{"view": true, "edit": true}"#);
+    /// let chain = manager
+    ///     .perm_from_json(r#"{"view": true, "edit": true}"#)
+    ///     .to_chain(b"issuer key")
+    ///     .attenuate(&manager.perm_from_json(r#"{"view": true}"#));
+    ///
+    /// let received = manager.chain_from_json(&chain.to_json(), b"issuer key").unwrap();
+    /// assert_eq!(received.permission().get_actions(), chain.permission().get_actions());
+    /// ```
+    pub fn to_json(&self) -> String {
+        let links: Vec<Value> = self
+            .links
+            .iter()
+            .map(|link| {
+                serde_json::from_str(&link.to_json())
+                    .expect("unexpected error, permission JSON should always be valid")
+            })
+            .collect();
+
+        json!({"links": links, "tag": URL_SAFE_NO_PAD.encode(&self.tag)}).to_string()
+    }
+}
+
+impl Permission {
+    /// Returns a [`PermissionChain`](crate::PermissionChain) issued with the calling [`Permission`](crate::Permission) as its only
+    /// link, signed with the issuer `key`.
+    ///
+    /// # Examples:
+    ///
+    /// ```
+    /// use simple_perm_manager::PermissionManager;
+    ///
+    /// let manager = PermissionManager::from_json(r#"{"view": true, "edit": true}"#);
+    /// let perm = manager.perm_from_json(r#"{"view": true, "edit": true}"#);
+    ///
+    /// let chain = perm.to_chain(b"issuer key").attenuate(&manager.perm_from_json(r#"{"view": true}"#));
+    /// assert!(chain.permission().contains_action("view"));
+    /// assert!(!chain.permission().contains_action("edit"));
+    /// assert!(chain.root().contains_action("edit"));
+    /// ```
+    pub fn to_chain(&self, key: &[u8]) -> PermissionChain {
+        PermissionChain::new(self.clone(), key)
+    }
+}
+
+impl PermissionManager {
+    /// Returns the [`PermissionChain`](crate::PermissionChain) from a JSON string created with
+    /// [`PermissionChain::to_json`](crate::PermissionChain::to_json()), checking its tag with the issuer `key` and that every link
+    /// is contained in the previous one.
+    ///
+    /// # Errors:
+    ///
+    /// - Returns [`ValidationError::InvalidJson`](crate::ValidationError::InvalidJson) if `chain_json` is not a JSON object with a
+    ///   non empty array of actions JSON objects as `links` and a base64 string as `tag`.
+    /// - Returns [`ValidationError::UnknownActionAt`](crate::ValidationError::UnknownActionAt) if a link has an action not in the universe.
+    /// - Returns [`ValidationError::InvalidSignature`](crate::ValidationError::InvalidSignature) if the tag does not match the
+    ///   links for `key`, i.e. the chain was not issued with `key` or was tampered with.
+    /// - Returns [`ValidationError::NotDelegable`](crate::ValidationError::NotDelegable) with the sorted actions of the first link
+    ///   not held by its parent.
+    pub fn chain_from_json(
+        &self,
+        chain_json: &str,
+        key: &[u8],
+    ) -> Result<PermissionChain, ValidationError> {
+        let chain = match serde_json::from_str::<Value>(chain_json) {
+            Ok(Value::Object(chain)) => chain,
+            Ok(_) => return Err(invalid("expected an object")),
+            Err(error) => {
+                telemetry::record_parse_error();
+                return Err(ValidationError::InvalidJson(error.to_string()));
+            }
+        };
+        let links = match chain.get("links") {
+            Some(Value::Array(links)) if !links.is_empty() => links,
+            _ => return Err(invalid("expected a non empty array as links")),
+        };
+        let Some(tag) = chain
+            .get("tag")
+            .and_then(Value::as_str)
+            .and_then(|tag| URL_SAFE_NO_PAD.decode(tag).ok())
+        else {
+            return Err(invalid("expected a base64 string as tag"));
+        };
+
+        let links = links
+            .iter()
+            .map(|link| self.perm_from_json_strict(&link.to_string()))
+            .collect::<Result<Vec<Permission>, ValidationError>>()?;
+        let chain = PermissionChain { links, tag };
+        if !chain.has_valid_tag(key) {
+            telemetry::record_validation_failure();
+            return Err(ValidationError::InvalidSignature);
+        }
+        for pair in chain.links.windows(2) {
+            pair[0].delegate(&pair[1])?;
+        }
+
+        Ok(chain)
+    }
+}
@@ -0,0 +1,196 @@
+use super::*;
+use std::collections::HashSet;
+
+const KEY: &[u8] = b"issuer key";
+
+fn manager() -> PermissionManager {
+    PermissionManager::from_json(r#"{"post": {"view": true, "edit": true, "delete": true}}"#)
+}
+
+#[test]
+fn attenuate_test() {
+    let manager = manager();
+    let perm = manager.perm_from_json(r#"{"post": {"view": true, "edit": true}}"#);
+
+    let chain = perm
+        .to_chain(KEY)
+        .attenuate(&manager.perm_from_json(r#"{"post": {"edit": true, "delete": true}}"#));
+    assert_eq!(chain.len(), 2);
+    assert_eq!(chain.is_empty(), false);
+    assert_eq!(chain.root().get_actions(), perm.get_actions());
+    assert_eq!(
+        *chain.permission().get_actions(),
+        HashSet::from([String::from("post:edit")])
+    );
+
+    // Caveats never widen the chain
+    let chain =
+        chain.attenuate(&manager.perm_from_json(r#"{"post": {"view": true, "edit": true}}"#));
+    assert_eq!(chain.len(), 3);
+    assert_eq!(
+        *chain.permission().get_actions(),
+        HashSet::from([String::from("post:edit")])
+    );
+    assert_eq!(chain.verify(KEY), true);
+
+    let chain = chain.attenuate(&manager.perm_from_json(r#"{"post": {"view": true}}"#));
+    assert_eq!(chain.permission().get_actions().is_empty(), true);
+    assert_eq!(chain.links().len(), 4);
+    assert_eq!(chain.verify(KEY), true);
+}
+
+#[test]
+fn attenuate_different_manager_test() {
+    let result = std::panic::catch_unwind(|| {
+        let manager = manager();
+        let perm = manager.perm_from_json(r#"{"post": {"view": true}}"#);
+        let unmanaged = Permission::from_json(r#"{"post": {"view": true}}"#);
+
+        perm.to_chain(KEY).attenuate(&unmanaged)
+    });
+
+    match result {
+        Ok(_) => panic!("Attenuation with a permission of another manager should panic"),
+        Err(_) => (),
+    }
+}
+
+#[test]
+fn verify_test() {
+    let manager = manager();
+    let chain = PermissionChain::new(manager.perm_from_json(r#"{"post": {"view": true}}"#), KEY);
+    assert_eq!(chain.verify(KEY), true);
+    assert_eq!(chain.verify(b"other key"), false);
+
+    // Widening links fails even with a valid tag
+    let mut widened = chain.clone();
+    widened
+        .links
+        .push(manager.perm_from_json(r#"{"post": {"view": true, "edit": true}}"#));
+    widened.tag = chain_tag(KEY, &widened.links);
+    assert_eq!(widened.verify(KEY), false);
+
+    let mut unmanaged = chain.clone();
+    unmanaged
+        .links
+        .push(Permission::from_json(r#"{"post": {"view": true}}"#));
+    unmanaged.tag = chain_tag(KEY, &unmanaged.links);
+    assert_eq!(unmanaged.verify(KEY), false);
+
+    // Replacing or removing links breaks the tag
+    let attenuated = chain.attenuate(&manager.perm_from_json("{}"));
+    let mut replaced = attenuated.clone();
+    replaced.links[0] = manager.perm_from_json(r#"{"post": true}"#);
+    assert_eq!(replaced.verify(KEY), false);
+
+    let mut removed = attenuated.clone();
+    removed.links.pop();
+    assert_eq!(removed.verify(KEY), false);
+}
+
+#[test]
+fn json_test() {
+    let manager = manager();
+    let chain = manager
+        .perm_from_json(r#"{"post": {"view": true, "edit": true}}"#)
+        .to_chain(KEY)
+        .attenuate(&manager.perm_from_json(r#"{"post": {"view": true}}"#));
+
+    let received = manager.chain_from_json(&chain.to_json(), KEY).unwrap();
+    assert_eq!(received.len(), 2);
+    assert_eq!(received.root().get_actions(), chain.root().get_actions());
+    assert_eq!(
+        received.permission().get_actions(),
+        chain.permission().get_actions()
+    );
+    assert_eq!(manager.validate_perm(received.permission()), true);
+    assert_eq!(received.verify(KEY), true);
+
+    // Receivers can keep attenuating the chain
+    let forwarded = received.attenuate(&manager.perm_from_json("{}"));
+    assert_eq!(
+        manager
+            .chain_from_json(&forwarded.to_json(), KEY)
+            .unwrap()
+            .len(),
+        3
+    );
+}
+
+#[test]
+fn chain_from_json_errors_test() {
+    let manager = manager();
+    let chain = manager
+        .perm_from_json(r#"{"post": {"view": true}}"#)
+        .to_chain(KEY);
+    let tag = URL_SAFE_NO_PAD.encode(&chain.tag);
+
+    assert_eq!(
+        manager
+            .chain_from_json(&chain.to_json(), b"other key")
+            .unwrap_err(),
+        ValidationError::InvalidSignature
+    );
+    // Dropping the caveats or widening a link breaks the tag
+    let attenuated = chain.attenuate(&manager.perm_from_json("{}"));
+    assert_eq!(
+        manager
+            .chain_from_json(
+                &format!(
+                    r#"{{"links": [{{"post": {{"view": true}}}}], "tag": "{}"}}"#,
+                    URL_SAFE_NO_PAD.encode(&attenuated.tag)
+                ),
+                KEY
+            )
+            .unwrap_err(),
+        ValidationError::InvalidSignature
+    );
+    assert_eq!(
+        manager
+            .chain_from_json(
+                &format!(
+                    r#"{{"links": [{{"post": {{"view": true, "delete": true}}}}], "tag": "{}"}}"#,
+                    tag
+                ),
+                KEY
+            )
+            .unwrap_err(),
+        ValidationError::InvalidSignature
+    );
+
+    let mut widened = chain.clone();
+    widened
+        .links
+        .push(manager.perm_from_json(r#"{"post": {"view": true, "delete": true}}"#));
+    widened.tag = chain_tag(KEY, &widened.links);
+    assert_eq!(
+        manager
+            .chain_from_json(&widened.to_json(), KEY)
+            .unwrap_err(),
+        ValidationError::NotDelegable(vec![String::from("post:delete")])
+    );
+    assert_eq!(
+        manager
+            .chain_from_json(
+                &format!(
+                    r#"{{"links": [{{"post": {{"publish": true}}}}], "tag": "{}"}}"#,
+                    tag
+                ),
+                KEY
+            )
+            .unwrap_err(),
+        ValidationError::UnknownActionAt(String::from("/post/publish"))
+    );
+    for chain_json in [
+        r#"{"links": [], "tag": "AA"}"#,
+        r#"{"links": [{"post": {"view": true}}]}"#,
+        r#"{"links": [{"post": {"view": true}}], "tag": "not base64!"}"#,
+        r#"[{"post": {"view": true}}]"#,
+        "[{",
+    ] {
+        match manager.chain_from_json(chain_json, KEY) {
+            Err(ValidationError::InvalidJson(_)) => (),
+            _ => panic!("chain {} should have failed", chain_json),
+        }
+    }
+}
@@ -0,0 +1,160 @@
+use crate::permission::Permission;
+use crate::telemetry;
+use crate::validation_error::ValidationError;
+use serde_json::{Map, Value};
+use std::collections::HashSet;
+
+//// TESTS ////
+#[cfg(test)]
+mod tests;
+
+/// Actions added and removed between two states of a [`Permission`](crate::Permission), applied with
+/// [`Permission::apply_delta`](crate::Permission::apply_delta()), e.g. to sync only the changes of a permission to edge caches.
+///
+/// # Examples:
+///
+/// ```
+/// use simple_perm_manager::{PermissionDelta, PermissionManager};
+///
+/// let manager = PermissionManager::from_json(r#"{"post": {"view": true, "edit": true, "delete": true}}"#);
+/// let before = manager.perm_from_json(r#"{"post": {"view": true, "edit": true}}"#);
+/// let after = manager.perm_from_json(r#"{"post": {"view": true, "delete": true}}"#);
+///
+/// let delta = PermissionDelta::between(&before, &after);
+/// assert_eq!(delta.to_json(), r#"{"added":["post:delete"],"removed":["post:edit"]}"#);
+///
+/// // On the other side of the wire
+/// let delta = PermissionDelta::from_json(&delta.to_json()).unwrap();
+/// assert_eq!(before.apply_delta(&delta).get_actions(), after.get_actions());
+/// ```
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct PermissionDelta {
+    added: HashSet<String>,
+    removed: HashSet<String>,
+}
+
+impl PermissionDelta {
+    /// Creates a [`PermissionDelta`](crate::PermissionDelta) from the actions added and removed.  
+    /// Actions both added and removed are added.
+    pub fn new(added: HashSet<String>, removed: HashSet<String>) -> PermissionDelta {
+        let removed = removed.difference(&added).cloned().collect();
+        PermissionDelta { added, removed }
+    }
+
+    /// Returns the [`PermissionDelta`](crate::PermissionDelta) turning the actions of `from` into the actions of `to`.
+    ///
+    /// # Panics:
+    ///
+    /// Panics if the [`Permission`](crate::Permission)s provided do not have the same [`PermissionManager`](crate::PermissionManager).
+    pub fn between(from: &Permission, to: &Permission) -> PermissionDelta {
+        if !from.has_same_manager(to) {
+            panic!("Permissions in delta operation do not have same manager");
+        }
+
+        PermissionDelta {
+            added: to
+                .actions()
+                .filter(|action| !from.has_action(action))
+                .map(String::from)
+                .collect(),
+            removed: from
+                .actions()
+                .filter(|action| !to.has_action(action))
+                .map(String::from)
+                .collect(),
+        }
+    }
+
+    /// Returns the actions added.
+    pub fn added(&self) -> &HashSet<String> {
+        &self.added
+    }
+
+    /// Returns the actions removed.
+    pub fn removed(&self) -> &HashSet<String> {
+        &self.removed
+    }
+
+    /// Returns `true` if the [`PermissionDelta`](crate::PermissionDelta) neither adds nor removes actions.
+    pub fn is_empty(&self) -> bool {
+        self.added.is_empty() && self.removed.is_empty()
+    }
+
+    /// Returns the [`PermissionDelta`](crate::PermissionDelta) equivalent to applying this one and then `next`.
+    ///
+    /// # Examples:
+    ///
+    /// ```
+    /// use std::collections::HashSet;
+    /// use simple_perm_manager::PermissionDelta;
+    ///
+    /// let first = PermissionDelta::new(HashSet::from([String::from("edit")]), HashSet::from([String::from("view")]));
+    /// let second = PermissionDelta::new(HashSet::from([String::from("view")]), HashSet::from([String::from("edit")]));
+    ///
+    /// let combined = first.then(&second);
+    /// assert_eq!(combined.added(), &HashSet::from([String::from("view")]));
+    /// assert_eq!(combined.removed(), &HashSet::from([String::from("edit")]));
+    /// ```
+    pub fn then(&self, next: &PermissionDelta) -> PermissionDelta {
+        let added: HashSet<String> = self
+            .added
+            .difference(&next.removed)
+            .chain(&next.added)
+            .cloned()
+            .collect();
+        let removed = self
+            .removed
+            .difference(&next.added)
+            .chain(&next.removed)
+            .cloned()
+            .collect();
+
+        PermissionDelta::new(added, removed)
+    }
+
+    /// Serializes the [`PermissionDelta`](crate::PermissionDelta) into a JSON string with the format
+    /// `{"added": [...], "removed": [...]}`, with the actions sorted.
+    pub fn to_json(&self) -> String {
+        let sorted = |actions: &HashSet<String>| {
+            let mut actions: Vec<String> = actions.iter().cloned().collect();
+            actions.sort();
+            Value::from(actions)
+        };
+
+        let mut map = Map::new();
+        map.insert(String::from("added"), sorted(&self.added));
+        map.insert(String::from("removed"), sorted(&self.removed));
+        Value::Object(map).to_string()
+    }
+
+    /// Deserializes a [`PermissionDelta`](crate::PermissionDelta) serialized with [`to_json`](Self::to_json()). Missing lists are empty.
+    ///
+    /// # Errors:
+    ///
+    /// Returns [`ValidationError::InvalidJson`](crate::ValidationError::InvalidJson) if `json` is not a valid delta JSON string.
+    pub fn from_json(json: &str) -> Result<PermissionDelta, ValidationError> {
+        let invalid = || {
+            telemetry::record_parse_error();
+            ValidationError::InvalidJson(String::from(
+                "wrong format in permission delta json string",
+            ))
+        };
+        let map = match serde_json::from_str(json) {
+            Ok(Value::Object(map)) => map,
+            _ => return Err(invalid()),
+        };
+        let actions = |key: &str| match map.get(key) {
+            None => Some(HashSet::new()),
+            Some(Value::Array(actions)) => actions
+                .iter()
+                .map(|action| action.as_str().map(String::from))
+                .collect(),
+            Some(_) => None,
+        };
+
+        match (actions("added"), actions("removed")) {
+            (Some(added), Some(removed)) => Ok(PermissionDelta::new(added, removed)),
+            _ => Err(invalid()),
+        }
+    }
+}
@@ -0,0 +1,63 @@
+use super::*;
+use crate::PermissionManager;
+
+fn actions(actions: &[&str]) -> HashSet<String> {
+    actions.iter().map(|action| action.to_string()).collect()
+}
+
+#[test]
+fn between_test() {
+    let manager = PermissionManager::from_json(r#"{"view": true, "edit": true, "delete": true}"#);
+    let before = manager.perm_from_json(r#"{"view": true, "edit": true}"#);
+    let after = manager.perm_from_json(r#"{"view": true, "delete": true}"#);
+
+    let delta = PermissionDelta::between(&before, &after);
+    assert_eq!(*delta.added(), actions(&["delete"]));
+    assert_eq!(*delta.removed(), actions(&["edit"]));
+    assert_eq!(delta.is_empty(), false);
+    assert_eq!(PermissionDelta::between(&after, &after).is_empty(), true);
+
+    let other = Permission::from_json(r#"{"view": true}"#);
+    let result = std::panic::catch_unwind(|| PermissionDelta::between(&before, &other));
+    assert_eq!(result.is_err(), true);
+}
+
+#[test]
+fn new_test() {
+    let delta = PermissionDelta::new(actions(&["view", "edit"]), actions(&["edit", "delete"]));
+
+    assert_eq!(*delta.added(), actions(&["view", "edit"]));
+    assert_eq!(*delta.removed(), actions(&["delete"]));
+    assert_eq!(PermissionDelta::default().is_empty(), true);
+}
+
+#[test]
+fn then_test() {
+    let first = PermissionDelta::new(actions(&["a", "b"]), actions(&["c"]));
+    let second = PermissionDelta::new(actions(&["c"]), actions(&["a", "d"]));
+
+    assert_eq!(
+        first.then(&second),
+        PermissionDelta::new(actions(&["b", "c"]), actions(&["a", "d"]))
+    );
+}
+
+#[test]
+fn json_test() {
+    let delta = PermissionDelta::new(actions(&["b", "a"]), actions(&["c"]));
+
+    assert_eq!(delta.to_json(), r#"{"added":["a","b"],"removed":["c"]}"#);
+    assert_eq!(PermissionDelta::from_json(&delta.to_json()), Ok(delta));
+    assert_eq!(
+        PermissionDelta::from_json(r#"{"added": ["a"]}"#),
+        Ok(PermissionDelta::new(actions(&["a"]), HashSet::new()))
+    );
+    for json in ["[]", "not json", r#"{"added": "a"}"#, r#"{"removed": [1]}"#] {
+        assert_eq!(
+            PermissionDelta::from_json(json),
+            Err(ValidationError::InvalidJson(String::from(
+                "wrong format in permission delta json string"
+            )))
+        );
+    }
+}
@@ -0,0 +1,349 @@
+use crate::permission::ACTION_DIVIDER;
+use base64::engine::general_purpose::URL_SAFE_NO_PAD;
+use base64::Engine;
+use std::borrow::Cow;
+use std::collections::{HashMap, HashSet};
+use std::sync::Arc;
+
+//// TESTS ////
+#[cfg(test)]
+mod tests;
+
+#[doc(hidden)]
+/// Canonical ordering of the actions in a [`PermissionManager`](crate::PermissionManager) universe.
+///
+/// Actions are sorted lexicographically and each one is assigned the position it holds in that order.
+/// Positions are used as bit indexes when encoding a set of actions as a bitset, so two managers
+/// with the same universe always produce the same encoding for the same actions.
+///
+/// Action names are interned: each name is allocated once as an `Arc<str>` shared by the position
+/// table and the lookup map, and handed out by [`intern`](Self::intern) to anyone needing a name
+/// that outlives a borrow of the index.
+///
+/// The index also holds the aliases of the universe actions, so permissions linked to it can resolve legacy names.
+#[derive(Clone, Debug)]
+pub struct ActionIndex {
+    actions: Vec<Arc<str>>,
+    positions: HashMap<Arc<str>, usize>,
+    aliases: HashMap<String, Arc<str>>,
+    // Actions mapped to every action they imply, directly or transitively
+    implications: HashMap<Arc<str>, HashSet<Arc<str>>>,
+    separator: char,
+    case_insensitive: bool,
+    // Hash of the actions, computed once when the index is built since it only depends on them
+    fingerprint: [u8; 8],
+}
+
+// Returns the 64 bit FNV-1a hash of the actions in canonical order as big endian bytes
+fn hash_actions(actions: &[Arc<str>]) -> [u8; 8] {
+    let mut hash: u64 = 0xcbf2_9ce4_8422_2325;
+    for action in actions {
+        // Each action is terminated by a zero byte so ["ab", "c"] and ["a", "bc"] hash differently
+        for byte in action.bytes().chain(std::iter::once(0)) {
+            hash ^= u64::from(byte);
+            hash = hash.wrapping_mul(0x0100_0000_01b3);
+        }
+    }
+
+    hash.to_be_bytes()
+}
+
+impl ActionIndex {
+    /// Creates the canonical index for the universe actions provided.
+    pub fn from_actions(universe_actions: &HashSet<String>) -> ActionIndex {
+        let mut actions: Vec<Arc<str>> = universe_actions
+            .iter()
+            .map(|action| Arc::from(action.as_str()))
+            .collect();
+        actions.sort();
+
+        let positions: HashMap<Arc<str>, usize> = actions
+            .iter()
+            .enumerate()
+            .map(|(position, action)| (action.clone(), position))
+            .collect();
+
+        ActionIndex {
+            fingerprint: hash_actions(&actions),
+            actions,
+            positions,
+            aliases: HashMap::new(),
+            implications: HashMap::new(),
+            separator: ACTION_DIVIDER,
+            case_insensitive: false,
+        }
+    }
+
+    /// Returns the index with the character dividing the groups and name of its actions, [`ACTION_DIVIDER`](crate::ACTION_DIVIDER) by default.
+    pub fn with_separator(mut self, separator: char) -> ActionIndex {
+        self.separator = separator;
+        self
+    }
+
+    /// Returns the character dividing the groups and name of the actions of the index.
+    pub fn separator(&self) -> char {
+        self.separator
+    }
+
+    /// Returns the index with case-insensitive matching enabled or disabled. The actions of a case-insensitive index must be case-folded.
+    pub fn with_case_insensitive(mut self, case_insensitive: bool) -> ActionIndex {
+        self.case_insensitive = case_insensitive;
+        self
+    }
+
+    /// Returns the action case-folded (lowercased) if the index is case-insensitive or the action unchanged if it is not.
+    pub fn fold<'a>(&self, action: &'a str) -> Cow<'a, str> {
+        if self.case_insensitive && action.chars().any(char::is_uppercase) {
+            Cow::Owned(action.to_lowercase())
+        } else {
+            Cow::Borrowed(action)
+        }
+    }
+
+    /// Returns the index with the aliases provided, mapping alias names to universe actions.  
+    /// Aliases of actions that are not in the index and aliases that are index actions themselves are ignored.
+    pub fn with_aliases(mut self, aliases: &HashMap<String, String>) -> ActionIndex {
+        self.aliases = aliases
+            .iter()
+            .filter(|(alias, _)| self.position(alias).is_none())
+            .filter_map(|(alias, action)| self.intern(action).map(|action| (alias.clone(), action)))
+            .collect();
+        self
+    }
+
+    /// Returns the index with the implications provided, mapping actions to the actions that granting them implies.  
+    /// Implications are followed transitively and implications between actions that are not in the index are ignored.
+    pub fn with_implications(
+        mut self,
+        implications: &HashMap<String, HashSet<String>>,
+    ) -> ActionIndex {
+        let direct: HashMap<Arc<str>, Vec<Arc<str>>> = implications
+            .iter()
+            .filter_map(|(action, implied)| {
+                let implied: Vec<Arc<str>> = implied
+                    .iter()
+                    .filter_map(|implied| self.intern(implied))
+                    .collect();
+                self.intern(action).map(|action| (action, implied))
+            })
+            .collect();
+
+        self.implications = direct
+            .keys()
+            .map(|action| {
+                let mut closure: HashSet<Arc<str>> = HashSet::new();
+                let mut pending: Vec<&Arc<str>> = vec![action];
+                while let Some(current) = pending.pop() {
+                    for implied in direct.get(current).into_iter().flatten() {
+                        if implied != action && closure.insert(implied.clone()) {
+                            pending.push(implied);
+                        }
+                    }
+                }
+                (action.clone(), closure)
+            })
+            .filter(|(_, closure)| !closure.is_empty())
+            .collect();
+        self
+    }
+
+    /// Returns an index of the actions provided that are in this index, sharing the interned names of this index.  
+    /// Aliases of the actions kept are kept, and so are the implications between actions kept, even when they were followed
+    /// through actions that are not kept.
+    pub fn restricted(&self, actions: &HashSet<String>) -> ActionIndex {
+        let actions: Vec<Arc<str>> = self
+            .actions
+            .iter()
+            .filter(|action| actions.contains(action.as_ref()))
+            .cloned()
+            .collect();
+        let positions: HashMap<Arc<str>, usize> = actions
+            .iter()
+            .enumerate()
+            .map(|(position, action)| (action.clone(), position))
+            .collect();
+
+        let aliases = self
+            .aliases
+            .iter()
+            .filter(|(_, action)| positions.contains_key(*action))
+            .map(|(alias, action)| (alias.clone(), action.clone()))
+            .collect();
+        let implications = self
+            .implications
+            .iter()
+            .filter(|(action, _)| positions.contains_key(*action))
+            .map(|(action, implied)| {
+                let implied: HashSet<Arc<str>> = implied
+                    .iter()
+                    .filter(|implied| positions.contains_key(*implied))
+                    .cloned()
+                    .collect();
+                (action.clone(), implied)
+            })
+            .filter(|(_, implied)| !implied.is_empty())
+            .collect();
+
+        ActionIndex {
+            fingerprint: hash_actions(&actions),
+            actions,
+            positions,
+            aliases,
+            implications,
+            separator: self.separator,
+            case_insensitive: self.case_insensitive,
+        }
+    }
+
+    /// Returns the actions implied by an action, directly or transitively.
+    pub fn implied(&self, action: &str) -> impl Iterator<Item = &str> + '_ {
+        self.implications
+            .get(action)
+            .into_iter()
+            .flatten()
+            .map(|implied| implied.as_ref())
+    }
+
+    /// Returns `true` if any of the actions provided implies the action.
+    pub fn is_implied<I, S>(&self, actions: I, action: &str) -> bool
+    where
+        I: IntoIterator<Item = S>,
+        S: AsRef<str>,
+    {
+        actions.into_iter().any(|granted| {
+            self.implied(granted.as_ref())
+                .any(|implied| implied == action)
+        })
+    }
+
+    /// Returns the action an alias refers to or `None` if the alias is not in the index.
+    pub fn alias(&self, alias: &str) -> Option<&str> {
+        self.aliases.get(alias).map(|action| action.as_ref())
+    }
+
+    /// Returns the index action for an action or alias, or `None` if it is neither in the index.
+    pub fn resolve<'a>(&'a self, action: &'a str) -> Option<&'a str> {
+        match self.position(action) {
+            Some(_) => Some(action),
+            None => self.alias(action),
+        }
+    }
+
+    /// Returns the number of actions in the index.
+    pub fn len(&self) -> usize {
+        self.actions.len()
+    }
+
+    /// Returns the number of bytes needed to encode a set of actions of this index.
+    pub fn encoded_len(&self) -> usize {
+        self.len().div_ceil(8)
+    }
+
+    /// Returns the canonical position of an action or `None` if the action is not in the index.
+    pub fn position(&self, action: &str) -> Option<usize> {
+        self.positions.get(action).copied()
+    }
+
+    /// Returns the actions of the index in canonical order.
+    pub fn actions(&self) -> impl Iterator<Item = &str> + '_ {
+        self.actions.iter().map(|action| action.as_ref())
+    }
+
+    /// Returns the action in the canonical position provided or `None` if it is out of bounds.
+    pub fn action(&self, position: usize) -> Option<&str> {
+        self.actions.get(position).map(|action| action.as_ref())
+    }
+
+    /// Returns the actions of the index beneath a group node in canonical order, e.g. `"building:view"` and
+    /// `"building:room:edit"` for the group `"building"`.
+    pub fn group_actions<'a>(&'a self, group: &str) -> impl Iterator<Item = &'a str> + 'a {
+        let prefix = format!("{}{}", group, self.separator);
+        // Actions sharing a prefix are contiguous in the canonical (lexicographic) order
+        let start = self
+            .actions
+            .partition_point(|action| action.as_ref() < prefix.as_str());
+
+        self.actions[start..]
+            .iter()
+            .map(|action| action.as_ref())
+            .take_while(move |action| action.starts_with(&prefix))
+    }
+
+    /// Returns the shared interned name of an action or `None` if the action is not in the index.
+    pub fn intern(&self, action: &str) -> Option<Arc<str>> {
+        self.positions
+            .get_key_value(action)
+            .map(|(interned, _)| interned.clone())
+    }
+
+    /// Encodes a set of actions as a bitset where bit `n` (least significant bit first inside each byte)
+    /// is set if the action in canonical position `n` is present.
+    ///
+    /// # Panics
+    ///
+    /// Panics if any of the actions provided is not in the index.
+    pub fn encode<I, S>(&self, actions: I) -> Vec<u8>
+    where
+        I: IntoIterator<Item = S>,
+        S: AsRef<str>,
+    {
+        let mut bytes: Vec<u8> = vec![0; self.encoded_len()];
+
+        for action in actions {
+            let position = self
+                .position(action.as_ref())
+                .expect("action to encode is not present in the action index");
+            bytes[position / 8] |= 1 << (position % 8);
+        }
+
+        bytes
+    }
+
+    /// Decodes a bitset created with [`encode`](Self::encode) back into a set of actions.
+    ///
+    /// # Panics
+    ///
+    /// - Panics if `bytes` length does not match the encoded length for this index.
+    /// - Panics if `bytes` has bits set for positions outside the index.
+    pub fn decode(&self, bytes: &[u8]) -> HashSet<String> {
+        self.try_decode(bytes)
+            .unwrap_or_else(|error| panic!("{}", error))
+    }
+
+    /// Same as [`decode`](Self::decode) but returns the error message instead of panicking.
+    pub fn try_decode(&self, bytes: &[u8]) -> Result<HashSet<String>, String> {
+        if bytes.len() != self.encoded_len() {
+            return Err(String::from(
+                "wrong length in encoded actions - does not match the action index",
+            ));
+        }
+
+        let mut actions: HashSet<String> = HashSet::new();
+
+        for (byte_number, byte) in bytes.iter().enumerate() {
+            for bit in 0..8 {
+                if byte & (1 << bit) != 0 {
+                    match self.action(byte_number * 8 + bit) {
+                        Some(action) => actions.insert(action.to_string()),
+                        None => return Err(String::from(
+                            "wrong format in encoded actions - bit set outside the action index",
+                        )),
+                    };
+                }
+            }
+        }
+
+        Ok(actions)
+    }
+
+    /// Returns a fingerprint of the actions of the index: their 64 bit FNV-1a hash in canonical order, as unpadded URL-safe base64.  
+    /// Indexes with the same actions have the same fingerprint, so it tells whether a bitset was encoded for this index.
+    pub fn fingerprint(&self) -> String {
+        URL_SAFE_NO_PAD.encode(self.fingerprint_bytes())
+    }
+
+    /// Returns the fingerprint of the actions of the index as the big endian bytes of their hash, before base64 encoding.
+    pub fn fingerprint_bytes(&self) -> [u8; 8] {
+        self.fingerprint
+    }
+}
@@ -0,0 +1,279 @@
+use super::*;
+
+#[test]
+fn from_actions_test() {
+    let index = ActionIndex::from_actions(&HashSet::from([
+        String::from("view"),
+        String::from("create"),
+        String::from("edit"),
+    ]));
+
+    // Actions are sorted lexicographically
+    assert_eq!(index.len(), 3);
+    assert_eq!(index.position("create"), Some(0));
+    assert_eq!(index.position("edit"), Some(1));
+    assert_eq!(index.position("view"), Some(2));
+    assert_eq!(index.position("other"), None);
+    assert_eq!(index.action(1), Some("edit"));
+    assert_eq!(index.action(3), None);
+}
+
+#[test]
+fn intern_test() {
+    let index = ActionIndex::from_actions(&HashSet::from([
+        String::from("view"),
+        String::from("create"),
+    ]));
+
+    let view = index.intern("view").unwrap();
+    let view_again = index.intern("view").unwrap();
+
+    // Every call returns the same allocation, shared with the index tables
+    assert_eq!(&*view, "view");
+    assert!(Arc::ptr_eq(&view, &view_again));
+    assert!(std::ptr::eq(
+        view.as_ptr(),
+        index.action(1).unwrap().as_ptr()
+    ));
+    assert_eq!(index.intern("other"), None);
+}
+
+#[test]
+fn encoded_len_test() {
+    let empty = ActionIndex::from_actions(&HashSet::from([]));
+    let actions: HashSet<String> = (0..9).map(|n| format!("action_{}", n)).collect();
+    let nine = ActionIndex::from_actions(&actions);
+
+    assert_eq!(empty.encoded_len(), 0);
+    assert_eq!(nine.encoded_len(), 2);
+}
+
+#[test]
+fn encode_test() {
+    let actions: HashSet<String> = (0..10).map(|n| format!("action_{}", n)).collect();
+    let index = ActionIndex::from_actions(&actions);
+
+    assert_eq!(index.encode(HashSet::<String>::new()), vec![0, 0]);
+    assert_eq!(
+        index.encode(HashSet::from([
+            String::from("action_0"),
+            String::from("action_2"),
+            String::from("action_9"),
+        ])),
+        vec![0b0000_0101, 0b0000_0010]
+    );
+    assert_eq!(index.encode(&actions), vec![0b1111_1111, 0b0000_0011]);
+
+    match std::panic::catch_unwind(|| {
+        index.encode(HashSet::from([String::from("other")]));
+    }) {
+        Ok(_) => panic!("encoding an action not present in the index should have panicked"),
+        Err(_) => (),
+    }
+}
+
+#[test]
+fn decode_test() {
+    let actions: HashSet<String> = (0..10).map(|n| format!("action_{}", n)).collect();
+    let index = ActionIndex::from_actions(&actions);
+    let some_actions = HashSet::from([String::from("action_1"), String::from("action_8")]);
+
+    assert_eq!(index.decode(&index.encode(&some_actions)), some_actions);
+    assert_eq!(index.decode(&index.encode(&actions)), actions);
+
+    // Wrong length
+    match std::panic::catch_unwind(|| {
+        index.decode(&[0]);
+    }) {
+        Ok(_) => panic!("decoding bytes with wrong length should have panicked"),
+        Err(_) => (),
+    }
+
+    // Bit set outside the index
+    match std::panic::catch_unwind(|| {
+        index.decode(&[0, 0b0000_0100]);
+    }) {
+        Ok(_) => panic!("decoding bytes with bits outside the index should have panicked"),
+        Err(_) => (),
+    }
+}
+
+#[test]
+fn try_decode_test() {
+    let actions: HashSet<String> = (0..10).map(|n| format!("action_{}", n)).collect();
+    let index = ActionIndex::from_actions(&actions);
+
+    assert_eq!(index.try_decode(&index.encode(&actions)), Ok(actions));
+    assert_eq!(index.try_decode(&[0]).is_err(), true);
+    assert_eq!(index.try_decode(&[0, 0b0000_0100]).is_err(), true);
+}
+
+#[test]
+fn fingerprint_test() {
+    let index =
+        ActionIndex::from_actions(&HashSet::from([String::from("view"), String::from("edit")]));
+    let same =
+        ActionIndex::from_actions(&HashSet::from([String::from("edit"), String::from("view")]))
+            .with_separator('.');
+    let split =
+        ActionIndex::from_actions(&HashSet::from([String::from("editv"), String::from("iew")]));
+
+    assert_eq!(index.fingerprint().len(), 11);
+    assert_eq!(index.fingerprint(), same.fingerprint());
+    assert_ne!(index.fingerprint(), split.fingerprint());
+    assert_ne!(
+        index.fingerprint(),
+        ActionIndex::from_actions(&HashSet::new()).fingerprint()
+    );
+
+    // Restricted indexes have the fingerprint of an index built from their actions
+    assert_eq!(
+        index
+            .restricted(&HashSet::from([String::from("view")]))
+            .fingerprint_bytes(),
+        ActionIndex::from_actions(&HashSet::from([String::from("view")])).fingerprint_bytes()
+    );
+}
+
+#[test]
+fn aliases_test() {
+    let index = ActionIndex::from_actions(&HashSet::from([
+        String::from("delete"),
+        String::from("view"),
+    ]))
+    .with_aliases(&HashMap::from([
+        (String::from("remove"), String::from("delete")),
+        (String::from("stale"), String::from("other")),
+    ]));
+
+    assert_eq!(index.alias("remove"), Some("delete"));
+    assert_eq!(index.alias("stale"), None);
+    assert_eq!(index.alias("delete"), None);
+    assert_eq!(index.resolve("remove"), Some("delete"));
+    assert_eq!(index.resolve("view"), Some("view"));
+    assert_eq!(index.resolve("other"), None);
+    // Aliases do not take positions
+    assert_eq!(index.len(), 2);
+    assert_eq!(index.position("remove"), None);
+}
+
+#[test]
+fn implications_test() {
+    let index = ActionIndex::from_actions(&HashSet::from([
+        String::from("admin"),
+        String::from("edit"),
+        String::from("view"),
+    ]))
+    .with_implications(&HashMap::from([
+        (String::from("admin"), HashSet::from([String::from("edit")])),
+        (
+            String::from("edit"),
+            HashSet::from([String::from("view"), String::from("other")]),
+        ),
+        (String::from("view"), HashSet::from([String::from("edit")])),
+    ]));
+
+    let implied = |action: &str| index.implied(action).collect::<HashSet<&str>>();
+
+    assert_eq!(implied("admin"), HashSet::from(["edit", "view"]));
+    // Cycles do not imply the action itself
+    assert_eq!(implied("edit"), HashSet::from(["view"]));
+    assert_eq!(implied("view"), HashSet::from(["edit"]));
+    assert_eq!(implied("other").is_empty(), true);
+
+    assert_eq!(
+        index.is_implied(HashSet::from([String::from("admin")]), "view"),
+        true
+    );
+    assert_eq!(
+        index.is_implied(HashSet::from([String::from("view")]), "admin"),
+        false
+    );
+}
+
+#[test]
+fn group_actions_test() {
+    let index = ActionIndex::from_actions(&HashSet::from([
+        String::from("building:view"),
+        String::from("building:room:edit"),
+        String::from("buildings:view"),
+        String::from("building"),
+        String::from("user:view"),
+    ]));
+
+    assert_eq!(
+        index.group_actions("building").collect::<Vec<&str>>(),
+        vec!["building:room:edit", "building:view"]
+    );
+    assert_eq!(
+        index.group_actions("building:room").collect::<Vec<&str>>(),
+        vec!["building:room:edit"]
+    );
+    assert_eq!(index.group_actions("user:view").count(), 0);
+    assert_eq!(index.group_actions("other").count(), 0);
+}
+
+#[test]
+fn separator_test() {
+    let index = ActionIndex::from_actions(&HashSet::from([
+        String::from("building.view"),
+        String::from("building:edit"),
+    ]));
+    assert_eq!(index.separator(), ACTION_DIVIDER);
+
+    let index = index.with_separator('.');
+    assert_eq!(index.separator(), '.');
+    assert_eq!(
+        index.group_actions("building").collect::<Vec<&str>>(),
+        vec!["building.view"]
+    );
+}
+
+#[test]
+fn restricted_test() {
+    let index = ActionIndex::from_actions(&HashSet::from([
+        String::from("create"),
+        String::from("edit"),
+        String::from("view"),
+        String::from("delete"),
+    ]))
+    .with_aliases(&HashMap::from([
+        (String::from("update"), String::from("edit")),
+        (String::from("remove"), String::from("delete")),
+    ]))
+    .with_implications(&HashMap::from([
+        (
+            String::from("delete"),
+            HashSet::from([String::from("edit")]),
+        ),
+        (String::from("edit"), HashSet::from([String::from("view")])),
+    ]))
+    .with_separator('.');
+
+    let restricted = index.restricted(&HashSet::from([
+        String::from("delete"),
+        String::from("view"),
+        String::from("other"),
+    ]));
+
+    assert_eq!(
+        restricted.actions().collect::<Vec<&str>>(),
+        vec!["delete", "view"]
+    );
+    assert_eq!(restricted.position("view"), Some(1));
+    assert_eq!(
+        Arc::ptr_eq(
+            &restricted.intern("view").unwrap(),
+            &index.intern("view").unwrap()
+        ),
+        true
+    );
+    assert_eq!(restricted.alias("remove"), Some("delete"));
+    assert_eq!(restricted.alias("update"), None);
+    // 'delete' implies 'view' through 'edit', which is not kept
+    assert_eq!(
+        restricted.implied("delete").collect::<Vec<&str>>(),
+        vec!["view"]
+    );
+    assert_eq!(restricted.separator(), '.');
+}
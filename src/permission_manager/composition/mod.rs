@@ -0,0 +1,53 @@
+use crate::validation_error::ValidationError;
+use std::collections::{BTreeMap, HashSet};
+
+//// TESTS ////
+#[cfg(test)]
+mod tests;
+
+// Returns the union of the actions of every named part, divided by `separator`.
+//
+// # Errors
+//
+// Returns ValidationError::ConflictingActions listing the actions defined by more than one part and the actions another part
+// defines as a group, each one with the names of the parts involved in order.
+pub(crate) fn compose<I>(parts: I, separator: char) -> Result<HashSet<String>, ValidationError>
+where
+    I: IntoIterator<Item = (String, HashSet<String>)>,
+{
+    let mut owners: BTreeMap<String, Vec<String>> = BTreeMap::new();
+    for (name, actions) in parts {
+        for action in actions {
+            owners.entry(action).or_default().push(name.clone());
+        }
+    }
+
+    let mut conflicts: BTreeMap<String, Vec<String>> = owners
+        .iter()
+        .filter(|(_, parts)| parts.len() > 1)
+        .map(|(action, parts)| (action.clone(), parts.clone()))
+        .collect();
+    for (action, parts) in &owners {
+        for (position, _) in action.match_indices(separator) {
+            let group = &action[..position];
+            if let Some(group_parts) = owners.get(group) {
+                let conflict = conflicts
+                    .entry(group.to_string())
+                    .or_insert_with(|| group_parts.clone());
+                for part in parts {
+                    if !conflict.contains(part) {
+                        conflict.push(part.clone());
+                    }
+                }
+            }
+        }
+    }
+
+    if conflicts.is_empty() {
+        Ok(owners.into_keys().collect())
+    } else {
+        Err(ValidationError::ConflictingActions(
+            conflicts.into_iter().collect(),
+        ))
+    }
+}
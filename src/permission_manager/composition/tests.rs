@@ -0,0 +1,74 @@
+use super::*;
+
+fn part(name: &str, actions: &[&str]) -> (String, HashSet<String>) {
+    (
+        name.to_string(),
+        actions.iter().map(|action| action.to_string()).collect(),
+    )
+}
+
+#[test]
+fn compose_test() {
+    let universe = compose(
+        [
+            part("buildings", &["building:view", "building:room:edit"]),
+            part("users", &["user:view"]),
+            part("empty", &[]),
+        ],
+        ':',
+    );
+
+    assert_eq!(
+        universe,
+        Ok(HashSet::from([
+            String::from("building:view"),
+            String::from("building:room:edit"),
+            String::from("user:view"),
+        ]))
+    );
+    assert_eq!(compose([], ':'), Ok(HashSet::new()));
+}
+
+#[test]
+fn conflicts_test() {
+    let universe = compose(
+        [
+            part("buildings", &["building:view", "building:room"]),
+            part("rooms", &["building:room:edit", "building:room:view"]),
+            part("legacy", &["building:view", "building:room"]),
+        ],
+        ':',
+    );
+
+    assert_eq!(
+        universe,
+        Err(ValidationError::ConflictingActions(vec![
+            (
+                String::from("building:room"),
+                vec![
+                    String::from("buildings"),
+                    String::from("legacy"),
+                    String::from("rooms")
+                ]
+            ),
+            (
+                String::from("building:view"),
+                vec![String::from("buildings"), String::from("legacy")]
+            ),
+        ]))
+    );
+    assert_eq!(
+        compose(
+            [
+                part("a", &["a.b"]),
+                part("b", &["a.b.c"]),
+                part("c", &["a:b"])
+            ],
+            '.'
+        ),
+        Err(ValidationError::ConflictingActions(vec![(
+            String::from("a.b"),
+            vec![String::from("a"), String::from("b")]
+        )]))
+    );
+}
@@ -0,0 +1,96 @@
+use crate::action_tree::{ActionNode, ActionTree};
+use std::collections::{HashMap, HashSet};
+
+//// TESTS ////
+#[cfg(test)]
+mod tests;
+
+// Returns a Graphviz digraph of the tree, where each group and action is a node identified by its full path
+// and labeled with its name. Actions in `granted` are filled.
+pub(crate) fn to_dot(tree: &ActionTree, granted: Option<&HashSet<String>>) -> String {
+    let mut lines = vec![
+        String::from("digraph universe {"),
+        String::from("    node [shape=box];"),
+    ];
+    walk(
+        tree.root(),
+        "",
+        tree.separator(),
+        &mut |path, name, node, parent| {
+            let mut attributes = format!("label=\"{}\"", escape(name));
+            if !node.is_leaf() {
+                attributes.push_str(", style=rounded");
+            } else if granted.is_some_and(|granted| granted.contains(path)) {
+                attributes.push_str(", style=filled, fillcolor=lightgreen");
+            }
+            lines.push(format!("    \"{}\" [{}];", escape(path), attributes));
+            if let Some(parent) = parent {
+                lines.push(format!(
+                    "    \"{}\" -> \"{}\";",
+                    escape(parent),
+                    escape(path)
+                ));
+            }
+        },
+    );
+    lines.push(String::from("}"));
+
+    lines.join("\n")
+}
+
+// Returns a Mermaid top-down flowchart of the tree, where each group and action is a node labeled with its name.
+// Groups are rounded and actions in `granted` use the `granted` class.
+pub(crate) fn to_mermaid(tree: &ActionTree, granted: Option<&HashSet<String>>) -> String {
+    let mut lines = vec![String::from("flowchart TD")];
+    let mut ids: HashMap<String, usize> = HashMap::new();
+    walk(
+        tree.root(),
+        "",
+        tree.separator(),
+        &mut |path, name, node, parent| {
+            let id = ids.len();
+            ids.insert(path.to_string(), id);
+            let label = name.replace('"', "#quot;");
+            let mut line = if node.is_leaf() {
+                format!("    n{}[\"{}\"]", id, label)
+            } else {
+                format!("    n{}(\"{}\")", id, label)
+            };
+            if node.is_leaf() && granted.is_some_and(|granted| granted.contains(path)) {
+                line.push_str(":::granted");
+            }
+            lines.push(line);
+            if let Some(parent) = parent {
+                lines.push(format!("    n{} --> n{}", ids[parent], id));
+            }
+        },
+    );
+    if granted.is_some() {
+        lines.push(String::from("    classDef granted fill:#9f9,stroke:#363"));
+    }
+
+    lines.join("\n")
+}
+
+// Calls `visit` with the path, name, node and parent path of every descendant of the node, parents first
+// and siblings sorted by name.
+fn walk<F>(node: &ActionNode, path: &str, separator: char, visit: &mut F)
+where
+    F: FnMut(&str, &str, &ActionNode, Option<&str>),
+{
+    for (name, child) in node.children() {
+        let child_path = if path.is_empty() {
+            name.to_string()
+        } else {
+            format!("{}{}{}", path, separator, name)
+        };
+        let parent = if path.is_empty() { None } else { Some(path) };
+        visit(&child_path, name, child, parent);
+        walk(child, &child_path, separator, visit);
+    }
+}
+
+// Escapes the characters that can not appear as is inside a double quoted Graphviz ID.
+fn escape(text: &str) -> String {
+    text.replace('\\', "\\\\").replace('"', "\\\"")
+}
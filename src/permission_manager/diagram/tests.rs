@@ -0,0 +1,92 @@
+use super::*;
+
+fn tree() -> ActionTree {
+    ActionTree::from_actions(
+        &HashSet::from([
+            String::from("building:view"),
+            String::from("building:room:edit"),
+            String::from("home"),
+        ]),
+        ':',
+    )
+}
+
+#[test]
+fn to_dot_test() {
+    assert_eq!(
+        to_dot(&tree(), None),
+        [
+            "digraph universe {",
+            "    node [shape=box];",
+            "    \"building\" [label=\"building\", style=rounded];",
+            "    \"building:room\" [label=\"room\", style=rounded];",
+            "    \"building\" -> \"building:room\";",
+            "    \"building:room:edit\" [label=\"edit\"];",
+            "    \"building:room\" -> \"building:room:edit\";",
+            "    \"building:view\" [label=\"view\"];",
+            "    \"building\" -> \"building:view\";",
+            "    \"home\" [label=\"home\"];",
+            "}",
+        ]
+        .join("\n")
+    );
+}
+
+#[test]
+fn to_dot_granted_test() {
+    let dot = to_dot(&tree(), Some(&HashSet::from([String::from("home")])));
+
+    assert_eq!(
+        dot.contains("    \"home\" [label=\"home\", style=filled, fillcolor=lightgreen];"),
+        true
+    );
+    assert_eq!(
+        dot.contains("    \"building:view\" [label=\"view\"];"),
+        true
+    );
+}
+
+#[test]
+fn escape_test() {
+    let tree = ActionTree::from_actions(&HashSet::from([String::from("say \"hi\"")]), ':');
+
+    assert_eq!(
+        to_dot(&tree, None).contains(r#"    "say \"hi\"" [label="say \"hi\""];"#),
+        true
+    );
+}
+
+#[test]
+fn to_mermaid_test() {
+    assert_eq!(
+        to_mermaid(&tree(), None),
+        [
+            "flowchart TD",
+            "    n0(\"building\")",
+            "    n1(\"room\")",
+            "    n0 --> n1",
+            "    n2[\"edit\"]",
+            "    n1 --> n2",
+            "    n3[\"view\"]",
+            "    n0 --> n3",
+            "    n4[\"home\"]",
+        ]
+        .join("\n")
+    );
+
+    let mermaid = to_mermaid(
+        &tree(),
+        Some(&HashSet::from([String::from("building:room:edit")])),
+    );
+    assert_eq!(mermaid.contains("    n2[\"edit\"]:::granted\n"), true);
+    assert_eq!(
+        mermaid.ends_with("classDef granted fill:#9f9,stroke:#363"),
+        true
+    );
+
+    let tree = ActionTree::from_actions(&HashSet::from([String::from("say \"hi\"")]), ':');
+    assert_eq!(
+        to_mermaid(&tree, None),
+        "flowchart TD\n    n0[\"say #quot;hi#quot;\"]"
+    );
+}
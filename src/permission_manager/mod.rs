@@ -1,6 +1,48 @@
-use crate::permission::Permission;
-use std::collections::HashSet;
+pub(crate) mod action_index;
+mod composition;
+mod diagram;
+#[cfg(feature = "crypto")]
+mod sealing;
+pub(crate) mod signing;
+mod strict_parser;
+use crate::action_name_policy::ActionNamePolicy;
+use crate::action_tree::ActionTree;
+use crate::decision::Decision;
+use crate::glob::glob_matches;
+use crate::load_error::LoadError;
+use crate::manager_id::ManagerId;
+use crate::migration::Migration;
+use crate::permission::{
+    deserialize_actions, deserialize_managed_actions, parse_policy_actions, serialize_actions,
+    serialize_policy_actions, try_deserialize_actions, try_parse_actions, Permission,
+    ACTION_DIVIDER, CLAIM_BITS_KEY, CLAIM_UNIVERSE_KEY,
+};
+use crate::permission_builder::PermissionBuilder;
+use crate::policy::{CombiningAlgorithm, Policy};
+use crate::telemetry;
+use crate::typed_permission::Action;
+use crate::universe_diff::UniverseDiff;
+use crate::universe_file::{parse_universe, read_file};
+use crate::validation_error::ValidationError;
+use crate::validation_report::{close_matches, ValidationReport};
+use action_index::ActionIndex;
+use base64::engine::general_purpose::URL_SAFE_NO_PAD;
+use base64::Engine;
+use composition::compose;
+use serde_json::{Map, Value};
+use std::borrow::Cow;
+use std::collections::{BTreeMap, BTreeSet, HashMap, HashSet};
+use std::fmt;
+use std::panic::{RefUnwindSafe, UnwindSafe};
+use std::path::Path;
+use std::sync::Arc;
+use std::time::Instant;
+use strict_parser::{parse_actions_strict, StrictParseError};
 use uuid::Uuid;
+#[cfg(feature = "roaring")]
+use {crate::bitmap_permission::BitmapPermission, roaring::RoaringBitmap};
+#[cfg(feature = "watch")]
+use {crate::watched_manager::WatchedManager, std::time::Duration};
 
 //// TESTS ////
 #[cfg(test)]
@@ -14,17 +56,182 @@ mod tests;
 /// [`PermissionManager`](crate::PermissionManager) and can only operate with other [`Permission`](crate::Permission)s belonging to the same [`PermissionManager`](crate::PermissionManager).
 ///
 /// You may want read the [`Permission`](crate::Permission) section in order to clearly understand the [`PermissionManager`](crate::PermissionManager).
-#[derive(Debug)]
 pub struct PermissionManager {
     universe: Permission,
     id: Uuid,
+    index: Arc<ActionIndex>,
+    // Aliases and old action names mapped to their current name in the universe
+    aliases: HashMap<String, String>,
+    // Actions mapped to the actions that granting them directly implies
+    implications: HashMap<String, HashSet<String>>,
+    // Actions and groups mapped to the obligations returned with the decisions allowing them
+    obligations: HashMap<String, HashSet<String>>,
+    combining_algorithm: CombiningAlgorithm,
+    // Character dividing the groups and name of the actions
+    separator: char,
+    // Legacy separators replaced by the manager separator in the actions provided
+    accepted_separators: Vec<char>,
+    name_policy: ActionNamePolicy,
+    // Whether action names are case-folded on ingestion and matched case-insensitively
+    case_insensitive: bool,
+    // Tenant of the managers created with `scoped`
+    tenant: Option<String>,
+    // Group every universe action is beneath in the managers created with `mounted`
+    namespace: Option<String>,
+    // Version of the universe and the migration steps upgrading permissions from the previous version to each version
+    version: u32,
+    migrations: BTreeMap<u32, Vec<Migration>>,
+    universe_hooks: Vec<UniverseHook>,
 }
 
+// Callback receiving the previous and the current universe after each change.
+type UniverseHook =
+    Box<dyn Fn(&Permission, &Permission) + Send + Sync + UnwindSafe + RefUnwindSafe>;
+
 impl PermissionManager {
+    // Returns a Permission managed by this manager and linked to its universe. Actions are not validated.
+    fn managed_perm(&self, actions: HashSet<String>) -> Permission {
+        Permission::from_actions_and_uuid(actions, Some(self.id)).with_universe(self.index.clone())
+    }
+
+    // Parses an actions JSON string with the separator of the manager. Actions are not validated.
+    fn parse_actions(&self, actions_json: &str) -> HashSet<String> {
+        match serde_json::from_str(actions_json).expect("wrong format in permission json string") {
+            Value::Object(map) => deserialize_actions(0, "", &map, self.separator),
+            _ => panic!("wrong format in permission json string"),
+        }
+    }
+
+    // Replaces the universe and its action index. Previously issued permissions keep the old index link.
+    // Universe hooks are called when the universe actions change.
+    fn set_universe(&mut self, universe_actions: HashSet<String>) {
+        self.index = Arc::new(
+            ActionIndex::from_actions(&universe_actions)
+                .with_aliases(&self.aliases)
+                .with_implications(&self.implications)
+                .with_separator(self.separator)
+                .with_case_insensitive(self.case_insensitive),
+        );
+        let universe = self.managed_perm(universe_actions);
+        let previous = std::mem::replace(&mut self.universe, universe);
+
+        if !previous.has_same_actions(&self.universe) {
+            for hook in &self.universe_hooks {
+                hook(&previous, &self.universe);
+            }
+        }
+    }
+
+    // Replaces the universe with the actions provided, case-folded if the manager is case insensitive, if they follow the
+    // name policy. Returns whether the universe actions changed.
+    #[cfg(feature = "watch")]
+    pub(crate) fn try_set_universe(
+        &mut self,
+        universe_actions: HashSet<String>,
+    ) -> Result<bool, ValidationError> {
+        let universe_actions: HashSet<String> = universe_actions
+            .iter()
+            .map(|action| self.fold(action))
+            .collect();
+        let rejected = self.name_policy.rejected(&universe_actions, self.separator);
+        if !rejected.is_empty() {
+            return Err(ValidationError::MalformedActions(rejected));
+        }
+        if universe_actions.len() == self.universe.len()
+            && universe_actions
+                .iter()
+                .all(|action| self.universe.has_action(action))
+        {
+            return Ok(false);
+        }
+
+        self.set_universe(universe_actions);
+        Ok(true)
+    }
+
+    // Returns the action case-folded if the manager is case insensitive.
+    fn fold(&self, action: &str) -> String {
+        self.index.fold(action).into_owned()
+    }
+
+    // Returns the action case-folded if the manager is case insensitive, with accepted separators replaced by the manager one.
+    fn normalize_name(&self, action: String) -> String {
+        let action = match self.index.fold(&action) {
+            Cow::Owned(folded) => folded,
+            Cow::Borrowed(_) => action,
+        };
+        if action.contains(self.accepted_separators.as_slice()) {
+            action.replace(
+                self.accepted_separators.as_slice(),
+                &self.separator.to_string(),
+            )
+        } else {
+            action
+        }
+    }
+
+    // Returns the group nodes among the actions, which are neither universe actions nor aliases but have universe actions
+    // beneath them.
+    fn group_nodes(&self, actions: &HashSet<String>) -> BTreeSet<String> {
+        actions
+            .iter()
+            .map(|action| self.normalize_name(action.clone()))
+            .filter(|action| {
+                self.index.resolve(action).is_none()
+                    && self.index.group_actions(action).next().is_some()
+            })
+            .collect()
+    }
+
+    // Returns a managed permission with the actions provided normalized and the group nodes among them granted as a whole.
+    fn granted_perm(&self, actions: HashSet<String>) -> Permission {
+        let groups = self.group_nodes(&actions);
+        self.managed_perm(self.normalize_actions(actions, true))
+            .with_groups(groups)
+    }
+
+    // Returns the actions case-folded if the manager is case insensitive, with accepted separators replaced by the
+    // manager one, aliases replaced by the universe action they refer to, group nodes replaced by the universe actions
+    // beneath them and, if `implications` is set, implied actions added.
+    fn normalize_actions(&self, actions: HashSet<String>, implications: bool) -> HashSet<String> {
+        let actions: HashSet<String> = actions
+            .into_iter()
+            .map(|action| self.normalize_name(action))
+            .flat_map(|action| match self.index.alias(&action) {
+                Some(resolved) => vec![resolved.to_string()],
+                None if self.index.position(&action).is_some() => vec![action],
+                None => {
+                    let children: Vec<String> = self
+                        .index
+                        .group_actions(&action)
+                        .map(String::from)
+                        .collect();
+                    if children.is_empty() {
+                        vec![action]
+                    } else {
+                        children
+                    }
+                }
+            })
+            .collect();
+        if !implications {
+            return actions;
+        }
+
+        let implied: Vec<String> = actions
+            .iter()
+            .flat_map(|action| self.index.implied(action))
+            .map(String::from)
+            .collect();
+
+        actions.into_iter().chain(implied).collect()
+    }
+
     /// Creates a new [`PermissionManager`](crate::PermissionManager) with a universe [`Permission`](crate::Permission) containing all the actions specified in the actions set provided.
     ///
     /// # Notes:  
-    /// Dots (.) in `universe_actions` elements are used in [`Permission`](crate::Permission) JSON serialization, do not use them unless you know what you are doing.
+    /// The separator (`:` unless changed with [`set_separator`](Self::set_separator())) in `universe_actions` elements divides the groups
+    /// used in [`Permission`](crate::Permission) JSON serialization, do not use it in action names unless you know what you are doing.
     ///
     /// # Examples:
     ///
@@ -47,11 +254,52 @@ impl PermissionManager {
     /// assert_eq!(actions, *manager.get_universe().get_actions());
     /// ```
     pub fn from_actions(universe_actions: HashSet<String>) -> PermissionManager {
-        let id = Uuid::new_v4();
+        PermissionManager::with_id(ManagerId::random(), universe_actions)
+    }
+
+    /// Creates a new [`PermissionManager`](crate::PermissionManager) with the [`ManagerId`](crate::ManagerId) provided and a universe
+    /// [`Permission`](crate::Permission) containing all the actions specified in the actions set provided.
+    ///
+    /// [`PermissionManager`](crate::PermissionManager)s created with the same [`ManagerId`](crate::ManagerId) manage the same
+    /// [`Permission`](crate::Permission)s, so the identity can be kept stable across restarts and services.
+    ///
+    /// # Examples:
+    ///
+    /// ```
+    /// use std::collections::HashSet;
+    /// use simple_perm_manager::{ManagerId, PermissionManager};
+    ///
+    /// let id = ManagerId::parse("b4a3a1e4-3c1a-4f5e-9a57-2f1f0d1c6a10").unwrap();
+    /// let actions = HashSet::from([String::from("create"), String::from("view")]);
+    ///
+    /// let manager = PermissionManager::with_id(id, actions.clone());
+    /// let restarted_manager = PermissionManager::with_id(id, actions);
+    ///
+    /// assert_eq!(manager.id(), id);
+    /// assert!(restarted_manager.validate_perm(&manager.perm_from_json(r#"{"view": true}"#)));
+    /// ```
+    pub fn with_id(id: ManagerId, universe_actions: HashSet<String>) -> PermissionManager {
+        let id = id.as_uuid();
+        let index = Arc::new(ActionIndex::from_actions(&universe_actions));
 
         PermissionManager {
-            universe: Permission::from_actions_and_uuid(universe_actions, Some(id)),
+            universe: Permission::from_actions_and_uuid(universe_actions, Some(id))
+                .with_universe(index.clone()),
             id,
+            index,
+            aliases: HashMap::new(),
+            implications: HashMap::new(),
+            obligations: HashMap::new(),
+            combining_algorithm: CombiningAlgorithm::default(),
+            separator: ACTION_DIVIDER,
+            accepted_separators: Vec::new(),
+            name_policy: ActionNamePolicy::default(),
+            case_insensitive: false,
+            tenant: None,
+            namespace: None,
+            version: 0,
+            migrations: BTreeMap::new(),
+            universe_hooks: Vec::new(),
         }
     }
 
@@ -84,12 +332,341 @@ impl PermissionManager {
     /// - Panics if `universe_actions_json` argument is not valid format for Permission actions.
     /// - Panics if `universe_actions_json` argument is s JSON with objects nested to a depth of more than 20.
     pub fn from_json(universe_actions_json: &str) -> PermissionManager {
-        let id = Uuid::new_v4();
+        PermissionManager::from_json_with_id(ManagerId::random(), universe_actions_json)
+    }
+
+    /// Creates a new [`PermissionManager`](crate::PermissionManager) with the [`ManagerId`](crate::ManagerId) provided and a universe
+    /// [`Permission`](crate::Permission) containing all the actions specified in the actions JSON string provided.
+    ///
+    /// # Examples:
+    ///
+    /// ```
+    /// use simple_perm_manager::{ManagerId, PermissionManager};
+    ///
+    /// let id = ManagerId::random();
+    /// let manager = PermissionManager::from_json_with_id(id, r#"{"create": true, "view": true}"#);
+    ///
+    /// assert_eq!(manager.id(), id);
+    /// ```
+    ///
+    /// # Panics:
+    ///
+    /// Panics in the same cases that [`from_json`](Self::from_json()) does.
+    pub fn from_json_with_id(id: ManagerId, universe_actions_json: &str) -> PermissionManager {
+        let id = id.as_uuid();
+        let universe = Permission::from_json_and_uuid(universe_actions_json, Some(id));
+        let index = Arc::new(ActionIndex::from_actions(&universe.get_actions()));
 
         PermissionManager {
-            universe: Permission::from_json_and_uuid(universe_actions_json, Some(id)),
+            universe: universe.with_universe(index.clone()),
             id,
+            index,
+            aliases: HashMap::new(),
+            implications: HashMap::new(),
+            obligations: HashMap::new(),
+            combining_algorithm: CombiningAlgorithm::default(),
+            separator: ACTION_DIVIDER,
+            accepted_separators: Vec::new(),
+            name_policy: ActionNamePolicy::default(),
+            case_insensitive: false,
+            tenant: None,
+            namespace: None,
+            version: 0,
+            migrations: BTreeMap::new(),
+            universe_hooks: Vec::new(),
+        }
+    }
+
+    /// Same as [`from_actions`](Self::from_actions()) but returns a [`ValidationError`](crate::ValidationError) instead of
+    /// creating a [`PermissionManager`](crate::PermissionManager) with malformed actions.  
+    /// An action is malformed if it is empty or has empty groups (e.g. `"group::view"`), as it could not be serialized to JSON and back.
+    ///
+    /// # Examples:
+    ///
+    /// ```
+    /// use std::collections::HashSet;
+    /// use simple_perm_manager::{PermissionManager, ValidationError};
+    ///
+    /// let manager = PermissionManager::try_from_actions(HashSet::from([String::from("group:view")]));
+    /// assert!(manager.is_ok());
+    ///
+    /// let manager = PermissionManager::try_from_actions(HashSet::from([String::from("group::view")]));
+    /// assert_eq!(manager.unwrap_err(), ValidationError::MalformedActions(vec![String::from("group::view")]));
+    /// ```
+    pub fn try_from_actions(
+        universe_actions: HashSet<String>,
+    ) -> Result<PermissionManager, ValidationError> {
+        PermissionManager::try_from_actions_with_policy(universe_actions, ActionNamePolicy::new())
+    }
+
+    /// Same as [`try_from_actions`](Self::try_from_actions()) but also returns a [`ValidationError::MalformedActions`](crate::ValidationError::MalformedActions)
+    /// listing the actions that do not follow the [`ActionNamePolicy`](crate::ActionNamePolicy) provided.  
+    /// The policy is kept by the [`PermissionManager`](crate::PermissionManager) and enforced on every later universe change.
+    ///
+    /// # Examples:
+    ///
+    /// ```
+    /// use std::collections::HashSet;
+    /// use simple_perm_manager::{ActionNamePolicy, PermissionManager, ValidationError};
+    ///
+    /// let manager = PermissionManager::try_from_actions_with_policy(
+    ///     HashSet::from([String::from("group:view"), String::from("group.edit")]),
+    ///     ActionNamePolicy::new().with_forbidden_chars("."),
+    /// );
+    /// assert_eq!(manager.unwrap_err(), ValidationError::MalformedActions(vec![String::from("group.edit")]));
+    /// ```
+    pub fn try_from_actions_with_policy(
+        universe_actions: HashSet<String>,
+        name_policy: ActionNamePolicy,
+    ) -> Result<PermissionManager, ValidationError> {
+        let rejected = name_policy.rejected(&universe_actions, ACTION_DIVIDER);
+        if !rejected.is_empty() {
+            return Err(ValidationError::MalformedActions(rejected));
         }
+
+        let mut manager = PermissionManager::from_actions(universe_actions);
+        manager.name_policy = name_policy;
+        Ok(manager)
+    }
+
+    /// Same as [`from_json`](Self::from_json()) but returns a [`ValidationError`](crate::ValidationError) instead of panicking.  
+    /// Malformed actions (e.g. `{"": true}`) are rejected as in [`try_from_actions`](Self::try_from_actions()).
+    ///
+    /// # Examples:
+    ///
+    /// ```
+    /// use simple_perm_manager::PermissionManager;
+    ///
+    /// assert!(PermissionManager::try_from_json(r#"{"create": true, "view": true}"#).is_ok());
+    /// assert!(PermissionManager::try_from_json(r#"{"create": "yes"}"#).is_err());
+    /// ```
+    pub fn try_from_json(
+        universe_actions_json: &str,
+    ) -> Result<PermissionManager, ValidationError> {
+        PermissionManager::try_from_json_with_policy(universe_actions_json, ActionNamePolicy::new())
+    }
+
+    /// Same as [`try_from_json`](Self::try_from_json()) but enforcing the [`ActionNamePolicy`](crate::ActionNamePolicy) provided,
+    /// as in [`try_from_actions_with_policy`](Self::try_from_actions_with_policy()).
+    ///
+    /// # Examples:
+    ///
+    /// ```
+    /// use simple_perm_manager::{ActionNamePolicy, PermissionManager};
+    ///
+    /// let policy = ActionNamePolicy::identifiers();
+    /// assert!(PermissionManager::try_from_json_with_policy(r#"{"user": {"view": true}}"#, policy.clone()).is_ok());
+    /// assert!(PermissionManager::try_from_json_with_policy(r#"{"user": {"view all": true}}"#, policy).is_err());
+    /// ```
+    pub fn try_from_json_with_policy(
+        universe_actions_json: &str,
+        name_policy: ActionNamePolicy,
+    ) -> Result<PermissionManager, ValidationError> {
+        let universe_actions =
+            try_parse_actions(universe_actions_json, ACTION_DIVIDER).map_err(|error| {
+                telemetry::record_parse_error();
+                ValidationError::InvalidJson(error)
+            })?;
+
+        PermissionManager::try_from_actions_with_policy(universe_actions, name_policy)
+    }
+
+    /// Creates a new [`PermissionManager`](crate::PermissionManager) with the universe of a JSON file, or of a YAML one if the path
+    /// ends in `.yaml` or `.yml` and the `yaml` feature is enabled.
+    ///
+    /// # Examples:
+    ///
+    /// ```
+    /// use std::fs;
+    /// use simple_perm_manager::{LoadError, PermissionManager};
+    ///
+    /// let path = std::env::temp_dir().join("from_json_file_example.json");
+    /// fs::write(&path, r#"{"user": {"view": true, "edit": true}}"#).unwrap();
+    ///
+    /// let manager = PermissionManager::from_json_file(&path).unwrap();
+    /// assert!(manager.get_universe().contains_action("user:edit"));
+    /// assert!(matches!(PermissionManager::from_json_file("missing.json"), Err(LoadError::Io(_))));
+    /// # fs::remove_file(&path).unwrap();
+    /// ```
+    ///
+    /// # Errors:
+    ///
+    /// - Returns [`LoadError::Io`](crate::LoadError::Io) if the file can not be read.
+    /// - Returns [`LoadError::Invalid`](crate::LoadError::Invalid) if the file is not a valid universe.
+    pub fn from_json_file<P: AsRef<Path>>(path: P) -> Result<PermissionManager, LoadError> {
+        let path = path.as_ref();
+        let universe_actions = parse_universe(path, &read_file(path)?, ACTION_DIVIDER)?;
+
+        Ok(PermissionManager::try_from_actions(universe_actions)?)
+    }
+
+    /// Creates a new [`PermissionManager`](crate::PermissionManager) whose universe is composed of several named universe actions
+    /// JSON strings, e.g. one per module or team.  
+    /// Parts can not define the same action, nor define as an action what another part defines as a group, so each part owns
+    /// its actions.
+    ///
+    /// # Examples:
+    ///
+    /// ```
+    /// use simple_perm_manager::{PermissionManager, ValidationError};
+    ///
+    /// let manager = PermissionManager::from_parts([
+    ///     ("buildings", r#"{"building": {"view": true, "room": {"edit": true}}}"#),
+    ///     ("users", r#"{"user": {"view": true}}"#),
+    /// ]).unwrap();
+    /// assert_eq!(manager.get_universe().get_actions().len(), 3);
+    ///
+    /// let conflict = PermissionManager::from_parts([
+    ///     ("buildings", r#"{"building": {"view": true, "room": true}}"#),
+    ///     ("rooms", r#"{"building": {"room": {"edit": true}}}"#),
+    /// ]);
+    /// assert_eq!(
+    ///     conflict.unwrap_err(),
+    ///     ValidationError::ConflictingActions(vec![(
+    ///         String::from("building:room"),
+    ///         vec![String::from("buildings"), String::from("rooms")]
+    ///     )])
+    /// );
+    /// ```
+    ///
+    /// # Errors:
+    ///
+    /// - Returns [`ValidationError::InvalidJson`](crate::ValidationError::InvalidJson) if a part is not a valid actions JSON string,
+    ///   with the message preceded by the part name.
+    /// - Returns [`ValidationError::ConflictingActions`](crate::ValidationError::ConflictingActions) listing the conflicting actions.
+    /// - Returns [`ValidationError::MalformedActions`](crate::ValidationError::MalformedActions) as in [`try_from_actions`](Self::try_from_actions()).
+    pub fn from_parts<I, N, J>(parts: I) -> Result<PermissionManager, ValidationError>
+    where
+        I: IntoIterator<Item = (N, J)>,
+        N: AsRef<str>,
+        J: AsRef<str>,
+    {
+        let parts = parts
+            .into_iter()
+            .map(|(name, json)| {
+                let name = name.as_ref().to_string();
+                match try_parse_actions(json.as_ref(), ACTION_DIVIDER) {
+                    Ok(actions) => Ok((name, actions)),
+                    Err(error) => {
+                        telemetry::record_parse_error();
+                        Err(ValidationError::InvalidJson(format!("{}: {}", name, error)))
+                    }
+                }
+            })
+            .collect::<Result<Vec<(String, HashSet<String>)>, ValidationError>>()?;
+
+        PermissionManager::try_from_actions(compose(parts, ACTION_DIVIDER)?)
+    }
+
+    /// Same as [`from_parts`](Self::from_parts()) but reading each part from a file named by its path, parsed as in
+    /// [`from_json_file`](Self::from_json_file()).
+    ///
+    /// # Examples:
+    ///
+    /// ```
+    /// use std::fs;
+    /// use simple_perm_manager::PermissionManager;
+    ///
+    /// let buildings = std::env::temp_dir().join("from_json_files_buildings.json");
+    /// let users = std::env::temp_dir().join("from_json_files_users.json");
+    /// fs::write(&buildings, r#"{"building": {"view": true}}"#).unwrap();
+    /// fs::write(&users, r#"{"user": {"view": true}}"#).unwrap();
+    ///
+    /// let manager = PermissionManager::from_json_files([&buildings, &users]).unwrap();
+    /// assert!(manager.get_universe().contains_action("user:view"));
+    /// # fs::remove_file(&buildings).unwrap();
+    /// # fs::remove_file(&users).unwrap();
+    /// ```
+    ///
+    /// # Errors:
+    ///
+    /// - Returns [`LoadError::Io`](crate::LoadError::Io) if a file can not be read.
+    /// - Returns [`LoadError::Invalid`](crate::LoadError::Invalid) if a file is not a valid universe or the parts conflict.
+    pub fn from_json_files<I, P>(paths: I) -> Result<PermissionManager, LoadError>
+    where
+        I: IntoIterator<Item = P>,
+        P: AsRef<Path>,
+    {
+        let parts = paths
+            .into_iter()
+            .map(|path| {
+                let path = path.as_ref();
+                let actions = parse_universe(path, &read_file(path)?, ACTION_DIVIDER)?;
+                Ok((path.display().to_string(), actions))
+            })
+            .collect::<Result<Vec<(String, HashSet<String>)>, LoadError>>()?;
+
+        Ok(PermissionManager::try_from_actions(compose(
+            parts,
+            ACTION_DIVIDER,
+        )?)?)
+    }
+
+    /// Creates a new [`PermissionManager`](crate::PermissionManager) with the universe actions JSON string of an environment variable,
+    /// e.g. `PERM_UNIVERSE`.
+    ///
+    /// # Examples:
+    ///
+    /// ```
+    /// use simple_perm_manager::PermissionManager;
+    ///
+    /// std::env::set_var("PERM_UNIVERSE", r#"{"user": {"view": true}}"#);
+    ///
+    /// let manager = PermissionManager::from_env("PERM_UNIVERSE").unwrap();
+    /// assert!(manager.get_universe().contains_action("user:view"));
+    /// ```
+    ///
+    /// # Errors:
+    ///
+    /// - Returns [`LoadError::Env`](crate::LoadError::Env) if the variable is not set or is not valid unicode.
+    /// - Returns [`LoadError::Invalid`](crate::LoadError::Invalid) if the variable is not a valid universe actions JSON string.
+    pub fn from_env(variable: &str) -> Result<PermissionManager, LoadError> {
+        let universe_actions_json = std::env::var(variable)
+            .map_err(|error| LoadError::Env(format!("{}: {}", variable, error)))?;
+
+        Ok(PermissionManager::try_from_json(&universe_actions_json)?)
+    }
+
+    /// Loads the universe from a JSON file, or from a YAML one if the path ends in `.yaml` or `.yml` and the `yaml` feature is enabled,
+    /// and reloads it when the file changes, polling it every second. See [`WatchedManager`](crate::WatchedManager).  
+    /// Requires the `watch` feature.
+    ///
+    /// # Examples:
+    ///
+    /// ```
+    /// use std::fs;
+    /// use simple_perm_manager::PermissionManager;
+    ///
+    /// let path = std::env::temp_dir().join("from_file_watched_example.json");
+    /// fs::write(&path, r#"{"user": {"view": true}}"#).unwrap();
+    ///
+    /// let watched = PermissionManager::from_file_watched(&path).unwrap();
+    /// assert!(watched.manager().get_universe().contains_action("user:view"));
+    /// # fs::remove_file(&path).unwrap();
+    /// ```
+    ///
+    /// # Errors:
+    ///
+    /// - Returns [`LoadError::Io`](crate::LoadError::Io) if the file can not be read.
+    /// - Returns [`LoadError::Invalid`](crate::LoadError::Invalid) if the file is not a valid universe.
+    #[cfg(feature = "watch")]
+    pub fn from_file_watched<P: AsRef<Path>>(path: P) -> Result<WatchedManager, LoadError> {
+        PermissionManager::from_file_watched_with_interval(path, Duration::from_secs(1))
+    }
+
+    /// Same as [`from_file_watched`](Self::from_file_watched()) but polling the file every `interval`.  
+    /// Requires the `watch` feature.
+    #[cfg(feature = "watch")]
+    pub fn from_file_watched_with_interval<P: AsRef<Path>>(
+        path: P,
+        interval: Duration,
+    ) -> Result<WatchedManager, LoadError> {
+        WatchedManager::watch(path.as_ref(), interval)
+    }
+
+    /// Returns the [`ActionNamePolicy`](crate::ActionNamePolicy) enforced on the universe actions of the [`PermissionManager`](crate::PermissionManager).
+    pub fn name_policy(&self) -> &ActionNamePolicy {
+        &self.name_policy
     }
 
     /// Returns the [`PermissionManager`](crate::PermissionManager) universe as a managed [`Permission`](crate::Permission).
@@ -115,157 +692,3079 @@ impl PermissionManager {
         self.universe.clone()
     }
 
-    /// Returns `true` if the [`Permission`](crate::Permission) provided is valid for the [`PermissionManager`](crate::PermissionManager).
-    /// A [`Permission`](crate::Permission) is valid if it is managed by this [`PermissionManager`](crate::PermissionManager) and its contained in the [`PermissionManager`](crate::PermissionManager) universe.
+    /// Returns the [`ManagerId`](crate::ManagerId) of the [`PermissionManager`](crate::PermissionManager).
     ///
     /// # Examples:
     ///
     /// ```
     /// use simple_perm_manager::PermissionManager;
-    /// use simple_perm_manager::Permission;
-    ///
-    /// let manager = PermissionManager::from_json(&String::from(r#"{
-    ///     "create": true,
-    ///     "view": true
-    /// }"#));
-    ///
-    /// // Create managed Permission from the previous PermissionManager
-    /// let managed_perm = manager.perm_from_json(&String::from(r#"{
-    ///     "create": true
-    /// }"#));
     ///
-    /// // Create UNmanaged Permission (actions do not have to be a subset of the manager universe actions)
-    /// let unmanaged_perm = Permission::from_json_and_uuid(&String::from(r#"{
-    ///     "other_action": true
-    /// }"#), None);
+    /// let manager = PermissionManager::from_json(r#"{"create": true}"#);
+    /// let perm = manager.perm_from_json(r#"{"create": true}"#);
     ///
-    /// assert!(manager.validate_perm(&managed_perm));
-    /// assert!(!manager.validate_perm(&unmanaged_perm));
+    /// assert_eq!(perm.manager_id(), Some(manager.id()));
     /// ```
-    pub fn validate_perm(&self, perm: &Permission) -> bool {
-        self.universe.has_same_manager(perm) && self.universe.contains(perm)
+    pub fn id(&self) -> ManagerId {
+        ManagerId::new(self.id)
     }
 
-    /// Returns a managed [`Permission`](crate::Permission) with the actions in the [`Permission`](crate::Permission) provided unless the ones not
-    /// allowed by the [`PermissionManager`](crate::PermissionManager) actions universe.  
-    /// The [`Permission`](crate::Permission) used as argument can be both managed and unmanaged.
+    /// Creates a new [`PermissionManager`](crate::PermissionManager) for a tenant whose universe is the subset of this universe given
+    /// by a [`Permission`](crate::Permission) of this manager, e.g. the modules a SaaS customer is subscribed to.  
+    /// The tenant manager shares the interned action names of this manager instead of copying them, and keeps the separator,
+    /// case sensitivity, name policy, combining algorithm and obligations of this manager, and the aliases and implications
+    /// of the actions in its universe. Universe change hooks are not kept.
     ///
-    /// /// # Examples:
+    /// The tenant manager has its own [`ManagerId`](crate::ManagerId), so [`Permission`](crate::Permission)s issued by one manager are
+    /// not managed by the other.
+    ///
+    /// # Examples:
     ///
     /// ```
-    /// use std::collections::HashSet;
     /// use simple_perm_manager::PermissionManager;
-    /// use simple_perm_manager::Permission;
     ///
-    /// // Manager only allowing 'create' and 'view' actions
-    /// let manager = PermissionManager::from_json(&String::from(r#"{
-    ///     "create": true,
-    ///     "view": true
-    /// }"#));
+    /// let manager = PermissionManager::from_json(r#"{"billing": {"view": true}, "reports": {"view": true, "export": true}}"#);
+    /// let subscription = manager.perm_from_json(r#"{"reports": {"view": true, "export": true}}"#);
     ///
-    /// // Create permissions
-    /// let perm1 = Permission::from_json(&String::from(r#"{
-    ///     "create": true,
-    ///     "delete": true
-    /// }"#));
-    /// let perm2 = Permission::from_json(&String::from(r#"{
-    ///     "create": true,
-    ///     "view": true,
-    ///     "delete": true
-    /// }"#));
+    /// let tenant = manager.scoped("acme", &subscription);
     ///
-    /// assert_eq!(*manager.clean_perm(&perm1).get_actions(), HashSet::from(["create".to_string()]));
-    /// assert_eq!(*manager.clean_perm(&perm2).get_actions(), HashSet::from(["create".to_string(), "view".to_string()]));
+    /// assert_eq!(tenant.tenant(), Some("acme"));
+    /// assert!(tenant.try_perm_from_json(r#"{"reports": {"export": true}}"#).is_ok());
+    /// assert!(tenant.try_perm_from_json(r#"{"billing": {"view": true}}"#).is_err());
     /// ```
-    pub fn clean_perm(&self, perm: &Permission) -> Permission {
-        let intersection: HashSet<String> = self
-            .universe
-            .get_actions()
-            .intersection(perm.get_actions())
-            .cloned()
+    ///
+    /// # Panics:
+    ///
+    /// Panics if the [`Permission`](crate::Permission) provided is not managed by this [`PermissionManager`](crate::PermissionManager).
+    pub fn scoped(&self, tenant: &str, subset: &Permission) -> PermissionManager {
+        if !self.universe.has_same_manager(subset) {
+            panic!("Permission to scope to is not managed by this manager");
+        }
+
+        let universe_actions: HashSet<String> = subset
+            .actions()
+            .filter(|action| self.universe.has_action(action))
+            .map(String::from)
             .collect();
-        Permission::from_actions_and_uuid(intersection, Some(self.id))
+
+        self.restricted(
+            ManagerId::random().as_uuid(),
+            Some(tenant.to_string()),
+            universe_actions,
+        )
     }
 
-    /// Returns a managed [`Permission`](crate::Permission) with the actions specified in the actions set provided.
+    /// Creates a view of this [`PermissionManager`](crate::PermissionManager) whose universe is only the actions beneath a group,
+    /// e.g. to hand a module team a narrower surface.  
+    /// The sub-manager keeps the [`ManagerId`](crate::ManagerId) of this manager, so the [`Permission`](crate::Permission)s it issues
+    /// are valid for this manager too, and keeps its settings as in [`scoped`](Self::scoped()). Actions keep their full names.  
+    /// Its encodings from [`encode_perm`](Self::encode_perm()) use its own universe, so they are not interchangeable with this manager's.
     ///
     /// # Examples:
     ///
     /// ```
-    /// use std::collections::HashSet;
     /// use simple_perm_manager::PermissionManager;
     ///
-    /// let manager = PermissionManager::from_actions(HashSet::from([
-    ///     String::from("create"),
-    ///     String::from("view"),
-    /// ]));
+    /// let manager = PermissionManager::from_json(r#"{"building": {"view": true, "edit": true}, "user": {"view": true}}"#);
+    /// let building = manager.submanager("building");
     ///
-    /// // Create managed Permission from the previous PermissionManager
-    /// let managed_perm = manager.perm_from_actions(HashSet::from([String::from("create")]));
+    /// let perm = building.perm_from_json(r#"{"building": {"edit": true}}"#);
+    /// assert!(manager.validate_perm(&perm));
+    /// assert!(building.try_perm_from_json(r#"{"user": {"view": true}}"#).is_err());
     /// ```
     ///
     /// # Panics:
     ///
-    /// Function panics if actions provided for the Permission are not included in the manager universe
-    /// ```rust,should_panic
-    /// use std::collections::HashSet;
-    /// use simple_perm_manager::PermissionManager;
-    ///
-    /// let manager = PermissionManager::from_actions(HashSet::from([
-    ///     String::from("create"),
-    ///     String::from("view"),
-    /// ]));
-    ///
-    /// // This line of code panics
-    /// let panics = manager.perm_from_actions(HashSet::from([String::from("other_action")]));
-    /// ```
-    pub fn perm_from_actions(&self, actions: HashSet<String>) -> Permission {
-        let perm = Permission::from_actions_and_uuid(actions, Some(self.id));
-
-        if !self.validate_perm(&perm) {
-            panic!("Actions for Permission creation not allowed in PermissionManager or Permission id does not correspond to Manager id")
+    /// Panics if the group is not in the [`PermissionManager`](crate::PermissionManager) universe.
+    pub fn submanager(&self, group: &str) -> PermissionManager {
+        let universe_actions = self.universe_subtree(group).get_actions().into_owned();
+        if universe_actions.is_empty() {
+            panic!("Group '{}' is not in the manager universe", group);
         }
 
-        perm
+        self.restricted(self.id, self.tenant.clone(), universe_actions)
     }
 
-    /// Returns a managed [`Permission`](crate::Permission) with the actions specified in the actions JSON string provided.
+    // Returns a manager with the id and tenant provided whose universe is the subset of universe actions provided, sharing the
+    // action index names and keeping the settings, obligations and the aliases and implications of the actions kept.
+    fn restricted(
+        &self,
+        id: Uuid,
+        tenant: Option<String>,
+        universe_actions: HashSet<String>,
+    ) -> PermissionManager {
+        let index = Arc::new(self.index.restricted(&universe_actions));
+
+        PermissionManager {
+            universe: Permission::from_actions_and_uuid(universe_actions, Some(id))
+                .with_universe(index.clone()),
+            id,
+            aliases: index
+                .actions()
+                .flat_map(|action| {
+                    self.aliases
+                        .iter()
+                        .filter(move |(_, aliased)| aliased.as_str() == action)
+                })
+                .map(|(alias, action)| (alias.clone(), action.clone()))
+                .collect(),
+            implications: index
+                .actions()
+                .map(|action| {
+                    (
+                        action.to_string(),
+                        index.implied(action).map(String::from).collect(),
+                    )
+                })
+                .filter(|(_, implied): &(String, HashSet<String>)| !implied.is_empty())
+                .collect(),
+            index,
+            obligations: self.obligations.clone(),
+            combining_algorithm: self.combining_algorithm,
+            separator: self.separator,
+            accepted_separators: self.accepted_separators.clone(),
+            name_policy: self.name_policy.clone(),
+            case_insensitive: self.case_insensitive,
+            tenant,
+            namespace: self.namespace.clone(),
+            version: self.version,
+            migrations: self.migrations.clone(),
+            universe_hooks: Vec::new(),
+        }
+    }
+
+    /// Creates a new [`PermissionManager`](crate::PermissionManager) whose universe is the universe of this one mounted beneath a
+    /// namespace group, so generic actions (`"view"`, `"edit"`) can be applied to different resource namespaces at runtime.  
+    /// The namespaced manager keeps the settings of this manager as in [`scoped`](Self::scoped()), with aliases, implications and
+    /// obligations mounted beneath the namespace too, and has its own [`ManagerId`](crate::ManagerId). Mounting a namespaced
+    /// manager mounts it beneath the new namespace, e.g. `generic.mounted("project").mounted("org")` has the namespace `org:project`.
+    ///
+    /// Use [`mount_perm`](Self::mount_perm()) and [`unmount_perm`](Self::unmount_perm()) to convert generic
+    /// [`Permission`](crate::Permission)s to and from the namespace.
     ///
     /// # Examples:
     ///
     /// ```
     /// use simple_perm_manager::PermissionManager;
     ///
-    /// let manager = PermissionManager::from_json(&String::from(r#"{
-    ///     "create": true,
-    ///     "view": true
-    /// }"#));
+    /// let generic = PermissionManager::from_json(r#"{"view": true, "edit": true}"#);
+    /// let project_x = generic.mounted("project_x");
     ///
-    /// // Create managed Permission from the previous PermissionManager
-    /// let managed_perm = manager.perm_from_json(&String::from(r#"{"create": true}"#));
+    /// let perm = project_x.mount_perm(&generic.perm_from_json(r#"{"edit": true}"#));
+    /// assert_eq!(project_x.namespace(), Some("project_x"));
+    /// assert_eq!(perm.to_json(), r#"{"project_x":{"edit":true}}"#);
+    /// assert_eq!(project_x.unmount_perm(&perm).to_json(), r#"{"edit":true}"#);
     /// ```
     ///
     /// # Panics:
     ///
-    /// Function panics if actions provided for the Permission are not included in the manager universe
-    /// ```rust,should_panic
+    /// Panics if the mounted actions do not follow the [`name_policy`](Self::name_policy()) of the
+    /// [`PermissionManager`](crate::PermissionManager), e.g. if the namespace is empty.
+    pub fn mounted(&self, namespace: &str) -> PermissionManager {
+        let namespace = self.fold(namespace);
+        let mount = |key: &str| format!("{}{}{}", namespace, self.separator, key);
+        let universe_actions: HashSet<String> = self.universe.actions().map(mount).collect();
+        let rejected = self.name_policy.rejected(&universe_actions, self.separator);
+        if !rejected.is_empty() {
+            panic!(
+                "Actions do not follow the PermissionManager name policy: {}",
+                rejected.join(", ")
+            );
+        }
+
+        let mut manager = PermissionManager::from_actions(HashSet::new());
+        manager.aliases = self
+            .aliases
+            .iter()
+            .map(|(alias, action)| (mount(alias), mount(action)))
+            .collect();
+        manager.implications = self
+            .implications
+            .iter()
+            .map(|(action, implied)| {
+                (
+                    mount(action),
+                    implied.iter().map(|implied| mount(implied)).collect(),
+                )
+            })
+            .collect();
+        manager.obligations = self
+            .obligations
+            .iter()
+            .map(|(action, obligations)| (mount(action), obligations.clone()))
+            .collect();
+        manager.combining_algorithm = self.combining_algorithm;
+        manager.separator = self.separator;
+        manager.accepted_separators = self.accepted_separators.clone();
+        manager.name_policy = self.name_policy.clone();
+        manager.case_insensitive = self.case_insensitive;
+        manager.tenant = self.tenant.clone();
+        manager.version = self.version;
+        manager.migrations = self
+            .migrations
+            .iter()
+            .map(|(version, steps)| {
+                let steps = steps
+                    .iter()
+                    .map(|step| step.map_actions(|action| mount(action)))
+                    .collect();
+                (*version, steps)
+            })
+            .collect();
+        manager.set_universe(universe_actions);
+        manager.namespace = Some(match &self.namespace {
+            Some(outer) => mount(outer),
+            None => namespace,
+        });
+
+        manager
+    }
+
+    /// Returns the namespace of a [`PermissionManager`](crate::PermissionManager) created with [`mounted`](Self::mounted()), if any.
+    pub fn namespace(&self) -> Option<&str> {
+        self.namespace.as_deref()
+    }
+
+    /// Returns a [`Permission`](crate::Permission) managed by this namespaced [`PermissionManager`](crate::PermissionManager) with the
+    /// actions of a generic [`Permission`](crate::Permission) mounted beneath the [`namespace`](Self::namespace()), normalized as in
+    /// [`perm_from_actions`](Self::perm_from_actions()).
+    ///
+    /// # Panics:
+    ///
+    /// - Panics if the [`PermissionManager`](crate::PermissionManager) was not created with [`mounted`](Self::mounted()).
+    /// - Panics in the same cases that [`perm_from_actions`](Self::perm_from_actions()) does.
+    pub fn mount_perm(&self, perm: &Permission) -> Permission {
+        let namespace = match &self.namespace {
+            Some(namespace) => namespace,
+            None => panic!("PermissionManager is not namespaced"),
+        };
+
+        self.perm_from_actions(
+            perm.actions()
+                .map(|action| format!("{}{}{}", namespace, self.separator, action))
+                .collect(),
+        )
+    }
+
+    /// Returns an unmanaged generic [`Permission`](crate::Permission) with the actions of a [`Permission`](crate::Permission) of this
+    /// namespaced [`PermissionManager`](crate::PermissionManager) without the [`namespace`](Self::namespace()), undoing
+    /// [`mount_perm`](Self::mount_perm()).
+    ///
+    /// # Panics:
+    ///
+    /// - Panics if the [`PermissionManager`](crate::PermissionManager) was not created with [`mounted`](Self::mounted()).
+    /// - Panics if the [`Permission`](crate::Permission) is not managed by this [`PermissionManager`](crate::PermissionManager).
+    pub fn unmount_perm(&self, perm: &Permission) -> Permission {
+        let namespace = match &self.namespace {
+            Some(namespace) => namespace,
+            None => panic!("PermissionManager is not namespaced"),
+        };
+        if !self.universe.has_same_manager(perm) {
+            panic!("Permission to unmount is not managed by this manager");
+        }
+
+        perm.strip_prefix(namespace)
+            .expect("universe actions should be beneath the namespace")
+    }
+
+    /// Returns the tenant of a [`PermissionManager`](crate::PermissionManager) created with [`scoped`](Self::scoped()), if any.
+    pub fn tenant(&self) -> Option<&str> {
+        self.tenant.as_deref()
+    }
+
+    /// Returns the [`PermissionManager`](crate::PermissionManager) universe actions in a JSON formatted string.  
+    /// The JSON uses the same nested format accepted by [`from_json`](Self::from_json()), so universes built programmatically
+    /// can be persisted or shipped to a frontend and loaded back.
+    ///
+    /// # Examples:
+    ///
+    /// ```
+    /// use std::collections::HashSet;
+    /// use simple_perm_manager::PermissionManager;
+    ///
+    /// let manager = PermissionManager::from_actions(HashSet::from([
+    ///    String::from("building:create"),
+    ///    String::from("building:view"),
+    /// ]));
+    ///
+    /// let json = manager.to_json();
+    /// assert_eq!(json, r#"{"building":{"create":true,"view":true}}"#);
+    ///
+    /// let loaded = PermissionManager::from_json(&json);
+    /// assert_eq!(loaded.to_actions(), manager.to_actions());
+    /// ```
+    pub fn to_json(&self) -> String {
+        self.universe.to_json()
+    }
+
+    /// Returns a [JSON Schema](https://json-schema.org) describing the actions JSON strings accepted by
+    /// [`perm_from_json`](Self::perm_from_json()) for the [`PermissionManager`](crate::PermissionManager) universe, so payloads can be
+    /// validated before reaching Rust, e.g. by an API gateway or a frontend.  
+    /// Actions and aliases are boolean properties and groups are objects without additional properties, which can also be set
+    /// to a boolean to include or exclude the whole group.
+    ///
+    /// # Examples:
+    ///
+    /// ```
+    /// use simple_perm_manager::PermissionManager;
+    ///
+    /// let manager = PermissionManager::from_json(r#"{"building": {"view": true}}"#);
+    ///
+    /// assert_eq!(
+    ///     manager.to_json_schema(),
+    ///     concat!(
+    ///         r#"{"$schema":"https://json-schema.org/draft/2020-12/schema","additionalProperties":false,"#,
+    ///         r#""properties":{"building":{"anyOf":[{"type":"boolean"},{"additionalProperties":false,"#,
+    ///         r#""properties":{"view":{"type":"boolean"}},"type":"object"}]}},"type":"object"}"#
+    ///     )
+    /// );
+    /// ```
+    pub fn to_json_schema(&self) -> String {
+        let actions: Vec<Vec<&str>> = self
+            .universe
+            .actions()
+            .chain(self.aliases.keys().map(String::as_str))
+            .map(|action| action.split(self.separator).collect())
+            .collect();
+
+        let mut schema = group_schema(&actions.iter().map(Vec::as_slice).collect::<Vec<_>>());
+        schema.insert(
+            String::from("$schema"),
+            Value::from("https://json-schema.org/draft/2020-12/schema"),
+        );
+        Value::Object(schema).to_string()
+    }
+
+    /// Returns the `components` fragment of an [OpenAPI](https://spec.openapis.org/oas/v3.1.0) document declaring an OAuth2
+    /// security scheme with the name and token URL provided, whose client credentials flow has one scope per universe action, so
+    /// API docs stay in sync with the universe.  
+    /// Scopes are named after the actions and sorted.
+    ///
+    /// # Examples:
+    ///
+    /// ```
+    /// use simple_perm_manager::PermissionManager;
+    ///
+    /// let manager = PermissionManager::from_json(r#"{"building": {"view": true, "edit": true}}"#);
+    ///
+    /// assert_eq!(
+    ///     manager.to_openapi_security_scheme("permissions", "https://auth.example.com/token"),
+    ///     concat!(
+    ///         r#"{"securitySchemes":{"permissions":{"flows":{"clientCredentials":{"scopes":{"#,
+    ///         r#""building:edit":"Allows the building:edit action","building:view":"Allows the building:view action"},"#,
+    ///         r#""tokenUrl":"https://auth.example.com/token"}},"type":"oauth2"}}}"#
+    ///     )
+    /// );
+    /// ```
+    pub fn to_openapi_security_scheme(&self, scheme_name: &str, token_url: &str) -> String {
+        let mut scopes = Map::new();
+        for action in self.universe.actions() {
+            scopes.insert(
+                action.to_string(),
+                Value::from(format!("Allows the {} action", action)),
+            );
+        }
+
+        let mut flow = Map::new();
+        flow.insert(String::from("tokenUrl"), Value::from(token_url));
+        flow.insert(String::from("scopes"), Value::Object(scopes));
+        let mut flows = Map::new();
+        flows.insert(String::from("clientCredentials"), Value::Object(flow));
+        let mut scheme = Map::new();
+        scheme.insert(String::from("type"), Value::from("oauth2"));
+        scheme.insert(String::from("flows"), Value::Object(flows));
+        let mut schemes = Map::new();
+        schemes.insert(String::from(scheme_name), Value::Object(scheme));
+        let mut components = Map::new();
+        components.insert(String::from("securitySchemes"), Value::Object(schemes));
+
+        Value::Object(components).to_string()
+    }
+
+    /// Returns the [`PermissionManager`](crate::PermissionManager) universe actions.
+    ///
+    /// # Examples:
+    ///
+    /// ```
+    /// use std::collections::HashSet;
+    /// use simple_perm_manager::PermissionManager;
+    ///
+    /// let manager = PermissionManager::from_json(r#"{"create": true, "view": true}"#);
+    ///
+    /// assert_eq!(manager.to_actions(), HashSet::from([String::from("create"), String::from("view")]));
+    /// ```
+    pub fn to_actions(&self) -> HashSet<String> {
+        self.universe.get_actions().into_owned()
+    }
+
+    /// Returns the universe actions as an [`ActionTree`](crate::ActionTree).
+    ///
+    /// # Examples:
+    ///
+    /// ```
+    /// use simple_perm_manager::PermissionManager;
+    ///
+    /// let manager = PermissionManager::from_json(r#"{"building": {"view": true, "edit": true}, "user": {"view": true}}"#);
+    ///
+    /// let tree = manager.universe_tree();
+    /// assert_eq!(tree.node("building").unwrap().children().count(), 2);
+    /// assert!(tree.node("user:view").unwrap().is_leaf());
+    /// ```
+    pub fn universe_tree(&self) -> ActionTree {
+        self.universe.to_tree()
+    }
+
+    /// Returns a [Graphviz](https://graphviz.org) DOT digraph of the universe action hierarchy, e.g. for generating diagrams for
+    /// security architecture docs.  
+    /// Each group and action is a node identified by its full name and labeled with its last group or name, groups are rounded
+    /// and, if a [`Permission`](crate::Permission) is provided, its actions are highlighted.
+    ///
+    /// # Examples:
+    ///
+    /// ```
+    /// use simple_perm_manager::PermissionManager;
+    ///
+    /// let manager = PermissionManager::from_json(r#"{"building": {"view": true, "edit": true}}"#);
+    /// let perm = manager.perm_from_json(r#"{"building": {"view": true}}"#);
+    ///
+    /// assert_eq!(
+    ///     manager.to_dot(Some(&perm)),
+    ///     r#"digraph universe {
+    ///     node [shape=box];
+    ///     "building" [label="building", style=rounded];
+    ///     "building:edit" [label="edit"];
+    ///     "building" -> "building:edit";
+    ///     "building:view" [label="view", style=filled, fillcolor=lightgreen];
+    ///     "building" -> "building:view";
+    /// }"#
+    /// );
+    /// ```
+    ///
+    /// # Panics:
+    ///
+    /// Panics if the [`Permission`](crate::Permission) provided is not managed by this [`PermissionManager`](crate::PermissionManager).
+    pub fn to_dot(&self, highlighted: Option<&Permission>) -> String {
+        if highlighted.is_some_and(|perm| !self.universe.has_same_manager(perm)) {
+            panic!("Permission to highlight is not managed by this manager");
+        }
+
+        diagram::to_dot(
+            &self.universe_tree(),
+            highlighted.map(|perm| perm.get_actions()).as_deref(),
+        )
+    }
+
+    /// Returns a [Mermaid](https://mermaid.js.org) flowchart of the universe action hierarchy, which can be pasted into wikis and
+    /// PR descriptions that render Mermaid.  
+    /// Each group and action is a node labeled with its last group or name, groups are rounded and, if a
+    /// [`Permission`](crate::Permission) is provided, its actions are highlighted with the `granted` class.
+    ///
+    /// # Examples:
+    ///
+    /// ```
+    /// use simple_perm_manager::PermissionManager;
+    ///
+    /// let manager = PermissionManager::from_json(r#"{"building": {"view": true, "edit": true}}"#);
+    /// let perm = manager.perm_from_json(r#"{"building": {"view": true}}"#);
+    ///
+    /// assert_eq!(
+    ///     manager.to_mermaid(Some(&perm)),
+    ///     r#"flowchart TD
+    ///     n0("building")
+    ///     n1["edit"]
+    ///     n0 --> n1
+    ///     n2["view"]:::granted
+    ///     n0 --> n2
+    ///     classDef granted fill:#9f9,stroke:#363"#
+    /// );
+    /// ```
+    ///
+    /// # Panics:
+    ///
+    /// Panics if the [`Permission`](crate::Permission) provided is not managed by this [`PermissionManager`](crate::PermissionManager).
+    pub fn to_mermaid(&self, highlighted: Option<&Permission>) -> String {
+        if highlighted.is_some_and(|perm| !self.universe.has_same_manager(perm)) {
+            panic!("Permission to highlight is not managed by this manager");
+        }
+
+        diagram::to_mermaid(
+            &self.universe_tree(),
+            highlighted.map(|perm| perm.get_actions()).as_deref(),
+        )
+    }
+
+    /// Returns a managed [`Permission`](crate::Permission) with the actions of an [`ActionTree`](crate::ActionTree), joined with the
+    /// [`separator`](Self::separator()) of the [`PermissionManager`](crate::PermissionManager).
+    ///
+    /// # Examples:
+    ///
+    /// ```
+    /// use simple_perm_manager::{ActionTree, PermissionManager};
+    ///
+    /// let manager = PermissionManager::from_json(r#"{"building": {"view": true, "edit": true}}"#);
+    /// let mut tree = ActionTree::new('/');
+    /// tree.insert("building/view");
+    ///
+    /// assert!(manager.perm_from_tree(&tree).contains_action("building:view"));
+    /// ```
+    ///
+    /// # Panics:
+    ///
+    /// Panics in the same cases that [`perm_from_actions`](Self::perm_from_actions()) does.
+    pub fn perm_from_tree(&self, tree: &ActionTree) -> Permission {
+        self.perm_from_actions(tree.actions_with_separator(self.separator))
+    }
+
+    /// Returns the groups of the universe actions by level, as in [`Permission::groups`](crate::Permission::groups()).
+    ///
+    /// # Examples:
+    ///
+    /// ```
+    /// use std::collections::HashSet;
+    /// use simple_perm_manager::PermissionManager;
+    ///
+    /// let manager = PermissionManager::from_json(r#"{"building": {"room": {"edit": true}}, "user": {"view": true}}"#);
+    ///
+    /// assert_eq!(manager.groups()[0], HashSet::from([String::from("building"), String::from("user")]));
+    /// assert_eq!(manager.groups()[1], HashSet::from([String::from("building:room")]));
+    /// ```
+    pub fn groups(&self) -> Vec<HashSet<String>> {
+        self.universe.groups()
+    }
+
+    /// Returns a managed [`Permission`](crate::Permission) with the universe actions beneath a group, e.g. the universe of a single
+    /// module for a micro-frontend. The [`Permission`](crate::Permission) is empty if the group is not in the universe.
+    ///
+    /// # Examples:
+    ///
+    /// ```
+    /// use std::collections::HashSet;
+    /// use simple_perm_manager::PermissionManager;
+    ///
+    /// let manager = PermissionManager::from_json(r#"{"building": {"view": true, "edit": true}, "user": {"view": true}}"#);
+    ///
+    /// let building = manager.universe_subtree("building");
+    /// assert_eq!(*building.get_actions(), HashSet::from([String::from("building:view"), String::from("building:edit")]));
+    /// assert!(manager.validate_perm(&building));
+    /// ```
+    pub fn universe_subtree(&self, group: &str) -> Permission {
+        let group = self.fold(group);
+        self.managed_perm(self.index.group_actions(&group).map(String::from).collect())
+    }
+
+    /// Returns the universe actions matching a glob pattern, sorted, e.g. for slicing the action space in admin UIs.  
+    /// The pattern is divided in groups by the [`separator`](Self::separator()): a `**` group matches any number of groups and,
+    /// inside a group, `*` matches any sequence of characters and `?` any single character.
+    ///
+    /// # Examples:
+    ///
+    /// ```
+    /// use simple_perm_manager::PermissionManager;
+    ///
+    /// let manager = PermissionManager::from_json(r#"{
+    ///     "building": {"view": true, "room": {"view": true, "edit": true}, "floor": {"view": true}},
+    ///     "user": {"view": true}
+    /// }"#);
+    ///
+    /// assert_eq!(manager.actions_matching("building:*:view"), vec!["building:floor:view", "building:room:view"]);
+    /// assert_eq!(manager.actions_matching("**:view").len(), 4);
+    /// assert_eq!(manager.actions_matching("building:room:*"), vec!["building:room:edit", "building:room:view"]);
+    /// ```
+    pub fn actions_matching(&self, glob: &str) -> Vec<String> {
+        self.index
+            .actions()
+            .filter(|action| glob_matches(glob, action, self.separator))
+            .map(String::from)
+            .collect()
+    }
+
+    /// Returns the actions of a [`Permission`](crate::Permission) managed by this [`PermissionManager`](crate::PermissionManager) in a
+    /// compact JSON formatted string, where groups whose universe actions are all in the [`Permission`](crate::Permission) are
+    /// collapsed into a group-level `true`.  
+    /// The JSON can be loaded back with [`perm_from_json`](Self::perm_from_json()), which expands the groups again.
+    ///
+    /// # Examples:
+    ///
+    /// ```
+    /// use simple_perm_manager::PermissionManager;
+    ///
+    /// let manager = PermissionManager::from_json(r#"{"building": {"view": true, "edit": true}, "user": {"view": true, "edit": true}}"#);
+    /// let perm = manager.perm_from_json(r#"{"building": {"view": true, "edit": true}, "user": {"view": true}}"#);
+    ///
+    /// let json = manager.perm_to_compact_json(&perm);
+    /// assert_eq!(json, r#"{"building":true,"user":{"view":true}}"#);
+    /// assert_eq!(manager.perm_from_json(&json).get_actions(), perm.get_actions());
+    /// ```
+    ///
+    /// # Panics:
+    ///
+    /// Panics if the [`Permission`](crate::Permission) is not managed by this [`PermissionManager`](crate::PermissionManager).
+    pub fn perm_to_compact_json(&self, perm: &Permission) -> String {
+        if !self.universe.has_same_manager(perm) {
+            panic!("Permission to serialize is not managed by this manager");
+        }
+
+        let compact: HashSet<String> = perm
+            .actions()
+            .map(|action| {
+                // Outermost group of the action with all its universe actions in the permission, if any
+                action
+                    .match_indices(self.separator)
+                    .map(|(position, _)| &action[..position])
+                    .find(|group| {
+                        self.index
+                            .group_actions(group)
+                            .all(|grouped| perm.has_action(grouped))
+                    })
+                    .unwrap_or(action)
+                    .to_string()
+            })
+            .collect();
+
+        Value::Object(serialize_actions(&compact, self.separator)).to_string()
+    }
+
+    /// Returns every universe action of the [`PermissionManager`](crate::PermissionManager) in a JSON formatted string, with `true`
+    /// values for the actions of the [`Permission`](crate::Permission) provided and `false` values for the rest, e.g. for rendering
+    /// a checkbox matrix in a frontend.  
+    /// `false` values are ignored when loading the JSON back with [`perm_from_json`](Self::perm_from_json()).
+    ///
+    /// # Examples:
+    ///
+    /// ```
+    /// use simple_perm_manager::PermissionManager;
+    ///
+    /// let manager = PermissionManager::from_json(r#"{"building": {"view": true, "edit": true}, "user": {"view": true}}"#);
+    /// let perm = manager.perm_from_json(r#"{"building": {"view": true}}"#);
+    ///
+    /// let json = manager.perm_to_json_full(&perm);
+    /// assert_eq!(json, r#"{"building":{"edit":false,"view":true},"user":{"view":false}}"#);
+    /// assert_eq!(manager.perm_from_json(&json).get_actions(), perm.get_actions());
+    /// ```
+    ///
+    /// # Panics:
+    ///
+    /// - Panics if the [`Permission`](crate::Permission) is not managed by this [`PermissionManager`](crate::PermissionManager).
+    /// - Panics if a universe action is also a group of another universe action (e.g. `"building"` and `"building:view"`),
+    ///   as a JSON key can not hold both a boolean and an object.
+    pub fn perm_to_json_full(&self, perm: &Permission) -> String {
+        if !self.universe.has_same_manager(perm) {
+            panic!("Permission to serialize is not managed by this manager");
+        }
+
+        let excluded = self
+            .universe
+            .actions()
+            .filter(|action| !perm.has_action(action));
+
+        Value::Object(serialize_policy_actions(
+            perm.actions(),
+            excluded,
+            self.separator,
+        ))
+        .to_string()
+    }
+
+    /// Returns `true` if the [`Permission`](crate::Permission) provided is valid for the [`PermissionManager`](crate::PermissionManager).
+    /// A [`Permission`](crate::Permission) is valid if it is managed by this [`PermissionManager`](crate::PermissionManager) and its contained in the [`PermissionManager`](crate::PermissionManager) universe.
+    ///
+    /// # Examples:
+    ///
+    /// ```
     /// use simple_perm_manager::PermissionManager;
+    /// use simple_perm_manager::Permission;
     ///
     /// let manager = PermissionManager::from_json(&String::from(r#"{
     ///     "create": true,
     ///     "view": true
     /// }"#));
     ///
-    /// // This line of code panics
-    /// let panics = manager.perm_from_json(&String::from(r#"{"other_action": true}"#));
+    /// // Create managed Permission from the previous PermissionManager
+    /// let managed_perm = manager.perm_from_json(&String::from(r#"{
+    ///     "create": true
+    /// }"#));
+    ///
+    /// // Create UNmanaged Permission (actions do not have to be a subset of the manager universe actions)
+    /// let unmanaged_perm = Permission::from_json_and_uuid(&String::from(r#"{
+    ///     "other_action": true
+    /// }"#), None);
+    ///
+    /// assert!(manager.validate_perm(&managed_perm));
+    /// assert!(!manager.validate_perm(&unmanaged_perm));
     /// ```
-    pub fn perm_from_json(&self, actions_json: &str) -> Permission {
-        let perm = Permission::from_json_and_uuid(actions_json, Some(self.id));
-
-        if !self.validate_perm(&perm) {
-            panic!("Actions for Permission creation not allowed in PermissionManager or Permission id does not correspond to Manager id")
+    #[cfg_attr(feature = "tracing", tracing::instrument(level = "debug", skip_all, fields(manager_id = %self.id, actions = perm.len()), ret))]
+    pub fn validate_perm(&self, perm: &Permission) -> bool {
+        let valid = self.universe.has_same_manager(perm) && self.universe.contains(perm);
+        if !valid {
+            telemetry::record_validation_failure();
         }
 
-        perm
+        valid
+    }
+
+    /// Returns a [`ValidationReport`](crate::ValidationReport) with the details of validating the [`Permission`](crate::Permission)
+    /// provided against the [`PermissionManager`](crate::PermissionManager): the actions outside the universe with suggested
+    /// close matches and whether the manager mismatches.  
+    /// The report is valid in the same cases that [`validate_perm`](Self::validate_perm()) returns `true`.
+    ///
+    /// # Examples:
+    ///
+    /// ```
+    /// use simple_perm_manager::{Permission, PermissionManager};
+    ///
+    /// let manager = PermissionManager::from_json(r#"{"building": {"view": true, "edit": true}}"#);
+    ///
+    /// let report = manager.validate_perm_report(&manager.perm_from_json(r#"{"building": {"view": true}}"#));
+    /// assert!(report.is_valid());
+    ///
+    /// let report = manager.validate_perm_report(&Permission::from_json(r#"{"building": {"edti": true}}"#));
+    /// assert!(!report.is_valid());
+    /// assert_eq!(report.suggestions("building:edti"), [String::from("building:edit")]);
+    /// ```
+    pub fn validate_perm_report(&self, perm: &Permission) -> ValidationReport {
+        ValidationReport::new(!self.universe.has_same_manager(perm), perm, &self.universe)
+    }
+
+    /// Returns a managed [`Permission`](crate::Permission) with the actions in the [`Permission`](crate::Permission) provided unless the ones not
+    /// allowed by the [`PermissionManager`](crate::PermissionManager) actions universe.  
+    /// The [`Permission`](crate::Permission) used as argument can be both managed and unmanaged.
+    ///
+    /// /// # Examples:
+    ///
+    /// ```
+    /// use std::collections::HashSet;
+    /// use simple_perm_manager::PermissionManager;
+    /// use simple_perm_manager::Permission;
+    ///
+    /// // Manager only allowing 'create' and 'view' actions
+    /// let manager = PermissionManager::from_json(&String::from(r#"{
+    ///     "create": true,
+    ///     "view": true
+    /// }"#));
+    ///
+    /// // Create permissions
+    /// let perm1 = Permission::from_json(&String::from(r#"{
+    ///     "create": true,
+    ///     "delete": true
+    /// }"#));
+    /// let perm2 = Permission::from_json(&String::from(r#"{
+    ///     "create": true,
+    ///     "view": true,
+    ///     "delete": true
+    /// }"#));
+    ///
+    /// assert_eq!(*manager.clean_perm(&perm1).get_actions(), HashSet::from(["create".to_string()]));
+    /// assert_eq!(*manager.clean_perm(&perm2).get_actions(), HashSet::from(["create".to_string(), "view".to_string()]));
+    /// ```
+    pub fn clean_perm(&self, perm: &Permission) -> Permission {
+        let intersection: HashSet<String> = perm
+            .actions()
+            .filter(|action| self.universe.has_action(action))
+            .map(String::from)
+            .collect();
+        self.managed_perm(intersection)
+    }
+
+    /// Adds the actions provided to the [`PermissionManager`](crate::PermissionManager) universe.
+    ///
+    /// [`Permission`](crate::Permission)s issued before the change remain valid, as the universe only grows.
+    /// Encoded [`Permission`](crate::Permission)s and [`BitmapPermission`](crate::BitmapPermission)s depend on the universe
+    /// action order, so they must be created again after changing the universe.
+    ///
+    /// # Examples:
+    ///
+    /// ```
+    /// use std::collections::HashSet;
+    /// use simple_perm_manager::PermissionManager;
+    ///
+    /// let mut manager = PermissionManager::from_json(r#"{"create": true, "view": true}"#);
+    /// manager.add_actions(HashSet::from([String::from("delete")]));
+    ///
+    /// assert!(manager.get_universe().contains_action("delete"));
+    /// assert!(manager.validate_perm(&manager.perm_from_json(r#"{"delete": true}"#)));
+    /// ```
+    ///
+    /// # Panics:
+    ///
+    /// Panics if any action does not follow the [`name_policy`](Self::name_policy()) of the [`PermissionManager`](crate::PermissionManager).
+    pub fn add_actions(&mut self, actions: HashSet<String>) {
+        let actions: HashSet<String> = actions.iter().map(|action| self.fold(action)).collect();
+        let rejected = self.name_policy.rejected(&actions, self.separator);
+        if !rejected.is_empty() {
+            panic!(
+                "Actions do not follow the PermissionManager name policy: {}",
+                rejected.join(", ")
+            );
+        }
+
+        let universe_actions = self
+            .universe
+            .actions()
+            .map(String::from)
+            .chain(actions)
+            .collect();
+        self.set_universe(universe_actions);
+    }
+
+    /// Removes the actions provided from the [`PermissionManager`](crate::PermissionManager) universe.
+    ///
+    /// [`Permission`](crate::Permission)s issued before the change that contain removed actions are no longer valid,
+    /// use [`migrate_perm`](Self::migrate_perm()) to strip them.
+    /// Encoded [`Permission`](crate::Permission)s and [`BitmapPermission`](crate::BitmapPermission)s depend on the universe
+    /// action order, so they must be created again after changing the universe.
+    ///
+    /// # Examples:
+    ///
+    /// ```
+    /// use std::collections::HashSet;
+    /// use simple_perm_manager::PermissionManager;
+    ///
+    /// let mut manager = PermissionManager::from_json(r#"{"create": true, "view": true}"#);
+    /// let perm = manager.perm_from_json(r#"{"create": true, "view": true}"#);
+    ///
+    /// manager.remove_actions(&HashSet::from([String::from("create")]));
+    ///
+    /// assert!(!manager.validate_perm(&perm));
+    /// assert!(manager.validate_perm(&manager.migrate_perm(&perm)));
+    /// ```
+    pub fn remove_actions(&mut self, actions: &HashSet<String>) {
+        let actions: HashSet<String> = actions.iter().map(|action| self.fold(action)).collect();
+        let universe_actions = self
+            .universe
+            .actions()
+            .filter(|action| !actions.contains(*action))
+            .map(String::from)
+            .collect();
+        self.set_universe(universe_actions);
+    }
+
+    /// Returns a [`Permission`](crate::Permission) issued by this [`PermissionManager`](crate::PermissionManager) without the actions
+    /// that are no longer in the universe, linked to the current universe.
+    ///
+    /// # Examples:
+    ///
+    /// ```
+    /// use std::collections::HashSet;
+    /// use simple_perm_manager::PermissionManager;
+    ///
+    /// let mut manager = PermissionManager::from_json(r#"{"create": true, "view": true}"#);
+    /// let perm = manager.perm_from_json(r#"{"create": true, "view": true}"#);
+    ///
+    /// manager.remove_actions(&HashSet::from([String::from("view")]));
+    ///
+    /// assert_eq!(*manager.migrate_perm(&perm).get_actions(), HashSet::from([String::from("create")]));
+    /// ```
+    ///
+    /// # Panics:
+    ///
+    /// Panics if the [`Permission`](crate::Permission) is not managed by this [`PermissionManager`](crate::PermissionManager).
+    pub fn migrate_perm(&self, perm: &Permission) -> Permission {
+        if !self.universe.has_same_manager(perm) {
+            panic!("Permission to migrate is not managed by this manager");
+        }
+
+        self.clean_perm(perm)
+    }
+
+    /// Renames an action of the [`PermissionManager`](crate::PermissionManager) universe.  
+    /// The old name is remembered so [`rewrite_perm`](Self::rewrite_perm()) can map it to the new one in stored [`Permission`](crate::Permission)s.
+    ///
+    /// Encoded [`Permission`](crate::Permission)s and [`BitmapPermission`](crate::BitmapPermission)s depend on the universe
+    /// action order, so they must be created again after changing the universe.
+    ///
+    /// # Examples:
+    ///
+    /// ```
+    /// use simple_perm_manager::PermissionManager;
+    ///
+    /// let mut manager = PermissionManager::from_json(r#"{"building": {"remove": true, "view": true}}"#);
+    /// let perm = manager.perm_from_json(r#"{"building": {"remove": true}}"#);
+    ///
+    /// manager.rename_action("building:remove", "building:delete");
+    ///
+    /// assert!(manager.rewrite_perm(&perm).contains_action("building:delete"));
+    /// ```
+    ///
+    /// # Panics:
+    ///
+    /// - Panics if `from` is not in the [`PermissionManager`](crate::PermissionManager) universe.
+    /// - Panics if `to` is already in the [`PermissionManager`](crate::PermissionManager) universe.
+    /// - Panics if `to` does not follow the [`name_policy`](Self::name_policy()) of the [`PermissionManager`](crate::PermissionManager).
+    pub fn rename_action(&mut self, from: &str, to: &str) {
+        self.rename_actions(HashMap::from([(self.fold(from), self.fold(to))]));
+    }
+
+    /// Renames a group of the [`PermissionManager`](crate::PermissionManager) universe, renaming all the actions in the group
+    /// and its subgroups.  
+    /// The old names are remembered so [`rewrite_perm`](Self::rewrite_perm()) can map them to the new ones in stored [`Permission`](crate::Permission)s.
+    ///
+    /// # Examples:
+    ///
+    /// ```
+    /// use simple_perm_manager::PermissionManager;
+    ///
+    /// let mut manager = PermissionManager::from_json(r#"{"building": {"view": true, "room": {"edit": true}}}"#);
+    /// let perm = manager.perm_from_json(r#"{"building": {"view": true, "room": {"edit": true}}}"#);
+    ///
+    /// manager.rename_group("building", "site");
+    ///
+    /// assert_eq!(manager.to_json(), r#"{"site":{"room":{"edit":true},"view":true}}"#);
+    /// assert_eq!(manager.rewrite_perm(&perm).to_json(), r#"{"site":{"room":{"edit":true},"view":true}}"#);
+    /// ```
+    ///
+    /// # Panics:
+    ///
+    /// - Panics if the [`PermissionManager`](crate::PermissionManager) universe has no actions in the group `from`.
+    /// - Panics if any renamed action is already in the [`PermissionManager`](crate::PermissionManager) universe.
+    /// - Panics if any renamed action does not follow the [`name_policy`](Self::name_policy()) of the [`PermissionManager`](crate::PermissionManager).
+    pub fn rename_group(&mut self, from: &str, to: &str) {
+        let (from, to) = (self.fold(from), self.fold(to));
+        let prefix = format!("{}{}", from, self.separator);
+        let renamed: HashMap<String, String> = self
+            .universe
+            .actions()
+            .filter_map(|action| {
+                action.strip_prefix(&prefix).map(|rest| {
+                    (
+                        action.to_string(),
+                        format!("{}{}{}", to, self.separator, rest),
+                    )
+                })
+            })
+            .collect();
+
+        if renamed.is_empty() {
+            panic!("Group to rename has no actions in PermissionManager universe");
+        }
+
+        self.rename_actions(renamed);
+    }
+
+    // Renames universe actions and records the renames, keeping previous renames pointing to the current names.
+    fn rename_actions(&mut self, renamed: HashMap<String, String>) {
+        let universe = &self.universe;
+        if renamed.keys().any(|from| !universe.has_action(from)) {
+            panic!("Action to rename is not in PermissionManager universe");
+        }
+        if renamed
+            .values()
+            .any(|to| universe.has_action(to) && !renamed.contains_key(to))
+        {
+            panic!("Renamed action is already in PermissionManager universe");
+        }
+        if !self
+            .name_policy
+            .rejected(renamed.values(), self.separator)
+            .is_empty()
+        {
+            panic!("Renamed action does not follow the PermissionManager name policy");
+        }
+
+        let universe_actions = universe
+            .actions()
+            .map(|action| {
+                renamed
+                    .get(action)
+                    .map_or(action, String::as_str)
+                    .to_string()
+            })
+            .collect();
+
+        for current in self.aliases.values_mut() {
+            if let Some(to) = renamed.get(current) {
+                *current = to.clone();
+            }
+        }
+        self.implications = self
+            .implications
+            .drain()
+            .map(|(action, implied)| {
+                (
+                    renamed.get(&action).unwrap_or(&action).clone(),
+                    implied
+                        .iter()
+                        .map(|implied| renamed.get(implied).unwrap_or(implied).clone())
+                        .collect(),
+                )
+            })
+            .collect();
+        self.obligations = self
+            .obligations
+            .drain()
+            .map(|(action, obligations)| {
+                (renamed.get(&action).unwrap_or(&action).clone(), obligations)
+            })
+            .collect();
+        self.aliases.extend(renamed);
+        self.set_universe(universe_actions);
+    }
+
+    /// Returns a [`Permission`](crate::Permission) managed by this [`PermissionManager`](crate::PermissionManager) with the old action names
+    /// of the [`Permission`](crate::Permission) provided mapped to the names given with [`rename_action`](Self::rename_action())
+    /// and [`rename_group`](Self::rename_group()).  
+    /// Old names added back to the universe are kept, and actions that are not in the universe after the mapping are dropped,
+    /// as in [`migrate_perm`](Self::migrate_perm()).
+    ///
+    /// Unmanaged [`Permission`](crate::Permission)s, such as stored ones deserialized with [`Permission::from_json`](crate::Permission::from_json()),
+    /// are rewritten too.
+    ///
+    /// # Examples:
+    ///
+    /// ```
+    /// use simple_perm_manager::{Permission, PermissionManager};
+    ///
+    /// let mut manager = PermissionManager::from_json(r#"{"building": {"view": true}}"#);
+    /// manager.rename_group("building", "site");
+    ///
+    /// let stored = Permission::from_json(r#"{"building": {"view": true}}"#);
+    /// assert!(manager.rewrite_perm(&stored).contains_action("site:view"));
+    /// ```
+    ///
+    /// # Panics:
+    ///
+    /// Panics if the [`Permission`](crate::Permission) is managed by a different [`PermissionManager`](crate::PermissionManager).
+    pub fn rewrite_perm(&self, perm: &Permission) -> Permission {
+        if perm.is_managed() && !self.universe.has_same_manager(perm) {
+            panic!("Permission to rewrite is managed by a different manager");
+        }
+
+        let actions = perm
+            .actions()
+            .map(|action| {
+                if self.universe.has_action(action) {
+                    action
+                } else {
+                    self.aliases.get(action).map_or(action, String::as_str)
+                }
+            })
+            .filter(|action| self.universe.has_action(action))
+            .map(String::from)
+            .collect();
+
+        self.managed_perm(actions)
+    }
+
+    /// Returns the [`UniverseDiff`](crate::UniverseDiff) from the universe of this [`PermissionManager`](crate::PermissionManager) to the
+    /// universe of `other`, with the renames of `other` made with [`rename_action`](Self::rename_action()) and
+    /// [`rename_group`](Self::rename_group()) reported as moved actions.
+    ///
+    /// # Examples:
+    ///
+    /// ```
+    /// use simple_perm_manager::PermissionManager;
+    ///
+    /// let old = PermissionManager::from_json(r#"{"building": {"view": true, "remove": true}}"#);
+    /// let mut new = PermissionManager::from_json(r#"{"building": {"view": true, "remove": true}}"#);
+    /// new.rename_group("building", "site");
+    /// new.rename_action("site:remove", "site:delete");
+    ///
+    /// let moved = old.diff_universe(&new).moved().to_vec();
+    /// assert_eq!(moved, vec![
+    ///     (String::from("building:remove"), String::from("site:delete")),
+    ///     (String::from("building:view"), String::from("site:view")),
+    /// ]);
+    /// ```
+    pub fn diff_universe(&self, other: &PermissionManager) -> UniverseDiff {
+        UniverseDiff::new(
+            &self.universe.get_actions(),
+            &other.universe.get_actions(),
+            &other.aliases,
+            self.separator,
+        )
+    }
+
+    /// Returns the version of the [`PermissionManager`](crate::PermissionManager) universe, `0` unless set with
+    /// [`set_version`](Self::set_version()) or [`add_migration`](Self::add_migration()).
+    pub fn version(&self) -> u32 {
+        self.version
+    }
+
+    /// Sets the version of the [`PermissionManager`](crate::PermissionManager) universe, written by
+    /// [`perm_to_versioned_json`](Self::perm_to_versioned_json()).
+    pub fn set_version(&mut self, version: u32) {
+        self.version = version;
+    }
+
+    /// Registers the [`Migration`](crate::Migration) steps upgrading [`Permission`](crate::Permission)s serialized under the
+    /// universe version `version - 1` to `version`, applied in order. The universe version is raised to `version` if it is older.
+    ///
+    /// # Examples:
+    ///
+    /// ```
+    /// use simple_perm_manager::{Migration, Permission, PermissionManager};
+    ///
+    /// let mut manager = PermissionManager::from_json(r#"{"post": {"view": true, "delete": true}}"#);
+    /// manager.add_migration(1, vec![Migration::rename("posts", "post")]);
+    /// manager.add_migration(2, vec![Migration::rename("post:remove", "post:delete")]);
+    ///
+    /// assert_eq!(manager.version(), 2);
+    /// let stored = Permission::from_json(r#"{"posts": {"remove": true}}"#);
+    /// assert_eq!(manager.upgrade_perm(&stored, 0).to_json(), r#"{"post":{"delete":true}}"#);
+    ///
+    /// // Steps before the version of the permission are not applied
+    /// let stored = Permission::from_json(r#"{"post": {"remove": true}}"#);
+    /// assert_eq!(manager.upgrade_perm(&stored, 1).to_json(), r#"{"post":{"delete":true}}"#);
+    /// ```
+    ///
+    /// # Panics:
+    ///
+    /// - Panics if `version` is `0`.
+    /// - Panics if steps were already registered for `version`.
+    pub fn add_migration(&mut self, version: u32, steps: Vec<Migration>) {
+        if version == 0 {
+            panic!("Migrations can not upgrade to version 0");
+        }
+        if self.migrations.contains_key(&version) {
+            panic!("A migration is already registered for version {}", version);
+        }
+
+        self.migrations.insert(version, steps);
+        self.version = self.version.max(version);
+    }
+
+    /// Returns a [`Permission`](crate::Permission) managed by this [`PermissionManager`](crate::PermissionManager) with the actions of a
+    /// [`Permission`](crate::Permission) serialized under the universe version `from_version` upgraded by the
+    /// [`Migration`](crate::Migration)s registered after it.  
+    /// Actions that are not in the universe after the upgrade are dropped, as in [`rewrite_perm`](Self::rewrite_perm()).
+    ///
+    /// # Panics:
+    ///
+    /// - Panics if the [`Permission`](crate::Permission) is managed by a different [`PermissionManager`](crate::PermissionManager).
+    /// - Panics if `from_version` is newer than the universe version.
+    pub fn upgrade_perm(&self, perm: &Permission, from_version: u32) -> Permission {
+        if perm.is_managed() && !self.universe.has_same_manager(perm) {
+            panic!("Permission to upgrade is managed by a different manager");
+        }
+        if from_version > self.version {
+            panic!(
+                "Permission version {} is newer than universe version {}",
+                from_version, self.version
+            );
+        }
+
+        let actions = self
+            .normalize_actions(
+                self.upgrade_actions(perm.get_actions().into_owned(), from_version),
+                false,
+            )
+            .into_iter()
+            .filter(|action| self.universe.has_action(action))
+            .collect();
+
+        self.managed_perm(actions)
+    }
+
+    // Applies the migration steps registered after the version provided to the actions
+    fn upgrade_actions(&self, actions: HashSet<String>, from_version: u32) -> HashSet<String> {
+        self.migrations
+            .range(from_version + 1..)
+            .flat_map(|(_, steps)| steps)
+            .fold(actions, |actions, step| step.apply(actions, self.separator))
+    }
+
+    /// Serializes a [`Permission`](crate::Permission) managed by this [`PermissionManager`](crate::PermissionManager) into a JSON
+    /// string with the universe version, with the format `{"actions": {...}, "version": <version>}`, to be loaded with
+    /// [`perm_from_versioned_json`](Self::perm_from_versioned_json()) after the universe evolves.
+    ///
+    /// # Examples:
+    ///
+    /// ```
+    /// use simple_perm_manager::PermissionManager;
+    ///
+    /// let mut manager = PermissionManager::from_json(r#"{"view": true}"#);
+    /// manager.set_version(3);
+    ///
+    /// let json = manager.perm_to_versioned_json(&manager.perm_from_json(r#"{"view": true}"#));
+    /// assert_eq!(json, r#"{"actions":{"view":true},"version":3}"#);
+    /// ```
+    ///
+    /// # Panics:
+    ///
+    /// Panics if the [`Permission`](crate::Permission) is not managed by this [`PermissionManager`](crate::PermissionManager).
+    pub fn perm_to_versioned_json(&self, perm: &Permission) -> String {
+        if !self.universe.has_same_manager(perm) {
+            panic!("Permission to serialize is not managed by this manager");
+        }
+
+        let mut map = Map::new();
+        map.insert(
+            String::from("actions"),
+            Value::Object(serialize_actions(perm.actions(), self.separator)),
+        );
+        map.insert(String::from("version"), Value::from(self.version));
+        Value::Object(map).to_string()
+    }
+
+    /// Returns a managed [`Permission`](crate::Permission) from a JSON string created with
+    /// [`perm_to_versioned_json`](Self::perm_to_versioned_json()), upgraded with the [`Migration`](crate::Migration)s registered after
+    /// the version it was serialized under. JSON strings without `version` are considered version `0`.
+    ///
+    /// # Examples:
+    ///
+    /// ```
+    /// use simple_perm_manager::{Migration, PermissionManager};
+    ///
+    /// let mut manager = PermissionManager::from_json(r#"{"view": true, "edit": true}"#);
+    /// manager.add_migration(1, vec![Migration::split("write", ["view", "edit"])]);
+    ///
+    /// let perm = manager.perm_from_versioned_json(r#"{"actions": {"write": true}}"#).unwrap();
+    /// assert_eq!(perm.get_actions().len(), 2);
+    /// ```
+    ///
+    /// # Errors:
+    ///
+    /// - Returns [`ValidationError::InvalidJson`](crate::ValidationError::InvalidJson) if the JSON string does not have the
+    ///   versioned format or its version is newer than the universe version.
+    /// - Returns [`ValidationError::UnknownActions`](crate::ValidationError::UnknownActions) if the upgraded actions are not in the
+    ///   [`PermissionManager`](crate::PermissionManager) universe.
+    pub fn perm_from_versioned_json(&self, json: &str) -> Result<Permission, ValidationError> {
+        let invalid = |message: String| {
+            telemetry::record_parse_error();
+            ValidationError::InvalidJson(message)
+        };
+        let format_error = |detail: &str| {
+            invalid(format!(
+                "wrong format in versioned permission json string{}",
+                detail
+            ))
+        };
+        let map = match serde_json::from_str(json) {
+            Ok(Value::Object(map)) => map,
+            _ => return Err(format_error("")),
+        };
+        let version = match map.get("version") {
+            None => 0,
+            Some(version) => version
+                .as_u64()
+                .and_then(|version| u32::try_from(version).ok())
+                .ok_or_else(|| format_error(" - invalid version"))?,
+        };
+        if version > self.version {
+            return Err(invalid(format!(
+                "permission version {} is newer than universe version {}",
+                version, self.version
+            )));
+        }
+        let actions = match map.get("actions") {
+            Some(Value::Object(actions)) => {
+                try_deserialize_actions(0, "", actions, self.separator).map_err(invalid)?
+            }
+            _ => return Err(format_error(" - invalid actions")),
+        };
+
+        self.try_perm_from_actions(self.upgrade_actions(actions, version))
+    }
+
+    /// Registers a callback called with the previous and the current universe [`Permission`](crate::Permission)s every time
+    /// the universe actions change, i.e. when calling [`add_actions`](Self::add_actions()), [`remove_actions`](Self::remove_actions()),
+    /// [`rename_action`](Self::rename_action()) or [`rename_group`](Self::rename_group()).  
+    /// Callbacks must be thread and unwind safe, so the [`PermissionManager`](crate::PermissionManager) can be shared across threads.
+    ///
+    /// # Examples:
+    ///
+    /// ```
+    /// use std::collections::HashSet;
+    /// use std::sync::{Arc, Mutex};
+    /// use simple_perm_manager::PermissionManager;
+    ///
+    /// let added = Arc::new(Mutex::new(Vec::new()));
+    /// let hook_added = added.clone();
+    ///
+    /// let mut manager = PermissionManager::from_json(r#"{"view": true}"#);
+    /// manager.on_universe_change(move |previous, current| {
+    ///     let mut actions: Vec<String> = current.difference(previous).get_actions().iter().cloned().collect();
+    ///     hook_added.lock().unwrap().append(&mut actions);
+    /// });
+    /// manager.add_actions(HashSet::from([String::from("edit")]));
+    ///
+    /// assert_eq!(*added.lock().unwrap(), vec![String::from("edit")]);
+    /// ```
+    pub fn on_universe_change<F>(&mut self, hook: F)
+    where
+        F: Fn(&Permission, &Permission) + Send + Sync + UnwindSafe + RefUnwindSafe + 'static,
+    {
+        self.universe_hooks.push(Box::new(hook));
+    }
+
+    /// Declares an alias for an action of the [`PermissionManager`](crate::PermissionManager) universe.
+    ///
+    /// Aliases are accepted when creating [`Permission`](crate::Permission)s, for example with [`perm_from_json`](Self::perm_from_json()),
+    /// and normalized to the action they refer to. [`Permission::contains_action`](crate::Permission::contains_action()) resolves aliases
+    /// for [`Permission`](crate::Permission)s issued after the alias is declared.  
+    /// Old names given to [`rename_action`](Self::rename_action()) and [`rename_group`](Self::rename_group()) become aliases too.
+    ///
+    /// # Examples:
+    ///
+    /// ```
+    /// use simple_perm_manager::PermissionManager;
+    ///
+    /// let mut manager = PermissionManager::from_json(r#"{"building": {"delete": true, "view": true}}"#);
+    /// manager.add_alias("building:remove", "building:delete");
+    ///
+    /// let perm = manager.perm_from_json(r#"{"building": {"remove": true}}"#);
+    ///
+    /// assert_eq!(perm.to_json(), r#"{"building":{"delete":true}}"#);
+    /// assert!(perm.contains_action("building:remove"));
+    /// ```
+    ///
+    /// # Panics:
+    ///
+    /// - Panics if `action` is not in the [`PermissionManager`](crate::PermissionManager) universe.
+    /// - Panics if `alias` is an action of the [`PermissionManager`](crate::PermissionManager) universe.
+    pub fn add_alias(&mut self, alias: &str, action: &str) {
+        let (alias, action) = (&self.fold(alias), &self.fold(action));
+        if !self.universe.has_action(action) {
+            panic!("Action to alias is not in PermissionManager universe");
+        }
+        if self.universe.has_action(alias) {
+            panic!("Alias is already an action in PermissionManager universe");
+        }
+
+        self.aliases.insert(alias.to_string(), action.to_string());
+        self.set_universe(self.universe.get_actions().into_owned());
+    }
+
+    /// Returns the aliases of the [`PermissionManager`](crate::PermissionManager) mapped to the universe action they refer to.
+    ///
+    /// # Examples:
+    ///
+    /// ```
+    /// use std::collections::HashMap;
+    /// use simple_perm_manager::PermissionManager;
+    ///
+    /// let mut manager = PermissionManager::from_json(r#"{"delete": true}"#);
+    /// manager.add_alias("remove", "delete");
+    ///
+    /// assert_eq!(manager.aliases(), HashMap::from([(String::from("remove"), String::from("delete"))]));
+    /// ```
+    pub fn aliases(&self) -> HashMap<String, String> {
+        self.aliases
+            .iter()
+            .filter(|(alias, action)| {
+                self.universe.has_action(action) && !self.universe.has_action(alias)
+            })
+            .map(|(alias, action)| (alias.clone(), action.clone()))
+            .collect()
+    }
+
+    /// Returns the deprecated aliases still in use by the [`Permission`](crate::Permission)s provided, such as stored
+    /// [`Permission`](crate::Permission)s that have not been normalized yet.
+    ///
+    /// # Examples:
+    ///
+    /// ```
+    /// use std::collections::HashSet;
+    /// use simple_perm_manager::{Permission, PermissionManager};
+    ///
+    /// let mut manager = PermissionManager::from_json(r#"{"delete": true, "view": true}"#);
+    /// manager.add_alias("remove", "delete");
+    ///
+    /// let stored = [
+    ///     Permission::from_json(r#"{"remove": true}"#),
+    ///     Permission::from_json(r#"{"view": true}"#),
+    /// ];
+    ///
+    /// assert_eq!(manager.aliases_in_use(&stored), HashSet::from([String::from("remove")]));
+    /// ```
+    pub fn aliases_in_use<'a, I>(&self, perms: I) -> HashSet<String>
+    where
+        I: IntoIterator<Item = &'a Permission>,
+    {
+        perms
+            .into_iter()
+            .flat_map(|perm| perm.actions())
+            .filter(|action| self.index.alias(action).is_some())
+            .map(String::from)
+            .collect()
+    }
+
+    /// Declares that granting an action of the [`PermissionManager`](crate::PermissionManager) universe implies another one,
+    /// for example that `"building:edit"` implies `"building:view"`. Implications are followed transitively.
+    ///
+    /// [`Permission`](crate::Permission)s created by the [`PermissionManager`](crate::PermissionManager) include the implied actions and
+    /// [`Permission::contains_action`](crate::Permission::contains_action()) honors implications for [`Permission`](crate::Permission)s
+    /// issued after the implication is declared.
+    ///
+    /// # Examples:
+    ///
+    /// ```
+    /// use std::collections::HashSet;
+    /// use simple_perm_manager::PermissionManager;
+    ///
+    /// let mut manager = PermissionManager::from_json(r#"{"building": {"edit": true, "view": true}}"#);
+    /// manager.add_implication("building:edit", "building:view");
+    ///
+    /// let perm = manager.perm_from_json(r#"{"building": {"edit": true}}"#);
+    /// assert_eq!(*perm.get_actions(), HashSet::from([String::from("building:edit"), String::from("building:view")]));
+    /// ```
+    ///
+    /// # Panics:
+    ///
+    /// - Panics if `action` or `implied` are not in the [`PermissionManager`](crate::PermissionManager) universe.
+    /// - Panics if `action` and `implied` are the same action.
+    pub fn add_implication(&mut self, action: &str, implied: &str) {
+        let (action, implied) = (&self.fold(action), &self.fold(implied));
+        if !self.universe.has_action(action) || !self.universe.has_action(implied) {
+            panic!("Actions in implication are not in PermissionManager universe");
+        }
+        if action == implied {
+            panic!("Action can not imply itself");
+        }
+
+        self.implications
+            .entry(action.to_string())
+            .or_default()
+            .insert(implied.to_string());
+        self.set_universe(self.universe.get_actions().into_owned());
+    }
+
+    /// Returns the actions directly implied by each action of the [`PermissionManager`](crate::PermissionManager) universe
+    /// declared with [`add_implication`](Self::add_implication()).
+    ///
+    /// # Examples:
+    ///
+    /// ```
+    /// use std::collections::{HashMap, HashSet};
+    /// use simple_perm_manager::PermissionManager;
+    ///
+    /// let mut manager = PermissionManager::from_json(r#"{"edit": true, "view": true}"#);
+    /// manager.add_implication("edit", "view");
+    ///
+    /// assert_eq!(manager.implications(), HashMap::from([(String::from("edit"), HashSet::from([String::from("view")]))]));
+    /// ```
+    pub fn implications(&self) -> HashMap<String, HashSet<String>> {
+        let universe = &self.universe;
+
+        self.implications
+            .iter()
+            .filter(|(action, _)| universe.has_action(action))
+            .map(|(action, implied)| {
+                (
+                    action.clone(),
+                    implied
+                        .iter()
+                        .filter(|implied| universe.has_action(implied))
+                        .cloned()
+                        .collect::<HashSet<String>>(),
+                )
+            })
+            .filter(|(_, implied)| !implied.is_empty())
+            .collect()
+    }
+
+    /// Returns a managed [`Permission`](crate::Permission) with the actions specified in the actions set provided.
+    ///
+    /// # Examples:
+    ///
+    /// ```
+    /// use std::collections::HashSet;
+    /// use simple_perm_manager::PermissionManager;
+    ///
+    /// let manager = PermissionManager::from_actions(HashSet::from([
+    ///     String::from("create"),
+    ///     String::from("view"),
+    /// ]));
+    ///
+    /// // Create managed Permission from the previous PermissionManager
+    /// let managed_perm = manager.perm_from_actions(HashSet::from([String::from("create")]));
+    /// ```
+    ///
+    /// # Panics:
+    ///
+    /// Function panics if actions provided for the Permission are not included in the manager universe
+    /// ```rust,should_panic
+    /// use std::collections::HashSet;
+    /// use simple_perm_manager::PermissionManager;
+    ///
+    /// let manager = PermissionManager::from_actions(HashSet::from([
+    ///     String::from("create"),
+    ///     String::from("view"),
+    /// ]));
+    ///
+    /// // This line of code panics
+    /// let panics = manager.perm_from_actions(HashSet::from([String::from("other_action")]));
+    /// ```
+    #[cfg_attr(feature = "tracing", tracing::instrument(level = "debug", skip_all, fields(manager_id = %self.id, actions = actions.len())))]
+    pub fn perm_from_actions(&self, actions: HashSet<String>) -> Permission {
+        let perm = self.granted_perm(actions);
+
+        if !self.validate_perm(&perm) {
+            panic!("Actions for Permission creation not allowed in PermissionManager or Permission id does not correspond to Manager id: {}", self.validate_perm_report(&perm))
+        }
+
+        perm
+    }
+
+    /// Returns a managed [`Permission`](crate::Permission) with the actions provided.  
+    /// Same as [`perm_from_actions`](Self::perm_from_actions()) but accepts any iterable of string-like actions,
+    /// such as `&str`s or the enums generated by [`define_permissions!`](crate::define_permissions).
+    ///
+    /// # Examples:
+    ///
+    /// ```
+    /// use simple_perm_manager::PermissionManager;
+    ///
+    /// let manager = PermissionManager::from_json(r#"{"create": true, "view": true}"#);
+    /// let perm = manager.perm_from(["create", "view"]);
+    ///
+    /// assert_eq!(perm.get_actions(), manager.get_universe().get_actions());
+    /// ```
+    ///
+    /// # Panics:
+    ///
+    /// Panics in the same cases that [`perm_from_actions`](Self::perm_from_actions()) does.
+    #[cfg_attr(feature = "tracing", tracing::instrument(level = "debug", skip_all, fields(manager_id = %self.id)))]
+    pub fn perm_from<I, A>(&self, actions: I) -> Permission
+    where
+        I: IntoIterator<Item = A>,
+        A: AsRef<str>,
+    {
+        self.perm_from_actions(
+            actions
+                .into_iter()
+                .map(|action| action.as_ref().to_string())
+                .collect(),
+        )
+    }
+
+    /// Returns a managed typed [`Permission`](crate::Permission) with the actions provided.
+    ///
+    /// # Examples:
+    ///
+    /// ```
+    /// use simple_perm_manager::{define_permissions, Permission};
+    ///
+    /// define_permissions! {
+    ///     enum PostAction { "post": { "view": true, "publish": true } }
+    /// }
+    ///
+    /// let manager = PostAction::permission_manager();
+    /// let perm: Permission<PostAction> = manager.typed_perm_from([PostAction::PostPublish]);
+    ///
+    /// assert!(manager.validate_perm(&perm.into_untyped()));
+    /// ```
+    ///
+    /// # Panics:
+    ///
+    /// Panics in the same cases that [`perm_from_actions`](Self::perm_from_actions()) does.
+    pub fn typed_perm_from<A, I>(&self, actions: I) -> Permission<A>
+    where
+        A: Action,
+        I: IntoIterator<Item = A>,
+    {
+        self.perm_from(
+            actions
+                .into_iter()
+                .map(|action| action.as_str().to_string()),
+        )
+        .retyped()
+    }
+
+    /// Returns a managed [`Permission`](crate::Permission) with the actions specified in the actions JSON string provided.
+    ///
+    /// # Examples:
+    ///
+    /// ```
+    /// use simple_perm_manager::PermissionManager;
+    ///
+    /// let manager = PermissionManager::from_json(&String::from(r#"{
+    ///     "create": true,
+    ///     "view": true
+    /// }"#));
+    ///
+    /// // Create managed Permission from the previous PermissionManager
+    /// let managed_perm = manager.perm_from_json(&String::from(r#"{"create": true}"#));
+    /// ```
+    ///
+    /// # Panics:
+    ///
+    /// Function panics if actions provided for the Permission are not included in the manager universe
+    /// ```rust,should_panic
+    /// use simple_perm_manager::PermissionManager;
+    ///
+    /// let manager = PermissionManager::from_json(&String::from(r#"{
+    ///     "create": true,
+    ///     "view": true
+    /// }"#));
+    ///
+    /// // This line of code panics
+    /// let panics = manager.perm_from_json(&String::from(r#"{"other_action": true}"#));
+    /// ```
+    #[cfg_attr(feature = "tracing", tracing::instrument(level = "debug", skip_all, fields(manager_id = %self.id)))]
+    pub fn perm_from_json(&self, actions_json: &str) -> Permission {
+        self.perm_from_actions(self.parse_actions(actions_json))
+    }
+
+    /// Adds a resource type to the [`PermissionManager`](crate::PermissionManager) universe, so one manager can host the actions of
+    /// several unrelated resource types ("building", "user", "invoice"...).  
+    /// The actions of the type are the ones of the universe JSON string provided, grouped under the type name.
+    ///
+    /// # Examples:
+    ///
+    /// ```
+    /// use simple_perm_manager::PermissionManager;
+    ///
+    /// let mut manager = PermissionManager::from_json("{}");
+    /// manager.add_resource_type("building", r#"{"view": true, "edit": true}"#);
+    /// manager.add_resource_type("invoice", r#"{"view": true, "pay": true}"#);
+    ///
+    /// assert!(manager.get_universe().contains_action("building:edit"));
+    /// assert!(manager.get_universe().contains_action("invoice:pay"));
+    /// ```
+    ///
+    /// # Panics:
+    ///
+    /// - Panics if the type name is empty or contains the action divider (`:`).
+    /// - Panics if the type name is already an action or a group of the universe.
+    /// - Panics if the universe JSON string is not valid or has no actions.
+    pub fn add_resource_type(&mut self, resource_type: &str, universe_json: &str) {
+        if resource_type.is_empty() || resource_type.contains(self.separator) {
+            panic!("resource type name must be a single non empty group");
+        }
+        if self.resource_types().contains(resource_type) || self.universe.has_action(resource_type)
+        {
+            panic!("resource type is already in the PermissionManager universe");
+        }
+
+        let actions: HashSet<String> = self
+            .parse_actions(universe_json)
+            .iter()
+            .map(|action| format!("{}{}{}", resource_type, self.separator, action))
+            .collect();
+        if actions.is_empty() {
+            panic!("resource type universe must have actions");
+        }
+
+        self.add_actions(actions);
+    }
+
+    /// Returns the resource types of the [`PermissionManager`](crate::PermissionManager): the top level groups of its universe.
+    pub fn resource_types(&self) -> HashSet<String> {
+        self.universe
+            .actions()
+            .filter_map(|action| action.split_once(self.separator))
+            .map(|(resource_type, _)| String::from(resource_type))
+            .collect()
+    }
+
+    /// Returns a managed [`Permission`](crate::Permission) with the actions of a resource type specified in the actions JSON string provided.  
+    /// The actions JSON string is relative to the resource type and is only validated against the actions of that type.
+    ///
+    /// # Examples:
+    ///
+    /// ```
+    /// use simple_perm_manager::PermissionManager;
+    ///
+    /// let manager = PermissionManager::from_json(r#"{
+    ///     "building": {"view": true, "edit": true},
+    ///     "invoice": {"view": true, "pay": true}
+    /// }"#);
+    ///
+    /// let perm = manager.perm_for_type("building", r#"{"edit": true}"#);
+    /// assert!(perm.contains_action("building:edit"));
+    /// assert!(!perm.contains_action("invoice:pay"));
+    /// ```
+    ///
+    /// # Panics:
+    ///
+    /// - Panics if the resource type is not in the [`PermissionManager`](crate::PermissionManager) universe.
+    /// - Panics if the actions JSON string is not valid or any action is not an action of the resource type.
+    pub fn perm_for_type(&self, resource_type: &str, actions_json: &str) -> Permission {
+        if !self.resource_types().contains(resource_type) {
+            panic!("resource type is not in the PermissionManager universe");
+        }
+
+        self.perm_from_actions(
+            self.parse_actions(actions_json)
+                .iter()
+                .map(|action| format!("{}{}{}", resource_type, self.separator, action))
+                .collect(),
+        )
+    }
+
+    /// Decides whether a [`Permission`](crate::Permission) allows an action:
+    ///
+    /// - [`Decision::Allow`](crate::Decision::Allow) if the [`Permission`](crate::Permission) contains the action, resolving aliases
+    ///   and implications as in [`Permission::contains_action`](crate::Permission::contains_action()).
+    /// - [`Decision::Deny`](crate::Decision::Deny) if the action is in the universe but not in the [`Permission`](crate::Permission).
+    /// - [`Decision::NotApplicable`](crate::Decision::NotApplicable) if the action is not in the universe of the
+    ///   [`PermissionManager`](crate::PermissionManager).
+    ///
+    /// Conditional grants are checked with a [`Context`](crate::Context) by [`GrantStore::check_with`](crate::GrantStore::check_with()). Use
+    /// [`Permission::contains_action`](crate::Permission::contains_action()) for simple `bool` checks.
+    ///
+    /// # Examples:
+    ///
+    /// ```
+    /// use simple_perm_manager::{Decision, PermissionManager};
+    ///
+    /// let manager = PermissionManager::from_json(r#"{"view": true, "edit": true}"#);
+    /// let perm = manager.perm_from_json(r#"{"view": true}"#);
+    ///
+    /// assert_eq!(manager.decide(&perm, "view"), Decision::Allow);
+    /// assert_eq!(manager.decide(&perm, "edit"), Decision::Deny);
+    /// assert_eq!(manager.decide(&perm, "share"), Decision::NotApplicable);
+    /// ```
+    ///
+    /// # Panics:
+    ///
+    /// Panics if the [`Permission`](crate::Permission) is managed by a different [`PermissionManager`](crate::PermissionManager).
+    #[cfg_attr(feature = "tracing", tracing::instrument(skip_all, fields(manager_id = %self.id, action = action_str, actions = perm.len()), ret))]
+    pub fn decide(&self, perm: &Permission, action_str: &str) -> Decision {
+        if perm.is_managed() && !self.universe.has_same_manager(perm) {
+            panic!("Permission to decide on is managed by a different manager");
+        }
+
+        let started = Instant::now();
+        let decision = if !self.universe.contains_action(action_str) {
+            Decision::NotApplicable
+        } else if perm.contains_action(action_str) {
+            Decision::Allow
+        } else {
+            Decision::Deny
+        };
+        telemetry::record_decision(decision, started);
+
+        decision
+    }
+
+    /// Attaches an obligation (e.g. `"log_access"` or `"require_mfa"`) to an action or a group of actions of the universe.  
+    /// Obligations are returned with the decisions allowing the action, see
+    /// [`decide_with_obligations`](Self::decide_with_obligations()), so the caller can enforce them.
+    ///
+    /// # Panics:
+    ///
+    /// Panics if the action or group is not in the [`PermissionManager`](crate::PermissionManager) universe.
+    pub fn add_obligation(&mut self, action: &str, obligation: &str) {
+        let action = &self.fold(action);
+        if !self.universe.has_action(action) && self.index.group_actions(action).next().is_none() {
+            panic!("Action or group for obligation is not in PermissionManager universe");
+        }
+
+        self.obligations
+            .entry(action.to_string())
+            .or_default()
+            .insert(obligation.to_string());
+    }
+
+    /// Returns the obligations of an action, sorted: the ones attached to the action and to all the groups containing it.  
+    /// Aliases are resolved to their action.
+    ///
+    /// # Examples:
+    ///
+    /// ```
+    /// use simple_perm_manager::PermissionManager;
+    ///
+    /// let mut manager = PermissionManager::from_json(r#"{"billing": {"view": true, "refund": true}}"#);
+    /// manager.add_obligation("billing", "log_access");
+    /// manager.add_obligation("billing:refund", "require_mfa");
+    ///
+    /// assert_eq!(manager.obligations_for("billing:view"), vec![String::from("log_access")]);
+    /// assert_eq!(
+    ///     manager.obligations_for("billing:refund"),
+    ///     vec![String::from("log_access"), String::from("require_mfa")]
+    /// );
+    /// ```
+    pub fn obligations_for(&self, action_str: &str) -> Vec<String> {
+        let folded = self.index.fold(action_str);
+        let action = self.index.alias(&folded).unwrap_or(&folded);
+        let mut obligations: Vec<String> = action
+            .match_indices(self.separator)
+            .map(|(position, _)| &action[..position])
+            .chain([action])
+            .filter_map(|key| self.obligations.get(key))
+            .flatten()
+            .cloned()
+            .collect::<HashSet<String>>()
+            .into_iter()
+            .collect();
+        obligations.sort();
+
+        obligations
+    }
+
+    /// Same as [`decide`](Self::decide()) but also returns the obligations of the action when the [`Decision`](crate::Decision) is
+    /// [`Decision::Allow`](crate::Decision::Allow). Other decisions have no obligations.
+    ///
+    /// # Examples:
+    ///
+    /// ```
+    /// use simple_perm_manager::{Decision, PermissionManager};
+    ///
+    /// let mut manager = PermissionManager::from_json(r#"{"view": true, "export": true}"#);
+    /// manager.add_obligation("export", "log_access");
+    /// let perm = manager.perm_from_json(r#"{"export": true}"#);
+    ///
+    /// assert_eq!(
+    ///     manager.decide_with_obligations(&perm, "export"),
+    ///     (Decision::Allow, vec![String::from("log_access")])
+    /// );
+    /// assert_eq!(manager.decide_with_obligations(&perm, "view"), (Decision::Deny, vec![]));
+    /// ```
+    ///
+    /// # Panics:
+    ///
+    /// Panics in the same cases that [`decide`](Self::decide()) does.
+    pub fn decide_with_obligations(
+        &self,
+        perm: &Permission,
+        action_str: &str,
+    ) -> (Decision, Vec<String>) {
+        match self.decide(perm, action_str) {
+            Decision::Allow => (Decision::Allow, self.obligations_for(action_str)),
+            decision => (decision, Vec::new()),
+        }
+    }
+
+    /// Returns a managed [`Policy`](crate::Policy) from a JSON string where `true` values are allowed actions
+    /// and `false` values are denied actions.
+    ///
+    /// Allowed actions are expanded as in [`perm_from_json`](Self::perm_from_json()). Denied actions resolve aliases and
+    /// group nodes, but do not deny the actions they imply.
+    ///
+    /// # Examples:
+    ///
+    /// ```
+    /// use simple_perm_manager::PermissionManager;
+    ///
+    /// let manager = PermissionManager::from_json(r#"{"building": {"view": true, "edit": true, "delete": true}}"#);
+    /// let policy = manager.policy_from_json(r#"{"building": true}"#).union(
+    ///     &manager.policy_from_json(r#"{"building": {"delete": false}}"#),
+    /// );
+    ///
+    /// assert!(policy.is_allowed("building:edit"));
+    /// assert!(!policy.is_allowed("building:delete"));
+    /// ```
+    ///
+    /// # Panics:
+    ///
+    /// - Panics if `actions_json` is not a valid actions JSON string.
+    /// - Panics if any allowed or denied action is not in the [`PermissionManager`](crate::PermissionManager) universe.
+    pub fn policy_from_json(&self, actions_json: &str) -> Policy {
+        let (allowed, denied) = parse_policy_actions(actions_json, self.separator);
+        let denied = self.managed_perm(self.normalize_actions(denied, false));
+
+        if !self.validate_perm(&denied) {
+            panic!("Actions for Permission creation not allowed in PermissionManager or Permission id does not correspond to Manager id")
+        }
+
+        Policy::new(self.perm_from_actions(allowed), denied)
+    }
+
+    /// Sets the [`CombiningAlgorithm`](crate::CombiningAlgorithm) used by [`combine_policies`](Self::combine_policies()).  
+    /// The default algorithm is [`CombiningAlgorithm::DenyOverrides`](crate::CombiningAlgorithm::DenyOverrides).
+    pub fn set_combining_algorithm(&mut self, algorithm: CombiningAlgorithm) {
+        self.combining_algorithm = algorithm;
+    }
+
+    /// Returns the [`CombiningAlgorithm`](crate::CombiningAlgorithm) used by [`combine_policies`](Self::combine_policies()).
+    pub fn combining_algorithm(&self) -> CombiningAlgorithm {
+        self.combining_algorithm
+    }
+
+    /// Sets the character dividing the groups and name of the actions of the [`PermissionManager`](crate::PermissionManager), used
+    /// both when parsing actions JSON strings and when serializing [`Permission`](crate::Permission)s to JSON.
+    /// The default separator is [`ACTION_DIVIDER`](crate::ACTION_DIVIDER) (`:`). Universe actions, aliases, implications and
+    /// obligations are rewritten with the new separator, while previously issued [`Permission`](crate::Permission)s keep the old one.
+    ///
+    /// # Examples:
+    ///
+    /// ```
+    /// use simple_perm_manager::PermissionManager;
+    ///
+    /// let mut manager = PermissionManager::from_json(r#"{"building": {"view": true, "edit": true}}"#);
+    /// manager.set_separator('.');
+    ///
+    /// let perm = manager.perm_from_json(r#"{"building": {"view": true}}"#);
+    /// assert!(perm.contains_action("building.view"));
+    /// assert_eq!(perm.to_json(), r#"{"building":{"view":true}}"#);
+    /// ```
+    ///
+    /// # Panics:
+    ///
+    /// Panics if any universe action already contains the new separator, as its groups could not be told apart.
+    pub fn set_separator(&mut self, separator: char) {
+        let previous = self.separator;
+        if previous == separator {
+            return;
+        }
+        if self
+            .universe
+            .actions()
+            .any(|action| action.contains(separator))
+        {
+            panic!("Universe action already contains the separator");
+        }
+
+        self.separator = separator;
+        self.accepted_separators
+            .retain(|accepted| *accepted != separator);
+        self.rewrite_names(|action| action.replace(previous, &separator.to_string()));
+    }
+
+    // Rewrites the universe actions, aliases, implications and obligations with the conversion provided.
+    fn rewrite_names<F: Fn(&str) -> String>(&mut self, convert: F) {
+        let universe_actions = self.universe.actions().map(&convert).collect();
+        self.aliases = self
+            .aliases
+            .iter()
+            .map(|(alias, action)| (convert(alias), convert(action)))
+            .collect();
+        self.implications = self
+            .implications
+            .iter()
+            .map(|(action, implied)| {
+                (
+                    convert(action),
+                    implied.iter().map(|implied| convert(implied)).collect(),
+                )
+            })
+            .collect();
+        self.obligations = self
+            .obligations
+            .iter()
+            .map(|(action, obligations)| (convert(action), obligations.clone()))
+            .collect();
+        self.set_universe(universe_actions);
+    }
+
+    /// Enables or disables the case-insensitive mode of the [`PermissionManager`](crate::PermissionManager), for actions coming from
+    /// systems with inconsistent casing.  
+    /// In case-insensitive mode, actions are case-folded (lowercased) when they are provided to the [`PermissionManager`](crate::PermissionManager),
+    /// including when the universe is changed with [`add_actions`](Self::add_actions()) or [`rename_action`](Self::rename_action()),
+    /// and [`Permission::contains_action`](crate::Permission::contains_action()) matches case-insensitively on the [`Permission`](crate::Permission)s
+    /// it issues. Enabling it case-folds the universe actions, aliases, implications and obligations, which stay folded if it is disabled again.
+    ///
+    /// # Examples:
+    ///
+    /// ```
+    /// use std::collections::HashSet;
+    /// use simple_perm_manager::PermissionManager;
+    ///
+    /// let mut manager = PermissionManager::from_json(r#"{"Building": {"View": true, "edit": true}}"#);
+    /// manager.set_case_insensitive(true);
+    ///
+    /// let perm = manager.perm_from_actions(HashSet::from([String::from("BUILDING:view")]));
+    /// assert_eq!(*perm.get_actions(), HashSet::from([String::from("building:view")]));
+    /// assert!(perm.contains_action("Building:View"));
+    /// ```
+    ///
+    /// # Panics:
+    ///
+    /// Panics if two universe actions only differ in case, leaving the [`PermissionManager`](crate::PermissionManager) unchanged.
+    pub fn set_case_insensitive(&mut self, case_insensitive: bool) {
+        if !case_insensitive {
+            self.case_insensitive = false;
+            self.set_universe(self.universe.get_actions().into_owned());
+            return;
+        }
+
+        let folded: HashSet<String> = self
+            .universe
+            .actions()
+            .map(|action| action.to_lowercase())
+            .collect();
+        if folded.len() != self.universe.len() {
+            panic!("Universe actions only differ in case");
+        }
+        self.case_insensitive = true;
+        self.rewrite_names(|action| action.to_lowercase());
+    }
+
+    /// Returns `true` if the [`PermissionManager`](crate::PermissionManager) is in case-insensitive mode, see
+    /// [`set_case_insensitive`](Self::set_case_insensitive()).
+    pub fn is_case_insensitive(&self) -> bool {
+        self.case_insensitive
+    }
+
+    /// Returns the character dividing the groups and name of the actions of the [`PermissionManager`](crate::PermissionManager).
+    pub fn separator(&self) -> char {
+        self.separator
+    }
+
+    /// Makes the [`PermissionManager`](crate::PermissionManager) accept actions divided by a legacy separator, so mixed legacy data can be
+    /// loaded without a manual rewrite.  
+    /// Actions provided to the [`PermissionManager`](crate::PermissionManager) (e.g. to [`perm_from_actions`](Self::perm_from_actions()) or
+    /// [`perm_from_json`](Self::perm_from_json())) have the accepted separators replaced by the manager [`separator`](Self::separator())
+    /// before being validated. Use [`Permission::normalize_separator`](crate::Permission::normalize_separator()) to migrate
+    /// [`Permission`](crate::Permission)s explicitly instead.
+    ///
+    /// # Examples:
+    ///
+    /// ```
+    /// use std::collections::HashSet;
+    /// use simple_perm_manager::PermissionManager;
+    ///
+    /// let mut manager = PermissionManager::from_json(r#"{"building": {"view": true, "edit": true}}"#);
+    /// manager.accept_separator('.');
+    ///
+    /// let perm = manager.perm_from_actions(HashSet::from([String::from("building.view"), String::from("building:edit")]));
+    /// assert_eq!(perm.get_actions(), manager.get_universe().get_actions());
+    ///
+    /// // Flat legacy keys in JSON are accepted too
+    /// assert!(manager.perm_from_json(r#"{"building.view": true}"#).contains_action("building:view"));
+    /// ```
+    pub fn accept_separator(&mut self, separator: char) {
+        if separator != self.separator && !self.accepted_separators.contains(&separator) {
+            self.accepted_separators.push(separator);
+        }
+    }
+
+    /// Returns the legacy separators accepted with [`accept_separator`](Self::accept_separator()), in the order they were added.
+    pub fn accepted_separators(&self) -> &[char] {
+        &self.accepted_separators
+    }
+
+    /// Returns a managed [`Permission`](crate::Permission) with the actions allowed by combining the [`Policy`](crate::Policy)s
+    /// provided, such as all the [`Policy`](crate::Policy)s of a subject, with the [`CombiningAlgorithm`](crate::CombiningAlgorithm)
+    /// of the [`PermissionManager`](crate::PermissionManager).
+    ///
+    /// # Examples:
+    ///
+    /// ```
+    /// use simple_perm_manager::{CombiningAlgorithm, PermissionManager};
+    ///
+    /// let mut manager = PermissionManager::from_json(r#"{"view": true, "edit": true}"#);
+    /// let policies = [
+    ///     manager.policy_from_json(r#"{"edit": false}"#),
+    ///     manager.policy_from_json(r#"{"view": true, "edit": true}"#),
+    /// ];
+    ///
+    /// assert!(!manager.combine_policies(&policies).contains_action("edit"));
+    ///
+    /// manager.set_combining_algorithm(CombiningAlgorithm::AllowOverrides);
+    /// assert!(manager.combine_policies(&policies).contains_action("edit"));
+    /// ```
+    ///
+    /// # Panics:
+    ///
+    /// Panics if any [`Policy`](crate::Policy) is not managed by this [`PermissionManager`](crate::PermissionManager).
+    pub fn combine_policies<'a, I>(&self, policies: I) -> Permission
+    where
+        I: IntoIterator<Item = &'a Policy>,
+    {
+        let mut allowed: HashSet<String> = HashSet::new();
+        let mut denied: HashSet<String> = HashSet::new();
+
+        for policy in policies {
+            if !self.universe.has_same_manager(policy.allowed()) {
+                panic!("Policy to combine is not managed by this manager");
+            }
+
+            match self.combining_algorithm {
+                CombiningAlgorithm::DenyOverrides | CombiningAlgorithm::AllowOverrides => {
+                    allowed.extend(policy.allowed().actions().map(String::from));
+                    denied.extend(policy.denied().actions().map(String::from));
+                }
+                // Actions already decided by a previous policy are kept in `allowed` or `denied`. Denials are applied
+                // first, as a policy does not allow the actions it denies
+                CombiningAlgorithm::FirstApplicable => {
+                    for action in policy.denied().actions() {
+                        if !allowed.contains(action) {
+                            denied.insert(action.to_string());
+                        }
+                    }
+                    for action in policy.allowed().actions() {
+                        if !denied.contains(action) {
+                            allowed.insert(action.to_string());
+                        }
+                    }
+                }
+            }
+        }
+
+        match self.combining_algorithm {
+            CombiningAlgorithm::DenyOverrides => {
+                self.managed_perm(allowed.difference(&denied).cloned().collect())
+            }
+            CombiningAlgorithm::AllowOverrides | CombiningAlgorithm::FirstApplicable => {
+                self.managed_perm(allowed)
+            }
+        }
+    }
+
+    /// Same as [`perm_from_actions`](Self::perm_from_actions()) but returns a [`ValidationError`](crate::ValidationError)
+    /// listing the actions not in the [`PermissionManager`](crate::PermissionManager) universe instead of panicking, each one
+    /// with did-you-mean suggestions of the closest universe actions.
+    ///
+    /// # Examples:
+    ///
+    /// ```
+    /// use std::collections::HashSet;
+    /// use simple_perm_manager::{PermissionManager, ValidationError};
+    ///
+    /// let manager = PermissionManager::from_json(r#"{"create": true, "view": true}"#);
+    ///
+    /// assert!(manager.try_perm_from_actions(HashSet::from([String::from("view")])).is_ok());
+    /// assert_eq!(
+    ///     manager.try_perm_from_actions(HashSet::from([String::from("view"), String::from("edit")])).unwrap_err(),
+    ///     ValidationError::UnknownActions(vec![(String::from("edit"), vec![])])
+    /// );
+    /// assert_eq!(
+    ///     manager.try_perm_from_actions(HashSet::from([String::from("craete")])).unwrap_err().to_string(),
+    ///     "actions not allowed in permission manager: craete (did you mean 'create'?)"
+    /// );
+    /// ```
+    #[cfg_attr(feature = "tracing", tracing::instrument(level = "debug", skip_all, fields(manager_id = %self.id, actions = actions.len()), err))]
+    pub fn try_perm_from_actions(
+        &self,
+        actions: HashSet<String>,
+    ) -> Result<Permission, ValidationError> {
+        let groups = self.group_nodes(&actions);
+        let actions = self.normalize_actions(actions, true);
+        let mut unknown: Vec<(String, Vec<String>)> = actions
+            .iter()
+            .filter(|action| !self.universe.has_action(action))
+            .map(|action| {
+                (
+                    action.clone(),
+                    close_matches(action, self.universe.actions()),
+                )
+            })
+            .collect();
+
+        if !unknown.is_empty() {
+            unknown.sort();
+            telemetry::record_validation_failure();
+            return Err(ValidationError::UnknownActions(unknown));
+        }
+
+        Ok(self.managed_perm(actions).with_groups(groups))
+    }
+
+    /// Same as [`perm_from_json`](Self::perm_from_json()) but returns a [`ValidationError`](crate::ValidationError) instead of panicking.
+    ///
+    /// # Examples:
+    ///
+    /// ```
+    /// use simple_perm_manager::{PermissionManager, ValidationError};
+    ///
+    /// let manager = PermissionManager::from_json(r#"{"create": true, "view": true}"#);
+    ///
+    /// assert!(manager.try_perm_from_json(r#"{"view": true}"#).is_ok());
+    /// assert!(matches!(manager.try_perm_from_json("not json"), Err(ValidationError::InvalidJson(_))));
+    /// assert_eq!(
+    ///     manager.try_perm_from_json(r#"{"edit": true}"#).unwrap_err(),
+    ///     ValidationError::UnknownActions(vec![(String::from("edit"), vec![])])
+    /// );
+    /// ```
+    #[cfg_attr(feature = "tracing", tracing::instrument(level = "debug", skip_all, fields(manager_id = %self.id), err))]
+    pub fn try_perm_from_json(&self, actions_json: &str) -> Result<Permission, ValidationError> {
+        let actions = try_parse_actions(actions_json, self.separator).map_err(|error| {
+            telemetry::record_parse_error();
+            ValidationError::InvalidJson(error)
+        })?;
+
+        self.try_perm_from_actions(actions)
+    }
+
+    /// Same as [`try_perm_from_json`](Self::try_perm_from_json()) but validating each action against the universe while the JSON is
+    /// parsed, failing fast with the [JSON pointer](https://www.rfc-editor.org/rfc/rfc6901) of the first key that is neither an
+    /// action, an alias nor a group of the universe.
+    ///
+    /// # Examples:
+    ///
+    /// ```
+    /// use simple_perm_manager::{PermissionManager, ValidationError};
+    ///
+    /// let manager = PermissionManager::from_json(r#"{"building": {"view": true, "edit": true}}"#);
+    ///
+    /// assert!(manager.perm_from_json_strict(r#"{"building": {"view": true}}"#).is_ok());
+    /// assert_eq!(
+    ///     manager.perm_from_json_strict(r#"{"building": {"view": true, "edti": true}}"#).unwrap_err(),
+    ///     ValidationError::UnknownActionAt(String::from("/building/edti"))
+    /// );
+    /// ```
+    ///
+    /// # Errors:
+    ///
+    /// - Returns [`ValidationError::UnknownActionAt`](crate::ValidationError::UnknownActionAt) for the first unknown key.
+    /// - Returns [`ValidationError::InvalidJson`](crate::ValidationError::InvalidJson) if `actions_json` is not a valid actions JSON string.
+    #[cfg_attr(feature = "tracing", tracing::instrument(level = "debug", skip_all, fields(manager_id = %self.id), err))]
+    pub fn perm_from_json_strict(&self, actions_json: &str) -> Result<Permission, ValidationError> {
+        let actions = parse_actions_strict(actions_json, self.separator, |action| {
+            let action = &self.index.fold(action);
+            self.index.position(action).is_some()
+                || self.index.alias(action).is_some()
+                || self.index.group_actions(action).next().is_some()
+        })
+        .map_err(|error| match error {
+            StrictParseError::InvalidJson(message) => {
+                telemetry::record_parse_error();
+                ValidationError::InvalidJson(message)
+            }
+            StrictParseError::UnknownAction(pointer) => {
+                telemetry::record_validation_failure();
+                ValidationError::UnknownActionAt(pointer)
+            }
+        })?;
+
+        Ok(self.granted_perm(actions))
+    }
+
+    /// Returns a managed [`Permission`](crate::Permission) with the actions provided that are in the [`PermissionManager`](crate::PermissionManager)
+    /// universe, along with the set of actions discarded because they are not.  
+    /// Unlike [`perm_from_actions`](Self::perm_from_actions()) it never panics, which is useful for loading stale grants after
+    /// the universe has shrunk.
+    ///
+    /// # Examples:
+    ///
+    /// ```
+    /// use std::collections::HashSet;
+    /// use simple_perm_manager::PermissionManager;
+    ///
+    /// let manager = PermissionManager::from_json(r#"{"create": true, "view": true}"#);
+    /// let (perm, discarded) = manager.perm_from_actions_lenient(HashSet::from([
+    ///     String::from("view"),
+    ///     String::from("delete"),
+    /// ]));
+    ///
+    /// assert_eq!(*perm.get_actions(), HashSet::from([String::from("view")]));
+    /// assert_eq!(discarded, HashSet::from([String::from("delete")]));
+    /// ```
+    #[cfg_attr(feature = "tracing", tracing::instrument(level = "debug", skip_all, fields(manager_id = %self.id, actions = actions.len())))]
+    pub fn perm_from_actions_lenient(
+        &self,
+        actions: HashSet<String>,
+    ) -> (Permission, HashSet<String>) {
+        let (allowed, discarded): (HashSet<String>, HashSet<String>) = self
+            .normalize_actions(actions, true)
+            .into_iter()
+            .partition(|action| self.universe.has_action(action));
+
+        (self.managed_perm(allowed), discarded)
+    }
+
+    /// Returns a managed [`Permission`](crate::Permission) with the actions in the JSON string provided that are in the
+    /// [`PermissionManager`](crate::PermissionManager) universe, along with the set of actions discarded because they are not.  
+    /// Same as [`perm_from_actions_lenient`](Self::perm_from_actions_lenient()) for actions JSON strings.
+    ///
+    /// # Examples:
+    ///
+    /// ```
+    /// use std::collections::HashSet;
+    /// use simple_perm_manager::PermissionManager;
+    ///
+    /// let manager = PermissionManager::from_json(r#"{"create": true, "view": true}"#);
+    /// let (perm, discarded) = manager.perm_from_json_lenient(r#"{"view": true, "room": {"edit": true}}"#);
+    ///
+    /// assert!(manager.validate_perm(&perm));
+    /// assert_eq!(discarded, HashSet::from([String::from("room:edit")]));
+    /// ```
+    ///
+    /// # Panics:
+    ///
+    /// Panics if `actions_json` is not a valid actions JSON string, in the same cases that
+    /// [Permission::from_json_and_uuid()](crate::Permission::from_json_and_uuid()) does.
+    #[cfg_attr(feature = "tracing", tracing::instrument(level = "debug", skip_all, fields(manager_id = %self.id)))]
+    pub fn perm_from_json_lenient(&self, actions_json: &str) -> (Permission, HashSet<String>) {
+        self.perm_from_actions_lenient(
+            Permission::from_json(actions_json)
+                .get_actions()
+                .into_owned(),
+        )
+    }
+
+    /// Returns a [`Permission`](crate::Permission) managed by this [`PermissionManager`](crate::PermissionManager) with the full set
+    /// of actions granted by the [`Permission`](crate::Permission) provided:
+    /// - Group nodes (e.g. `"building"`) are replaced by every universe action beneath them, modelling "full access to the building module"
+    ///   without storing every action.
+    /// - Aliases are replaced by the action they refer to.
+    /// - Actions implied by the granted ones are added.
+    ///
+    /// [`Permission`](crate::Permission)s created by the [`PermissionManager`](crate::PermissionManager) are expanded on creation,
+    /// so this is mostly useful for stored [`Permission`](crate::Permission)s and [`Permission`](crate::Permission)s issued before
+    /// declaring new aliases or implications. Group nodes granted to a [`Permission`](crate::Permission) issued by the
+    /// [`PermissionManager`](crate::PermissionManager) are expanded again, covering the actions added beneath them since, which
+    /// [`Permission::contains_action`](crate::Permission::contains_action()) already resolves at check time.
+    ///
+    /// # Examples:
+    ///
+    /// ```
+    /// use std::collections::HashSet;
+    /// use simple_perm_manager::{Permission, PermissionManager};
+    ///
+    /// let manager = PermissionManager::from_json(r#"{"building": {"view": true, "room": {"edit": true}}, "user": {"view": true}}"#);
+    /// let stored = Permission::from_json(r#"{"building": true}"#);
+    ///
+    /// assert_eq!(
+    ///     *manager.expand(&stored).get_actions(),
+    ///     HashSet::from([String::from("building:view"), String::from("building:room:edit")])
+    /// );
+    /// ```
+    ///
+    /// # Panics:
+    ///
+    /// - Panics if the [`Permission`](crate::Permission) is managed by a different [`PermissionManager`](crate::PermissionManager).
+    /// - Panics if the [`Permission`](crate::Permission) contains actions that are neither in the [`PermissionManager`](crate::PermissionManager)
+    ///   universe nor group nodes or aliases of universe actions.
+    pub fn expand(&self, perm: &Permission) -> Permission {
+        if perm.is_managed() && !self.universe.has_same_manager(perm) {
+            panic!("Permission to expand is managed by a different manager");
+        }
+
+        self.perm_from_actions(
+            perm.actions()
+                .chain(perm.granted_groups().iter().map(String::as_str))
+                .map(String::from)
+                .collect(),
+        )
+    }
+
+    /// Returns the least privileged [`Permission`](crate::Permission) covering the actions actually used, e.g. collected from audit
+    /// logs, out of the granted [`Permission`](crate::Permission) provided, along with the set of granted actions that were never used.  
+    /// Used actions are kept along with the actions they imply. Aliases are resolved, and used actions that are not granted are ignored.
+    ///
+    /// # Examples:
+    ///
+    /// ```
+    /// use std::collections::HashSet;
+    /// use simple_perm_manager::PermissionManager;
+    ///
+    /// let mut manager = PermissionManager::from_json(r#"{"post": {"view": true, "edit": true, "delete": true}}"#);
+    /// manager.add_implication("post:edit", "post:view");
+    /// let granted = manager.perm_from_json(r#"{"post": true}"#);
+    ///
+    /// let (minimized, unused) = manager.minimize(&HashSet::from([String::from("post:edit")]), &granted);
+    ///
+    /// assert_eq!(minimized.to_json(), r#"{"post":{"edit":true,"view":true}}"#);
+    /// assert_eq!(unused, HashSet::from([String::from("post:delete")]));
+    /// ```
+    ///
+    /// # Panics:
+    ///
+    /// Panics if the granted [`Permission`](crate::Permission) is not managed by this [`PermissionManager`](crate::PermissionManager).
+    pub fn minimize(
+        &self,
+        used: &HashSet<String>,
+        granted: &Permission,
+    ) -> (Permission, HashSet<String>) {
+        if !self.universe.has_same_manager(granted) {
+            panic!("Permission to minimize is not managed by this manager");
+        }
+
+        let used = self.normalize_actions(used.clone(), true);
+        let (minimized, unused) = granted
+            .actions()
+            .map(String::from)
+            .partition(|action| used.contains(action));
+
+        (self.managed_perm(minimized), unused)
+    }
+
+    /// Returns a [`Permission`](crate::Permission) managed by this [`PermissionManager`](crate::PermissionManager) with the actions
+    /// of the [`Permission`](crate::Permission) provided.  
+    /// Intended for re-linking deserialized [`Permission`](crate::Permission)s to their manager.
+    ///
+    /// # Examples:
+    ///
+    /// ```
+    /// use simple_perm_manager::{Permission, PermissionManager};
+    ///
+    /// let manager = PermissionManager::from_json(r#"{"create": true, "view": true}"#);
+    /// let perm = manager.adopt(&Permission::from_json(r#"{"view": true}"#));
+    ///
+    /// assert!(manager.validate_perm(&perm));
+    /// ```
+    ///
+    /// # Panics:
+    ///
+    /// - Panics if the [`Permission`](crate::Permission) is managed by a different [`PermissionManager`](crate::PermissionManager).
+    /// - Panics if the [`Permission`](crate::Permission) contains actions that are not in the [`PermissionManager`](crate::PermissionManager) universe.
+    pub fn adopt(&self, perm: &Permission) -> Permission {
+        if perm.is_managed() && !self.universe.has_same_manager(perm) {
+            panic!("Permission to adopt is managed by a different manager");
+        }
+
+        self.perm_from_actions(perm.get_actions().into_owned())
+    }
+
+    /// Returns a [`Permission`](crate::Permission) managed by this [`PermissionManager`](crate::PermissionManager) from a JSON string
+    /// created with [`Permission::to_managed_json`](crate::Permission::to_managed_json()).
+    ///
+    /// # Examples:
+    ///
+    /// ```
+    /// use simple_perm_manager::{ManagerId, PermissionManager};
+    ///
+    /// let id = ManagerId::random();
+    /// let manager = PermissionManager::from_json_with_id(id, r#"{"create": true, "view": true}"#);
+    /// let json = manager.perm_from_json(r#"{"view": true}"#).to_managed_json();
+    ///
+    /// // The same manager identity in another process adopts the permission
+    /// let restarted_manager = PermissionManager::from_json_with_id(id, r#"{"create": true, "view": true}"#);
+    /// let perm = restarted_manager.adopt_json(&json);
+    ///
+    /// assert!(restarted_manager.validate_perm(&perm));
+    /// ```
+    ///
+    /// # Panics:
+    ///
+    /// - Panics if `json` is not a valid managed JSON string.
+    /// - Panics if `json` contains a manager id different from the [`PermissionManager`](crate::PermissionManager) one.
+    /// - Panics if `json` contains actions that are not in the [`PermissionManager`](crate::PermissionManager) universe.
+    pub fn adopt_json(&self, json: &str) -> Permission {
+        let (manager_id, actions) = deserialize_managed_actions(json, self.separator);
+
+        if manager_id.is_some_and(|id| id != self.id) {
+            panic!("Permission to adopt is managed by a different manager");
+        }
+
+        self.perm_from_actions(actions)
+    }
+
+    /// Returns the actions of a managed [`Permission`](crate::Permission) encoded as a bitset over the
+    /// [`PermissionManager`](crate::PermissionManager) universe.
+    ///
+    /// Universe actions are sorted lexicographically and each one takes a bit in that order
+    /// (least significant bit first inside each byte), so the encoding always has the same size
+    /// for every [`Permission`](crate::Permission) of the manager: one bit per universe action.
+    /// Managers created with the same universe produce the same encoding.
+    ///
+    /// The bitset is preceded by the 8 bytes of the [`universe_hash`](Self::universe_hash()), so encodings of a manager with a
+    /// different universe, such as a [`submanager`](Self::submanager()) sharing the same id, are rejected when decoding.
+    ///
+    /// # Examples:
+    ///
+    /// ```
+    /// use simple_perm_manager::PermissionManager;
+    ///
+    /// let manager = PermissionManager::from_json(r#"{
+    ///     "create": true,
+    ///     "delete": true,
+    ///     "edit": true,
+    ///     "view": true
+    /// }"#);
+    ///
+    /// let perm = manager.perm_from_json(r#"{"create": true, "view": true}"#);
+    ///
+    /// // 'create' is the first action in canonical order and 'view' the fourth.
+    /// assert_eq!(manager.encode_perm(&perm)[8..], [0b0000_1001]);
+    /// ```
+    ///
+    /// # Panics:
+    ///
+    /// Panics if the [`Permission`](crate::Permission) provided is not valid for the [`PermissionManager`](crate::PermissionManager).
+    pub fn encode_perm(&self, perm: &Permission) -> Vec<u8> {
+        let mut bytes = self.index.fingerprint_bytes().to_vec();
+        bytes.extend(self.encode_bitset(perm));
+
+        bytes
+    }
+
+    // Returns the bitset of a permission valid for the manager, without the universe hash
+    fn encode_bitset(&self, perm: &Permission) -> Vec<u8> {
+        if !self.validate_perm(perm) {
+            panic!("Permission to encode is not valid for the PermissionManager");
+        }
+
+        self.index.encode(perm.actions())
+    }
+
+    // Returns the bitset of an encoding created with encode_perm, checking it was encoded for this universe
+    fn encoded_bitset<'a>(&self, bytes: &'a [u8]) -> Result<&'a [u8], &'static str> {
+        match bytes.strip_prefix(self.index.fingerprint_bytes().as_slice()) {
+            Some(bitset) if bitset.len() == self.index.encoded_len() => Ok(bitset),
+            Some(_) => Err(
+                "wrong length in encoded actions - does not match the PermissionManager universe",
+            ),
+            None => Err("wrong universe in encoded actions - encoded for a different universe"),
+        }
+    }
+
+    /// Returns a managed [`Permission`](crate::Permission) from a bitset created with [`encode_perm`](Self::encode_perm()).
+    ///
+    /// # Examples:
+    ///
+    /// ```
+    /// use simple_perm_manager::PermissionManager;
+    ///
+    /// let manager = PermissionManager::from_json(r#"{
+    ///     "create": true,
+    ///     "view": true
+    /// }"#);
+    ///
+    /// let perm = manager.perm_from_json(r#"{"view": true}"#);
+    /// let decoded = manager.decode_perm(&manager.encode_perm(&perm));
+    ///
+    /// assert!(manager.validate_perm(&decoded));
+    /// assert_eq!(decoded.get_actions(), perm.get_actions());
+    /// ```
+    ///
+    /// # Panics:
+    ///
+    /// - Panics if `bytes` does not start with the [`universe_hash`](Self::universe_hash()) of the manager.
+    /// - Panics if the bitset length is not one bit per universe action rounded up to whole bytes.
+    /// - Panics if the bitset has bits set beyond the number of universe actions.
+    pub fn decode_perm(&self, bytes: &[u8]) -> Permission {
+        match self.encoded_bitset(bytes) {
+            Ok(bitset) => self.managed_perm(self.index.decode(bitset)),
+            Err(message) => panic!("{}", message),
+        }
+    }
+
+    /// Returns `true` if the bitset encoded [`Permission`](crate::Permission) `encoded` contains at least
+    /// all the actions in the bitset encoded [`Permission`](crate::Permission) `other`.
+    ///
+    /// This is the same check as [`Permission::contains`](crate::Permission::contains()) but performed directly
+    /// over the encodings returned by [`encode_perm`](Self::encode_perm()), without decoding them.
+    ///
+    /// # Examples:
+    ///
+    /// ```
+    /// use simple_perm_manager::PermissionManager;
+    ///
+    /// let manager = PermissionManager::from_json(r#"{
+    ///     "create": true,
+    ///     "view": true,
+    ///     "edit": true
+    /// }"#);
+    ///
+    /// let perm_foo_bar = manager.encode_perm(&manager.perm_from_json(r#"{"create": true, "view": true}"#));
+    /// let perm_bar = manager.encode_perm(&manager.perm_from_json(r#"{"create": true}"#));
+    ///
+    /// assert!(manager.encoded_contains(&perm_foo_bar, &perm_bar));
+    /// assert!(!manager.encoded_contains(&perm_bar, &perm_foo_bar));
+    /// ```
+    ///
+    /// # Panics:
+    ///
+    /// Panics if any of the encodings provided was not encoded for the manager universe or its length does not match the manager
+    /// encoding length.
+    pub fn encoded_contains(&self, encoded: &[u8], other: &[u8]) -> bool {
+        let (encoded, other) = match (self.encoded_bitset(encoded), self.encoded_bitset(other)) {
+            (Ok(encoded), Ok(other)) => (encoded, other),
+            (Err(message), _) | (_, Err(message)) => panic!("{}", message),
+        };
+
+        encoded
+            .iter()
+            .zip(other.iter())
+            .all(|(byte, other_byte)| byte & other_byte == *other_byte)
+    }
+
+    /// Returns a managed [`Permission`](crate::Permission) as a compact token: the bitset from
+    /// [`encode_perm`](Self::encode_perm()) encoded as unpadded URL-safe base64.
+    ///
+    /// Tokens only use the characters `A-Z`, `a-z`, `0-9`, `-` and `_`, so they can be embedded
+    /// in cookies, query params or JWT claims without further escaping.
+    ///
+    /// # Examples:
+    ///
+    /// ```
+    /// use simple_perm_manager::PermissionManager;
+    ///
+    /// let manager = PermissionManager::from_json(r#"{
+    ///     "create": true,
+    ///     "delete": true,
+    ///     "edit": true,
+    ///     "view": true
+    /// }"#);
+    ///
+    /// let perm = manager.perm_from_json(r#"{"create": true, "view": true}"#);
+    ///
+    /// // The universe hash and a single byte of bitset
+    /// assert_eq!(manager.perm_to_token(&perm).len(), 12);
+    /// ```
+    ///
+    /// # Panics:
+    ///
+    /// Panics if the [`Permission`](crate::Permission) provided is not valid for the [`PermissionManager`](crate::PermissionManager).
+    pub fn perm_to_token(&self, perm: &Permission) -> String {
+        URL_SAFE_NO_PAD.encode(self.encode_perm(perm))
+    }
+
+    /// Returns a managed [`Permission`](crate::Permission) from a token created with [`perm_to_token`](Self::perm_to_token()).
+    ///
+    /// # Examples:
+    ///
+    /// ```
+    /// use simple_perm_manager::PermissionManager;
+    ///
+    /// let manager = PermissionManager::from_json(r#"{
+    ///     "create": true,
+    ///     "view": true
+    /// }"#);
+    ///
+    /// let perm = manager.perm_from_json(r#"{"view": true}"#);
+    /// let from_token = manager.perm_from_token(&manager.perm_to_token(&perm));
+    ///
+    /// assert!(manager.validate_perm(&from_token));
+    /// assert_eq!(from_token.get_actions(), perm.get_actions());
+    /// ```
+    ///
+    /// # Panics:
+    ///
+    /// - Panics if `token` is not valid unpadded URL-safe base64.
+    /// - Panics in the same cases that [`decode_perm`](Self::decode_perm()) does for the decoded bytes.
+    #[cfg_attr(feature = "tracing", tracing::instrument(level = "debug", skip_all, fields(manager_id = %self.id)))]
+    pub fn perm_from_token(&self, token: &str) -> Permission {
+        match URL_SAFE_NO_PAD.decode(token) {
+            Ok(bytes) => self.decode_perm(&bytes),
+            Err(_) => panic!("wrong format in permission token - not valid url-safe base64"),
+        }
+    }
+
+    /// Returns a hash of the universe actions of the [`PermissionManager`](crate::PermissionManager) as an 11 character URL-safe
+    /// base64 string.  
+    /// Managers with the same universe actions have the same hash, so it tells whether a bitset from
+    /// [`encode_perm`](Self::encode_perm()) or a JWT claim from [`Permission::to_jwt_claim`](crate::Permission::to_jwt_claim())
+    /// can be decoded by this manager.
+    ///
+    /// # Examples:
+    ///
+    /// ```
+    /// use simple_perm_manager::PermissionManager;
+    ///
+    /// let manager = PermissionManager::from_json(r#"{"view": true, "edit": true}"#);
+    ///
+    /// assert_eq!(manager.universe_hash(), PermissionManager::from_json(r#"{"edit": true, "view": true}"#).universe_hash());
+    /// assert_ne!(manager.universe_hash(), PermissionManager::from_json(r#"{"view": true}"#).universe_hash());
+    /// ```
+    pub fn universe_hash(&self) -> String {
+        self.index.fingerprint()
+    }
+
+    /// Returns a managed [`Permission`](crate::Permission) from a JWT claim created with
+    /// [`Permission::to_jwt_claim`](crate::Permission::to_jwt_claim()), validating it against the universe: bitset claims must have
+    /// been encoded for a universe with the same [`universe_hash`](Self::universe_hash()) and scope claims may only contain
+    /// universe actions.
+    ///
+    /// # Examples:
+    ///
+    /// ```
+    /// use serde_json::json;
+    /// use simple_perm_manager::PermissionManager;
+    ///
+    /// let manager = PermissionManager::from_json(r#"{"post": {"view": true, "edit": true}}"#);
+    /// let perm = manager.perm_from_json(r#"{"post": {"edit": true}}"#);
+    ///
+    /// let decoded = manager.perm_from_jwt_claim(&perm.to_jwt_claim()).unwrap();
+    /// assert!(manager.validate_perm(&decoded));
+    /// assert_eq!(decoded.get_actions(), perm.get_actions());
+    ///
+    /// assert!(manager.perm_from_jwt_claim(&json!("post:view")).is_ok());
+    /// assert!(manager.perm_from_jwt_claim(&json!("post:share")).is_err());
+    /// ```
+    ///
+    /// # Errors:
+    ///
+    /// - Returns [`ValidationError::InvalidJson`](crate::ValidationError::InvalidJson) if the claim does not have the format of
+    ///   [`Permission::to_jwt_claim`](crate::Permission::to_jwt_claim()), its bitset is not valid for the universe or it was
+    ///   encoded for a different universe.
+    /// - Returns [`ValidationError::UnknownActions`](crate::ValidationError::UnknownActions) if a scope claim has actions that
+    ///   are not in the universe.
+    pub fn perm_from_jwt_claim(&self, claim: &Value) -> Result<Permission, ValidationError> {
+        let Value::Object(map) = claim else {
+            let perm = Permission::from_jwt_claim(claim)?;
+            return self.try_perm_from_actions(perm.get_actions().into_owned());
+        };
+
+        let invalid = |message: &str| {
+            telemetry::record_parse_error();
+            ValidationError::InvalidJson(format!("wrong format in permission claim - {}", message))
+        };
+        let (Some(Value::String(universe)), Some(Value::String(bits)), 2) = (
+            map.get(CLAIM_UNIVERSE_KEY),
+            map.get(CLAIM_BITS_KEY),
+            map.len(),
+        ) else {
+            return Err(invalid(
+                "expected a scope string or a universe hash and bitset",
+            ));
+        };
+        if *universe != self.universe_hash() {
+            return Err(invalid("encoded for a different universe"));
+        }
+        let bytes = URL_SAFE_NO_PAD
+            .decode(bits)
+            .map_err(|_| invalid("bitset is not valid url-safe base64"))?;
+        let actions = self
+            .index
+            .try_decode(&bytes)
+            .map_err(|error| invalid(&error))?;
+
+        Ok(self.managed_perm(actions))
+    }
+
+    /// Returns a managed [`Permission`](crate::Permission) as a tamper-evident string signed with HMAC-SHA256 using `key`, so it can be
+    /// stored client-side (cookies, local storage) and trusted when it comes back through [`verify_perm`](Self::verify_perm()).
+    ///
+    /// The string is the [`universe_hash`](Self::universe_hash()), the bitset from [`encode_perm`](Self::encode_perm()) without the
+    /// hash and the signature of both, as URL-safe base64 separated by `.`. Actions are encoded, not encrypted, so anyone holding the string can
+    /// read them without the key.
+    ///
+    /// # Examples:
+    ///
+    /// ```
+    /// use simple_perm_manager::{PermissionManager, ValidationError};
+    ///
+    /// let manager = PermissionManager::from_json(r#"{"post": {"view": true, "edit": true}}"#);
+    /// let perm = manager.perm_from_json(r#"{"post": {"view": true}}"#);
+    ///
+    /// let signed = manager.sign_perm(&perm, b"secret key");
+    /// let verified = manager.verify_perm(&signed, b"secret key").unwrap();
+    /// assert_eq!(verified.get_actions(), perm.get_actions());
+    ///
+    /// assert_eq!(manager.verify_perm(&signed, b"other key").unwrap_err(), ValidationError::InvalidSignature);
+    /// ```
+    ///
+    /// # Panics:
+    ///
+    /// Panics if the [`Permission`](crate::Permission) provided is not valid for the [`PermissionManager`](crate::PermissionManager).
+    pub fn sign_perm(&self, perm: &Permission, key: &[u8]) -> String {
+        let payload = format!(
+            "{}.{}",
+            self.universe_hash(),
+            URL_SAFE_NO_PAD.encode(self.encode_bitset(perm))
+        );
+        let signature = signing::sign(key, payload.as_bytes());
+
+        format!("{}.{}", payload, URL_SAFE_NO_PAD.encode(&signature))
+    }
+
+    /// Returns the managed [`Permission`](crate::Permission) of a string created with [`sign_perm`](Self::sign_perm()) after checking
+    /// its signature with `key`.
+    ///
+    /// # Errors:
+    ///
+    /// - Returns [`ValidationError::InvalidSignature`](crate::ValidationError::InvalidSignature) if `signed` does not have the
+    ///   format of [`sign_perm`](Self::sign_perm()) or its signature does not match for `key`, i.e. it was tampered with.
+    /// - Returns [`ValidationError::InvalidJson`](crate::ValidationError::InvalidJson) if it was signed for a different universe.
+    #[cfg_attr(feature = "tracing", tracing::instrument(level = "debug", skip_all, fields(manager_id = %self.id), err))]
+    pub fn verify_perm(
+        &self,
+        signed: impl AsRef<[u8]>,
+        key: &[u8],
+    ) -> Result<Permission, ValidationError> {
+        let signed = signed.as_ref();
+        let verified = signed
+            .iter()
+            .rposition(|byte| *byte == b'.')
+            .and_then(|position| {
+                let (payload, signature) = (&signed[..position], &signed[position + 1..]);
+                let signature = URL_SAFE_NO_PAD.decode(signature).ok()?;
+                signing::verify(key, payload, &signature).then_some(payload)
+            })
+            .and_then(|payload| std::str::from_utf8(payload).ok());
+        let Some((universe, token)) = verified.and_then(|payload| payload.split_once('.')) else {
+            telemetry::record_validation_failure();
+            return Err(ValidationError::InvalidSignature);
+        };
+
+        self.perm_from_authenticated(universe, token, "signed")
+    }
+
+    /// Returns a managed [`Permission`](crate::Permission) encrypted with XChaCha20-Poly1305 using `key`, for cases where the
+    /// action names themselves are sensitive and must not be readable by whoever holds the result.
+    /// [`open_perm`](Self::open_perm()) decrypts it and detects any tampering.
+    ///
+    /// The result is URL-safe base64 of a random nonce followed by the encrypted [`universe_hash`](Self::universe_hash()) and
+    /// bitset from [`encode_perm`](Self::encode_perm()). Its length only depends on the universe size, so it does not tell
+    /// how many actions are granted either.
+    ///
+    /// # Examples:
+    ///
+    /// ```
+    /// use simple_perm_manager::{PermissionManager, ValidationError};
+    ///
+    /// let manager = PermissionManager::from_json(r#"{"payroll": {"view": true, "approve": true}}"#);
+    /// let perm = manager.perm_from_json(r#"{"payroll": {"view": true}}"#);
+    /// let key = [42; 32];
+    ///
+    /// let sealed = manager.seal_perm(&perm, &key);
+    /// let opened = manager.open_perm(&sealed, &key).unwrap();
+    /// assert_eq!(opened.get_actions(), perm.get_actions());
+    ///
+    /// assert_eq!(manager.open_perm(&sealed, &[0; 32]).unwrap_err(), ValidationError::InvalidSignature);
+    /// ```
+    ///
+    /// # Panics:
+    ///
+    /// Panics if the [`Permission`](crate::Permission) provided is not valid for the [`PermissionManager`](crate::PermissionManager).
+    #[cfg(feature = "crypto")]
+    pub fn seal_perm(&self, perm: &Permission, key: &[u8; 32]) -> String {
+        let payload = format!(
+            "{}.{}",
+            self.universe_hash(),
+            URL_SAFE_NO_PAD.encode(self.encode_bitset(perm))
+        );
+
+        URL_SAFE_NO_PAD.encode(sealing::seal(key, payload.as_bytes()))
+    }
+
+    /// Returns the managed [`Permission`](crate::Permission) of a string created with [`seal_perm`](Self::seal_perm()),
+    /// decrypting it with `key`.
+    ///
+    /// # Errors:
+    ///
+    /// - Returns [`ValidationError::InvalidSignature`](crate::ValidationError::InvalidSignature) if `sealed` does not have the
+    ///   format of [`seal_perm`](Self::seal_perm()), was sealed with another key or was tampered with.
+    /// - Returns [`ValidationError::InvalidJson`](crate::ValidationError::InvalidJson) if it was sealed for a different universe.
+    #[cfg(feature = "crypto")]
+    #[cfg_attr(feature = "tracing", tracing::instrument(level = "debug", skip_all, fields(manager_id = %self.id), err))]
+    pub fn open_perm(
+        &self,
+        sealed: impl AsRef<[u8]>,
+        key: &[u8; 32],
+    ) -> Result<Permission, ValidationError> {
+        let payload = std::str::from_utf8(sealed.as_ref())
+            .ok()
+            .and_then(|sealed| URL_SAFE_NO_PAD.decode(sealed).ok())
+            .and_then(|sealed| sealing::open(key, &sealed))
+            .and_then(|payload| String::from_utf8(payload).ok());
+        let Some((universe, token)) = payload
+            .as_deref()
+            .and_then(|payload| payload.split_once('.'))
+        else {
+            telemetry::record_validation_failure();
+            return Err(ValidationError::InvalidSignature);
+        };
+
+        self.perm_from_authenticated(universe, token, "sealed")
+    }
+
+    // Returns the managed permission of an authenticated universe hash and token, checking that they belong to the universe
+    fn perm_from_authenticated(
+        &self,
+        universe: &str,
+        token: &str,
+        kind: &str,
+    ) -> Result<Permission, ValidationError> {
+        let invalid = |message: &str| {
+            telemetry::record_parse_error();
+            ValidationError::InvalidJson(format!(
+                "wrong format in {} permission - {}",
+                kind, message
+            ))
+        };
+        if universe != self.universe_hash() {
+            return Err(invalid(&format!("{} for a different universe", kind)));
+        }
+        let bytes = URL_SAFE_NO_PAD
+            .decode(token)
+            .map_err(|_| invalid("bitset is not valid url-safe base64"))?;
+        let actions = self
+            .index
+            .try_decode(&bytes)
+            .map_err(|error| invalid(&error))?;
+
+        Ok(self.managed_perm(actions))
+    }
+
+    /// Returns the dense integer id the [`PermissionManager`](crate::PermissionManager) assigns to a universe action
+    /// or `None` if the action is not in the universe.  
+    /// Ids go from zero to the number of universe actions minus one, following the canonical (lexicographic) order of the actions.
+    ///
+    /// # Examples:
+    ///
+    /// ```
+    /// use simple_perm_manager::PermissionManager;
+    ///
+    /// let manager = PermissionManager::from_json(r#"{"view": true, "create": true}"#);
+    ///
+    /// assert_eq!(manager.action_id("create"), Some(0));
+    /// assert_eq!(manager.action_id("view"), Some(1));
+    /// assert_eq!(manager.action_id("other"), None);
+    /// ```
+    pub fn action_id(&self, action: &str) -> Option<u32> {
+        self.index.position(action).map(|position| position as u32)
+    }
+
+    /// Returns the interned name of a universe action or `None` if the action is not in the universe.
+    ///
+    /// A [`PermissionManager`](crate::PermissionManager) allocates every universe action name once.
+    /// The same shared allocation is returned on every call and is also referenced by all the managed
+    /// [`Permission`](crate::Permission)s and [`BitmapPermission`](crate::BitmapPermission)s of the manager, so storing interned
+    /// names instead of `String`s avoids duplicating action names across many permissions.
+    ///
+    /// # Examples:
+    ///
+    /// ```
+    /// use std::sync::Arc;
+    /// use simple_perm_manager::PermissionManager;
+    ///
+    /// let manager = PermissionManager::from_json(r#"{"create": true, "view": true}"#);
+    ///
+    /// let view = manager.intern("view").unwrap();
+    /// assert_eq!(&*view, "view");
+    /// assert!(Arc::ptr_eq(&view, &manager.intern("view").unwrap()));
+    /// assert_eq!(manager.intern("other"), None);
+    /// ```
+    pub fn intern(&self, action: &str) -> Option<Arc<str>> {
+        self.index.intern(action)
+    }
+
+    /// Returns a managed [`Permission`](crate::Permission) as a [`BitmapPermission`](crate::BitmapPermission),
+    /// its representation backed by a compressed bitmap of action ids.  
+    /// The [`BitmapPermission`](crate::BitmapPermission) does not copy any action name, it shares the
+    /// [`PermissionManager`](crate::PermissionManager) interned action table.
+    ///
+    /// # Examples:
+    ///
+    /// ```
+    /// use simple_perm_manager::PermissionManager;
+    ///
+    /// let manager = PermissionManager::from_json(r#"{"create": true, "edit": true, "view": true}"#);
+    /// let perm = manager.perm_from_json(r#"{"create": true, "view": true}"#);
+    ///
+    /// let bitmap_perm = manager.perm_to_bitmap(&perm);
+    /// assert_eq!(bitmap_perm.len(), 2);
+    /// assert!(bitmap_perm.contains_action_id(manager.action_id("view").unwrap()));
+    /// ```
+    ///
+    /// # Panics:
+    ///
+    /// Panics if the [`Permission`](crate::Permission) provided is not valid for the [`PermissionManager`](crate::PermissionManager).
+    #[cfg(feature = "roaring")]
+    pub fn perm_to_bitmap(&self, perm: &Permission) -> BitmapPermission {
+        if !self.validate_perm(perm) {
+            panic!("Permission to convert is not valid for the PermissionManager");
+        }
+
+        let actions: RoaringBitmap = perm
+            .actions()
+            .filter_map(|action| self.action_id(action))
+            .collect();
+
+        BitmapPermission::from_bitmap_and_index(actions, self.id, self.index.clone())
+    }
+
+    /// Returns a managed [`Permission`](crate::Permission) with the actions of a [`BitmapPermission`](crate::BitmapPermission)
+    /// created by this [`PermissionManager`](crate::PermissionManager).
+    ///
+    /// # Examples:
+    ///
+    /// ```
+    /// use simple_perm_manager::PermissionManager;
+    ///
+    /// let manager = PermissionManager::from_json(r#"{"create": true, "edit": true, "view": true}"#);
+    /// let perm = manager.perm_from_json(r#"{"create": true, "view": true}"#);
+    ///
+    /// let back = manager.perm_from_bitmap(&manager.perm_to_bitmap(&perm));
+    /// assert_eq!(back.get_actions(), perm.get_actions());
+    /// ```
+    ///
+    /// # Panics:
+    ///
+    /// Panics if the [`BitmapPermission`](crate::BitmapPermission) provided was created by a different [`PermissionManager`](crate::PermissionManager).
+    #[cfg(feature = "roaring")]
+    #[cfg_attr(feature = "tracing", tracing::instrument(level = "debug", skip_all, fields(manager_id = %self.id)))]
+    pub fn perm_from_bitmap(&self, perm: &BitmapPermission) -> Permission {
+        if perm.get_manager_id() != self.id {
+            panic!("BitmapPermission to convert does not belong to the PermissionManager");
+        }
+
+        let actions: HashSet<String> = perm.actions().map(String::from).collect();
+
+        self.managed_perm(actions)
+    }
+
+    /// Returns a managed [`Permission`](crate::Permission) containing all the actions in every [`Permission`](crate::Permission) provided.  
+    /// This is the same as [`Permission::union_all`](crate::Permission::union_all()) but an empty iterator
+    /// results in an empty [`Permission`](crate::Permission) managed by this [`PermissionManager`](crate::PermissionManager).
+    ///
+    /// # Examples:
+    ///
+    /// ```
+    /// use simple_perm_manager::PermissionManager;
+    ///
+    /// let manager = PermissionManager::from_json(r#"{"create": true, "view": true, "edit": true}"#);
+    ///
+    /// let grants = vec![
+    ///     manager.perm_from_json(r#"{"create": true}"#),
+    ///     manager.perm_from_json(r#"{"edit": true}"#),
+    /// ];
+    ///
+    /// let perm = manager.union_all(&grants);
+    /// assert_eq!(perm.get_actions(), manager.perm_from_json(r#"{"create": true, "edit": true}"#).get_actions());
+    ///
+    /// let nothing = manager.union_all(&vec![]);
+    /// assert!(manager.validate_perm(&nothing));
+    /// ```
+    ///
+    /// # Panics:
+    ///
+    /// Panics if any of the [`Permission`](crate::Permission)s provided is not managed by this [`PermissionManager`](crate::PermissionManager).
+    pub fn union_all<'a, I>(&self, perms: I) -> Permission
+    where
+        I: IntoIterator<Item = &'a Permission>,
+    {
+        let mut union = self.managed_perm(HashSet::new());
+        for perm in perms {
+            union.union_with(perm);
+        }
+
+        union
+    }
+
+    /// Returns a [`PermissionBuilder`](crate::PermissionBuilder) for creating a [`Permission`](crate::Permission)
+    /// managed by this [`PermissionManager`](crate::PermissionManager).
+    /// Actions added to the builder are validated against the universe when calling [`build`](crate::PermissionBuilder::build()).
+    ///
+    /// # Examples:
+    ///
+    /// ```
+    /// use simple_perm_manager::PermissionManager;
+    ///
+    /// let manager = PermissionManager::from_json(r#"{"user": {"create": true, "edit": true}}"#);
+    /// let perm = manager.perm_builder().group("user", ["create"]).build();
+    ///
+    /// assert!(manager.validate_perm(&perm));
+    /// ```
+    pub fn perm_builder(&self) -> PermissionBuilder<'_> {
+        PermissionBuilder::for_manager(self)
+    }
+}
+
+// Returns the JSON Schema of an object holding the actions provided, split into their groups and name.
+fn group_schema(actions: &[&[&str]]) -> Map<String, Value> {
+    let mut children: HashMap<&str, Vec<&[&str]>> = HashMap::new();
+    for action in actions {
+        if let Some((first, rest)) = action.split_first() {
+            children.entry(first).or_default().push(rest);
+        }
+    }
+
+    let mut properties = Map::new();
+    for (key, rest) in children {
+        let nested: Vec<&[&str]> = rest.into_iter().filter(|rest| !rest.is_empty()).collect();
+        let mut boolean = Map::new();
+        boolean.insert(String::from("type"), Value::from("boolean"));
+
+        let property = if nested.is_empty() {
+            boolean
+        } else {
+            let mut any_of = Map::new();
+            any_of.insert(
+                String::from("anyOf"),
+                Value::Array(vec![
+                    Value::Object(boolean),
+                    Value::Object(group_schema(&nested)),
+                ]),
+            );
+            any_of
+        };
+        properties.insert(String::from(key), Value::Object(property));
+    }
+
+    let mut schema = Map::new();
+    schema.insert(String::from("type"), Value::from("object"));
+    schema.insert(String::from("properties"), Value::Object(properties));
+    schema.insert(String::from("additionalProperties"), Value::Bool(false));
+    schema
+}
+
+impl fmt::Debug for PermissionManager {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("PermissionManager")
+            .field("universe", &self.universe)
+            .field("id", &self.id)
+            .field("index", &self.index)
+            .field("aliases", &self.aliases)
+            .field("implications", &self.implications)
+            .field("obligations", &self.obligations)
+            .field("combining_algorithm", &self.combining_algorithm)
+            .field("universe_hooks", &self.universe_hooks.len())
+            .finish()
     }
 }
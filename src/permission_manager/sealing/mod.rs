@@ -0,0 +1,35 @@
+use chacha20poly1305::aead::{Aead, AeadCore, KeyInit, OsRng};
+use chacha20poly1305::{XChaCha20Poly1305, XNonce};
+
+//// TESTS ////
+#[cfg(test)]
+mod tests;
+
+// Length of the random nonce preceding the ciphertext
+const NONCE_LEN: usize = 24;
+
+// Returns the plaintext encrypted with XChaCha20-Poly1305 under the key, preceded by the random nonce used
+pub(crate) fn seal(key: &[u8; 32], plaintext: &[u8]) -> Vec<u8> {
+    let cipher = XChaCha20Poly1305::new(key.into());
+    let nonce = XChaCha20Poly1305::generate_nonce(&mut OsRng);
+
+    let mut sealed = nonce.to_vec();
+    sealed.extend(
+        cipher
+            .encrypt(&nonce, plaintext)
+            .expect("unexpected error, XChaCha20-Poly1305 encryption should not fail"),
+    );
+    sealed
+}
+
+// Returns the plaintext of bytes created with `seal` or `None` if they were not sealed with the key or were tampered with
+pub(crate) fn open(key: &[u8; 32], sealed: &[u8]) -> Option<Vec<u8>> {
+    if sealed.len() < NONCE_LEN {
+        return None;
+    }
+    let (nonce, ciphertext) = sealed.split_at(NONCE_LEN);
+
+    XChaCha20Poly1305::new(key.into())
+        .decrypt(XNonce::from_slice(nonce), ciphertext)
+        .ok()
+}
@@ -0,0 +1,33 @@
+use super::*;
+
+const KEY: [u8; 32] = [7; 32];
+
+#[test]
+fn seal_test() {
+    let sealed = seal(&KEY, b"post:view");
+
+    // Nonce, ciphertext and 16 bytes tag
+    assert_eq!(sealed.len(), NONCE_LEN + 9 + 16);
+    assert_eq!(
+        sealed.windows(9).any(|window| window == b"post:view"),
+        false
+    );
+
+    // Random nonces make every sealing different
+    assert_ne!(sealed, seal(&KEY, b"post:view"));
+}
+
+#[test]
+fn open_test() {
+    let sealed = seal(&KEY, b"post:view");
+    assert_eq!(open(&KEY, &sealed), Some(b"post:view".to_vec()));
+    assert_eq!(open(&[8; 32], &sealed), None);
+
+    let mut tampered = sealed.clone();
+    *tampered.last_mut().unwrap() ^= 1;
+    assert_eq!(open(&KEY, &tampered), None);
+
+    assert_eq!(open(&KEY, &sealed[..NONCE_LEN + 8]), None);
+    assert_eq!(open(&KEY, &sealed[..NONCE_LEN - 1]), None);
+    assert_eq!(open(&KEY, &[]), None);
+}
@@ -0,0 +1,24 @@
+use hmac::{Hmac, Mac};
+use sha2::Sha256;
+
+//// TESTS ////
+#[cfg(test)]
+mod tests;
+
+type HmacSha256 = Hmac<Sha256>;
+
+// Returns the HMAC-SHA256 tag of the payload for the key
+pub(crate) fn sign(key: &[u8], payload: &[u8]) -> Vec<u8> {
+    let mut mac = HmacSha256::new_from_slice(key).expect("HMAC accepts keys of any length");
+    mac.update(payload);
+
+    mac.finalize().into_bytes().to_vec()
+}
+
+// Returns `true` if the tag is the HMAC-SHA256 tag of the payload for the key, comparing in constant time
+pub(crate) fn verify(key: &[u8], payload: &[u8], tag: &[u8]) -> bool {
+    let mut mac = HmacSha256::new_from_slice(key).expect("HMAC accepts keys of any length");
+    mac.update(payload);
+
+    mac.verify_slice(tag).is_ok()
+}
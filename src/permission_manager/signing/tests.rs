@@ -0,0 +1,26 @@
+use super::*;
+
+#[test]
+fn sign_test() {
+    // RFC 4231 test case 2
+    assert_eq!(
+        sign(b"Jefe", b"what do ya want for nothing?"),
+        vec![
+            0x5b, 0xdc, 0xc1, 0x46, 0xbf, 0x60, 0x75, 0x4e, 0x6a, 0x04, 0x24, 0x26, 0x08, 0x95,
+            0x75, 0xc7, 0x5a, 0x00, 0x3f, 0x08, 0x9d, 0x27, 0x39, 0x83, 0x9d, 0xec, 0x58, 0xb9,
+            0x64, 0xec, 0x38, 0x43
+        ]
+    );
+    assert_eq!(sign(b"", b"payload").len(), 32);
+}
+
+#[test]
+fn verify_test() {
+    let tag = sign(b"key", b"payload");
+
+    assert_eq!(verify(b"key", b"payload", &tag), true);
+    assert_eq!(verify(b"other key", b"payload", &tag), false);
+    assert_eq!(verify(b"key", b"payload!", &tag), false);
+    assert_eq!(verify(b"key", b"payload", &tag[..31]), false);
+    assert_eq!(verify(b"key", b"payload", &[]), false);
+}
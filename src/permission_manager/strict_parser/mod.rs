@@ -0,0 +1,143 @@
+use serde::de::{self, DeserializeSeed, Deserializer, MapAccess, Visitor};
+use std::collections::HashSet;
+use std::fmt;
+
+//// TESTS ////
+#[cfg(test)]
+mod tests;
+
+// Maximum nesting allowed in an actions JSON object, as in the non strict deserialization.
+const MAX_JSON_DEPTH_ALLOWED: usize = 20;
+
+#[doc(hidden)]
+/// Error of a strict actions JSON parse.
+#[derive(Debug, PartialEq, Eq)]
+pub enum StrictParseError {
+    /// The JSON is not valid or does not have the actions format.
+    InvalidJson(String),
+    /// JSON pointer of the first key that is not a known action or group.
+    UnknownAction(String),
+}
+
+#[doc(hidden)]
+/// Parses an actions JSON string into the actions with `true` values, checking every key against `is_known` as soon as it is
+/// read, so parsing stops at the first unknown action or group. Nested keys are joined with `separator`.
+///
+/// # Errors
+///
+/// - Returns [`StrictParseError::UnknownAction`] with the JSON pointer of the first key for which `is_known` returns `false`,
+///   given the full action or group of the key.
+/// - Returns [`StrictParseError::InvalidJson`] if the JSON is not an object of nested objects and booleans or is nested too deep.
+pub fn parse_actions_strict<F>(
+    actions_json: &str,
+    separator: char,
+    is_known: F,
+) -> Result<HashSet<String>, StrictParseError>
+where
+    F: Fn(&str) -> bool,
+{
+    let mut actions = HashSet::new();
+    let mut unknown = None;
+    let mut deserializer = serde_json::Deserializer::from_str(actions_json);
+    let result = ActionsSeed {
+        prefix: String::new(),
+        pointer: String::new(),
+        depth: 0,
+        separator,
+        is_known: &is_known,
+        actions: &mut actions,
+        unknown: &mut unknown,
+    }
+    .deserialize(&mut deserializer)
+    .and_then(|_| deserializer.end());
+
+    match (result, unknown) {
+        (_, Some(pointer)) => Err(StrictParseError::UnknownAction(pointer)),
+        (Err(error), None) => Err(StrictParseError::InvalidJson(format!(
+            "wrong format in permission json string: {}",
+            error
+        ))),
+        (Ok(()), None) => Ok(actions),
+    }
+}
+
+// Deserializes the value of an action or group key, or the root object when the prefix is empty.
+struct ActionsSeed<'a, F> {
+    prefix: String,
+    pointer: String,
+    depth: usize,
+    separator: char,
+    is_known: &'a F,
+    actions: &'a mut HashSet<String>,
+    unknown: &'a mut Option<String>,
+}
+
+impl<'de, F: Fn(&str) -> bool> DeserializeSeed<'de> for ActionsSeed<'_, F> {
+    type Value = ();
+
+    fn deserialize<D: Deserializer<'de>>(self, deserializer: D) -> Result<(), D::Error> {
+        deserializer.deserialize_any(self)
+    }
+}
+
+impl<'de, F: Fn(&str) -> bool> Visitor<'de> for ActionsSeed<'_, F> {
+    type Value = ();
+
+    fn expecting(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+        if self.prefix.is_empty() {
+            write!(formatter, "an object of actions")
+        } else {
+            write!(
+                formatter,
+                "an object or a boolean value for '{}'",
+                self.prefix
+            )
+        }
+    }
+
+    fn visit_bool<E: de::Error>(self, value: bool) -> Result<(), E> {
+        if self.prefix.is_empty() {
+            return Err(E::invalid_type(de::Unexpected::Bool(value), &self));
+        }
+        if value {
+            self.actions.insert(self.prefix);
+        }
+
+        Ok(())
+    }
+
+    fn visit_map<A: MapAccess<'de>>(self, mut map: A) -> Result<(), A::Error> {
+        if self.depth >= MAX_JSON_DEPTH_ALLOWED {
+            return Err(de::Error::custom("too much nesting"));
+        }
+
+        while let Some(key) = map.next_key::<String>()? {
+            let action = if self.prefix.is_empty() {
+                key.clone()
+            } else {
+                format!("{}{}{}", self.prefix, self.separator, key)
+            };
+            let pointer = format!(
+                "{}/{}",
+                self.pointer,
+                key.replace('~', "~0").replace('/', "~1")
+            );
+            if !(self.is_known)(&action) {
+                *self.unknown = Some(pointer);
+                return Err(de::Error::custom("unknown action"));
+            }
+
+            map.next_value_seed(ActionsSeed {
+                prefix: action,
+                pointer,
+                depth: self.depth + 1,
+                separator: self.separator,
+                is_known: self.is_known,
+                actions: &mut *self.actions,
+                unknown: &mut *self.unknown,
+            })?;
+        }
+
+        Ok(())
+    }
+}
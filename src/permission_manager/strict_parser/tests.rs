@@ -0,0 +1,69 @@
+use super::*;
+use crate::permission::ACTION_DIVIDER;
+
+fn is_known(action: &str) -> bool {
+    ["building", "building:view", "building:edit", "home"].contains(&action)
+}
+
+#[test]
+fn parse_actions_strict_test() {
+    assert_eq!(
+        parse_actions_strict(
+            r#"{"building": {"view": true, "edit": false}, "home": true}"#,
+            ACTION_DIVIDER,
+            is_known
+        ),
+        Ok(HashSet::from([
+            String::from("building:view"),
+            String::from("home")
+        ]))
+    );
+    assert_eq!(
+        parse_actions_strict(r#"{"building": true}"#, ACTION_DIVIDER, is_known),
+        Ok(HashSet::from([String::from("building")]))
+    );
+    assert_eq!(
+        parse_actions_strict("{}", ACTION_DIVIDER, is_known),
+        Ok(HashSet::new())
+    );
+}
+
+#[test]
+fn unknown_action_test() {
+    assert_eq!(
+        parse_actions_strict(
+            r#"{"building": {"view": true, "edti": true}}"#,
+            ACTION_DIVIDER,
+            is_known
+        ),
+        Err(StrictParseError::UnknownAction(String::from(
+            "/building/edti"
+        )))
+    );
+    // Parsing stops at the first unknown key, before reading the rest of the document
+    assert_eq!(
+        parse_actions_strict(
+            r#"{"a/b~c": true, "home": not json"#,
+            ACTION_DIVIDER,
+            is_known
+        ),
+        Err(StrictParseError::UnknownAction(String::from("/a~1b~0c")))
+    );
+}
+
+#[test]
+fn invalid_json_test() {
+    let nested = format!("{}{}", "{\"building\": ".repeat(21), "}".repeat(21));
+    for json in [
+        "not json",
+        "true",
+        r#"{"home": 1}"#,
+        r#"{"home": true} {}"#,
+        nested.as_str(),
+    ] {
+        match parse_actions_strict(json, ACTION_DIVIDER, |_| true) {
+            Err(StrictParseError::InvalidJson(_)) => (),
+            other => panic!("{} should be invalid, got {:?}", json, other),
+        }
+    }
+}
@@ -13,6 +13,36 @@ fn from_actions_test() {
     assert_eq!(*pm.get_universe().get_actions(), actions);
 }
 
+#[test]
+fn to_json_test() {
+    let pm = PermissionManager::from_actions(HashSet::from([
+        String::from("building:view"),
+        String::from("building:floor:edit"),
+        String::from("delete"),
+    ]));
+
+    assert_eq!(
+        pm.to_json(),
+        r#"{"building":{"floor":{"edit":true},"view":true},"delete":true}"#
+    );
+
+    // Round trip keeps the universe
+    let loaded = PermissionManager::from_json(&pm.to_json());
+    assert_eq!(loaded.to_actions(), pm.to_actions());
+    assert_eq!(
+        PermissionManager::from_actions(HashSet::new()).to_json(),
+        "{}"
+    );
+}
+
+#[test]
+fn to_actions_test() {
+    let actions = HashSet::from([String::from("view"), String::from("group:edit")]);
+    let pm = PermissionManager::from_actions(actions.clone());
+
+    assert_eq!(pm.to_actions(), actions);
+}
+
 #[test]
 fn from_json_test() {
     // Create 2 equals sets of actions in different formats
@@ -50,6 +80,57 @@ fn from_json_test() {
     );
 }
 
+#[test]
+fn with_id_test() {
+    let id = ManagerId::random();
+    let actions = HashSet::from([String::from("view"), String::from("edit")]);
+    let pm = PermissionManager::with_id(id, actions.clone());
+    let other_pm = PermissionManager::with_id(id, actions.clone());
+
+    assert_eq!(pm.id(), id);
+    assert_eq!(*pm.get_universe().get_actions(), actions);
+    // Managers with the same id manage the same permissions
+    assert_eq!(
+        other_pm.validate_perm(&pm.perm_from_json(r#"{"view": true}"#)),
+        true
+    );
+    assert_eq!(
+        PermissionManager::from_actions(actions)
+            .validate_perm(&pm.perm_from_json(r#"{"view": true}"#)),
+        false
+    );
+}
+
+#[test]
+fn from_json_with_id_test() {
+    let id = ManagerId::random();
+    let pm = PermissionManager::from_json_with_id(id, r#"{"view": true, "group": {"edit": true}}"#);
+
+    assert_eq!(pm.id(), id);
+    assert_eq!(
+        pm.to_actions(),
+        HashSet::from([String::from("view"), String::from("group:edit")])
+    );
+
+    match std::panic::catch_unwind(|| {
+        PermissionManager::from_json_with_id(id, r#"{"view": 1}"#);
+    }) {
+        Ok(_) => panic!("invalid universe JSON should have panicked"),
+        Err(_) => (),
+    }
+}
+
+#[test]
+fn id_test() {
+    let pm = PermissionManager::from_json(r#"{"view": true}"#);
+
+    assert_ne!(
+        pm.id(),
+        PermissionManager::from_json(r#"{"view": true}"#).id()
+    );
+    assert_eq!(pm.get_universe().manager_id(), Some(pm.id()));
+}
+
 #[test]
 fn get_universe_test() {
     let actions = HashSet::from([
@@ -89,6 +170,32 @@ fn validate_perm_test() {
     assert_eq!(pm.validate_perm(&false_managed_perm_diff_actions), false);
 }
 
+#[test]
+fn validate_perm_report_test() {
+    let pm = PermissionManager::from_json(r#"{"view": true, "edit": true}"#);
+
+    let report = pm.validate_perm_report(&pm.perm_from_json(r#"{"view": true}"#));
+    assert_eq!(report.is_valid(), true);
+
+    let report = pm.validate_perm_report(&Permission::from_json(r#"{"view": true, "edti": true}"#));
+    assert_eq!(report.is_valid(), false);
+    assert_eq!(report.manager_mismatch(), true);
+    assert_eq!(report.unknown_actions(), vec![String::from("edti")]);
+    assert_eq!(report.suggestions("edti"), [String::from("edit")]);
+
+    // Reports agree with validate_perm
+    let other_pm = PermissionManager::from_json(r#"{"view": true}"#);
+    let perm = other_pm.perm_from_json(r#"{"view": true}"#);
+    assert_eq!(
+        pm.validate_perm_report(&perm).is_valid(),
+        pm.validate_perm(&perm)
+    );
+    assert_eq!(
+        pm.validate_perm_report(&perm).unknown_actions().is_empty(),
+        true
+    );
+}
+
 #[test]
 fn clean_perm_test() {
     let actions = HashSet::from([String::from("view"), String::from("create")]);
@@ -97,7 +204,7 @@ fn clean_perm_test() {
 
     // Clean managed Perm, unamanged Perm with all actions allowed and unamanged Perm with actions not allowed
     let perm_managed = pm.perm_from_actions(actions.clone());
-    let perm_unmanaged_allowed = Permission::from_actions(HashSet::from(actions.clone()));
+    let perm_unmanaged_allowed = Permission::from_actions(actions.clone());
     let perm_unmanaged_not_allowed = Permission::from_actions(HashSet::from([
         String::from("view"),
         String::from("edit"),
@@ -115,6 +222,458 @@ fn clean_perm_test() {
     );
 }
 
+#[test]
+fn add_actions_test() {
+    let mut pm = PermissionManager::from_json(r#"{"view": true}"#);
+    let perm = pm.perm_from_json(r#"{"view": true}"#);
+
+    pm.add_actions(HashSet::from([String::from("edit"), String::from("view")]));
+
+    assert_eq!(
+        pm.to_actions(),
+        HashSet::from([String::from("view"), String::from("edit")])
+    );
+    assert_eq!(pm.validate_perm(&perm), true);
+    assert_eq!(
+        pm.validate_perm(&pm.perm_from_json(r#"{"edit": true}"#)),
+        true
+    );
+    // New universe is used for encoding
+    assert_eq!(pm.action_id("edit"), Some(0));
+    assert_eq!(pm.action_id("view"), Some(1));
+}
+
+#[test]
+fn remove_actions_test() {
+    let mut pm = PermissionManager::from_json(r#"{"view": true, "edit": true}"#);
+    let perm = pm.perm_from_json(r#"{"view": true, "edit": true}"#);
+
+    pm.remove_actions(&HashSet::from([
+        String::from("edit"),
+        String::from("other"),
+    ]));
+
+    assert_eq!(pm.to_actions(), HashSet::from([String::from("view")]));
+    assert_eq!(pm.validate_perm(&perm), false);
+    assert_eq!(pm.action_id("edit"), None);
+
+    match std::panic::catch_unwind(|| {
+        pm.perm_from_json(r#"{"edit": true}"#);
+    }) {
+        Ok(_) => panic!("removed actions should not be allowed"),
+        Err(_) => (),
+    }
+}
+
+#[test]
+fn migrate_perm_test() {
+    let mut pm = PermissionManager::from_json(r#"{"view": true, "edit": true}"#);
+    let perm = pm.perm_from_json(r#"{"view": true, "edit": true}"#);
+
+    pm.remove_actions(&HashSet::from([String::from("edit")]));
+    let mut migrated = pm.migrate_perm(&perm);
+
+    assert_eq!(pm.validate_perm(&migrated), true);
+    assert_eq!(
+        *migrated.get_actions(),
+        HashSet::from([String::from("view")])
+    );
+    // Migrated permissions are linked to the current universe
+    match std::panic::catch_unwind(move || {
+        migrated.add_action("edit");
+    }) {
+        Ok(_) => panic!("adding a removed action should have panicked"),
+        Err(_) => (),
+    }
+
+    let other_pm = PermissionManager::from_json(r#"{"view": true}"#);
+    match std::panic::catch_unwind(|| {
+        pm.migrate_perm(&other_pm.perm_from_json(r#"{"view": true}"#));
+    }) {
+        Ok(_) => panic!("migrating a permission of another manager should have panicked"),
+        Err(_) => (),
+    }
+    match std::panic::catch_unwind(|| {
+        pm.migrate_perm(&Permission::from_json(r#"{"view": true}"#));
+    }) {
+        Ok(_) => panic!("migrating an unmanaged permission should have panicked"),
+        Err(_) => (),
+    }
+}
+
+#[test]
+fn rename_action_test() {
+    let mut pm = PermissionManager::from_json(r#"{"remove": true, "view": true}"#);
+    let perm = pm.perm_from_json(r#"{"remove": true, "view": true}"#);
+
+    pm.rename_action("remove", "delete");
+    assert_eq!(
+        pm.to_actions(),
+        HashSet::from([String::from("delete"), String::from("view")])
+    );
+    assert_eq!(
+        *pm.rewrite_perm(&perm).get_actions(),
+        HashSet::from([String::from("delete"), String::from("view")])
+    );
+
+    // Chained renames map the oldest name to the current one
+    pm.rename_action("delete", "erase");
+    assert_eq!(
+        *pm.rewrite_perm(&perm).get_actions(),
+        HashSet::from([String::from("erase"), String::from("view")])
+    );
+
+    match std::panic::catch_unwind(|| {
+        let mut pm = PermissionManager::from_json(r#"{"view": true}"#);
+        pm.rename_action("other", "new");
+    }) {
+        Ok(_) => panic!("renaming an action not in the universe should have panicked"),
+        Err(_) => (),
+    }
+    match std::panic::catch_unwind(|| {
+        let mut pm = PermissionManager::from_json(r#"{"view": true, "edit": true}"#);
+        pm.rename_action("view", "edit");
+    }) {
+        Ok(_) => panic!("renaming an action to an existing one should have panicked"),
+        Err(_) => (),
+    }
+}
+
+#[test]
+fn rename_group_test() {
+    let mut pm = PermissionManager::from_json(
+        r#"{"building": {"view": true, "room": {"edit": true}}, "buildings": {"view": true}}"#,
+    );
+
+    pm.rename_group("building", "site");
+    assert_eq!(
+        pm.to_actions(),
+        HashSet::from([
+            String::from("site:view"),
+            String::from("site:room:edit"),
+            String::from("buildings:view"),
+        ])
+    );
+
+    pm.rename_group("site:room", "site:area");
+    assert_eq!(pm.get_universe().contains_action("site:area:edit"), true);
+
+    match std::panic::catch_unwind(|| {
+        let mut pm = PermissionManager::from_json(r#"{"view": true}"#);
+        pm.rename_group("view", "see");
+    }) {
+        Ok(_) => panic!("renaming a group without actions should have panicked"),
+        Err(_) => (),
+    }
+    match std::panic::catch_unwind(|| {
+        let mut pm = PermissionManager::from_json(r#"{"a": {"view": true}, "b": {"view": true}}"#);
+        pm.rename_group("a", "b");
+    }) {
+        Ok(_) => panic!("renaming a group to actions already in the universe should have panicked"),
+        Err(_) => (),
+    }
+}
+
+#[test]
+fn rewrite_perm_test() {
+    let mut pm = PermissionManager::from_json(r#"{"building": {"view": true, "edit": true}}"#);
+    let stored = Permission::from_json(r#"{"building": {"view": true}, "other": true}"#);
+
+    pm.rename_group("building", "site");
+    let rewritten = pm.rewrite_perm(&stored);
+
+    assert_eq!(pm.validate_perm(&rewritten), true);
+    assert_eq!(
+        *rewritten.get_actions(),
+        HashSet::from([String::from("site:view")])
+    );
+
+    // Old names added back to the universe are not renamed anymore
+    pm.add_actions(HashSet::from([String::from("building:view")]));
+    let rewritten = pm.rewrite_perm(&stored);
+    assert_eq!(
+        *rewritten.get_actions(),
+        HashSet::from([String::from("building:view")])
+    );
+    assert_eq!(
+        *pm.perm_from_json(r#"{"building": {"view": true}}"#)
+            .get_actions(),
+        HashSet::from([String::from("building:view")])
+    );
+    assert_eq!(pm.aliases().contains_key("building:view"), false);
+    assert_eq!(
+        pm.rewrite_perm(&Permission::from_json(r#"{"building": {"edit": true}}"#))
+            .contains_action("site:edit"),
+        true
+    );
+
+    let other_pm = PermissionManager::from_json(r#"{"building": {"view": true}}"#);
+    match std::panic::catch_unwind(|| {
+        pm.rewrite_perm(&other_pm.perm_from_json(r#"{"building": {"view": true}}"#));
+    }) {
+        Ok(_) => panic!("rewriting a permission of another manager should have panicked"),
+        Err(_) => (),
+    }
+}
+
+#[test]
+fn versioned_json_test() {
+    let mut pm =
+        PermissionManager::from_json(r#"{"post": {"view": true, "edit": true, "publish": true}}"#);
+    pm.add_migration(
+        2,
+        vec![Migration::split(
+            "post:write",
+            ["post:edit", "post:publish"],
+        )],
+    );
+    pm.add_migration(
+        1,
+        vec![
+            Migration::rename("article", "post"),
+            Migration::remove("post:audit"),
+        ],
+    );
+    assert_eq!(pm.version(), 2);
+
+    let perm = pm
+        .perm_from_versioned_json(
+            r#"{"actions": {"article": {"write": true, "audit": true}}, "version": 0}"#,
+        )
+        .unwrap();
+    assert_eq!(
+        *perm.get_actions(),
+        HashSet::from([String::from("post:edit"), String::from("post:publish")])
+    );
+    assert_eq!(
+        pm.perm_from_versioned_json(&pm.perm_to_versioned_json(&perm))
+            .unwrap()
+            .get_actions(),
+        perm.get_actions()
+    );
+    assert_eq!(
+        pm.perm_from_versioned_json(r#"{"actions": {"article": {"view": true}}, "version": 1}"#)
+            .unwrap_err(),
+        ValidationError::UnknownActions(vec![(String::from("article:view"), vec![])])
+    );
+    assert_eq!(
+        pm.perm_from_versioned_json(r#"{"actions": {"post": {"view": true}}, "version": 3}"#)
+            .unwrap_err(),
+        ValidationError::InvalidJson(String::from(
+            "permission version 3 is newer than universe version 2"
+        ))
+    );
+    for json in [
+        "[]",
+        r#"{"actions": true}"#,
+        r#"{"actions": {}, "version": -1}"#,
+    ] {
+        assert_eq!(
+            matches!(
+                pm.perm_from_versioned_json(json),
+                Err(ValidationError::InvalidJson(_))
+            ),
+            true
+        );
+    }
+}
+
+#[test]
+fn upgrade_perm_test() {
+    let mut pm = PermissionManager::from_json(r#"{"site": {"view": true}}"#);
+    pm.add_migration(1, vec![Migration::rename("building", "site")]);
+    let stored = Permission::from_json(r#"{"building": {"view": true, "edit": true}}"#);
+
+    let upgraded = pm.upgrade_perm(&stored, 0);
+    assert_eq!(pm.validate_perm(&upgraded), true);
+    assert_eq!(upgraded.to_json(), r#"{"site":{"view":true}}"#);
+    assert_eq!(pm.upgrade_perm(&stored, 1).to_json(), "{}");
+
+    let result = std::panic::catch_unwind(|| pm.upgrade_perm(&stored, 2));
+    assert_eq!(result.is_err(), true);
+
+    let mounted = pm.mounted("org");
+    let stored = Permission::from_json(r#"{"org": {"building": {"view": true}}}"#);
+    assert_eq!(
+        mounted.upgrade_perm(&stored, 0).to_json(),
+        r#"{"org":{"site":{"view":true}}}"#
+    );
+}
+
+#[test]
+fn add_alias_test() {
+    let mut pm = PermissionManager::from_json(r#"{"delete": true, "view": true}"#);
+    pm.add_alias("remove", "delete");
+
+    let perm = pm.perm_from_json(r#"{"remove": true, "view": true}"#);
+    assert_eq!(
+        *perm.get_actions(),
+        HashSet::from([String::from("delete"), String::from("view")])
+    );
+    assert_eq!(perm.contains_action("remove"), true);
+    assert_eq!(
+        pm.try_perm_from_actions(HashSet::from([String::from("remove")]))
+            .unwrap()
+            .contains_action("delete"),
+        true
+    );
+    #[cfg(feature = "roaring")]
+    assert_eq!(pm.perm_to_bitmap(&perm).contains_action("remove"), true);
+
+    let mut empty = pm.perm_from_json("{}");
+    assert_eq!(empty.add_action("remove"), true);
+    assert_eq!(
+        *empty.get_actions(),
+        HashSet::from([String::from("delete")])
+    );
+    assert_eq!(empty.remove_action("remove"), true);
+    assert_eq!(empty.contains_action("remove"), false);
+    assert_eq!(empty.contains_action("delete"), false);
+
+    // Aliases of removed actions are dropped
+    pm.remove_actions(&HashSet::from([String::from("delete")]));
+    assert_eq!(pm.aliases().is_empty(), true);
+
+    match std::panic::catch_unwind(|| {
+        let mut pm = PermissionManager::from_json(r#"{"view": true}"#);
+        pm.add_alias("see", "other");
+    }) {
+        Ok(_) => panic!("aliasing an action not in the universe should have panicked"),
+        Err(_) => (),
+    }
+    match std::panic::catch_unwind(|| {
+        let mut pm = PermissionManager::from_json(r#"{"view": true, "edit": true}"#);
+        pm.add_alias("edit", "view");
+    }) {
+        Ok(_) => panic!("aliasing with a universe action name should have panicked"),
+        Err(_) => (),
+    }
+}
+
+#[test]
+fn aliases_test() {
+    let mut pm = PermissionManager::from_json(r#"{"building": {"delete": true}}"#);
+    pm.add_alias("building:remove", "building:delete");
+    pm.rename_group("building", "site");
+
+    assert_eq!(
+        pm.aliases(),
+        HashMap::from([
+            (String::from("building:remove"), String::from("site:delete")),
+            (String::from("building:delete"), String::from("site:delete")),
+        ])
+    );
+    // Renamed actions are accepted through their old names
+    assert_eq!(
+        pm.perm_from_json(r#"{"building": {"remove": true}}"#)
+            .contains_action("site:delete"),
+        true
+    );
+}
+
+#[test]
+fn aliases_in_use_test() {
+    let mut pm = PermissionManager::from_json(r#"{"delete": true, "view": true}"#);
+    pm.add_alias("remove", "delete");
+    pm.add_alias("see", "view");
+
+    let perms = [
+        Permission::from_json(r#"{"remove": true}"#),
+        Permission::from_json(r#"{"view": true, "other": true}"#),
+        pm.perm_from_json(r#"{"see": true}"#),
+    ];
+
+    assert_eq!(
+        pm.aliases_in_use(&perms),
+        HashSet::from([String::from("remove")])
+    );
+    assert_eq!(pm.aliases_in_use(&[]).is_empty(), true);
+}
+
+#[test]
+fn add_implication_test() {
+    let mut pm = PermissionManager::from_json(r#"{"admin": true, "edit": true, "view": true}"#);
+    let issued_before = pm.perm_from_json(r#"{"edit": true}"#);
+    pm.add_implication("edit", "view");
+    pm.add_implication("admin", "edit");
+
+    // Implications are expanded transitively on creation
+    assert_eq!(
+        *pm.perm_from_json(r#"{"admin": true}"#).get_actions(),
+        HashSet::from([
+            String::from("admin"),
+            String::from("edit"),
+            String::from("view"),
+        ])
+    );
+    assert_eq!(
+        *pm.try_perm_from_actions(HashSet::from([String::from("edit")]))
+            .unwrap()
+            .get_actions(),
+        HashSet::from([String::from("edit"), String::from("view")])
+    );
+
+    // contains_action honors implications added after issuing only for new permissions
+    let mut perm = pm.perm_from_json("{}");
+    perm.add_action("admin");
+    assert_eq!(perm.contains_action("view"), true);
+    assert_eq!(issued_before.contains_action("view"), false);
+
+    // Removing an implied action removes the actions implying it
+    let view = pm.perm_from_json(r#"{"view": true}"#);
+    let mut perm = pm.perm_from_json(r#"{"admin": true}"#);
+    assert_eq!(perm.remove_action("view"), true);
+    assert_eq!(perm.contains_action("view"), false);
+    assert_eq!(perm.contains(&view), false);
+    assert_eq!(perm.is_empty(), true);
+
+    // Also when the implied action was not held directly
+    let mut perm = pm.perm_from_json("{}");
+    perm.add_action("edit");
+    assert_eq!(perm.contains_action("view"), true);
+    assert_eq!(perm.remove_action("view"), true);
+    assert_eq!(perm.contains_action("view"), false);
+    assert_eq!(perm.contains_action("edit"), false);
+
+    match std::panic::catch_unwind(|| {
+        let mut pm = PermissionManager::from_json(r#"{"view": true}"#);
+        pm.add_implication("view", "other");
+    }) {
+        Ok(_) => panic!("implying an action not in the universe should have panicked"),
+        Err(_) => (),
+    }
+    match std::panic::catch_unwind(|| {
+        let mut pm = PermissionManager::from_json(r#"{"view": true}"#);
+        pm.add_implication("view", "view");
+    }) {
+        Ok(_) => panic!("an action implying itself should have panicked"),
+        Err(_) => (),
+    }
+}
+
+#[test]
+fn implications_test() {
+    let mut pm = PermissionManager::from_json(r#"{"building": {"edit": true, "view": true}}"#);
+    pm.add_implication("building:edit", "building:view");
+    pm.rename_group("building", "site");
+
+    assert_eq!(
+        pm.implications(),
+        HashMap::from([(
+            String::from("site:edit"),
+            HashSet::from([String::from("site:view")])
+        )])
+    );
+
+    pm.remove_actions(&HashSet::from([String::from("site:view")]));
+    assert_eq!(pm.implications().is_empty(), true);
+    assert_eq!(
+        *pm.perm_from_json(r#"{"site": {"edit": true}}"#)
+            .get_actions(),
+        HashSet::from([String::from("site:edit")])
+    );
+}
+
 #[test]
 fn perm_from_actions_test() {
     let actions = HashSet::from([String::from("view"), String::from("create")]);
@@ -152,3 +711,2019 @@ fn perm_from_json_test() {
     // Ensure permission created is valid for the manager
     assert_eq!(pm.validate_perm(&p), true);
 }
+
+#[test]
+fn try_perm_from_actions_test() {
+    let pm = PermissionManager::from_json(r#"{"view": true, "edit": true}"#);
+    let perm = pm
+        .try_perm_from_actions(HashSet::from([String::from("view")]))
+        .unwrap();
+
+    assert_eq!(pm.validate_perm(&perm), true);
+    assert_eq!(
+        pm.try_perm_from_actions(HashSet::from([
+            String::from("view"),
+            String::from("delete"),
+            String::from("create"),
+        ]))
+        .unwrap_err(),
+        ValidationError::UnknownActions(vec![
+            (String::from("create"), vec![]),
+            (String::from("delete"), vec![])
+        ])
+    );
+}
+
+#[test]
+fn try_perm_from_json_test() {
+    let pm = PermissionManager::from_json(r#"{"view": true, "edit": true}"#);
+
+    assert_eq!(
+        pm.validate_perm(&pm.try_perm_from_json(r#"{"edit": true}"#).unwrap()),
+        true
+    );
+    assert_eq!(
+        pm.try_perm_from_json(r#"{"group": {"edit": true}}"#)
+            .unwrap_err(),
+        ValidationError::UnknownActions(vec![(String::from("group:edit"), vec![])])
+    );
+    assert_eq!(
+        matches!(
+            pm.try_perm_from_json(r#"{"view": 1}"#),
+            Err(ValidationError::InvalidJson(_))
+        ),
+        true
+    );
+}
+
+#[test]
+fn perm_from_actions_lenient_test() {
+    let pm = PermissionManager::from_json(r#"{"view": true, "edit": true}"#);
+
+    let (perm, discarded) = pm.perm_from_actions_lenient(HashSet::from([
+        String::from("view"),
+        String::from("delete"),
+        String::from("create"),
+    ]));
+    assert_eq!(pm.validate_perm(&perm), true);
+    assert_eq!(*perm.get_actions(), HashSet::from([String::from("view")]));
+    assert_eq!(
+        discarded,
+        HashSet::from([String::from("delete"), String::from("create")])
+    );
+
+    let (perm, discarded) = pm.perm_from_actions_lenient(HashSet::from([String::from("edit")]));
+    assert_eq!(*perm.get_actions(), HashSet::from([String::from("edit")]));
+    assert_eq!(discarded.is_empty(), true);
+}
+
+#[test]
+fn perm_from_json_lenient_test() {
+    let pm = PermissionManager::from_json(r#"{"view": true, "edit": true}"#);
+
+    let (perm, discarded) = pm.perm_from_json_lenient(r#"{"view": true, "group": {"edit": true}}"#);
+    assert_eq!(pm.validate_perm(&perm), true);
+    assert_eq!(*perm.get_actions(), HashSet::from([String::from("view")]));
+    assert_eq!(discarded, HashSet::from([String::from("group:edit")]));
+
+    match std::panic::catch_unwind(|| {
+        pm.perm_from_json_lenient(r#"{"view": 1}"#);
+    }) {
+        Ok(_) => panic!("invalid actions JSON should have panicked"),
+        Err(_) => (),
+    }
+}
+
+#[test]
+fn try_from_actions_test() {
+    let actions = HashSet::from([String::from("view"), String::from("group:edit")]);
+
+    assert_eq!(
+        PermissionManager::try_from_actions(actions.clone())
+            .unwrap()
+            .to_actions(),
+        actions
+    );
+    assert_eq!(
+        PermissionManager::try_from_actions(HashSet::from([
+            String::from("view"),
+            String::from("group:"),
+            String::from(""),
+        ]))
+        .unwrap_err(),
+        ValidationError::MalformedActions(vec![String::from(""), String::from("group:")])
+    );
+
+    // Malformed actions are only rejected by try_from_actions, from_actions keeps accepting them
+    for action in ["", "group:", ":view", "group::view"] {
+        let actions = HashSet::from([String::from("view"), String::from(action)]);
+        assert_eq!(
+            PermissionManager::try_from_actions(actions.clone()).unwrap_err(),
+            ValidationError::MalformedActions(vec![String::from(action)])
+        );
+        assert_eq!(
+            PermissionManager::from_actions(actions.clone()).to_actions(),
+            actions
+        );
+    }
+    for action in ["view", "group:view", "group view", "group.view"] {
+        let actions = HashSet::from([String::from(action)]);
+        assert_eq!(
+            PermissionManager::try_from_actions(actions.clone()).is_ok(),
+            true
+        );
+        PermissionManager::from_actions(actions);
+    }
+}
+
+#[test]
+fn try_from_json_test() {
+    assert_eq!(
+        PermissionManager::try_from_json(r#"{"view": true}"#)
+            .unwrap()
+            .to_actions(),
+        HashSet::from([String::from("view")])
+    );
+    assert_eq!(
+        matches!(
+            PermissionManager::try_from_json("{"),
+            Err(ValidationError::InvalidJson(_))
+        ),
+        true
+    );
+    assert_eq!(
+        PermissionManager::try_from_json(r#"{"": true}"#).unwrap_err(),
+        ValidationError::MalformedActions(vec![String::from("")])
+    );
+
+    // try_from_json returns an error on the JSON from_json panics on
+    for json in ["{", r#"{"view": 1}"#] {
+        assert_eq!(
+            matches!(
+                PermissionManager::try_from_json(json),
+                Err(ValidationError::InvalidJson(_))
+            ),
+            true
+        );
+        match std::panic::catch_unwind(|| PermissionManager::from_json(json)) {
+            Ok(_) => panic!("universe JSON {} should have panicked", json),
+            Err(_) => (),
+        }
+    }
+
+    // Malformed actions are only rejected by try_from_json, from_json keeps accepting them
+    for json in [r#"{"": true}"#, r#"{"group": {"": true}}"#] {
+        assert_eq!(
+            matches!(
+                PermissionManager::try_from_json(json),
+                Err(ValidationError::MalformedActions(_))
+            ),
+            true
+        );
+        PermissionManager::from_json(json);
+    }
+    for json in [
+        r#"{"view": true}"#,
+        r#"{"group": {"view": true, "edit": false}}"#,
+    ] {
+        assert_eq!(PermissionManager::try_from_json(json).is_ok(), true);
+        PermissionManager::from_json(json);
+    }
+}
+
+#[test]
+fn expand_test() {
+    let mut pm = PermissionManager::from_json(
+        r#"{"building": {"view": true, "edit": true, "room": {"edit": true}}, "user": {"view": true}}"#,
+    );
+
+    assert_eq!(
+        *pm.expand(&Permission::from_json(
+            r#"{"building": {"room": true}, "user": true}"#
+        ))
+        .get_actions(),
+        HashSet::from([
+            String::from("building:room:edit"),
+            String::from("user:view")
+        ])
+    );
+
+    // Group nodes are accepted on creation too
+    assert_eq!(
+        pm.perm_from_json(r#"{"building": true}"#)
+            .get_actions()
+            .len(),
+        3
+    );
+
+    // Permissions issued before declaring an implication get the implied actions
+    let issued = pm.perm_from_json(r#"{"building": {"edit": true}}"#);
+    pm.add_implication("building:edit", "building:view");
+    assert_eq!(
+        *pm.expand(&issued).get_actions(),
+        HashSet::from([String::from("building:edit"), String::from("building:view")])
+    );
+
+    // Granted groups cover the actions added beneath them later, explicit actions do not
+    let group = pm.perm_from_json(r#"{"building": {"room": true}}"#);
+    let explicit = pm.perm_from_json(r#"{"building": {"room": {"edit": true}}}"#);
+    pm.add_actions(HashSet::from([String::from("building:room:delete")]));
+    assert_eq!(group.contains_action("building:room:delete"), true);
+    assert_eq!(group.contains_action("building:delete"), false);
+    assert_eq!(explicit.contains_action("building:room:delete"), false);
+    assert_eq!(
+        *pm.expand(&group).get_actions(),
+        HashSet::from([
+            String::from("building:room:edit"),
+            String::from("building:room:delete")
+        ])
+    );
+    assert_eq!(
+        pm.expand(&explicit).contains_action("building:room:delete"),
+        false
+    );
+
+    // Removing actions of a group stops granting it as a whole
+    let mut revoked = group.clone();
+    revoked.remove_action("building:room:edit");
+    assert_eq!(revoked.contains_action("building:room:delete"), false);
+    let mut revoked = group.union(&pm.perm_from_json(r#"{"user": true}"#));
+    assert_eq!(revoked.contains_action("building:room:delete"), true);
+    revoked.difference_with(&explicit);
+    assert_eq!(revoked.contains_action("building:room:delete"), false);
+    let mut intersected = group.clone();
+    intersected.intersect_with(&pm.perm_from_json(r#"{"building": true}"#));
+    assert_eq!(intersected.contains_action("building:room:delete"), true);
+    intersected.intersect_with(&explicit);
+    assert_eq!(intersected.contains_action("building:room:delete"), false);
+
+    match std::panic::catch_unwind(|| {
+        pm.expand(&Permission::from_json(r#"{"floor": true}"#));
+    }) {
+        Ok(_) => panic!("expanding an unknown group should have panicked"),
+        Err(_) => (),
+    }
+    let other_pm = PermissionManager::from_json(r#"{"user": {"view": true}}"#);
+    match std::panic::catch_unwind(|| {
+        pm.expand(&other_pm.perm_from_json(r#"{"user": true}"#));
+    }) {
+        Ok(_) => panic!("expanding a permission of another manager should have panicked"),
+        Err(_) => (),
+    }
+}
+
+#[test]
+fn adopt_test() {
+    let pm = PermissionManager::from_json(r#"{"view": true, "edit": true}"#);
+    let adopted = pm.adopt(&Permission::from_json(r#"{"view": true}"#));
+
+    assert_eq!(pm.validate_perm(&adopted), true);
+    assert_eq!(
+        pm.validate_perm(&pm.adopt(&pm.perm_from_json(r#"{"edit": true}"#))),
+        true
+    );
+
+    // Actions outside the universe
+    match std::panic::catch_unwind(|| {
+        pm.adopt(&Permission::from_json(r#"{"other": true}"#));
+    }) {
+        Ok(_) => {
+            panic!("adopting a permission with actions outside the universe should have panicked")
+        }
+        Err(_) => (),
+    }
+
+    // Permission from other manager
+    let other_pm = PermissionManager::from_json(r#"{"view": true}"#);
+    match std::panic::catch_unwind(|| {
+        pm.adopt(&other_pm.perm_from_json(r#"{"view": true}"#));
+    }) {
+        Ok(_) => panic!("adopting a permission with different manager should have panicked"),
+        Err(_) => (),
+    }
+}
+
+#[test]
+fn adopt_json_test() {
+    let id = ManagerId::random();
+    let pm = PermissionManager::from_json_with_id(id, r#"{"view": true, "edit": true}"#);
+    let restarted_pm = PermissionManager::from_json_with_id(id, r#"{"view": true, "edit": true}"#);
+    let perm = pm.perm_from_json(r#"{"view": true}"#);
+
+    let adopted = restarted_pm.adopt_json(&perm.to_managed_json());
+    assert_eq!(*adopted.get_actions(), *perm.get_actions());
+    assert_eq!(restarted_pm.validate_perm(&adopted), true);
+
+    // Unmanaged serialized permissions are adopted too
+    assert_eq!(
+        pm.validate_perm(
+            &pm.adopt_json(&Permission::from_json(r#"{"edit": true}"#).to_managed_json())
+        ),
+        true
+    );
+
+    let other_pm = PermissionManager::from_json(r#"{"view": true, "edit": true}"#);
+    match std::panic::catch_unwind(|| {
+        other_pm.adopt_json(&perm.to_managed_json());
+    }) {
+        Ok(_) => panic!("adopting a permission with different manager should have panicked"),
+        Err(_) => (),
+    }
+    match std::panic::catch_unwind(|| {
+        pm.adopt_json(r#"{"view": true}"#);
+    }) {
+        Ok(_) => panic!("adopting a JSON without managed format should have panicked"),
+        Err(_) => (),
+    }
+}
+
+#[test]
+fn policy_from_json_test() {
+    let mut pm = PermissionManager::from_json(
+        r#"{"building": {"view": true, "edit": true, "delete": true}, "user": {"view": true}}"#,
+    );
+    pm.add_implication("building:edit", "building:view");
+
+    let policy =
+        pm.policy_from_json(r#"{"building": {"edit": true, "delete": false}, "user": false}"#);
+
+    assert_eq!(pm.validate_perm(policy.allowed()), true);
+    assert_eq!(pm.validate_perm(policy.denied()), true);
+    assert_eq!(
+        *policy.allowed().get_actions(),
+        HashSet::from([String::from("building:edit"), String::from("building:view")])
+    );
+    // Denied groups are expanded, denied actions do not deny implied ones
+    assert_eq!(
+        *policy.denied().get_actions(),
+        HashSet::from([String::from("building:delete"), String::from("user:view")])
+    );
+
+    let deny_edit = pm.policy_from_json(r#"{"building": {"edit": false}}"#);
+    assert_eq!(
+        *deny_edit.denied().get_actions(),
+        HashSet::from([String::from("building:edit")])
+    );
+
+    match std::panic::catch_unwind(|| {
+        pm.policy_from_json(r#"{"floor": false}"#);
+    }) {
+        Ok(_) => panic!("denying actions not in the universe should have panicked"),
+        Err(_) => (),
+    }
+}
+
+#[test]
+fn combine_policies_test() {
+    let mut pm = PermissionManager::from_json(r#"{"view": true, "edit": true, "delete": true}"#);
+    let policies = [
+        pm.policy_from_json(r#"{"view": true, "edit": false}"#),
+        pm.policy_from_json(r#"{"edit": true, "delete": true, "view": false}"#),
+        pm.policy_from_json(r#"{"delete": false}"#),
+    ];
+
+    assert_eq!(pm.combining_algorithm(), CombiningAlgorithm::DenyOverrides);
+    assert_eq!(
+        pm.combine_policies(&policies).get_actions().is_empty(),
+        true
+    );
+
+    pm.set_combining_algorithm(CombiningAlgorithm::AllowOverrides);
+    assert_eq!(
+        *pm.combine_policies(&policies).get_actions(),
+        HashSet::from([
+            String::from("view"),
+            String::from("edit"),
+            String::from("delete"),
+        ])
+    );
+
+    pm.set_combining_algorithm(CombiningAlgorithm::FirstApplicable);
+    let combined = pm.combine_policies(&policies);
+    assert_eq!(
+        *combined.get_actions(),
+        HashSet::from([String::from("view"), String::from("delete")])
+    );
+    assert_eq!(pm.validate_perm(&combined), true);
+    assert_eq!(pm.combine_policies(&[]).get_actions().is_empty(), true);
+
+    // A policy that allows and denies the same action denies it, whatever the later policies say
+    let policies = [
+        Policy::new(
+            pm.perm_from_json(r#"{"view": true, "edit": true}"#),
+            pm.perm_from_json(r#"{"edit": true}"#),
+        ),
+        pm.policy_from_json(r#"{"edit": true}"#),
+    ];
+    assert_eq!(
+        *pm.combine_policies(&policies).get_actions(),
+        HashSet::from([String::from("view")])
+    );
+
+    match std::panic::catch_unwind(|| {
+        pm.combine_policies(&[Policy::from_json(r#"{"view": true}"#)]);
+    }) {
+        Ok(_) => panic!("combining policies of another manager should have panicked"),
+        Err(_) => (),
+    }
+}
+
+#[test]
+fn encode_perm_test() {
+    let actions: HashSet<String> = (0..10).map(|n| format!("action_{}", n)).collect();
+    let pm = PermissionManager::from_actions(actions.clone());
+    let pm_second = PermissionManager::from_actions(actions.clone());
+
+    let empty = pm.perm_from_actions(HashSet::from([]));
+    let some = pm.perm_from_actions(HashSet::from([
+        String::from("action_0"),
+        String::from("action_9"),
+    ]));
+    let full = pm.perm_from_actions(actions.clone());
+
+    // Encoding has always the universe hash followed by one bit per universe action
+    assert_eq!(pm.encode_perm(&empty)[..8], pm.index.fingerprint_bytes());
+    assert_eq!(pm.encode_perm(&empty)[8..], [0, 0]);
+    assert_eq!(pm.encode_perm(&some)[8..], [0b0000_0001, 0b0000_0010]);
+    assert_eq!(pm.encode_perm(&full)[8..], [0b1111_1111, 0b0000_0011]);
+
+    // Managers with the same universe produce the same encoding
+    assert_eq!(
+        pm.encode_perm(&some),
+        pm_second.encode_perm(&pm_second.perm_from_actions(some.get_actions().into_owned()))
+    );
+
+    // Ensure method panics for permissions not valid for the manager
+    let unmanaged = Permission::from_actions(HashSet::from([String::from("action_0")]));
+    let other_manager = pm_second.perm_from_actions(HashSet::from([String::from("action_0")]));
+
+    match std::panic::catch_unwind(|| {
+        pm.encode_perm(&unmanaged);
+    }) {
+        Ok(_) => panic!("encoding an unmanaged permission should have panicked"),
+        Err(_) => (),
+    }
+    match std::panic::catch_unwind(|| {
+        pm.encode_perm(&other_manager);
+    }) {
+        Ok(_) => panic!("encoding a permission of other manager should have panicked"),
+        Err(_) => (),
+    }
+}
+
+#[test]
+fn decode_perm_test() {
+    let actions: HashSet<String> = (0..10).map(|n| format!("action_{}", n)).collect();
+    let pm = PermissionManager::from_actions(actions.clone());
+
+    let some = pm.perm_from_actions(HashSet::from([
+        String::from("action_3"),
+        String::from("action_8"),
+    ]));
+    let decoded = pm.decode_perm(&pm.encode_perm(&some));
+
+    assert_eq!(pm.validate_perm(&decoded), true);
+    assert_eq!(*decoded.get_actions(), *some.get_actions());
+
+    // Ensure method panics for wrong encodings
+    let encoded = |bitset: &[u8]| [pm.index.fingerprint_bytes().as_slice(), bitset].concat();
+    match std::panic::catch_unwind(|| {
+        pm.decode_perm(&[0, 0]);
+    }) {
+        Ok(_) => panic!("decoding bytes without universe hash should have panicked"),
+        Err(_) => (),
+    }
+    match std::panic::catch_unwind(|| {
+        pm.decode_perm(&encoded(&[0, 0, 0]));
+    }) {
+        Ok(_) => panic!("decoding bytes with wrong length should have panicked"),
+        Err(_) => (),
+    }
+    match std::panic::catch_unwind(|| {
+        pm.decode_perm(&encoded(&[0, 0b1000_0000]));
+    }) {
+        Ok(_) => panic!("decoding bytes with bits outside the universe should have panicked"),
+        Err(_) => (),
+    }
+}
+
+#[test]
+fn encoded_contains_test() {
+    let actions: HashSet<String> = (0..10).map(|n| format!("action_{}", n)).collect();
+    let pm = PermissionManager::from_actions(actions.clone());
+
+    let full = pm.encode_perm(&pm.perm_from_actions(actions));
+    let some = pm.encode_perm(&pm.perm_from_actions(HashSet::from([
+        String::from("action_1"),
+        String::from("action_9"),
+    ])));
+    let other = pm.encode_perm(&pm.perm_from_actions(HashSet::from([String::from("action_2")])));
+    let empty = pm.encode_perm(&pm.perm_from_actions(HashSet::from([])));
+
+    assert_eq!(pm.encoded_contains(&full, &some), true);
+    assert_eq!(pm.encoded_contains(&some, &full), false);
+    assert_eq!(pm.encoded_contains(&some, &other), false);
+    assert_eq!(pm.encoded_contains(&some, &empty), true);
+    assert_eq!(pm.encoded_contains(&empty, &empty), true);
+
+    match std::panic::catch_unwind(|| {
+        pm.encoded_contains(&full[..9], &empty);
+    }) {
+        Ok(_) => panic!("encodings with wrong length should have panicked"),
+        Err(_) => (),
+    }
+    match std::panic::catch_unwind(|| {
+        pm.encoded_contains(&full, &empty[8..]);
+    }) {
+        Ok(_) => panic!("encodings without universe hash should have panicked"),
+        Err(_) => (),
+    }
+}
+
+#[test]
+fn perm_to_token_test() {
+    let actions: HashSet<String> = (0..10).map(|n| format!("action_{}", n)).collect();
+    let pm = PermissionManager::from_actions(actions.clone());
+
+    let empty = pm.perm_from_actions(HashSet::from([]));
+    let full = pm.perm_from_actions(actions);
+
+    // Tokens are the universe hash and bitset as base64
+    assert_eq!(pm.perm_to_token(&empty).len(), 14);
+    assert_eq!(
+        URL_SAFE_NO_PAD.decode(pm.perm_to_token(&empty)),
+        Ok(pm.encode_perm(&empty))
+    );
+    assert_eq!(
+        URL_SAFE_NO_PAD.decode(pm.perm_to_token(&full)),
+        Ok(pm.encode_perm(&full))
+    );
+
+    match std::panic::catch_unwind(|| {
+        pm.perm_to_token(&Permission::from_actions(HashSet::from([String::from(
+            "action_0",
+        )])));
+    }) {
+        Ok(_) => panic!("creating a token for an unmanaged permission should have panicked"),
+        Err(_) => (),
+    }
+}
+
+#[test]
+fn perm_from_token_test() {
+    let actions: HashSet<String> = (0..10).map(|n| format!("action_{}", n)).collect();
+    let pm = PermissionManager::from_actions(actions);
+
+    let some = pm.perm_from_actions(HashSet::from([
+        String::from("action_2"),
+        String::from("action_7"),
+    ]));
+    let from_token = pm.perm_from_token(&pm.perm_to_token(&some));
+
+    assert_eq!(pm.validate_perm(&from_token), true);
+    assert_eq!(*from_token.get_actions(), *some.get_actions());
+
+    // Ensure method panics for wrong tokens
+    match std::panic::catch_unwind(|| {
+        pm.perm_from_token("not a token");
+    }) {
+        Ok(_) => panic!("not base64 token should have panicked"),
+        Err(_) => (),
+    }
+    match std::panic::catch_unwind(|| {
+        pm.perm_from_token("AAAA");
+    }) {
+        Ok(_) => panic!("token with wrong length should have panicked"),
+        Err(_) => (),
+    }
+}
+
+#[test]
+fn universe_hash_test() {
+    let pm = PermissionManager::from_json(r#"{"post": {"view": true, "edit": true}}"#);
+
+    assert_eq!(pm.universe_hash().len(), 11);
+    assert_eq!(
+        pm.universe_hash(),
+        PermissionManager::from_json(r#"{"post": {"edit": true, "view": true}}"#).universe_hash()
+    );
+    assert_ne!(
+        pm.universe_hash(),
+        PermissionManager::from_json(r#"{"post": {"view": true}}"#).universe_hash()
+    );
+}
+
+#[test]
+fn perm_from_jwt_claim_test() {
+    let pm = PermissionManager::from_json(r#"{"post": {"view": true, "edit": true}}"#);
+    let perm = pm.perm_from_json(r#"{"post": {"edit": true}}"#);
+
+    let decoded = pm.perm_from_jwt_claim(&perm.to_jwt_claim()).unwrap();
+    assert_eq!(pm.validate_perm(&decoded), true);
+    assert_eq!(*decoded.get_actions(), *perm.get_actions());
+
+    let from_scope = pm
+        .perm_from_jwt_claim(&Permission::from_json(r#"{"post": {"view": true}}"#).to_jwt_claim())
+        .unwrap();
+    assert_eq!(pm.validate_perm(&from_scope), true);
+    assert_eq!(
+        *from_scope.get_actions(),
+        HashSet::from([String::from("post:view")])
+    );
+    match pm.perm_from_jwt_claim(&Value::String(String::from("post:share"))) {
+        Err(ValidationError::UnknownActions(_)) => (),
+        _ => panic!("scope claim with unknown actions should have failed"),
+    }
+
+    // Bitset claims of other universes, malformed bitsets and other formats are rejected
+    let other = PermissionManager::from_json(r#"{"post": {"view": true, "share": true}}"#);
+    let other_claim = other.perm_from_json(r#"{"post": true}"#).to_jwt_claim();
+    for claim in [
+        other_claim,
+        serde_json::json!({"u": pm.universe_hash(), "b": "not base64"}),
+        serde_json::json!({"u": pm.universe_hash(), "b": "AAAA"}),
+        serde_json::json!({"u": pm.universe_hash(), "b": "BA"}),
+        serde_json::json!({"u": pm.universe_hash(), "b": "Aw", "x": 1}),
+        serde_json::json!({"u": pm.universe_hash()}),
+        serde_json::json!(3),
+    ] {
+        match pm.perm_from_jwt_claim(&claim) {
+            Err(ValidationError::InvalidJson(_)) => (),
+            _ => panic!("claim {} should have failed", claim),
+        }
+    }
+}
+
+#[test]
+fn sign_perm_test() {
+    let pm = PermissionManager::from_json(r#"{"post": {"view": true, "edit": true}}"#);
+    let perm = pm.perm_from_json(r#"{"post": {"edit": true}}"#);
+
+    let signed = pm.sign_perm(&perm, b"key");
+    assert_eq!(signed.split('.').count(), 3);
+    assert_eq!(signed, pm.sign_perm(&perm, b"key"));
+    assert_ne!(signed, pm.sign_perm(&perm, b"other key"));
+
+    let verified = pm.verify_perm(&signed, b"key").unwrap();
+    assert_eq!(pm.validate_perm(&verified), true);
+    assert_eq!(*verified.get_actions(), *perm.get_actions());
+    assert_eq!(
+        pm.verify_perm(signed.as_bytes(), b"key")
+            .unwrap()
+            .get_actions()
+            .len(),
+        1
+    );
+}
+
+#[test]
+fn verify_perm_test() {
+    let pm = PermissionManager::from_json(r#"{"post": {"view": true, "edit": true}}"#);
+    let signed = pm.sign_perm(&pm.perm_from_json(r#"{"post": {"edit": true}}"#), b"key");
+    let (payload, signature) = signed.rsplit_once('.').unwrap();
+
+    // Wrong keys, tampered payloads and malformed strings are rejected
+    let tampered = format!(
+        "{}.{}",
+        payload.replace(
+            &URL_SAFE_NO_PAD
+                .encode(&pm.encode_perm(&pm.perm_from_json(r#"{"post": {"edit": true}}"#))[8..]),
+            "Aw"
+        ),
+        signature
+    );
+    for (signed, key) in [
+        (signed.as_str(), b"other key".as_slice()),
+        (tampered.as_str(), b"key".as_slice()),
+        (payload, b"key".as_slice()),
+        ("", b"key".as_slice()),
+        ("no signature.", b"key".as_slice()),
+    ] {
+        assert_eq!(
+            pm.verify_perm(signed, key).unwrap_err(),
+            ValidationError::InvalidSignature
+        );
+    }
+    assert_eq!(
+        pm.verify_perm([0xff, b'.', b'A'], b"key").unwrap_err(),
+        ValidationError::InvalidSignature
+    );
+
+    // Validly signed strings of other universes are rejected
+    let other = PermissionManager::from_json(r#"{"post": {"view": true, "share": true}}"#);
+    match pm.verify_perm(
+        other.sign_perm(&other.perm_from_json(r#"{"post": true}"#), b"key"),
+        b"key",
+    ) {
+        Err(ValidationError::InvalidJson(_)) => (),
+        _ => panic!("permission signed for another universe should have failed"),
+    }
+}
+
+#[cfg(feature = "crypto")]
+#[test]
+fn seal_perm_test() {
+    let pm = PermissionManager::from_json(r#"{"payroll": {"view": true, "approve": true}}"#);
+    let perm = pm.perm_from_json(r#"{"payroll": {"approve": true}}"#);
+    let key = [1; 32];
+
+    let sealed = pm.seal_perm(&perm, &key);
+    assert_eq!(sealed.contains("approve"), false);
+    assert_eq!(URL_SAFE_NO_PAD.decode(&sealed).is_ok(), true);
+    assert_ne!(sealed, pm.seal_perm(&perm, &key));
+    assert_eq!(
+        sealed.len(),
+        pm.seal_perm(&pm.perm_from_json(r#"{"payroll": true}"#), &key)
+            .len()
+    );
+
+    let opened = pm.open_perm(&sealed, &key).unwrap();
+    assert_eq!(pm.validate_perm(&opened), true);
+    assert_eq!(*opened.get_actions(), *perm.get_actions());
+}
+
+#[cfg(feature = "crypto")]
+#[test]
+fn open_perm_test() {
+    let pm = PermissionManager::from_json(r#"{"payroll": {"view": true, "approve": true}}"#);
+    let key = [1; 32];
+    let sealed = pm.seal_perm(&pm.perm_from_json(r#"{"payroll": {"view": true}}"#), &key);
+
+    // Wrong keys, tampered and malformed strings are rejected
+    let mut tampered = URL_SAFE_NO_PAD.decode(&sealed).unwrap();
+    tampered[30] ^= 1;
+    let tampered = URL_SAFE_NO_PAD.encode(&tampered);
+    for (sealed, key) in [
+        (sealed.as_str(), [2; 32]),
+        (tampered.as_str(), key),
+        (&sealed[..20], key),
+        ("not base64", key),
+        ("", key),
+    ] {
+        assert_eq!(
+            pm.open_perm(sealed, &key).unwrap_err(),
+            ValidationError::InvalidSignature
+        );
+    }
+
+    // Permissions sealed for other universes are rejected
+    let other = PermissionManager::from_json(r#"{"payroll": {"view": true}}"#);
+    match pm.open_perm(
+        other.seal_perm(&other.perm_from_json(r#"{"payroll": true}"#), &key),
+        &key,
+    ) {
+        Err(ValidationError::InvalidJson(_)) => (),
+        _ => panic!("permission sealed for another universe should have failed"),
+    }
+}
+
+#[test]
+fn action_id_test() {
+    let pm = PermissionManager::from_actions(HashSet::from([
+        String::from("view"),
+        String::from("create"),
+        String::from("edit"),
+    ]));
+
+    assert_eq!(pm.action_id("create"), Some(0));
+    assert_eq!(pm.action_id("edit"), Some(1));
+    assert_eq!(pm.action_id("view"), Some(2));
+    assert_eq!(pm.action_id("delete"), None);
+}
+
+#[test]
+fn intern_test() {
+    let pm = PermissionManager::from_actions(HashSet::from([
+        String::from("view"),
+        String::from("create"),
+    ]));
+    let pm_second = PermissionManager::from_actions(HashSet::from([String::from("view")]));
+
+    let view = pm.intern("view").unwrap();
+
+    assert_eq!(&*view, "view");
+    assert!(Arc::ptr_eq(&view, &pm.intern("view").unwrap()));
+    // Interned tables are not shared between managers
+    assert!(!Arc::ptr_eq(&view, &pm_second.intern("view").unwrap()));
+    assert_eq!(pm.intern("delete"), None);
+}
+
+#[cfg(feature = "roaring")]
+#[test]
+fn perm_to_bitmap_test() {
+    let actions: HashSet<String> = (0..100_000).map(|n| format!("action_{:06}", n)).collect();
+    let pm = PermissionManager::from_actions(actions.clone());
+    let pm_second = PermissionManager::from_actions(HashSet::from([String::from("action_000001")]));
+
+    let full = pm.perm_to_bitmap(&pm.perm_from_actions(actions));
+    let some = pm.perm_to_bitmap(&pm.perm_from_actions(HashSet::from([
+        String::from("action_000001"),
+        String::from("action_099999"),
+    ])));
+
+    assert_eq!(full.len(), 100_000);
+    assert_eq!(some.action_ids().collect::<Vec<u32>>(), vec![1, 99_999]);
+    assert_eq!(full.contains(&some), true);
+    assert_eq!(full.difference(&some).len(), 99_998);
+
+    // Ensure method panics for permissions not valid for the manager
+    match std::panic::catch_unwind(|| {
+        pm.perm_to_bitmap(&pm_second.get_universe());
+    }) {
+        Ok(_) => panic!("converting a permission of other manager should have panicked"),
+        Err(_) => (),
+    }
+}
+
+#[cfg(feature = "roaring")]
+#[test]
+fn perm_from_bitmap_test() {
+    let actions: HashSet<String> = (0..10).map(|n| format!("action_{}", n)).collect();
+    let pm = PermissionManager::from_actions(actions.clone());
+    let pm_second = PermissionManager::from_actions(actions);
+
+    let some = pm.perm_from_actions(HashSet::from([
+        String::from("action_3"),
+        String::from("action_8"),
+    ]));
+    let back = pm.perm_from_bitmap(&pm.perm_to_bitmap(&some));
+
+    assert_eq!(pm.validate_perm(&back), true);
+    assert_eq!(*back.get_actions(), *some.get_actions());
+
+    match std::panic::catch_unwind(|| {
+        pm.perm_from_bitmap(&pm_second.perm_to_bitmap(&pm_second.get_universe()));
+    }) {
+        Ok(_) => panic!("converting a bitmap permission of other manager should have panicked"),
+        Err(_) => (),
+    }
+}
+
+#[test]
+fn union_all_test() {
+    let actions = HashSet::from([
+        String::from("view"),
+        String::from("create"),
+        String::from("edit"),
+    ]);
+    let pm = PermissionManager::from_actions(actions.clone());
+    let pm_second = PermissionManager::from_actions(actions.clone());
+
+    let perms = vec![
+        pm.perm_from_actions(HashSet::from([String::from("view")])),
+        pm.perm_from_actions(HashSet::from([
+            String::from("create"),
+            String::from("edit"),
+        ])),
+    ];
+
+    assert_eq!(*pm.union_all(&perms).get_actions(), actions);
+    assert_eq!(pm.validate_perm(&pm.union_all(&perms)), true);
+
+    // Empty iterator results in an empty managed permission
+    let empty = pm.union_all(&vec![]);
+    assert_eq!(*empty.get_actions(), HashSet::from([]));
+    assert_eq!(pm.validate_perm(&empty), true);
+
+    // Ensure method panics for permissions of other manager
+    match std::panic::catch_unwind(|| {
+        pm.union_all(&vec![pm_second.get_universe()]);
+    }) {
+        Ok(_) => panic!("operation with perm with different manager_id should have panicked"),
+        Err(_) => (),
+    }
+}
+
+#[test]
+fn perm_builder_test() {
+    let pm = PermissionManager::from_json(r#"{"user": {"create": true, "edit": true}}"#);
+
+    let perm = pm.perm_builder().group("user", ["create", "edit"]).build();
+    assert_eq!(pm.validate_perm(&perm), true);
+    assert_eq!(*perm.get_actions(), *pm.get_universe().get_actions());
+
+    match std::panic::catch_unwind(|| {
+        pm.perm_builder().action("other").build();
+    }) {
+        Ok(_) => panic!("building with actions not in the manager universe should have panicked"),
+        Err(_) => (),
+    }
+}
+
+#[test]
+fn perm_from_test() {
+    let pm = PermissionManager::from_actions(HashSet::from([
+        String::from("view"),
+        String::from("create"),
+    ]));
+
+    let p = pm.perm_from(["view"]);
+    let p2 = pm.perm_from(vec![String::from("view"), String::from("create")]);
+
+    assert_eq!(*p.get_actions(), HashSet::from([String::from("view")]));
+    assert_eq!(*p2.get_actions(), *pm.get_universe().get_actions());
+    assert_eq!(pm.validate_perm(&p), true);
+
+    match std::panic::catch_unwind(|| {
+        pm.perm_from(["other"]);
+    }) {
+        Ok(_) => panic!("actions supplied for creating a permission are not present in permission manager universe"),
+        Err(_) => (),
+    }
+}
+
+crate::define_permissions! {
+    /// Universe used for testing the define_permissions macro
+    enum TestAction {
+        "building": { "view": true, "edit": false, "meter_readings": { "view": true } },
+        "user": { "create": true }
+    }
+}
+
+crate::define_permissions! {
+    enum SortedTestAction {
+        "user_groups": { "view": true },
+        "user": { "view": true }
+    }
+}
+
+#[test]
+fn define_permissions_test() {
+    assert_eq!(
+        TestAction::ALL,
+        &[
+            TestAction::BuildingMeterReadingsView,
+            TestAction::BuildingView,
+            TestAction::UserCreate
+        ]
+    );
+    assert_eq!(
+        TestAction::BuildingMeterReadingsView.as_str(),
+        "building:meter_readings:view"
+    );
+    assert_eq!(TestAction::UserCreate.to_string(), "user:create");
+    assert_eq!(
+        TestAction::from_action("building:view"),
+        Some(TestAction::BuildingView)
+    );
+    assert_eq!(TestAction::from_action("building:edit"), None);
+
+    let pm = TestAction::permission_manager();
+    assert_eq!(
+        *pm.get_universe().get_actions(),
+        HashSet::from([
+            String::from("building:view"),
+            String::from("building:meter_readings:view"),
+            String::from("user:create")
+        ])
+    );
+
+    let p = pm.perm_from([TestAction::BuildingView, TestAction::UserCreate]);
+    assert_eq!(pm.validate_perm(&p), true);
+    assert_eq!(p.contains_action(TestAction::UserCreate.as_ref()), true);
+    assert_eq!(
+        p.contains_action(TestAction::BuildingMeterReadingsView.as_str()),
+        false
+    );
+
+    // ALL follows the canonical order of the actions, not of the variant names
+    assert_eq!(
+        SortedTestAction::ALL,
+        &[SortedTestAction::UserView, SortedTestAction::UserGroupsView]
+    );
+    let pm = SortedTestAction::permission_manager();
+    for (id, action) in SortedTestAction::ALL.iter().enumerate() {
+        assert_eq!(pm.action_id(action.as_str()), Some(id as u32));
+    }
+}
+
+#[test]
+fn add_resource_type_test() {
+    let mut manager = PermissionManager::from_json(r#"{"admin": true}"#);
+    manager.add_resource_type("building", r#"{"view": true, "floor": {"edit": true}}"#);
+
+    assert_eq!(
+        *manager.get_universe().get_actions(),
+        HashSet::from([
+            String::from("admin"),
+            String::from("building:view"),
+            String::from("building:floor:edit"),
+        ])
+    );
+    assert_eq!(
+        manager.resource_types(),
+        HashSet::from([String::from("building")])
+    );
+
+    for (resource_type, universe) in [
+        ("building", r#"{"delete": true}"#),
+        ("admin", r#"{"view": true}"#),
+        ("", r#"{"view": true}"#),
+        ("user:group", r#"{"view": true}"#),
+        ("user", "{}"),
+    ] {
+        let mut manager =
+            PermissionManager::from_json(r#"{"admin": true, "building": {"view": true}}"#);
+        match std::panic::catch_unwind(move || {
+            manager.add_resource_type(resource_type, universe);
+        }) {
+            Ok(_) => panic!(
+                "adding resource type {:?} should have panicked",
+                resource_type
+            ),
+            Err(_) => (),
+        }
+    }
+}
+
+#[test]
+fn perm_for_type_test() {
+    let manager = PermissionManager::from_json(
+        r#"{"building": {"view": true, "edit": true}, "invoice": {"view": true, "pay": true}}"#,
+    );
+    let perm = manager.perm_for_type("invoice", r#"{"view": true, "pay": true}"#);
+
+    assert_eq!(
+        *perm.get_actions(),
+        HashSet::from([String::from("invoice:view"), String::from("invoice:pay")])
+    );
+    assert_eq!(manager.validate_perm(&perm), true);
+
+    match std::panic::catch_unwind(|| {
+        manager.perm_for_type("building", r#"{"pay": true}"#);
+    }) {
+        Ok(_) => panic!("actions of another resource type should have panicked"),
+        Err(_) => (),
+    }
+    match std::panic::catch_unwind(|| {
+        manager.perm_for_type("user", r#"{"view": true}"#);
+    }) {
+        Ok(_) => panic!("unknown resource type should have panicked"),
+        Err(_) => (),
+    }
+}
+
+#[test]
+fn decide_test() {
+    let mut manager = PermissionManager::from_json(r#"{"view": true, "edit": true}"#);
+    manager.add_alias("read", "view");
+    let perm = manager.perm_from_json(r#"{"view": true}"#);
+
+    assert_eq!(manager.decide(&perm, "view"), Decision::Allow);
+    assert_eq!(manager.decide(&perm, "read"), Decision::Allow);
+    assert_eq!(manager.decide(&perm, "edit"), Decision::Deny);
+    assert_eq!(manager.decide(&perm, "share"), Decision::NotApplicable);
+
+    // Unmanaged permissions are decided against the universe too
+    let unmanaged = Permission::from_json(r#"{"edit": true, "share": true}"#);
+    assert_eq!(manager.decide(&unmanaged, "edit"), Decision::Allow);
+    assert_eq!(manager.decide(&unmanaged, "share"), Decision::NotApplicable);
+
+    let other_manager = PermissionManager::from_json(r#"{"view": true}"#);
+    match std::panic::catch_unwind(|| {
+        manager.decide(&other_manager.perm_from_json(r#"{"view": true}"#), "view");
+    }) {
+        Ok(_) => panic!("deciding on a permission of another manager should have panicked"),
+        Err(_) => (),
+    }
+}
+
+#[test]
+fn add_obligation_test() {
+    let mut manager = PermissionManager::from_json(
+        r#"{"billing": {"view": true, "refund": true}, "home": true}"#,
+    );
+    manager.add_alias("billing:return", "billing:refund");
+    manager.add_obligation("billing", "log_access");
+    manager.add_obligation("billing:refund", "require_mfa");
+    manager.add_obligation("billing:refund", "log_access");
+
+    assert_eq!(
+        manager.obligations_for("billing:refund"),
+        vec![String::from("log_access"), String::from("require_mfa")]
+    );
+    assert_eq!(
+        manager.obligations_for("billing:return"),
+        vec![String::from("log_access"), String::from("require_mfa")]
+    );
+    assert_eq!(manager.obligations_for("home").is_empty(), true);
+
+    // Obligations follow renamed actions
+    manager.rename_action("billing:refund", "billing:reimburse");
+    assert_eq!(
+        manager.obligations_for("billing:reimburse"),
+        vec![String::from("log_access"), String::from("require_mfa")]
+    );
+
+    for action in ["other", "bill"] {
+        let mut manager = PermissionManager::from_json(r#"{"billing": {"view": true}}"#);
+        match std::panic::catch_unwind(move || {
+            manager.add_obligation(action, "log_access");
+        }) {
+            Ok(_) => panic!("obligation for {} should have panicked", action),
+            Err(_) => (),
+        }
+    }
+}
+
+#[test]
+fn decide_with_obligations_test() {
+    let mut manager = PermissionManager::from_json(r#"{"view": true, "export": true}"#);
+    manager.add_obligation("export", "log_access");
+    let perm = manager.perm_from_json(r#"{"export": true}"#);
+
+    assert_eq!(
+        manager.decide_with_obligations(&perm, "export"),
+        (Decision::Allow, vec![String::from("log_access")])
+    );
+    assert_eq!(
+        manager.decide_with_obligations(&perm, "view"),
+        (Decision::Deny, vec![])
+    );
+    assert_eq!(
+        manager.decide_with_obligations(&perm, "other"),
+        (Decision::NotApplicable, vec![])
+    );
+}
+
+#[test]
+fn on_universe_change_test() {
+    let changes = Arc::new(std::sync::Mutex::new(Vec::new()));
+    let hook_changes = changes.clone();
+
+    let mut manager = PermissionManager::from_json(r#"{"building": {"view": true}}"#);
+    manager.on_universe_change(move |previous, current| {
+        hook_changes
+            .lock()
+            .unwrap()
+            .push((previous.get_actions().len(), current.to_json()));
+    });
+
+    manager.add_actions(HashSet::from([String::from("building:edit")]));
+    manager.remove_actions(&HashSet::from([String::from("building:view")]));
+    manager.rename_group("building", "site");
+    // Aliases, implications and unchanged universes are not universe changes
+    manager.add_alias("site:modify", "site:edit");
+    manager.add_actions(HashSet::from([String::from("site:edit")]));
+
+    assert_eq!(
+        *changes.lock().unwrap(),
+        vec![
+            (1, String::from(r#"{"building":{"edit":true,"view":true}}"#)),
+            (2, String::from(r#"{"building":{"edit":true}}"#)),
+            (1, String::from(r#"{"site":{"edit":true}}"#)),
+        ]
+    );
+}
+
+#[test]
+fn minimize_test() {
+    let mut manager = PermissionManager::from_json(
+        r#"{"post": {"view": true, "edit": true, "delete": true}, "user": {"view": true}}"#,
+    );
+    manager.add_implication("post:edit", "post:view");
+    manager.add_alias("post:remove", "post:delete");
+    let granted = manager.perm_from_json(r#"{"post": {"edit": true, "delete": true}}"#);
+
+    let (minimized, unused) = manager.minimize(
+        &HashSet::from([
+            String::from("post:remove"),
+            String::from("user:view"),
+            String::from("other"),
+        ]),
+        &granted,
+    );
+    assert_eq!(manager.validate_perm(&minimized), true);
+    assert_eq!(
+        *minimized.get_actions(),
+        HashSet::from([String::from("post:delete")])
+    );
+    assert_eq!(
+        unused,
+        HashSet::from([String::from("post:edit"), String::from("post:view")])
+    );
+
+    let (minimized, unused) = manager.minimize(&HashSet::new(), &granted);
+    assert_eq!(minimized.get_actions().is_empty(), true);
+    assert_eq!(unused, granted.get_actions().into_owned());
+
+    match std::panic::catch_unwind(|| {
+        manager.minimize(&HashSet::new(), &Permission::from_json(r#"{"post": true}"#));
+    }) {
+        Ok(_) => panic!("minimizing an unmanaged permission should have panicked"),
+        Err(_) => (),
+    }
+}
+
+#[test]
+fn perm_to_compact_json_test() {
+    let manager = PermissionManager::from_json(
+        r#"{"building": {"view": true, "room": {"view": true, "edit": true}}, "user": {"view": true}, "home": true}"#,
+    );
+    let cases = [
+        (r#"{}"#, r#"{}"#),
+        (r#"{"home": true}"#, r#"{"home":true}"#),
+        (
+            r#"{"building": {"view": true, "room": {"view": true, "edit": true}}, "user": {"view": true}}"#,
+            r#"{"building":true,"user":true}"#,
+        ),
+        (
+            r#"{"building": {"room": {"view": true, "edit": true}}}"#,
+            r#"{"building":{"room":true}}"#,
+        ),
+        (
+            r#"{"building": {"view": true, "room": {"edit": true}}}"#,
+            r#"{"building":{"room":{"edit":true},"view":true}}"#,
+        ),
+    ];
+
+    for (actions, compact) in cases {
+        let perm = manager.perm_from_json(actions);
+        assert_eq!(manager.perm_to_compact_json(&perm), compact);
+        assert_eq!(
+            manager.perm_from_json(compact).get_actions(),
+            perm.get_actions()
+        );
+    }
+
+    match std::panic::catch_unwind(|| {
+        manager.perm_to_compact_json(&Permission::from_json(r#"{"home": true}"#));
+    }) {
+        Ok(_) => panic!("serializing an unmanaged permission should have panicked"),
+        Err(_) => (),
+    }
+}
+
+#[test]
+fn perm_to_json_full_test() {
+    let manager = PermissionManager::from_json(
+        r#"{"building": {"view": true, "room": {"edit": true}}, "home": true}"#,
+    );
+    let cases = [
+        (
+            r#"{}"#,
+            r#"{"building":{"room":{"edit":false},"view":false},"home":false}"#,
+        ),
+        (
+            r#"{"building": {"room": {"edit": true}}}"#,
+            r#"{"building":{"room":{"edit":true},"view":false},"home":false}"#,
+        ),
+        (
+            r#"{"building": true, "home": true}"#,
+            r#"{"building":{"room":{"edit":true},"view":true},"home":true}"#,
+        ),
+    ];
+
+    for (actions, full) in cases {
+        let perm = manager.perm_from_json(actions);
+        assert_eq!(manager.perm_to_json_full(&perm), full);
+        assert_eq!(
+            manager.perm_from_json(full).get_actions(),
+            perm.get_actions()
+        );
+    }
+
+    match std::panic::catch_unwind(|| {
+        manager.perm_to_json_full(&Permission::from_json(r#"{"home": true}"#));
+    }) {
+        Ok(_) => panic!("serializing an unmanaged permission should have panicked"),
+        Err(_) => (),
+    }
+}
+
+#[test]
+fn to_json_schema_test() {
+    let mut manager = PermissionManager::from_json(
+        r#"{"building": {"view": true, "room": {"edit": true}}, "home": true}"#,
+    );
+    manager.add_alias("building:see", "building:view");
+    let schema: serde_json::Value = serde_json::from_str(&manager.to_json_schema()).unwrap();
+
+    assert_eq!(
+        schema,
+        serde_json::json!({
+            "$schema": "https://json-schema.org/draft/2020-12/schema",
+            "type": "object",
+            "additionalProperties": false,
+            "properties": {
+                "home": {"type": "boolean"},
+                "building": {"anyOf": [
+                    {"type": "boolean"},
+                    {
+                        "type": "object",
+                        "additionalProperties": false,
+                        "properties": {
+                            "view": {"type": "boolean"},
+                            "see": {"type": "boolean"},
+                            "room": {"anyOf": [
+                                {"type": "boolean"},
+                                {
+                                    "type": "object",
+                                    "additionalProperties": false,
+                                    "properties": {"edit": {"type": "boolean"}}
+                                }
+                            ]}
+                        }
+                    }
+                ]}
+            }
+        })
+    );
+
+    let empty = PermissionManager::from_actions(HashSet::new());
+    assert_eq!(
+        empty.to_json_schema(),
+        r#"{"$schema":"https://json-schema.org/draft/2020-12/schema","additionalProperties":false,"properties":{},"type":"object"}"#
+    );
+}
+
+#[test]
+fn to_openapi_security_scheme_test() {
+    let manager = PermissionManager::from_json(r#"{"building": {"view": true}, "home": true}"#);
+    let components: serde_json::Value = serde_json::from_str(
+        &manager.to_openapi_security_scheme("oauth", "https://auth.example.com/token"),
+    )
+    .unwrap();
+
+    assert_eq!(
+        components,
+        serde_json::json!({
+            "securitySchemes": {
+                "oauth": {
+                    "type": "oauth2",
+                    "flows": {
+                        "clientCredentials": {
+                            "tokenUrl": "https://auth.example.com/token",
+                            "scopes": {
+                                "building:view": "Allows the building:view action",
+                                "home": "Allows the home action"
+                            }
+                        }
+                    }
+                }
+            }
+        })
+    );
+}
+
+#[test]
+fn perm_from_json_strict_test() {
+    let mut manager = PermissionManager::from_json(
+        r#"{"building": {"view": true, "edit": true}, "user": {"view": true}}"#,
+    );
+    manager.add_alias("building:see", "building:view");
+    manager.add_implication("building:edit", "building:view");
+
+    let perm = manager
+        .perm_from_json_strict(r#"{"building": {"edit": true, "see": false}, "user": true}"#)
+        .unwrap();
+    assert_eq!(manager.validate_perm(&perm), true);
+    assert_eq!(
+        perm.get_actions(),
+        manager
+            .perm_from_json(r#"{"building": {"edit": true}, "user": true}"#)
+            .get_actions()
+    );
+
+    assert_eq!(
+        manager
+            .perm_from_json_strict(r#"{"user": {"view": true, "edit": true}}"#)
+            .unwrap_err(),
+        ValidationError::UnknownActionAt(String::from("/user/edit"))
+    );
+    // Groups can not be used as actions of the wrong level
+    assert_eq!(
+        manager
+            .perm_from_json_strict(r#"{"view": true}"#)
+            .unwrap_err(),
+        ValidationError::UnknownActionAt(String::from("/view"))
+    );
+    assert!(matches!(
+        manager.perm_from_json_strict(r#"{"user": "yes"}"#),
+        Err(ValidationError::InvalidJson(_))
+    ));
+}
+
+#[test]
+fn set_separator_test() {
+    let mut manager = PermissionManager::from_json(
+        r#"{"building": {"view": true, "edit": true, "room": {"open": true}}}"#,
+    );
+    manager.add_alias("building:see", "building:view");
+    manager.add_implication("building:edit", "building:view");
+    manager.add_obligation("building:room", "log_access");
+    manager.set_separator('.');
+
+    assert_eq!(manager.separator(), '.');
+    assert_eq!(
+        manager.to_actions(),
+        HashSet::from([
+            String::from("building.view"),
+            String::from("building.edit"),
+            String::from("building.room.open"),
+        ])
+    );
+    let perm = manager.perm_from_json(r#"{"building": {"edit": true, "room": true}}"#);
+    assert_eq!(perm.contains_action("building.view"), true);
+    assert_eq!(perm.contains_action("building.see"), true);
+    assert_eq!(perm.contains_action("building.room.open"), true);
+    assert_eq!(
+        manager.obligations_for("building.room.open"),
+        vec![String::from("log_access")]
+    );
+    assert_eq!(
+        perm.to_json(),
+        r#"{"building":{"edit":true,"room":{"open":true},"view":true}}"#
+    );
+    assert_eq!(
+        manager.adopt_json(&perm.to_managed_json()).get_actions(),
+        perm.get_actions()
+    );
+    assert_eq!(manager.perm_to_compact_json(&perm), r#"{"building":true}"#);
+
+    let result = std::panic::catch_unwind(|| {
+        let mut manager = PermissionManager::from_json(r#"{"v1.0": {"view": true}}"#);
+        manager.set_separator('.');
+    });
+    assert_eq!(result.is_err(), true);
+}
+
+#[test]
+fn accept_separator_test() {
+    let mut manager =
+        PermissionManager::from_json(r#"{"building": {"view": true, "room": {"edit": true}}}"#);
+    manager.accept_separator('.');
+    manager.accept_separator('/');
+    manager.accept_separator('.');
+    manager.accept_separator(':');
+    assert_eq!(manager.accepted_separators(), &['.', '/']);
+
+    let perm = manager.perm_from_actions(HashSet::from([
+        String::from("building.view"),
+        String::from("building/room.edit"),
+    ]));
+    assert_eq!(perm.get_actions(), manager.get_universe().get_actions());
+    assert_eq!(
+        *manager
+            .perm_from_json(r#"{"building": {"room.edit": true}}"#)
+            .get_actions(),
+        HashSet::from([String::from("building:room:edit")])
+    );
+    assert_eq!(
+        manager
+            .try_perm_from_actions(HashSet::from([String::from("building.edit")]))
+            .is_err(),
+        true
+    );
+
+    manager.set_separator('.');
+    assert_eq!(manager.accepted_separators(), &['/']);
+}
+
+#[test]
+fn name_policy_test() {
+    let policy = ActionNamePolicy::identifiers().with_max_length(16);
+    let mut manager = PermissionManager::try_from_json_with_policy(
+        r#"{"building": {"view": true, "edit": true}}"#,
+        policy.clone(),
+    )
+    .unwrap();
+    assert_eq!(manager.name_policy(), &policy);
+    assert_eq!(
+        PermissionManager::try_from_json(r#"{"a": {"b c": true}}"#).is_ok(),
+        true
+    );
+    assert_eq!(
+        PermissionManager::try_from_json_with_policy(r#"{"a": {"b c": true}}"#, policy.clone())
+            .unwrap_err(),
+        ValidationError::MalformedActions(vec![String::from("a:b c")])
+    );
+
+    manager.add_actions(HashSet::from([String::from("building:delete")]));
+    manager.rename_action("building:delete", "building:remove");
+    assert_eq!(
+        manager.get_universe().contains_action("building:remove"),
+        true
+    );
+
+    let result = std::panic::catch_unwind(move || {
+        manager.add_actions(HashSet::from([String::from("building:view all")]));
+    });
+    assert_eq!(result.is_err(), true);
+}
+
+#[test]
+fn case_insensitive_test() {
+    let mut manager = PermissionManager::from_json(
+        r#"{"Building": {"View": true, "Edit": true}, "user": {"view": true}}"#,
+    );
+    manager.add_alias("Building:See", "Building:View");
+    manager.add_obligation("Building", "log_access");
+    assert_eq!(manager.is_case_insensitive(), false);
+    manager.set_case_insensitive(true);
+    assert_eq!(manager.is_case_insensitive(), true);
+
+    assert_eq!(
+        manager.to_actions(),
+        HashSet::from([
+            String::from("building:view"),
+            String::from("building:edit"),
+            String::from("user:view"),
+        ])
+    );
+    let mut perm = manager.perm_from_json(r#"{"BUILDING": {"view": true}, "User": true}"#);
+    assert_eq!(
+        *perm.get_actions(),
+        HashSet::from([String::from("building:view"), String::from("user:view")])
+    );
+    assert_eq!(perm.contains_action("Building:VIEW"), true);
+    assert_eq!(perm.contains_action("building:SEE"), true);
+    assert_eq!(perm.contains_action("Building:Edit"), false);
+    assert_eq!(perm.add_action("Building:Edit"), true);
+    assert_eq!(perm.remove_action("USER:VIEW"), true);
+    assert_eq!(
+        manager.obligations_for("BUILDING:EDIT"),
+        vec![String::from("log_access")]
+    );
+    assert_eq!(
+        manager
+            .perm_from_json_strict(r#"{"Building": {"Edit": true}}"#)
+            .is_ok(),
+        true
+    );
+    assert_eq!(manager.decide(&perm, "Building:Edit"), Decision::Allow);
+
+    let result = std::panic::catch_unwind(|| {
+        let mut manager = PermissionManager::from_json(r#"{"view": true, "View": true}"#);
+        manager.set_case_insensitive(true);
+    });
+    assert_eq!(result.is_err(), true);
+}
+
+#[test]
+fn case_insensitive_write_test() {
+    let mut manager = PermissionManager::from_json(r#"{"building": {"view": true}}"#);
+    manager.set_case_insensitive(true);
+
+    manager.add_actions(HashSet::from([String::from("Building:Edit")]));
+    assert_eq!(
+        manager.get_universe().contains_action("building:edit"),
+        true
+    );
+    assert_eq!(manager.to_actions().contains("Building:Edit"), false);
+
+    manager.rename_action("BUILDING:EDIT", "Building:Update");
+    assert_eq!(manager.to_actions().contains("building:update"), true);
+    manager.rename_group("Building", "Site");
+    assert_eq!(
+        manager.to_actions(),
+        HashSet::from([String::from("site:view"), String::from("site:update")])
+    );
+
+    manager.remove_actions(&HashSet::from([String::from("SITE:UPDATE")]));
+    assert_eq!(
+        manager.to_actions(),
+        HashSet::from([String::from("site:view")])
+    );
+
+    // A failed switch to case-insensitive mode leaves the manager unchanged
+    let mut manager = PermissionManager::from_json(r#"{"view": true, "View": true}"#);
+    let result = std::panic::catch_unwind(std::panic::AssertUnwindSafe(|| {
+        manager.set_case_insensitive(true);
+    }));
+    assert_eq!(result.is_err(), true);
+    assert_eq!(manager.is_case_insensitive(), false);
+}
+
+#[test]
+fn unknown_action_suggestions_test() {
+    let manager = PermissionManager::from_json(
+        r#"{"building": {"view": true, "edit": true}, "user": {"view": true}}"#,
+    );
+
+    let error = manager
+        .try_perm_from_json(r#"{"bulding": {"view": true}, "billing": {"pay": true}}"#)
+        .unwrap_err();
+    assert_eq!(
+        error,
+        ValidationError::UnknownActions(vec![
+            (String::from("billing:pay"), vec![]),
+            (
+                String::from("bulding:view"),
+                vec![String::from("building:view")]
+            ),
+        ])
+    );
+    assert_eq!(
+        error.to_string(),
+        "actions not allowed in permission manager: billing:pay, bulding:view (did you mean 'building:view'?)"
+    );
+    let panic = std::panic::catch_unwind(|| manager.perm_from_json(r#"{"user": {"veiw": true}}"#))
+        .unwrap_err();
+    assert_eq!(
+        panic
+            .downcast_ref::<String>()
+            .unwrap()
+            .ends_with("unknown action 'user:veiw' (did you mean 'user:view'?)"),
+        true
+    );
+}
+
+#[test]
+fn actions_matching_test() {
+    let mut manager = PermissionManager::from_json(
+        r#"{"building": {"view": true, "room": {"view": true, "edit": true}}, "user": {"view": true}}"#,
+    );
+
+    assert_eq!(
+        manager.actions_matching("*:view"),
+        vec![String::from("building:view"), String::from("user:view")]
+    );
+    assert_eq!(manager.actions_matching("building:**").len(), 3);
+    assert_eq!(manager.actions_matching("invoice:*").is_empty(), true);
+
+    manager.set_separator('.');
+    assert_eq!(
+        manager.actions_matching("building.*.view"),
+        vec![String::from("building.room.view")]
+    );
+    let perm = manager.perm_from_json(r#"{"building": {"room": true}}"#);
+    assert_eq!(
+        perm.actions_matching("building.room.*"),
+        vec![
+            String::from("building.room.edit"),
+            String::from("building.room.view")
+        ]
+    );
+}
+
+#[test]
+fn universe_subtree_test() {
+    let manager = PermissionManager::from_json(
+        r#"{"building": {"view": true, "room": {"edit": true}}, "buildings": {"view": true}}"#,
+    );
+
+    assert_eq!(
+        *manager.universe_subtree("building").get_actions(),
+        HashSet::from([
+            String::from("building:view"),
+            String::from("building:room:edit")
+        ])
+    );
+    assert_eq!(
+        *manager.universe_subtree("building:room").get_actions(),
+        HashSet::from([String::from("building:room:edit")])
+    );
+    assert_eq!(
+        manager.universe_subtree("user").get_actions().is_empty(),
+        true
+    );
+    assert_eq!(
+        manager
+            .universe_subtree("building")
+            .has_same_manager(&manager.get_universe()),
+        true
+    );
+}
+
+#[test]
+fn to_dot_test() {
+    let manager = PermissionManager::from_json(r#"{"building": {"view": true}}"#);
+    let other = PermissionManager::from_json(r#"{"building": {"view": true}}"#);
+
+    assert_eq!(manager.to_dot(None).starts_with("digraph universe {"), true);
+    assert_eq!(
+        manager
+            .to_dot(Some(&manager.get_universe()))
+            .contains("fillcolor=lightgreen"),
+        true
+    );
+    let result = std::panic::catch_unwind(|| manager.to_dot(Some(&other.get_universe())));
+    assert_eq!(result.is_err(), true);
+}
+
+#[test]
+fn to_mermaid_test() {
+    let manager = PermissionManager::from_json(r#"{"building": {"view": true}}"#);
+    let other = PermissionManager::from_json(r#"{"building": {"view": true}}"#);
+
+    assert_eq!(
+        manager.to_mermaid(None),
+        "flowchart TD\n    n0(\"building\")\n    n1[\"view\"]\n    n0 --> n1"
+    );
+    let result = std::panic::catch_unwind(|| manager.to_mermaid(Some(&other.get_universe())));
+    assert_eq!(result.is_err(), true);
+}
+
+#[test]
+fn from_json_file_test() {
+    let path = std::env::temp_dir().join(format!("{}.json", Uuid::new_v4()));
+    std::fs::write(
+        &path,
+        r#"{"building": {"view": true, "room": {"edit": true}}}"#,
+    )
+    .unwrap();
+
+    let manager = PermissionManager::from_json_file(&path).unwrap();
+    assert_eq!(
+        *manager.get_universe().get_actions(),
+        HashSet::from([
+            String::from("building:view"),
+            String::from("building:room:edit")
+        ])
+    );
+
+    std::fs::write(&path, r#"{"building": {"view": 1}}"#).unwrap();
+    assert_eq!(
+        matches!(
+            PermissionManager::from_json_file(&path),
+            Err(LoadError::Invalid(ValidationError::InvalidJson(_)))
+        ),
+        true
+    );
+    std::fs::remove_file(&path).unwrap();
+    assert_eq!(
+        matches!(
+            PermissionManager::from_json_file(&path),
+            Err(LoadError::Io(_))
+        ),
+        true
+    );
+}
+
+#[test]
+fn from_env_test() {
+    std::env::set_var("FROM_ENV_TEST_UNIVERSE", r#"{"view": true, "edit": true}"#);
+    let manager = PermissionManager::from_env("FROM_ENV_TEST_UNIVERSE").unwrap();
+    assert_eq!(manager.get_universe().get_actions().len(), 2);
+
+    std::env::set_var("FROM_ENV_TEST_UNIVERSE", "view");
+    assert_eq!(
+        matches!(
+            PermissionManager::from_env("FROM_ENV_TEST_UNIVERSE"),
+            Err(LoadError::Invalid(ValidationError::InvalidJson(_)))
+        ),
+        true
+    );
+    assert_eq!(
+        PermissionManager::from_env("FROM_ENV_TEST_MISSING").unwrap_err(),
+        LoadError::Env(String::from(
+            "FROM_ENV_TEST_MISSING: environment variable not found"
+        ))
+    );
+}
+
+#[test]
+fn from_parts_test() {
+    let manager = PermissionManager::from_parts(vec![
+        (String::from("buildings"), r#"{"building": {"view": true}}"#),
+        (String::from("users"), r#"{"user": {"view": true}}"#),
+    ])
+    .unwrap();
+    assert_eq!(
+        *manager.get_universe().get_actions(),
+        HashSet::from([String::from("building:view"), String::from("user:view")])
+    );
+
+    assert_eq!(
+        PermissionManager::from_parts([("users", r#"{"user": 1}"#)]).unwrap_err(),
+        ValidationError::InvalidJson(String::from(
+            "users: wrong format in permission json string - found no object or boolean value, user-1"
+        ))
+    );
+    assert_eq!(
+        PermissionManager::from_parts([
+            ("users", r#"{"user": {"view": true}}"#),
+            ("legacy", r#"{"user": {"view": true}}"#),
+        ])
+        .unwrap_err(),
+        ValidationError::ConflictingActions(vec![(
+            String::from("user:view"),
+            vec![String::from("users"), String::from("legacy")]
+        )])
+    );
+    assert_eq!(
+        PermissionManager::from_parts([("users", r#"{"user": {"": true}}"#)]).unwrap_err(),
+        ValidationError::MalformedActions(vec![String::from("user:")])
+    );
+}
+
+#[test]
+fn from_json_files_test() {
+    let buildings = std::env::temp_dir().join(format!("{}.json", Uuid::new_v4()));
+    let rooms = std::env::temp_dir().join(format!("{}.json", Uuid::new_v4()));
+    std::fs::write(&buildings, r#"{"building": {"room": true}}"#).unwrap();
+    std::fs::write(&rooms, r#"{"building": {"room": {"edit": true}}}"#).unwrap();
+
+    assert_eq!(
+        PermissionManager::from_json_files([&buildings, &rooms]).unwrap_err(),
+        LoadError::Invalid(ValidationError::ConflictingActions(vec![(
+            String::from("building:room"),
+            vec![buildings.display().to_string(), rooms.display().to_string()]
+        )]))
+    );
+    assert_eq!(
+        PermissionManager::from_json_files([&rooms])
+            .unwrap()
+            .get_universe()
+            .contains_action("building:room:edit"),
+        true
+    );
+
+    std::fs::remove_file(&buildings).unwrap();
+    assert_eq!(
+        matches!(
+            PermissionManager::from_json_files([&buildings, &rooms]),
+            Err(LoadError::Io(_))
+        ),
+        true
+    );
+    std::fs::remove_file(&rooms).unwrap();
+}
+
+#[test]
+fn scoped_test() {
+    let mut manager = PermissionManager::from_json(
+        r#"{"reports": {"view": true, "export": true, "delete": true}, "billing": {"view": true}}"#,
+    );
+    manager.add_alias("reports:download", "reports:export");
+    manager.add_alias("billing:read", "billing:view");
+    manager.add_implication("reports:delete", "reports:export");
+    manager.add_implication("reports:export", "reports:view");
+    manager.add_obligation("reports", "audit");
+    manager.set_separator('.');
+    // Cleaned instead of created from the manager so implied actions are not added
+    let subset = manager.clean_perm(&Permission::from_actions(HashSet::from([
+        String::from("reports.view"),
+        String::from("reports.delete"),
+    ])));
+
+    let tenant = manager.scoped("acme", &subset);
+    assert_eq!(tenant.tenant(), Some("acme"));
+    assert_eq!(manager.tenant(), None);
+    assert_eq!(tenant.id() == manager.id(), false);
+    assert_eq!(tenant.separator(), '.');
+    assert_eq!(
+        *tenant.get_universe().get_actions(),
+        HashSet::from([String::from("reports.view"), String::from("reports.delete")])
+    );
+    assert_eq!(tenant.aliases(), HashMap::new());
+    assert_eq!(
+        tenant.implications(),
+        HashMap::from([(
+            String::from("reports.delete"),
+            HashSet::from([String::from("reports.view")])
+        )])
+    );
+    assert_eq!(
+        tenant
+            .perm_from_json(r#"{"reports": {"delete": true}}"#)
+            .to_json(),
+        r#"{"reports":{"delete":true,"view":true}}"#
+    );
+    assert_eq!(
+        tenant.obligations_for("reports.view"),
+        vec![String::from("audit")]
+    );
+    assert_eq!(
+        tenant.validate_perm(&manager.perm_from_json(r#"{"reports": {"view": true}}"#)),
+        false
+    );
+
+    let other = PermissionManager::from_json(r#"{"reports": {"view": true}}"#);
+    let result = std::panic::catch_unwind(|| manager.scoped("acme", &other.get_universe()));
+    assert_eq!(result.is_err(), true);
+}
+
+#[test]
+fn submanager_test() {
+    let mut manager = PermissionManager::from_json(
+        r#"{"building": {"view": true, "room": {"edit": true}}, "user": {"view": true}}"#,
+    );
+    manager.add_alias("building:read", "building:view");
+    manager.add_alias("user:read", "user:view");
+    let building = manager.submanager("building");
+
+    assert_eq!(building.id(), manager.id());
+    assert_eq!(
+        *building.get_universe().get_actions(),
+        HashSet::from([
+            String::from("building:view"),
+            String::from("building:room:edit")
+        ])
+    );
+    assert_eq!(
+        building.aliases(),
+        HashMap::from([(String::from("building:read"), String::from("building:view"))])
+    );
+
+    let perm = building.perm_from_json(r#"{"building": {"read": true}}"#);
+    assert_eq!(manager.validate_perm(&perm), true);
+    assert_eq!(
+        building.validate_perm(&manager.perm_from_json(r#"{"user": {"view": true}}"#)),
+        false
+    );
+    assert_eq!(
+        building.validate_perm(&manager.perm_from_json(r#"{"building": {"view": true}}"#)),
+        true
+    );
+    assert_eq!(
+        building
+            .submanager("building:room")
+            .get_universe()
+            .get_actions()
+            .len(),
+        1
+    );
+
+    let result = std::panic::catch_unwind(|| manager.submanager("building:view"));
+    assert_eq!(result.is_err(), true);
+}
+
+#[test]
+fn submanager_encoding_test() {
+    let manager =
+        PermissionManager::from_json(r#"{"a": {"x": true}, "b": {"y": true, "z": true}}"#);
+    let sub = manager.submanager("b");
+
+    // Same id but different bit layouts, so encodings of one are rejected by the other
+    let parent_token = manager.perm_to_token(&manager.perm_from_json(r#"{"a": {"x": true}}"#));
+    let sub_token = sub.perm_to_token(&sub.perm_from_json(r#"{"b": {"z": true}}"#));
+    assert_eq!(
+        std::panic::catch_unwind(|| sub.perm_from_token(&parent_token)).is_err(),
+        true
+    );
+    assert_eq!(
+        std::panic::catch_unwind(|| manager.perm_from_token(&sub_token)).is_err(),
+        true
+    );
+    assert_eq!(
+        *sub.perm_from_token(&sub_token).get_actions(),
+        HashSet::from([String::from("b:z")])
+    );
+
+    let signed = manager.sign_perm(&manager.perm_from_json(r#"{"a": {"x": true}}"#), b"key");
+    assert_eq!(sub.verify_perm(&signed, b"key").is_err(), true);
+}
+
+#[test]
+fn mounted_test() {
+    let mut generic = PermissionManager::from_json(r#"{"view": true, "edit": true}"#);
+    generic.add_alias("update", "edit");
+    generic.add_implication("edit", "view");
+    generic.add_obligation("edit", "audit");
+    generic.set_separator('/');
+
+    let project = generic.mounted("Project").mounted("org");
+    assert_eq!(project.namespace(), Some("org/Project"));
+    assert_eq!(generic.namespace(), None);
+    assert_eq!(
+        *project.get_universe().get_actions(),
+        HashSet::from([
+            String::from("org/Project/view"),
+            String::from("org/Project/edit")
+        ])
+    );
+    assert_eq!(
+        project.aliases(),
+        HashMap::from([(
+            String::from("org/Project/update"),
+            String::from("org/Project/edit")
+        )])
+    );
+    assert_eq!(
+        project.obligations_for("org/Project/edit"),
+        vec![String::from("audit")]
+    );
+
+    let perm = project.mount_perm(&Permission::from_actions(HashSet::from([String::from(
+        "update",
+    )])));
+    assert_eq!(project.validate_perm(&perm), true);
+    assert_eq!(
+        *perm.get_actions(),
+        HashSet::from([
+            String::from("org/Project/view"),
+            String::from("org/Project/edit")
+        ])
+    );
+    assert_eq!(
+        *project.unmount_perm(&perm).get_actions(),
+        HashSet::from([String::from("view"), String::from("edit")])
+    );
+
+    let result = std::panic::catch_unwind(|| generic.mounted(""));
+    assert_eq!(result.is_err(), true);
+    let result = std::panic::catch_unwind(|| generic.mount_perm(&generic.get_universe()));
+    assert_eq!(result.is_err(), true);
+    let result = std::panic::catch_unwind(|| generic.unmount_perm(&generic.get_universe()));
+    assert_eq!(result.is_err(), true);
+    let result = std::panic::catch_unwind(|| project.unmount_perm(&generic.get_universe()));
+    assert_eq!(result.is_err(), true);
+    let result = std::panic::catch_unwind(|| {
+        project.mount_perm(&Permission::from_actions(HashSet::from([String::from(
+            "delete",
+        )])))
+    });
+    assert_eq!(result.is_err(), true);
+}
@@ -0,0 +1,112 @@
+use crate::permission::Permission;
+use crate::role_manager::RoleManager;
+use std::collections::HashSet;
+use std::error::Error;
+use std::future::Future;
+
+//// TESTS ////
+#[cfg(test)]
+mod tests;
+
+/// Asynchronous counterpart of [`PermissionStore`](crate::PermissionStore), available with the `async` feature, so authorization
+/// that hits a database does not block the worker threads of an asynchronous runtime such as Tokio.
+///
+/// Backends store the same plain actions and role names keyed by subject, and the returned futures are `Send` so they can be
+/// awaited from spawned tasks. [`InMemoryStore`](crate::InMemoryStore) implements both traits, and with the `postgres` feature
+/// [`AsyncPostgresStore`](crate::AsyncPostgresStore) is the asynchronous version of [`PostgresStore`](crate::PostgresStore).  
+/// The methods have the same names as the [`PermissionStore`](crate::PermissionStore) ones, so import only the trait in use
+/// where a type implements both.
+///
+/// # Examples:
+///
+/// ```
+/// use simple_perm_manager::{AsyncPermissionStore, RoleManager};
+///
+/// async fn can_edit<S: AsyncPermissionStore + Sync>(store: &S, roles: &RoleManager, subject: &str) -> bool {
+///     store.check(roles, subject, "post:edit").await.unwrap_or(false)
+/// }
+/// ```
+pub trait AsyncPermissionStore {
+    /// Error returned by the backend operations.
+    type Error: Error + Send;
+
+    /// Returns the [`Permission`](crate::Permission) stored for a subject, if any.
+    fn load_perm(
+        &self,
+        subject: &str,
+    ) -> impl Future<Output = Result<Option<Permission>, Self::Error>> + Send;
+
+    /// Stores the actions of a [`Permission`](crate::Permission) for a subject, replacing the stored ones.
+    fn save_perm(
+        &mut self,
+        subject: &str,
+        perm: &Permission,
+    ) -> impl Future<Output = Result<(), Self::Error>> + Send;
+
+    /// Returns the names of the [`Role`](crate::Role)s stored for a subject, empty if none.
+    fn load_roles(
+        &self,
+        subject: &str,
+    ) -> impl Future<Output = Result<HashSet<String>, Self::Error>> + Send;
+
+    /// Stores the names of the [`Role`](crate::Role)s assigned to a subject, replacing the stored ones.
+    fn save_roles(
+        &mut self,
+        subject: &str,
+        roles: &HashSet<String>,
+    ) -> impl Future<Output = Result<(), Self::Error>> + Send;
+
+    /// Returns the subjects with a stored [`Permission`](crate::Permission) or roles, sorted.
+    fn subjects(&self) -> impl Future<Output = Result<Vec<String>, Self::Error>> + Send;
+
+    /// Returns the effective [`Permission`](crate::Permission) of a subject, managed by the
+    /// [`PermissionManager`](crate::PermissionManager) of the [`RoleManager`](crate::RoleManager) provided: the stored
+    /// [`Permission`](crate::Permission) unioned with the effective [`Permission`](crate::Permission)s of the stored
+    /// [`Role`](crate::Role)s, including inherited actions.  
+    /// Stored actions are rewritten with [`PermissionManager::rewrite_perm`](crate::PermissionManager::rewrite_perm()) and stored
+    /// roles that do not exist in the [`RoleManager`](crate::RoleManager) are ignored.
+    fn effective_perm(
+        &self,
+        roles: &RoleManager,
+        subject: &str,
+    ) -> impl Future<Output = Result<Permission, Self::Error>> + Send
+    where
+        Self: Sync,
+    {
+        async move {
+            let manager = roles.manager();
+            let stored = match self.load_perm(subject).await? {
+                Some(perm) => manager.rewrite_perm(&perm),
+                None => manager.clean_perm(&Permission::from_actions(HashSet::new())),
+            };
+            let role_perms: Vec<Permission> = self
+                .load_roles(subject)
+                .await?
+                .iter()
+                .filter(|role| roles.role(role).is_some())
+                .map(|role| roles.effective_role_perm(role))
+                .collect();
+
+            Ok(manager.union_all(std::iter::once(&stored).chain(&role_perms)))
+        }
+    }
+
+    /// Returns `true` if the action is in the effective [`Permission`](crate::Permission) of a subject, see
+    /// [`effective_perm`](Self::effective_perm()).
+    fn check(
+        &self,
+        roles: &RoleManager,
+        subject: &str,
+        action_str: &str,
+    ) -> impl Future<Output = Result<bool, Self::Error>> + Send
+    where
+        Self: Sync,
+    {
+        async move {
+            Ok(self
+                .effective_perm(roles, subject)
+                .await?
+                .contains_action(action_str))
+        }
+    }
+}
@@ -0,0 +1,104 @@
+use super::*;
+use crate::permission_manager::PermissionManager;
+use crate::permission_store::InMemoryStore;
+use std::pin::pin;
+use std::task::{Context, Poll, Waker};
+
+// Polls a future that completes without waiting, as the futures of the InMemoryStore do
+fn ready<F: Future>(future: F) -> F::Output {
+    match pin!(future).poll(&mut Context::from_waker(Waker::noop())) {
+        Poll::Ready(output) => output,
+        Poll::Pending => panic!("future should have been ready"),
+    }
+}
+
+fn roles() -> RoleManager {
+    let mut roles = RoleManager::new(PermissionManager::from_json(
+        r#"{"post": {"view": true, "edit": true, "delete": true}}"#,
+    ));
+    let viewer = roles
+        .manager()
+        .perm_from_json(r#"{"post": {"view": true}}"#);
+    let editor = roles
+        .manager()
+        .perm_from_json(r#"{"post": {"edit": true}}"#);
+    roles.add_role("viewer", viewer);
+    roles.add_role("editor", editor);
+    roles.inherit("editor", "viewer");
+
+    roles
+}
+
+#[test]
+fn store_test() {
+    let mut store = InMemoryStore::new();
+    ready(store.save_perm(
+        "alice",
+        &Permission::from_json(r#"{"post": {"view": true}}"#),
+    ))
+    .unwrap();
+    ready(store.save_roles("bob", &HashSet::from([String::from("editor")]))).unwrap();
+
+    assert_eq!(
+        *ready(store.load_perm("alice"))
+            .unwrap()
+            .unwrap()
+            .get_actions(),
+        HashSet::from([String::from("post:view")])
+    );
+    assert_eq!(
+        ready(store.load_roles("bob")).unwrap(),
+        HashSet::from([String::from("editor")])
+    );
+    assert_eq!(
+        ready(store.subjects()).unwrap(),
+        vec![String::from("alice"), String::from("bob")]
+    );
+}
+
+#[test]
+fn effective_perm_test() {
+    let roles = roles();
+    let mut store = InMemoryStore::new();
+    ready(store.save_perm(
+        "alice",
+        &Permission::from_json(r#"{"post": {"delete": true}, "comment": true}"#),
+    ))
+    .unwrap();
+    ready(store.save_roles(
+        "alice",
+        &HashSet::from([String::from("editor"), String::from("removed")]),
+    ))
+    .unwrap();
+
+    let perm = ready(store.effective_perm(&roles, "alice")).unwrap();
+    assert_eq!(roles.manager().validate_perm(&perm), true);
+    assert_eq!(
+        *perm.get_actions(),
+        HashSet::from([
+            String::from("post:view"),
+            String::from("post:edit"),
+            String::from("post:delete"),
+        ])
+    );
+    assert_eq!(
+        ready(store.effective_perm(&roles, "bob"))
+            .unwrap()
+            .get_actions()
+            .is_empty(),
+        true
+    );
+
+    assert_eq!(
+        ready(store.check(&roles, "alice", "post:view")).unwrap(),
+        true
+    );
+    assert_eq!(
+        ready(store.check(&roles, "alice", "comment")).unwrap(),
+        false
+    );
+    assert_eq!(
+        ready(store.check(&roles, "bob", "post:view")).unwrap(),
+        false
+    );
+}
@@ -0,0 +1,108 @@
+use crate::permission::Permission;
+use crate::permission_store::PermissionStore;
+use std::collections::{BTreeSet, HashMap, HashSet};
+use std::convert::Infallible;
+
+//// TESTS ////
+#[cfg(test)]
+mod tests;
+
+/// [`PermissionStore`](crate::PermissionStore) keeping the stored actions and roles in memory, e.g. for tests and prototypes.
+///
+/// # Examples:
+///
+/// ```
+/// use std::collections::HashSet;
+/// use simple_perm_manager::{InMemoryStore, Permission, PermissionStore};
+///
+/// let mut store = InMemoryStore::new();
+/// store.save_perm("alice", &Permission::from_json(r#"{"view": true}"#)).unwrap();
+/// store.save_roles("bob", &HashSet::from([String::from("editor")])).unwrap();
+///
+/// assert_eq!(store.subjects().unwrap(), vec![String::from("alice"), String::from("bob")]);
+/// assert!(store.load_perm("alice").unwrap().unwrap().contains_action("view"));
+/// assert!(store.load_perm("bob").unwrap().is_none());
+/// ```
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct InMemoryStore {
+    perms: HashMap<String, HashSet<String>>,
+    roles: HashMap<String, HashSet<String>>,
+}
+
+impl InMemoryStore {
+    /// Creates a new empty [`InMemoryStore`](crate::InMemoryStore).
+    pub fn new() -> InMemoryStore {
+        InMemoryStore::default()
+    }
+}
+
+impl PermissionStore for InMemoryStore {
+    type Error = Infallible;
+
+    fn load_perm(&self, subject: &str) -> Result<Option<Permission>, Infallible> {
+        Ok(self
+            .perms
+            .get(subject)
+            .map(|actions| Permission::from_actions(actions.clone())))
+    }
+
+    fn save_perm(&mut self, subject: &str, perm: &Permission) -> Result<(), Infallible> {
+        if perm.is_empty() {
+            self.perms.remove(subject);
+        } else {
+            self.perms
+                .insert(subject.to_string(), perm.get_actions().into_owned());
+        }
+
+        Ok(())
+    }
+
+    fn load_roles(&self, subject: &str) -> Result<HashSet<String>, Infallible> {
+        Ok(self.roles.get(subject).cloned().unwrap_or_default())
+    }
+
+    fn save_roles(&mut self, subject: &str, roles: &HashSet<String>) -> Result<(), Infallible> {
+        if roles.is_empty() {
+            self.roles.remove(subject);
+        } else {
+            self.roles.insert(subject.to_string(), roles.clone());
+        }
+
+        Ok(())
+    }
+
+    fn subjects(&self) -> Result<Vec<String>, Infallible> {
+        let subjects: BTreeSet<&String> = self.perms.keys().chain(self.roles.keys()).collect();
+
+        Ok(subjects.into_iter().cloned().collect())
+    }
+}
+
+#[cfg(feature = "async")]
+impl crate::permission_store::async_store::AsyncPermissionStore for InMemoryStore {
+    type Error = Infallible;
+
+    async fn load_perm(&self, subject: &str) -> Result<Option<Permission>, Infallible> {
+        PermissionStore::load_perm(self, subject)
+    }
+
+    async fn save_perm(&mut self, subject: &str, perm: &Permission) -> Result<(), Infallible> {
+        PermissionStore::save_perm(self, subject, perm)
+    }
+
+    async fn load_roles(&self, subject: &str) -> Result<HashSet<String>, Infallible> {
+        PermissionStore::load_roles(self, subject)
+    }
+
+    async fn save_roles(
+        &mut self,
+        subject: &str,
+        roles: &HashSet<String>,
+    ) -> Result<(), Infallible> {
+        PermissionStore::save_roles(self, subject, roles)
+    }
+
+    async fn subjects(&self) -> Result<Vec<String>, Infallible> {
+        PermissionStore::subjects(self)
+    }
+}
@@ -0,0 +1,45 @@
+use super::*;
+
+#[test]
+fn perm_test() {
+    let mut store = InMemoryStore::new();
+    store
+        .save_perm(
+            "alice",
+            &Permission::from_json(r#"{"post": {"view": true}}"#),
+        )
+        .unwrap();
+
+    assert_eq!(
+        *store.load_perm("alice").unwrap().unwrap().get_actions(),
+        HashSet::from([String::from("post:view")])
+    );
+    assert_eq!(store.load_perm("bob").unwrap().is_none(), true);
+
+    store
+        .save_perm("alice", &Permission::from_json("{}"))
+        .unwrap();
+    assert_eq!(store.load_perm("alice").unwrap().is_none(), true);
+    assert_eq!(store.subjects().unwrap().is_empty(), true);
+}
+
+#[test]
+fn roles_test() {
+    let mut store = InMemoryStore::new();
+    let roles = HashSet::from([String::from("editor"), String::from("viewer")]);
+    store.save_roles("bob", &roles).unwrap();
+    store
+        .save_perm("alice", &Permission::from_json(r#"{"view": true}"#))
+        .unwrap();
+
+    assert_eq!(store.load_roles("bob").unwrap(), roles);
+    assert_eq!(store.load_roles("alice").unwrap().is_empty(), true);
+    assert_eq!(
+        store.subjects().unwrap(),
+        vec![String::from("alice"), String::from("bob")]
+    );
+
+    store.remove_subject("bob").unwrap();
+    store.remove_subject("alice").unwrap();
+    assert_eq!(store, InMemoryStore::new());
+}
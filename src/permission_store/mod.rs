@@ -0,0 +1,149 @@
+#[cfg(feature = "async")]
+pub(crate) mod async_store;
+mod memory_store;
+#[cfg(feature = "postgres")]
+mod postgres_store;
+#[cfg(feature = "sled")]
+mod sled_store;
+#[cfg(feature = "sqlite")]
+mod sqlite_store;
+use crate::grant_store::GrantStore;
+use crate::permission::Permission;
+use crate::permission_manager::PermissionManager;
+use crate::role_manager::RoleManager;
+use std::collections::HashSet;
+use std::error::Error;
+use std::sync::Arc;
+
+pub use memory_store::InMemoryStore;
+#[cfg(all(feature = "postgres", feature = "async"))]
+pub use postgres_store::AsyncPostgresStore;
+#[cfg(feature = "postgres")]
+pub use postgres_store::PostgresStore;
+#[cfg(feature = "sled")]
+pub use sled_store::{SledStore, SledStoreError};
+#[cfg(feature = "sqlite")]
+pub use sqlite_store::SqliteStore;
+
+//// TESTS ////
+#[cfg(test)]
+mod tests;
+
+/// Persistence backend for the [`Permission`](crate::Permission)s and [`Role`](crate::Role) assignments of subjects, so storage can
+/// be plugged in instead of gluing it around raw JSON strings. [`InMemoryStore`](crate::InMemoryStore) is shipped in-crate.
+///
+/// Backends only store plain actions and role names keyed by subject: loaded [`Permission`](crate::Permission)s are unmanaged and
+/// must be validated against a [`PermissionManager`](crate::PermissionManager), as [`load_grants`](Self::load_grants()) does.
+/// Saving an empty [`Permission`](crate::Permission) or an empty set of roles removes it.
+///
+/// # Examples:
+///
+/// ```
+/// use std::sync::Arc;
+/// use simple_perm_manager::{GrantStore, InMemoryStore, PermissionManager, PermissionStore};
+///
+/// let manager = Arc::new(PermissionManager::from_json(r#"{"post": {"view": true, "edit": true}}"#));
+/// let mut grants: GrantStore<String> = GrantStore::new(manager.clone());
+/// grants.grant(String::from("alice"), &manager.perm_from_json(r#"{"post": {"edit": true}}"#));
+///
+/// let mut store = InMemoryStore::new();
+/// store.save_grants(&grants).unwrap();
+///
+/// let loaded = store.load_grants(manager).unwrap();
+/// assert!(loaded.check(&String::from("alice"), "post:edit"));
+/// ```
+pub trait PermissionStore {
+    /// Error returned by the backend operations.
+    type Error: Error;
+
+    /// Returns the [`Permission`](crate::Permission) stored for a subject, if any.
+    fn load_perm(&self, subject: &str) -> Result<Option<Permission>, Self::Error>;
+
+    /// Stores the actions of a [`Permission`](crate::Permission) for a subject, replacing the stored ones.
+    fn save_perm(&mut self, subject: &str, perm: &Permission) -> Result<(), Self::Error>;
+
+    /// Returns the names of the [`Role`](crate::Role)s stored for a subject, empty if none.
+    fn load_roles(&self, subject: &str) -> Result<HashSet<String>, Self::Error>;
+
+    /// Stores the names of the [`Role`](crate::Role)s assigned to a subject, replacing the stored ones.
+    fn save_roles(&mut self, subject: &str, roles: &HashSet<String>) -> Result<(), Self::Error>;
+
+    /// Returns the subjects with a stored [`Permission`](crate::Permission) or roles, sorted.
+    fn subjects(&self) -> Result<Vec<String>, Self::Error>;
+
+    /// Removes the [`Permission`](crate::Permission) and roles stored for a subject.
+    fn remove_subject(&mut self, subject: &str) -> Result<(), Self::Error> {
+        self.save_perm(subject, &Permission::from_actions(HashSet::new()))?;
+        self.save_roles(subject, &HashSet::new())
+    }
+
+    /// Stores the [`Permission`](crate::Permission)s granted directly to the subjects of a [`GrantStore`](crate::GrantStore),
+    /// removing the stored [`Permission`](crate::Permission)s of subjects without grants.  
+    /// Temporary and conditional grants and group memberships are not stored, use
+    /// [`GrantStore::to_json`](crate::GrantStore::to_json()) for them.
+    fn save_grants(&mut self, grants: &GrantStore<String>) -> Result<(), Self::Error> {
+        for subject in self.subjects()? {
+            if grants.grant_of(&subject).is_none() && self.load_perm(&subject)?.is_some() {
+                self.save_perm(&subject, &Permission::from_actions(HashSet::new()))?;
+            }
+        }
+        for subject in grants.subjects() {
+            if let Some(perm) = grants.grant_of(subject) {
+                self.save_perm(subject, perm)?;
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Returns a [`GrantStore`](crate::GrantStore) for the [`PermissionManager`](crate::PermissionManager) provided with the stored
+    /// [`Permission`](crate::Permission)s granted to their subjects.  
+    /// Stored actions are rewritten with [`PermissionManager::rewrite_perm`](crate::PermissionManager::rewrite_perm()), so renamed
+    /// actions are mapped to their current names and actions no longer in the universe are dropped.
+    fn load_grants<M>(&self, manager: M) -> Result<GrantStore<String>, Self::Error>
+    where
+        M: Into<Arc<PermissionManager>>,
+        Self: Sized,
+    {
+        let mut grants = GrantStore::new(manager);
+        for subject in self.subjects()? {
+            if let Some(perm) = self.load_perm(&subject)? {
+                let perm = grants.manager().rewrite_perm(&perm);
+                if !perm.is_empty() {
+                    grants.grant(subject, &perm);
+                }
+            }
+        }
+
+        Ok(grants)
+    }
+
+    /// Stores the names of the [`Role`](crate::Role)s assigned to the subjects of a [`RoleManager`](crate::RoleManager), removing the
+    /// stored roles of subjects without assignments.
+    fn save_assignments(&mut self, roles: &RoleManager) -> Result<(), Self::Error> {
+        for subject in self.subjects()? {
+            if roles.subject_roles(&subject).is_empty() && !self.load_roles(&subject)?.is_empty() {
+                self.save_roles(&subject, &HashSet::new())?;
+            }
+        }
+        for subject in roles.assigned_subjects() {
+            self.save_roles(subject, &roles.subject_roles(subject))?;
+        }
+
+        Ok(())
+    }
+
+    /// Assigns the stored [`Role`](crate::Role)s of every subject in the [`RoleManager`](crate::RoleManager) provided.  
+    /// Stored roles that do not exist in the [`RoleManager`](crate::RoleManager) are ignored.
+    fn load_assignments(&self, roles: &mut RoleManager) -> Result<(), Self::Error> {
+        for subject in self.subjects()? {
+            for role in self.load_roles(&subject)? {
+                if roles.role(&role).is_some() {
+                    roles.assign(&subject, &role);
+                }
+            }
+        }
+
+        Ok(())
+    }
+}
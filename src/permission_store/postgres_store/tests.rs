@@ -0,0 +1,38 @@
+use super::*;
+
+#[test]
+fn migrations_test() {
+    let versions: Vec<i64> = MIGRATIONS.iter().map(|(version, _)| *version).collect();
+    let mut sorted = versions.clone();
+    sorted.sort();
+    sorted.dedup();
+
+    assert_eq!(versions, sorted);
+    assert_eq!(versions[0], 1);
+    for table in ["spm_subjects", "spm_grants", "spm_roles", "spm_universes"] {
+        assert_eq!(
+            MIGRATIONS
+                .iter()
+                .any(|(_, sql)| sql.contains(&format!("CREATE TABLE {} ", table))),
+            true
+        );
+    }
+}
+
+#[test]
+fn connect_error_test() {
+    assert_eq!(PostgresStore::connect("not a url").is_err(), true);
+}
+
+#[cfg(feature = "async")]
+#[test]
+fn async_connect_error_test() {
+    let runtime = Builder::new_current_thread().enable_all().build().unwrap();
+
+    assert_eq!(
+        runtime
+            .block_on(AsyncPostgresStore::connect("not a url"))
+            .is_err(),
+        true
+    );
+}
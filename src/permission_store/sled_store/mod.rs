@@ -0,0 +1,239 @@
+use crate::permission::Permission;
+use crate::permission_manager::PermissionManager;
+use crate::permission_store::PermissionStore;
+use crate::validation_error::ValidationError;
+use sled::{Db, Tree};
+use std::collections::HashSet;
+use std::error::Error;
+use std::fmt;
+use std::path::Path;
+use std::sync::Arc;
+
+//// TESTS ////
+#[cfg(test)]
+mod tests;
+
+// Key of the universe actions the stored permissions are encoded with, in the default tree
+const UNIVERSE_KEY: &str = "universe";
+
+/// Error returned by the [`SledStore`](crate::SledStore) operations.
+#[derive(Debug)]
+pub enum SledStoreError {
+    /// The sled database operation failed.
+    Sled(sled::Error),
+    /// The [`Permission`](crate::Permission) to store or the stored universe is not valid.
+    Invalid(ValidationError),
+}
+
+impl fmt::Display for SledStoreError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            SledStoreError::Sled(error) => write!(f, "{}", error),
+            SledStoreError::Invalid(error) => write!(f, "{}", error),
+        }
+    }
+}
+
+impl Error for SledStoreError {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        match self {
+            SledStoreError::Sled(error) => Some(error),
+            SledStoreError::Invalid(error) => Some(error),
+        }
+    }
+}
+
+impl From<sled::Error> for SledStoreError {
+    fn from(error: sled::Error) -> SledStoreError {
+        SledStoreError::Sled(error)
+    }
+}
+
+impl From<ValidationError> for SledStoreError {
+    fn from(error: ValidationError) -> SledStoreError {
+        SledStoreError::Invalid(error)
+    }
+}
+
+/// [`PermissionStore`](crate::PermissionStore) over an embedded [sled](https://docs.rs/sled) database, available with the `sled`
+/// feature, for IoT and edge deployments.
+///
+/// [`Permission`](crate::Permission)s are stored bitset encoded with [`PermissionManager::encode_perm`](crate::PermissionManager::encode_perm())
+/// in the `perms` tree and role names as JSON arrays in the `roles` tree, both keyed by subject. Stored permissions are managed by
+/// the [`PermissionManager`](crate::PermissionManager) of the store. The universe they were encoded with is stored too, and opening
+/// the store with a different universe re-encodes them, dropping the actions no longer in the universe.
+///
+/// # Examples:
+///
+/// ```
+/// use simple_perm_manager::{PermissionManager, PermissionStore, SledStore};
+///
+/// let manager = PermissionManager::from_json(r#"{"sensor": {"read": true, "calibrate": true}}"#);
+/// let mut store = SledStore::temporary(manager).unwrap();
+///
+/// let read = store.manager().perm_from_json(r#"{"sensor": {"read": true}}"#);
+/// store.save_perm("device-1", &read).unwrap();
+///
+/// let loaded = store.load_perm("device-1").unwrap().unwrap();
+/// assert!(store.manager().validate_perm(&loaded));
+/// assert!(loaded.contains_action("sensor:read"));
+/// ```
+pub struct SledStore {
+    db: Db,
+    perms: Tree,
+    roles: Tree,
+    manager: Arc<PermissionManager>,
+}
+
+impl SledStore {
+    /// Opens or creates the sled database at the path provided for the [`PermissionManager`](crate::PermissionManager) provided,
+    /// re-encoding the stored [`Permission`](crate::Permission)s if they were encoded with a different universe.
+    ///
+    /// # Errors:
+    ///
+    /// - Returns [`SledStoreError::Sled`](crate::SledStoreError::Sled) if the database can not be opened or written.
+    /// - Returns [`SledStoreError::Invalid`](crate::SledStoreError::Invalid) if the stored universe is not valid.
+    pub fn open<P, M>(path: P, manager: M) -> Result<SledStore, SledStoreError>
+    where
+        P: AsRef<Path>,
+        M: Into<Arc<PermissionManager>>,
+    {
+        SledStore::from_db(sled::open(path)?, manager.into())
+    }
+
+    /// Same as [`open`](Self::open()) but with a new temporary database removed when the store is dropped, e.g. for tests.
+    ///
+    /// # Errors:
+    ///
+    /// Returns [`SledStoreError::Sled`](crate::SledStoreError::Sled) if the database can not be created.
+    pub fn temporary<M>(manager: M) -> Result<SledStore, SledStoreError>
+    where
+        M: Into<Arc<PermissionManager>>,
+    {
+        SledStore::from_db(sled::Config::new().temporary(true).open()?, manager.into())
+    }
+
+    // Opens the trees and re-encodes the stored permissions if the stored universe is not the one of the manager
+    fn from_db(db: Db, manager: Arc<PermissionManager>) -> Result<SledStore, SledStoreError> {
+        let store = SledStore {
+            perms: db.open_tree("perms")?,
+            roles: db.open_tree("roles")?,
+            db,
+            manager,
+        };
+
+        let universe = store.manager.to_actions();
+        let stored: Option<HashSet<String>> = match store.db.get(UNIVERSE_KEY)? {
+            Some(bytes) => Some(serde_json::from_slice(&bytes).map_err(|_| {
+                ValidationError::InvalidJson(String::from(
+                    "wrong format in stored universe of sled store",
+                ))
+            })?),
+            None => None,
+        };
+        if stored.as_ref() != Some(&universe) {
+            if let Some(stored) = stored {
+                let previous = PermissionManager::try_from_actions(stored)?;
+                for entry in store.perms.iter() {
+                    let (subject, bytes) = entry?;
+                    let actions = previous.decode_perm(&bytes).get_actions().into_owned();
+                    let perm = store.manager.clean_perm(&Permission::from_actions(actions));
+                    store
+                        .perms
+                        .insert(subject, store.manager.encode_perm(&perm))?;
+                }
+            }
+            let mut sorted: Vec<&String> = universe.iter().collect();
+            sorted.sort();
+            store.db.insert(
+                UNIVERSE_KEY,
+                serde_json::to_vec(&sorted).expect("actions should always serialize to JSON"),
+            )?;
+        }
+
+        Ok(store)
+    }
+
+    /// Returns the [`PermissionManager`](crate::PermissionManager) the [`Permission`](crate::Permission)s are encoded with.
+    pub fn manager(&self) -> &PermissionManager {
+        &self.manager
+    }
+
+    /// Flushes the pending writes to disk. Returns the number of bytes flushed.
+    ///
+    /// # Errors:
+    ///
+    /// Returns [`SledStoreError::Sled`](crate::SledStoreError::Sled) if the writes can not be flushed.
+    pub fn flush(&self) -> Result<usize, SledStoreError> {
+        Ok(self.db.flush()?)
+    }
+}
+
+impl PermissionStore for SledStore {
+    type Error = SledStoreError;
+
+    fn load_perm(&self, subject: &str) -> Result<Option<Permission>, SledStoreError> {
+        Ok(self
+            .perms
+            .get(subject)?
+            .map(|bytes| self.manager.decode_perm(&bytes)))
+    }
+
+    /// Stores the actions of a [`Permission`](crate::Permission) for a subject bitset encoded, replacing the stored ones.
+    ///
+    /// # Errors:
+    ///
+    /// - Returns [`SledStoreError::Sled`](crate::SledStoreError::Sled) if the database can not be written.
+    /// - Returns [`SledStoreError::Invalid`](crate::SledStoreError::Invalid) if any action is not in the universe of the
+    ///   [`PermissionManager`](crate::PermissionManager) of the store.
+    fn save_perm(&mut self, subject: &str, perm: &Permission) -> Result<(), SledStoreError> {
+        if perm.is_empty() {
+            self.perms.remove(subject)?;
+        } else {
+            let perm = self
+                .manager
+                .try_perm_from_actions(perm.get_actions().into_owned())?;
+            self.perms
+                .insert(subject, self.manager.encode_perm(&perm))?;
+        }
+
+        Ok(())
+    }
+
+    fn load_roles(&self, subject: &str) -> Result<HashSet<String>, SledStoreError> {
+        match self.roles.get(subject)? {
+            Some(bytes) => serde_json::from_slice(&bytes).map_err(|_| {
+                SledStoreError::Invalid(ValidationError::InvalidJson(String::from(
+                    "wrong format in stored roles of sled store",
+                )))
+            }),
+            None => Ok(HashSet::new()),
+        }
+    }
+
+    fn save_roles(&mut self, subject: &str, roles: &HashSet<String>) -> Result<(), SledStoreError> {
+        if roles.is_empty() {
+            self.roles.remove(subject)?;
+        } else {
+            let mut sorted: Vec<&String> = roles.iter().collect();
+            sorted.sort();
+            self.roles.insert(
+                subject,
+                serde_json::to_vec(&sorted).expect("roles should always serialize to JSON"),
+            )?;
+        }
+
+        Ok(())
+    }
+
+    fn subjects(&self) -> Result<Vec<String>, SledStoreError> {
+        let mut subjects = Vec::new();
+        for key in self.perms.iter().keys().chain(self.roles.iter().keys()) {
+            subjects.push(String::from_utf8_lossy(&key?).into_owned());
+        }
+        subjects.sort();
+        subjects.dedup();
+
+        Ok(subjects)
+    }
+}
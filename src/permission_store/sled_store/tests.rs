@@ -0,0 +1,89 @@
+use super::*;
+
+fn manager() -> PermissionManager {
+    PermissionManager::from_json(r#"{"sensor": {"read": true, "calibrate": true}}"#)
+}
+
+#[test]
+fn perm_test() {
+    let mut store = SledStore::temporary(manager()).unwrap();
+    store
+        .save_perm(
+            "device-1",
+            &Permission::from_json(r#"{"sensor": {"read": true}}"#),
+        )
+        .unwrap();
+
+    let loaded = store.load_perm("device-1").unwrap().unwrap();
+    assert_eq!(store.manager().validate_perm(&loaded), true);
+    assert_eq!(
+        *loaded.get_actions(),
+        HashSet::from([String::from("sensor:read")])
+    );
+    assert_eq!(
+        store.perms.get("device-1").unwrap().unwrap().to_vec(),
+        store.manager().encode_perm(&loaded)
+    );
+
+    match store.save_perm("device-2", &Permission::from_json(r#"{"reboot": true}"#)) {
+        Err(SledStoreError::Invalid(ValidationError::UnknownActions(_))) => (),
+        _ => panic!("storing unknown actions should have failed"),
+    }
+
+    store
+        .save_perm("device-1", &Permission::from_json("{}"))
+        .unwrap();
+    assert_eq!(store.load_perm("device-1").unwrap().is_none(), true);
+}
+
+#[test]
+fn roles_test() {
+    let mut store = SledStore::temporary(manager()).unwrap();
+    let roles = HashSet::from([String::from("reader"), String::from("technician")]);
+    store.save_roles("device-1", &roles).unwrap();
+    store
+        .save_perm("device-2", &Permission::from_json(r#"{"sensor": true}"#))
+        .unwrap();
+
+    assert_eq!(store.load_roles("device-1").unwrap(), roles);
+    assert_eq!(
+        store.subjects().unwrap(),
+        vec![String::from("device-1"), String::from("device-2")]
+    );
+
+    store.remove_subject("device-1").unwrap();
+    assert_eq!(store.subjects().unwrap(), vec![String::from("device-2")]);
+}
+
+#[test]
+fn universe_change_test() {
+    let path = std::env::temp_dir().join(format!("spm-sled-store-{}", uuid::Uuid::new_v4()));
+    let mut store = SledStore::open(&path, manager()).unwrap();
+    store
+        .save_perm(
+            "device-1",
+            &Permission::from_json(r#"{"sensor": {"read": true, "calibrate": true}}"#),
+        )
+        .unwrap();
+    store.flush().unwrap();
+    drop(store);
+
+    let manager =
+        PermissionManager::from_json(r#"{"sensor": {"read": true, "reset": true}, "a": true}"#);
+    let store = SledStore::open(&path, manager).unwrap();
+    assert_eq!(
+        *store.load_perm("device-1").unwrap().unwrap().get_actions(),
+        HashSet::from([String::from("sensor:read")])
+    );
+
+    drop(store);
+    std::fs::remove_dir_all(path).unwrap();
+}
+
+#[test]
+fn error_test() {
+    let error = SledStoreError::from(ValidationError::InvalidJson(String::from("wrong")));
+
+    assert_eq!(error.to_string(), "wrong");
+    assert_eq!(error.source().is_some(), true);
+}
@@ -0,0 +1,240 @@
+use crate::permission::Permission;
+use crate::permission_manager::PermissionManager;
+use crate::permission_store::PermissionStore;
+use rusqlite::types::Type;
+use rusqlite::{params, Connection, OptionalExtension};
+use std::collections::HashSet;
+use std::path::Path;
+
+//// TESTS ////
+#[cfg(test)]
+mod tests;
+
+// Schema migrations applied in order when opening, the number of migrations applied is kept in `PRAGMA user_version`
+const MIGRATIONS: &[&str] = &["CREATE TABLE spm_subjects (
+        subject TEXT PRIMARY KEY
+    );
+    CREATE TABLE spm_grants (
+        subject TEXT NOT NULL REFERENCES spm_subjects ON DELETE CASCADE,
+        action TEXT NOT NULL,
+        PRIMARY KEY (subject, action)
+    );
+    CREATE TABLE spm_roles (
+        subject TEXT NOT NULL REFERENCES spm_subjects ON DELETE CASCADE,
+        role TEXT NOT NULL,
+        PRIMARY KEY (subject, role)
+    );
+    CREATE TABLE spm_universes (
+        name TEXT PRIMARY KEY,
+        version INTEGER NOT NULL,
+        actions TEXT NOT NULL,
+        updated_at TEXT NOT NULL DEFAULT CURRENT_TIMESTAMP
+    );"];
+
+/// [`PermissionStore`](crate::PermissionStore) over an embedded SQLite database, available with the `sqlite` feature, for
+/// desktop and embedded applications that need persistent local permission state without a database server.
+///
+/// The store creates and migrates the same `spm_subjects`, `spm_grants`, `spm_roles` and `spm_universes` tables as the
+/// `PostgresStore` of the `postgres` feature when opening the
+/// database, keeping the schema version in `PRAGMA user_version`. Universe actions are stored as JSON text.
+///
+/// # Examples:
+///
+/// ```
+/// use simple_perm_manager::{Permission, PermissionManager, PermissionStore, SqliteStore};
+///
+/// let mut store = SqliteStore::open_in_memory().unwrap();
+/// store.save_perm("alice", &Permission::from_json(r#"{"post": {"view": true}}"#)).unwrap();
+/// store.save_universe("posts", &PermissionManager::from_json(r#"{"post": {"view": true, "edit": true}}"#)).unwrap();
+///
+/// let manager = store.load_universe("posts").unwrap().unwrap();
+/// let grants = store.load_grants(manager).unwrap();
+/// assert!(grants.check(&String::from("alice"), "post:view"));
+/// ```
+pub struct SqliteStore {
+    connection: Connection,
+}
+
+impl SqliteStore {
+    /// Opens or creates the SQLite database at the path provided and applies the pending schema migrations.
+    ///
+    /// # Errors:
+    ///
+    /// Returns a [`rusqlite::Error`](rusqlite::Error) if the database can not be opened or a migration can not be applied.
+    pub fn open<P: AsRef<Path>>(path: P) -> Result<SqliteStore, rusqlite::Error> {
+        SqliteStore::from_connection(Connection::open(path)?)
+    }
+
+    /// Same as [`open`](Self::open()) but with a new in-memory database, e.g. for tests.
+    ///
+    /// # Errors:
+    ///
+    /// Returns a [`rusqlite::Error`](rusqlite::Error) if a migration can not be applied.
+    pub fn open_in_memory() -> Result<SqliteStore, rusqlite::Error> {
+        SqliteStore::from_connection(Connection::open_in_memory()?)
+    }
+
+    // Enables foreign keys and applies the migrations after the ones already applied, each one in its own transaction
+    fn from_connection(mut connection: Connection) -> Result<SqliteStore, rusqlite::Error> {
+        connection.pragma_update(None, "foreign_keys", true)?;
+        let applied: usize =
+            connection.pragma_query_value(None, "user_version", |row| row.get(0))?;
+        for (position, sql) in MIGRATIONS.iter().enumerate().skip(applied) {
+            let transaction = connection.transaction()?;
+            transaction.execute_batch(sql)?;
+            transaction.pragma_update(None, "user_version", position + 1)?;
+            transaction.commit()?;
+        }
+
+        Ok(SqliteStore { connection })
+    }
+
+    /// Returns the connection to the database of the [`SqliteStore`](crate::SqliteStore).
+    pub fn connection(&self) -> &Connection {
+        &self.connection
+    }
+
+    /// Stores the universe and version of a [`PermissionManager`](crate::PermissionManager) under the name provided, replacing the
+    /// stored one.
+    ///
+    /// # Errors:
+    ///
+    /// Returns a [`rusqlite::Error`](rusqlite::Error) if the query fails.
+    pub fn save_universe(
+        &mut self,
+        name: &str,
+        manager: &PermissionManager,
+    ) -> Result<(), rusqlite::Error> {
+        self.connection.execute(
+            "INSERT INTO spm_universes (name, version, actions) VALUES (?1, ?2, ?3)
+             ON CONFLICT (name) DO UPDATE SET version = ?2, actions = ?3, updated_at = CURRENT_TIMESTAMP",
+            params![name, manager.version(), manager.to_json()],
+        )?;
+
+        Ok(())
+    }
+
+    /// Returns a new [`PermissionManager`](crate::PermissionManager) with the universe and version stored under the name provided, if any.
+    ///
+    /// # Errors:
+    ///
+    /// Returns a [`rusqlite::Error`](rusqlite::Error) if the query fails, or
+    /// [`rusqlite::Error::FromSqlConversionFailure`](rusqlite::Error::FromSqlConversionFailure) with the
+    /// [`ValidationError`](crate::ValidationError) if the stored universe is not valid.
+    pub fn load_universe(&self, name: &str) -> Result<Option<PermissionManager>, rusqlite::Error> {
+        self.connection
+            .query_row(
+                "SELECT version, actions FROM spm_universes WHERE name = ?1",
+                params![name],
+                |row| {
+                    let version: u32 = row.get(0)?;
+                    let actions: String = row.get(1)?;
+                    let mut manager =
+                        PermissionManager::try_from_json(&actions).map_err(|error| {
+                            rusqlite::Error::FromSqlConversionFailure(
+                                1,
+                                Type::Text,
+                                Box::new(error),
+                            )
+                        })?;
+                    manager.set_version(version);
+
+                    Ok(manager)
+                },
+            )
+            .optional()
+    }
+
+    // Replaces the values of a subject in the table (`spm_grants` or `spm_roles`) and column provided, keeping the subject row
+    // only while it has grants or roles
+    fn replace(
+        &mut self,
+        table: &str,
+        column: &str,
+        subject: &str,
+        values: &HashSet<String>,
+    ) -> Result<(), rusqlite::Error> {
+        let transaction = self.connection.transaction()?;
+        transaction.execute(
+            &format!("DELETE FROM {} WHERE subject = ?1", table),
+            params![subject],
+        )?;
+        if values.is_empty() {
+            transaction.execute(
+                "DELETE FROM spm_subjects WHERE subject = ?1
+                 AND NOT EXISTS (SELECT 1 FROM spm_grants WHERE subject = ?1)
+                 AND NOT EXISTS (SELECT 1 FROM spm_roles WHERE subject = ?1)",
+                params![subject],
+            )?;
+        } else {
+            transaction.execute(
+                "INSERT INTO spm_subjects (subject) VALUES (?1) ON CONFLICT DO NOTHING",
+                params![subject],
+            )?;
+            let mut insert = transaction.prepare(&format!(
+                "INSERT INTO {} (subject, {}) VALUES (?1, ?2)",
+                table, column
+            ))?;
+            for value in values {
+                insert.execute(params![subject, value])?;
+            }
+            drop(insert);
+        }
+
+        transaction.commit()
+    }
+
+    // Returns the values of a subject in the table and column provided
+    fn values(
+        &self,
+        table: &str,
+        column: &str,
+        subject: &str,
+    ) -> Result<HashSet<String>, rusqlite::Error> {
+        let mut select = self.connection.prepare(&format!(
+            "SELECT {} FROM {} WHERE subject = ?1",
+            column, table
+        ))?;
+        let values = select.query_map(params![subject], |row| row.get(0))?;
+
+        values.collect()
+    }
+}
+
+impl PermissionStore for SqliteStore {
+    type Error = rusqlite::Error;
+
+    fn load_perm(&self, subject: &str) -> Result<Option<Permission>, rusqlite::Error> {
+        let actions = self.values("spm_grants", "action", subject)?;
+
+        Ok((!actions.is_empty()).then(|| Permission::from_actions(actions)))
+    }
+
+    fn save_perm(&mut self, subject: &str, perm: &Permission) -> Result<(), rusqlite::Error> {
+        self.replace("spm_grants", "action", subject, &perm.get_actions())
+    }
+
+    fn load_roles(&self, subject: &str) -> Result<HashSet<String>, rusqlite::Error> {
+        self.values("spm_roles", "role", subject)
+    }
+
+    fn save_roles(
+        &mut self,
+        subject: &str,
+        roles: &HashSet<String>,
+    ) -> Result<(), rusqlite::Error> {
+        self.replace("spm_roles", "role", subject, roles)
+    }
+
+    fn subjects(&self) -> Result<Vec<String>, rusqlite::Error> {
+        let mut select = self
+            .connection
+            .prepare("SELECT subject FROM spm_subjects")?;
+        let mut subjects = select
+            .query_map([], |row| row.get(0))?
+            .collect::<Result<Vec<String>, rusqlite::Error>>()?;
+        subjects.sort();
+
+        Ok(subjects)
+    }
+}
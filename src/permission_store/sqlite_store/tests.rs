@@ -0,0 +1,102 @@
+use super::*;
+
+#[test]
+fn perm_and_roles_test() {
+    let mut store = SqliteStore::open_in_memory().unwrap();
+    store
+        .save_perm(
+            "alice",
+            &Permission::from_json(r#"{"post": {"view": true, "edit": true}}"#),
+        )
+        .unwrap();
+    store
+        .save_roles("bob", &HashSet::from([String::from("editor")]))
+        .unwrap();
+
+    assert_eq!(
+        *store.load_perm("alice").unwrap().unwrap().get_actions(),
+        HashSet::from([String::from("post:view"), String::from("post:edit")])
+    );
+    assert_eq!(store.load_perm("bob").unwrap().is_none(), true);
+    assert_eq!(
+        store.load_roles("bob").unwrap(),
+        HashSet::from([String::from("editor")])
+    );
+    assert_eq!(
+        store.subjects().unwrap(),
+        vec![String::from("alice"), String::from("bob")]
+    );
+
+    store
+        .save_perm(
+            "alice",
+            &Permission::from_json(r#"{"post": {"view": true}}"#),
+        )
+        .unwrap();
+    assert_eq!(
+        store
+            .load_perm("alice")
+            .unwrap()
+            .unwrap()
+            .get_actions()
+            .len(),
+        1
+    );
+
+    store.remove_subject("alice").unwrap();
+    store.remove_subject("bob").unwrap();
+    assert_eq!(store.subjects().unwrap().is_empty(), true);
+    let rows: usize = store
+        .connection()
+        .query_row("SELECT COUNT(*) FROM spm_grants", [], |row| row.get(0))
+        .unwrap();
+    assert_eq!(rows, 0);
+}
+
+#[test]
+fn universe_test() {
+    let mut store = SqliteStore::open_in_memory().unwrap();
+    let mut manager = PermissionManager::from_json(r#"{"post": {"view": true}}"#);
+    manager.set_version(4);
+    store.save_universe("posts", &manager).unwrap();
+    store.save_universe("posts", &manager).unwrap();
+
+    let loaded = store.load_universe("posts").unwrap().unwrap();
+    assert_eq!(loaded.to_json(), manager.to_json());
+    assert_eq!(loaded.version(), 4);
+    assert_eq!(store.load_universe("users").unwrap().is_none(), true);
+
+    store
+        .connection()
+        .execute("UPDATE spm_universes SET actions = '[]'", [])
+        .unwrap();
+    assert_eq!(
+        matches!(
+            store.load_universe("posts"),
+            Err(rusqlite::Error::FromSqlConversionFailure(..))
+        ),
+        true
+    );
+}
+
+#[test]
+fn open_test() {
+    let path = std::env::temp_dir().join(format!("spm-sqlite-store-{}.db", uuid::Uuid::new_v4()));
+    let mut store = SqliteStore::open(&path).unwrap();
+    store
+        .save_perm("alice", &Permission::from_json(r#"{"view": true}"#))
+        .unwrap();
+    drop(store);
+
+    // Reopening does not apply the migrations again
+    let store = SqliteStore::open(&path).unwrap();
+    assert_eq!(store.subjects().unwrap(), vec![String::from("alice")]);
+    let version: usize = store
+        .connection()
+        .pragma_query_value(None, "user_version", |row| row.get(0))
+        .unwrap();
+    assert_eq!(version, MIGRATIONS.len());
+
+    drop(store);
+    std::fs::remove_file(path).unwrap();
+}
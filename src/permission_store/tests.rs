@@ -0,0 +1,100 @@
+use super::*;
+
+fn manager() -> Arc<PermissionManager> {
+    Arc::new(PermissionManager::from_json(
+        r#"{"post": {"view": true, "edit": true, "delete": true}}"#,
+    ))
+}
+
+#[test]
+fn grants_test() {
+    let manager = manager();
+    let mut grants: GrantStore<String> = GrantStore::new(manager.clone());
+    grants.grant(
+        String::from("alice"),
+        &manager.perm_from_json(r#"{"post": {"view": true, "edit": true}}"#),
+    );
+    grants.grant(
+        String::from("bob"),
+        &manager.perm_from_json(r#"{"post": {"view": true}}"#),
+    );
+
+    let mut store = InMemoryStore::new();
+    store.save_grants(&grants).unwrap();
+    let loaded = store.load_grants(manager.clone()).unwrap();
+    assert_eq!(loaded.check(&String::from("alice"), "post:edit"), true);
+    assert_eq!(loaded.check(&String::from("bob"), "post:view"), true);
+    assert_eq!(loaded.check(&String::from("bob"), "post:edit"), false);
+
+    grants.revoke_all(&String::from("bob"));
+    store.save_grants(&grants).unwrap();
+    assert_eq!(store.subjects().unwrap(), vec![String::from("alice")]);
+}
+
+#[test]
+fn load_grants_rewrite_test() {
+    let mut store = InMemoryStore::new();
+    store
+        .save_perm(
+            "alice",
+            &Permission::from_json(r#"{"post": {"remove": true, "archive": true}}"#),
+        )
+        .unwrap();
+    store
+        .save_perm(
+            "bob",
+            &Permission::from_json(r#"{"post": {"archive": true}}"#),
+        )
+        .unwrap();
+
+    let mut manager = PermissionManager::from_json(r#"{"post": {"view": true, "remove": true}}"#);
+    manager.rename_action("post:remove", "post:delete");
+    let grants = store.load_grants(manager).unwrap();
+
+    assert_eq!(
+        *grants.effective_perm(&String::from("alice")).get_actions(),
+        HashSet::from([String::from("post:delete")])
+    );
+    assert_eq!(grants.subjects().count(), 1);
+}
+
+#[test]
+fn assignments_test() {
+    let manager = manager();
+    let mut roles = RoleManager::new(manager.clone());
+    roles.add_role(
+        "viewer",
+        manager.perm_from_json(r#"{"post": {"view": true}}"#),
+    );
+    roles.add_role(
+        "editor",
+        manager.perm_from_json(r#"{"post": {"edit": true}}"#),
+    );
+    roles.assign("alice", "viewer");
+    roles.assign("alice", "editor");
+    roles.assign("bob", "viewer");
+
+    let mut store = InMemoryStore::new();
+    store.save_assignments(&roles).unwrap();
+    store
+        .save_roles("carol", &HashSet::from([String::from("admin")]))
+        .unwrap();
+
+    let mut loaded = RoleManager::new(manager.clone());
+    loaded.add_role(
+        "viewer",
+        manager.perm_from_json(r#"{"post": {"view": true}}"#),
+    );
+    loaded.add_role(
+        "editor",
+        manager.perm_from_json(r#"{"post": {"edit": true}}"#),
+    );
+    store.load_assignments(&mut loaded).unwrap();
+    assert_eq!(loaded.subject_roles("alice"), roles.subject_roles("alice"));
+    assert_eq!(loaded.subject_roles("bob"), roles.subject_roles("bob"));
+    assert_eq!(loaded.subject_roles("carol").is_empty(), true);
+
+    roles.unassign("bob", "viewer");
+    store.save_assignments(&roles).unwrap();
+    assert_eq!(store.load_roles("bob").unwrap().is_empty(), true);
+}
@@ -0,0 +1,180 @@
+use crate::permission::Permission;
+use crate::permission_manager::PermissionManager;
+use crate::telemetry;
+use crate::validation_error::ValidationError;
+use crate::validation_report::close_matches;
+use std::collections::{HashMap, HashSet};
+
+//// TESTS ////
+#[cfg(test)]
+mod tests;
+
+/// Validated bridge translating [`Permission`](crate::Permission)s of a source [`PermissionManager`](crate::PermissionManager) into
+/// [`Permission`](crate::Permission)s of a target one, following a mapping between their action names, e.g. between federated systems.
+///
+/// Every action of a translated [`Permission`](crate::Permission) must be mapped, and an action can be mapped to several target
+/// actions when it was split. Translated [`Permission`](crate::Permission)s are normalized by the target manager as in
+/// [`PermissionManager::perm_from_actions`](crate::PermissionManager::perm_from_actions()).
+///
+/// # Examples:
+///
+/// ```
+/// use simple_perm_manager::{PermissionManager, PermissionTranslator, ValidationError};
+///
+/// let legacy = PermissionManager::from_json(r#"{"docs": {"read": true, "write": true, "purge": true}}"#);
+/// let current = PermissionManager::from_json(r#"{"document": {"view": true, "edit": true}}"#);
+///
+/// let translator = PermissionTranslator::new(
+///     &legacy,
+///     &current,
+///     [("docs:read", "document:view"), ("docs:write", "document:edit")],
+/// ).unwrap();
+///
+/// let perm = translator.translate(&legacy.perm_from_json(r#"{"docs": {"write": true}}"#)).unwrap();
+/// assert!(current.validate_perm(&perm));
+/// assert!(perm.contains_action("document:edit"));
+///
+/// assert_eq!(
+///     translator.translate(&legacy.perm_from_json(r#"{"docs": {"purge": true}}"#)).unwrap_err(),
+///     ValidationError::UntranslatableActions(vec![String::from("docs:purge")])
+/// );
+/// ```
+#[derive(Debug)]
+pub struct PermissionTranslator<'a> {
+    source: &'a PermissionManager,
+    target: &'a PermissionManager,
+    // Source actions mapped to the target actions they translate to
+    mapping: HashMap<String, HashSet<String>>,
+}
+
+impl<'a> PermissionTranslator<'a> {
+    /// Creates a [`PermissionTranslator`](crate::PermissionTranslator) from pairs of source and target actions. Pairs with the same
+    /// source action map it to every target action provided.
+    ///
+    /// # Errors:
+    ///
+    /// Returns [`ValidationError::UnknownActions`](crate::ValidationError::UnknownActions) listing the source actions that are not
+    /// in the source universe and the target actions that are not in the target universe.
+    pub fn new<I, S, T>(
+        source: &'a PermissionManager,
+        target: &'a PermissionManager,
+        mapping: I,
+    ) -> Result<PermissionTranslator<'a>, ValidationError>
+    where
+        I: IntoIterator<Item = (S, T)>,
+        S: AsRef<str>,
+        T: AsRef<str>,
+    {
+        let mut translator = PermissionTranslator {
+            source,
+            target,
+            mapping: HashMap::new(),
+        };
+        for (from, to) in mapping {
+            translator
+                .mapping
+                .entry(from.as_ref().to_string())
+                .or_default()
+                .insert(to.as_ref().to_string());
+        }
+
+        let source_universe = source.get_universe();
+        let target_universe = target.get_universe();
+        let mut unknown: Vec<(String, Vec<String>)> = translator
+            .mapping
+            .keys()
+            .filter(|action| !source_universe.has_action(action))
+            .map(|action| {
+                let suggestions = close_matches(action, source_universe.actions());
+                (action.clone(), suggestions)
+            })
+            .chain(
+                translator
+                    .mapping
+                    .values()
+                    .flatten()
+                    .collect::<HashSet<&String>>()
+                    .into_iter()
+                    .filter(|action| !target_universe.has_action(action))
+                    .map(|action| {
+                        let suggestions = close_matches(action, target_universe.actions());
+                        (action.clone(), suggestions)
+                    }),
+            )
+            .collect();
+        if !unknown.is_empty() {
+            unknown.sort();
+            telemetry::record_validation_failure();
+            return Err(ValidationError::UnknownActions(unknown));
+        }
+
+        Ok(translator)
+    }
+
+    /// Returns the [`PermissionTranslator`](crate::PermissionTranslator) also mapping every source action that is not mapped yet
+    /// to the target action with the same name, if any.
+    ///
+    /// # Examples:
+    ///
+    /// ```
+    /// use simple_perm_manager::{PermissionManager, PermissionTranslator};
+    ///
+    /// let source = PermissionManager::from_json(r#"{"view": true, "remove": true}"#);
+    /// let target = PermissionManager::from_json(r#"{"view": true, "delete": true}"#);
+    ///
+    /// let translator = PermissionTranslator::new(&source, &target, [("remove", "delete")]).unwrap().with_identical();
+    ///
+    /// assert_eq!(translator.translate(&source.get_universe()).unwrap().to_json(), r#"{"delete":true,"view":true}"#);
+    /// ```
+    pub fn with_identical(mut self) -> PermissionTranslator<'a> {
+        let target_universe = self.target.get_universe();
+        for action in self.source.get_universe().actions() {
+            if !self.mapping.contains_key(action) && target_universe.has_action(action) {
+                self.mapping
+                    .insert(action.to_string(), HashSet::from([action.to_string()]));
+            }
+        }
+        self
+    }
+
+    /// Returns the target actions a source action translates to, or `None` if the action is not mapped.
+    pub fn translate_action(&self, action: &str) -> Option<&HashSet<String>> {
+        self.mapping.get(action)
+    }
+
+    /// Translates a [`Permission`](crate::Permission) of the source [`PermissionManager`](crate::PermissionManager), or an unmanaged
+    /// one, into a [`Permission`](crate::Permission) managed by the target [`PermissionManager`](crate::PermissionManager).
+    ///
+    /// # Errors:
+    ///
+    /// Returns [`ValidationError::UntranslatableActions`](crate::ValidationError::UntranslatableActions) listing the actions of the
+    /// [`Permission`](crate::Permission) that are not mapped.
+    ///
+    /// # Panics:
+    ///
+    /// Panics if the [`Permission`](crate::Permission) is managed by a [`PermissionManager`](crate::PermissionManager) other than
+    /// the source one.
+    pub fn translate(&self, perm: &Permission) -> Result<Permission, ValidationError> {
+        if perm.is_managed() && perm.manager_id() != Some(self.source.id()) {
+            panic!("Permission to translate is not managed by the source manager");
+        }
+
+        let mut untranslatable: Vec<String> = perm
+            .actions()
+            .filter(|action| !self.mapping.contains_key(*action))
+            .map(String::from)
+            .collect();
+        if !untranslatable.is_empty() {
+            untranslatable.sort();
+            telemetry::record_validation_failure();
+            return Err(ValidationError::UntranslatableActions(untranslatable));
+        }
+
+        self.target.try_perm_from_actions(
+            perm.actions()
+                .flat_map(|action| &self.mapping[action])
+                .cloned()
+                .collect(),
+        )
+    }
+}
@@ -0,0 +1,119 @@
+use super::*;
+
+fn managers() -> (PermissionManager, PermissionManager) {
+    (
+        PermissionManager::from_json(
+            r#"{"docs": {"read": true, "write": true, "admin": true}, "view": true}"#,
+        ),
+        PermissionManager::from_json(
+            r#"{"document": {"view": true, "edit": true, "share": true}, "view": true}"#,
+        ),
+    )
+}
+
+#[test]
+fn new_test() {
+    let (source, target) = managers();
+
+    let translator = PermissionTranslator::new(
+        &source,
+        &target,
+        vec![
+            (String::from("docs:admin"), String::from("document:edit")),
+            (String::from("docs:admin"), String::from("document:share")),
+        ],
+    )
+    .unwrap();
+    assert_eq!(
+        translator.translate_action("docs:admin"),
+        Some(&HashSet::from([
+            String::from("document:edit"),
+            String::from("document:share")
+        ]))
+    );
+    assert_eq!(translator.translate_action("docs:read"), None);
+
+    assert_eq!(
+        PermissionTranslator::new(
+            &source,
+            &target,
+            [
+                ("docs:raed", "document:view"),
+                ("docs:read", "document:veiw")
+            ]
+        )
+        .unwrap_err(),
+        ValidationError::UnknownActions(vec![
+            (String::from("docs:raed"), vec![String::from("docs:read")]),
+            (
+                String::from("document:veiw"),
+                vec![String::from("document:view")]
+            ),
+        ])
+    );
+}
+
+#[test]
+fn translate_test() {
+    let (source, target) = managers();
+    let translator = PermissionTranslator::new(
+        &source,
+        &target,
+        [
+            ("docs:read", "document:view"),
+            ("docs:admin", "document:edit"),
+            ("docs:admin", "document:share"),
+        ],
+    )
+    .unwrap();
+
+    let perm = translator
+        .translate(&source.perm_from_json(r#"{"docs": {"read": true, "admin": true}}"#))
+        .unwrap();
+    assert_eq!(target.validate_perm(&perm), true);
+    assert_eq!(
+        *perm.get_actions(),
+        HashSet::from([
+            String::from("document:view"),
+            String::from("document:edit"),
+            String::from("document:share")
+        ])
+    );
+
+    let unmanaged = Permission::from_actions(HashSet::from([String::from("docs:read")]));
+    assert_eq!(
+        translator
+            .translate(&unmanaged)
+            .unwrap()
+            .get_actions()
+            .len(),
+        1
+    );
+    assert_eq!(
+        translator.translate(&source.get_universe()).unwrap_err(),
+        ValidationError::UntranslatableActions(vec![
+            String::from("docs:write"),
+            String::from("view")
+        ])
+    );
+    let result = std::panic::catch_unwind(|| translator.translate(&target.get_universe()));
+    assert_eq!(result.is_err(), true);
+}
+
+#[test]
+fn with_identical_test() {
+    let (source, target) = managers();
+    let translator = PermissionTranslator::new(&source, &target, [("docs:read", "view")])
+        .unwrap()
+        .with_identical();
+
+    assert_eq!(
+        translator.translate_action("view"),
+        Some(&HashSet::from([String::from("view")]))
+    );
+    assert_eq!(
+        translator.translate_action("docs:read"),
+        Some(&HashSet::from([String::from("view")]))
+    );
+    assert_eq!(translator.translate_action("docs:write"), None);
+}
@@ -0,0 +1,191 @@
+use crate::permission::{
+    parse_policy_actions, serialize_policy_actions, Permission, ACTION_DIVIDER,
+};
+use serde_json::Value;
+
+//// TESTS ////
+#[cfg(test)]
+mod tests;
+
+/// Algorithm used by a [`PermissionManager`](crate::PermissionManager) to combine the [`Policy`](crate::Policy)s of a subject
+/// into the actions it is allowed, mirroring XACML combining algorithms.  
+/// Set it with [`PermissionManager::set_combining_algorithm`](crate::PermissionManager::set_combining_algorithm()) and
+/// combine [`Policy`](crate::Policy)s with [`PermissionManager::combine_policies`](crate::PermissionManager::combine_policies()).
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+pub enum CombiningAlgorithm {
+    /// An action is allowed if some [`Policy`](crate::Policy) allows it and no [`Policy`](crate::Policy) denies it.
+    #[default]
+    DenyOverrides,
+    /// An action is allowed if some [`Policy`](crate::Policy) allows it, even if other [`Policy`](crate::Policy)s deny it.
+    AllowOverrides,
+    /// An action is decided by the first [`Policy`](crate::Policy), in the order provided, that allows or denies it. A
+    /// [`Policy`](crate::Policy) that both allows and denies an action denies it.
+    FirstApplicable,
+}
+
+/// Set of allowed and denied actions, where denials subtract from allows during evaluation.
+///
+/// A [`Policy`](crate::Policy) is made of two [`Permission`](crate::Permission)s with the same manager: the allowed actions and the denied ones.
+/// An action is allowed by the [`Policy`](crate::Policy) if it is allowed and not denied, so combining [`Policy`](crate::Policy)s
+/// with [`union`](Self::union()) lets a denial override an allow coming from another [`Policy`](crate::Policy).
+///
+/// [`Policy`](crate::Policy)s use the [`Permission`](crate::Permission) JSON format, with `false` values expressing denials.
+///
+/// # Examples:
+///
+/// ```
+/// use simple_perm_manager::Policy;
+///
+/// let policy = Policy::from_json(r#"{"building": {"view": true, "edit": true, "delete": false}}"#);
+///
+/// assert!(policy.is_allowed("building:view"));
+/// assert!(!policy.is_allowed("building:delete"));
+/// assert_eq!(policy.to_json(), r#"{"building":{"delete":false,"edit":true,"view":true}}"#);
+/// ```
+#[derive(Clone, Debug)]
+pub struct Policy {
+    allowed: Permission,
+    denied: Permission,
+}
+
+impl Policy {
+    /// Creates a new [`Policy`](crate::Policy) with the allowed and denied [`Permission`](crate::Permission)s provided.
+    ///
+    /// # Examples:
+    ///
+    /// ```
+    /// use simple_perm_manager::{Permission, Policy};
+    ///
+    /// let policy = Policy::new(
+    ///     Permission::from_json(r#"{"view": true, "edit": true}"#),
+    ///     Permission::from_json(r#"{"edit": true}"#),
+    /// );
+    ///
+    /// assert!(policy.is_allowed("view"));
+    /// assert!(!policy.is_allowed("edit"));
+    /// ```
+    ///
+    /// # Panics:
+    ///
+    /// Panics if the [`Permission`](crate::Permission)s do not have the same manager.
+    pub fn new(allowed: Permission, denied: Permission) -> Policy {
+        if !allowed.has_same_manager(&denied) {
+            panic!("Permissions in policy do not have same manager");
+        }
+
+        Policy { allowed, denied }
+    }
+
+    /// Creates a new unmanaged [`Policy`](crate::Policy) from a JSON string where `true` values are allowed actions
+    /// and `false` values are denied actions.  
+    /// Use [`PermissionManager::policy_from_json`](crate::PermissionManager::policy_from_json()) for managed [`Policy`](crate::Policy)s.
+    ///
+    /// # Panics:
+    ///
+    /// Panics in the same cases that [`Permission::from_json`](crate::Permission::from_json()) does.
+    pub fn from_json(actions_json: &str) -> Policy {
+        let (allowed, denied) = parse_policy_actions(actions_json, ACTION_DIVIDER);
+
+        Policy {
+            allowed: Permission::from_actions(allowed),
+            denied: Permission::from_actions(denied),
+        }
+    }
+
+    /// Returns the [`Policy`](crate::Policy) actions in a JSON formatted string, with `true` values for allowed actions
+    /// and `false` values for denied actions. Actions both allowed and denied are written as denied.
+    ///
+    /// # Panics:
+    ///
+    /// Panics if an action is also a group of another action (e.g. `"building"` and `"building:view"`), as the JSON
+    /// format can not express it.
+    pub fn to_json(&self) -> String {
+        Value::Object(serialize_policy_actions(
+            self.allowed.actions(),
+            self.denied.actions(),
+            self.allowed.separator(),
+        ))
+        .to_string()
+    }
+
+    /// Returns the allowed actions of the [`Policy`](crate::Policy).
+    pub fn allowed(&self) -> &Permission {
+        &self.allowed
+    }
+
+    /// Returns the denied actions of the [`Policy`](crate::Policy).
+    pub fn denied(&self) -> &Permission {
+        &self.denied
+    }
+
+    /// Returns a [`Permission`](crate::Permission) with the actions the [`Policy`](crate::Policy) allows: the allowed actions that are not denied.
+    ///
+    /// # Examples:
+    ///
+    /// ```
+    /// use simple_perm_manager::Policy;
+    ///
+    /// let policy = Policy::from_json(r#"{"view": true, "edit": true, "delete": false}"#);
+    ///
+    /// assert_eq!(policy.effective().to_json(), r#"{"edit":true,"view":true}"#);
+    /// ```
+    pub fn effective(&self) -> Permission {
+        self.allowed.difference(&self.denied)
+    }
+
+    /// Returns `true` if the [`Policy`](crate::Policy) allows the action: it is allowed and not denied.  
+    /// Aliases and implications of managed [`Policy`](crate::Policy)s are resolved as in [`Permission::contains_action`](crate::Permission::contains_action()).
+    pub fn is_allowed(&self, action_str: &str) -> bool {
+        self.allowed.contains_action(action_str) && !self.denied.contains_action(action_str)
+    }
+
+    /// Returns `true` if the [`Policy`](crate::Policy) allows all the actions of the [`Permission`](crate::Permission) provided.
+    ///
+    /// # Examples:
+    ///
+    /// ```
+    /// use simple_perm_manager::{Permission, Policy};
+    ///
+    /// let policy = Policy::from_json(r#"{"view": true, "edit": true, "delete": false}"#);
+    ///
+    /// assert!(policy.contains(&Permission::from_json(r#"{"view": true, "edit": true}"#)));
+    /// assert!(!policy.contains(&Permission::from_json(r#"{"delete": true}"#)));
+    /// ```
+    ///
+    /// # Panics:
+    ///
+    /// Panics if the [`Permission`](crate::Permission) does not have the same manager as the [`Policy`](crate::Policy).
+    pub fn contains(&self, perm: &Permission) -> bool {
+        if !self.allowed.has_same_manager(perm) {
+            panic!("Permissions in contains operation do not have same manager");
+        }
+
+        perm.actions().all(|action| self.is_allowed(action))
+    }
+
+    /// Returns a [`Policy`](crate::Policy) with the allowed and the denied actions of both [`Policy`](crate::Policy)s.  
+    /// Denials of any [`Policy`](crate::Policy) override the allows of the other one.
+    ///
+    /// # Examples:
+    ///
+    /// ```
+    /// use simple_perm_manager::Policy;
+    ///
+    /// let editor = Policy::from_json(r#"{"view": true, "edit": true}"#);
+    /// let suspended = Policy::from_json(r#"{"edit": false}"#);
+    ///
+    /// let policy = editor.union(&suspended);
+    /// assert!(policy.is_allowed("view"));
+    /// assert!(!policy.is_allowed("edit"));
+    /// ```
+    ///
+    /// # Panics:
+    ///
+    /// Panics if the [`Policy`](crate::Policy)s do not have the same manager.
+    pub fn union(&self, other: &Policy) -> Policy {
+        Policy {
+            allowed: self.allowed.union(&other.allowed),
+            denied: self.denied.union(&other.denied),
+        }
+    }
+}
@@ -0,0 +1,145 @@
+use super::*;
+use crate::PermissionManager;
+use std::collections::HashSet;
+
+#[test]
+fn new_test() {
+    let policy = Policy::new(
+        Permission::from_json(r#"{"view": true, "edit": true}"#),
+        Permission::from_json(r#"{"edit": true}"#),
+    );
+
+    assert_eq!(policy.is_allowed("view"), true);
+    assert_eq!(policy.is_allowed("edit"), false);
+
+    let pm = PermissionManager::from_json(r#"{"view": true}"#);
+    match std::panic::catch_unwind(|| {
+        Policy::new(
+            pm.perm_from_json(r#"{"view": true}"#),
+            Permission::from_json("{}"),
+        );
+    }) {
+        Ok(_) => panic!("policy with permissions of different managers should have panicked"),
+        Err(_) => (),
+    }
+}
+
+#[test]
+fn from_json_test() {
+    let policy = Policy::from_json(r#"{"view": true, "group": {"edit": true, "delete": false}}"#);
+
+    assert_eq!(policy.allowed().is_managed(), false);
+    assert_eq!(
+        *policy.allowed().get_actions(),
+        HashSet::from([String::from("view"), String::from("group:edit")])
+    );
+    assert_eq!(
+        *policy.denied().get_actions(),
+        HashSet::from([String::from("group:delete")])
+    );
+
+    match std::panic::catch_unwind(|| {
+        Policy::from_json(r#"{"view": "no"}"#);
+    }) {
+        Ok(_) => panic!("invalid policy json should have panicked"),
+        Err(_) => (),
+    }
+}
+
+#[test]
+fn to_json_test() {
+    let json = r#"{"group":{"delete":false,"edit":true},"view":true}"#;
+
+    assert_eq!(Policy::from_json(json).to_json(), json);
+    // Actions both allowed and denied are written as denied
+    assert_eq!(
+        Policy::new(
+            Permission::from_json(r#"{"view": true}"#),
+            Permission::from_json(r#"{"view": true}"#)
+        )
+        .to_json(),
+        r#"{"view":false}"#
+    );
+}
+
+#[test]
+fn effective_test() {
+    let policy = Policy::from_json(r#"{"view": true, "edit": true, "delete": false}"#);
+
+    assert_eq!(
+        *policy.effective().get_actions(),
+        HashSet::from([String::from("view"), String::from("edit")])
+    );
+    assert_eq!(
+        *Policy::new(
+            Permission::from_json(r#"{"view": true, "edit": true}"#),
+            Permission::from_json(r#"{"edit": true}"#)
+        )
+        .effective()
+        .get_actions(),
+        HashSet::from([String::from("view")])
+    );
+}
+
+#[test]
+fn is_allowed_test() {
+    let mut pm = PermissionManager::from_json(r#"{"edit": true, "view": true}"#);
+    pm.add_implication("edit", "view");
+    let policy = pm
+        .policy_from_json(r#"{"edit": true}"#)
+        .union(&pm.policy_from_json(r#"{"view": false}"#));
+
+    assert_eq!(policy.is_allowed("edit"), true);
+    // Denials override implied actions
+    assert_eq!(policy.is_allowed("view"), false);
+    assert_eq!(policy.is_allowed("other"), false);
+}
+
+#[test]
+fn contains_test() {
+    let pm = PermissionManager::from_json(r#"{"edit": true, "view": true}"#);
+    let policy = pm.policy_from_json(r#"{"edit": false, "view": true}"#);
+
+    assert_eq!(
+        policy.contains(&pm.perm_from_json(r#"{"view": true}"#)),
+        true
+    );
+    assert_eq!(
+        policy.contains(&pm.perm_from_json(r#"{"edit": true}"#)),
+        false
+    );
+    assert_eq!(policy.contains(&pm.perm_from_json("{}")), true);
+
+    match std::panic::catch_unwind(|| {
+        policy.contains(&Permission::from_json(r#"{"view": true}"#));
+    }) {
+        Ok(_) => panic!("contains with permission of different manager should have panicked"),
+        Err(_) => (),
+    }
+}
+
+#[test]
+fn union_test() {
+    let policy = Policy::from_json(r#"{"view": true, "edit": true}"#)
+        .union(&Policy::from_json(r#"{"edit": false, "delete": true}"#));
+
+    assert_eq!(policy.is_allowed("view"), true);
+    assert_eq!(policy.is_allowed("edit"), false);
+    assert_eq!(policy.is_allowed("delete"), true);
+
+    let pm = PermissionManager::from_json(r#"{"view": true}"#);
+    match std::panic::catch_unwind(|| {
+        policy.union(&pm.policy_from_json(r#"{"view": true}"#));
+    }) {
+        Ok(_) => panic!("union of policies with different managers should have panicked"),
+        Err(_) => (),
+    }
+}
+
+#[test]
+fn combining_algorithm_test() {
+    assert_eq!(
+        CombiningAlgorithm::default(),
+        CombiningAlgorithm::DenyOverrides
+    );
+}
@@ -0,0 +1,267 @@
+use crate::permission::Permission;
+use crate::permission_manager::PermissionManager;
+use crate::telemetry;
+use crate::validation_error::ValidationError;
+use std::collections::{BTreeMap, HashSet};
+
+//// TESTS ////
+#[cfg(test)]
+mod tests;
+
+// Actions of a rwx universe with the character and bit of each one in a mode, in mode order
+const RWX: [(&str, char, u32); 3] = [
+    ("read", 'r', 0b100),
+    ("write", 'w', 0b010),
+    ("execute", 'x', 0b001),
+];
+// Number of bits of the mode of each class (owner, group and other)
+const CLASS_BITS: u32 = 3;
+
+// Returns the invalid JSON error for a file mode or ACL
+fn invalid(kind: &str, message: &str) -> ValidationError {
+    telemetry::record_parse_error();
+    ValidationError::InvalidJson(format!("wrong format in {} - {}", kind, message))
+}
+
+// Returns the bits of a symbolic (`r-x`) or octal digit (`5`) mode of a single class
+fn parse_mode(mode: &str) -> Option<u32> {
+    if let [digit @ b'0'..=b'7'] = mode.as_bytes() {
+        return Some(u32::from(digit - b'0'));
+    }
+
+    let chars: Vec<char> = mode.chars().collect();
+    if chars.len() != RWX.len() {
+        return None;
+    }
+    chars
+        .into_iter()
+        .zip(RWX)
+        .try_fold(0, |bits, (c, (_, set, bit))| match c {
+            '-' => Some(bits),
+            c if c == set => Some(bits | bit),
+            _ => None,
+        })
+}
+
+// Returns the full name of a POSIX ACL entry tag, which may be abbreviated
+fn acl_tag(tag: &str) -> Option<&'static str> {
+    match tag {
+        "user" | "u" => Some("user"),
+        "group" | "g" => Some("group"),
+        "mask" | "m" => Some("mask"),
+        "other" | "o" => Some("other"),
+        _ => None,
+    }
+}
+
+impl Permission {
+    /// Returns the `read`, `write` and `execute` actions of the [`Permission`](crate::Permission) as a symbolic mode, e.g. `r-x`.
+    /// Other actions are ignored.
+    ///
+    /// # Examples:
+    ///
+    /// ```
+    /// use simple_perm_manager::PermissionManager;
+    ///
+    /// let manager = PermissionManager::rwx();
+    ///
+    /// assert_eq!(manager.perm_from_json(r#"{"read": true, "execute": true}"#).to_mode(), "r-x");
+    /// assert_eq!(manager.perm_from_json("{}").to_mode(), "---");
+    /// ```
+    pub fn to_mode(&self) -> String {
+        RWX.iter()
+            .map(|(action, set, _)| match self.contains_action(action) {
+                true => *set,
+                false => '-',
+            })
+            .collect()
+    }
+
+    /// Returns the `read`, `write` and `execute` actions of the [`Permission`](crate::Permission) as the bits of an octal mode digit,
+    /// e.g. `0o5` for `read` and `execute`. Other actions are ignored.
+    ///
+    /// # Examples:
+    ///
+    /// ```
+    /// use simple_perm_manager::PermissionManager;
+    ///
+    /// let manager = PermissionManager::rwx();
+    /// let owner = manager.perm_from_json(r#"{"read": true, "write": true, "execute": true}"#);
+    /// let other = manager.perm_from_json(r#"{"read": true}"#);
+    ///
+    /// assert_eq!(owner.to_mode_bits() << 6 | other.to_mode_bits() << 3 | other.to_mode_bits(), 0o744);
+    /// ```
+    pub fn to_mode_bits(&self) -> u32 {
+        RWX.iter()
+            .filter(|(action, _, _)| self.contains_action(action))
+            .fold(0, |bits, (_, _, bit)| bits | bit)
+    }
+
+    /// Returns the [`Permission`](crate::Permission) as a POSIX ACL entry, in the `tag:qualifier:mode` format of `getfacl` and
+    /// `setfacl`, e.g. `user:alice:r-x`. See [`to_mode`](Self::to_mode()).
+    ///
+    /// # Examples:
+    ///
+    /// ```
+    /// use simple_perm_manager::PermissionManager;
+    ///
+    /// let manager = PermissionManager::rwx();
+    /// let perm = manager.perm_from_json(r#"{"read": true, "write": true}"#);
+    ///
+    /// assert_eq!(perm.to_acl_entry("group", "dev"), "group:dev:rw-");
+    /// assert_eq!(perm.to_acl_entry("other", ""), "other::rw-");
+    /// ```
+    pub fn to_acl_entry(&self, tag: &str, qualifier: &str) -> String {
+        format!("{}:{}:{}", tag, qualifier, self.to_mode())
+    }
+}
+
+impl PermissionManager {
+    /// Creates a new [`PermissionManager`](crate::PermissionManager) whose universe is the `read`, `write` and `execute` actions
+    /// of filesystem modes, for bridging filesystem and application permissions.
+    ///
+    /// # Examples:
+    ///
+    /// ```
+    /// use simple_perm_manager::PermissionManager;
+    ///
+    /// let manager = PermissionManager::rwx();
+    /// assert_eq!(manager.to_actions().len(), 3);
+    /// ```
+    pub fn rwx() -> PermissionManager {
+        PermissionManager::from_actions(
+            RWX.iter()
+                .map(|(action, _, _)| String::from(*action))
+                .collect(),
+        )
+    }
+
+    /// Returns a managed [`Permission`](crate::Permission) from the mode of a single class, either symbolic (`r-x`) or an octal
+    /// digit (`5`).
+    ///
+    /// # Examples:
+    ///
+    /// ```
+    /// use simple_perm_manager::PermissionManager;
+    ///
+    /// let manager = PermissionManager::rwx();
+    ///
+    /// let perm = manager.perm_from_mode("r-x").unwrap();
+    /// assert!(perm.contains_action("execute"));
+    /// assert!(!perm.contains_action("write"));
+    /// assert_eq!(manager.perm_from_mode("6").unwrap().to_mode(), "rw-");
+    /// ```
+    ///
+    /// # Errors:
+    ///
+    /// - Returns [`ValidationError::InvalidJson`](crate::ValidationError::InvalidJson) if `mode` is not a symbolic mode or octal digit.
+    /// - Returns [`ValidationError::UnknownActions`](crate::ValidationError::UnknownActions) if the mode grants an action that is not
+    ///   in the universe.
+    pub fn perm_from_mode(&self, mode: &str) -> Result<Permission, ValidationError> {
+        let bits = parse_mode(mode)
+            .ok_or_else(|| invalid("file mode", "expected 'rwx' characters or an octal digit"))?;
+
+        self.perm_from_mode_bits(bits)
+    }
+
+    /// Returns the managed [`Permission`](crate::Permission)s of the owner, group and other classes of a file mode, e.g. `0o754`.
+    /// Bits above the permission bits, such as the file type and setuid bits of `st_mode`, are ignored.
+    ///
+    /// # Examples:
+    ///
+    /// ```
+    /// use simple_perm_manager::PermissionManager;
+    ///
+    /// let manager = PermissionManager::rwx();
+    /// let [owner, group, other] = manager.perms_from_file_mode(0o100754).unwrap();
+    ///
+    /// assert_eq!(owner.to_mode(), "rwx");
+    /// assert_eq!(group.to_mode(), "r-x");
+    /// assert_eq!(other.to_mode(), "r--");
+    /// ```
+    ///
+    /// # Errors:
+    ///
+    /// Returns [`ValidationError::UnknownActions`](crate::ValidationError::UnknownActions) if the mode grants an action that is not
+    /// in the universe.
+    pub fn perms_from_file_mode(&self, mode: u32) -> Result<[Permission; 3], ValidationError> {
+        let class = |shift: u32| self.perm_from_mode_bits((mode >> shift) & 0o7);
+
+        Ok([class(2 * CLASS_BITS)?, class(CLASS_BITS)?, class(0)?])
+    }
+
+    /// Returns a managed [`Permission`](crate::Permission) for each entry of a POSIX ACL in the text format of `getfacl`, keyed by
+    /// `tag:qualifier` (e.g. `user:alice`, `group:`, `mask:` or `default:user:alice`).  
+    /// Blank lines and comments are skipped, including the `#effective:` comments, so the `mask` entry is not applied.
+    /// Tags can be abbreviated as in `setfacl` (`u`, `g`, `m` and `o`).
+    ///
+    /// # Examples:
+    ///
+    /// ```
+    /// use simple_perm_manager::PermissionManager;
+    ///
+    /// let manager = PermissionManager::rwx();
+    /// let perms = manager.perms_from_acl("
+    ///     user::rw-
+    ///     user:alice:rwx    #effective:r-x
+    ///     group::r--
+    ///     mask::r-x
+    ///     other::---
+    /// ").unwrap();
+    ///
+    /// assert_eq!(perms.len(), 5);
+    /// assert_eq!(perms["user:alice"].to_mode(), "rwx");
+    /// assert_eq!(perms["other:"].to_mode(), "---");
+    /// ```
+    ///
+    /// # Errors:
+    ///
+    /// - Returns [`ValidationError::InvalidJson`](crate::ValidationError::InvalidJson) if an entry does not have the
+    ///   `tag:qualifier:mode` format or its tag is unknown.
+    /// - Returns [`ValidationError::UnknownActions`](crate::ValidationError::UnknownActions) if an entry grants an action that is not
+    ///   in the universe.
+    pub fn perms_from_acl(
+        &self,
+        acl: &str,
+    ) -> Result<BTreeMap<String, Permission>, ValidationError> {
+        let mut perms: BTreeMap<String, Permission> = BTreeMap::new();
+
+        for (number, line) in acl.lines().enumerate() {
+            let entry = line.split_once('#').map_or(line, |(entry, _)| entry).trim();
+            if entry.is_empty() {
+                continue;
+            }
+
+            let invalid =
+                |message: &str| invalid("acl", &format!("line {}: {}", number + 1, message));
+            let (default, entry) = match entry.strip_prefix("default:") {
+                Some(entry) => ("default:", entry),
+                None => ("", entry),
+            };
+            let [tag, qualifier, mode] = entry.split(':').collect::<Vec<&str>>()[..] else {
+                return Err(invalid("expected 'tag:qualifier:mode'"));
+            };
+            let tag = acl_tag(tag).ok_or_else(|| invalid(&format!("unknown tag '{}'", tag)))?;
+            let bits = parse_mode(mode)
+                .ok_or_else(|| invalid("expected 'rwx' characters or an octal digit as mode"))?;
+
+            perms.insert(
+                format!("{}{}:{}", default, tag, qualifier),
+                self.perm_from_mode_bits(bits)?,
+            );
+        }
+
+        Ok(perms)
+    }
+
+    // Returns the managed permission of the bits of a single class mode
+    fn perm_from_mode_bits(&self, bits: u32) -> Result<Permission, ValidationError> {
+        let actions: HashSet<String> = RWX
+            .iter()
+            .filter(|(_, _, bit)| bits & bit != 0)
+            .map(|(action, _, _)| String::from(*action))
+            .collect();
+
+        self.try_perm_from_actions(actions)
+    }
+}
@@ -0,0 +1,162 @@
+use super::*;
+
+#[test]
+fn parse_mode_test() {
+    assert_eq!(parse_mode("rwx"), Some(0o7));
+    assert_eq!(parse_mode("r-x"), Some(0o5));
+    assert_eq!(parse_mode("---"), Some(0o0));
+    assert_eq!(parse_mode("6"), Some(0o6));
+    assert_eq!(parse_mode("0"), Some(0o0));
+    assert_eq!(parse_mode("8"), None);
+    assert_eq!(parse_mode("xwr"), None);
+    assert_eq!(parse_mode("rw"), None);
+    assert_eq!(parse_mode("rwxr"), None);
+    assert_eq!(parse_mode(""), None);
+}
+
+#[test]
+fn to_mode_test() {
+    let manager = PermissionManager::rwx();
+
+    for bits in 0..8 {
+        let perm = manager.perm_from_mode_bits(bits).unwrap();
+        assert_eq!(perm.to_mode_bits(), bits);
+        assert_eq!(
+            manager
+                .perm_from_mode(&perm.to_mode())
+                .unwrap()
+                .to_mode_bits(),
+            bits
+        );
+        assert_eq!(
+            manager.perm_from_mode(&bits.to_string()).unwrap().to_mode(),
+            perm.to_mode()
+        );
+    }
+    assert_eq!(
+        manager.perm_from_json(r#"{"write": true}"#).to_mode(),
+        "-w-"
+    );
+}
+
+#[test]
+fn to_mode_ignores_other_actions_test() {
+    let manager =
+        PermissionManager::from_json(r#"{"read": true, "execute": true, "delete": true}"#);
+    let perm = manager.perm_from_json(r#"{"execute": true, "delete": true}"#);
+
+    assert_eq!(perm.to_mode(), "--x");
+    assert_eq!(perm.to_mode_bits(), 0o1);
+    assert_eq!(perm.to_acl_entry("user", "alice"), "user:alice:--x");
+}
+
+#[test]
+fn perm_from_mode_error_test() {
+    let manager = PermissionManager::rwx();
+
+    match manager.perm_from_mode("rwz") {
+        Err(ValidationError::InvalidJson(message)) => {
+            assert_eq!(message.starts_with("wrong format in file mode"), true)
+        }
+        _ => panic!("expected an invalid mode"),
+    }
+
+    let manager = PermissionManager::from_json(r#"{"read": true, "execute": true}"#);
+    assert_eq!(manager.perm_from_mode("r-x").is_ok(), true);
+    match manager.perm_from_mode("rw-") {
+        Err(ValidationError::UnknownActions(unknown)) => {
+            assert_eq!(unknown[0].0, "write")
+        }
+        _ => panic!("expected an unknown action"),
+    }
+}
+
+#[test]
+fn perms_from_file_mode_test() {
+    let manager = PermissionManager::rwx();
+
+    let [owner, group, other] = manager.perms_from_file_mode(0o640).unwrap();
+    assert_eq!(owner.to_mode(), "rw-");
+    assert_eq!(group.to_mode(), "r--");
+    assert_eq!(other.to_mode(), "---");
+
+    let [owner, group, other] = manager.perms_from_file_mode(0o104711).unwrap();
+    assert_eq!(
+        owner.to_mode_bits() << 6 | group.to_mode_bits() << 3 | other.to_mode_bits(),
+        0o711
+    );
+}
+
+#[test]
+fn perms_from_acl_test() {
+    let manager = PermissionManager::rwx();
+    let perms = manager
+        .perms_from_acl(
+            "# file: shared
+# owner: alice
+user::rwx
+u:bob:r-x
+group::r-x    #effective:r--
+g:dev:7
+mask::r--
+other::---
+default:user::rwx
+default:other::r--
+",
+        )
+        .unwrap();
+
+    assert_eq!(
+        perms.keys().collect::<Vec<&String>>(),
+        vec![
+            "default:other:",
+            "default:user:",
+            "group:",
+            "group:dev",
+            "mask:",
+            "other:",
+            "user:",
+            "user:bob"
+        ]
+    );
+    assert_eq!(perms["group:"].to_mode(), "r-x");
+    assert_eq!(perms["group:dev"].to_mode(), "rwx");
+    assert_eq!(
+        perms["default:other:"].to_acl_entry("other", ""),
+        "other::r--"
+    );
+    assert_eq!(
+        manager.perms_from_acl("\n# empty\n").unwrap().is_empty(),
+        true
+    );
+}
+
+#[test]
+fn perms_from_acl_error_test() {
+    let manager = PermissionManager::rwx();
+
+    for (acl, expected) in [
+        (
+            "user::rwx\nuser:bob\n",
+            "line 2: expected 'tag:qualifier:mode'",
+        ),
+        ("owner::rwx", "line 1: unknown tag 'owner'"),
+        (
+            "user::rwxr",
+            "line 1: expected 'rwx' characters or an octal digit as mode",
+        ),
+    ] {
+        match manager.perms_from_acl(acl) {
+            Err(ValidationError::InvalidJson(message)) => {
+                assert_eq!(message, format!("wrong format in acl - {}", expected))
+            }
+            _ => panic!("expected an invalid acl"),
+        }
+    }
+
+    let manager = PermissionManager::from_json(r#"{"read": true}"#);
+    match manager.perms_from_acl("user::r--\nother::rw-") {
+        Err(ValidationError::UnknownActions(unknown)) => assert_eq!(unknown[0].0, "write"),
+        _ => panic!("expected an unknown action"),
+    }
+}
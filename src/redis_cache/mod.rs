@@ -0,0 +1,220 @@
+use crate::action_name_policy::ActionNamePolicy;
+use crate::grant_store::GrantStore;
+use crate::permission::Permission;
+#[cfg(feature = "async")]
+use redis::AsyncCommands;
+use redis::{Client, Commands, RedisResult};
+use std::time::Duration;
+
+//// TESTS ////
+#[cfg(test)]
+mod tests;
+
+// Prefix of the keys of the cached permissions when none is provided
+const DEFAULT_PREFIX: &str = "spm:effective:";
+
+/// Cache of the effective [`Permission`](crate::Permission)s of subjects in Redis, available with the `redis` feature, for
+/// deployments with several instances that can not rely on in-process caches.
+///
+/// Permissions are stored as JSON strings under `<prefix><subject>` keys that expire after the TTL provided. Register
+/// [`invalidation_hook`](Self::invalidation_hook()) in the [`GrantStore`](crate::GrantStore) with
+/// [`on_grant`](crate::GrantStore::on_grant()) and [`on_revoke`](crate::GrantStore::on_revoke()) to drop the cached permission of
+/// a subject when its grants change.
+///
+/// # Examples:
+///
+/// ```no_run
+/// use simple_perm_manager::{GrantStore, PermissionManager, RedisPermissionCache};
+/// use std::time::Duration;
+///
+/// let cache = RedisPermissionCache::open("redis://127.0.0.1/", Duration::from_secs(60)).unwrap();
+/// let mut grants: GrantStore<String> = GrantStore::new(PermissionManager::from_json(r#"{"view": true}"#));
+/// grants.on_grant(cache.invalidation_hook());
+/// grants.on_revoke(cache.invalidation_hook());
+///
+/// let view = grants.manager().perm_from_json(r#"{"view": true}"#);
+/// grants.grant(String::from("alice"), &view);
+///
+/// // Computed from the grants and cached the first time, read from Redis until it expires or alice's grants change
+/// assert!(cache.effective_perm(&grants, "alice").unwrap().contains_action("view"));
+/// ```
+#[derive(Clone, Debug)]
+pub struct RedisPermissionCache {
+    client: Client,
+    ttl: Duration,
+    prefix: String,
+}
+
+impl RedisPermissionCache {
+    /// Creates a [`RedisPermissionCache`](crate::RedisPermissionCache) for the Redis server at the URL provided, caching
+    /// permissions for `ttl`, with the key prefix `spm:effective:`. Connections are opened on each operation.
+    ///
+    /// # Errors:
+    ///
+    /// Returns a [`redis::RedisError`](redis::RedisError) if the URL is not valid.
+    pub fn open(url: &str, ttl: Duration) -> RedisResult<RedisPermissionCache> {
+        Ok(RedisPermissionCache {
+            client: Client::open(url)?,
+            ttl,
+            prefix: String::from(DEFAULT_PREFIX),
+        })
+    }
+
+    /// Returns the [`RedisPermissionCache`](crate::RedisPermissionCache) with the key prefix provided, e.g. to share a Redis
+    /// server between applications.
+    pub fn with_prefix(mut self, prefix: &str) -> RedisPermissionCache {
+        self.prefix = prefix.to_string();
+        self
+    }
+
+    /// Returns the time cached permissions expire after.
+    pub fn ttl(&self) -> Duration {
+        self.ttl
+    }
+
+    // Key of the cached permission of a subject
+    fn key(&self, subject: &str) -> String {
+        format!("{}{}", self.prefix, subject)
+    }
+
+    // TTL in milliseconds for PSETEX, which rejects 0
+    fn ttl_millis(&self) -> u64 {
+        u64::try_from(self.ttl.as_millis())
+            .unwrap_or(u64::MAX)
+            .max(1)
+    }
+
+    // Parses a cached permission JSON string
+    fn parse(json: &str) -> RedisResult<Permission> {
+        Permission::try_from_json(json, &ActionNamePolicy::new()).map_err(|error| {
+            redis::RedisError::from((
+                redis::ErrorKind::TypeError,
+                "invalid cached permission",
+                error.to_string(),
+            ))
+        })
+    }
+
+    /// Returns the cached [`Permission`](crate::Permission) of a subject, unmanaged, if it has not expired.
+    ///
+    /// # Errors:
+    ///
+    /// Returns a [`redis::RedisError`](redis::RedisError) if the command fails, or if the cached value is not a permission JSON string.
+    pub fn get(&self, subject: &str) -> RedisResult<Option<Permission>> {
+        let json: Option<String> = self.client.get_connection()?.get(self.key(subject))?;
+
+        json.map(|json| RedisPermissionCache::parse(&json))
+            .transpose()
+    }
+
+    /// Caches the [`Permission`](crate::Permission) of a subject for the TTL of the [`RedisPermissionCache`](crate::RedisPermissionCache).
+    ///
+    /// # Errors:
+    ///
+    /// Returns a [`redis::RedisError`](redis::RedisError) if the command fails.
+    pub fn set(&self, subject: &str, perm: &Permission) -> RedisResult<()> {
+        self.client
+            .get_connection()?
+            .pset_ex(self.key(subject), perm.to_json(), self.ttl_millis())
+    }
+
+    /// Removes the cached [`Permission`](crate::Permission) of a subject.
+    ///
+    /// # Errors:
+    ///
+    /// Returns a [`redis::RedisError`](redis::RedisError) if the command fails.
+    pub fn invalidate(&self, subject: &str) -> RedisResult<()> {
+        let _: usize = self.client.get_connection()?.del(self.key(subject))?;
+
+        Ok(())
+    }
+
+    /// Removes every cached [`Permission`](crate::Permission) with the key prefix of the
+    /// [`RedisPermissionCache`](crate::RedisPermissionCache), e.g. after changing group memberships or the universe.
+    ///
+    /// # Errors:
+    ///
+    /// Returns a [`redis::RedisError`](redis::RedisError) if a command fails.
+    pub fn invalidate_all(&self) -> RedisResult<()> {
+        let mut connection = self.client.get_connection()?;
+        let keys: Vec<String> = connection
+            .scan_match::<_, String>(format!("{}*", self.prefix))?
+            .collect();
+        if !keys.is_empty() {
+            let _: usize = connection.del(keys)?;
+        }
+
+        Ok(())
+    }
+
+    /// Returns the effective [`Permission`](crate::Permission) of a subject in a [`GrantStore`](crate::GrantStore), managed by its
+    /// [`PermissionManager`](crate::PermissionManager): the cached one if any, or the one computed by
+    /// [`GrantStore::effective_perm`](crate::GrantStore::effective_perm()), which is cached.  
+    /// Cached actions no longer in the universe are dropped.
+    ///
+    /// # Errors:
+    ///
+    /// Returns a [`redis::RedisError`](redis::RedisError) if a command fails.
+    ///
+    /// # Panics:
+    ///
+    /// Panics in the same cases that [`GrantStore::effective_perm`](crate::GrantStore::effective_perm()) does.
+    pub fn effective_perm(
+        &self,
+        grants: &GrantStore<String>,
+        subject: &str,
+    ) -> RedisResult<Permission> {
+        if let Some(cached) = self.get(subject)? {
+            return Ok(grants.manager().clean_perm(&cached));
+        }
+
+        let perm = grants.effective_perm(&subject.to_string());
+        self.set(subject, &perm)?;
+
+        Ok(perm)
+    }
+
+    /// Same as [`effective_perm`](Self::effective_perm()) but awaiting the Redis commands on an asynchronous connection, so Tokio
+    /// worker threads are not blocked. Available with the `async` feature.
+    ///
+    /// # Errors:
+    ///
+    /// Returns a [`redis::RedisError`](redis::RedisError) if a command fails.
+    ///
+    /// # Panics:
+    ///
+    /// Panics in the same cases that [`GrantStore::effective_perm`](crate::GrantStore::effective_perm()) does.
+    #[cfg(feature = "async")]
+    pub async fn effective_perm_async(
+        &self,
+        grants: &GrantStore<String>,
+        subject: &str,
+    ) -> RedisResult<Permission> {
+        let mut connection = self.client.get_multiplexed_async_connection().await?;
+        let json: Option<String> = connection.get(self.key(subject)).await?;
+        if let Some(json) = json {
+            return Ok(grants
+                .manager()
+                .clean_perm(&RedisPermissionCache::parse(&json)?));
+        }
+
+        let perm = grants.effective_perm(&subject.to_string());
+        let _: () = connection
+            .pset_ex(self.key(subject), perm.to_json(), self.ttl_millis())
+            .await?;
+
+        Ok(perm)
+    }
+
+    /// Returns a callback for [`GrantStore::on_grant`](crate::GrantStore::on_grant()) and
+    /// [`GrantStore::on_revoke`](crate::GrantStore::on_revoke()) removing the cached [`Permission`](crate::Permission) of the subject
+    /// whose grants changed.  
+    /// Errors are ignored, so cached permissions stay until their TTL if Redis is unavailable. Grants to groups only invalidate the
+    /// group, use [`invalidate_all`](Self::invalidate_all()) when the members of a group must see the change before the TTL.
+    pub fn invalidation_hook(&self) -> impl Fn(&String, &Permission) + Send + Sync + 'static {
+        let cache = self.clone();
+        move |subject: &String, _: &Permission| {
+            let _ = cache.invalidate(subject);
+        }
+    }
+}
@@ -0,0 +1,56 @@
+use super::*;
+use crate::permission_manager::PermissionManager;
+
+// Nothing listens on port 1, so every command fails to connect
+const UNREACHABLE: &str = "redis://127.0.0.1:1/";
+
+#[test]
+fn open_test() {
+    let cache = RedisPermissionCache::open(UNREACHABLE, Duration::from_secs(30)).unwrap();
+
+    assert_eq!(cache.ttl(), Duration::from_secs(30));
+    assert_eq!(cache.key("alice"), "spm:effective:alice");
+    assert_eq!(cache.with_prefix("app:").key("alice"), "app:alice");
+    assert_eq!(
+        RedisPermissionCache::open("not a url", Duration::from_secs(30)).is_err(),
+        true
+    );
+}
+
+#[test]
+fn unreachable_test() {
+    let cache = RedisPermissionCache::open(UNREACHABLE, Duration::from_secs(30)).unwrap();
+    let perm = Permission::from_json(r#"{"view": true}"#);
+
+    assert_eq!(cache.get("alice").is_err(), true);
+    assert_eq!(cache.set("alice", &perm).is_err(), true);
+    assert_eq!(cache.invalidate("alice").is_err(), true);
+    assert_eq!(cache.invalidate_all().is_err(), true);
+
+    // The hook ignores errors so grants still work without Redis
+    let mut grants: GrantStore<String> =
+        GrantStore::new(PermissionManager::from_json(r#"{"view": true}"#));
+    grants.on_grant(cache.invalidation_hook());
+    let view = grants.manager().perm_from_json(r#"{"view": true}"#);
+    grants.grant(String::from("alice"), &view);
+    assert_eq!(cache.effective_perm(&grants, "alice").is_err(), true);
+}
+
+#[cfg(feature = "async")]
+#[test]
+fn async_unreachable_test() {
+    let cache = RedisPermissionCache::open(UNREACHABLE, Duration::from_secs(30)).unwrap();
+    let grants: GrantStore<String> =
+        GrantStore::new(PermissionManager::from_json(r#"{"view": true}"#));
+    let runtime = tokio::runtime::Builder::new_current_thread()
+        .enable_all()
+        .build()
+        .unwrap();
+
+    assert_eq!(
+        runtime
+            .block_on(cache.effective_perm_async(&grants, "alice"))
+            .is_err(),
+        true
+    );
+}
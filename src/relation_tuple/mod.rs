@@ -0,0 +1,178 @@
+use crate::grant_store::GrantStore;
+use crate::validation_error::ValidationError;
+use serde_json::{json, Value};
+use std::collections::HashSet;
+use std::fmt;
+use std::fmt::Display;
+use std::hash::Hash;
+
+//// TESTS ////
+#[cfg(test)]
+mod tests;
+
+// Relation tying a subject to the groups it belongs to
+const MEMBER_RELATION: &str = "member";
+// Separator of the groups of an action inside an object id, as ':' divides the object type from the id
+const OBJECT_ID_DIVIDER: char = '/';
+
+/// [Zanzibar](https://research.google/pubs/pub48190/) style relation tuple stating that a user has a relation with an object,
+/// as written to [OpenFGA](https://openfga.dev), see [`GrantStore::to_relation_tuples`](crate::GrantStore::to_relation_tuples()).
+///
+/// Users and objects have the `type:id` form and users can also be usersets (`type:id#relation`).
+///
+/// # Examples:
+///
+/// ```
+/// use simple_perm_manager::RelationTuple;
+///
+/// let tuple = RelationTuple::new("user:alice", "view", "document:readme");
+/// assert_eq!(tuple.to_string(), "document:readme#view@user:alice");
+/// assert_eq!(tuple.to_json().to_string(), r#"{"object":"document:readme","relation":"view","user":"user:alice"}"#);
+/// ```
+#[derive(Clone, Debug, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct RelationTuple {
+    object: String,
+    relation: String,
+    user: String,
+}
+
+impl RelationTuple {
+    /// Creates a new [`RelationTuple`](crate::RelationTuple) stating that `user` has `relation` with `object`.
+    pub fn new(user: &str, relation: &str, object: &str) -> RelationTuple {
+        RelationTuple {
+            object: String::from(object),
+            relation: String::from(relation),
+            user: String::from(user),
+        }
+    }
+
+    /// Returns the user (or userset) of the tuple.
+    pub fn user(&self) -> &str {
+        &self.user
+    }
+
+    /// Returns the relation of the tuple.
+    pub fn relation(&self) -> &str {
+        &self.relation
+    }
+
+    /// Returns the object of the tuple.
+    pub fn object(&self) -> &str {
+        &self.object
+    }
+
+    /// Returns the tuple as an OpenFGA tuple key, a JSON object with `user`, `relation` and `object`.
+    pub fn to_json(&self) -> Value {
+        json!({"user": self.user, "relation": self.relation, "object": self.object})
+    }
+
+    /// Returns the body of an OpenFGA `Write` request adding the tuples provided.
+    ///
+    /// # Examples:
+    ///
+    /// ```
+    /// use simple_perm_manager::RelationTuple;
+    ///
+    /// let body = RelationTuple::to_openfga_writes(&[RelationTuple::new("user:alice", "view", "document:readme")]);
+    /// assert_eq!(body, r#"{"writes":{"tuple_keys":[{"object":"document:readme","relation":"view","user":"user:alice"}]}}"#);
+    /// ```
+    pub fn to_openfga_writes(tuples: &[RelationTuple]) -> String {
+        let tuple_keys: Vec<Value> = tuples.iter().map(RelationTuple::to_json).collect();
+
+        json!({"writes": {"tuple_keys": tuple_keys}}).to_string()
+    }
+}
+
+impl Display for RelationTuple {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}#{}@{}", self.object, self.relation, self.user)
+    }
+}
+
+impl<S: Hash + Eq + Display> GrantStore<S> {
+    /// Returns the permanent grants and group memberships of the store as sorted [`RelationTuple`](crate::RelationTuple)s, e.g. to
+    /// load them into OpenFGA before moving to a centralized authorization service.
+    ///
+    /// Each action is split into a resource, its groups, and the action name: `"document:readme:view"` granted to `alice` becomes
+    /// `document:readme#view@user:alice` for a `user` user type. Further groups go into the object id divided by `/`, so
+    /// `"document:drafts:readme:view"` relates to `document:drafts/readme`.  
+    /// Memberships become `member` relations between a subject and its group (`user:team#member@user:alice`), and grants to
+    /// subjects that have members are given to their members (`document:readme#view@user:team#member`).
+    /// Temporary and conditional grants are not exported.
+    ///
+    /// # Examples:
+    ///
+    /// ```
+    /// use simple_perm_manager::{GrantStore, PermissionManager};
+    ///
+    /// let mut grants: GrantStore<String> = GrantStore::new(PermissionManager::from_json(
+    ///     r#"{"document": {"readme": {"view": true, "edit": true}}}"#,
+    /// ));
+    /// grants.grant(String::from("team"), &grants.manager().perm_from_json(r#"{"document": {"readme": {"view": true}}}"#));
+    /// grants.add_member(String::from("alice"), String::from("team"));
+    ///
+    /// let tuples: Vec<String> = grants.to_relation_tuples("user").unwrap().iter().map(|tuple| tuple.to_string()).collect();
+    /// assert_eq!(tuples, vec!["document:readme#view@user:team#member", "user:team#member@user:alice"]);
+    /// ```
+    ///
+    /// # Errors:
+    ///
+    /// Returns [`ValidationError::UntranslatableActions`](crate::ValidationError::UntranslatableActions) with the sorted granted
+    /// actions that have less than two groups, as they do not name a resource type and id.
+    pub fn to_relation_tuples(
+        &self,
+        user_type: &str,
+    ) -> Result<Vec<RelationTuple>, ValidationError> {
+        let separator = self.manager().separator();
+        let memberships = self.direct_memberships();
+        let groups: HashSet<&S> = memberships.values().flatten().collect();
+        let user = |subject: &S| match groups.contains(subject) {
+            true => format!("{}:{}#{}", user_type, subject, MEMBER_RELATION),
+            false => format!("{}:{}", user_type, subject),
+        };
+
+        let mut tuples: Vec<RelationTuple> = Vec::new();
+        let mut untranslatable: HashSet<String> = HashSet::new();
+        for subject in self.subjects() {
+            let Some(perm) = self.grant_of(subject) else {
+                continue;
+            };
+            for action in perm.actions() {
+                let parts: Vec<&str> = action.split(separator).collect();
+                let [object_type, ids @ .., relation] = parts.as_slice() else {
+                    untranslatable.insert(action.to_string());
+                    continue;
+                };
+                if ids.is_empty() {
+                    untranslatable.insert(action.to_string());
+                    continue;
+                }
+
+                let object = format!(
+                    "{}:{}",
+                    object_type,
+                    ids.join(&OBJECT_ID_DIVIDER.to_string())
+                );
+                tuples.push(RelationTuple::new(&user(subject), relation, &object));
+            }
+        }
+        if !untranslatable.is_empty() {
+            let mut untranslatable: Vec<String> = untranslatable.into_iter().collect();
+            untranslatable.sort();
+            return Err(ValidationError::UntranslatableActions(untranslatable));
+        }
+
+        for (member, member_groups) in memberships {
+            for group in member_groups {
+                tuples.push(RelationTuple::new(
+                    &user(member),
+                    MEMBER_RELATION,
+                    &format!("{}:{}", user_type, group),
+                ));
+            }
+        }
+        tuples.sort();
+
+        Ok(tuples)
+    }
+}
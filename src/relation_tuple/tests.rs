@@ -0,0 +1,91 @@
+use super::*;
+use crate::PermissionManager;
+
+fn new_grants() -> GrantStore<String> {
+    GrantStore::new(PermissionManager::from_json(
+        r#"{
+            "document": {"readme": {"view": true, "edit": true}, "drafts": {"plan": {"view": true}}},
+            "folder": {"docs": {"view": true}},
+            "admin": {"view": true},
+            "export": true
+        }"#,
+    ))
+}
+
+#[test]
+fn relation_tuple_test() {
+    let tuple = RelationTuple::new("user:team#member", "edit", "document:readme");
+
+    assert_eq!(tuple.user(), "user:team#member");
+    assert_eq!(tuple.relation(), "edit");
+    assert_eq!(tuple.object(), "document:readme");
+    assert_eq!(tuple.to_string(), "document:readme#edit@user:team#member");
+    assert_eq!(
+        tuple.to_json(),
+        json!({"user": "user:team#member", "relation": "edit", "object": "document:readme"})
+    );
+    assert_eq!(
+        RelationTuple::to_openfga_writes(&[]),
+        r#"{"writes":{"tuple_keys":[]}}"#
+    );
+}
+
+#[test]
+fn to_relation_tuples_test() {
+    let mut grants = new_grants();
+    let perm = grants.manager().perm_from_json(
+        r#"{"document": {"readme": {"edit": true}, "drafts": {"plan": {"view": true}}}}"#,
+    );
+    grants.grant(String::from("alice"), &perm);
+    let perm = grants.manager().perm_from_json(
+        r#"{"document": {"readme": {"view": true}}, "folder": {"docs": {"view": true}}}"#,
+    );
+    grants.grant(String::from("team"), &perm);
+    grants.add_member(String::from("alice"), String::from("team"));
+    grants.add_member(String::from("team"), String::from("org"));
+
+    // Temporary grants are not exported
+    let perm = grants
+        .manager()
+        .perm_from_json(r#"{"admin": {"view": true}}"#);
+    grants.grant_expiring(
+        String::from("bob"),
+        crate::ExpiringPermission::new(perm, None, None),
+    );
+
+    let tuples: Vec<String> = grants
+        .to_relation_tuples("user")
+        .unwrap()
+        .iter()
+        .map(RelationTuple::to_string)
+        .collect();
+    assert_eq!(
+        tuples,
+        vec![
+            "document:drafts/plan#view@user:alice",
+            "document:readme#edit@user:alice",
+            "document:readme#view@user:team#member",
+            "folder:docs#view@user:team#member",
+            "user:org#member@user:team#member",
+            "user:team#member@user:alice",
+        ]
+    );
+    assert_eq!(new_grants().to_relation_tuples("user"), Ok(vec![]));
+}
+
+#[test]
+fn to_relation_tuples_untranslatable_test() {
+    let mut grants = new_grants();
+    let perm = grants.manager().perm_from_json(
+        r#"{"admin": {"view": true}, "export": true, "folder": {"docs": {"view": true}}}"#,
+    );
+    grants.grant(String::from("alice"), &perm);
+
+    assert_eq!(
+        grants.to_relation_tuples("user"),
+        Err(ValidationError::UntranslatableActions(vec![
+            String::from("admin:view"),
+            String::from("export")
+        ]))
+    );
+}
@@ -0,0 +1,371 @@
+use crate::manager_id::ManagerId;
+use crate::permission::Permission;
+use crate::permission_manager::PermissionManager;
+use std::collections::{HashMap, HashSet};
+use std::sync::Arc;
+use std::time::SystemTime;
+
+//// TESTS ////
+#[cfg(test)]
+mod tests;
+
+/// Named [`Permission`](crate::Permission) stored in a [`RoleManager`](crate::RoleManager).
+///
+/// The [`Permission`](crate::Permission) of a [`Role`](crate::Role) is always valid for the
+/// [`PermissionManager`](crate::PermissionManager) of its [`RoleManager`](crate::RoleManager).
+#[derive(Clone, Debug)]
+pub struct Role {
+    name: String,
+    perm: Permission,
+    parents: HashSet<String>,
+}
+
+impl Role {
+    /// Returns the name of the [`Role`](crate::Role).
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    /// Returns the [`Permission`](crate::Permission) of the [`Role`](crate::Role), without the actions inherited from its parents.  
+    /// Use [`RoleManager::effective_role_perm`](crate::RoleManager::effective_role_perm()) to include them.
+    pub fn perm(&self) -> &Permission {
+        &self.perm
+    }
+
+    /// Returns the names of the [`Role`](crate::Role)s this [`Role`](crate::Role) directly inherits from.
+    pub fn parents(&self) -> &HashSet<String> {
+        &self.parents
+    }
+}
+
+/// Stores [`Role`](crate::Role)s validated against a [`PermissionManager`](crate::PermissionManager), assigns them to
+/// subjects (users, API keys, services...) and computes the combined [`Permission`](crate::Permission) of each subject.
+///
+/// Subjects are identified by string ids chosen by the application.
+///
+/// # Examples:
+///
+/// ```
+/// use simple_perm_manager::{PermissionManager, RoleManager};
+///
+/// let manager = PermissionManager::from_json(r#"{"post": {"view": true, "edit": true, "delete": true}}"#);
+/// let mut roles = RoleManager::new(manager);
+///
+/// let viewer = roles.manager().perm_from_json(r#"{"post": {"view": true}}"#);
+/// let editor = roles.manager().perm_from_json(r#"{"post": {"edit": true}}"#);
+/// roles.add_role("viewer", viewer);
+/// roles.add_role("editor", editor);
+///
+/// roles.assign("alice", "viewer");
+/// roles.assign("alice", "editor");
+///
+/// let perm = roles.subject_perm("alice");
+/// assert!(perm.contains_action("post:view"));
+/// assert!(perm.contains_action("post:edit"));
+/// assert!(!perm.contains_action("post:delete"));
+/// ```
+#[derive(Clone, Debug)]
+pub struct RoleManager {
+    manager: Arc<PermissionManager>,
+    roles: HashMap<String, Role>,
+    // Subject ids mapped to the names of the roles assigned to them
+    assignments: HashMap<String, HashSet<String>>,
+}
+
+impl RoleManager {
+    /// Creates a new [`RoleManager`](crate::RoleManager) without roles for the [`PermissionManager`](crate::PermissionManager) provided.  
+    /// Accepts an owned [`PermissionManager`](crate::PermissionManager) or a shared one, such as the ones stored in a
+    /// [`ManagerRegistry`](crate::ManagerRegistry).
+    pub fn new<M>(manager: M) -> RoleManager
+    where
+        M: Into<Arc<PermissionManager>>,
+    {
+        RoleManager {
+            manager: manager.into(),
+            roles: HashMap::new(),
+            assignments: HashMap::new(),
+        }
+    }
+
+    /// Returns the [`PermissionManager`](crate::PermissionManager) the [`Role`](crate::Role)s are validated against.
+    pub fn manager(&self) -> &PermissionManager {
+        &self.manager
+    }
+
+    /// Adds a [`Role`](crate::Role) with the name and [`Permission`](crate::Permission) provided.
+    ///
+    /// # Panics:
+    ///
+    /// - Panics if a [`Role`](crate::Role) with the same name already exists.
+    /// - Panics if the [`Permission`](crate::Permission) is not valid for the [`PermissionManager`](crate::PermissionManager).
+    pub fn add_role(&mut self, name: &str, perm: Permission) {
+        if self.roles.contains_key(name) {
+            panic!("A role already exists with name '{}'", name);
+        }
+        if !self.manager.validate_perm(&perm) {
+            panic!("Role permission is not valid for the RoleManager PermissionManager");
+        }
+
+        self.roles.insert(
+            name.to_string(),
+            Role {
+                name: name.to_string(),
+                perm,
+                parents: HashSet::new(),
+            },
+        );
+    }
+
+    /// Removes the [`Role`](crate::Role) with the name provided, its assignments and its inheritances.  
+    /// Returns the removed [`Role`](crate::Role) or `None` if it did not exist.
+    ///
+    /// # Examples:
+    ///
+    /// ```
+    /// use simple_perm_manager::{PermissionManager, RoleManager};
+    ///
+    /// let mut roles = RoleManager::new(PermissionManager::from_json(r#"{"view": true}"#));
+    /// roles.add_role("viewer", roles.manager().perm_from_json(r#"{"view": true}"#));
+    /// roles.assign("alice", "viewer");
+    ///
+    /// assert!(roles.remove_role("viewer").is_some());
+    /// assert!(roles.subject_roles("alice").is_empty());
+    /// ```
+    pub fn remove_role(&mut self, name: &str) -> Option<Role> {
+        let role = self.roles.remove(name)?;
+        for child in self.roles.values_mut() {
+            child.parents.remove(name);
+        }
+        for roles in self.assignments.values_mut() {
+            roles.remove(name);
+        }
+        self.assignments.retain(|_, roles| !roles.is_empty());
+
+        Some(role)
+    }
+
+    /// Makes a [`Role`](crate::Role) inherit the actions of another one, so its effective [`Permission`](crate::Permission)
+    /// is its own actions unioned with the ones of all its ancestors.  
+    /// Returns `true` if the [`Role`](crate::Role) did not already inherit from the parent.
+    ///
+    /// # Examples:
+    ///
+    /// ```
+    /// use simple_perm_manager::{PermissionManager, RoleManager};
+    ///
+    /// let mut roles = RoleManager::new(PermissionManager::from_json(r#"{"view": true, "edit": true, "delete": true}"#));
+    /// roles.add_role("viewer", roles.manager().perm_from_json(r#"{"view": true}"#));
+    /// roles.add_role("editor", roles.manager().perm_from_json(r#"{"edit": true}"#));
+    /// roles.add_role("admin", roles.manager().perm_from_json(r#"{"delete": true}"#));
+    ///
+    /// // admin extends editor extends viewer
+    /// roles.inherit("editor", "viewer");
+    /// roles.inherit("admin", "editor");
+    ///
+    /// assert_eq!(roles.effective_role_perm("admin").get_actions().len(), 3);
+    /// ```
+    ///
+    /// # Panics:
+    ///
+    /// - Panics if any of the [`Role`](crate::Role)s does not exist.
+    /// - Panics if the inheritance creates a cycle, e.g. the parent already inherits from the [`Role`](crate::Role).
+    pub fn inherit(&mut self, role: &str, parent: &str) -> bool {
+        if !self.roles.contains_key(role) || !self.roles.contains_key(parent) {
+            panic!("Roles in inheritance do not exist");
+        }
+        if self.ancestors(parent).contains(role) {
+            panic!(
+                "Role '{}' inheriting from '{}' creates an inheritance cycle",
+                role, parent
+            );
+        }
+
+        match self.roles.get_mut(role) {
+            Some(role) => role.parents.insert(parent.to_string()),
+            None => false,
+        }
+    }
+
+    /// Removes the inheritance of a [`Role`](crate::Role) from another one.  
+    /// Returns `true` if the [`Role`](crate::Role) inherited from the parent.
+    pub fn disinherit(&mut self, role: &str, parent: &str) -> bool {
+        match self.roles.get_mut(role) {
+            Some(role) => role.parents.remove(parent),
+            None => false,
+        }
+    }
+
+    // Returns the names of a role and all the roles it inherits from, directly or transitively.
+    fn ancestors(&self, role: &str) -> HashSet<String> {
+        let mut ancestors: HashSet<String> = HashSet::new();
+        let mut pending: Vec<&str> = vec![role];
+
+        while let Some(current) = pending.pop() {
+            if ancestors.insert(current.to_string()) {
+                if let Some(role) = self.roles.get(current) {
+                    pending.extend(role.parents.iter().map(String::as_str));
+                }
+            }
+        }
+
+        ancestors
+    }
+
+    /// Returns the effective [`Permission`](crate::Permission) of a [`Role`](crate::Role): its own actions unioned with
+    /// the actions of all the [`Role`](crate::Role)s it inherits from, directly or transitively.
+    ///
+    /// # Panics:
+    ///
+    /// Panics if there is no [`Role`](crate::Role) with the name provided.
+    pub fn effective_role_perm(&self, name: &str) -> Permission {
+        if !self.roles.contains_key(name) {
+            panic!("No role exists with name '{}'", name);
+        }
+
+        let ancestors = self.ancestors(name);
+        self.manager.union_all(
+            ancestors
+                .iter()
+                .filter_map(|role| self.roles.get(role))
+                .map(Role::perm),
+        )
+    }
+
+    /// Returns the [`Role`](crate::Role) with the name provided.
+    pub fn role(&self, name: &str) -> Option<&Role> {
+        self.roles.get(name)
+    }
+
+    /// Returns the [`Role`](crate::Role)s of the [`RoleManager`](crate::RoleManager) in no particular order.
+    pub fn roles(&self) -> impl Iterator<Item = &Role> + '_ {
+        self.roles.values()
+    }
+
+    /// Assigns the [`Role`](crate::Role) with the name provided to a subject.  
+    /// Returns `true` if the [`Role`](crate::Role) was not already assigned to the subject.
+    ///
+    /// # Panics:
+    ///
+    /// Panics if there is no [`Role`](crate::Role) with the name provided.
+    pub fn assign(&mut self, subject: &str, role: &str) -> bool {
+        if !self.roles.contains_key(role) {
+            panic!("No role exists with name '{}'", role);
+        }
+
+        self.assignments
+            .entry(subject.to_string())
+            .or_default()
+            .insert(role.to_string())
+    }
+
+    /// Removes the [`Role`](crate::Role) with the name provided from a subject.  
+    /// Returns `true` if the [`Role`](crate::Role) was assigned to the subject.
+    pub fn unassign(&mut self, subject: &str, role: &str) -> bool {
+        let removed = match self.assignments.get_mut(subject) {
+            Some(roles) => roles.remove(role),
+            None => false,
+        };
+        self.assignments.retain(|_, roles| !roles.is_empty());
+
+        removed
+    }
+
+    /// Returns the names of the [`Role`](crate::Role)s assigned to a subject.
+    pub fn subject_roles(&self, subject: &str) -> HashSet<String> {
+        self.assignments.get(subject).cloned().unwrap_or_default()
+    }
+
+    // Returns the subjects with roles assigned in no particular order.
+    pub(crate) fn assigned_subjects(&self) -> impl Iterator<Item = &String> + '_ {
+        self.assignments.keys()
+    }
+
+    /// Returns the combined [`Permission`](crate::Permission) of a subject: the union of the effective
+    /// [`Permission`](crate::Permission)s of all the [`Role`](crate::Role)s assigned to it, including inherited actions.  
+    /// Subjects without [`Role`](crate::Role)s get an empty [`Permission`](crate::Permission) managed by the
+    /// [`PermissionManager`](crate::PermissionManager).
+    pub fn subject_perm(&self, subject: &str) -> Permission {
+        let roles: HashSet<String> = self
+            .assignments
+            .get(subject)
+            .into_iter()
+            .flatten()
+            .flat_map(|role| self.ancestors(role))
+            .collect();
+
+        self.manager.union_all(
+            roles
+                .iter()
+                .filter_map(|role| self.roles.get(role))
+                .map(Role::perm),
+        )
+    }
+
+    /// Returns an immutable [`RoleSnapshot`](crate::RoleSnapshot) of every [`Role`](crate::Role) and assignment of the
+    /// [`RoleManager`](crate::RoleManager), taken at the current time, to [`restore`](Self::restore()) it later.
+    ///
+    /// # Examples:
+    ///
+    /// ```
+    /// use simple_perm_manager::{PermissionManager, RoleManager};
+    ///
+    /// let mut roles = RoleManager::new(PermissionManager::from_json(r#"{"post": {"view": true}}"#));
+    /// let viewer = roles.manager().perm_from_json(r#"{"post": {"view": true}}"#);
+    /// roles.add_role("viewer", viewer);
+    /// roles.assign("alice", "viewer");
+    ///
+    /// let snapshot = roles.snapshot();
+    /// roles.remove_role("viewer");
+    /// assert!(!roles.subject_perm("alice").contains_action("post:view"));
+    ///
+    /// roles.restore(&snapshot);
+    /// assert!(roles.subject_perm("alice").contains_action("post:view"));
+    /// ```
+    pub fn snapshot(&self) -> RoleSnapshot {
+        RoleSnapshot {
+            manager_id: self.manager.id(),
+            roles: self.roles.clone(),
+            assignments: self.assignments.clone(),
+            taken_at: SystemTime::now(),
+        }
+    }
+
+    /// Replaces every [`Role`](crate::Role) and assignment of the [`RoleManager`](crate::RoleManager) with the ones of a
+    /// [`RoleSnapshot`](crate::RoleSnapshot), e.g. to roll back a bad bulk change.
+    ///
+    /// # Panics:
+    ///
+    /// Panics if the [`RoleSnapshot`](crate::RoleSnapshot) was taken from a [`RoleManager`](crate::RoleManager) of another
+    /// [`PermissionManager`](crate::PermissionManager).
+    pub fn restore(&mut self, snapshot: &RoleSnapshot) {
+        if snapshot.manager_id != self.manager.id() {
+            panic!("RoleSnapshot was not taken for the RoleManager PermissionManager");
+        }
+
+        self.roles = snapshot.roles.clone();
+        self.assignments = snapshot.assignments.clone();
+    }
+}
+
+/// Immutable copy of the [`Role`](crate::Role)s and assignments of a [`RoleManager`](crate::RoleManager), created with
+/// [`RoleManager::snapshot`](crate::RoleManager::snapshot()) and applied with [`RoleManager::restore`](crate::RoleManager::restore()).
+#[derive(Clone, Debug)]
+pub struct RoleSnapshot {
+    manager_id: ManagerId,
+    roles: HashMap<String, Role>,
+    assignments: HashMap<String, HashSet<String>>,
+    taken_at: SystemTime,
+}
+
+impl RoleSnapshot {
+    /// Returns the time the [`RoleSnapshot`](crate::RoleSnapshot) was taken.
+    pub fn taken_at(&self) -> SystemTime {
+        self.taken_at
+    }
+
+    /// Returns the [`ManagerId`](crate::ManagerId) of the [`PermissionManager`](crate::PermissionManager) of the
+    /// [`RoleManager`](crate::RoleManager) the [`RoleSnapshot`](crate::RoleSnapshot) was taken from.
+    pub fn manager_id(&self) -> ManagerId {
+        self.manager_id
+    }
+}
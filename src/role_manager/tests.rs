@@ -0,0 +1,260 @@
+use super::*;
+
+fn test_role_manager() -> RoleManager {
+    let mut roles = RoleManager::new(PermissionManager::from_json(
+        r#"{"post": {"view": true, "edit": true, "delete": true}}"#,
+    ));
+    roles.add_role(
+        "viewer",
+        roles
+            .manager()
+            .perm_from_json(r#"{"post": {"view": true}}"#),
+    );
+    roles.add_role(
+        "editor",
+        roles
+            .manager()
+            .perm_from_json(r#"{"post": {"edit": true}}"#),
+    );
+
+    roles
+}
+
+#[test]
+fn new_test() {
+    let manager = Arc::new(PermissionManager::from_json(r#"{"view": true}"#));
+    let roles = RoleManager::new(manager.clone());
+
+    assert_eq!(roles.manager().id(), manager.id());
+    assert_eq!(roles.roles().count(), 0);
+}
+
+#[test]
+fn add_role_test() {
+    let mut roles = test_role_manager();
+
+    assert_eq!(roles.role("viewer").unwrap().name(), "viewer");
+    assert_eq!(
+        *roles.role("viewer").unwrap().perm().get_actions(),
+        HashSet::from([String::from("post:view")])
+    );
+    assert_eq!(roles.roles().count(), 2);
+
+    match std::panic::catch_unwind(move || {
+        let perm = roles.manager().perm_from_json("{}");
+        roles.add_role("viewer", perm);
+    }) {
+        Ok(_) => panic!("adding a duplicated role should have panicked"),
+        Err(_) => (),
+    }
+
+    let mut roles = test_role_manager();
+    match std::panic::catch_unwind(move || {
+        roles.add_role(
+            "other",
+            Permission::from_json(r#"{"post": {"view": true}}"#),
+        );
+    }) {
+        Ok(_) => panic!("adding a role with a permission of another manager should have panicked"),
+        Err(_) => (),
+    }
+}
+
+#[test]
+fn remove_role_test() {
+    let mut roles = test_role_manager();
+    roles.assign("alice", "viewer");
+    roles.assign("alice", "editor");
+
+    assert_eq!(roles.remove_role("viewer").unwrap().name(), "viewer");
+    assert_eq!(roles.remove_role("viewer").is_none(), true);
+    assert_eq!(roles.role("viewer").is_none(), true);
+    assert_eq!(
+        roles.subject_roles("alice"),
+        HashSet::from([String::from("editor")])
+    );
+}
+
+#[test]
+fn assign_test() {
+    let mut roles = test_role_manager();
+
+    assert_eq!(roles.assign("alice", "viewer"), true);
+    assert_eq!(roles.assign("alice", "viewer"), false);
+    assert_eq!(
+        roles.subject_roles("alice"),
+        HashSet::from([String::from("viewer")])
+    );
+    assert_eq!(roles.subject_roles("bob").is_empty(), true);
+
+    match std::panic::catch_unwind(move || {
+        roles.assign("alice", "admin");
+    }) {
+        Ok(_) => panic!("assigning a role that does not exist should have panicked"),
+        Err(_) => (),
+    }
+}
+
+#[test]
+fn unassign_test() {
+    let mut roles = test_role_manager();
+    roles.assign("alice", "viewer");
+
+    assert_eq!(roles.unassign("alice", "viewer"), true);
+    assert_eq!(roles.unassign("alice", "viewer"), false);
+    assert_eq!(roles.unassign("bob", "viewer"), false);
+    assert_eq!(roles.subject_roles("alice").is_empty(), true);
+}
+
+#[test]
+fn subject_perm_test() {
+    let mut roles = test_role_manager();
+    roles.assign("alice", "viewer");
+    roles.assign("alice", "editor");
+    roles.assign("bob", "viewer");
+
+    let alice = roles.subject_perm("alice");
+    assert_eq!(roles.manager().validate_perm(&alice), true);
+    assert_eq!(
+        *alice.get_actions(),
+        HashSet::from([String::from("post:view"), String::from("post:edit")])
+    );
+    assert_eq!(
+        *roles.subject_perm("bob").get_actions(),
+        HashSet::from([String::from("post:view")])
+    );
+
+    let nobody = roles.subject_perm("nobody");
+    assert_eq!(nobody.get_actions().is_empty(), true);
+    assert_eq!(roles.manager().validate_perm(&nobody), true);
+}
+
+fn test_hierarchy() -> RoleManager {
+    let mut roles = test_role_manager();
+    roles.add_role(
+        "admin",
+        roles
+            .manager()
+            .perm_from_json(r#"{"post": {"delete": true}}"#),
+    );
+    roles.inherit("editor", "viewer");
+    roles.inherit("admin", "editor");
+
+    roles
+}
+
+#[test]
+fn inherit_test() {
+    let mut roles = test_hierarchy();
+
+    assert_eq!(
+        *roles.role("admin").unwrap().parents(),
+        HashSet::from([String::from("editor")])
+    );
+    assert_eq!(roles.inherit("admin", "viewer"), true);
+    assert_eq!(roles.inherit("admin", "viewer"), false);
+
+    let mut roles = test_hierarchy();
+    match std::panic::catch_unwind(move || {
+        roles.inherit("viewer", "admin");
+    }) {
+        Ok(_) => panic!("inheritance cycle should have panicked"),
+        Err(_) => (),
+    }
+    let mut roles = test_hierarchy();
+    match std::panic::catch_unwind(move || {
+        roles.inherit("viewer", "viewer");
+    }) {
+        Ok(_) => panic!("role inheriting from itself should have panicked"),
+        Err(_) => (),
+    }
+    let mut roles = test_hierarchy();
+    match std::panic::catch_unwind(move || {
+        roles.inherit("viewer", "other");
+    }) {
+        Ok(_) => panic!("inheriting from a role that does not exist should have panicked"),
+        Err(_) => (),
+    }
+}
+
+#[test]
+fn disinherit_test() {
+    let mut roles = test_hierarchy();
+
+    assert_eq!(roles.disinherit("admin", "editor"), true);
+    assert_eq!(roles.disinherit("admin", "editor"), false);
+    assert_eq!(roles.disinherit("other", "editor"), false);
+    assert_eq!(
+        *roles.effective_role_perm("admin").get_actions(),
+        HashSet::from([String::from("post:delete")])
+    );
+}
+
+#[test]
+fn effective_role_perm_test() {
+    let mut roles = test_hierarchy();
+
+    assert_eq!(
+        *roles.effective_role_perm("admin").get_actions(),
+        HashSet::from([
+            String::from("post:view"),
+            String::from("post:edit"),
+            String::from("post:delete"),
+        ])
+    );
+    assert_eq!(
+        *roles.effective_role_perm("viewer").get_actions(),
+        HashSet::from([String::from("post:view")])
+    );
+
+    // Removing a role removes its inheritances
+    roles.remove_role("editor");
+    assert_eq!(roles.role("admin").unwrap().parents().is_empty(), true);
+
+    match std::panic::catch_unwind(|| {
+        roles.effective_role_perm("editor");
+    }) {
+        Ok(_) => panic!("effective permission of a role that does not exist should have panicked"),
+        Err(_) => (),
+    }
+}
+
+#[test]
+fn subject_perm_inheritance_test() {
+    let mut roles = test_hierarchy();
+    roles.assign("alice", "admin");
+
+    assert_eq!(
+        *roles.subject_perm("alice").get_actions(),
+        *roles.effective_role_perm("admin").get_actions()
+    );
+}
+
+#[test]
+fn snapshot_test() {
+    let mut roles = test_role_manager();
+    roles.assign("alice", "viewer");
+
+    let snapshot = roles.snapshot();
+    assert_eq!(snapshot.manager_id(), roles.manager().id());
+
+    roles.remove_role("viewer");
+    let edit = roles
+        .manager()
+        .perm_from_json(r#"{"post": {"edit": true}}"#);
+    roles.add_role("writer", edit);
+    roles.assign("bob", "writer");
+
+    roles.restore(&snapshot);
+    assert_eq!(roles.role("writer").is_none(), true);
+    assert_eq!(roles.subject_roles("bob").is_empty(), true);
+    assert_eq!(
+        roles.subject_perm("alice").contains_action("post:view"),
+        true
+    );
+
+    let mut other = test_role_manager();
+    let result =
+        std::panic::catch_unwind(std::panic::AssertUnwindSafe(|| other.restore(&snapshot)));
+    assert_eq!(result.is_err(), true);
+}
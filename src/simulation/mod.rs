@@ -0,0 +1,60 @@
+use crate::permission::Permission;
+use std::fmt;
+
+//// TESTS ////
+#[cfg(test)]
+mod tests;
+
+/// Hypothetical change to the state of an [`Authorizer`](crate::Authorizer), applied by
+/// [`Authorizer::simulate`](crate::Authorizer::simulate()) without modifying the [`Authorizer`](crate::Authorizer).
+#[derive(Clone, Debug)]
+pub enum PolicyChange {
+    /// Grants the actions of the [`Permission`](crate::Permission) to a subject, see [`GrantStore::grant`](crate::GrantStore::grant()).
+    Grant { subject: String, perm: Permission },
+    /// Revokes the actions of the [`Permission`](crate::Permission) from a subject, see [`GrantStore::revoke`](crate::GrantStore::revoke()).
+    Revoke { subject: String, perm: Permission },
+    /// Adds a subject to a group, see [`GrantStore::add_member`](crate::GrantStore::add_member()).
+    AddMember { member: String, group: String },
+    /// Removes a subject from a group, see [`GrantStore::remove_member`](crate::GrantStore::remove_member()).
+    RemoveMember { member: String, group: String },
+    /// Adds a [`Role`](crate::Role), see [`RoleManager::add_role`](crate::RoleManager::add_role()).
+    AddRole { name: String, perm: Permission },
+    /// Removes a [`Role`](crate::Role), see [`RoleManager::remove_role`](crate::RoleManager::remove_role()).
+    RemoveRole { name: String },
+    /// Assigns a [`Role`](crate::Role) to a subject, see [`RoleManager::assign`](crate::RoleManager::assign()).
+    Assign { subject: String, role: String },
+    /// Removes a [`Role`](crate::Role) from a subject, see [`RoleManager::unassign`](crate::RoleManager::unassign()).
+    Unassign { subject: String, role: String },
+    /// Denies the actions of the [`Permission`](crate::Permission) to a subject, see [`Authorizer::deny`](crate::Authorizer::deny()).
+    Deny { subject: String, perm: Permission },
+    /// Removes deny rules of a subject, see [`Authorizer::undeny`](crate::Authorizer::undeny()).
+    Undeny { subject: String, perm: Permission },
+}
+
+/// Check whose outcome changes with the [`PolicyChange`](crate::PolicyChange)s given to
+/// [`Authorizer::simulate`](crate::Authorizer::simulate()).
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub struct OutcomeFlip {
+    /// Subject checked.
+    pub subject: String,
+    /// Action checked.
+    pub action: String,
+    /// Outcome of the check before the changes.
+    pub before: bool,
+    /// Outcome of the check after the changes.
+    pub after: bool,
+}
+
+impl fmt::Display for OutcomeFlip {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let outcome = |allowed: bool| if allowed { "allowed" } else { "denied" };
+        write!(
+            f,
+            "{} {}: {} -> {}",
+            self.subject,
+            self.action,
+            outcome(self.before),
+            outcome(self.after)
+        )
+    }
+}
@@ -0,0 +1,22 @@
+use super::*;
+
+#[test]
+fn display_test() {
+    let flip = OutcomeFlip {
+        subject: String::from("alice"),
+        action: String::from("post:delete"),
+        before: true,
+        after: false,
+    };
+
+    assert_eq!(flip.to_string(), "alice post:delete: allowed -> denied");
+    assert_eq!(
+        OutcomeFlip {
+            before: false,
+            after: true,
+            ..flip
+        }
+        .to_string(),
+        "alice post:delete: denied -> allowed"
+    );
+}
@@ -0,0 +1,155 @@
+use crate::permission_manager::PermissionManager;
+use crate::validation_error::ValidationError;
+use std::collections::{BTreeMap, BTreeSet};
+
+//// TESTS ////
+#[cfg(test)]
+mod tests;
+
+// Relation of the subject definition holding the members of a group
+const MEMBER_RELATION: &str = "member";
+// Prefix of the permission computed for each relation, as SpiceDB relations and permissions can not share names
+const PERMISSION_PREFIX: &str = "can_";
+
+// Returns `true` if the name is a valid SpiceDB definition, relation or permission identifier
+fn is_identifier(name: &str) -> bool {
+    let bytes = name.as_bytes();
+
+    (3..=64).contains(&bytes.len())
+        && bytes[0].is_ascii_lowercase()
+        && bytes[bytes.len() - 1] != b'_'
+        && bytes
+            .iter()
+            .all(|byte| byte.is_ascii_lowercase() || byte.is_ascii_digit() || *byte == b'_')
+}
+
+// Returns the definition (first group), all the groups and the relation (name) of an action, or `None` if it has no group
+// or its definition or relation are not valid SpiceDB identifiers
+fn split_action(action: &str, separator: char) -> Option<(&str, &str, &str)> {
+    let (groups, relation) = action.rsplit_once(separator)?;
+    let definition = groups.split(separator).next()?;
+
+    (is_identifier(definition) && is_identifier(relation)).then_some((definition, groups, relation))
+}
+
+impl PermissionManager {
+    /// Returns a [SpiceDB](https://authzed.com/spicedb) schema for the universe, so the same source of truth can drive both the
+    /// embedded checks and a SpiceDB cluster.
+    ///
+    /// The first group of each action names a definition and its last name a relation of that definition, matching the
+    /// tuples from [`GrantStore::to_relation_tuples`](crate::GrantStore::to_relation_tuples()): `"document:view"` and
+    /// `"document:readme:view"` both give a `view` relation to the `document` definition. Relations can be written for the
+    /// `user_type` definition and its group members (`user_type#member`).  
+    /// Each relation gets a `can_` permission, which also includes the permissions of the actions that imply it
+    /// (see [`add_implication`](Self::add_implication())), so checks should use `can_view` rather than `view`.
+    ///
+    /// # Examples:
+    ///
+    /// ```
+    /// use simple_perm_manager::PermissionManager;
+    ///
+    /// let mut manager = PermissionManager::from_json(r#"{"document": {"view": true, "edit": true}}"#);
+    /// manager.add_implication("document:edit", "document:view");
+    ///
+    /// assert_eq!(manager.to_spicedb_schema("user").unwrap(), "\
+    /// definition user {
+    ///     relation member: user | user#member
+    /// }
+    ///
+    /// definition document {
+    ///     relation edit: user | user#member
+    ///     relation view: user | user#member
+    ///
+    ///     permission can_edit = edit
+    ///     permission can_view = view + can_edit
+    /// }
+    /// ");
+    /// ```
+    ///
+    /// # Errors:
+    ///
+    /// Returns [`ValidationError::UntranslatableActions`](crate::ValidationError::UntranslatableActions) with the sorted actions
+    /// that have no group or whose definition or relation are not valid SpiceDB identifiers (lowercase letters, digits and `_`,
+    /// 3 to 64 characters), and the actions implying an action with different groups, which a schema can not express.
+    pub fn to_spicedb_schema(&self, user_type: &str) -> Result<String, ValidationError> {
+        let separator = self.separator();
+        let implications = self.implications();
+
+        let mut untranslatable: BTreeSet<String> = BTreeSet::new();
+        // Relations of each definition, with the relations of the actions implying them
+        let mut definitions: BTreeMap<&str, BTreeMap<&str, BTreeSet<&str>>> = BTreeMap::new();
+        let actions = self.to_actions();
+        for action in &actions {
+            match split_action(action, separator) {
+                Some((definition, _, relation)) => {
+                    definitions
+                        .entry(definition)
+                        .or_default()
+                        .entry(relation)
+                        .or_default();
+                }
+                None => {
+                    untranslatable.insert(action.clone());
+                }
+            }
+        }
+        for (action, implied) in &implications {
+            for implied in implied {
+                match (
+                    split_action(action, separator),
+                    split_action(implied, separator),
+                ) {
+                    (
+                        Some((definition, groups, relation)),
+                        Some((_, implied_groups, implied_relation)),
+                    ) if groups == implied_groups => {
+                        definitions
+                            .entry(definition)
+                            .or_default()
+                            .entry(implied_relation)
+                            .or_default()
+                            .insert(relation);
+                    }
+                    _ => {
+                        untranslatable.insert(action.clone());
+                    }
+                }
+            }
+        }
+        if !untranslatable.is_empty() {
+            return Err(ValidationError::UntranslatableActions(
+                untranslatable.into_iter().collect(),
+            ));
+        }
+
+        let subjects = format!("{} | {}#{}", user_type, user_type, MEMBER_RELATION);
+        let mut schema = format!(
+            "definition {} {{\n    relation {}: {}\n}}\n",
+            user_type, MEMBER_RELATION, subjects
+        );
+        for (definition, relations) in definitions {
+            schema.push_str(&format!("\ndefinition {} {{\n", definition));
+            for relation in relations.keys() {
+                schema.push_str(&format!("    relation {}: {}\n", relation, subjects));
+            }
+            schema.push('\n');
+            for (relation, implying) in &relations {
+                let mut terms = vec![relation.to_string()];
+                terms.extend(
+                    implying
+                        .iter()
+                        .map(|implying| format!("{}{}", PERMISSION_PREFIX, implying)),
+                );
+                schema.push_str(&format!(
+                    "    permission {}{} = {}\n",
+                    PERMISSION_PREFIX,
+                    relation,
+                    terms.join(" + ")
+                ));
+            }
+            schema.push_str("}\n");
+        }
+
+        Ok(schema)
+    }
+}
@@ -0,0 +1,95 @@
+use super::*;
+
+#[test]
+fn is_identifier_test() {
+    assert_eq!(is_identifier("view"), true);
+    assert_eq!(is_identifier("can_view_2"), true);
+    assert_eq!(is_identifier("get"), true);
+    assert_eq!(is_identifier("do"), false);
+    assert_eq!(is_identifier("View"), false);
+    assert_eq!(is_identifier("2fa"), false);
+    assert_eq!(is_identifier("view_"), false);
+    assert_eq!(is_identifier("pods/log"), false);
+    assert_eq!(is_identifier(&"a".repeat(64)), true);
+    assert_eq!(is_identifier(&"a".repeat(65)), false);
+}
+
+#[test]
+fn split_action_test() {
+    assert_eq!(
+        split_action("document:view", ':'),
+        Some(("document", "document", "view"))
+    );
+    assert_eq!(
+        split_action("document:readme:view", ':'),
+        Some(("document", "document:readme", "view"))
+    );
+    assert_eq!(split_action("view", ':'), None);
+    assert_eq!(split_action("Document:view", ':'), None);
+}
+
+#[test]
+fn to_spicedb_schema_test() {
+    let mut manager = PermissionManager::from_json(
+        r#"{
+            "document": {"view": true, "edit": true, "delete": true, "readme": {"view": true, "share": true}},
+            "folder": {"view": true}
+        }"#,
+    );
+    manager.add_implication("document:delete", "document:edit");
+    manager.add_implication("document:edit", "document:view");
+    manager.add_implication("document:delete", "document:view");
+    manager.add_implication("document:readme:share", "document:readme:view");
+
+    assert_eq!(
+        manager.to_spicedb_schema("account").unwrap(),
+        "\
+definition account {
+    relation member: account | account#member
+}
+
+definition document {
+    relation delete: account | account#member
+    relation edit: account | account#member
+    relation share: account | account#member
+    relation view: account | account#member
+
+    permission can_delete = delete
+    permission can_edit = edit + can_delete
+    permission can_share = share
+    permission can_view = view + can_delete + can_edit + can_share
+}
+
+definition folder {
+    relation view: account | account#member
+
+    permission can_view = view
+}
+"
+    );
+    assert_eq!(
+        PermissionManager::from_actions(Default::default()).to_spicedb_schema("user"),
+        Ok(String::from(
+            "definition user {\n    relation member: user | user#member\n}\n"
+        ))
+    );
+}
+
+#[test]
+fn to_spicedb_schema_untranslatable_test() {
+    let mut manager = PermissionManager::from_json(
+        r#"{"export": true, "Report": {"view": true}, "document": {"view": true, "readme": {"edit": true}}, "folder": {"edit": true}}"#,
+    );
+    manager.add_implication("folder:edit", "document:view");
+    manager.add_implication("document:readme:edit", "document:view");
+
+    assert_eq!(
+        manager.to_spicedb_schema("user"),
+        Err(ValidationError::UntranslatableActions(vec![
+            String::from("Report:view"),
+            String::from("document:readme:edit"),
+            String::from("export"),
+            String::from("folder:edit"),
+        ]))
+    );
+}
@@ -0,0 +1,27 @@
+// Metrics emitted through the `metrics` facade when the `metrics` feature is enabled, no-ops otherwise.
+use crate::decision::Decision;
+use std::time::Instant;
+
+// Counts a check with its decision and records how long it took since `started`.
+pub(crate) fn record_decision(decision: Decision, started: Instant) {
+    #[cfg(feature = "metrics")]
+    {
+        metrics::counter!("simple_perm_manager_checks_total", "decision" => decision.to_string())
+            .increment(1);
+        metrics::histogram!("simple_perm_manager_check_duration_seconds").record(started.elapsed());
+    }
+    #[cfg(not(feature = "metrics"))]
+    let _ = (decision, started);
+}
+
+// Counts a permission rejected by the universe of a manager.
+pub(crate) fn record_validation_failure() {
+    #[cfg(feature = "metrics")]
+    metrics::counter!("simple_perm_manager_validation_failures_total").increment(1);
+}
+
+// Counts an actions JSON string that could not be parsed.
+pub(crate) fn record_parse_error() {
+    #[cfg(feature = "metrics")]
+    metrics::counter!("simple_perm_manager_parse_errors_total").increment(1);
+}
@@ -0,0 +1,124 @@
+use std::fmt;
+
+//// TESTS ////
+#[cfg(test)]
+mod tests;
+
+/// Kind of grant that supplied an action to a subject, see [`TraceStep::Granted`](crate::TraceStep::Granted).
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub enum GrantSource {
+    /// Permanent grant of the [`GrantStore`](crate::GrantStore).
+    Direct,
+    /// Temporary grant of the [`GrantStore`](crate::GrantStore).
+    Temporary,
+    /// Conditional grant of the [`GrantStore`](crate::GrantStore).
+    Conditional,
+    /// [`Role`](crate::Role) assigned to the subject, including the actions it inherits.
+    Role(String),
+}
+
+/// Single finding of a decision [`Trace`](crate::Trace). Subjects are the evaluated subject or one of the groups it belongs to.
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub enum TraceStep {
+    /// The action is not in the universe of the [`PermissionManager`](crate::PermissionManager).
+    UnknownAction,
+    /// A grant of a subject supplied the action.
+    Granted {
+        subject: String,
+        source: GrantSource,
+    },
+    /// A temporary grant of a subject has the action but is not active.
+    InactiveGrant { subject: String },
+    /// A conditional grant of a subject has the action but its [`Condition`](crate::Condition) does not hold.
+    ConditionFailed { subject: String },
+    /// No grant or role supplies the action.
+    MissingAction,
+    /// A deny rule of a subject blocks the action.
+    DeniedBy { subject: String },
+    /// The action is not allowed on the resource by the [`ObjectAcl`](crate::ObjectAcl).
+    NotAllowedOnResource { resource: String },
+}
+
+/// Structured explanation of an authorization decision, listing why the action was allowed or denied.
+///
+/// # Examples:
+///
+/// ```
+/// use simple_perm_manager::{Authorizer, Context, Decision, GrantSource, PermissionManager, TraceStep};
+///
+/// let mut auth = Authorizer::new(PermissionManager::from_json(r#"{"doc": {"view": true, "edit": true}}"#));
+/// let view = auth.manager().perm_from_json(r#"{"doc": {"view": true}}"#);
+/// auth.grants_mut().grant(String::from("alice"), &view);
+/// auth.acl_mut().grant_on(String::from("readme"), &view);
+///
+/// let (decision, trace) = auth.decide_explained("alice", "readme", "doc:view", &Context::new());
+/// assert_eq!(decision, Decision::Allow);
+/// assert_eq!(trace.steps(), &[TraceStep::Granted { subject: String::from("alice"), source: GrantSource::Direct }]);
+///
+/// let (decision, trace) = auth.decide_explained("alice", "readme", "doc:edit", &Context::new());
+/// assert_eq!(decision, Decision::Deny);
+/// assert_eq!(trace.to_string(), "no grant supplies the action\nnot allowed on resource readme");
+/// ```
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct Trace {
+    steps: Vec<TraceStep>,
+}
+
+impl Trace {
+    #[doc(hidden)]
+    /// Creates an empty trace.
+    pub fn new() -> Trace {
+        Trace::default()
+    }
+
+    #[doc(hidden)]
+    /// Appends a step to the trace.
+    pub fn push(&mut self, step: TraceStep) {
+        self.steps.push(step);
+    }
+
+    /// Returns the steps of the [`Trace`](crate::Trace) in evaluation order.
+    pub fn steps(&self) -> &[TraceStep] {
+        &self.steps
+    }
+}
+
+impl fmt::Display for GrantSource {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            GrantSource::Direct => write!(f, "direct grant"),
+            GrantSource::Temporary => write!(f, "temporary grant"),
+            GrantSource::Conditional => write!(f, "conditional grant"),
+            GrantSource::Role(role) => write!(f, "role {}", role),
+        }
+    }
+}
+
+impl fmt::Display for TraceStep {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            TraceStep::UnknownAction => write!(f, "action not in the permission manager universe"),
+            TraceStep::Granted { subject, source } => {
+                write!(f, "granted to {} by {}", subject, source)
+            }
+            TraceStep::InactiveGrant { subject } => {
+                write!(f, "temporary grant of {} not active", subject)
+            }
+            TraceStep::ConditionFailed { subject } => {
+                write!(f, "condition of conditional grant of {} failed", subject)
+            }
+            TraceStep::MissingAction => write!(f, "no grant supplies the action"),
+            TraceStep::DeniedBy { subject } => write!(f, "denied by deny rule of {}", subject),
+            TraceStep::NotAllowedOnResource { resource } => {
+                write!(f, "not allowed on resource {}", resource)
+            }
+        }
+    }
+}
+
+impl fmt::Display for Trace {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let steps: Vec<String> = self.steps.iter().map(TraceStep::to_string).collect();
+        write!(f, "{}", steps.join("\n"))
+    }
+}
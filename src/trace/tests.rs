@@ -0,0 +1,68 @@
+use super::*;
+
+#[test]
+fn push_test() {
+    let mut trace = Trace::new();
+    trace.push(TraceStep::MissingAction);
+    trace.push(TraceStep::DeniedBy {
+        subject: String::from("alice"),
+    });
+
+    assert_eq!(
+        trace.steps(),
+        &[
+            TraceStep::MissingAction,
+            TraceStep::DeniedBy {
+                subject: String::from("alice")
+            }
+        ]
+    );
+}
+
+#[test]
+fn display_test() {
+    let alice = String::from("alice");
+    let steps = [
+        (
+            TraceStep::UnknownAction,
+            "action not in the permission manager universe",
+        ),
+        (
+            TraceStep::Granted {
+                subject: alice.clone(),
+                source: GrantSource::Direct,
+            },
+            "granted to alice by direct grant",
+        ),
+        (
+            TraceStep::Granted {
+                subject: alice.clone(),
+                source: GrantSource::Role(String::from("editor")),
+            },
+            "granted to alice by role editor",
+        ),
+        (
+            TraceStep::InactiveGrant {
+                subject: alice.clone(),
+            },
+            "temporary grant of alice not active",
+        ),
+        (
+            TraceStep::ConditionFailed {
+                subject: alice.clone(),
+            },
+            "condition of conditional grant of alice failed",
+        ),
+        (
+            TraceStep::NotAllowedOnResource {
+                resource: String::from("readme"),
+            },
+            "not allowed on resource readme",
+        ),
+    ];
+
+    for (step, display) in steps {
+        assert_eq!(step.to_string(), display);
+    }
+    assert_eq!(Trace::new().to_string(), "");
+}
@@ -0,0 +1,169 @@
+use crate::permission::Permission;
+use crate::validation_error::ValidationError;
+use std::collections::HashSet;
+use std::hash::Hash;
+
+//// TESTS ////
+#[cfg(test)]
+mod tests;
+
+/// Trait for user defined action types that can be used in a typed [`Permission<A>`](crate::Permission).
+///
+/// An action type must map every value to its action string and back. Enums generated with
+/// [`define_permissions!`](crate::define_permissions) implement it automatically.
+///
+/// The string-based API is the default `Permission<String>`, so `String` does not implement [`Action`](crate::Action).
+///
+/// # Examples:
+///
+/// ```
+/// use simple_perm_manager::Action;
+///
+/// #[derive(Clone, Debug, PartialEq, Eq, Hash)]
+/// enum PostAction {
+///     View,
+///     Publish,
+/// }
+///
+/// impl Action for PostAction {
+///     fn as_str(&self) -> &str {
+///         match self {
+///             PostAction::View => "post:view",
+///             PostAction::Publish => "post:publish",
+///         }
+///     }
+///
+///     fn from_str(action: &str) -> Option<PostAction> {
+///         match action {
+///             "post:view" => Some(PostAction::View),
+///             "post:publish" => Some(PostAction::Publish),
+///             _ => None,
+///         }
+///     }
+/// }
+/// ```
+pub trait Action: Clone + Eq + Hash {
+    /// Returns the action string for the value.
+    fn as_str(&self) -> &str;
+
+    /// Returns the value for an action string or `None` if the string does not correspond to any value.
+    fn from_str(action: &str) -> Option<Self>;
+}
+
+/// Typed [`Permission`](crate::Permission)s, whose actions are values of a user defined [`Action`](crate::Action) type.
+///
+/// The operations that do not depend on the action type ([`union`](crate::Permission::union()),
+/// [`difference`](crate::Permission::difference()), [`contains`](crate::Permission::contains()),
+/// [`is_managed`](crate::Permission::is_managed()), ...) are shared with the string-based `Permission<String>`.
+/// Create managed ones with [`PermissionManager::typed_perm_from`](crate::PermissionManager::typed_perm_from()) and
+/// unmanaged ones by collecting actions.
+///
+/// # Examples:
+///
+/// ```
+/// use simple_perm_manager::{define_permissions, Permission};
+///
+/// define_permissions! {
+///     enum PostAction { "post": { "view": true, "publish": true } }
+/// }
+///
+/// let manager = PostAction::permission_manager();
+/// let perm: Permission<PostAction> = manager.typed_perm_from([PostAction::PostView]);
+///
+/// assert!(perm.contains_action(&PostAction::PostView));
+/// assert!(!perm.contains_action(&PostAction::PostPublish));
+/// ```
+impl<A: Action> Permission<A> {
+    /// Creates a typed [`Permission`](crate::Permission) from a string-based one, keeping its manager.
+    ///
+    /// # Examples:
+    ///
+    /// ```
+    /// use simple_perm_manager::{define_permissions, Permission};
+    ///
+    /// define_permissions! {
+    ///     enum PostAction { "post": { "view": true, "publish": true } }
+    /// }
+    ///
+    /// let perm = Permission::from_json(r#"{"post:view": true}"#);
+    /// let typed: Permission<PostAction> = Permission::from_untyped(perm);
+    ///
+    /// assert!(typed.contains_action(&PostAction::PostView));
+    /// ```
+    ///
+    /// # Panics:
+    ///
+    /// Panics if any action of the [`Permission`](crate::Permission) is not a valid value of the [`Action`](crate::Action) type.
+    pub fn from_untyped(perm: Permission) -> Permission<A> {
+        if let Some(action) = perm.actions().find(|action| A::from_str(action).is_none()) {
+            panic!("Permission action '{}' is not a valid typed action", action);
+        }
+
+        perm.retyped()
+    }
+
+    /// Returns the string-based [`Permission`](crate::Permission) with the same actions and manager.
+    pub fn into_untyped(self) -> Permission {
+        self.retyped()
+    }
+
+    /// Returns the actions of the typed [`Permission`](crate::Permission).
+    ///
+    /// # Examples:
+    ///
+    /// ```
+    /// use std::collections::HashSet;
+    /// use simple_perm_manager::{define_permissions, Permission};
+    ///
+    /// define_permissions! {
+    ///     enum PostAction { "post": { "view": true, "publish": true } }
+    /// }
+    ///
+    /// let perm: Permission<PostAction> = [PostAction::PostView].into_iter().collect();
+    /// assert_eq!(perm.get_actions(), Ok(HashSet::from([PostAction::PostView])));
+    /// ```
+    ///
+    /// # Errors:
+    ///
+    /// Returns [`ValidationError::UntranslatableActions`](crate::ValidationError::UntranslatableActions) with the sorted actions
+    /// that are not a valid value of the [`Action`](crate::Action) type, e.g. actions stored under their canonical name by a
+    /// [`PermissionManager`](crate::PermissionManager) with aliases.
+    pub fn get_actions(&self) -> Result<HashSet<A>, ValidationError> {
+        let mut untranslatable = Vec::new();
+        let mut actions = HashSet::new();
+        for action in self.actions() {
+            match A::from_str(action) {
+                Some(typed) => {
+                    actions.insert(typed);
+                }
+                None => untranslatable.push(action.to_string()),
+            }
+        }
+
+        if !untranslatable.is_empty() {
+            untranslatable.sort();
+            return Err(ValidationError::UntranslatableActions(untranslatable));
+        }
+
+        Ok(actions)
+    }
+
+    /// Returns `true` if the typed [`Permission`](crate::Permission) contains the action used as argument, resolving the
+    /// aliases, implications and groups of its manager as [`Permission::contains_action`](crate::Permission::contains_action()) does.
+    pub fn contains_action(&self, action: &A) -> bool {
+        self.holds(action.as_str())
+    }
+}
+
+// Collecting typed actions creates an unmanaged permission
+impl<A: Action> FromIterator<A> for Permission<A> {
+    fn from_iter<I: IntoIterator<Item = A>>(actions: I) -> Permission<A> {
+        Permission::from_actions(
+            actions
+                .into_iter()
+                .map(|action| action.as_str().to_string())
+                .collect(),
+        )
+        .retyped()
+    }
+}
@@ -0,0 +1,135 @@
+use super::*;
+use crate::permission_manager::PermissionManager;
+
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+enum TestAction {
+    View,
+    Edit,
+}
+
+impl Action for TestAction {
+    fn as_str(&self) -> &str {
+        match self {
+            TestAction::View => "view",
+            TestAction::Edit => "edit",
+        }
+    }
+
+    fn from_str(action: &str) -> Option<TestAction> {
+        match action {
+            "view" => Some(TestAction::View),
+            "edit" => Some(TestAction::Edit),
+            _ => None,
+        }
+    }
+}
+
+#[test]
+fn from_iter_test() {
+    let p: Permission<TestAction> = [TestAction::View].into_iter().collect();
+
+    assert_eq!(p.get_actions(), Ok(HashSet::from([TestAction::View])));
+    assert_eq!(
+        *p.into_untyped().get_actions(),
+        HashSet::from([String::from("view")])
+    );
+}
+
+#[test]
+fn from_untyped_test() {
+    let p: Permission<TestAction> =
+        Permission::from_untyped(Permission::from_json(r#"{"view": true, "edit": true}"#));
+
+    assert_eq!(
+        p.get_actions(),
+        Ok(HashSet::from([TestAction::View, TestAction::Edit]))
+    );
+    assert_eq!(p.is_managed(), false);
+
+    match std::panic::catch_unwind(|| {
+        Permission::<TestAction>::from_untyped(Permission::from_json(r#"{"other": true}"#));
+    }) {
+        Ok(_) => {
+            panic!("permission with actions not valid for the action type should have panicked")
+        }
+        Err(_) => (),
+    }
+}
+
+#[test]
+fn into_untyped_test() {
+    let pm = PermissionManager::from_actions(HashSet::from([String::from("edit")]));
+    let p = pm.typed_perm_from([TestAction::Edit]).into_untyped();
+
+    assert_eq!(*p.get_actions(), HashSet::from([String::from("edit")]));
+    assert_eq!(pm.validate_perm(&p), true);
+}
+
+#[test]
+fn get_actions_test() {
+    // Actions stored under their canonical name are not valid values of the action type
+    let mut pm = PermissionManager::from_actions(HashSet::from([
+        String::from("view"),
+        String::from("read"),
+        String::from("write"),
+    ]));
+    pm.add_alias("edit", "write");
+    let p = pm.typed_perm_from([TestAction::View, TestAction::Edit]);
+
+    assert_eq!(p.contains_action(&TestAction::Edit), true);
+    assert_eq!(
+        p.get_actions(),
+        Err(ValidationError::UntranslatableActions(vec![String::from(
+            "write"
+        )]))
+    );
+}
+
+#[test]
+fn operations_test() {
+    let pm = PermissionManager::from_actions(HashSet::from([
+        String::from("view"),
+        String::from("edit"),
+    ]));
+    let view = pm.typed_perm_from([TestAction::View]);
+    let edit = pm.typed_perm_from([TestAction::Edit]);
+    let both = view.union(&edit);
+
+    assert_eq!(
+        both.get_actions(),
+        Ok(HashSet::from([TestAction::View, TestAction::Edit]))
+    );
+    assert_eq!(
+        both.difference(&edit).get_actions(),
+        Ok(HashSet::from([TestAction::View]))
+    );
+    assert_eq!(both.contains(&view), true);
+    assert_eq!(view.contains(&both), false);
+    assert_eq!(both.contains_action(&TestAction::Edit), true);
+    assert_eq!(view.contains_action(&TestAction::Edit), false);
+    assert_eq!(view.is_managed(), true);
+
+    // Operations between different managers panic as with string-based permissions
+    let unmanaged: Permission<TestAction> = [TestAction::View].into_iter().collect();
+    match std::panic::catch_unwind(|| {
+        view.union(&unmanaged);
+    }) {
+        Ok(_) => panic!("operation with perm with different manager_id should have panicked"),
+        Err(_) => (),
+    }
+}
+
+#[test]
+fn typed_perm_from_test() {
+    let pm = PermissionManager::from_actions(HashSet::from([String::from("view")]));
+    let p = pm.typed_perm_from([TestAction::View]);
+
+    assert_eq!(p.manager_id(), Some(pm.id()));
+
+    match std::panic::catch_unwind(|| {
+        pm.typed_perm_from([TestAction::Edit]);
+    }) {
+        Ok(_) => panic!("actions supplied for creating a permission are not present in permission manager universe"),
+        Err(_) => (),
+    }
+}
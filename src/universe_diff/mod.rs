@@ -0,0 +1,171 @@
+use serde_json::{Map, Value};
+use std::collections::{HashMap, HashSet};
+use std::fmt;
+
+//// TESTS ////
+#[cfg(test)]
+mod tests;
+
+/// Differences between the universes of two [`PermissionManager`](crate::PermissionManager)s, created with
+/// [`PermissionManager::diff_universe`](crate::PermissionManager::diff_universe()). Actions are sorted.
+///
+/// Displayed as release notes listing added (`+`), removed (`-`) and moved (`~`) actions.
+///
+/// # Examples:
+///
+/// ```
+/// use simple_perm_manager::PermissionManager;
+///
+/// let old = PermissionManager::from_json(r#"{"post": {"view": true, "audit": true}, "publish": true}"#);
+/// let new = PermissionManager::from_json(r#"{"post": {"view": true, "edit": true, "publish": true}}"#);
+///
+/// let diff = old.diff_universe(&new);
+/// assert_eq!(diff.added(), &[String::from("post:edit")]);
+/// assert_eq!(diff.removed(), &[String::from("post:audit")]);
+/// assert_eq!(diff.moved(), &[(String::from("publish"), String::from("post:publish"))]);
+/// assert_eq!(diff.to_string(), "added:\n  + post:edit\nremoved:\n  - post:audit\nmoved:\n  ~ publish -> post:publish");
+/// ```
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct UniverseDiff {
+    added: Vec<String>,
+    removed: Vec<String>,
+    moved: Vec<(String, String)>,
+}
+
+impl UniverseDiff {
+    // Creates the diff from the old to the new universe actions. Renames recorded in `renames` (old name to new name) and removed
+    // actions whose name is only found in a single added action of another group are reported as moved.
+    pub(crate) fn new(
+        old: &HashSet<String>,
+        new: &HashSet<String>,
+        renames: &HashMap<String, String>,
+        separator: char,
+    ) -> UniverseDiff {
+        let mut added: HashSet<&String> = new.difference(old).collect();
+        let mut removed: HashSet<&String> = old.difference(new).collect();
+        let mut moved: Vec<(String, String)> = Vec::new();
+
+        for (from, to) in renames {
+            if removed.contains(from) && added.contains(to) {
+                moved.push((from.clone(), to.clone()));
+            }
+        }
+        for (from, to) in &moved {
+            removed.remove(from);
+            added.remove(to);
+        }
+
+        let name = |action: &str| {
+            action
+                .rsplit(separator)
+                .next()
+                .unwrap_or(action)
+                .to_string()
+        };
+        let mut added_by_name: HashMap<String, Vec<&String>> = HashMap::new();
+        for action in &added {
+            added_by_name.entry(name(action)).or_default().push(action);
+        }
+        let mut removed_by_name: HashMap<String, Vec<&String>> = HashMap::new();
+        for action in &removed {
+            removed_by_name
+                .entry(name(action))
+                .or_default()
+                .push(action);
+        }
+        for (name, from) in removed_by_name {
+            if let (&[from], Some(&[to])) =
+                (from.as_slice(), added_by_name.get(&name).map(Vec::as_slice))
+            {
+                moved.push((from.clone(), to.clone()));
+                removed.remove(from);
+                added.remove(to);
+            }
+        }
+
+        let sorted = |actions: HashSet<&String>| {
+            let mut actions: Vec<String> = actions.into_iter().cloned().collect();
+            actions.sort();
+            actions
+        };
+        moved.sort();
+
+        UniverseDiff {
+            added: sorted(added),
+            removed: sorted(removed),
+            moved,
+        }
+    }
+
+    /// Returns the actions only in the new universe.
+    pub fn added(&self) -> &[String] {
+        &self.added
+    }
+
+    /// Returns the actions only in the old universe.
+    pub fn removed(&self) -> &[String] {
+        &self.removed
+    }
+
+    /// Returns the actions of the old universe renamed or moved to another group in the new universe, as `(old, new)` pairs.  
+    /// An action is moved if it was renamed with [`PermissionManager::rename_action`](crate::PermissionManager::rename_action()) or
+    /// [`PermissionManager::rename_group`](crate::PermissionManager::rename_group()), or if its name, without groups, is the name of
+    /// a single removed action and a single added action.
+    pub fn moved(&self) -> &[(String, String)] {
+        &self.moved
+    }
+
+    /// Returns `true` if both universes have the same actions.
+    pub fn is_empty(&self) -> bool {
+        self.added.is_empty() && self.removed.is_empty() && self.moved.is_empty()
+    }
+
+    /// Serializes the [`UniverseDiff`](crate::UniverseDiff) into a JSON string with the format
+    /// `{"added": [...], "moved": [{"from": "...", "to": "..."}], "removed": [...]}`.
+    pub fn to_json(&self) -> String {
+        let moved = self
+            .moved
+            .iter()
+            .map(|(from, to)| {
+                let mut map = Map::new();
+                map.insert(String::from("from"), Value::from(from.as_str()));
+                map.insert(String::from("to"), Value::from(to.as_str()));
+                Value::Object(map)
+            })
+            .collect();
+
+        let mut map = Map::new();
+        map.insert(String::from("added"), Value::from(self.added.clone()));
+        map.insert(String::from("removed"), Value::from(self.removed.clone()));
+        map.insert(String::from("moved"), Value::Array(moved));
+        Value::Object(map).to_string()
+    }
+}
+
+impl fmt::Display for UniverseDiff {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if self.is_empty() {
+            return write!(f, "no differences");
+        }
+
+        let mut lines = Vec::new();
+        if !self.added.is_empty() {
+            lines.push(String::from("added:"));
+            lines.extend(self.added.iter().map(|action| format!("  + {}", action)));
+        }
+        if !self.removed.is_empty() {
+            lines.push(String::from("removed:"));
+            lines.extend(self.removed.iter().map(|action| format!("  - {}", action)));
+        }
+        if !self.moved.is_empty() {
+            lines.push(String::from("moved:"));
+            lines.extend(
+                self.moved
+                    .iter()
+                    .map(|(from, to)| format!("  ~ {} -> {}", from, to)),
+            );
+        }
+
+        write!(f, "{}", lines.join("\n"))
+    }
+}
@@ -0,0 +1,68 @@
+use super::*;
+
+fn actions(actions: &[&str]) -> HashSet<String> {
+    actions.iter().map(|action| action.to_string()).collect()
+}
+
+#[test]
+fn new_test() {
+    let old = actions(&["post:view", "post:remove", "user:view", "login"]);
+    let new = actions(&[
+        "post:view",
+        "post:delete",
+        "account:view",
+        "admin:view",
+        "auth:login",
+    ]);
+    let renames = HashMap::from([(String::from("post:remove"), String::from("post:delete"))]);
+
+    let diff = UniverseDiff::new(&old, &new, &renames, ':');
+    assert_eq!(
+        diff.added(),
+        &[String::from("account:view"), String::from("admin:view")]
+    );
+    assert_eq!(diff.removed(), &[String::from("user:view")]);
+    assert_eq!(
+        diff.moved(),
+        &[
+            (String::from("login"), String::from("auth:login")),
+            (String::from("post:remove"), String::from("post:delete")),
+        ]
+    );
+    assert_eq!(diff.is_empty(), false);
+    assert_eq!(
+        UniverseDiff::new(&old, &old, &HashMap::new(), ':').is_empty(),
+        true
+    );
+}
+
+#[test]
+fn display_test() {
+    let diff = UniverseDiff::new(
+        &actions(&["a:view", "b:edit"]),
+        &actions(&["c:edit"]),
+        &HashMap::new(),
+        ':',
+    );
+
+    assert_eq!(
+        diff.to_string(),
+        "removed:\n  - a:view\nmoved:\n  ~ b:edit -> c:edit"
+    );
+    assert_eq!(UniverseDiff::default().to_string(), "no differences");
+}
+
+#[test]
+fn to_json_test() {
+    let diff = UniverseDiff::new(
+        &actions(&["a:view", "b:edit"]),
+        &actions(&["c:edit", "d"]),
+        &HashMap::new(),
+        ':',
+    );
+
+    assert_eq!(
+        diff.to_json(),
+        r#"{"added":["d"],"moved":[{"from":"b:edit","to":"c:edit"}],"removed":["a:view"]}"#
+    );
+}
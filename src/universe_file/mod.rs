@@ -0,0 +1,58 @@
+use crate::load_error::LoadError;
+use crate::permission::try_parse_actions;
+use crate::telemetry;
+use crate::validation_error::ValidationError;
+use std::collections::HashSet;
+use std::fs;
+use std::path::Path;
+
+//// TESTS ////
+#[cfg(test)]
+mod tests;
+
+// Returns the content of the file at `path`.
+pub(crate) fn read_file(path: &Path) -> Result<String, LoadError> {
+    fs::read_to_string(path)
+        .map_err(|error| LoadError::Io(format!("{}: {}", path.display(), error)))
+}
+
+// Returns the actions of a universe file content, divided by `separator`. The content is read as YAML if the path ends in
+// '.yaml' or '.yml' and the `yaml` feature is enabled, and as JSON otherwise.
+pub(crate) fn parse_universe(
+    path: &Path,
+    content: &str,
+    separator: char,
+) -> Result<HashSet<String>, LoadError> {
+    let json = if is_yaml(path) {
+        yaml_to_json(content)?
+    } else {
+        content.to_string()
+    };
+
+    try_parse_actions(&json, separator).map_err(|error| {
+        telemetry::record_parse_error();
+        LoadError::Invalid(ValidationError::InvalidJson(error))
+    })
+}
+
+fn is_yaml(path: &Path) -> bool {
+    cfg!(feature = "yaml")
+        && path
+            .extension()
+            .is_some_and(|extension| extension == "yaml" || extension == "yml")
+}
+
+#[cfg(feature = "yaml")]
+fn yaml_to_json(content: &str) -> Result<String, LoadError> {
+    serde_yaml::from_str::<serde_json::Value>(content)
+        .map(|value| value.to_string())
+        .map_err(|error| {
+            telemetry::record_parse_error();
+            LoadError::Invalid(ValidationError::InvalidJson(error.to_string()))
+        })
+}
+
+#[cfg(not(feature = "yaml"))]
+fn yaml_to_json(content: &str) -> Result<String, LoadError> {
+    Ok(content.to_string())
+}
@@ -0,0 +1,63 @@
+use super::*;
+
+#[test]
+fn read_file_test() {
+    let path = std::env::temp_dir().join(format!("{}.json", uuid::Uuid::new_v4()));
+
+    assert_eq!(
+        matches!(read_file(&path), Err(LoadError::Io(message)) if message.starts_with(&path.display().to_string())),
+        true
+    );
+    fs::write(&path, r#"{"view": true}"#).unwrap();
+    assert_eq!(read_file(&path), Ok(String::from(r#"{"view": true}"#)));
+    fs::remove_file(&path).unwrap();
+}
+
+#[test]
+fn parse_universe_test() {
+    assert_eq!(
+        parse_universe(
+            Path::new("universe.json"),
+            r#"{"building": {"view": true}, "user": {"edit": true}}"#,
+            '.'
+        ),
+        Ok(HashSet::from([
+            String::from("building.view"),
+            String::from("user.edit")
+        ]))
+    );
+    assert_eq!(
+        matches!(
+            parse_universe(Path::new("universe.json"), r#"{"view": "yes"}"#, ':'),
+            Err(LoadError::Invalid(ValidationError::InvalidJson(_)))
+        ),
+        true
+    );
+}
+
+#[cfg(feature = "yaml")]
+#[test]
+fn parse_yaml_universe_test() {
+    let yaml = "building:\n  view: true\n  room:\n    edit: true\n";
+
+    for path in ["universe.yaml", "universe.yml"] {
+        assert_eq!(
+            parse_universe(Path::new(path), yaml, ':'),
+            Ok(HashSet::from([
+                String::from("building:view"),
+                String::from("building:room:edit")
+            ]))
+        );
+    }
+    assert_eq!(
+        matches!(
+            parse_universe(Path::new("universe.yaml"), "building: [view", ':'),
+            Err(LoadError::Invalid(ValidationError::InvalidJson(_)))
+        ),
+        true
+    );
+    assert_eq!(
+        parse_universe(Path::new("universe.json"), yaml, ':').is_err(),
+        true
+    );
+}
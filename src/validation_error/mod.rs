@@ -0,0 +1,122 @@
+use std::error::Error;
+use std::fmt;
+
+//// TESTS ////
+#[cfg(test)]
+mod tests;
+
+/// Error returned by the non-panicking [`PermissionManager`](crate::PermissionManager) constructors and
+/// [`Permission`](crate::Permission) creation methods, such as [`try_perm_from_json`](crate::PermissionManager::try_perm_from_json()).
+///
+/// Offending actions are listed sorted, so they can be shown directly to the user that submitted them.
+///
+/// # Examples:
+///
+/// ```
+/// use simple_perm_manager::{PermissionManager, ValidationError};
+///
+/// let manager = PermissionManager::from_json(r#"{"create": true, "view": true}"#);
+///
+/// match manager.try_perm_from_json(r#"{"view": true, "delete": true, "veiw": true}"#) {
+///     Err(error @ ValidationError::UnknownActions(_)) => assert_eq!(
+///         error.to_string(),
+///         "actions not allowed in permission manager: delete, veiw (did you mean 'view'?)"
+///     ),
+///     _ => panic!("delete is not in the manager universe"),
+/// }
+/// ```
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum ValidationError {
+    /// The actions JSON string is not valid JSON or does not have the actions format.
+    InvalidJson(String),
+    /// The actions are empty, contain empty groups (e.g. `"group::view"`) or do not follow the
+    /// [`ActionNamePolicy`](crate::ActionNamePolicy) in use.
+    MalformedActions(Vec<String>),
+    /// The actions are not in the [`PermissionManager`](crate::PermissionManager) universe, each one with the closest universe actions
+    /// as did-you-mean suggestions (closest first, empty if none is close enough).
+    UnknownActions(Vec<(String, Vec<String>)>),
+    /// The actions are defined by more than one universe part, or as an action by one part and as a group by another, each one
+    /// with the names of the parts defining it, see [`PermissionManager::from_parts`](crate::PermissionManager::from_parts()).
+    ConflictingActions(Vec<(String, Vec<String>)>),
+    /// The action at the [JSON pointer](https://www.rfc-editor.org/rfc/rfc6901) is not in the [`PermissionManager`](crate::PermissionManager)
+    /// universe, see [`perm_from_json_strict`](crate::PermissionManager::perm_from_json_strict()).
+    UnknownActionAt(String),
+    /// The actions are not held by the delegating [`Permission`](crate::Permission), see [`Permission::delegate`](crate::Permission::delegate()).
+    NotDelegable(Vec<String>),
+    /// The actions have no translation, see [`PermissionTranslator::translate`](crate::PermissionTranslator::translate())
+    /// and [`Permission::to_casbin_policy`](crate::Permission::to_casbin_policy()).
+    UntranslatableActions(Vec<String>),
+    /// The Datalog code is not valid, see [`PermissionManager::perm_from_biscuit_facts`](crate::PermissionManager::perm_from_biscuit_facts()).
+    InvalidDatalog(String),
+    /// The condition expression is not valid, see [`Expression`](crate::Expression).
+    InvalidExpression(String),
+    /// The event with the sequence number can not be applied, see [`EventLog::replay`](crate::EventLog::replay()).
+    InvalidEvent(u64, String),
+    /// The signature does not match the signed [`Permission`](crate::Permission) for the key, see
+    /// [`PermissionManager::verify_perm`](crate::PermissionManager::verify_perm()), or the encrypted [`Permission`](crate::Permission)
+    /// can not be decrypted with the key.
+    InvalidSignature,
+    /// The [`Permission`](crate::Permission)s are managed by different [`PermissionManager`](crate::PermissionManager)s, see
+    /// [`Permission::delegate`](crate::Permission::delegate()).
+    ManagerMismatch,
+}
+
+impl fmt::Display for ValidationError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ValidationError::InvalidJson(message) => write!(f, "{}", message),
+            ValidationError::MalformedActions(actions) => {
+                write!(f, "malformed actions: {}", actions.join(", "))
+            }
+            ValidationError::UnknownActions(actions) => {
+                let actions: Vec<String> = actions
+                    .iter()
+                    .map(|(action, suggestions)| match suggestions.as_slice() {
+                        [] => action.clone(),
+                        _ => format!("{} (did you mean '{}'?)", action, suggestions.join("', '")),
+                    })
+                    .collect();
+                write!(
+                    f,
+                    "actions not allowed in permission manager: {}",
+                    actions.join(", ")
+                )
+            }
+            ValidationError::ConflictingActions(actions) => {
+                let actions: Vec<String> = actions
+                    .iter()
+                    .map(|(action, parts)| format!("{} ({})", action, parts.join(", ")))
+                    .collect();
+                write!(f, "conflicting actions: {}", actions.join(", "))
+            }
+            ValidationError::UnknownActionAt(pointer) => {
+                write!(f, "action not allowed in permission manager at {}", pointer)
+            }
+            ValidationError::NotDelegable(actions) => {
+                write!(
+                    f,
+                    "actions not held by the delegating permission: {}",
+                    actions.join(", ")
+                )
+            }
+            ValidationError::UntranslatableActions(actions) => {
+                write!(f, "actions without translation: {}", actions.join(", "))
+            }
+            ValidationError::InvalidDatalog(message) => {
+                write!(f, "invalid datalog: {}", message)
+            }
+            ValidationError::InvalidExpression(message) => {
+                write!(f, "invalid expression: {}", message)
+            }
+            ValidationError::InvalidEvent(sequence, message) => {
+                write!(f, "invalid event {}: {}", sequence, message)
+            }
+            ValidationError::InvalidSignature => write!(f, "invalid permission signature"),
+            ValidationError::ManagerMismatch => {
+                write!(f, "permissions do not have the same permission manager")
+            }
+        }
+    }
+}
+
+impl Error for ValidationError {}
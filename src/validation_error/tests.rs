@@ -0,0 +1,89 @@
+use super::*;
+
+#[test]
+fn display_test() {
+    assert_eq!(
+        ValidationError::InvalidJson(String::from("wrong format in permission json string"))
+            .to_string(),
+        "wrong format in permission json string"
+    );
+    assert_eq!(
+        ValidationError::MalformedActions(vec![String::from(""), String::from("group::view")])
+            .to_string(),
+        "malformed actions: , group::view"
+    );
+    assert_eq!(
+        ValidationError::UnknownActions(vec![
+            (String::from("delete"), vec![]),
+            (String::from("edti"), vec![String::from("edit")])
+        ])
+        .to_string(),
+        "actions not allowed in permission manager: delete, edti (did you mean 'edit'?)"
+    );
+    assert_eq!(
+        ValidationError::UnknownActions(vec![(
+            String::from("veiw"),
+            vec![String::from("view"), String::from("vie")]
+        )])
+        .to_string(),
+        "actions not allowed in permission manager: veiw (did you mean 'view', 'vie'?)"
+    );
+    assert_eq!(
+        ValidationError::UnknownActionAt(String::from("/building/edti")).to_string(),
+        "action not allowed in permission manager at /building/edti"
+    );
+    assert_eq!(
+        ValidationError::NotDelegable(vec![String::from("delete")]).to_string(),
+        "actions not held by the delegating permission: delete"
+    );
+    assert_eq!(
+        ValidationError::InvalidDatalog(String::from("unterminated string")).to_string(),
+        "invalid datalog: unterminated string"
+    );
+    assert_eq!(
+        ValidationError::InvalidExpression(String::from("unexpected end of expression"))
+            .to_string(),
+        "invalid expression: unexpected end of expression"
+    );
+    assert_eq!(
+        ValidationError::ConflictingActions(vec![
+            (
+                String::from("building:room"),
+                vec![String::from("buildings"), String::from("rooms")]
+            ),
+            (
+                String::from("user:view"),
+                vec![String::from("users"), String::from("legacy")]
+            ),
+        ])
+        .to_string(),
+        "conflicting actions: building:room (buildings, rooms), user:view (users, legacy)"
+    );
+    assert_eq!(
+        ValidationError::UntranslatableActions(vec![String::from("docs:purge")]).to_string(),
+        "actions without translation: docs:purge"
+    );
+    assert_eq!(
+        ValidationError::InvalidEvent(3, String::from("no role exists with name 'admin'"))
+            .to_string(),
+        "invalid event 3: no role exists with name 'admin'"
+    );
+    assert_eq!(
+        ValidationError::InvalidSignature.to_string(),
+        "invalid permission signature"
+    );
+    assert_eq!(
+        ValidationError::ManagerMismatch.to_string(),
+        "permissions do not have the same permission manager"
+    );
+}
+
+#[test]
+fn error_test() {
+    let error: Box<dyn Error> = Box::new(ValidationError::UnknownActions(vec![(
+        String::from("edit"),
+        vec![],
+    )]));
+
+    assert_eq!(error.source().is_none(), true);
+}
@@ -0,0 +1,164 @@
+use crate::permission::Permission;
+use std::fmt;
+
+//// TESTS ////
+#[cfg(test)]
+mod tests;
+
+// Maximum number of close matches suggested for each unknown action
+const MAX_SUGGESTIONS: usize = 3;
+// Maximum edit distance between an unknown action and a suggested universe action
+const MAX_SUGGESTION_DISTANCE: usize = 2;
+
+/// Detailed result of validating a [`Permission`](crate::Permission) against a [`PermissionManager`](crate::PermissionManager),
+/// returned by [`validate_perm_report`](crate::PermissionManager::validate_perm_report()).
+///
+/// Lists the actions outside the [`PermissionManager`](crate::PermissionManager) universe with suggested close matches
+/// and whether the [`Permission`](crate::Permission) has a different manager, so APIs can return actionable error messages.  
+/// The `Display` implementation writes a human readable summary of the problems found.
+///
+/// # Examples:
+///
+/// ```
+/// use simple_perm_manager::{Permission, PermissionManager};
+///
+/// let manager = PermissionManager::from_json(r#"{"create": true, "view": true}"#);
+/// let report = manager.validate_perm_report(&Permission::from_json(r#"{"veiw": true}"#));
+///
+/// assert!(!report.is_valid());
+/// assert!(report.manager_mismatch());
+/// assert_eq!(report.unknown_actions(), [String::from("veiw")]);
+/// assert_eq!(report.suggestions("veiw"), [String::from("view")]);
+/// assert_eq!(
+///     report.to_string(),
+///     "permission is managed by a different manager; unknown action 'veiw' (did you mean 'view'?)"
+/// );
+/// ```
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct ValidationReport {
+    manager_mismatch: bool,
+    unknown_actions: Vec<(String, Vec<String>)>,
+}
+
+impl ValidationReport {
+    // Builds the report for the actions and manager check provided, suggesting close matches from the universe.
+    pub(crate) fn new(
+        manager_mismatch: bool,
+        perm: &Permission,
+        universe: &Permission,
+    ) -> ValidationReport {
+        let mut unknown_actions: Vec<(String, Vec<String>)> = perm
+            .actions()
+            .filter(|action| !universe.has_action(action))
+            .map(|action| {
+                (
+                    action.to_string(),
+                    close_matches(action, universe.actions()),
+                )
+            })
+            .collect();
+        unknown_actions.sort();
+
+        ValidationReport {
+            manager_mismatch,
+            unknown_actions,
+        }
+    }
+
+    /// Returns `true` if the [`Permission`](crate::Permission) is valid for the [`PermissionManager`](crate::PermissionManager).
+    pub fn is_valid(&self) -> bool {
+        !self.manager_mismatch && self.unknown_actions.is_empty()
+    }
+
+    /// Returns `true` if the [`Permission`](crate::Permission) is not managed by the [`PermissionManager`](crate::PermissionManager).
+    pub fn manager_mismatch(&self) -> bool {
+        self.manager_mismatch
+    }
+
+    /// Returns the actions of the [`Permission`](crate::Permission) that are not in the [`PermissionManager`](crate::PermissionManager) universe, sorted.
+    pub fn unknown_actions(&self) -> Vec<String> {
+        self.unknown_actions
+            .iter()
+            .map(|(action, _)| action.clone())
+            .collect()
+    }
+
+    /// Returns the universe actions closest to the unknown action provided, closest first.  
+    /// Returns an empty slice if the action is not unknown or there are no close matches.
+    pub fn suggestions(&self, action: &str) -> &[String] {
+        match self
+            .unknown_actions
+            .iter()
+            .find(|(unknown, _)| unknown == action)
+        {
+            Some((_, suggestions)) => suggestions,
+            None => &[],
+        }
+    }
+}
+
+impl fmt::Display for ValidationReport {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if self.is_valid() {
+            return write!(f, "permission is valid");
+        }
+
+        let mut problems: Vec<String> = Vec::new();
+        if self.manager_mismatch {
+            problems.push(String::from("permission is managed by a different manager"));
+        }
+        for (action, suggestions) in &self.unknown_actions {
+            problems.push(match suggestions.as_slice() {
+                [] => format!("unknown action '{}'", action),
+                _ => format!(
+                    "unknown action '{}' (did you mean '{}'?)",
+                    action,
+                    suggestions.join("', '")
+                ),
+            });
+        }
+
+        write!(f, "{}", problems.join("; "))
+    }
+}
+
+// Returns the universe actions within a small edit distance of the action, closest first.
+pub(crate) fn close_matches<I, S>(action: &str, universe: I) -> Vec<String>
+where
+    I: IntoIterator<Item = S>,
+    S: AsRef<str>,
+{
+    let mut matches: Vec<(usize, String)> = universe
+        .into_iter()
+        .filter_map(|candidate| {
+            let distance = edit_distance(action, candidate.as_ref());
+            (distance <= MAX_SUGGESTION_DISTANCE)
+                .then(|| (distance, String::from(candidate.as_ref())))
+        })
+        .collect();
+    matches.sort();
+
+    matches
+        .into_iter()
+        .take(MAX_SUGGESTIONS)
+        .map(|(_, candidate)| candidate)
+        .collect()
+}
+
+#[doc(hidden)]
+/// Returns the Levenshtein distance between two strings, counted in chars.
+pub fn edit_distance(a: &str, b: &str) -> usize {
+    let b: Vec<char> = b.chars().collect();
+    let mut previous: Vec<usize> = (0..=b.len()).collect();
+
+    for (i, a_char) in a.chars().enumerate() {
+        let mut current = vec![i + 1; b.len() + 1];
+        for (j, b_char) in b.iter().enumerate() {
+            let substitution = previous[j] + usize::from(a_char != *b_char);
+            current[j + 1] = substitution.min(previous[j + 1] + 1).min(current[j] + 1);
+        }
+        previous = current;
+    }
+
+    previous[b.len()]
+}
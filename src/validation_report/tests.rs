@@ -0,0 +1,69 @@
+use super::*;
+use std::collections::HashSet;
+
+fn universe() -> Permission {
+    Permission::from_actions(HashSet::from([
+        String::from("building:view"),
+        String::from("building:edit"),
+        String::from("building:delete"),
+        String::from("floor:view"),
+    ]))
+}
+
+#[test]
+fn new_test() {
+    let report = ValidationReport::new(
+        false,
+        &Permission::from_actions(HashSet::from([String::from("building:view")])),
+        &universe(),
+    );
+
+    assert_eq!(report.is_valid(), true);
+    assert_eq!(report.manager_mismatch(), false);
+    assert_eq!(report.unknown_actions().is_empty(), true);
+    assert_eq!(report.to_string(), "permission is valid");
+
+    let report = ValidationReport::new(true, &Permission::from_json("{}"), &universe());
+    assert_eq!(report.is_valid(), false);
+    assert_eq!(report.manager_mismatch(), true);
+}
+
+#[test]
+fn unknown_actions_test() {
+    let report = ValidationReport::new(
+        false,
+        &Permission::from_actions(HashSet::from([
+            String::from("building:veiw"),
+            String::from("building:view"),
+            String::from("zzz"),
+        ])),
+        &universe(),
+    );
+
+    assert_eq!(report.is_valid(), false);
+    assert_eq!(
+        report.unknown_actions(),
+        vec![String::from("building:veiw"), String::from("zzz")]
+    );
+    assert_eq!(
+        report.suggestions("building:veiw"),
+        [String::from("building:view")]
+    );
+    assert_eq!(report.suggestions("zzz").is_empty(), true);
+    assert_eq!(report.suggestions("building:view").is_empty(), true);
+    assert_eq!(
+        report.to_string(),
+        "unknown action 'building:veiw' (did you mean 'building:view'?); unknown action 'zzz'"
+    );
+}
+
+#[test]
+fn edit_distance_test() {
+    assert_eq!(edit_distance("", ""), 0);
+    assert_eq!(edit_distance("view", "view"), 0);
+    assert_eq!(edit_distance("view", "veiw"), 2);
+    assert_eq!(edit_distance("view", "views"), 1);
+    assert_eq!(edit_distance("", "edit"), 4);
+    assert_eq!(edit_distance("kitten", "sitting"), 3);
+    assert_eq!(edit_distance("añadir", "anadir"), 1);
+}
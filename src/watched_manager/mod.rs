@@ -0,0 +1,149 @@
+use crate::load_error::LoadError;
+use crate::permission_manager::PermissionManager;
+use crate::universe_file::{parse_universe, read_file};
+use std::path::{Path, PathBuf};
+use std::sync::mpsc::{self, RecvTimeoutError};
+use std::sync::{Arc, Mutex, RwLock, RwLockReadGuard, RwLockWriteGuard};
+use std::thread;
+use std::time::Duration;
+
+//// TESTS ////
+#[cfg(test)]
+mod tests;
+
+/// [`PermissionManager`](crate::PermissionManager) whose universe is loaded from a file and reloaded when the file changes,
+/// created with [`PermissionManager::from_file_watched`](crate::PermissionManager::from_file_watched()).
+///
+/// A background thread polls the file and stops when the [`WatchedManager`](crate::WatchedManager) is dropped. Reloads replace the
+/// universe of the same [`PermissionManager`](crate::PermissionManager), so [`Permission`](crate::Permission)s issued before a reload
+/// stay managed by it and can be revalidated with [`validate_perm`](crate::PermissionManager::validate_perm()) or stripped of removed
+/// actions with [`migrate_perm`](crate::PermissionManager::migrate_perm()). Hooks registered with
+/// [`on_universe_change`](crate::PermissionManager::on_universe_change()) are called on every reload that changes the universe.  
+/// If the file can not be read or is not a valid universe, the previous universe is kept and the error is available in
+/// [`last_error`](Self::last_error()).
+///
+/// # Examples:
+///
+/// ```
+/// use std::fs;
+/// use simple_perm_manager::PermissionManager;
+///
+/// let path = std::env::temp_dir().join("watched_manager_example.json");
+/// fs::write(&path, r#"{"building": {"view": true}}"#).unwrap();
+///
+/// let watched = PermissionManager::from_file_watched(&path).unwrap();
+/// let perm = watched.manager().perm_from_json(r#"{"building": {"view": true}}"#);
+///
+/// fs::write(&path, r#"{"building": {"view": true, "edit": true}}"#).unwrap();
+/// assert_eq!(watched.reload(), Ok(true));
+///
+/// let manager = watched.manager();
+/// assert!(manager.get_universe().contains_action("building:edit"));
+/// assert!(manager.validate_perm(&perm));
+/// # drop(manager);
+/// # fs::remove_file(&path).unwrap();
+/// ```
+pub struct WatchedManager {
+    path: PathBuf,
+    state: Arc<WatchState>,
+    // Disconnects the polling thread when the WatchedManager is dropped
+    _stop: mpsc::Sender<()>,
+}
+
+struct WatchState {
+    manager: RwLock<PermissionManager>,
+    // Content of the file the current universe was loaded from
+    content: Mutex<String>,
+    last_error: Mutex<Option<LoadError>>,
+}
+
+impl WatchState {
+    // Loads the universe of the file if its content changed, keeping the error if it fails.
+    fn reload(&self, path: &Path) -> Result<bool, LoadError> {
+        let result = self.try_reload(path);
+        *self.last_error.lock().unwrap() = result.as_ref().err().cloned();
+
+        result
+    }
+
+    fn try_reload(&self, path: &Path) -> Result<bool, LoadError> {
+        let content = read_file(path)?;
+        let mut loaded = self.content.lock().unwrap();
+        if content == *loaded {
+            return Ok(false);
+        }
+
+        let separator = self.manager.read().unwrap().separator();
+        let universe_actions = parse_universe(path, &content, separator)?;
+        let changed = self
+            .manager
+            .write()
+            .unwrap()
+            .try_set_universe(universe_actions)?;
+        *loaded = content;
+
+        Ok(changed)
+    }
+}
+
+impl WatchedManager {
+    // Loads the universe of the file and starts polling it every `interval`.
+    pub(crate) fn watch(path: &Path, interval: Duration) -> Result<WatchedManager, LoadError> {
+        let content = read_file(path)?;
+        let universe_actions = parse_universe(path, &content, crate::ACTION_DIVIDER)?;
+        let manager = PermissionManager::try_from_actions(universe_actions)?;
+
+        let state = Arc::new(WatchState {
+            manager: RwLock::new(manager),
+            content: Mutex::new(content),
+            last_error: Mutex::new(None),
+        });
+        let (stop, stopped) = mpsc::channel::<()>();
+        let (thread_path, thread_state) = (path.to_path_buf(), state.clone());
+        thread::spawn(move || {
+            while let Err(RecvTimeoutError::Timeout) = stopped.recv_timeout(interval) {
+                let _ = thread_state.reload(&thread_path);
+            }
+        });
+
+        Ok(WatchedManager {
+            path: path.to_path_buf(),
+            state,
+            _stop: stop,
+        })
+    }
+
+    /// Returns the path of the file watched.
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+
+    /// Locks the [`PermissionManager`](crate::PermissionManager) for reading, blocking reloads until the guard is dropped.
+    pub fn manager(&self) -> RwLockReadGuard<'_, PermissionManager> {
+        self.state.manager.read().unwrap()
+    }
+
+    /// Locks the [`PermissionManager`](crate::PermissionManager) for writing, e.g. to register
+    /// [`on_universe_change`](crate::PermissionManager::on_universe_change()) hooks or aliases.  
+    /// Universe changes made through the guard are overwritten by the next reload that finds the file changed.
+    pub fn manager_mut(&self) -> RwLockWriteGuard<'_, PermissionManager> {
+        self.state.manager.write().unwrap()
+    }
+
+    /// Reloads the universe from the file without waiting for the next poll.  
+    /// Returns whether the universe actions changed.
+    ///
+    /// # Errors:
+    ///
+    /// - Returns [`LoadError::Io`](crate::LoadError::Io) if the file can not be read.
+    /// - Returns [`LoadError::Invalid`](crate::LoadError::Invalid) if the file is not a valid universe for the
+    ///   [`PermissionManager`](crate::PermissionManager), which keeps its previous universe.
+    pub fn reload(&self) -> Result<bool, LoadError> {
+        self.state.reload(&self.path)
+    }
+
+    /// Returns the error of the last reload, if it failed.
+    pub fn last_error(&self) -> Option<LoadError> {
+        self.state.last_error.lock().unwrap().clone()
+    }
+}
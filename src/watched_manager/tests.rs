@@ -0,0 +1,137 @@
+use super::*;
+use crate::validation_error::ValidationError;
+use std::fs;
+use std::time::Instant;
+
+// Returns a path in the temporary directory not used by other tests.
+fn temp_path(extension: &str) -> PathBuf {
+    std::env::temp_dir().join(format!("{}.{}", uuid::Uuid::new_v4(), extension))
+}
+
+#[test]
+fn watch_test() {
+    let path = temp_path("json");
+    fs::write(&path, r#"{"building": {"view": true, "edit": true}}"#).unwrap();
+
+    let watched = WatchedManager::watch(&path, Duration::from_millis(10)).unwrap();
+    let perm = watched
+        .manager()
+        .perm_from_json(r#"{"building": {"view": true, "edit": true}}"#);
+    assert_eq!(watched.path(), path.as_path());
+
+    fs::write(&path, r#"{"building": {"view": true}}"#).unwrap();
+    let start = Instant::now();
+    while watched.manager().get_universe().get_actions().len() != 1 {
+        assert_eq!(start.elapsed() < Duration::from_secs(10), true);
+        thread::sleep(Duration::from_millis(10));
+    }
+
+    let manager = watched.manager();
+    assert_eq!(manager.validate_perm(&perm), false);
+    assert_eq!(
+        manager.migrate_perm(&perm).to_json(),
+        r#"{"building":{"view":true}}"#
+    );
+    drop(manager);
+    fs::remove_file(&path).unwrap();
+}
+
+#[test]
+fn reload_test() {
+    let path = temp_path("json");
+    fs::write(&path, r#"{"view": true}"#).unwrap();
+
+    let watched = WatchedManager::watch(&path, Duration::from_secs(3600)).unwrap();
+    let changes = Arc::new(Mutex::new(0));
+    let hook_changes = changes.clone();
+    watched
+        .manager_mut()
+        .on_universe_change(move |_, _| *hook_changes.lock().unwrap() += 1);
+
+    assert_eq!(watched.reload(), Ok(false));
+    fs::write(&path, r#"{"view": true, "edit": true}"#).unwrap();
+    assert_eq!(watched.reload(), Ok(true));
+    assert_eq!(*changes.lock().unwrap(), 1);
+
+    fs::write(&path, r#"{"view": "yes"}"#).unwrap();
+    assert_eq!(
+        matches!(
+            watched.reload(),
+            Err(LoadError::Invalid(ValidationError::InvalidJson(_)))
+        ),
+        true
+    );
+    assert_eq!(watched.last_error().is_some(), true);
+    assert_eq!(watched.manager().get_universe().get_actions().len(), 2);
+
+    fs::write(&path, r#"{"edit": true, "view": true}"#).unwrap();
+    assert_eq!(watched.reload(), Ok(false));
+    assert_eq!(watched.last_error(), None);
+
+    fs::remove_file(&path).unwrap();
+    assert_eq!(matches!(watched.reload(), Err(LoadError::Io(_))), true);
+    assert_eq!(*changes.lock().unwrap(), 1);
+}
+
+#[test]
+fn reload_policy_test() {
+    let path = temp_path("json");
+    fs::write(&path, r#"{"user": {"view": true}}"#).unwrap();
+
+    let watched = WatchedManager::watch(&path, Duration::from_secs(3600)).unwrap();
+    watched.manager_mut().set_case_insensitive(true);
+    fs::write(&path, r#"{"User": {"View": true, "Edit": true}}"#).unwrap();
+    assert_eq!(watched.reload(), Ok(true));
+    assert_eq!(
+        watched.manager().get_universe().to_json(),
+        r#"{"user":{"edit":true,"view":true}}"#
+    );
+
+    fs::write(&path, r#"{"user": {"": true}}"#).unwrap();
+    assert_eq!(
+        watched.reload(),
+        Err(LoadError::Invalid(ValidationError::MalformedActions(vec![
+            String::from("user:")
+        ])))
+    );
+    fs::remove_file(&path).unwrap();
+}
+
+#[test]
+fn watch_error_test() {
+    let path = temp_path("json");
+    assert_eq!(
+        matches!(
+            WatchedManager::watch(&path, Duration::from_secs(1)),
+            Err(LoadError::Io(_))
+        ),
+        true
+    );
+
+    fs::write(&path, "[]").unwrap();
+    assert_eq!(
+        matches!(
+            WatchedManager::watch(&path, Duration::from_secs(1)),
+            Err(LoadError::Invalid(_))
+        ),
+        true
+    );
+    fs::remove_file(&path).unwrap();
+}
+
+#[cfg(feature = "yaml")]
+#[test]
+fn watch_yaml_test() {
+    let path = temp_path("yaml");
+    fs::write(&path, "building:\n  view: true\n").unwrap();
+
+    let watched = WatchedManager::watch(&path, Duration::from_secs(3600)).unwrap();
+    assert_eq!(
+        watched
+            .manager()
+            .get_universe()
+            .contains_action("building:view"),
+        true
+    );
+    fs::remove_file(&path).unwrap();
+}